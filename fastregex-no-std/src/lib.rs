@@ -0,0 +1,50 @@
+//! Matchers of every kind declared in a `no_std` crate, so that building it checks that the
+//! generated code names nothing from `std`. Without the `alloc` feature, fastregex-rt does not link
+//! `alloc` either, so it also checks that matching does not allocate.
+
+#![no_std]
+
+#[cfg(test)]
+extern crate std;
+
+use fastregex::{matcher, matchers};
+
+matcher!(pub is_identifier, "[a-z_][a-z0-9_]*");
+matcher!(pub is_capitalized, r"\p{Lu}\p{Ll}*");
+matcher!(pub const is_version, "v[0-9]+[.][0-9]+");
+matchers! {
+    pub contains_digit => "[0-9]" { search: true },
+    pub method => "(GET)|(POST)" { branch: true },
+    pub is_tag => "<[a-z]+>" { bytes: true },
+    pub IsWord => "[a-z]+" { struct_type: true },
+    pub key_value => "(?<key>[a-z]+)=(?<value>[0-9]+)",
+}
+
+/// Counts the words in `input`, which finding them does without allocating.
+pub fn count_words(input: &str) -> usize {
+    IsWord.find_iter(input).count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_matchers() {
+        assert!(is_identifier("snake_case"));
+        assert!(is_capitalized("\u{C9}cole"));
+        assert!(is_version("v1.2"));
+        assert!(contains_digit("ab4"));
+        assert_eq!(method_branch("POST"), Some(1));
+        assert!(is_tag(b"<a>"));
+        assert_eq!(count_words("ab 12 cd"), 2);
+        assert_eq!(key_value_longest_prefix("a=1"), Some(3));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_allocating_functions() {
+        assert_eq!(IsWord.replace_all("ab 12 cd", "x"), "x 12 x");
+        assert_eq!(key_value_captures("a=1").unwrap().name("value"), Some("1"));
+    }
+}
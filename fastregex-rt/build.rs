@@ -0,0 +1,96 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use regexlib::general_category;
+use regexlib::grapheme::GraphemeBreak;
+use regexlib::script::Script;
+use unic_char_property::EnumeratedCharProperty;
+use unic_ucd_category::GeneralCategory;
+
+//writes one sorted table of inclusive codepoint ranges per general category, named by the category's abbreviation in upper case (e.g., LU)
+fn main() {
+    let mut ranges = GeneralCategory::all_values()
+        .iter()
+        .map(|category| (*category, Vec::<(char, char)>::new()))
+        .collect::<Vec<_>>();
+
+    for ch in (0..=char::MAX as u32).filter_map(char::from_u32) {
+        let category = general_category::of(ch);
+        let (_, category_ranges) = ranges
+            .iter_mut()
+            .find(|(existing, _)| *existing == category)
+            .unwrap();
+
+        match category_ranges.last_mut() {
+            Some((_, end)) if *end as u32 + 1 == ch as u32 => *end = ch,
+            _ => category_ranges.push((ch, ch)),
+        }
+    }
+
+    let mut tables = String::new();
+    for (category, category_ranges) in ranges {
+        writeln!(
+            tables,
+            "/// Codepoints in the {:?} ({}) general category.",
+            category,
+            category.abbr_name()
+        )
+        .unwrap();
+        writeln!(
+            tables,
+            "pub static {}: &[(char, char)] = &{:?};",
+            category.abbr_name().to_uppercase(),
+            category_ranges
+        )
+        .unwrap();
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("general_categories.rs"), tables).unwrap();
+    fs::write(Path::new(&out_dir).join("scripts.rs"), script_tables()).unwrap();
+    fs::write(
+        Path::new(&out_dir).join("grapheme_breaks.rs"),
+        grapheme_break_tables(),
+    )
+    .unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+//one table per script, named by the script's full name in upper case (e.g., OLD_ITALIC)
+fn script_tables() -> String {
+    let mut tables = String::new();
+    for script in Script::all() {
+        writeln!(tables, "/// Codepoints in the {} script.", script.name()).unwrap();
+        writeln!(
+            tables,
+            "pub static {}: &[(char, char)] = &{:?};",
+            script.name().to_uppercase(),
+            script.ranges()
+        )
+        .unwrap();
+    }
+    tables
+}
+
+//one table per grapheme break value, named by the value's name in upper case (e.g., REGIONAL_INDICATOR)
+fn grapheme_break_tables() -> String {
+    let mut tables = String::new();
+    for value in GraphemeBreak::ALL {
+        writeln!(
+            tables,
+            "/// Codepoints whose grapheme break value is {}.",
+            value.name()
+        )
+        .unwrap();
+        writeln!(
+            tables,
+            "pub static {}: &[(char, char)] = &{:?};",
+            value.name().to_uppercase(),
+            value.ranges()
+        )
+        .unwrap();
+    }
+    tables
+}
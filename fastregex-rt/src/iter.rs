@@ -0,0 +1,177 @@
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+/// An iterator over the byte offsets (as `(start, end)`) of each successive non-overlapping match
+/// in a haystack, for the `find_iter` of a matcher.
+pub struct FindIter<'haystack, Scratch> {
+    haystack: &'haystack str,
+    position: Option<usize>,
+    last_match_end: Option<usize>,
+    scratch: Scratch,
+    find_at: fn(&str, usize, &mut Scratch) -> Option<(usize, usize)>,
+}
+
+impl<'haystack, Scratch> FindIter<'haystack, Scratch> {
+    /// `find_at` returns the first match starting at or after the given offset, using the
+    /// matcher's scratch.
+    pub fn new(
+        haystack: &'haystack str,
+        scratch: Scratch,
+        find_at: fn(&str, usize, &mut Scratch) -> Option<(usize, usize)>,
+    ) -> Self {
+        Self {
+            haystack,
+            position: Some(0),
+            last_match_end: None,
+            scratch,
+            find_at,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Replaces the first `limit` matches (or all of them, if `limit` is `None`) with
+    /// `replacement`, which is inserted as is. The haystack is borrowed if nothing matched.
+    pub fn replace(mut self, replacement: &str, limit: Option<usize>) -> Cow<'haystack, str> {
+        let haystack = self.haystack;
+        let mut replaced = String::new();
+        let mut copied_up_to = 0;
+        let mut replacements = 0;
+
+        while limit.is_none_or(|limit| replacements < limit) {
+            let Some((start, end)) = self.next() else {
+                break;
+            };
+
+            replaced.push_str(&haystack[copied_up_to..start]);
+            replaced.push_str(replacement);
+            copied_up_to = end;
+            replacements += 1;
+        }
+
+        if replacements == 0 {
+            return Cow::Borrowed(haystack);
+        }
+
+        replaced.push_str(&haystack[copied_up_to..]);
+        Cow::Owned(replaced)
+    }
+
+    /// The substrings of the haystack between the matches, like `str::split` with the matches as
+    /// separators.
+    pub fn split(self) -> Split<'haystack, Scratch> {
+        Split {
+            finder: self,
+            last: 0,
+            done: false,
+        }
+    }
+
+    fn next_char_boundary(&self, position: usize) -> Option<usize> {
+        self.haystack[position..]
+            .chars()
+            .next()
+            .map(|char| position + char.len_utf8())
+    }
+}
+
+impl<Scratch> Iterator for FindIter<'_, Scratch> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(position) = self.position {
+            let Some((start, end)) = (self.find_at)(self.haystack, position, &mut self.scratch)
+            else {
+                self.position = None;
+                return None;
+            };
+
+            //an empty match right where the previous match ended would just repeat that match's end, so search again one character later
+            if start == end && Some(end) == self.last_match_end {
+                self.position = self.next_char_boundary(end);
+                continue;
+            }
+
+            self.position = if start == end {
+                self.next_char_boundary(end)
+            } else {
+                Some(end)
+            };
+            self.last_match_end = Some(end);
+
+            return Some((start, end));
+        }
+
+        None
+    }
+}
+
+/// An iterator over the substrings of a haystack between successive non-overlapping matches, for
+/// the `split` of a matcher.
+pub struct Split<'haystack, Scratch> {
+    finder: FindIter<'haystack, Scratch>,
+    last: usize,
+    done: bool,
+}
+
+impl<'haystack, Scratch> Iterator for Split<'haystack, Scratch> {
+    type Item = &'haystack str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let haystack = self.finder.haystack;
+        match self.finder.next() {
+            Some((start, end)) => {
+                let piece = &haystack[self.last..start];
+                self.last = end;
+                Some(piece)
+            }
+            None => {
+                self.done = true;
+                Some(&haystack[self.last..])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+    use std::vec;
+    use std::vec::Vec;
+
+    use super::FindIter;
+
+    //finds runs of 'a', including empty ones, like the pattern a*
+    fn find_as(haystack: &str, start: usize, _: &mut ()) -> Option<(usize, usize)> {
+        let start = start + haystack[start..].find('a').unwrap_or(0);
+        let length = haystack[start..]
+            .find(|char| char != 'a')
+            .unwrap_or(haystack.len() - start);
+        Some((start, start + length))
+    }
+
+    #[test]
+    fn test_replace() {
+        assert_eq!(FindIter::new("aabé", (), find_as).replace("-", None), "-b-é-");
+        assert_eq!(FindIter::new("aabé", (), find_as).replace("-", Some(1)), "-bé");
+        assert!(matches!(
+            FindIter::new("aabé", (), find_as).replace("-", Some(0)),
+            Cow::Borrowed("aabé")
+        ));
+    }
+
+    #[test]
+    fn test_split() {
+        let pieces = FindIter::new("xaay", (), find_as).split().collect::<Vec<_>>();
+        assert_eq!(pieces, vec!["x", "y", ""]);
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        let matches = FindIter::new("aabé", (), find_as).collect::<Vec<_>>();
+        assert_eq!(matches, vec![(0, 2), (3, 3), (5, 5)]);
+    }
+}
@@ -0,0 +1,260 @@
+//! Runtime support for the code generated by `fastregex`. Data and code that do not depend on the
+//! pattern, such as Unicode tables, UTF-8 decoding, the scratch space a match works in and the
+//! search for capture groups, live here so that every matcher shares one copy of them instead of
+//! embedding its own.
+//!
+//! It is `no_std`, as are the matchers generated against it. Replacing matches and finding capture
+//! groups allocate, so they need the `alloc` feature (on by default); without it, generated
+//! matchers leave those functions out. Finding where matches are does not allocate.
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(test)]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+pub use self::pike::Captures;
+pub use self::iter::{FindIter, Split};
+pub use self::scratch::{ScratchSpace, StateId};
+/// What the `replace` functions of a matcher return, re-exported so that generated code does not
+/// depend on `std`.
+#[cfg(feature = "alloc")]
+pub use alloc::borrow::Cow;
+
+mod iter;
+/// Running a matcher's automaton as constructed, in the priority order that decides where its
+/// groups matched and where a leftmost-first match ends.
+pub mod pike;
+mod scratch;
+
+/// Expands to the items it is given if the `alloc` feature is enabled, and to nothing otherwise.
+/// Generated matchers wrap the functions that allocate in it, since the macro that generates them
+/// can not see this crate's features.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! with_alloc {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! with_alloc {
+    ($($item:item)*) => {};
+}
+
+/// Sorted, non-overlapping codepoint ranges of each Unicode general category, named by the
+/// category's abbreviation in upper case (e.g., `LU` for `Uppercase_Letter`).
+pub mod general_category {
+    include!(concat!(env!("OUT_DIR"), "/general_categories.rs"));
+}
+
+/// Sorted, non-overlapping codepoint ranges of each Unicode script, named by the script's full
+/// name in upper case (e.g., `OLD_ITALIC`).
+pub mod script {
+    include!(concat!(env!("OUT_DIR"), "/scripts.rs"));
+}
+
+/// Sorted, non-overlapping codepoint ranges of each value `\X` tells characters apart by (see
+/// `regexlib::grapheme::GraphemeBreak`), named by the value's name in upper case (e.g.,
+/// `EXTENDED_PICTOGRAPHIC`).
+pub mod grapheme_break {
+    include!(concat!(env!("OUT_DIR"), "/grapheme_breaks.rs"));
+}
+
+/// Whether `ch` is in one of the sorted, non-overlapping inclusive `ranges`. It is a const fn (as
+/// are the decoders below) so that `const` matchers can call it.
+#[inline]
+pub const fn in_ranges(ranges: &[(char, char)], ch: char) -> bool {
+    let (mut low, mut high) = (0, ranges.len());
+    while low < high {
+        let middle = low + (high - low) / 2;
+        let (start, end) = ranges[middle];
+        if (end as u32) < (ch as u32) {
+            low = middle + 1;
+        } else if (start as u32) > (ch as u32) {
+            high = middle;
+        } else {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Whether `ch` is a word character, as `\w` matches and `\b{start}` and `\b{end}` look for: a
+/// letter, mark, decimal digit, letter number or connector punctuation.
+pub const fn is_word_character(ch: char) -> bool {
+    use general_category::*;
+    in_ranges(LU, ch)
+        || in_ranges(LL, ch)
+        || in_ranges(LT, ch)
+        || in_ranges(LM, ch)
+        || in_ranges(LO, ch)
+        || in_ranges(MN, ch)
+        || in_ranges(MC, ch)
+        || in_ranges(ME, ch)
+        || in_ranges(ND, ch)
+        || in_ranges(NL, ch)
+        || in_ranges(PC, ch)
+}
+
+/// Decodes the character whose multi-byte UTF-8 sequence starts at `bytes[*index]`, and moves
+/// `index` past it. Matchers check for ASCII before calling this, so
+/// the common case skips the general decoder; the input is a str, so it is always valid UTF-8.
+#[inline]
+pub const fn decode_multi_byte_char(bytes: &[u8], index: &mut usize) -> char {
+    let byte = bytes[*index];
+    let (width, mut code_point) = if byte < 0xE0 {
+        (2, (byte & 0x1F) as u32)
+    } else if byte < 0xF0 {
+        (3, (byte & 0x0F) as u32)
+    } else {
+        (4, (byte & 0x07) as u32)
+    };
+
+    let mut continuation = *index + 1;
+    while continuation < *index + width {
+        code_point = (code_point << 6) | (bytes[continuation] & 0x3F) as u32;
+        continuation += 1;
+    }
+    *index += width;
+
+    match char::from_u32(code_point) {
+        Some(char) => char,
+        None => char::REPLACEMENT_CHARACTER,
+    }
+}
+
+/// A matcher declared as a type, with `matcher!`'s `struct_type` option, so that code can be
+/// generic over which pattern it matches:
+///
+/// ```ignore
+/// matcher!(IsWord, "[a-z]+", struct_type);
+///
+/// fn count<M: fastregex_rt::MatcherType<Input = str>>(matcher: M, inputs: &[&str]) -> usize {
+///     inputs.iter().filter(|input| matcher.is_match(input)).count()
+/// }
+/// assert_eq!(count(IsWord, &["abc", "123"]), 1);
+/// ```
+pub trait MatcherType: Copy + Default {
+    /// `str`, or `[u8]` for byte matchers.
+    type Input: ?Sized;
+
+    /// The pattern the matcher was declared with.
+    const PATTERN: &'static str;
+
+    /// Whether the whole input matches.
+    fn is_match(&self, input: &Self::Input) -> bool;
+}
+
+/// A nom parser consuming the longest start of its input that matches a generated matcher, given
+/// the matcher's `<name>_longest_prefix` function, and returning it:
+///
+/// ```ignore
+/// matcher!(number, "[0-9]+");
+/// let (rest, digits) = fastregex_rt::nom_parser::<nom::error::Error<_>>(number_longest_prefix)("12ab")?;
+/// ```
+///
+/// If no start of the input matches, it fails with
+/// [`ErrorKind::RegexpMatch`](nom::error::ErrorKind::RegexpMatch).
+#[cfg(feature = "nom")]
+pub fn nom_parser<'input, E: nom::error::ParseError<&'input str>>(
+    longest_prefix: impl Fn(&'input str) -> Option<usize>,
+) -> impl FnMut(&'input str) -> nom::IResult<&'input str, &'input str, E> {
+    move |input| match longest_prefix(input) {
+        Some(length) => Ok((&input[length..], &input[..length])),
+        None => Err(nom::Err::Error(E::from_error_kind(
+            input,
+            nom::error::ErrorKind::RegexpMatch,
+        ))),
+    }
+}
+
+/// Like [`nom_parser`], but a winnow parser. It fails without consuming anything if no start of
+/// the input matches.
+#[cfg(feature = "winnow")]
+pub fn winnow_parser<'input, E: winnow::error::ParserError<&'input str>>(
+    longest_prefix: impl Fn(&'input str) -> Option<usize>,
+) -> impl FnMut(&mut &'input str) -> Result<&'input str, E> {
+    move |input| match longest_prefix(input) {
+        Some(length) => {
+            let (matched, rest) = input.split_at(length);
+            *input = rest;
+            Ok(matched)
+        }
+        None => Err(E::from_input(input)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec::Vec;
+
+    use regexlib::general_category;
+    use unic_ucd_category::GeneralCategory;
+
+    use crate::general_category::{LL, LU, ND};
+    use crate::{decode_multi_byte_char, in_ranges, ScratchSpace};
+
+    #[test]
+    fn test_tables_agree_with_regexlib() {
+        for ch in "Aa0 \u{e9}\u{3a3}\u{661}\u{1F600}\u{10D40}".chars() {
+            assert_eq!(
+                in_ranges(LU, ch),
+                general_category::of(ch) == GeneralCategory::UppercaseLetter
+            );
+            assert_eq!(
+                in_ranges(LL, ch),
+                general_category::of(ch) == GeneralCategory::LowercaseLetter
+            );
+            assert_eq!(
+                in_ranges(ND, ch),
+                general_category::of(ch) == GeneralCategory::DecimalNumber
+            );
+        }
+    }
+
+    #[test]
+    fn test_const_evaluable() {
+        const DECODED: char = decode_multi_byte_char("\u{e9}".as_bytes(), &mut 0);
+
+        const { assert!(in_ranges(LU, 'A')) };
+        assert_eq!(DECODED, '\u{e9}');
+    }
+
+    #[test]
+    fn test_decode_multi_byte_char() {
+        let string = "a\u{e9}\u{3a3}\u{20AC}\u{1F600}";
+        let bytes = string.as_bytes();
+        let mut index = 1;
+        let mut decoded = Vec::new();
+        while index < bytes.len() {
+            decoded.push(decode_multi_byte_char(bytes, &mut index));
+        }
+
+        assert_eq!(decoded, string.chars().skip(1).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_scratch_space_generations() {
+        let mut scratch = ScratchSpace::<u8, 3>::new();
+        scratch.begin_step();
+        scratch.did_add_state[1] = scratch.did_add_state_value;
+
+        //after the counter wraps around, no state counts as added in the new step
+        for _ in 0..u8::MAX {
+            scratch.begin_step();
+        }
+        assert_eq!(scratch.did_add_state_value, 1);
+        assert!(scratch
+            .did_add_state
+            .iter()
+            .all(|&added| added != scratch.did_add_state_value));
+    }
+}
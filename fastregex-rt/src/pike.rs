@@ -0,0 +1,613 @@
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+
+use crate::is_word_character;
+
+/// The capture positions of one path through a [`Program`]: for group `n`, slot `2n` holds the
+/// start and slot `2n + 1` the end, as byte offsets into the haystack.
+#[cfg(feature = "alloc")]
+pub type Slots = Vec<Option<usize>>;
+
+/// One transition out of a state of a [`Program`]. A state's transitions are in priority order,
+/// which is what makes alternations prefer earlier branches and repetitions greedy.
+pub enum Instruction {
+    /// Moves to the state without reading a character.
+    Epsilon(usize),
+    /// Records the current position in the slot, then moves to the state without reading a
+    /// character.
+    Save { slot: usize, target: usize },
+    /// Moves to the state without reading a character, if nothing has been read from the
+    /// haystack yet (i.e., `^`).
+    StartOfInput(usize),
+    /// Moves to the state without reading a character, if the whole haystack has been read
+    /// (i.e., `$`).
+    EndOfInput(usize),
+    /// Moves to the state without reading a character, if nothing has been read from the
+    /// haystack yet or the last character read was a line terminator (i.e., a multi-line `^`).
+    StartOfLine {
+        line_terminator: LineTerminator,
+        target: usize,
+    },
+    /// Moves to the state without reading a character, if the whole haystack has been read or the
+    /// next character is a line terminator (i.e., a multi-line `$`).
+    EndOfLine {
+        line_terminator: LineTerminator,
+        target: usize,
+    },
+    /// Moves to the state without reading a character, if the next character is a word character
+    /// and the last one read was not (i.e., `\b{start}`).
+    WordStart(usize),
+    /// Moves to the state without reading a character, if the last character read was a word
+    /// character and the next one is not (i.e., `\b{end}`).
+    WordEnd(usize),
+    /// Reads a character for which `condition` holds and moves to `target`.
+    Consume {
+        condition: fn(char) -> bool,
+        target: usize,
+    },
+}
+
+/// Which characters end a line for the multi-line anchors, as set by a matcher's
+/// `line_terminator` option.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LineTerminator {
+    /// Only `\n`.
+    Newline,
+    /// `\n` and `\r`, with a `\r\n` being one line terminator.
+    Crlf,
+    /// `\n`, `\v`, `\f`, `\r`, `\x85`, `\u{2028}` and `\u{2029}`, with a `\r\n` being one line
+    /// terminator.
+    Unicode,
+}
+
+impl LineTerminator {
+    fn ends_line(self, ch: char) -> bool {
+        match self {
+            LineTerminator::Newline => ch == '\n',
+            LineTerminator::Crlf => matches!(ch, '\n' | '\r'),
+            LineTerminator::Unicode => {
+                matches!(ch, '\n'..='\r' | '\u{85}' | '\u{2028}' | '\u{2029}')
+            }
+        }
+    }
+
+    //no line starts or ends between the `\r` and `\n` of a `\r\n` where `\r` ends lines too
+    fn is_line_start(self, haystack: &str, position: usize) -> bool {
+        match haystack[..position].chars().next_back() {
+            None => true,
+            Some('\r') if self != LineTerminator::Newline => {
+                !haystack[position..].starts_with('\n')
+            }
+            Some(ch) => self.ends_line(ch),
+        }
+    }
+
+    fn is_line_end(self, haystack: &str, position: usize) -> bool {
+        match haystack[position..].chars().next() {
+            None => true,
+            Some('\n') if self != LineTerminator::Newline => !haystack[..position].ends_with('\r'),
+            Some(ch) => self.ends_line(ch),
+        }
+    }
+}
+
+/// A matcher's automaton as it was constructed, with the transitions that mark where its groups
+/// start and end, which the simplified automaton the matcher runs does not have.
+pub struct Program {
+    /// The transitions out of each state.
+    pub states: &'static [&'static [Instruction]],
+    pub start_states: &'static [usize],
+    /// Whether each state is terminal.
+    pub terminal_states: &'static [bool],
+    /// The number of groups, not counting the implicit group 0.
+    pub capture_count: usize,
+}
+
+impl Program {
+    /// The end of the highest priority match that starts at `start`, or `None` if no match
+    /// starts there. Once `start` is known to be the leftmost position a match starts at, this is
+    /// where the leftmost-first match ends, the same one [`search`](Self::search) finds. It
+    /// tracks no groups, so it works in `threads` rather than allocating.
+    pub fn match_end<const STATES: usize, const STACK: usize>(
+        &self,
+        haystack: &str,
+        start: usize,
+        threads: &mut PriorityThreads<STATES, STACK>,
+    ) -> Option<usize> {
+        threads.begin_step();
+        for start_state in self.start_states {
+            threads.add(self, haystack, *start_state, start);
+        }
+        threads.end_step();
+
+        let mut found = None;
+        let mut position = start;
+        let mut chars = haystack[start..].chars();
+
+        loop {
+            //threads after the first match are lower priority than it, so they can never win
+            if let Some(index) = threads.current[..threads.current_len]
+                .iter()
+                .position(|state_id| self.terminal_states[*state_id])
+            {
+                found = Some(position);
+                threads.current_len = index;
+            }
+
+            if threads.current_len == 0 {
+                return found;
+            }
+
+            let Some(char) = chars.next() else {
+                return found;
+            };
+            position += char.len_utf8();
+
+            threads.begin_step();
+            for index in 0..threads.current_len {
+                let state_id = threads.current[index];
+                for instruction in self.states[state_id] {
+                    if let Instruction::Consume { condition, target } = instruction {
+                        if condition(char) {
+                            threads.add(self, haystack, *target, position);
+                        }
+                    }
+                }
+            }
+            threads.end_step();
+        }
+    }
+
+    /// Returns the capture slots of the highest priority path that matches the whole haystack.
+    #[cfg(feature = "alloc")]
+    pub fn full_match(&self, haystack: &str) -> Option<Slots> {
+        let mut threads = Threads::new(self, haystack);
+        threads.add_start(self, 0);
+
+        for (index, char) in haystack.char_indices() {
+            threads = threads.step(self, char, index + char.len_utf8());
+
+            if threads.list.is_empty() {
+                return None;
+            }
+        }
+
+        threads
+            .list
+            .into_iter()
+            .find(|(state_id, _)| self.terminal_states[*state_id])
+            .map(|(_, mut slots)| {
+                slots[1] = Some(haystack.len());
+                slots
+            })
+    }
+
+    /// Returns the capture slots of the leftmost match. Among the matches starting there, the
+    /// highest priority one is chosen (i.e., leftmost-first semantics).
+    #[cfg(feature = "alloc")]
+    pub fn search(&self, haystack: &str) -> Option<Slots> {
+        let mut threads = Threads::new(self, haystack);
+        let mut found = None;
+        let mut chars = haystack.char_indices();
+        let mut position = 0;
+
+        loop {
+            //a match found at an earlier start is always preferred, so stop starting new attempts
+            if found.is_none() {
+                threads.add_start(self, position);
+            }
+
+            //threads after the first match are lower priority than it, so they can never win
+            if let Some(index) = threads
+                .list
+                .iter()
+                .position(|(state_id, _)| self.terminal_states[*state_id])
+            {
+                let (_, mut slots) = threads.list.remove(index);
+                slots[1] = Some(position);
+                found = Some(slots);
+                threads.list.truncate(index);
+            }
+
+            if threads.list.is_empty() && found.is_some() {
+                break;
+            }
+
+            match chars.next() {
+                Some((index, char)) => {
+                    position = index + char.len_utf8();
+                    threads = threads.step(self, char, position);
+                }
+                None => break,
+            }
+        }
+
+        found
+    }
+}
+
+/// The states of the threads [`Program::match_end`] runs, without the capture slots that
+/// [`Program::search`] tracks, in arrays sized for a program of `STATES` states: `STACK` has to be
+/// at least one more than the number of transitions the program has, which bounds how many states
+/// following the transitions that read no character can have waiting at once.
+pub struct PriorityThreads<const STATES: usize, const STACK: usize> {
+    //the threads for the current position, in priority order
+    current: [usize; STATES],
+    current_len: usize,
+    //the threads for the next position, as they are added
+    next: [usize; STATES],
+    next_len: usize,
+    has_thread: [bool; STATES],
+    stack: [usize; STACK],
+}
+
+impl<const STATES: usize, const STACK: usize> PriorityThreads<STATES, STACK> {
+    pub const fn new() -> Self {
+        Self {
+            current: [0; STATES],
+            current_len: 0,
+            next: [0; STATES],
+            next_len: 0,
+            has_thread: [false; STATES],
+            stack: [0; STACK],
+        }
+    }
+
+    fn begin_step(&mut self) {
+        self.next_len = 0;
+        self.has_thread = [false; STATES];
+    }
+
+    fn end_step(&mut self) {
+        core::mem::swap(&mut self.current, &mut self.next);
+        self.current_len = self.next_len;
+    }
+
+    //like `Threads::add`, minus the slots
+    fn add(&mut self, program: &Program, haystack: &str, state_id: usize, position: usize) {
+        self.stack[0] = state_id;
+        let mut stack_len = 1;
+
+        while stack_len > 0 {
+            stack_len -= 1;
+            let state_id = self.stack[stack_len];
+            if self.has_thread[state_id] {
+                continue;
+            }
+            self.has_thread[state_id] = true;
+
+            for instruction in program.states[state_id].iter().rev() {
+                let target = match instruction {
+                    Instruction::Epsilon(target) | Instruction::Save { target, .. } => target,
+                    Instruction::StartOfInput(target) if position == 0 => target,
+                    Instruction::EndOfInput(target) if position == haystack.len() => target,
+                    Instruction::StartOfLine {
+                        line_terminator,
+                        target,
+                    } if line_terminator.is_line_start(haystack, position) => target,
+                    Instruction::EndOfLine {
+                        line_terminator,
+                        target,
+                    } if line_terminator.is_line_end(haystack, position) => target,
+                    Instruction::WordStart(target) if is_word_start(haystack, position) => target,
+                    Instruction::WordEnd(target) if is_word_end(haystack, position) => target,
+                    _ => continue,
+                };
+                self.stack[stack_len] = *target;
+                stack_len += 1;
+            }
+
+            self.next[self.next_len] = state_id;
+            self.next_len += 1;
+        }
+    }
+}
+
+impl<const STATES: usize, const STACK: usize> Default for PriorityThreads<STATES, STACK> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_word_start(haystack: &str, position: usize) -> bool {
+    !is_word_before(haystack, position) && is_word_after(haystack, position)
+}
+
+fn is_word_end(haystack: &str, position: usize) -> bool {
+    is_word_before(haystack, position) && !is_word_after(haystack, position)
+}
+
+fn is_word_before(haystack: &str, position: usize) -> bool {
+    haystack[..position]
+        .chars()
+        .next_back()
+        .is_some_and(is_word_character)
+}
+
+fn is_word_after(haystack: &str, position: usize) -> bool {
+    haystack[position..]
+        .chars()
+        .next()
+        .is_some_and(is_word_character)
+}
+
+/// The active threads for one position in the haystack, in priority order.
+#[cfg(feature = "alloc")]
+struct Threads<'haystack> {
+    list: Vec<(usize, Slots)>,
+    has_thread: Vec<bool>,
+    haystack: &'haystack str,
+}
+
+#[cfg(feature = "alloc")]
+impl<'haystack> Threads<'haystack> {
+    fn new(program: &Program, haystack: &'haystack str) -> Self {
+        Self {
+            list: Vec::new(),
+            has_thread: vec![false; program.states.len()],
+            haystack,
+        }
+    }
+
+    fn add_start(&mut self, program: &Program, position: usize) {
+        let mut slots = vec![None; 2 * (program.capture_count + 1)];
+        slots[0] = Some(position);
+
+        for start_state in program.start_states {
+            self.add(program, *start_state, slots.clone(), position);
+        }
+    }
+
+    /// Consumes `char` in every thread, producing the threads for the following position.
+    fn step(self, program: &Program, char: char, next_position: usize) -> Threads<'haystack> {
+        let mut next = Threads::new(program, self.haystack);
+
+        for (state_id, slots) in self.list {
+            for instruction in program.states[state_id] {
+                if let Instruction::Consume { condition, target } = instruction {
+                    if condition(char) {
+                        next.add(program, *target, slots.clone(), next_position);
+                    }
+                }
+            }
+        }
+
+        next
+    }
+
+    /// Adds the thread for `state_id` and every state reachable from it without reading a
+    /// character, in priority order. A state that already has a thread keeps it, since that
+    /// thread got there by a higher priority path.
+    fn add(&mut self, program: &Program, state_id: usize, slots: Slots, position: usize) {
+        //an explicit stack rather than recursion, since long chains of epsilon transitions are common
+        let mut stack = vec![(state_id, slots)];
+
+        while let Some((state_id, slots)) = stack.pop() {
+            if self.has_thread[state_id] {
+                continue;
+            }
+            self.has_thread[state_id] = true;
+
+            //pushed in reverse so that the first transition is explored first
+            for instruction in program.states[state_id].iter().rev() {
+                match instruction {
+                    Instruction::Epsilon(target) => stack.push((*target, slots.clone())),
+                    Instruction::Save { slot, target } => {
+                        let mut slots = slots.clone();
+                        slots[*slot] = Some(position);
+                        stack.push((*target, slots));
+                    }
+                    Instruction::StartOfInput(target) if position == 0 => {
+                        stack.push((*target, slots.clone()))
+                    }
+                    Instruction::EndOfInput(target) if position == self.haystack.len() => {
+                        stack.push((*target, slots.clone()))
+                    }
+                    Instruction::StartOfLine {
+                        line_terminator,
+                        target,
+                    } if line_terminator.is_line_start(self.haystack, position) => {
+                        stack.push((*target, slots.clone()))
+                    }
+                    Instruction::EndOfLine {
+                        line_terminator,
+                        target,
+                    } if line_terminator.is_line_end(self.haystack, position) => {
+                        stack.push((*target, slots.clone()))
+                    }
+                    Instruction::WordStart(target) if is_word_start(self.haystack, position) => {
+                        stack.push((*target, slots.clone()))
+                    }
+                    Instruction::WordEnd(target) if is_word_end(self.haystack, position) => {
+                        stack.push((*target, slots.clone()))
+                    }
+                    Instruction::StartOfInput(_)
+                    | Instruction::EndOfInput(_)
+                    | Instruction::StartOfLine { .. }
+                    | Instruction::EndOfLine { .. }
+                    | Instruction::WordStart(_)
+                    | Instruction::WordEnd(_)
+                    | Instruction::Consume { .. } => {}
+                }
+            }
+
+            self.list.push((state_id, slots));
+        }
+    }
+}
+
+/// The parts of the haystack matched by each group of a pattern. Group 0 is the whole match, and
+/// the remaining groups are numbered by the position of their opening parenthesis.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Captures<'haystack> {
+    haystack: &'haystack str,
+    slots: Slots,
+}
+
+#[cfg(feature = "alloc")]
+impl<'haystack> Captures<'haystack> {
+    pub fn new(haystack: &'haystack str, slots: Slots) -> Self {
+        Self { haystack, slots }
+    }
+
+    /// The text matched by group `index`, or `None` if that group did not participate in the
+    /// match (or does not exist).
+    pub fn get(&self, index: usize) -> Option<&'haystack str> {
+        self.range(index).map(|range| &self.haystack[range])
+    }
+
+    /// The byte offsets of the text matched by group `index`, like [`Captures::get`].
+    pub fn range(&self, index: usize) -> Option<Range<usize>> {
+        let start = (*self.slots.get(2 * index)?)?;
+        let end = (*self.slots.get(2 * index + 1)?)?;
+        Some(start..end)
+    }
+
+    /// The number of groups, including group 0.
+    pub fn len(&self) -> usize {
+        self.slots.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<&'haystack str>> + '_ {
+        (0..self.len()).map(|index| self.get(index))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Captures, Instruction, PriorityThreads, Program};
+
+    fn is_a(char: char) -> bool {
+        char == 'a'
+    }
+
+    fn is_b(char: char) -> bool {
+        char == 'b'
+    }
+
+    //(a*)b: group 1 runs from state 1 to state 3
+    static PROGRAM: Program = Program {
+        states: &[
+            &[Instruction::Save { slot: 2, target: 1 }],
+            &[
+                Instruction::Consume {
+                    condition: is_a,
+                    target: 1,
+                },
+                Instruction::Epsilon(2),
+            ],
+            &[Instruction::Save { slot: 3, target: 3 }],
+            &[Instruction::Consume {
+                condition: is_b,
+                target: 4,
+            }],
+            &[],
+        ],
+        start_states: &[0],
+        terminal_states: &[false, false, false, false, true],
+        capture_count: 1,
+    };
+
+    #[test]
+    fn test_full_match() {
+        let slots = PROGRAM.full_match("aab").unwrap();
+        let captures = Captures::new("aab", slots);
+        assert_eq!(captures.get(0), Some("aab"));
+        assert_eq!(captures.get(1), Some("aa"));
+        assert_eq!(captures.range(1), Some(0..2));
+        assert_eq!(captures.get(2), None);
+        assert_eq!(captures.len(), 2);
+
+        assert_eq!(PROGRAM.full_match("aabb"), None);
+        assert_eq!(PROGRAM.full_match("aa"), None);
+    }
+
+    //^a|b$, with the anchors on the transitions out of the start state
+    static ANCHORED: Program = Program {
+        states: &[
+            &[
+                Instruction::StartOfInput(1),
+                Instruction::Consume {
+                    condition: is_b,
+                    target: 2,
+                },
+            ],
+            &[Instruction::Consume {
+                condition: is_a,
+                target: 3,
+            }],
+            &[Instruction::EndOfInput(3)],
+            &[],
+        ],
+        start_states: &[0],
+        terminal_states: &[false, false, false, true],
+        capture_count: 0,
+    };
+
+    #[test]
+    fn test_anchors() {
+        assert!(ANCHORED.full_match("a").is_some());
+        assert!(ANCHORED.full_match("b").is_some());
+
+        let search = |haystack| {
+            let slots = ANCHORED.search(haystack)?;
+            Captures::new(haystack, slots).range(0)
+        };
+        assert_eq!(search("ab"), Some(0..1));
+        assert_eq!(search("xab"), Some(2..3));
+        assert_eq!(search("xa"), None);
+        assert_eq!(search("bx"), None);
+    }
+
+    #[test]
+    fn test_search() {
+        let slots = PROGRAM.search("xxaabxab").unwrap();
+        let captures = Captures::new("xxaabxab", slots);
+        assert_eq!(captures.get(0), Some("aab"));
+        assert_eq!(captures.range(0), Some(2..5));
+        assert_eq!(captures.get(1), Some("aa"));
+
+        assert_eq!(PROGRAM.search("xxaa"), None);
+    }
+
+    //a+?, which prefers leaving at state 1 over going back to read another a; swapping the transitions out of state 1 makes it a+
+    static LAZY: Program = Program {
+        states: &[
+            &[Instruction::Consume {
+                condition: is_a,
+                target: 1,
+            }],
+            &[Instruction::Epsilon(2), Instruction::Epsilon(0)],
+            &[],
+        ],
+        start_states: &[0],
+        terminal_states: &[false, false, true],
+        capture_count: 0,
+    };
+
+    #[test]
+    fn test_match_end() {
+        let mut threads = PriorityThreads::<5, 7>::new();
+        assert_eq!(PROGRAM.match_end("xaabab", 1, &mut threads), Some(4));
+        assert_eq!(PROGRAM.match_end("xaabab", 4, &mut threads), Some(6));
+        assert_eq!(PROGRAM.match_end("xaabab", 0, &mut threads), None);
+
+        let mut threads = PriorityThreads::<3, 4>::new();
+        assert_eq!(LAZY.match_end("aaa", 0, &mut threads), Some(1));
+        assert_eq!(LAZY.match_end("baaa", 1, &mut threads), Some(2));
+
+        let mut threads = PriorityThreads::<4, 5>::new();
+        assert_eq!(ANCHORED.match_end("ab", 0, &mut threads), Some(1));
+        assert_eq!(ANCHORED.match_end("xa", 1, &mut threads), None);
+        assert_eq!(ANCHORED.match_end("xb", 1, &mut threads), Some(2));
+    }
+}
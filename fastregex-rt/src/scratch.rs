@@ -0,0 +1,63 @@
+/// The integer types a matcher's states are numbered with. A matcher picks the smallest one
+/// that fits its state count.
+pub trait StateId: Copy + Eq {
+    const ZERO: Self;
+    const MAX: Self;
+
+    fn successor(self) -> Self;
+}
+
+macro_rules! impl_state_id {
+    ($($int: ty),*) => {
+        $(
+            impl StateId for $int {
+                const ZERO: Self = 0;
+                const MAX: Self = <$int>::MAX;
+
+                #[inline]
+                fn successor(self) -> Self {
+                    self + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_state_id!(u8, u16, u32, u64, usize);
+
+/// Marks which of a matcher's `N` states have been added in the current step, so that a state
+/// reached by several transitions is only added once.
+///
+/// Rather than clearing the marks on every step, each step has its own generation number, and a
+/// state is marked by storing the current generation in `did_add_state`.
+pub struct ScratchSpace<Id: StateId, const N: usize> {
+    pub did_add_state: [Id; N],
+    pub did_add_state_value: Id,
+}
+
+impl<Id: StateId, const N: usize> ScratchSpace<Id, N> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            did_add_state: [Id::ZERO; N],
+            did_add_state_value: Id::ZERO,
+        }
+    }
+
+    /// Starts a new step, in which no state has been added yet.
+    #[inline]
+    pub fn begin_step(&mut self) {
+        //the generation counter is as small as a state id, so once it runs out the marks are cleared and it starts over
+        if self.did_add_state_value == Id::MAX {
+            self.did_add_state.fill(Id::ZERO);
+            self.did_add_state_value = Id::ZERO;
+        }
+        self.did_add_state_value = self.did_add_state_value.successor();
+    }
+}
+
+impl<Id: StateId, const N: usize> Default for ScratchSpace<Id, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -19,11 +19,18 @@ fn bench_comparatively(c: &mut Criterion) {
     ];
     //let haystack = ["The quick brown fox jumped over the lazy dog."];
 
+    let mut reused_matcher = https_matcher::Matcher::new();
+
     for haystack in haystack {
         let mut group = c.benchmark_group(haystack);
         group.bench_with_input(BenchmarkId::new("Fastregex", haystack), haystack, |b, i| {
             b.iter(|| https_matcher(black_box(i)))
         });
+        group.bench_with_input(
+            BenchmarkId::new("Fastregex (reused matcher)", haystack),
+            haystack,
+            |b, i| b.iter(|| reused_matcher.is_match(black_box(i))),
+        );
         group.bench_with_input(
             BenchmarkId::new("Traditional Regex", haystack),
             haystack,
@@ -1,23 +1,37 @@
+use crate::automaton::ascii_table::AsciiTables;
+use crate::automaton::dispatch::{Dispatch, RangeArm};
+use crate::automaton::hotness::states_by_hotness;
 use crate::automaton::state_enum::StateEnum;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use regexlib::automata::{Automaton, State, Transition, TransitionCondition};
+use regexlib::grapheme::GraphemeBreak;
 use regexlib::parser::character_class::CharacterClass;
+use regexlib::script::Script;
+use unic_char_property::EnumeratedCharProperty;
+use unic_ucd_category::GeneralCategory;
 
 pub fn emit_advance_function(automaton: &Automaton, state_enum: &StateEnum) -> TokenStream {
-    let transition_handlers = automaton
-        .states()
-        .iter()
-        .map(|state| emit_state_handler(state, state_enum))
+    let mut ascii_tables = AsciiTables::default();
+
+    //arms for the states that are most often active come first
+    let transition_handlers = states_by_hotness(automaton)
+        .into_iter()
+        .map(|state_id| {
+            emit_state_handler(automaton.get_state(state_id), state_enum, &mut ascii_tables)
+        })
         .collect::<Vec<_>>();
+    let ascii_tables = ascii_tables.emit_statics();
 
     quote! {
         pub fn advance_from(&mut self, from: &Automaton, next: char, scratch: &mut ScratchSpace){
-            scratch.did_add_state_value += 1;
+            #ascii_tables
+
+            scratch.begin_step();
 
             self.valid_state_count = 0;
 
-            for from_state in from.states.iter().take(from.valid_state_count){
+            for from_state in from.states.iter().take(from.valid_state_count as usize){
                 match from_state{
                     #(#transition_handlers)*
                 }
@@ -26,14 +40,21 @@ pub fn emit_advance_function(automaton: &Automaton, state_enum: &StateEnum) -> T
     }
 }
 
-fn emit_state_handler(state: &State, state_enum: &StateEnum) -> TokenStream {
+fn emit_state_handler(
+    state: &State,
+    state_enum: &StateEnum,
+    ascii_tables: &mut AsciiTables,
+) -> TokenStream {
     let state_identifier = state_enum.reference_state(state);
 
-    let transition_handlers = state
-        .transitions
-        .iter()
-        .map(|transition| emit_state_transition_handler(transition, state_enum))
-        .collect::<Vec<_>>();
+    let transition_handlers = match Dispatch::for_state(state) {
+        Dispatch::Direct => state
+            .transitions
+            .iter()
+            .map(|transition| emit_state_transition_handler(transition, state_enum, ascii_tables))
+            .collect::<Vec<_>>(),
+        Dispatch::RangeMatch(arms) => vec![emit_range_match(&arms, state_enum)],
+    };
 
     quote! {
         #state_identifier => {
@@ -42,24 +63,108 @@ fn emit_state_handler(state: &State, state_enum: &StateEnum) -> TokenStream {
     }
 }
 
-fn emit_state_transition_handler(transition: &Transition, state_enum: &StateEnum) -> TokenStream {
+fn emit_range_match(arms: &[RangeArm], state_enum: &StateEnum) -> TokenStream {
+    let arms = arms
+        .iter()
+        .map(|arm| {
+            let patterns = arm
+                .ranges
+                .iter()
+                .map(|(start, end)| {
+                    if start == end {
+                        quote!(#start)
+                    } else {
+                        quote!(#start..=#end)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let add_targets = arm
+                .targets
+                .iter()
+                .map(|target_state_id| emit_add_state(*target_state_id, None, state_enum))
+                .collect::<Vec<_>>();
+
+            quote! {
+                #(#patterns)|* => {
+                    #(#add_targets)*
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        match next{
+            #(#arms)*
+            _ => {}
+        }
+    }
+}
+
+fn emit_state_transition_handler(
+    transition: &Transition,
+    state_enum: &StateEnum,
+    ascii_tables: &mut AsciiTables,
+) -> TokenStream {
     let condition_checker = match &transition.condition {
+        TransitionCondition::CharacterClass(class) => {
+            let comparisons = character_class_to_token_stream(class);
+            ascii_tables
+                .emit_check(class, comparisons.clone())
+                .unwrap_or(comparisons)
+        }
+        condition => emit_condition_checker(condition),
+    };
+
+    emit_add_state(
+        transition.next_state_id,
+        Some(condition_checker),
+        state_enum,
+    )
+}
+
+/// The expression checking whether the character `next` satisfies a condition that reads one.
+pub(super) fn emit_condition_checker(condition: &TransitionCondition) -> TokenStream {
+    match condition {
         TransitionCondition::Literal(literal) => {
             let literal = *literal;
             quote! { next == #literal }
         }
         TransitionCondition::CharacterClass(class) => character_class_to_token_stream(class),
+        TransitionCondition::UnicodeCharacterClass(categories) => {
+            unicode_class_to_token_stream(categories)
+        }
+        TransitionCondition::NegatedUnicodeClass(categories) => {
+            let base = unicode_class_to_token_stream(categories);
+            quote!(!(#base))
+        }
+        TransitionCondition::UnicodeScript(script) => script_to_token_stream(*script),
+        TransitionCondition::NegatedUnicodeScript(script) => {
+            let base = script_to_token_stream(*script);
+            quote!(!(#base))
+        }
+        TransitionCondition::GraphemeBreak(values) => grapheme_break_to_token_stream(values),
+        TransitionCondition::NegatedGraphemeBreak(values) => {
+            let base = grapheme_break_to_token_stream(values);
+            quote!(!(#base))
+        }
         TransitionCondition::AnyCharacter => quote! { true },
         _ => unimplemented!(),
-    };
+    }
+}
 
-    let target_state_id = transition.next_state_id;
-    let target_state_ident = state_enum.reference_id(transition.next_state_id);
+fn emit_add_state(
+    target_state_id: usize,
+    condition_checker: Option<TokenStream>,
+    state_enum: &StateEnum,
+) -> TokenStream {
+    let target_state_ident = state_enum.reference_id(target_state_id);
+    let condition_checker =
+        condition_checker.map(|condition_checker| quote!(&& (#condition_checker)));
 
     quote! {
-        if (scratch.did_add_state[#target_state_id] != scratch.did_add_state_value) && (#condition_checker) {
+        if (scratch.did_add_state[#target_state_id] != scratch.did_add_state_value) #condition_checker {
             scratch.did_add_state[#target_state_id] = scratch.did_add_state_value;
-            self.states[self.valid_state_count] = #target_state_ident;
+            self.states[self.valid_state_count as usize] = #target_state_ident;
             self.valid_state_count += 1;
         }
     }
@@ -72,6 +177,8 @@ fn character_class_to_token_stream(character_class: &CharacterClass) -> TokenStr
             let base = character_class_to_token_stream(class);
             quote!(!(#base))
         }
+        //a range of one character would be a comparison that clippy warns about in the caller's crate
+        CharacterClass::Range { start, end } if start == end => quote!(next == #start),
         CharacterClass::Range { start, end } => {
             quote!(((next as u32) >= (#start as u32)) && ((next as u32) <= (#end as u32)))
         }
@@ -82,5 +189,41 @@ fn character_class_to_token_stream(character_class: &CharacterClass) -> TokenStr
                 .collect::<Vec<_>>();
             quote!(#((#token_streams))||*)
         }
+        CharacterClass::Intersection(left, right) => {
+            let left = character_class_to_token_stream(left);
+            let right = character_class_to_token_stream(right);
+            quote!((#left) && (#right))
+        }
+        CharacterClass::Difference(left, right) => {
+            let left = character_class_to_token_stream(left);
+            let right = character_class_to_token_stream(right);
+            quote!((#left) && !(#right))
+        }
     }
 }
+
+//the tables are shared by all matchers through fastregex-rt, like the rest of the engine's generic parts
+fn unicode_class_to_token_stream(categories: &[GeneralCategory]) -> TokenStream {
+    if categories.is_empty() {
+        return quote!(false);
+    }
+
+    let tables = categories
+        .iter()
+        .map(|category| Ident::new(&category.abbr_name().to_uppercase(), Span::call_site()))
+        .collect::<Vec<_>>();
+
+    quote!(#(::fastregex_rt::in_ranges(::fastregex_rt::general_category::#tables, next))||*)
+}
+
+fn script_to_token_stream(script: Script) -> TokenStream {
+    let table = Ident::new(&script.name().to_uppercase(), Span::call_site());
+    quote!(::fastregex_rt::in_ranges(::fastregex_rt::script::#table, next))
+}
+
+fn grapheme_break_to_token_stream(values: &[GraphemeBreak]) -> TokenStream {
+    let tables = values
+        .iter()
+        .map(|value| Ident::new(&value.name().to_uppercase(), Span::call_site()));
+    quote!(#(::fastregex_rt::in_ranges(::fastregex_rt::grapheme_break::#tables, next))||*)
+}
@@ -0,0 +1,166 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use regexlib::parser::character_class::CharacterClass;
+
+use crate::automaton::dispatch::class_ranges;
+
+const ASCII_END: u32 = 0x80;
+
+/// Static lookup tables for the ASCII part of character classes, which check a character with a
+/// single load no matter how many ranges the class has. Classes with the same ASCII members share
+/// a table.
+#[derive(Default)]
+pub struct AsciiTables {
+    tables: Vec<Vec<bool>>,
+}
+
+impl AsciiTables {
+    /// Checks `next` against `class` with a table when it is ASCII. `fallback` checks characters
+    /// outside of ASCII, and is only used if the class neither matches all nor none of them.
+    ///
+    /// Returns `None` for classes that are a single character or range, which are checked at least
+    /// as fast by comparing directly.
+    pub fn emit_check(
+        &mut self,
+        class: &CharacterClass,
+        fallback: TokenStream,
+    ) -> Option<TokenStream> {
+        match class {
+            CharacterClass::Char(_) | CharacterClass::Range { .. } => return None,
+            CharacterClass::Disjunction(_)
+            | CharacterClass::Negated(_)
+            | CharacterClass::Intersection(..)
+            | CharacterClass::Difference(..) => {}
+        }
+
+        let ranges = class_ranges(class);
+
+        let table = (0..ASCII_END)
+            .map(|code_point| {
+                ranges
+                    .iter()
+                    .any(|(start, end)| (*start..=*end).contains(&code_point))
+            })
+            .collect::<Vec<_>>();
+        let table_identifier = self.table_identifier(table);
+
+        let non_ascii_ranges = ranges
+            .iter()
+            .filter(|(_, end)| *end >= ASCII_END)
+            .map(|(start, end)| ((*start).max(ASCII_END), *end))
+            .collect::<Vec<_>>();
+        let non_ascii_check = if non_ascii_ranges.is_empty() {
+            quote!(false)
+        } else if covers_non_ascii(non_ascii_ranges) {
+            quote!(true)
+        } else {
+            fallback
+        };
+
+        Some(quote! {
+            if (next as u32) < #ASCII_END {
+                #table_identifier[next as usize]
+            } else {
+                #non_ascii_check
+            }
+        })
+    }
+
+    fn table_identifier(&mut self, table: Vec<bool>) -> Ident {
+        let index = match self.tables.iter().position(|existing| *existing == table) {
+            Some(index) => index,
+            None => {
+                self.tables.push(table);
+                self.tables.len() - 1
+            }
+        };
+
+        Ident::new(&format!("ASCII_TABLE_{}", index), Span::call_site())
+    }
+
+    pub fn emit_statics(&self) -> TokenStream {
+        let statics = self
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(index, table)| {
+                let table_identifier =
+                    Ident::new(&format!("ASCII_TABLE_{}", index), Span::call_site());
+                quote!(static #table_identifier: [bool; 128] = [#(#table),*];)
+            })
+            .collect::<Vec<_>>();
+
+        quote!(#(#statics)*)
+    }
+}
+
+fn covers_non_ascii(mut ranges: Vec<(u32, u32)>) -> bool {
+    ranges.sort_unstable();
+
+    let mut covered_until = ASCII_END;
+    for (start, end) in ranges {
+        if start > covered_until {
+            return false;
+        }
+        covered_until = covered_until.max(end + 1);
+    }
+
+    covered_until > char::MAX as u32
+}
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use regexlib::parser::character_class::CharacterClass;
+
+    use super::AsciiTables;
+
+    fn class(ranges: &[(char, char)]) -> CharacterClass {
+        CharacterClass::Disjunction(
+            ranges
+                .iter()
+                .map(|(start, end)| CharacterClass::Range {
+                    start: *start,
+                    end: *end,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_tables_are_shared() {
+        let mut tables = AsciiTables::default();
+        tables.emit_check(&class(&[('a', 'z'), ('.', '.')]), quote!());
+        tables.emit_check(&class(&[('.', '.'), ('a', 'z')]), quote!());
+        tables.emit_check(&class(&[('0', '9'), ('_', '_')]), quote!());
+
+        assert_eq!(tables.tables.len(), 2);
+        assert!(tables.tables[0]['q' as usize]);
+        assert!(!tables.tables[0]['0' as usize]);
+    }
+
+    #[test]
+    fn test_non_ascii_fallback() {
+        let fallback = quote!(fallback());
+        let mut tables = AsciiTables::default();
+        let uses_fallback = |check: Option<proc_macro2::TokenStream>| {
+            check.unwrap().to_string().contains("fallback")
+        };
+
+        assert!(!uses_fallback(tables.emit_check(
+            &class(&[('a', 'z'), ('0', '9')]),
+            fallback.clone()
+        )));
+        assert!(uses_fallback(tables.emit_check(
+            &class(&[('a', 'z'), ('\u{e9}', '\u{e9}')]),
+            fallback.clone()
+        )));
+        let negated = CharacterClass::Negated(Box::new(class(&[('a', 'z'), ('0', '9')])));
+        assert!(!uses_fallback(
+            tables.emit_check(&negated, fallback.clone())
+        ));
+        assert!(tables
+            .emit_check(&CharacterClass::Char('a'), fallback)
+            .is_none());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::automaton::advance_function::emit_condition_checker;
+use proc_macro2::TokenStream;
+use quote::quote;
+use regexlib::automata::{Automaton, Transition, TransitionCondition};
+use regexlib::line_terminator::LineTerminator;
+
+/// Emits `CAPTURE_PROGRAM`, the `::fastregex_rt::pike::Program` for an automaton that has not
+/// been simplified, since simplification removes the transitions marking where groups start and
+/// end and the priorities between paths, and `PriorityThreads`, the threads big enough to run it.
+pub fn emit_capture_program(automaton: &Automaton, capture_count: usize) -> TokenStream {
+    let states = automaton.states().iter().map(|state| {
+        let instructions = state.transitions.iter().map(emit_instruction);
+        quote!(&[#(#instructions),*])
+    });
+    let start_states = automaton.start_states();
+    let terminal_states =
+        (0..automaton.state_count()).map(|state_id| automaton.is_terminal_state(state_id));
+    let state_count = automaton.state_count();
+    //every transition pushes at most one state while adding a thread, on top of the one it starts from
+    let stack_size = automaton
+        .states()
+        .iter()
+        .map(|state| state.transitions.len())
+        .sum::<usize>()
+        + 1;
+
+    quote! {
+        static CAPTURE_PROGRAM: ::fastregex_rt::pike::Program = ::fastregex_rt::pike::Program{
+            states: &[#(#states),*],
+            start_states: &[#(#start_states),*],
+            terminal_states: &[#(#terminal_states),*],
+            capture_count: #capture_count,
+        };
+
+        type PriorityThreads = ::fastregex_rt::pike::PriorityThreads<#state_count, #stack_size>;
+    }
+}
+
+fn emit_instruction(transition: &Transition) -> TokenStream {
+    let target = transition.next_state_id;
+    match &transition.condition {
+        TransitionCondition::Epsilon => quote!(::fastregex_rt::pike::Instruction::Epsilon(#target)),
+        TransitionCondition::StartOfInput => {
+            quote!(::fastregex_rt::pike::Instruction::StartOfInput(#target))
+        }
+        TransitionCondition::EndOfInput => {
+            quote!(::fastregex_rt::pike::Instruction::EndOfInput(#target))
+        }
+        TransitionCondition::StartOfLine(line_terminator) => {
+            let line_terminator = line_terminator_tokens(*line_terminator);
+            quote!(::fastregex_rt::pike::Instruction::StartOfLine{ line_terminator: #line_terminator, target: #target })
+        }
+        TransitionCondition::EndOfLine(line_terminator) => {
+            let line_terminator = line_terminator_tokens(*line_terminator);
+            quote!(::fastregex_rt::pike::Instruction::EndOfLine{ line_terminator: #line_terminator, target: #target })
+        }
+        TransitionCondition::WordStart => {
+            quote!(::fastregex_rt::pike::Instruction::WordStart(#target))
+        }
+        TransitionCondition::WordEnd => {
+            quote!(::fastregex_rt::pike::Instruction::WordEnd(#target))
+        }
+        TransitionCondition::CaptureStart(index) => {
+            let slot = 2 * index;
+            quote!(::fastregex_rt::pike::Instruction::Save{ slot: #slot, target: #target })
+        }
+        TransitionCondition::CaptureEnd(index) => {
+            let slot = 2 * index + 1;
+            quote!(::fastregex_rt::pike::Instruction::Save{ slot: #slot, target: #target })
+        }
+        //the closure would not use its argument, which warns in the caller's crate
+        TransitionCondition::AnyCharacter => quote! {
+            ::fastregex_rt::pike::Instruction::Consume{ condition: |_: char| true, target: #target }
+        },
+        condition => {
+            let condition_checker = emit_condition_checker(condition);
+            quote! {
+                ::fastregex_rt::pike::Instruction::Consume{ condition: |next: char| #condition_checker, target: #target }
+            }
+        }
+    }
+}
+
+fn line_terminator_tokens(line_terminator: LineTerminator) -> TokenStream {
+    match line_terminator {
+        LineTerminator::Newline => quote!(::fastregex_rt::pike::LineTerminator::Newline),
+        LineTerminator::Crlf => quote!(::fastregex_rt::pike::LineTerminator::Crlf),
+        LineTerminator::Unicode => quote!(::fastregex_rt::pike::LineTerminator::Unicode),
+    }
+}
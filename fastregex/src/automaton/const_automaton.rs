@@ -0,0 +1,102 @@
+//! The automaton as something a `const fn` can run. Const fns can not call the automaton's
+//! methods, which iterate over its list of states and work in a `ScratchSpace`, so this one keeps
+//! a flag per state instead and checks each state's transitions in turn.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use regexlib::automata::{Automaton, TransitionCondition};
+
+use crate::automaton::advance_function::emit_condition_checker;
+
+pub fn emit_const_automaton(automaton: &Automaton, emit_terminal_branch: bool) -> TokenStream {
+    let state_count = automaton.state_count();
+    let start_states = automaton.start_states();
+
+    let state_handlers = automaton.states().iter().map(|state| {
+        let state_id = state.id;
+        let transitions = state.transitions.iter().map(|transition| {
+            let target = transition.next_state_id;
+            match &transition.condition {
+                TransitionCondition::AnyCharacter => quote!(to[#target] = true;),
+                condition => {
+                    let condition_checker = emit_condition_checker(condition);
+                    quote! {
+                        if #condition_checker {
+                            to[#target] = true;
+                        }
+                    }
+                }
+            }
+        });
+
+        quote! {
+            if self.0[#state_id] {
+                #(#transitions)*
+            }
+        }
+    });
+
+    let terminal_states = automaton.terminal_state_ids();
+    let is_terminated = if terminal_states.is_empty() {
+        quote!(false)
+    } else {
+        quote!(#(self.0[#terminal_states])||*)
+    };
+    let terminal_branch = if emit_terminal_branch {
+        //several branches may match, in which case the first one is reported
+        let branches = terminal_states
+            .iter()
+            .enumerate()
+            .map(|(branch, terminal_state_id)| {
+                quote! {
+                    if self.0[#terminal_state_id] {
+                        return ::core::option::Option::Some(#branch);
+                    }
+                }
+            });
+        quote! {
+            const fn terminal_branch(&self) -> ::core::option::Option<usize>{
+                #(#branches)*
+                ::core::option::Option::None
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    quote! {
+        #[derive(Clone, Copy)]
+        struct ConstAutomaton([bool; #state_count]);
+
+        impl ConstAutomaton{
+            const START: Self = {
+                let mut states = [false; #state_count];
+                #(states[#start_states] = true;)*
+                Self(states)
+            };
+
+            const fn advance(&self, next: char) -> Self{
+                let mut to = [false; #state_count];
+                #(#state_handlers)*
+                Self(to)
+            }
+
+            const fn is_terminated(&self) -> bool{
+                #is_terminated
+            }
+
+            #terminal_branch
+
+            const fn is_failed(&self) -> bool{
+                let mut state_id = 0;
+                while state_id < #state_count {
+                    if self.0[state_id] {
+                        return false;
+                    }
+                    state_id += 1;
+                }
+                true
+            }
+        }
+    }
+}
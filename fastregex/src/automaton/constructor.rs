@@ -7,6 +7,8 @@ use syn::{Lit, LitInt};
 pub struct AutomatonConstructor {
     initial_states: Vec<TokenStream>,
     initial_states_count: Lit,
+    start_states: Vec<TokenStream>,
+    start_state_ids: Vec<usize>,
 }
 
 impl AutomatonConstructor {
@@ -27,6 +29,8 @@ impl AutomatonConstructor {
         ));
 
         Self {
+            start_states: initial_states[..automaton.start_states().len()].to_vec(),
+            start_state_ids: automaton.start_states().to_vec(),
             initial_states,
             initial_states_count,
         }
@@ -37,6 +41,9 @@ impl ToTokens for AutomatonConstructor {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let initial_states = &self.initial_states;
         let initial_states_count = &self.initial_states_count;
+        let start_states = &self.start_states;
+        let start_state_indices = 0..start_states.len();
+        let start_state_ids = &self.start_state_ids;
 
         tokens.append_all(quote! {
             pub fn new() -> Self{
@@ -45,6 +52,23 @@ impl ToTokens for AutomatonConstructor {
                     valid_state_count: #initial_states_count
                 }
             }
+
+            //only the start states are written, since states past valid_state_count are never read
+            pub fn reset(&mut self){
+                #(self.states[#start_state_indices] = #start_states;)*
+                self.valid_state_count = #initial_states_count;
+            }
+
+            //lets a match start at the next character as well, for searching; it must follow advance_from, since it reuses that step's marks of the states already added
+            pub fn add_start_states(&mut self, scratch: &mut ScratchSpace){
+                #(
+                    if scratch.did_add_state[#start_state_ids] != scratch.did_add_state_value {
+                        scratch.did_add_state[#start_state_ids] = scratch.did_add_state_value;
+                        self.states[self.valid_state_count as usize] = #start_states;
+                        self.valid_state_count += 1;
+                    }
+                )*
+            }
         });
     }
 }
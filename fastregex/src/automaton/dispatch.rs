@@ -0,0 +1,238 @@
+use std::collections::BTreeSet;
+
+use regexlib::automata::{State, Transition, TransitionCondition};
+use regexlib::parser::character_class::CharacterClass;
+
+//states with at most this many transitions check each of them directly
+const DIRECT_COMPARISON_MAX_TRANSITIONS: usize = 2;
+
+const SURROGATES: (u32, u32) = (0xD800, 0xDFFF);
+
+/// How the transitions of a state are checked against the next character.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Dispatch {
+    /// One comparison per transition.
+    Direct,
+    /// A single `match` over disjoint character ranges, each listing the target states of the
+    /// transitions it is covered by.
+    RangeMatch(Vec<RangeArm>),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct RangeArm {
+    pub ranges: Vec<(char, char)>,
+    pub targets: Vec<usize>,
+}
+
+impl Dispatch {
+    pub fn for_state(state: &State) -> Self {
+        if state.transitions.len() <= DIRECT_COMPARISON_MAX_TRANSITIONS {
+            return Dispatch::Direct;
+        }
+
+        match transition_arms(&state.transitions) {
+            Some(arms) => Dispatch::RangeMatch(arms),
+            None => Dispatch::Direct,
+        }
+    }
+}
+
+/// The disjoint character ranges that the transitions are taken on, or `None` if some condition is
+/// not known as ranges of characters (i.e., it is a Unicode class, a script or part of a `\X`).
+pub fn transition_arms<'a>(
+    transitions: impl IntoIterator<Item = &'a Transition>,
+) -> Option<Vec<RangeArm>> {
+    let transition_ranges = transitions
+        .into_iter()
+        .map(|transition| {
+            condition_ranges(&transition.condition).map(|ranges| (ranges, transition.next_state_id))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(range_arms(&transition_ranges))
+}
+
+//splits the ranges of all transitions into disjoint segments, and groups the segments by the targets covering them
+fn range_arms(transition_ranges: &[(Vec<(u32, u32)>, usize)]) -> Vec<RangeArm> {
+    let boundaries = transition_ranges
+        .iter()
+        .flat_map(|(ranges, _)| ranges)
+        .flat_map(|(start, end)| [*start, end + 1])
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    let mut arms: Vec<RangeArm> = Vec::new();
+    for segment in boundaries.windows(2) {
+        let (start, end) = (segment[0], segment[1] - 1);
+
+        let mut targets = Vec::new();
+        for (ranges, target) in transition_ranges {
+            let covered = ranges
+                .iter()
+                .any(|(range_start, range_end)| *range_start <= start && end <= *range_end);
+            if covered && !targets.contains(target) {
+                targets.push(*target);
+            }
+        }
+
+        let char_range = match to_char_range(start, end) {
+            Some(char_range) => char_range,
+            None => continue,
+        };
+        if targets.is_empty() {
+            continue;
+        }
+
+        match arms.iter_mut().find(|arm| arm.targets == targets) {
+            Some(arm) => match arm.ranges.last_mut() {
+                Some((_, last_end)) if (*last_end as u32) + 1 == start => *last_end = char_range.1,
+                _ => arm.ranges.push(char_range),
+            },
+            None => arms.push(RangeArm {
+                ranges: vec![char_range],
+                targets,
+            }),
+        }
+    }
+
+    arms
+}
+
+fn condition_ranges(condition: &TransitionCondition) -> Option<Vec<(u32, u32)>> {
+    match condition {
+        TransitionCondition::Literal(literal) => Some(vec![(*literal as u32, *literal as u32)]),
+        TransitionCondition::CharacterClass(class) => Some(class_ranges(class)),
+        TransitionCondition::AnyCharacter => Some(vec![(0, char::MAX as u32)]),
+        _ => None,
+    }
+}
+
+pub fn class_ranges(class: &CharacterClass) -> Vec<(u32, u32)> {
+    match class {
+        CharacterClass::Char(ch) => vec![(*ch as u32, *ch as u32)],
+        CharacterClass::Range { start, end } => {
+            if start <= end {
+                vec![(*start as u32, *end as u32)]
+            } else {
+                vec![]
+            }
+        }
+        CharacterClass::Disjunction(classes) => classes.iter().flat_map(class_ranges).collect(),
+        CharacterClass::Negated(class) => complement(class_ranges(class)),
+        //the ranges of neither side's complement
+        CharacterClass::Intersection(left, right) => {
+            let mut outside = complement(class_ranges(left));
+            outside.extend(complement(class_ranges(right)));
+            complement(outside)
+        }
+        CharacterClass::Difference(left, right) => {
+            let mut outside = complement(class_ranges(left));
+            outside.extend(class_ranges(right));
+            complement(outside)
+        }
+    }
+}
+
+fn complement(mut ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    ranges.sort_unstable();
+
+    let mut complement = Vec::new();
+    let mut next_start = 0;
+    for (start, end) in ranges {
+        if start > next_start {
+            complement.push((next_start, start - 1));
+        }
+        next_start = next_start.max(end + 1);
+    }
+    if next_start <= char::MAX as u32 {
+        complement.push((next_start, char::MAX as u32));
+    }
+    complement
+}
+
+//no char is a surrogate, so the ends of a range are moved out of them
+fn to_char_range(mut start: u32, mut end: u32) -> Option<(char, char)> {
+    if (SURROGATES.0..=SURROGATES.1).contains(&start) {
+        start = SURROGATES.1 + 1;
+    }
+    if (SURROGATES.0..=SURROGATES.1).contains(&end) {
+        end = SURROGATES.0 - 1;
+    }
+
+    if start > end {
+        return None;
+    }
+
+    Some((char::from_u32(start)?, char::from_u32(end)?))
+}
+
+#[cfg(test)]
+mod test {
+    use regexlib::automata::{Automaton, State, Transition, TransitionCondition};
+    use regexlib::parser::character_class::CharacterClass;
+    use regexlib::parser::RegexEntry;
+
+    use super::Dispatch;
+
+    #[test]
+    fn test_few_transitions_are_direct() {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse("[x]((a)|(b))").unwrap());
+        automaton.simplify();
+
+        for state in automaton.states() {
+            assert_eq!(Dispatch::for_state(state), Dispatch::Direct);
+        }
+    }
+
+    #[test]
+    fn test_overlapping_ranges_are_split() {
+        let transition = |next_state_id, condition| Transition {
+            next_state_id,
+            condition,
+        };
+        let state = State {
+            debug_name: String::new(),
+            id: 0,
+            transitions: vec![
+                transition(1, TransitionCondition::Literal('a')),
+                transition(
+                    2,
+                    TransitionCondition::CharacterClass(CharacterClass::Range {
+                        start: 'a',
+                        end: 'c',
+                    }),
+                ),
+                transition(
+                    3,
+                    TransitionCondition::CharacterClass(CharacterClass::Negated(Box::new(
+                        CharacterClass::Char('b'),
+                    ))),
+                ),
+            ],
+            span: None,
+        };
+
+        let arms = match Dispatch::for_state(&state) {
+            Dispatch::RangeMatch(arms) => arms,
+            Dispatch::Direct => panic!("expected a range match"),
+        };
+
+        //every character leads to the same states as the transitions it matches
+        let targets_of = |ch: char| {
+            arms.iter()
+                .find(|arm| {
+                    arm.ranges
+                        .iter()
+                        .any(|(start, end)| (*start..=*end).contains(&ch))
+                })
+                .map(|arm| arm.targets.clone())
+                .unwrap_or_default()
+        };
+        assert_eq!(targets_of('a'), vec![1, 2, 3]);
+        assert_eq!(targets_of('b'), vec![2]);
+        assert_eq!(targets_of('c'), vec![2, 3]);
+        assert_eq!(targets_of('z'), vec![3]);
+        assert_eq!(targets_of('\u{10FFFF}'), vec![3]);
+    }
+}
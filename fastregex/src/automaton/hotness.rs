@@ -0,0 +1,100 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+use regexlib::automata::Automaton;
+
+/// Orders the state ids of `automaton` by how often they are expected to be active: start states
+/// first, then states on a loop, each group ordered by how early in the input they are reachable.
+pub fn states_by_hotness(automaton: &Automaton) -> Vec<usize> {
+    let distances = distances_from_start(automaton);
+
+    let mut state_ids = (0..automaton.state_count()).collect::<Vec<_>>();
+    state_ids.sort_by_key(|state_id| {
+        (
+            Reverse(automaton.start_states().contains(state_id)),
+            Reverse(is_on_loop(automaton, *state_id)),
+            distances[*state_id],
+            *state_id,
+        )
+    });
+    state_ids
+}
+
+//number of transitions needed to reach each state, or usize::MAX for unreachable states
+fn distances_from_start(automaton: &Automaton) -> Vec<usize> {
+    let mut distances = vec![usize::MAX; automaton.state_count()];
+    let mut queue = VecDeque::new();
+
+    for start_state_id in automaton.start_states() {
+        distances[*start_state_id] = 0;
+        queue.push_back(*start_state_id);
+    }
+
+    while let Some(state_id) = queue.pop_front() {
+        for transition in &automaton.get_state(state_id).transitions {
+            if distances[transition.next_state_id] == usize::MAX {
+                distances[transition.next_state_id] = distances[state_id] + 1;
+                queue.push_back(transition.next_state_id);
+            }
+        }
+    }
+
+    distances
+}
+
+fn is_on_loop(automaton: &Automaton, state_id: usize) -> bool {
+    let mut visited = vec![false; automaton.state_count()];
+    let mut stack = vec![state_id];
+
+    while let Some(current) = stack.pop() {
+        for transition in &automaton.get_state(current).transitions {
+            if transition.next_state_id == state_id {
+                return true;
+            }
+
+            if !visited[transition.next_state_id] {
+                visited[transition.next_state_id] = true;
+                stack.push(transition.next_state_id);
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use regexlib::automata::Automaton;
+    use regexlib::parser::RegexEntry;
+
+    use super::states_by_hotness;
+
+    #[test]
+    fn test_start_and_loop_states_first() {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse("[x]a[0-9]*b").unwrap());
+        automaton.simplify();
+
+        let order = states_by_hotness(&automaton);
+        assert_eq!(order.len(), automaton.state_count());
+        assert_eq!(
+            &order[..automaton.start_states().len()],
+            automaton.start_states()
+        );
+
+        //the state looping on digits comes before the states that are not on a loop
+        let loop_state = automaton
+            .states()
+            .iter()
+            .find(|state| {
+                state
+                    .transitions
+                    .iter()
+                    .any(|transition| transition.next_state_id == state.id)
+            })
+            .unwrap()
+            .id;
+        let terminal_state = automaton.terminal_state_ids()[0];
+        let position = |state_id| order.iter().position(|id| *id == state_id).unwrap();
+        assert!(position(loop_state) < position(terminal_state));
+    }
+}
@@ -15,7 +15,7 @@ pub fn emit_is_terminated_function(automata: &Automaton, state_enum: &StateEnum)
 
     quote! {
         fn is_terminated(&self) -> bool{
-            for from_state in self.states.iter().take(self.valid_state_count){
+            for from_state in self.states.iter().take(self.valid_state_count as usize){
                 match from_state{
                     #(#terminal_state_match_arms)*
                     _ => {}
@@ -26,3 +26,64 @@ pub fn emit_is_terminated_function(automata: &Automaton, state_enum: &StateEnum)
         }
     }
 }
+
+pub fn emit_terminal_branch_function(automata: &Automaton, state_enum: &StateEnum) -> TokenStream {
+    let terminal_state_match_arms = automata
+        .terminal_state_ids()
+        .iter()
+        .enumerate()
+        .map(|(branch, terminal_state_id)| {
+            let state_identifier = state_enum.reference_id(*terminal_state_id);
+            quote! {#state_identifier => #branch,}
+        })
+        .collect::<Vec<_>>();
+
+    //several branches may match, in which case the first one is reported
+    quote! {
+        fn terminal_branch(&self) -> ::core::option::Option<usize>{
+            let mut first_branch = ::core::option::Option::None;
+
+            for from_state in self.states.iter().take(self.valid_state_count as usize){
+                let branch = match from_state{
+                    #(#terminal_state_match_arms)*
+                    _ => continue,
+                };
+
+                match first_branch{
+                    ::core::option::Option::Some(first) if first <= branch => {}
+                    _ => first_branch = ::core::option::Option::Some(branch),
+                }
+            }
+
+            first_branch
+        }
+    }
+}
+
+pub fn emit_matched_branches_function(automata: &Automaton, state_enum: &StateEnum) -> TokenStream {
+    let branch_count = automata.terminal_state_ids().len();
+    let terminal_state_match_arms = automata
+        .terminal_state_ids()
+        .iter()
+        .enumerate()
+        .map(|(branch, terminal_state_id)| {
+            let state_identifier = state_enum.reference_id(*terminal_state_id);
+            quote! {#state_identifier => matched[#branch] = true,}
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        fn matched_branches(&self) -> [bool; #branch_count]{
+            let mut matched = [false; #branch_count];
+
+            for from_state in self.states.iter().take(self.valid_state_count as usize){
+                match from_state{
+                    #(#terminal_state_match_arms)*
+                    _ => {}
+                }
+            }
+
+            matched
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::automaton::state_enum::StateEnum;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use regexlib::automata::{Automaton, TransitionCondition};
+use syn::LitByteStr;
+
+/// Runs of states that each have a single literal transition, keyed by the state the run starts
+/// at. When that state is the only active one, the automaton can only follow the run, so the
+/// whole run can be compared against the input at once.
+pub struct LiteralChains {
+    //start state id -> (literals, state id after the last literal)
+    chains: BTreeMap<usize, (String, usize)>,
+    //whether the automaton reads bytes, each as the character of the same value, so that a run is compared as those bytes rather than as UTF-8
+    pub bytes: bool,
+}
+
+impl LiteralChains {
+    pub fn new(automaton: &Automaton) -> Self {
+        let chains = automaton
+            .states()
+            .iter()
+            .filter_map(|state| {
+                let (literals, end) = Self::follow_chain(automaton, state.id);
+                //a chain of one literal is no faster than the general engine
+                if literals.chars().count() >= 2 {
+                    Some((state.id, (literals, end)))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            chains,
+            bytes: false,
+        }
+    }
+
+    fn follow_chain(automaton: &Automaton, start: usize) -> (String, usize) {
+        let mut literals = String::new();
+        let mut current = start;
+        let mut visited = BTreeSet::new();
+
+        //a terminal state ends the chain, since the input may end there
+        while visited.insert(current) && (current == start || !automaton.is_terminal_state(current))
+        {
+            let transitions = &automaton.get_state(current).transitions;
+            match transitions.as_slice() {
+                [transition] => match transition.condition {
+                    TransitionCondition::Literal(literal) => {
+                        literals.push(literal);
+                        current = transition.next_state_id;
+                    }
+                    _ => break,
+                },
+                _ => break,
+            }
+        }
+
+        (literals, current)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chains.is_empty()
+    }
+
+    pub fn emit(&self, state_enum: &StateEnum) -> TokenStream {
+        if self.is_empty() {
+            return quote!();
+        }
+
+        let chain_match_arms = self
+            .chains
+            .iter()
+            .map(|(start, (literals, end))| {
+                let start_identifier = state_enum.reference_id(*start);
+                let end_identifier = state_enum.reference_id(*end);
+                let literals = if self.bytes {
+                    let bytes = literals.chars().map(|char| char as u8).collect::<Vec<_>>();
+                    let literals = LitByteStr::new(&bytes, Span::call_site());
+                    quote!(#literals)
+                } else {
+                    quote!(#literals)
+                };
+                quote! {#start_identifier => ::core::option::Option::Some((#literals, #end_identifier)),}
+            })
+            .collect::<Vec<_>>();
+        let literals_type = if self.bytes {
+            quote!([u8])
+        } else {
+            quote!(str)
+        };
+
+        quote! {
+            impl State{
+                fn literal_chain(&self) -> ::core::option::Option<(&'static #literals_type, State)>{
+                    match self{
+                        #(#chain_match_arms)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+        }
+    }
+}
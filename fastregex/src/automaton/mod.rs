@@ -1,11 +1,24 @@
 mod advance_function;
+mod ascii_table;
+mod capture_program;
+mod const_automaton;
 mod constructor;
+mod dispatch;
+mod hotness;
 mod is_terminated;
+mod literal_chain;
+mod one_pass;
 mod state_enum;
 
 use crate::automaton::advance_function::emit_advance_function;
+pub use crate::automaton::capture_program::emit_capture_program;
+use crate::automaton::const_automaton::emit_const_automaton;
 use crate::automaton::constructor::AutomatonConstructor;
-use crate::automaton::is_terminated::emit_is_terminated_function;
+use crate::automaton::is_terminated::{
+    emit_is_terminated_function, emit_matched_branches_function, emit_terminal_branch_function,
+};
+use crate::automaton::literal_chain::LiteralChains;
+use crate::automaton::one_pass::OnePass;
 use crate::automaton::state_enum::StateEnum;
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens, TokenStreamExt};
@@ -17,21 +30,76 @@ pub struct EmittableAutomaton {
     state_enum: StateEnum,
     state_count: Lit,
     constructor: AutomatonConstructor,
+    literal_chains: LiteralChains,
+    one_pass: Option<OnePass>,
+    emit_terminal_branch: bool,
+    emit_matched_branches: bool,
+    emit_const_automaton: bool,
 }
 
 impl EmittableAutomaton {
-    pub fn new(automaton: Automaton) -> Self {
+    /// `emit_terminal_branch` adds a `terminal_branch` function reporting which terminal state
+    /// (by its index in `terminal_state_ids`) was reached, for automata built as a union.
+    pub fn new(automaton: Automaton, emit_terminal_branch: bool) -> Self {
         let state_enum = StateEnum::new(&automaton);
         Self {
             constructor: AutomatonConstructor::new(&automaton, &state_enum),
+            literal_chains: LiteralChains::new(&automaton),
+            one_pass: OnePass::new(&automaton, emit_terminal_branch),
             state_enum,
             state_count: Lit::Int(LitInt::new(
                 &format!("{}", automaton.state_count()),
                 Span::call_site(),
             )),
             automaton,
+            emit_terminal_branch,
+            emit_matched_branches: false,
+            emit_const_automaton: false,
         }
     }
+
+    /// Adds a `matched_branches` function reporting every terminal state that was reached, for
+    /// automata built as a union of several patterns that are all of interest.
+    pub fn emitting_matched_branches(mut self) -> Self {
+        self.emit_matched_branches = true;
+        //the set's matcher reads every branch that matched, which the one-pass state does not report
+        self.one_pass = None;
+        self
+    }
+
+    /// Makes the runs of literals `literal_chain` returns bytes rather than a str, for an
+    /// automaton that reads each byte of its input as the character of the same value.
+    pub fn reading_bytes(mut self) -> Self {
+        self.literal_chains.bytes = true;
+        self
+    }
+
+    /// Adds a `ConstAutomaton`, which const fns can run: `ConstAutomaton::START`, then `advance`
+    /// on each character, with `is_failed`, `is_terminated` (and `terminal_branch`) as on the
+    /// automaton.
+    pub fn emitting_const_automaton(mut self) -> Self {
+        self.emit_const_automaton = true;
+        self
+    }
+}
+
+impl EmittableAutomaton {
+    /// Whether some states start a run of literals, for which the emitted `State` has a
+    /// `literal_chain` function returning the run and the state after it.
+    pub fn has_literal_chains(&self) -> bool {
+        !self.literal_chains.is_empty()
+    }
+
+    /// Whether the automaton is one-pass, for which the emitted `OnePassState` follows the input
+    /// through a single state: `OnePassState::START`, then `step` on each character until it
+    /// returns `None`, with `is_terminal` (and `terminal_branch`) on the state it ends in.
+    pub fn is_one_pass(&self) -> bool {
+        self.one_pass.is_some()
+    }
+
+    pub fn state_count(&self) -> usize {
+        self.automaton.state_count()
+    }
 }
 
 impl ToTokens for EmittableAutomaton {
@@ -41,27 +109,39 @@ impl ToTokens for EmittableAutomaton {
         let constructor = &self.constructor;
         let advance_function = emit_advance_function(&self.automaton, &self.state_enum);
         let is_terminated = emit_is_terminated_function(&self.automaton, &self.state_enum);
+        let literal_chains = self.literal_chains.emit(&self.state_enum);
+        let terminal_branch = if self.emit_terminal_branch {
+            emit_terminal_branch_function(&self.automaton, &self.state_enum)
+        } else {
+            quote!()
+        };
+        let one_pass = &self.one_pass;
+        let const_automaton = if self.emit_const_automaton {
+            emit_const_automaton(&self.automaton, self.emit_terminal_branch)
+        } else {
+            quote!()
+        };
+        let matched_branches = if self.emit_matched_branches {
+            emit_matched_branches_function(&self.automaton, &self.state_enum)
+        } else {
+            quote!()
+        };
 
         tokens.append_all(quote!(
             #state_enum
 
-            struct ScratchSpace{
-                did_add_state: [usize; #state_count],
-                did_add_state_value: usize,
-            }
+            #literal_chains
 
-            impl ScratchSpace{
-                fn new() -> Self{
-                    Self{
-                        did_add_state: [0; #state_count],
-                        did_add_state_value: 0
-                    }
-                }
-            }
+            #one_pass
+
+            #const_automaton
+
+            //the parts of the engine that do not depend on the pattern live in fastregex-rt, so they are compiled once rather than per matcher; this requires the macro's caller to depend on it
+            type ScratchSpace = ::fastregex_rt::ScratchSpace<StateId, #state_count>;
 
             struct Automaton {
                 states: [State; #state_count],
-                valid_state_count: usize,
+                valid_state_count: StateId,
             }
 
             impl Automaton{
@@ -72,6 +152,10 @@ impl ToTokens for EmittableAutomaton {
 
                 #is_terminated
 
+                #terminal_branch
+
+                #matched_branches
+
                 fn is_failed(&self) -> bool{
                     self.valid_state_count == 0
                 }
@@ -80,3 +164,111 @@ impl ToTokens for EmittableAutomaton {
         ));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use quote::ToTokens;
+    use regexlib::automata::Automaton;
+    use regexlib::parser::RegexEntry;
+
+    use super::EmittableAutomaton;
+
+    //snapshots of the emitted code, so that codegen changes show up as reviewable diffs
+    fn emit(regex: &str, emit_terminal_branch: bool) -> String {
+        let mut automaton = match RegexEntry::parse(regex).unwrap() {
+            RegexEntry::Alternation(branches) if emit_terminal_branch => {
+                Automaton::from_regexes(branches)
+            }
+            regex => Automaton::from_regex(regex),
+        };
+        automaton.simplify();
+
+        let tokens = EmittableAutomaton::new(automaton, emit_terminal_branch).into_token_stream();
+        prettyplease::unparse(&syn::parse2(tokens).unwrap())
+    }
+
+    #[test]
+    fn test_literal_chain() {
+        insta::assert_snapshot!(emit("[x]abc", false));
+    }
+
+    #[test]
+    fn test_character_classes() {
+        insta::assert_snapshot!(emit("x[a-z0-9_]+[^.]", false));
+    }
+
+    #[test]
+    fn test_unicode_classes() {
+        insta::assert_snapshot!(emit(r"x\p{Lu}\PL", false));
+    }
+
+    #[test]
+    fn test_any_character_repetition() {
+        insta::assert_snapshot!(emit("a.{2,3}", false));
+    }
+
+    #[test]
+    fn test_range_dispatch() {
+        insta::assert_snapshot!(emit("[x]((a)|([a-c])|([^b]))*y", false));
+    }
+
+    #[test]
+    fn test_alternation() {
+        insta::assert_snapshot!(emit("(ab)|(a[0-9]*)", false));
+    }
+
+    #[test]
+    fn test_terminal_branch() {
+        insta::assert_snapshot!(emit("(ab)|(a[0-9]*)", true));
+    }
+
+    #[test]
+    fn test_one_pass() {
+        insta::assert_snapshot!(emit("a*b[0-9]+(x|y)?", false));
+    }
+
+    #[test]
+    fn test_one_pass_terminal_branch() {
+        insta::assert_snapshot!(emit("(ab)|(cd*)", true));
+    }
+
+    #[test]
+    fn test_const_automaton() {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse(r"[x](a|\p{Lu})+").unwrap());
+        automaton.simplify();
+
+        let tokens = EmittableAutomaton::new(automaton, false)
+            .emitting_const_automaton()
+            .into_token_stream();
+        insta::assert_snapshot!(prettyplease::unparse(&syn::parse2(tokens).unwrap()));
+    }
+
+    #[test]
+    fn test_byte_literal_chain() {
+        let regex = RegexEntry::parse("[xy]é!")
+            .unwrap()
+            .to_utf8_bytes()
+            .unwrap();
+        let mut automaton = Automaton::from_regex(regex);
+        automaton.simplify();
+
+        let tokens = EmittableAutomaton::new(automaton, false)
+            .reading_bytes()
+            .into_token_stream();
+        insta::assert_snapshot!(prettyplease::unparse(&syn::parse2(tokens).unwrap()));
+    }
+
+    #[test]
+    fn test_matched_branches() {
+        let mut automaton = Automaton::from_regexes([
+            RegexEntry::parse("ab").unwrap(),
+            RegexEntry::parse("a[0-9]*").unwrap(),
+        ]);
+        automaton.simplify();
+
+        let tokens = EmittableAutomaton::new(automaton, false)
+            .emitting_matched_branches()
+            .into_token_stream();
+        insta::assert_snapshot!(prettyplease::unparse(&syn::parse2(tokens).unwrap()));
+    }
+}
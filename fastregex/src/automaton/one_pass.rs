@@ -0,0 +1,290 @@
+//! Automata in which the input alone decides which states are active: each set of states that a
+//! prefix of the input can lead to is known when the matcher is generated, and there are no more of
+//! them than there are states. Each set is then a single state of a one-pass automaton, so matching
+//! needs one state variable rather than a set of states and the scratch space for building it.
+
+use std::collections::HashMap;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens, TokenStreamExt};
+use regexlib::automata::{Automaton, TransitionCondition};
+
+use crate::automaton::advance_function::emit_condition_checker;
+use crate::automaton::dispatch::transition_arms;
+
+pub struct OnePass {
+    states: Vec<OnePassState>,
+    emit_terminal_branch: bool,
+}
+
+struct OnePassState {
+    ident: Ident,
+    is_terminal: bool,
+    //the index of the first terminal state among the automaton's, as its `terminal_branch` reports
+    terminal_branch: Option<usize>,
+    step: Step,
+}
+
+//how a state picks the state after the next character
+enum Step {
+    //the state has no transitions, so it fails on any character
+    Fail,
+    //conditions that are not ranges of characters, which are all the same or all lead into the same state, so it is enough that one of them holds
+    Conditions(Vec<TransitionCondition>, usize),
+    //disjoint ranges of characters, each leading into one state
+    Ranges(Vec<(Vec<(char, char)>, usize)>),
+}
+
+impl OnePass {
+    /// The one-pass form of the automaton, or `None` if it would have more states than the
+    /// automaton, or if the transitions of some set of states can not be told apart by ranges of
+    /// characters (i.e., they are Unicode classes leading into different states).
+    pub fn new(automaton: &Automaton, emit_terminal_branch: bool) -> Option<Self> {
+        let mut sets = vec![sorted(automaton.start_states().to_vec())];
+        let mut indices = HashMap::new();
+        indices.insert(sets[0].clone(), 0);
+
+        let mut steps = Vec::new();
+        while steps.len() < sets.len() {
+            if sets.len() > automaton.state_count() {
+                return None;
+            }
+
+            let transitions = sets[steps.len()]
+                .iter()
+                .flat_map(|state_id| &automaton.get_state(*state_id).transitions)
+                .collect::<Vec<_>>();
+
+            let mut index_of = |targets: Vec<usize>| {
+                let targets = sorted(targets);
+                match indices.get(&targets) {
+                    Some(index) => *index,
+                    None => {
+                        indices.insert(targets.clone(), sets.len());
+                        sets.push(targets);
+                        sets.len() - 1
+                    }
+                }
+            };
+
+            let conditions = transitions
+                .iter()
+                .map(|transition| transition.condition.clone())
+                .collect::<Vec<_>>();
+            let targets = transitions
+                .iter()
+                .map(|transition| transition.next_state_id)
+                .collect::<Vec<_>>();
+
+            let step = if transitions.is_empty() {
+                Step::Fail
+            } else if let Some(arms) = transition_arms(transitions.iter().copied()) {
+                Step::Ranges(
+                    arms.into_iter()
+                        .map(|arm| (arm.ranges, index_of(arm.targets)))
+                        .collect(),
+                )
+            } else if conditions
+                .iter()
+                .all(|condition| *condition == conditions[0])
+            {
+                Step::Conditions(vec![conditions[0].clone()], index_of(targets))
+            } else if targets.iter().all(|target| *target == targets[0]) {
+                Step::Conditions(conditions, index_of(vec![targets[0]]))
+            } else {
+                return None;
+            };
+            steps.push(step);
+        }
+
+        let states = sets
+            .into_iter()
+            .zip(steps)
+            .enumerate()
+            .map(|(index, (members, step))| {
+                let ident = match members.as_slice() {
+                    [state_id] => format!("State{}", state_id),
+                    _ if index == 0 => "Start".to_string(),
+                    _ => format!(
+                        "States{}",
+                        members
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join("_")
+                    ),
+                };
+                let terminal_branch = automaton
+                    .terminal_state_ids()
+                    .iter()
+                    .position(|terminal_state_id| members.contains(terminal_state_id));
+
+                OnePassState {
+                    ident: Ident::new(&ident, Span::call_site()),
+                    is_terminal: terminal_branch.is_some(),
+                    terminal_branch,
+                    step,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Some(Self {
+            states,
+            emit_terminal_branch,
+        })
+    }
+}
+
+fn sorted(mut state_ids: Vec<usize>) -> Vec<usize> {
+    state_ids.sort_unstable();
+    state_ids.dedup();
+    state_ids
+}
+
+impl OnePass {
+    fn reference(&self, index: usize) -> TokenStream {
+        let ident = &self.states[index].ident;
+        quote!(OnePassState::#ident)
+    }
+
+    fn emit_step(&self, step: &Step) -> TokenStream {
+        match step {
+            Step::Fail => quote!(::core::option::Option::None),
+            Step::Conditions(conditions, target) => {
+                let target = self.reference(*target);
+                if conditions.contains(&TransitionCondition::AnyCharacter) {
+                    return quote!(::core::option::Option::Some(#target));
+                }
+
+                let checkers = conditions.iter().map(emit_condition_checker);
+                quote! {
+                    if #((#checkers))||* {
+                        ::core::option::Option::Some(#target)
+                    } else {
+                        ::core::option::Option::None
+                    }
+                }
+            }
+            Step::Ranges(arms) => {
+                let arms = arms.iter().map(|(ranges, target)| {
+                    let target = self.reference(*target);
+                    let patterns = ranges.iter().map(|(start, end)| {
+                        if start == end {
+                            quote!(#start)
+                        } else {
+                            quote!(#start..=#end)
+                        }
+                    });
+                    quote!(#(#patterns)|* => ::core::option::Option::Some(#target),)
+                });
+
+                quote! {
+                    match next{
+                        #(#arms)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ToTokens for OnePass {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let idents = self.states.iter().map(|state| &state.ident);
+        let start = self.reference(0);
+        let step_arms = self.states.iter().enumerate().map(|(index, state)| {
+            let state_identifier = self.reference(index);
+            let step = self.emit_step(&state.step);
+            quote!(#state_identifier => #step,)
+        });
+        let terminal_states = (0..self.states.len())
+            .filter(|index| self.states[*index].is_terminal)
+            .map(|index| self.reference(index))
+            .collect::<Vec<_>>();
+        let is_terminal = if terminal_states.is_empty() {
+            quote!(false)
+        } else {
+            quote!(::core::matches!(self, #(#terminal_states)|*))
+        };
+        let terminal_branch = if self.emit_terminal_branch {
+            let arms = self.states.iter().enumerate().map(|(index, state)| {
+                let state_identifier = self.reference(index);
+                match state.terminal_branch {
+                    Some(branch) => {
+                        quote!(#state_identifier => ::core::option::Option::Some(#branch),)
+                    }
+                    None => quote!(#state_identifier => ::core::option::Option::None,),
+                }
+            });
+            quote! {
+                fn terminal_branch(self) -> ::core::option::Option<usize>{
+                    match self{
+                        #(#arms)*
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        tokens.append_all(quote! {
+            //the automaton is one-pass, so matching follows a single state rather than a set of them
+            #[derive(Clone, Copy)]
+            enum OnePassState{
+                #(#idents),*
+            }
+
+            impl OnePassState{
+                const START: Self = #start;
+
+                #[allow(unreachable_patterns)]
+                fn step(self, next: char) -> ::core::option::Option<Self>{
+                    match self{
+                        #(#step_arms)*
+                    }
+                }
+
+                fn is_terminal(self) -> bool{
+                    #is_terminal
+                }
+
+                #terminal_branch
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use regexlib::automata::Automaton;
+    use regexlib::parser::RegexEntry;
+
+    use super::OnePass;
+
+    fn is_one_pass(pattern: &str) -> bool {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse(pattern).unwrap());
+        automaton.simplify();
+        OnePass::new(&automaton, false).is_some()
+    }
+
+    #[test]
+    fn test_detects_one_pass() {
+        assert!(is_one_pass("abc"));
+        assert!(is_one_pass("a*b[0-9]+(x|y)?"));
+        assert!(is_one_pass("(ab)|(cd*)"));
+        assert!(is_one_pass("[a-z]+=[0-9]+"));
+        assert!(is_one_pass(r"x\p{Lu}"));
+        //the sets of states the input leads to repeat, so there are few of them
+        assert!(is_one_pass("[a-z]*z"));
+        assert!(is_one_pass("(ab)|(a[0-9]*)"));
+    }
+
+    #[test]
+    fn test_rejects_ambiguous() {
+        //whether a character is one of the last ten is only known at the end, so every combination of them is a set of states
+        assert!(!is_one_pass("[ab]*a[ab]{10}"));
+        //unicode classes are not compared, so they are taken to overlap
+        assert!(!is_one_pass(r"(\p{Lu})|(\p{Ll}b)"));
+    }
+}
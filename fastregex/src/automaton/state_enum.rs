@@ -4,16 +4,27 @@ use regexlib::automata::{Automaton, State};
 
 pub struct StateEnum {
     states: Vec<Ident>,
+    id_type: Ident,
 }
 
 impl StateEnum {
     pub fn new(automaton: &Automaton) -> Self {
+        //the smallest type holding every state id as well as the number of states, so that buffers of states and the counters next to them stay small
+        let id_type = if automaton.state_count() <= u8::MAX as usize {
+            "u8"
+        } else if automaton.state_count() <= u16::MAX as usize {
+            "u16"
+        } else {
+            "usize"
+        };
+
         Self {
             states: automaton
                 .states()
                 .iter()
                 .map(|state| Ident::new(&format!("State{}", state.id), Span::call_site()))
                 .collect(),
+            id_type: Ident::new(id_type, Span::call_site()),
         }
     }
 
@@ -30,7 +41,11 @@ impl StateEnum {
 impl ToTokens for StateEnum {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let states = &self.states;
+        let id_type = &self.id_type;
         tokens.append_all(quote! {
+            type StateId = #id_type;
+
+            #[repr(#id_type)]
             enum State{
                 #(#states),*
             }
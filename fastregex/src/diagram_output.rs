@@ -0,0 +1,54 @@
+use std::path::{Path, PathBuf};
+
+use regexlib::automata::Automaton;
+use regexlib::diagram::to_graphviz;
+
+/// Set to a directory while building to have every `matcher!` write the graphviz diagram of the
+/// automaton it compiled there, as `<crate>-<matcher>.dot` (render one with e.g.
+/// `dot -Tsvg`). A relative directory is resolved against the directory cargo runs the compiler
+/// in, which is the workspace root, so `FASTREGEX_DIAGRAM_DIR=target/diagrams` keeps them next to
+/// the build output. As with the other reports, crates that are already built must be rebuilt for
+/// their matchers to be written.
+pub const DIAGRAM_DIR_VARIABLE: &str = "FASTREGEX_DIAGRAM_DIR";
+
+pub fn write_diagram_if_requested(function_name: &str, pattern: &str, automaton: &Automaton) {
+    let directory = match std::env::var_os(DIAGRAM_DIR_VARIABLE) {
+        Some(directory) => PathBuf::from(directory),
+        None => return,
+    };
+
+    //the crate name keeps matchers of the same name in different crates apart
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "unknown".into());
+    let path = diagram_path(&directory, &crate_name, function_name);
+
+    //a diagram that can not be written should not fail the build it is only documenting
+    let written = std::fs::create_dir_all(&directory)
+        .and_then(|_| std::fs::write(&path, to_graphviz(automaton, pattern)));
+    if let Err(err) = written {
+        eprintln!(
+            "fastregex: matcher `{}`: could not write diagram to {}: {}",
+            function_name,
+            path.display(),
+            err
+        );
+    }
+}
+
+fn diagram_path(directory: &Path, crate_name: &str, function_name: &str) -> PathBuf {
+    directory.join(format!("{}-{}.dot", crate_name, function_name))
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::diagram_path;
+
+    #[test]
+    fn test_diagram_path() {
+        assert_eq!(
+            diagram_path(Path::new("target/diagrams"), "my_crate", "is_ip"),
+            Path::new("target/diagrams/my_crate-is_ip.dot")
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::{bracketed, Ident, LitStr, Token};
+
+/// A `fragment!` invocation: a name, and the pattern it stands for.
+pub struct FragmentDeclaration {
+    pub name: Ident,
+    pub pattern: LitStr,
+    //the pattern with the fragments it uses filled in, once they are
+    filled: Option<String>,
+}
+
+impl Parse for FragmentDeclaration {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let pattern = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        Ok(Self {
+            name,
+            pattern,
+            filled: None,
+        })
+    }
+}
+
+impl UsesFragments for FragmentDeclaration {
+    fn written_patterns(&self) -> Vec<&LitStr> {
+        vec![&self.pattern]
+    }
+
+    fn fill_fragments(&mut self, fragments: &Fragments) -> syn::Result<()> {
+        let (filled, _) = fragments.interpolate(&self.pattern)?;
+        self.filled = Some(filled);
+        Ok(())
+    }
+}
+
+impl FragmentDeclaration {
+    /// Declares the fragment as a macro of the same name, which hands its pattern to the macro
+    /// invocations whose patterns use it (see [`parse_with_fragments`]), and as a constant holding
+    /// its pattern. The fragments it uses must have been filled in.
+    pub fn declare(self) -> TokenStream {
+        let name = self.name;
+        let pattern = self
+            .filled
+            .expect("Attempted to declare a fragment before filling in the fragments it uses.");
+
+        quote! {
+            #[allow(unused_macros)]
+            macro_rules! #name {
+                (($($callback:tt)*) @fragments [$($resolved:tt)*] $($input:tt)*) => {
+                    $($callback)*! { @fragments [$($resolved)* #name = #pattern,] $($input)* }
+                };
+            }
+
+            #[allow(dead_code)]
+            const #name: &str = #pattern;
+        }
+    }
+}
+
+/// Something declared with regexes that may use fragments, which are filled in once the
+/// fragments they use are known.
+pub trait UsesFragments: Sized {
+    /// The patterns as written, with their `{NAME}`s not filled in yet.
+    fn written_patterns(&self) -> Vec<&LitStr>;
+
+    fn fill_fragments(&mut self, fragments: &Fragments) -> syn::Result<()>;
+}
+
+/// The patterns of the fragments a macro invocation's patterns use, as far as they have been
+/// handed to it.
+#[derive(Default)]
+pub struct Fragments {
+    patterns: BTreeMap<String, String>,
+}
+
+impl Fragments {
+    //the `@fragments [NAME = "pattern", ...]` the fragment macros put before a macro's own input
+    fn parse_resolved(input: ParseStream) -> syn::Result<Self> {
+        let mut fragments = Fragments::default();
+        if input.parse::<Option<Token![@]>>()?.is_none() {
+            return Ok(fragments);
+        }
+
+        let marker = input.parse::<Ident>()?;
+        if marker != "fragments" {
+            return Err(syn::Error::new(marker.span(), "Expected `@fragments`"));
+        }
+        let resolved;
+        bracketed!(resolved in input);
+        while !resolved.is_empty() {
+            let name = resolved.parse::<Ident>()?;
+            resolved.parse::<Token![=]>()?;
+            let pattern = resolved.parse::<LitStr>()?;
+            resolved.parse::<Option<Token![,]>>()?;
+            fragments.patterns.insert(name.to_string(), pattern.value());
+        }
+
+        Ok(fragments)
+    }
+
+    //the first fragment used by one of `patterns` that has not been handed over yet, named with the span of the pattern using it
+    fn first_missing(&self, patterns: &[&LitStr]) -> Option<Ident> {
+        patterns.iter().find_map(|pattern| {
+            let value = pattern.value();
+            let missing = fragment_names(&value)
+                .into_iter()
+                .find(|name| !self.patterns.contains_key(name))?;
+            Some(Ident::new(&missing, pattern.span()))
+        })
+    }
+
+    /// Replaces each `{NAME}` in `pattern` with the pattern of the fragment named `NAME`, in a
+    /// non-capturing group `(?:...)` of its own so that it is matched as a unit without changing
+    /// the numbers of the groups after it. Braces that hold a number, like the `{3}` of a
+    /// repetition, braces that are escaped or belong to an escape, like `\{` or `\p{Lu}`, and
+    /// braces inside a bracketed class, a `\Q...\E` quote or a comment of verbose mode are left as
+    /// they are.
+    ///
+    /// Returns the interpolated pattern and the fragments filled into it, in the order they
+    /// appear.
+    pub fn interpolate(&self, pattern: &LitStr) -> syn::Result<(String, Vec<FilledFragment>)> {
+        interpolate(&pattern.value(), &self.patterns)
+            .map_err(|error| syn::Error::new(pattern.span(), error))
+    }
+}
+
+/// Parses the input of a macro whose patterns may use fragments, and fills them in.
+///
+/// Procedural macros can not see each other's output, so a fragment is handed to the patterns
+/// using it by the macro `fragment!` declares for it: when a pattern uses a fragment that has
+/// not been handed over yet, the result is an invocation of that fragment's macro, which invokes
+/// `callback` (the path of the macro being expanded) again with the same input and the fragment's
+/// pattern added to those before it. Fragments thus follow the scoping of `macro_rules!` macros.
+///
+/// The error is the tokens to expand to instead: either that invocation or a compile error.
+pub fn parse_with_fragments<Declaration: UsesFragments>(
+    input: TokenStream,
+    callback: TokenStream,
+    parse: impl FnOnce(ParseStream) -> syn::Result<Declaration>,
+) -> Result<Declaration, TokenStream> {
+    let (fragments, own_input) = (|input: ParseStream| {
+        let fragments = Fragments::parse_resolved(input)?;
+        Ok((fragments, input.parse::<TokenStream>()?))
+    })
+    .parse2(input)
+    .map_err(|error| error.to_compile_error())?;
+
+    let mut declaration = parse
+        .parse2(own_input.clone())
+        .map_err(|error| error.to_compile_error())?;
+
+    if let Some(missing) = fragments.first_missing(&declaration.written_patterns()) {
+        let resolved = fragments.patterns.iter().map(|(name, pattern)| {
+            let name = Ident::new(name, Span::call_site());
+            quote!(#name = #pattern,)
+        });
+        return Err(quote! {
+            #missing! { (#callback) @fragments [#(#resolved)*] #own_input }
+        });
+    }
+
+    declaration
+        .fill_fragments(&fragments)
+        .map_err(|error| error.to_compile_error())?;
+    Ok(declaration)
+}
+
+/// A fragment [`Fragments::interpolate`] filled into a pattern.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FilledFragment {
+    /// The bytes of the interpolated pattern the fragment's `(?:...)` group takes up.
+    pub filled: Range<usize>,
+    /// The bytes of the `{NAME}` it was filled in for, in the pattern as written.
+    pub written: Range<usize>,
+}
+
+fn interpolate(
+    pattern: &str,
+    fragments: &BTreeMap<String, String>,
+) -> Result<(String, Vec<FilledFragment>), String> {
+    let mut interpolated = String::with_capacity(pattern.len());
+    let mut filled = Vec::new();
+    let mut copied = 0;
+    for (written, name) in fragment_uses(pattern) {
+        interpolated.push_str(&pattern[copied..written.start]);
+        copied = written.end;
+
+        let fragment = fragments.get(name).ok_or_else(|| {
+            format!(
+                "{{{name}}} does not name a fragment declared with fragment! before this pattern"
+            )
+        })?;
+        let filled_start = interpolated.len();
+        interpolated.push_str("(?:");
+        interpolated.push_str(fragment);
+        interpolated.push(')');
+        filled.push(FilledFragment {
+            filled: filled_start..interpolated.len(),
+            written,
+        });
+    }
+    interpolated.push_str(&pattern[copied..]);
+
+    Ok((interpolated, filled))
+}
+
+//the names of the fragments `pattern` uses, in the order they first appear
+fn fragment_names(pattern: &str) -> Vec<String> {
+    let mut names = Vec::<String>::new();
+    for (_, name) in fragment_uses(pattern) {
+        if !names.iter().any(|named| named == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+//each `{NAME}` of a pattern: the bytes it takes up, and the name in it; the scan follows the classes, quotes, comments and groups of the pattern as the tokenizer reads them, since braces in the first three are not syntax
+fn fragment_uses(pattern: &str) -> Vec<(Range<usize>, &str)> {
+    let mut uses = Vec::new();
+    //classes nest, as in `[[a-z]_]`
+    let mut class_depth = 0usize;
+    //whether the `x` flag is set, and whether it was set outside each open group
+    let mut verbose = false;
+    let mut enclosing = Vec::new();
+
+    let mut remaining = pattern;
+    while let Some(char) = remaining.chars().next() {
+        let offset = pattern.len() - remaining.len();
+        remaining = &remaining[char.len_utf8()..];
+
+        match char {
+            '\\' => {
+                //a `\Q` quotes the rest of the pattern up to a `\E`
+                if let (0, Some(quoted)) = (class_depth, remaining.strip_prefix('Q')) {
+                    remaining = quoted.find("\\E").map_or("", |end| &quoted[end + 2..]);
+                    continue;
+                }
+
+                let mut chars = remaining.chars();
+                if let Some(escaped) = chars.next() {
+                    remaining = chars.as_str();
+
+                    //the braces of an escape like `\p{Lu}` belong to it
+                    if escaped.is_alphabetic() && remaining.starts_with('{') {
+                        let end = remaining.find('}').map_or(remaining.len(), |end| end + 1);
+                        remaining = &remaining[end..];
+                    }
+                }
+            }
+            '[' => class_depth += 1,
+            ']' if class_depth > 0 => class_depth -= 1,
+            _ if class_depth > 0 => {}
+            '#' if verbose => remaining = remaining.find('\n').map_or("", |end| &remaining[end..]),
+            '(' => match set_flags(remaining, verbose) {
+                Some((set, after, true)) => {
+                    enclosing.push(verbose);
+                    verbose = set;
+                    remaining = after;
+                }
+                Some((set, after, false)) => {
+                    verbose = set;
+                    remaining = after;
+                }
+                None => enclosing.push(verbose),
+            },
+            ')' => {
+                if let Some(outside) = enclosing.pop() {
+                    verbose = outside;
+                }
+            }
+            '{' => {
+                if let Some((name, _)) = remaining.split_once('}') {
+                    if is_identifier(name) {
+                        uses.push((offset..offset + name.len() + 2, name));
+                        remaining = &remaining[name.len() + 1..];
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    uses
+}
+
+//for a `(?flags)` or the opening of a `(?flags:...)` group right after a `(`: whether it leaves the `x` flag set, the rest of the pattern after it, and whether it opens a group
+fn set_flags(after_paren: &str, verbose: bool) -> Option<(bool, &str, bool)> {
+    let flags = after_paren.strip_prefix('?')?;
+    let end = flags.find([')', ':'])?;
+    let letters = &flags[..end];
+    if !letters
+        .chars()
+        .all(|letter| letter.is_ascii_alphabetic() || letter == '-')
+    {
+        return None;
+    }
+
+    let mut set = verbose;
+    let mut enabled = true;
+    for letter in letters.chars() {
+        match letter {
+            '-' => enabled = false,
+            'x' => set = enabled,
+            _ => {}
+        }
+    }
+    Some((set, &flags[end + 1..], flags[end..].starts_with(':')))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|char| char.is_alphanumeric() || char == '_')
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::{fragment_names, interpolate, FilledFragment};
+
+    #[test]
+    fn test_interpolate() {
+        let fragments = BTreeMap::from([("TEST_DIGITS".to_string(), "[0-9]+".to_string())]);
+
+        let pattern = |pattern| interpolate(pattern, &fragments).unwrap().0;
+        assert_eq!(
+            pattern("{TEST_DIGITS}-{TEST_DIGITS}"),
+            "(?:[0-9]+)-(?:[0-9]+)"
+        );
+        assert_eq!(pattern("a{3}b{2,}"), "a{3}b{2,}");
+        assert_eq!(pattern("\\{TEST_DIGITS}"), "\\{TEST_DIGITS}");
+        assert_eq!(pattern("ä{TEST_DIGITS}"), "ä(?:[0-9]+)");
+        assert_eq!(pattern("\\p{Lu}\\P{Lu"), "\\p{Lu}\\P{Lu");
+        assert!(interpolate("{TEST_UNDECLARED}", &fragments).is_err());
+
+        assert_eq!(
+            interpolate("ä{TEST_DIGITS}-{TEST_DIGITS}", &fragments)
+                .unwrap()
+                .1,
+            vec![
+                FilledFragment {
+                    filled: 2..12,
+                    written: 2..15,
+                },
+                FilledFragment {
+                    filled: 13..23,
+                    written: 16..29,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_braces_that_are_not_fragments() {
+        let fragments = BTreeMap::from([("x".to_string(), "[0-9]".to_string())]);
+
+        let pattern = |pattern| interpolate(pattern, &fragments).unwrap().0;
+        //a class, including one nested in another, and one whose `]` is escaped
+        assert_eq!(pattern("[{x}]+{x}"), "[{x}]+(?:[0-9])");
+        assert_eq!(pattern("[[a-z]{x}]{x}"), "[[a-z]{x}](?:[0-9])");
+        assert_eq!(pattern(r"[\]{x}]{x}"), r"[\]{x}](?:[0-9])");
+        //a quote, with or without its `\E`
+        assert_eq!(pattern(r"\Q{x}\E{x}"), r"\Q{x}\E(?:[0-9])");
+        assert_eq!(pattern(r"{x}\Q{x}"), r"(?:[0-9])\Q{x}");
+        //a comment, in verbose mode only and up to the end of its line
+        assert_eq!(pattern("(?x)# {x}\n{x}"), "(?x)# {x}\n(?:[0-9])");
+        assert_eq!(pattern("(?x:a)#{x}"), "(?x:a)#(?:[0-9])");
+        assert_eq!(pattern("(?x)(?-x)#{x}"), "(?x)(?-x)#(?:[0-9])");
+        assert_eq!(pattern("#{x}"), "#(?:[0-9])");
+    }
+
+    #[test]
+    fn test_fragment_names() {
+        assert_eq!(
+            fragment_names(r"{A}x{B}\{C}\p{D}{A}{3}"),
+            vec!["A".to_string(), "B".to_string()]
+        );
+    }
+}
@@ -1,91 +1,2027 @@
 use proc_macro::TokenStream;
-use proc_macro2::Span;
+use proc_macro2::{Ident, Span};
 
-use quote::quote;
-use syn::{parse_macro_input, Lit, LitInt, LitStr};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, Parser};
+use syn::{Lit, LitByteStr, LitStr};
 
+use regexlib::analysis::unreachable_branches;
 use regexlib::automata::Automaton;
-use regexlib::parser::RegexEntry;
+use regexlib::normalization::to_nfc;
+use regexlib::parser::{ParseOptions, RegexEntry};
+use regexlib::{from_glob, from_ilike, from_like};
 
-use crate::automaton::EmittableAutomaton;
-use crate::matcher_declaration::MatcherDeclaration;
+use crate::automaton::{emit_capture_program, EmittableAutomaton};
+use crate::diagram_output::write_diagram_if_requested;
+use crate::fragment::{parse_with_fragments, FragmentDeclaration};
+use crate::matcher_declaration::{
+    AssertValidDeclaration, MatcherDeclaration, MatcherSetDeclaration, MatchersDeclaration,
+    PatternSyntax,
+};
+use crate::size_report::SizeReport;
+use crate::timing_report::TimingReport;
+use crate::warning::warning;
 
 mod automaton;
+mod diagram_output;
+mod fragment;
 mod matcher_declaration;
+mod pattern_source;
+mod size_report;
+mod timing_report;
+mod warning;
 
+/// Declares a function matching its input against a regex, along with a module of the same name
+/// holding the automaton behind it. The name can be preceded by attributes, which go on the
+/// function, and a visibility, which the function and its module share:
+///
+/// ```ignore
+/// matcher!(
+///     /// Whether the input is a lowercase word.
+///     #[inline]
+///     pub(crate) is_word,
+///     "[a-z]+"
+/// );
+/// ```
+///
+/// Writing `const` before the name (e.g., `matcher!(pub const is_word, "[a-z]+")`) makes the
+/// function a `const fn`, so that it can be used in const contexts such as static assertions.
 #[proc_macro]
 pub fn matcher(input: TokenStream) -> TokenStream {
-    let matcher_declaration = parse_macro_input!(input as MatcherDeclaration);
-    let regex = match RegexEntry::parse(&matcher_declaration.regex) {
-        Ok(regex) => regex,
-        Err(e) => {
-            return syn::parse::Error::new(
-                matcher_declaration.regex_span,
-                format!("Failed to parse as regex: {}", e),
-            )
-            .to_compile_error()
-            .into();
-        }
+    match parse_with_fragments(
+        input.into(),
+        quote!(::fastregex::matcher),
+        MatcherDeclaration::parse,
+    ) {
+        Ok(matcher_declaration) => generate_matcher(matcher_declaration).into(),
+        Err(tokens) => tokens.into(),
+    }
+}
+
+/// Like `matcher!`, but the pattern is a shell glob (e.g., `*.tar.gz`) rather than a regex. See
+/// [`regexlib::from_glob`] for the syntax.
+#[proc_macro]
+pub fn glob_matcher(input: TokenStream) -> TokenStream {
+    generate_matcher_with_syntax(input, PatternSyntax::Glob)
+}
+
+/// Like `matcher!`, but the pattern is an SQL `LIKE` pattern (e.g., `abc%`). The character that
+/// escapes `%` and `_` is set with the `escape` option, as in `LIKE 'abc\%' ESCAPE '\'`:
+///
+/// ```ignore
+/// like_matcher!(is_percentage, "%\\%", escape = '\\');
+/// ```
+#[proc_macro]
+pub fn like_matcher(input: TokenStream) -> TokenStream {
+    generate_matcher_with_syntax(
+        input,
+        PatternSyntax::Like {
+            case_insensitive: false,
+            escape: None,
+        },
+    )
+}
+
+/// Like [`like_matcher!`], but case-insensitive, as SQL's `ILIKE`. See [`regexlib::from_ilike`] for
+/// which characters are matched regardless of case.
+#[proc_macro]
+pub fn ilike_matcher(input: TokenStream) -> TokenStream {
+    generate_matcher_with_syntax(
+        input,
+        PatternSyntax::Like {
+            case_insensitive: true,
+            escape: None,
+        },
+    )
+}
+
+/// Declares several matchers at once, each as if by `matcher!`:
+///
+/// ```ignore
+/// matchers! {
+///     { max_states: 4096 }
+///     is_get => "GET",
+///     is_method => "(GET)|(POST)" { branch: true },
+/// }
+/// ```
+///
+/// The options block before the first matcher applies to all of them, and a matcher's own
+/// options block overrides it. Each name can be preceded by attributes and a visibility, as in
+/// `matcher!`.
+#[proc_macro]
+pub fn matchers(input: TokenStream) -> TokenStream {
+    let matchers_declaration = match parse_with_fragments(
+        input.into(),
+        quote!(::fastregex::matchers),
+        MatchersDeclaration::parse,
+    ) {
+        Ok(matchers_declaration) => matchers_declaration,
+        Err(tokens) => return tokens.into(),
+    };
+    matchers_declaration
+        .matchers
+        .into_iter()
+        .map(generate_matcher)
+        .collect::<proc_macro2::TokenStream>()
+        .into()
+}
+
+/// Declares a matcher for several patterns at once, which reports which of them the whole input
+/// matches, like `regex::RegexSet`:
+///
+/// ```ignore
+/// matcher_set!(classify, "[0-9]+", "[a-z]+", "[0-9a-z]+");
+/// assert_eq!(classify("abc"), [false, true, true]);
+/// ```
+///
+/// The patterns share one automaton, so the input is read once however many patterns there are.
+/// The name can be preceded by a visibility, which the function and its module share as a
+/// matcher's do, and the patterns can be followed by the `max_states` and `max_repetition` options,
+/// as flags or in an options block. `max_states` limits the states of all the patterns together:
+///
+/// ```ignore
+/// matcher_set!(pub(crate) fields, "[a-z]{1500}", "[0-9]+", { max_repetition: 1500, max_states: 4096 });
+/// ```
+#[proc_macro]
+pub fn matcher_set(input: TokenStream) -> TokenStream {
+    match parse_with_fragments(
+        input.into(),
+        quote!(::fastregex::matcher_set),
+        MatcherSetDeclaration::parse,
+    ) {
+        Ok(matcher_set_declaration) => generate_matcher_set(matcher_set_declaration).into(),
+        Err(tokens) => tokens.into(),
+    }
+}
+
+/// Declares a named fragment of a regex, which the patterns of the matchers declared after it can
+/// use as `{NAME}`:
+///
+/// ```ignore
+/// fragment!(OCTET, r"(25[0-5])|(2[0-4]\d)|(1?\d?\d)");
+/// matcher!(is_ipv4, r"{OCTET}([.]{OCTET}){3}");
+/// ```
+///
+/// The fragment is filled in as a non-capturing group `(?:...)`, so it adds no group of its own
+/// (the groups written in it are numbered like any other), and can itself use the fragments
+/// declared before it. It is declared as a `macro_rules!` macro of the same name, which hands its
+/// pattern to the macros using it, so it is in scope wherever that macro would be: after it in
+/// the same module and the modules declared after it there, and after the module declaring it if
+/// that module is marked `#[macro_use]`. It is also declared as a constant holding its pattern,
+/// so that two fragments of the same name in one module are an error.
+#[proc_macro]
+pub fn fragment(input: TokenStream) -> TokenStream {
+    match parse_with_fragments(
+        input.into(),
+        quote!(::fastregex::fragment),
+        FragmentDeclaration::parse,
+    ) {
+        Ok(fragment_declaration) => fragment_declaration.declare().into(),
+        Err(tokens) => tokens.into(),
+    }
+}
+
+/// Checks at compile time that a pattern parses and stays within the limits, as `matcher!` would,
+/// without generating a matcher for it. It takes the same options as `matcher!`:
+///
+/// ```ignore
+/// regex_assert_valid!("[a-z]{1500}", max_states = 2000, max_repetition = 1500);
+/// ```
+///
+/// This is for patterns that are matched by something else, such as `regexlib`'s runtime engine.
+#[proc_macro]
+pub fn regex_assert_valid(input: TokenStream) -> TokenStream {
+    let AssertValidDeclaration(matcher_declaration) = match parse_with_fragments(
+        input.into(),
+        quote!(::fastregex::regex_assert_valid),
+        AssertValidDeclaration::parse,
+    ) {
+        Ok(assert_valid_declaration) => assert_valid_declaration,
+        Err(tokens) => return tokens.into(),
     };
+    match build_automaton(&matcher_declaration, &mut TimingReport::start()) {
+        Ok(built) => built.warnings.into(),
+        Err(error) => error.into(),
+    }
+}
+
+//for the macros that declare a single matcher whose pattern is not a regex
+fn generate_matcher_with_syntax(input: TokenStream, syntax: PatternSyntax) -> TokenStream {
+    match MatcherDeclaration::parse_with_syntax(syntax).parse(input) {
+        Ok(matcher_declaration) => generate_matcher(matcher_declaration).into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn generate_matcher(matcher_declaration: MatcherDeclaration) -> proc_macro2::TokenStream {
+    //normalizing allocates, which a const fn can not do
+    if matcher_declaration.is_const && matcher_declaration.normalize_nfc {
+        return syn::parse::Error::new(
+            matcher_declaration.function_name.span(),
+            "A const matcher can not normalize its input, so it can not have the \"nfc\" option",
+        )
+        .to_compile_error();
+    }
 
-    let automaton = {
-        let mut automaton = Automaton::from_regex(regex);
-        automaton.simplify();
-        automaton
+    let mut timing_report = TimingReport::start();
+    let BuiltAutomaton {
+        automaton,
+        program_automaton,
+        capture_names,
+        has_start_anchor,
+        has_end_anchor,
+        normalized_pattern,
+        warnings,
+    } = match build_automaton(&matcher_declaration, &mut timing_report) {
+        Ok(built) => built,
+        Err(error) => return error,
+    };
+
+    let no_match = if matcher_declaration.report_branch {
+        quote!(None)
+    } else {
+        quote!(false)
     };
 
     let prefix_check = if automaton.prefix().is_empty() {
         quote!()
+    } else if matcher_declaration.bytes {
+        let prefix_literal = literal_bytes(automaton.prefix(), true);
+
+        quote! {
+            let bytes = match bytes.strip_prefix(#prefix_literal){
+                ::core::option::Option::Some(bytes) => bytes,
+                ::core::option::Option::None => return failed(),
+            };
+        }
     } else {
         let prefix_literal = Lit::Str(LitStr::new(automaton.prefix(), Span::call_site()));
-        let prefix_length_literal = Lit::Int(LitInt::new(
-            &format!("{}", automaton.prefix().len()),
-            Span::call_site(),
-        ));
 
+        //splitting at the prefix's length would panic on an input with a character straddling that offset
+        quote! {
+            let string = match string.strip_prefix(#prefix_literal){
+                ::core::option::Option::Some(string) => string,
+                ::core::option::Option::None => return failed(),
+            };
+        }
+    };
+    //byte matchers' input may not be utf-8, so they leave the classes to the automaton
+    let prefix_class_check = if matcher_declaration.bytes {
+        quote!()
+    } else {
+        emit_prefix_class_check(automaton.prefix_classes(), quote!(failed()))
+    };
+    let prefix_check = quote! {
+        #prefix_check
+        #prefix_class_check
+    };
+
+    //the generated code can not depend on anything the macro's caller does not, so this requires the caller to depend on regexlib
+    let input_normalization = if matcher_declaration.normalize_nfc {
         quote! {
-            if string.len() < #prefix_length_literal{
-                return false;
+            let string = ::regexlib::normalization::to_nfc(string);
+            let string: &str = &string;
+        }
+    } else {
+        quote!()
+    };
+
+    //byte matchers are given the bytes directly
+    let (input_type, input_parameter, input_bytes) = if matcher_declaration.bytes {
+        (quote!([u8]), quote!(bytes: &[u8]), quote!())
+    } else {
+        (
+            quote!(str),
+            quote!(string: &str),
+            quote!(let bytes = str::as_bytes(string);),
+        )
+    };
+
+    let input_argument = if matcher_declaration.bytes {
+        quote!(bytes)
+    } else {
+        quote!(string)
+    };
+
+    //the anchored searches for the shortest match and longest prefix have to strip the prefix themselves, since they do not return the matcher's type on failure
+    let anchored_prefix_check = if automaton.prefix().is_empty() {
+        quote!()
+    } else {
+        let prefix_literal = Lit::Str(LitStr::new(automaton.prefix(), Span::call_site()));
+
+        quote! {
+            let string = match string.strip_prefix(#prefix_literal){
+                ::core::option::Option::Some(string) => string,
+                ::core::option::Option::None => return ::core::option::Option::None,
+            };
+        }
+    };
+    let anchored_prefix_class_check = emit_prefix_class_check(
+        automaton.prefix_classes(),
+        quote!(::core::option::Option::None),
+    );
+    let anchored_prefix_check = quote! {
+        #anchored_prefix_check
+        #anchored_prefix_class_check
+    };
+    let prefix_length = automaton.prefix().len();
+    let automaton_prefix = automaton.prefix().to_string();
+
+    let size_report = SizeReport::new(&automaton);
+    write_diagram_if_requested(
+        &matcher_declaration.function_name.to_string(),
+        &matcher_declaration.regex,
+        &automaton,
+    );
+    let emittable_automata = EmittableAutomaton::new(automaton, matcher_declaration.report_branch);
+    let emittable_automata = if matcher_declaration.is_const {
+        emittable_automata.emitting_const_automaton()
+    } else {
+        emittable_automata
+    };
+    let emittable_automata = if matcher_declaration.bytes {
+        emittable_automata.reading_bytes()
+    } else {
+        emittable_automata
+    };
+
+    //a struct shares the namespace of the module, so a struct type's module is named in snake case instead
+    let (function_name, struct_name) = if matcher_declaration.struct_type {
+        let struct_name = matcher_declaration.function_name.clone();
+        (
+            Ident::new(&to_snake_case(&struct_name.to_string()), struct_name.span()),
+            Some(struct_name),
+        )
+    } else {
+        (matcher_declaration.function_name.clone(), None)
+    };
+    let visibility = &matcher_declaration.visibility;
+    //the attributes go on the function named after the matcher, which is the `_branch` function's wrapper for branch matchers
+    let attributes = &matcher_declaration.attributes;
+    let matching_function_attributes = if matcher_declaration.report_branch {
+        quote!()
+    } else {
+        quote!(#(#attributes)*)
+    };
+    let (matching_function_name, return_type, result) = if matcher_declaration.report_branch {
+        (
+            format_ident!("{}_branch", function_name),
+            quote!(::core::option::Option<usize>),
+            quote!(from_automaton.terminal_branch()),
+        )
+    } else {
+        (
+            function_name.clone(),
+            quote!(bool),
+            quote!(from_automaton.is_terminated()),
+        )
+    };
+
+    //decoding by hand lets the common ASCII case skip the general decoder, which is shared by every matcher; a byte matcher's automaton reads each byte as the character of the same value instead (see RegexEntry::to_utf8_bytes)
+    let decode_multi_byte_char = if matcher_declaration.bytes {
+        quote! {
+            {
+                index += 1;
+                byte as char
             }
+        }
+    } else {
+        quote! {
+            ::fastregex_rt::decode_multi_byte_char(bytes, &mut index)
+        }
+    };
 
-            let (prefix, string) = string.split_at(#prefix_length_literal);
+    //the automaton can only follow a run of literals when its first state is the only active one, so the run is checked in one comparison
+    let literal_chain_bytes = if matcher_declaration.bytes {
+        quote!(literals)
+    } else {
+        quote!(str::as_bytes(literals))
+    };
+    let literal_chain_check = if emittable_automata.has_literal_chains() {
+        quote! {
+            if from_automaton.valid_state_count == 1 {
+                if let ::core::option::Option::Some((literals, end_state)) = from_automaton.states[0].literal_chain(){
+                    if !bytes[index..].starts_with(#literal_chain_bytes) {
+                        return failed();
+                    }
+
+                    index += literals.len();
+                    from_automaton.states[0] = end_state;
+                    continue;
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
 
-            if prefix != #prefix_literal{
-                return false;
+    //a search is done as soon as any match ends, since its automaton never fails and the rest of the input can not undo the match; a match that ends in a `$` is undone by reading past it, though
+    let stops_at_match = matcher_declaration.search && !has_end_anchor;
+    let search_check = if stops_at_match {
+        quote! {
+            if from_automaton.is_terminated(){
+                return #result;
             }
         }
+    } else {
+        quote!()
     };
 
-    let emittable_automata = EmittableAutomaton::new(automaton);
+    //a one-pass automaton has a single state after each character, so matching follows it without the scratch's sets of states
+    let (scratch_parameter, matches_loop) = if emittable_automata.is_one_pass() {
+        let one_pass_result = if matcher_declaration.report_branch {
+            quote!(state.terminal_branch())
+        } else {
+            quote!(state.is_terminal())
+        };
+        let one_pass_search_check = if stops_at_match {
+            quote! {
+                if state.is_terminal(){
+                    return #one_pass_result;
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        (
+            format_ident!("_scratch"),
+            quote! {
+                let mut state = OnePassState::START;
+
+                while index < bytes.len(){
+                    #one_pass_search_check
+
+                    let byte = bytes[index];
+                    let char = if byte < 0x80 {
+                        index += 1;
+                        byte as char
+                    } else {
+                        #decode_multi_byte_char
+                    };
+
+                    state = match state.step(char){
+                        ::core::option::Option::Some(state) => state,
+                        ::core::option::Option::None => return failed(),
+                    };
+                }
+
+                #one_pass_result
+            },
+        )
+    } else {
+        (
+            format_ident!("scratch"),
+            quote! {
+                //a scratch from an earlier call still holds that call's states
+                scratch.automaton_a.reset();
+                let scratch_space = &mut scratch.scratch_space;
+                let mut from_automaton = &mut scratch.automaton_a;
+                let mut to_automaton = &mut scratch.automaton_b;
+
+                while index < bytes.len(){
+                    #search_check
+                    #literal_chain_check
+
+                    let byte = bytes[index];
+                    let char = if byte < 0x80 {
+                        index += 1;
+                        byte as char
+                    } else {
+                        #decode_multi_byte_char
+                    };
+
+                    to_automaton.advance_from(from_automaton, char, scratch_space);
+
+                    if(to_automaton.is_failed()){
+                        return failed();
+                    }
+
+                    ::core::mem::swap(to_automaton, from_automaton);
+                }
+
+                //the swap leaves the states after the last character in from_automaton
+                #result
+            },
+        )
+    };
+
+    let matcher_branch_method = if matcher_declaration.report_branch {
+        quote! {
+            /// Like the matcher's `_branch` function, reusing this matcher's scratch.
+            pub fn matching_branch(&mut self, string: impl ::core::convert::AsRef<#input_type>) -> ::core::option::Option<usize>{
+                matches(::core::convert::AsRef::as_ref(&string), &mut self.scratch)
+            }
+        }
+    } else {
+        quote!()
+    };
+    let matcher_is_match_result = if matcher_declaration.report_branch {
+        quote!(.is_some())
+    } else {
+        quote!()
+    };
 
-    let function_name = matcher_declaration.function_name;
-    quote!(
-        fn #function_name(string: impl ::core::convert::AsRef<str>) -> bool{
+    //the offsets of a normalized input do not correspond to the caller's input, so nfc matchers have no shortest match or longest prefix, nor any of the other functions returning offsets into the input; byte matchers leave them out too, since they are written for strs
+    //the automaton only passes a `$` at the end of the whole input, not at the end of a start of it
+    let has_offsets =
+        !matcher_declaration.normalize_nfc && !matcher_declaration.bytes && !has_end_anchor;
+    let (shortest_match_function, shortest_match_wrapper) = if !has_offsets {
+        (quote!(), quote!())
+    } else {
+        let shortest_match_function_name = format_ident!("{}_shortest_match", function_name);
+        let longest_prefix_function_name = format_ident!("{}_longest_prefix", function_name);
+        (
+            quote! {
+                /// The length of the shortest start of the input that matches, or `None` if none
+                /// does. Unlike [`matches`], this stops at the first character after which the
+                /// pattern is matched, without reading the rest of the input.
+                pub fn shortest_match(string: &str, scratch: &mut Scratch) -> ::core::option::Option<usize>{
+                    #anchored_prefix_check
+                    let bytes = str::as_bytes(string);
+                    let mut index = 0;
+
+                    scratch.automaton_a.reset();
+                    let scratch_space = &mut scratch.scratch_space;
+                    let mut from_automaton = &mut scratch.automaton_a;
+                    let mut to_automaton = &mut scratch.automaton_b;
+
+                    loop{
+                        if from_automaton.is_terminated(){
+                            return ::core::option::Option::Some(#prefix_length + index);
+                        }
+
+                        if index == bytes.len(){
+                            return ::core::option::Option::None;
+                        }
+
+                        let byte = bytes[index];
+                        let char = if byte < 0x80 {
+                            index += 1;
+                            byte as char
+                        } else {
+                            #decode_multi_byte_char
+                        };
+
+                        to_automaton.advance_from(from_automaton, char, scratch_space);
+
+                        if(to_automaton.is_failed()){
+                            return ::core::option::Option::None;
+                        }
+
+                        ::core::mem::swap(to_automaton, from_automaton);
+                    }
+                }
+
+                /// The length of the longest start of the input that matches, or `None` if none
+                /// does, which is what a parser consuming a token does. This stops at the first
+                /// character after which the pattern can not match anymore.
+                pub fn longest_prefix(string: &str, scratch: &mut Scratch) -> ::core::option::Option<usize>{
+                    #anchored_prefix_check
+                    let bytes = str::as_bytes(string);
+                    let mut index = 0;
+                    let mut longest = ::core::option::Option::None;
+
+                    scratch.automaton_a.reset();
+                    let scratch_space = &mut scratch.scratch_space;
+                    let mut from_automaton = &mut scratch.automaton_a;
+                    let mut to_automaton = &mut scratch.automaton_b;
+
+                    loop{
+                        if from_automaton.is_terminated(){
+                            longest = ::core::option::Option::Some(#prefix_length + index);
+                        }
+
+                        if index == bytes.len(){
+                            return longest;
+                        }
+
+                        let byte = bytes[index];
+                        let char = if byte < 0x80 {
+                            index += 1;
+                            byte as char
+                        } else {
+                            #decode_multi_byte_char
+                        };
+
+                        to_automaton.advance_from(from_automaton, char, scratch_space);
+
+                        if(to_automaton.is_failed()){
+                            return longest;
+                        }
+
+                        ::core::mem::swap(to_automaton, from_automaton);
+                    }
+                }
+            },
+            quote! {
+                #[allow(dead_code)]
+                #visibility fn #shortest_match_function_name(string: &str) -> ::core::option::Option<usize>{
+                    #function_name::shortest_match(string, &mut #function_name::Scratch::new())
+                }
+
+                #[allow(dead_code)]
+                #visibility fn #longest_prefix_function_name(string: &str) -> ::core::option::Option<usize>{
+                    #function_name::longest_prefix(string, &mut #function_name::Scratch::new())
+                }
+            },
+        )
+    };
+
+    //finding matches builds on longest_prefix, which nfc matchers do not have, and a search's automaton already skips to the end of its match
+    //finding also starts the automaton anew at every character, where it would pass a `^`
+    let has_find = has_offsets && !matcher_declaration.search && !has_start_anchor;
+    let (find_function, find_wrapper) = if !has_find {
+        (quote!(), quote!())
+    } else {
+        let find_iter_function_name = format_ident!("{}_find_iter", function_name);
+        let split_function_name = format_ident!("{}_split", function_name);
+        let replace_function_name = format_ident!("{}_replace", function_name);
+        let replace_all_function_name = format_ident!("{}_replace_all", function_name);
+        //a match can only start where its literal prefix does, which is quicker to look for than running the automaton
+        let find_candidates = if automaton_prefix.is_empty() {
+            quote! {
+                //the earliest ending match bounds where the leftmost one can start
+                let end = start + first_match_end(&string[start..], scratch)?;
+                let mut candidate = start;
+                while candidate <= end {
+                    if longest_prefix(&string[candidate..], scratch).is_some(){
+                        return ::core::option::Option::Some(candidate);
+                    }
+                    candidate += string[candidate..].chars().next()?.len_utf8();
+                }
+                ::core::option::Option::None
+            }
+        } else {
+            quote! {
+                let mut candidate = start;
+                loop{
+                    candidate += string[candidate..].find(#automaton_prefix)?;
+                    if longest_prefix(&string[candidate..], scratch).is_some(){
+                        return ::core::option::Option::Some(candidate);
+                    }
+                    candidate += string[candidate..].chars().next()?.len_utf8();
+                }
+            }
+        };
+        (
+            quote! {
+                /// Where the first match of the pattern anywhere in the input ends, found by
+                /// letting the automaton start over at every character.
+                fn first_match_end(string: &str, scratch: &mut Scratch) -> ::core::option::Option<usize>{
+                    let bytes = str::as_bytes(string);
+                    let mut index = 0;
+
+                    scratch.automaton_a.reset();
+                    let scratch_space = &mut scratch.scratch_space;
+                    let mut from_automaton = &mut scratch.automaton_a;
+                    let mut to_automaton = &mut scratch.automaton_b;
+
+                    loop{
+                        if from_automaton.is_terminated(){
+                            return ::core::option::Option::Some(index);
+                        }
+
+                        if index == bytes.len(){
+                            return ::core::option::Option::None;
+                        }
+
+                        let byte = bytes[index];
+                        let char = if byte < 0x80 {
+                            index += 1;
+                            byte as char
+                        } else {
+                            #decode_multi_byte_char
+                        };
+
+                        to_automaton.advance_from(from_automaton, char, scratch_space);
+                        to_automaton.add_start_states(scratch_space);
+
+                        ::core::mem::swap(to_automaton, from_automaton);
+                    }
+                }
+
+                //the leftmost offset at or after `start` that a match starts at, which the automaton finds without telling the matches starting there apart
+                fn leftmost_start(string: &str, start: usize, scratch: &mut Scratch) -> ::core::option::Option<usize>{
+                    #find_candidates
+                }
+
+                /// The memory [`find_at`] works in: a [`Scratch`] to find where the leftmost match
+                /// starts, and the threads that decide where it ends.
+                pub struct FindScratch{
+                    scratch: Scratch,
+                    threads: PriorityThreads,
+                }
+
+                impl FindScratch{
+                    pub fn new() -> Self{
+                        Self{
+                            scratch: Scratch::new(),
+                            threads: PriorityThreads::new(),
+                        }
+                    }
+                }
+
+                impl ::core::default::Default for FindScratch{
+                    fn default() -> Self{
+                        Self::new()
+                    }
+                }
+
+                /// The byte offsets of the leftmost match that starts at or after `start`, or
+                /// `None` if there is none. Of the matches starting there, it is the one the
+                /// pattern prefers, like regexlib's `Regex::find`: earlier alternation branches
+                /// win over later ones, and greedy quantifiers match as much as they can and lazy
+                /// ones as little.
+                pub fn find_at(string: &str, start: usize, scratch: &mut FindScratch) -> ::core::option::Option<(usize, usize)>{
+                    let start = leftmost_start(string, start, &mut scratch.scratch)?;
+                    let end = CAPTURE_PROGRAM.match_end(string, start, &mut scratch.threads)?;
+                    ::core::option::Option::Some((start, end))
+                }
+
+                pub type FindIter<'haystack> = ::fastregex_rt::FindIter<'haystack, FindScratch>;
+
+                /// The byte offsets of each successive non-overlapping match in the input, as
+                /// found by [`find_at`].
+                pub fn find_iter(string: &str) -> FindIter<'_>{
+                    ::fastregex_rt::FindIter::new(string, FindScratch::new(), find_at)
+                }
+
+                pub type Split<'haystack> = ::fastregex_rt::Split<'haystack, FindScratch>;
+
+                /// The substrings of the input between the matches found by [`find_iter`], like
+                /// `str::split` with the matches as separators.
+                pub fn split(string: &str) -> Split<'_>{
+                    find_iter(string).split()
+                }
+
+                //replacing allocates, so it is left out where fastregex-rt is used without its `alloc` feature
+                ::fastregex_rt::with_alloc! {
+                    /// The input with its first match replaced by `replacement`, which is inserted as
+                    /// is, or the input itself if nothing matches.
+                    pub fn replace<'haystack>(string: &'haystack str, replacement: &str) -> ::fastregex_rt::Cow<'haystack, str>{
+                        find_iter(string).replace(replacement, ::core::option::Option::Some(1))
+                    }
+
+                    /// Like [`replace`], but replacing every match found by [`find_iter`].
+                    pub fn replace_all<'haystack>(string: &'haystack str, replacement: &str) -> ::fastregex_rt::Cow<'haystack, str>{
+                        find_iter(string).replace(replacement, ::core::option::Option::None)
+                    }
+                }
+            },
+            quote! {
+                #[allow(dead_code)]
+                #visibility fn #find_iter_function_name(string: &str) -> #function_name::FindIter<'_>{
+                    #function_name::find_iter(string)
+                }
+
+                #[allow(dead_code)]
+                #visibility fn #split_function_name(string: &str) -> #function_name::Split<'_>{
+                    #function_name::split(string)
+                }
+
+                ::fastregex_rt::with_alloc! {
+                    #[allow(dead_code)]
+                    #visibility fn #replace_function_name<'haystack>(string: &'haystack str, replacement: &str) -> ::fastregex_rt::Cow<'haystack, str>{
+                        #function_name::replace(string, replacement)
+                    }
+
+                    #[allow(dead_code)]
+                    #visibility fn #replace_all_function_name<'haystack>(string: &'haystack str, replacement: &str) -> ::fastregex_rt::Cow<'haystack, str>{
+                        #function_name::replace_all(string, replacement)
+                    }
+                }
+            },
+        )
+    };
+
+    //normalizing could join characters across chunks, and a chunk of bytes could end inside of a character, so only plain str matchers stream
+    let stream_type = if matcher_declaration.normalize_nfc || matcher_declaration.bytes {
+        quote!()
+    } else {
+        let prefix = automaton_prefix.as_str();
+        let (prefix_field, prefix_field_initializer, prefix_reset, prefix_feed, prefix_read_check) =
+            if prefix.is_empty() {
+                (quote!(), quote!(), quote!(), quote!(), quote!())
+            } else {
+                (
+                    quote! {
+                        //how many bytes of the automaton's literal prefix, which it has no states for, have been read
+                        prefix_read: usize,
+                    },
+                    quote!(prefix_read: 0,),
+                    quote!(self.prefix_read = 0;),
+                    quote! {
+                        let mut chunk = chunk;
+                        if self.prefix_read < #prefix.len(){
+                            let expected = &#prefix[self.prefix_read..];
+                            let length = expected.len().min(chunk.len());
+                            if chunk.as_bytes()[..length] != expected.as_bytes()[..length]{
+                                self.scratch.automaton_a.valid_state_count = 0;
+                                self.finished = true;
+                                return;
+                            }
+
+                            self.prefix_read += length;
+                            chunk = &chunk[length..];
+                        }
+                    },
+                    quote! {
+                        if self.prefix_read < #prefix.len(){
+                            return false;
+                        }
+                    },
+                )
+            };
+        //a search keeps the states it matched in, which the rest of the input can not undo
+        let search_check = if stops_at_match {
+            quote! {
+                if automaton_a.is_terminated(){
+                    *finished = true;
+                    return;
+                }
+            }
+        } else {
+            quote!()
+        };
+        let stream_branch_method = if matcher_declaration.report_branch {
+            quote! {
+                /// Which branch of the pattern's top-level alternation the input so far matched,
+                /// like the matcher's `_branch` function.
+                pub fn matching_branch(&self) -> ::core::option::Option<usize>{
+                    if !self.is_match(){
+                        return ::core::option::Option::None;
+                    }
+                    self.scratch.automaton_a.terminal_branch()
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        quote! {
+            /// A match over input that arrives in pieces, such as from a socket or reader, without
+            /// buffering it: each chunk is fed to the automaton as it arrives, and [`Stream::is_match`]
+            /// tells whether everything fed so far matches.
+            pub struct Stream{
+                scratch: Scratch,
+                #prefix_field
+                //set once nothing more the stream is fed can change whether it matches
+                finished: bool,
+            }
+
+            impl Stream{
+                pub fn new() -> Self{
+                    Self{
+                        scratch: Scratch::new(),
+                        #prefix_field_initializer
+                        finished: false,
+                    }
+                }
+
+                /// Forgets everything fed so far, to match a new input.
+                pub fn reset(&mut self){
+                    self.scratch.automaton_a.reset();
+                    #prefix_reset
+                    self.finished = false;
+                }
+
+                /// Continues the match with the next piece of the input.
+                pub fn feed(&mut self, chunk: &str){
+                    if self.finished {
+                        return;
+                    }
+
+                    #prefix_feed
+
+                    let Self{ scratch: Scratch{ scratch_space, automaton_a, automaton_b }, finished, .. } = self;
+                    for char in chunk.chars(){
+                        #search_check
+
+                        automaton_b.advance_from(automaton_a, char, scratch_space);
+                        ::core::mem::swap(automaton_a, automaton_b);
+
+                        if automaton_a.is_failed(){
+                            *finished = true;
+                            return;
+                        }
+                    }
+                }
+
+                /// Whether everything fed so far matches.
+                pub fn is_match(&self) -> bool{
+                    #prefix_read_check
+                    self.scratch.automaton_a.is_terminated()
+                }
+
+                #stream_branch_method
+            }
+
+            impl ::core::default::Default for Stream{
+                fn default() -> Self{
+                    Self::new()
+                }
+            }
+        }
+    };
+
+    let has_captures = program_automaton.is_some() && capture_names.len() > 1;
+    //finding the groups and where a match ends both run the automaton as constructed, in the order it prefers its paths
+    let program = match &program_automaton {
+        Some(program_automaton) if has_captures || has_find => {
+            emit_capture_program(program_automaton, capture_names.len() - 1)
+        }
+        _ => quote!(),
+    };
+    let const_matches_function = if matcher_declaration.is_const {
+        emit_const_matches_function(
+            &matcher_declaration,
+            automaton_prefix.as_str(),
+            &decode_multi_byte_char,
+            &return_type,
+            stops_at_match,
+        )
+    } else {
+        quote!()
+    };
+    let (captures_function, captures_wrapper) = if has_captures {
+        {
+            let capture_count = capture_names.len() - 1;
+            let capture_name_literals = capture_names.iter().map(|name| match name {
+                Some(name) => quote!(::core::option::Option::Some(#name)),
+                None => quote!(::core::option::Option::None),
+            });
+            let named_group_accessors = capture_names
+                .iter()
+                .enumerate()
+                .filter_map(|(index, name)| Some((index, name.as_deref()?)))
+                .filter(|(_, name)| !CAPTURES_METHODS.contains(name))
+                //keywords can not name a method
+                .filter_map(|(index, name)| {
+                    Some((index, name, syn::parse_str::<Ident>(name).ok()?))
+                })
+                .map(|(index, name, accessor)| {
+                    let doc = format!("The text matched by the group named `{name}` (group {index}), like [`Captures::name`].");
+                    quote! {
+                        #[doc = #doc]
+                        pub fn #accessor(&self) -> ::core::option::Option<&'haystack str>{
+                            self.0.get(#index)
+                        }
+                    }
+                });
+            let captures_function_name = format_ident!("{}_captures", function_name);
+            let run = if matcher_declaration.search {
+                quote!(search)
+            } else {
+                quote!(full_match)
+            };
+            (
+                quote! {
+                    /// The number of groups in [`PATTERN`], not counting group 0, the whole match.
+                    pub const CAPTURE_COUNT: usize = #capture_count;
+
+                    /// The name of each group, indexed like the groups, or `None` for groups
+                    /// without one (which always includes group 0).
+                    pub const CAPTURE_NAMES: [::core::option::Option<&str>; #capture_count + 1] = [#(#capture_name_literals),*];
+
+                    //finding the groups allocates, so it is left out where fastregex-rt is used without its `alloc` feature
+                    ::fastregex_rt::with_alloc! {
+                        /// The text matched by each group of [`PATTERN`]. Groups are looked up by
+                        /// index through the `::fastregex_rt::Captures` this dereferences to, and named
+                        /// groups by [`Captures::name`] or the method named after them (unless the
+                        /// name is a keyword or the name of another method).
+                        #[derive(Clone, Debug, Eq, PartialEq)]
+                        pub struct Captures<'haystack>(::fastregex_rt::Captures<'haystack>);
+
+                        impl<'haystack> ::core::ops::Deref for Captures<'haystack>{
+                            type Target = ::fastregex_rt::Captures<'haystack>;
+
+                            fn deref(&self) -> &Self::Target{
+                                &self.0
+                            }
+                        }
+
+                        impl<'haystack> Captures<'haystack>{
+                            /// The text matched by the group named `name`, or `None` if that group did
+                            /// not participate in the match (or does not exist).
+                            pub fn name(&self, name: &str) -> ::core::option::Option<&'haystack str>{
+                                let index = CAPTURE_NAMES.iter().position(|group_name| *group_name == ::core::option::Option::Some(name))?;
+                                self.0.get(index)
+                            }
+
+                            #(#named_group_accessors)*
+                        }
+
+                        /// The text matched by each group of the pattern, or `None` if the input does
+                        /// not match. Tracking the groups takes more work than [`matches`] does, so
+                        /// this is slower.
+                        pub fn captures(string: &str) -> ::core::option::Option<Captures<'_>>{
+                            CAPTURE_PROGRAM.#run(string).map(|slots| Captures(::fastregex_rt::Captures::new(string, slots)))
+                        }
+                    }
+                },
+                quote! {
+                    ::fastregex_rt::with_alloc! {
+                        #[allow(dead_code)]
+                        #visibility fn #captures_function_name(string: &str) -> ::core::option::Option<#function_name::Captures<'_>>{
+                            #function_name::captures(string)
+                        }
+                    }
+                },
+            )
+        }
+    } else {
+        (quote!(), quote!())
+    };
+
+    let state_count = emittable_automata.state_count();
+    let pattern = &matcher_declaration.regex;
+    let matcher_module_doc = format!(
+        "The automaton behind [`{}`], for callers that need to bound or provide the memory it uses.",
+        struct_name.as_ref().unwrap_or(&matching_function_name)
+    );
+
+    //the module shares the matcher's name (modules and functions live in separate namespaces), so that its items read as `name::Scratch`
+    let matcher_module = quote!(
+        #[doc = #matcher_module_doc]
+        #[allow(dead_code)]
+        #visibility mod #function_name {
             #emittable_automata
 
-            let string = ::core::convert::AsRef::as_ref(&string);
+            //failing ends the loop at most once, so keeping that path out of line favors the path taken on every character
+            #[cold]
+            const fn failed() -> #return_type{
+                #no_match
+            }
+
+            /// The pattern the matcher was declared with, with its fragments filled in.
+            pub const PATTERN: &str = #pattern;
+
+            /// The pattern as the matcher understood it: [`PATTERN`] after parsing (and normalizing,
+            /// for `nfc` matchers), written back out in a normalized form, e.g., `a{0,}` as `a*`.
+            pub const NORMALIZED_PATTERN: &str = #normalized_pattern;
+
+            /// The number of states of the automaton, which sizes every array in [`Scratch`].
+            pub const STATE_COUNT: usize = #state_count;
+
+            /// The bytes of memory a match uses: the size of [`Scratch`], which a call without a
+            /// caller-provided scratch keeps on its stack. The call's other locals add a few
+            /// machine words on top of this.
+            pub const STACK_BYTES: usize = ::core::mem::size_of::<Scratch>();
+
+            /// The memory a match works in. It can be created once and passed to every call of
+            /// [`matches`], or placed wherever the caller's memory budget requires.
+            pub struct Scratch{
+                scratch_space: ScratchSpace,
+                automaton_a: Automaton,
+                automaton_b: Automaton,
+            }
+
+            impl Scratch{
+                pub fn new() -> Self{
+                    Self{
+                        scratch_space: ScratchSpace::new(),
+                        automaton_a: Automaton::new(),
+                        automaton_b: Automaton::new(),
+                    }
+                }
+            }
+
+            impl ::core::default::Default for Scratch{
+                fn default() -> Self{
+                    Self::new()
+                }
+            }
+
+            /// The matcher as an object that is set up once and then reused, which saves
+            /// initializing a [`Scratch`] on every call when matching many short inputs.
+            pub struct Matcher{
+                scratch: Scratch,
+            }
+
+            impl Matcher{
+                pub fn new() -> Self{
+                    Self{
+                        scratch: Scratch::new(),
+                    }
+                }
+
+                /// The pattern the matcher was declared with, like [`PATTERN`].
+                pub fn pattern(&self) -> &'static str{
+                    PATTERN
+                }
+
+                /// Whether the whole input matches, like the matcher function.
+                pub fn is_match(&mut self, string: impl ::core::convert::AsRef<#input_type>) -> bool{
+                    matches(::core::convert::AsRef::as_ref(&string), &mut self.scratch)#matcher_is_match_result
+                }
+
+                #matcher_branch_method
+            }
+
+            impl ::core::default::Default for Matcher{
+                fn default() -> Self{
+                    Self::new()
+                }
+            }
+
+            pub fn matches(#input_parameter, #scratch_parameter: &mut Scratch) -> #return_type{
+                #input_normalization
+                #prefix_check
+                #input_bytes
+                let mut index = 0;
+
+                #matches_loop
+            }
+
+            #shortest_match_function
+
+            #find_function
+
+            #stream_type
+
+            #program
+
+            #captures_function
+
+            #const_matches_function
+        }
+    );
+    let matching_function = if matcher_declaration.is_const {
+        quote!(
+            #matcher_module
+
+            #matching_function_attributes
+            #visibility const fn #matching_function_name(#input_parameter) -> #return_type{
+                #function_name::const_matches(#input_argument)
+            }
+        )
+    } else {
+        quote!(
+            #matcher_module
+
+            #matching_function_attributes
+            #visibility fn #matching_function_name(string: impl ::core::convert::AsRef<#input_type>) -> #return_type{
+                #function_name::matches(::core::convert::AsRef::as_ref(&string), &mut #function_name::Scratch::new())
+            }
+        )
+    };
+
+    let generated = match (matcher_declaration.report_branch, matcher_declaration.is_const) {
+        (false, _) => matching_function,
+        (true, false) => quote!(
+            #matching_function
+
+            #(#attributes)*
+            #visibility fn #function_name(string: impl ::core::convert::AsRef<#input_type>) -> bool{
+                #matching_function_name(string).is_some()
+            }
+        ),
+        (true, true) => quote!(
+            #matching_function
+
+            #(#attributes)*
+            #visibility const fn #function_name(#input_parameter) -> bool{
+                #matching_function_name(#input_argument).is_some()
+            }
+        ),
+    };
+
+    //matchers match the whole input, so the matched part is the input itself; a search's match is somewhere inside it instead
+    let match_str_function = if matcher_declaration.search || matcher_declaration.bytes {
+        quote!()
+    } else {
+        let match_str_function_name = format_ident!("{}_match_str", function_name);
+        quote! {
+            #[allow(dead_code)]
+            #visibility fn #match_str_function_name(string: &str) -> ::core::option::Option<&str>{
+                if #function_name(string) {
+                    ::core::option::Option::Some(string)
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+        }
+    };
+    let generated = match &struct_name {
+        Some(struct_name) => {
+            let struct_type = emit_struct_type(
+                struct_name,
+                &function_name,
+                &matcher_declaration,
+                StructTypeMethods {
+                    input_type,
+                    has_offsets,
+                    has_find,
+                    has_captures,
+                },
+            );
+            quote!(
+                #matcher_module
+                #warnings
+
+                #struct_type
+            )
+        }
+        None => quote!(
+            #generated
+            #warnings
+
+            #match_str_function
+
+            #shortest_match_wrapper
+
+            #find_wrapper
+
+            #captures_wrapper
+        ),
+    };
+
+    timing_report.step_done("generating");
+    size_report.report_if_requested(&function_name.to_string(), &generated);
+    timing_report.report_if_requested(&function_name.to_string());
+
+    generated
+}
+
+//rejects an input whose first characters after the prefix are not in the automaton's prefix classes, returning `failure`
+fn emit_prefix_class_check(
+    prefix_classes: &[Vec<char>],
+    failure: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if prefix_classes.is_empty() {
+        return quote!();
+    }
+
+    let checks = prefix_classes.iter().map(|class| {
+        quote! {
+            if !::core::matches!(prefix_chars.next(), ::core::option::Option::Some(#(#class)|*)){
+                return #failure;
+            }
+        }
+    });
+    quote! {
+        let mut prefix_chars = string.chars();
+        #(#checks)*
+    }
+}
+
+//the bytes of the input that a run of literals of the automaton reads; a byte matcher's automaton reads each byte as the character of the same value, rather than as part of UTF-8
+fn literal_bytes(literals: &str, bytes: bool) -> proc_macro2::TokenStream {
+    if bytes {
+        let literal_bytes = literals.chars().map(|char| char as u8).collect::<Vec<_>>();
+        let literal = LitByteStr::new(&literal_bytes, Span::call_site());
+        quote!(#literal)
+    } else {
+        let literal = Lit::Str(LitStr::new(literals, Span::call_site()));
+        quote!(str::as_bytes(#literal))
+    }
+}
+
+//the matcher as a const fn, which runs the `ConstAutomaton` rather than the automaton
+fn emit_const_matches_function(
+    matcher_declaration: &MatcherDeclaration,
+    prefix: &str,
+    decode_multi_byte_char: &proc_macro2::TokenStream,
+    return_type: &proc_macro2::TokenStream,
+    stops_at_match: bool,
+) -> proc_macro2::TokenStream {
+    let (input_parameter, input_bytes) = if matcher_declaration.bytes {
+        (quote!(bytes: &[u8]), quote!())
+    } else {
+        (quote!(string: &str), quote!(let bytes = str::as_bytes(string);))
+    };
+    let result = if matcher_declaration.report_branch {
+        quote!(automaton.terminal_branch())
+    } else {
+        quote!(automaton.is_terminated())
+    };
+    let search_check = if stops_at_match {
+        quote! {
+            if automaton.is_terminated(){
+                return #result;
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    //stripping a prefix is not const, so its bytes are compared one by one
+    let prefix_check = if prefix.is_empty() {
+        quote!(let mut index = 0;)
+    } else {
+        let prefix_literal = literal_bytes(prefix, matcher_declaration.bytes);
+        quote! {
+            let prefix: &[u8] = #prefix_literal;
+            if bytes.len() < prefix.len() {
+                return failed();
+            }
+
+            let mut index = 0;
+            while index < prefix.len() {
+                if bytes[index] != prefix[index] {
+                    return failed();
+                }
+                index += 1;
+            }
+        }
+    };
+
+    quote! {
+        /// Like [`matches`], but a const fn, so that it can be called in const contexts. It keeps
+        /// its states in a flag per state rather than in a [`Scratch`], which makes it slower.
+        pub const fn const_matches(#input_parameter) -> #return_type{
+            #input_bytes
             #prefix_check
-            let mut chars = str::chars(string);
 
-            let mut scratch_space = ScratchSpace::new();
-            let mut automaton_a = Automaton::new();
-            let mut automaton_b = Automaton::new();
+            let mut automaton = ConstAutomaton::START;
+            while index < bytes.len(){
+                #search_check
+
+                let byte = bytes[index];
+                let char = if byte < 0x80 {
+                    index += 1;
+                    byte as char
+                } else {
+                    #decode_multi_byte_char
+                };
+
+                automaton = automaton.advance(char);
+                if automaton.is_failed(){
+                    return failed();
+                }
+            }
+
+            #result
+        }
+    }
+}
+
+//which of the matcher's functions a struct type has methods for
+struct StructTypeMethods {
+    input_type: proc_macro2::TokenStream,
+    has_offsets: bool,
+    has_find: bool,
+    has_captures: bool,
+}
+
+//the struct of a `struct_type` matcher, whose methods call into the module like the functions of any other matcher
+fn emit_struct_type(
+    struct_name: &Ident,
+    module_name: &Ident,
+    matcher_declaration: &MatcherDeclaration,
+    methods: StructTypeMethods,
+) -> proc_macro2::TokenStream {
+    let StructTypeMethods {
+        input_type,
+        has_offsets,
+        has_find,
+        has_captures,
+    } = methods;
+    let attributes = &matcher_declaration.attributes;
+    let visibility = &matcher_declaration.visibility;
+
+    //a const matcher's methods take the input by reference, as its `const_matches` does
+    let (input_parameter, matches_call) = if !matcher_declaration.is_const {
+        (
+            quote!(string: impl ::core::convert::AsRef<#input_type>),
+            quote!(#module_name::matches(::core::convert::AsRef::as_ref(&string), &mut #module_name::Scratch::new())),
+        )
+    } else if matcher_declaration.bytes {
+        (
+            quote!(bytes: &[u8]),
+            quote!(#module_name::const_matches(bytes)),
+        )
+    } else {
+        (
+            quote!(string: &str),
+            quote!(#module_name::const_matches(string)),
+        )
+    };
+    let constness = if matcher_declaration.is_const {
+        quote!(const)
+    } else {
+        quote!()
+    };
+
+    let (branch_method, is_match_result) = if matcher_declaration.report_branch {
+        (
+            quote! {
+                /// Which branch of the pattern's top-level alternation matched, or `None` if the
+                /// input does not match.
+                pub #constness fn matching_branch(&self, #input_parameter) -> ::core::option::Option<usize>{
+                    #matches_call
+                }
+            },
+            quote!(.is_some()),
+        )
+    } else {
+        (quote!(), quote!())
+    };
+    let match_str_method = if matcher_declaration.search || matcher_declaration.bytes {
+        quote!()
+    } else {
+        quote! {
+            /// The input if it matches.
+            pub fn match_str<'haystack>(&self, string: &'haystack str) -> ::core::option::Option<&'haystack str>{
+                if self.is_match(string) {
+                    ::core::option::Option::Some(string)
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+        }
+    };
+    let offset_methods = if has_offsets {
+        quote! {
+            /// The length of the shortest start of the input that matches, or `None` if none does.
+            pub fn shortest_match(&self, string: &str) -> ::core::option::Option<usize>{
+                #module_name::shortest_match(string, &mut #module_name::Scratch::new())
+            }
+
+            /// The length of the longest start of the input that matches, or `None` if none does.
+            pub fn longest_prefix(&self, string: &str) -> ::core::option::Option<usize>{
+                #module_name::longest_prefix(string, &mut #module_name::Scratch::new())
+            }
+        }
+    } else {
+        quote!()
+    };
+    let (find_methods, replace_methods) = if has_find {
+        (
+            quote! {
+                /// The non-overlapping matches in the input, from left to right.
+                pub fn find_iter<'haystack>(&self, string: &'haystack str) -> #module_name::FindIter<'haystack>{
+                    #module_name::find_iter(string)
+                }
+
+                /// The parts of the input between the matches.
+                pub fn split<'haystack>(&self, string: &'haystack str) -> #module_name::Split<'haystack>{
+                    #module_name::split(string)
+                }
+            },
+            quote! {
+                /// The input with its first match replaced.
+                pub fn replace<'haystack>(&self, string: &'haystack str, replacement: &str) -> ::fastregex_rt::Cow<'haystack, str>{
+                    #module_name::replace(string, replacement)
+                }
+
+                /// The input with every match replaced.
+                pub fn replace_all<'haystack>(&self, string: &'haystack str, replacement: &str) -> ::fastregex_rt::Cow<'haystack, str>{
+                    #module_name::replace_all(string, replacement)
+                }
+            },
+        )
+    } else {
+        (quote!(), quote!())
+    };
+    let captures_method = if has_captures {
+        quote! {
+            /// The capture groups of the pattern in the input, or `None` if the input does not match.
+            pub fn captures<'haystack>(&self, string: &'haystack str) -> ::core::option::Option<#module_name::Captures<'haystack>>{
+                #module_name::captures(string)
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    quote! {
+        #(#attributes)*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        #visibility struct #struct_name;
+
+        #[allow(dead_code)]
+        impl #struct_name{
+            /// The pattern the matcher was declared with, with its fragments filled in.
+            pub const fn pattern() -> &'static str{
+                #module_name::PATTERN
+            }
+
+            /// The pattern as the matcher understood it, after parsing, written back out in a normalized form.
+            pub const fn normalized_pattern() -> &'static str{
+                #module_name::NORMALIZED_PATTERN
+            }
+
+            /// A matcher that keeps its scratch between calls, for matching many inputs.
+            pub fn matcher(&self) -> #module_name::Matcher{
+                #module_name::Matcher::new()
+            }
+
+            /// Whether the whole input matches.
+            pub #constness fn is_match(&self, #input_parameter) -> bool{
+                #matches_call #is_match_result
+            }
+
+            #branch_method
+
+            #match_str_method
+
+            #offset_methods
+
+            #find_methods
+        }
+
+        //the methods that allocate, which are left out where fastregex-rt is used without its `alloc` feature
+        ::fastregex_rt::with_alloc! {
+            #[allow(dead_code)]
+            impl #struct_name{
+                #replace_methods
+
+                #captures_method
+            }
+        }
+
+        impl ::fastregex_rt::MatcherType for #struct_name{
+            type Input = #input_type;
+
+            const PATTERN: &'static str = #module_name::PATTERN;
+
+            fn is_match(&self, input: &#input_type) -> bool{
+                #struct_name::is_match(self, input)
+            }
+        }
+    }
+}
+
+//`IsWord` as `is_word`, and `HTTPMethod` as `http_method`
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake_case = String::new();
+    for (index, ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && index > 0 {
+            let after_lowercase = chars[index - 1].is_lowercase() || chars[index - 1].is_numeric();
+            let ends_acronym = chars[index - 1].is_uppercase()
+                && chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if after_lowercase || ends_acronym {
+                snake_case.push('_');
+            }
+        }
+        snake_case.extend(ch.to_lowercase());
+    }
+    snake_case
+}
+
+fn generate_matcher_set(matcher_set_declaration: MatcherSetDeclaration) -> proc_macro2::TokenStream {
+    let MatcherSetDeclaration {
+        visibility,
+        function_name,
+        patterns,
+        max_states,
+        max_repetition,
+    } = matcher_set_declaration;
+
+    let mut regexes = Vec::new();
+    let mut states = 0u64;
+    for (pattern, source) in &patterns {
+        let regex = match RegexEntry::parse(pattern) {
+            Ok(regex) => regex,
+            Err(e) => {
+                return syn::parse::Error::new(
+                    source.span(e.span.clone()),
+                    format!("Failed to parse as regex: {}", e),
+                )
+                .to_compile_error()
+            }
+        };
+
+        let repetition = regex.largest_repetition_count();
+        if repetition > max_repetition {
+            return syn::parse::Error::new(
+                source.literal_span(),
+                format!(
+                    "Pattern repeats {} times, more than the limit of {}; raise it with `max_repetition = {}`",
+                    repetition, max_repetition, repetition
+                ),
+            )
+            .to_compile_error();
+        }
+
+        //the counts saturate rather than overflow, so the total of several may not fit
+        let Some(total) = states.checked_add(Automaton::constructed_state_count(&regex)) else {
+            return syn::parse::Error::new(
+                source.literal_span(),
+                "The patterns need more states together than can be counted",
+            )
+            .to_compile_error();
+        };
+        states = total;
+        regexes.push(regex);
+    }
+
+    //the patterns share one automaton, so the limit applies to all of them together
+    if states > max_states {
+        return syn::parse::Error::new(
+            function_name.span(),
+            format!(
+                "The patterns need {} states together, more than the limit of {}; raise it with `max_states = {}`",
+                states, max_states, states
+            ),
+        )
+        .to_compile_error();
+    }
+
+    //a union keeps one terminal state per pattern, in the order of the patterns
+    let mut automaton = Automaton::from_regexes(regexes);
+    automaton.simplify();
+
+    let pattern_count = patterns.len();
+    let pattern_literals = patterns.iter().map(|(pattern, _)| pattern);
+
+    let prefix_check = if automaton.prefix().is_empty() {
+        quote!()
+    } else {
+        let prefix_literal = Lit::Str(LitStr::new(automaton.prefix(), Span::call_site()));
+
+        quote! {
+            let string = match string.strip_prefix(#prefix_literal){
+                ::core::option::Option::Some(string) => string,
+                ::core::option::Option::None => return failed(),
+            };
+        }
+    };
+
+    let emittable_automata = EmittableAutomaton::new(automaton, false).emitting_matched_branches();
+
+    let literal_chain_check = if emittable_automata.has_literal_chains() {
+        quote! {
+            if from_automaton.valid_state_count == 1 {
+                if let ::core::option::Option::Some((literals, end_state)) = from_automaton.states[0].literal_chain(){
+                    if !bytes[index..].starts_with(str::as_bytes(literals)) {
+                        return failed();
+                    }
+
+                    index += literals.len();
+                    from_automaton.states[0] = end_state;
+                    continue;
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let matcher_module_doc = format!(
+        "The automaton behind [`{}`], for callers that need to bound or provide the memory it uses.",
+        function_name
+    );
+
+    quote! {
+        #[doc = #matcher_module_doc]
+        #[allow(dead_code)]
+        #visibility mod #function_name {
+            #emittable_automata
+
+            #[cold]
+            fn failed() -> [bool; #pattern_count]{
+                [false; #pattern_count]
+            }
 
-            let mut from_automaton = &mut automaton_a;
-            let mut to_automaton = &mut automaton_b;
+            /// The patterns the set was declared with, with their fragments filled in, in the
+            /// order [`matches`] reports them.
+            pub const PATTERNS: [&str; #pattern_count] = [#(#pattern_literals),*];
 
-            while let Some(char) = chars.next(){
-                to_automaton.advance_from(from_automaton, char, &mut scratch_space);
+            /// The memory a match works in, like a matcher's.
+            pub struct Scratch{
+                scratch_space: ScratchSpace,
+                automaton_a: Automaton,
+                automaton_b: Automaton,
+            }
 
-                if(to_automaton.is_failed()){
-                    return false;
+            impl Scratch{
+                pub fn new() -> Self{
+                    Self{
+                        scratch_space: ScratchSpace::new(),
+                        automaton_a: Automaton::new(),
+                        automaton_b: Automaton::new(),
+                    }
                 }
+            }
 
-                ::core::mem::swap(to_automaton, from_automaton);
+            impl ::core::default::Default for Scratch{
+                fn default() -> Self{
+                    Self::new()
+                }
             }
 
-            to_automaton.is_terminated()
+            /// Whether the whole input matches each of [`PATTERNS`].
+            pub fn matches(string: &str, scratch: &mut Scratch) -> [bool; #pattern_count]{
+                #prefix_check
+                let bytes = str::as_bytes(string);
+                let mut index = 0;
+
+                scratch.automaton_a.reset();
+                let scratch_space = &mut scratch.scratch_space;
+                let mut from_automaton = &mut scratch.automaton_a;
+                let mut to_automaton = &mut scratch.automaton_b;
+
+                while index < bytes.len(){
+                    #literal_chain_check
+
+                    let byte = bytes[index];
+                    let char = if byte < 0x80 {
+                        index += 1;
+                        byte as char
+                    } else {
+                        ::fastregex_rt::decode_multi_byte_char(bytes, &mut index)
+                    };
+
+                    to_automaton.advance_from(from_automaton, char, scratch_space);
+
+                    if(to_automaton.is_failed()){
+                        return failed();
+                    }
+
+                    ::core::mem::swap(to_automaton, from_automaton);
+                }
+
+                from_automaton.matched_branches()
+            }
+        }
+
+        #visibility fn #function_name(string: impl ::core::convert::AsRef<str>) -> [bool; #pattern_count]{
+            #function_name::matches(::core::convert::AsRef::as_ref(&string), &mut #function_name::Scratch::new())
+        }
+    }
+}
+
+//the methods a generated `Captures` has, which a named group's accessor method must not shadow
+const CAPTURES_METHODS: &[&str] = &["name", "get", "range", "len", "is_empty", "iter"];
+
+struct BuiltAutomaton {
+    automaton: Automaton,
+    //the automaton as constructed, with its group boundaries and the priorities between its paths, for matchers that can report where in their input something matched
+    program_automaton: Option<Automaton>,
+    //the name of each group, indexed like the groups, which always includes the implicit group 0
+    capture_names: Vec<Option<String>>,
+    //whether the pattern has a `^` or a `$`, which the automaton only passes at the ends of the whole input, or a word boundary, which it decides by what is on both sides of it there
+    has_start_anchor: bool,
+    has_end_anchor: bool,
+    //the pattern as it was parsed, written back out
+    normalized_pattern: String,
+    //the warnings about the pattern, to be emitted with whatever is generated for it
+    warnings: proc_macro2::TokenStream,
+}
+
+//parses the pattern and builds its simplified automaton, or returns the compile error explaining why it can not be
+fn build_automaton(
+    matcher_declaration: &MatcherDeclaration,
+    timing_report: &mut TimingReport,
+) -> Result<BuiltAutomaton, proc_macro2::TokenStream> {
+    //normalizing needs a str to normalize
+    if matcher_declaration.normalize_nfc && matcher_declaration.bytes {
+        return Err(syn::parse::Error::new(
+            matcher_declaration.regex_source.literal_span(),
+            "The nfc and bytes options can not be combined, since bytes that may not be valid UTF-8 can not be normalized",
+        )
+        .to_compile_error());
+    }
+
+    //only parsed regexes have spans, which the warnings need to quote the part of the pattern they are about
+    let parsed = match matcher_declaration.syntax {
+        PatternSyntax::Regex {
+            dialect,
+            unicode,
+            line_terminator,
+        } => {
+            let options = ParseOptions {
+                dialect,
+                unicode,
+                line_terminator,
+                case_folding: matcher_declaration.case_folding,
+                bytes: matcher_declaration.bytes,
+                ..ParseOptions::default()
+            };
+            RegexEntry::parse_with_spans_and_options(&matcher_declaration.regex, options)
+                .map(|(regex, spans)| (regex, Some(spans)))
+                .map_err(|error| (error.to_string(), Some(error.span)))
+        }
+        PatternSyntax::Glob => from_glob(&matcher_declaration.regex)
+            .map(|regex| (regex, None))
+            .map_err(|message| (message, None)),
+        PatternSyntax::Like {
+            case_insensitive: false,
+            escape,
+        } => from_like(&matcher_declaration.regex, escape)
+            .map(|regex| (regex, None))
+            .map_err(|message| (message, None)),
+        PatternSyntax::Like {
+            case_insensitive: true,
+            escape,
+        } => from_ilike(&matcher_declaration.regex, escape)
+            .map(|regex| (regex, None))
+            .map_err(|message| (message, None)),
+    };
+    let (mut regex, spans) = match parsed {
+        Ok(parsed) => parsed,
+        //a regex error says which part of the pattern is wrong, which is underlined where the compiler can
+        Err((e, error_span)) => {
+            return Err(syn::parse::Error::new(
+                error_span.map_or_else(
+                    || matcher_declaration.regex_source.literal_span(),
+                    |span| matcher_declaration.regex_source.span(span),
+                ),
+                format!(
+                    "Failed to parse as {}: {}",
+                    matcher_declaration.syntax.name(),
+                    e
+                ),
+            )
+            .to_compile_error());
+        }
+    };
+
+    //checked before construction, which is what the limits protect
+    let repetition = regex.largest_repetition_count();
+    if repetition > matcher_declaration.max_repetition {
+        return Err(syn::parse::Error::new(
+            matcher_declaration.regex_source.literal_span(),
+            format!(
+                "Pattern repeats {} times, more than the limit of {}; raise it with `max_repetition = {}`",
+                repetition, matcher_declaration.max_repetition, repetition
+            ),
+        )
+        .to_compile_error());
+    }
+
+    check_state_count(matcher_declaration, &regex)?;
+
+    //the glushkov construction makes a state per character of the pattern, and anchors are not characters
+    if matcher_declaration.glushkov && regex.has_anchors() {
+        return Err(syn::parse::Error::new(
+            matcher_declaration.regex_source.literal_span(),
+            "The glushkov option can not be used with a pattern that has `^`, `$` or a word boundary",
+        )
+        .to_compile_error());
+    }
+    //nor is a grapheme cluster, which can be several characters
+    if matcher_declaration.glushkov && regex.has_grapheme_clusters() {
+        return Err(syn::parse::Error::new(
+            matcher_declaration.regex_source.literal_span(),
+            "The glushkov option can not be used with a pattern that has `\\X`",
+        )
+        .to_compile_error());
+    }
+
+    //each warning underlines the branch it is about where the compiler can make spans inside of the literal
+    let warnings = spans
+        .iter()
+        .flat_map(|spans| unreachable_branches(&regex, spans))
+        .map(|branch| {
+            let text = &matcher_declaration.regex[branch.span.clone()];
+            let message = match branch.duplicate_of {
+                Some(duplicate_of) => format!(
+                    "Alternation branch {} (`{}`) can never match, since it repeats branch {}",
+                    branch.index, text, duplicate_of
+                ),
+                None => format!(
+                    "Alternation branch {} (`{}`) can never match, since the branches before it match everything it does",
+                    branch.index, text
+                ),
+            };
+            warning(&message, matcher_declaration.regex_source.span(branch.span))
+        })
+        .collect();
+
+    if matcher_declaration.normalize_nfc {
+        regex.normalize_literals_nfc();
+    }
+    //after normalizing, which only composes runs of literals
+    if matcher_declaration.case_insensitive {
+        regex.make_case_insensitive_with(matcher_declaration.case_folding);
+    }
+    let normalized_pattern = regex.to_string();
+    let has_start_anchor = regex.has_start_anchor();
+    let has_end_anchor = regex.has_end_anchor();
+    //a class read as bytes can become an alternation of byte sequences, which is still one branch
+    let is_alternation = matches!(regex, RegexEntry::Alternation(_));
+    //last, since everything before works on characters; reading a character's encoding byte by byte takes more states than reading the character
+    if matcher_declaration.bytes {
+        regex = regex.to_utf8_bytes().map_err(|message| {
+            syn::parse::Error::new(
+                matcher_declaration.regex_source.literal_span(),
+                format!("Failed to match the regex against bytes: {message}"),
+            )
+            .to_compile_error()
+        })?;
+        check_state_count(matcher_declaration, &regex)?;
+    }
+    timing_report.step_done("parsing");
+
+    //the offsets of a normalized input do not correspond to the caller's input, so nfc matchers have no captures or matches to find; neither do byte matchers, whose input may not be a str
+    let capture_names = regex
+        .capture_names()
+        .into_iter()
+        .map(|name| name.map(String::from))
+        .collect();
+    let program_automaton = (!matcher_declaration.normalize_nfc && !matcher_declaration.bytes)
+        .then(|| Automaton::from_regex(regex.clone()));
+
+    //reporting the branch needs one terminal state per branch, which a union of the branches provides
+    let mut automaton = match (matcher_declaration.report_branch, regex) {
+        (true, RegexEntry::Alternation(branches)) if is_alternation && matcher_declaration.glushkov => {
+            let branches = branches
+                .iter()
+                .map(Automaton::from_regex_glushkov)
+                .collect::<Vec<_>>();
+            Automaton::union(&branches)
+        }
+        (true, RegexEntry::Alternation(branches)) if is_alternation => {
+            Automaton::from_regexes(branches)
         }
+        (true, regex) if matcher_declaration.glushkov => {
+            Automaton::union([&Automaton::from_regex_glushkov(&regex)])
+        }
+        (true, regex) => Automaton::from_regexes([regex]),
+        (false, regex) if matcher_declaration.glushkov => Automaton::from_regex_glushkov(&regex),
+        (false, regex) => Automaton::from_regex(regex),
+    };
+    if matcher_declaration.search {
+        automaton.make_unanchored();
+    }
+    timing_report.constructed(&automaton);
+    automaton.simplify();
+    timing_report.simplified(&automaton);
+    check_examples(matcher_declaration, &automaton)?;
+    Ok(BuiltAutomaton {
+        automaton,
+        program_automaton,
+        capture_names,
+        has_start_anchor,
+        has_end_anchor,
+        normalized_pattern,
+        warnings,
+    })
+}
+
+//the automaton the regex would be constructed as may not have more states than the matcher allows
+fn check_state_count(
+    matcher_declaration: &MatcherDeclaration,
+    regex: &RegexEntry,
+) -> Result<(), proc_macro2::TokenStream> {
+    Automaton::check_constructed_state_count(regex, matcher_declaration.max_states).map_err(
+        |message| {
+            let states = Automaton::constructed_state_count(regex);
+            syn::parse::Error::new(
+                matcher_declaration.regex_source.literal_span(),
+                format!("{message}; raise it with `max_states = {states}`"),
+            )
+            .to_compile_error()
+        },
     )
-    .into()
+}
+
+//runs the `matches` and `rejects` examples through the automaton, pointing an error at each one it disagrees with
+fn check_examples(
+    matcher_declaration: &MatcherDeclaration,
+    automaton: &Automaton,
+) -> Result<(), proc_macro2::TokenStream> {
+    let examples = matcher_declaration
+        .must_match
+        .iter()
+        .map(|example| (example, true))
+        .chain(
+            matcher_declaration
+                .must_reject
+                .iter()
+                .map(|example| (example, false)),
+        );
+
+    let mut error: Option<syn::Error> = None;
+    for (example, should_match) in examples {
+        let input = example.value();
+        let input = if matcher_declaration.normalize_nfc {
+            to_nfc(&input).into_owned()
+        } else if matcher_declaration.bytes {
+            //the automaton reads each byte as the character of the same value
+            input.bytes().map(char::from).collect()
+        } else {
+            input
+        };
+        if automaton.is_match(&input) == should_match {
+            continue;
+        }
+
+        let message = if should_match {
+            format!(
+                "The pattern does not match {:?}, which is listed in `matches`",
+                example.value()
+            )
+        } else {
+            format!(
+                "The pattern matches {:?}, which is listed in `rejects`",
+                example.value()
+            )
+        };
+        let example_error = syn::Error::new(example.span(), message);
+        match &mut error {
+            Some(error) => error.combine(example_error),
+            None => error = Some(example_error),
+        }
+    }
+
+    match error {
+        Some(error) => Err(error.to_compile_error()),
+        None => Ok(()),
+    }
 }
@@ -1,34 +1,703 @@
 use proc_macro2::Span;
 use syn::parse::{Parse, ParseStream};
-use syn::{Ident, Lit, Token};
+use syn::token::{Brace, Bracket};
+use syn::{braced, bracketed, Attribute, Ident, Lit, LitStr, Token, Visibility};
+
+use regexlib::case_folding::CaseFolding;
+use regexlib::line_terminator::LineTerminator;
+use regexlib::parser::Dialect;
+
+use crate::fragment::{Fragments, UsesFragments};
+use crate::pattern_source::PatternSource;
+
+/// The most states a matcher's automaton may have before simplification, unless the matcher sets
+/// its own `max_states`.
+pub const DEFAULT_MAX_STATES: u64 = 10_000;
+/// The largest count a repetition in a matcher's pattern may have (e.g., the 300 in `a{2,300}`),
+/// unless the matcher sets its own `max_repetition`.
+pub const DEFAULT_MAX_REPETITION: u64 = 1_000;
+
+/// The options a matcher can be configured with. Each can be given as a trailing flag (e.g.,
+/// `nfc` or `max_states = 4096`) or in a trailing options block (e.g., `{ nfc: true }`).
+const OPTIONS: &str =
+    "\"nfc\", \"case_insensitive\", \"case_folding\", \"branch\", \"glushkov\", \"engine\", \"search\", \"anchored\", \"bytes\", \"struct_type\", \"max_states\", \"max_repetition\", \"dialect\", \"unicode\", \"line_terminator\", \"escape\", \"matches\" or \"rejects\"";
+
+/// How a matcher's pattern is written, which is decided by the macro it is declared with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PatternSyntax {
+    /// A regex, read in the `dialect` set by the `dialect` option. `unicode` is set by the
+    /// `unicode` option, and defaults to true; without it, `\d`, `\w` and `\s` only match ASCII
+    /// (see [`regexlib::parser::ParseOptions::unicode`]). `line_terminator` is set by the
+    /// `line_terminator` option, and decides what `.`, `\R` and the multi-line `^` and `$` treat
+    /// as the end of a line (see [`regexlib::parser::ParseOptions::line_terminator`]).
+    Regex {
+        dialect: Dialect,
+        unicode: bool,
+        line_terminator: LineTerminator,
+    },
+    /// A shell glob, converted by [`regexlib::from_glob`].
+    Glob,
+    /// An SQL `LIKE` pattern, converted by [`regexlib::from_like`] (or [`regexlib::from_ilike`]
+    /// if `case_insensitive`). `escape` is set by the `escape` option.
+    Like {
+        case_insensitive: bool,
+        escape: Option<char>,
+    },
+}
+
+impl PatternSyntax {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PatternSyntax::Regex { .. } => "regex",
+            PatternSyntax::Glob => "glob",
+            PatternSyntax::Like {
+                case_insensitive: false,
+                ..
+            } => "LIKE pattern",
+            PatternSyntax::Like {
+                case_insensitive: true,
+                ..
+            } => "ILIKE pattern",
+        }
+    }
+}
 
 pub struct MatcherDeclaration {
+    /// Written before the matcher's name (e.g., `#[inline]` or a doc comment), and put on the
+    /// function named after the matcher, or on the struct of a `struct_type` matcher.
+    pub attributes: Vec<Attribute>,
+    /// Written before the matcher's name (e.g., `pub(crate)`), and given to every function
+    /// generated for the matcher and to its module. Matchers are private by default.
+    pub visibility: Visibility,
+    /// Set by writing `const` before the matcher's name: the function named after the matcher is
+    /// a `const fn` taking the input by reference, so that it can be called in const contexts.
+    pub is_const: bool,
     pub function_name: Ident,
     pub regex: String,
-    pub regex_span: Span,
+    /// Where `regex` was written, with any fragments it uses filled in.
+    pub regex_source: PatternSource,
+    pub syntax: PatternSyntax,
+    /// Set by the `nfc` option: the pattern's literals are NFC-normalized when the matcher is
+    /// generated, and the matcher normalizes its input the same way before matching it. This
+    /// calls into `regexlib`, so unlike other matchers, these need `std`.
+    pub normalize_nfc: bool,
+    /// Set by the `case_insensitive` option: the pattern matches regardless of case, as if it
+    /// started with `(?i)` (see [`RegexEntry::make_case_insensitive`](regexlib::parser::RegexEntry::make_case_insensitive)).
+    /// The pattern is folded when the matcher is generated, so matching still only compares
+    /// characters.
+    pub case_insensitive: bool,
+    /// Set by the `case_folding` option (`simple` or `full`): how `case_insensitive` and the `i`
+    /// flag fold case. Full folding lets a literal match a different number of characters (e.g.,
+    /// `ß` matches `ss`).
+    pub case_folding: CaseFolding,
+    /// Set by the `branch` option: a `<function_name>_branch` function is generated as well,
+    /// returning which branch of the pattern's top-level alternation matched.
+    pub report_branch: bool,
+    /// Set by the `glushkov` option: the automaton is built with the Glushkov construction (see
+    /// [`Automaton::from_regex_glushkov`](regexlib::automata::Automaton::from_regex_glushkov))
+    /// instead of the default one. Also set by `engine: glushkov`, while `engine: dfa` selects the
+    /// default construction; either way, the matcher runs the automaton as a DFA whose states are
+    /// the sets of automaton states it can be in, so there is no other engine to choose.
+    pub glushkov: bool,
+    /// Set by the `search` option: the matcher finds the pattern anywhere in its input instead of
+    /// matching the whole input, stopping at the end of the first match it finds. The shortest
+    /// match and longest prefix are then the ends of the first and last matches. `anchored` is
+    /// the opposite option, so `anchored: false` sets it.
+    pub search: bool,
+    /// Set by the `bytes` option: the matcher takes bytes rather than a str, which may not be
+    /// valid UTF-8. The pattern matches the UTF-8 encoding of its characters, so a byte that is
+    /// not part of valid UTF-8 is only matched where the `u` flag is cleared, which makes `.`,
+    /// literals and classes match single bytes (e.g., `(?-u:\xFF)` or `(?-u:.)`; see
+    /// [`regexlib::parser::RegexEntry::to_utf8_bytes`]).
+    pub bytes: bool,
+    /// Set by the `struct_type` option: the matcher is declared as a zero-sized struct named
+    /// `function_name` whose methods do the matching, rather than as functions. Its module is
+    /// named after it in snake case (e.g., `is_word` for `IsWord`), since a module and a struct
+    /// can not share a name.
+    pub struct_type: bool,
+    /// Set by the `max_states` option, defaulting to [`DEFAULT_MAX_STATES`].
+    pub max_states: u64,
+    /// Set by the `max_repetition` option, defaulting to [`DEFAULT_MAX_REPETITION`].
+    pub max_repetition: u64,
+    /// Set by the `matches` option: inputs the pattern must match, checked when the matcher is
+    /// generated.
+    pub must_match: Vec<LitStr>,
+    /// Set by the `rejects` option: inputs the pattern must not match.
+    pub must_reject: Vec<LitStr>,
 }
 
-impl Parse for MatcherDeclaration {
+/// The matchers declared by one `matchers!` invocation.
+pub struct MatchersDeclaration {
+    pub matchers: Vec<MatcherDeclaration>,
+}
+
+/// The pattern and options given to `regex_assert_valid!`. No matcher is generated for it, so
+/// its declaration is named after the macro.
+pub struct AssertValidDeclaration(pub MatcherDeclaration);
+
+#[derive(Clone)]
+struct MatcherOption {
+    name: Ident,
+    value: OptionValue,
+}
+
+#[derive(Clone)]
+enum OptionValue {
+    //a bare trailing flag, like `nfc`
+    Flag,
+    Lit(Lit),
+    Ident(Ident),
+    //a bracketed list of strings, like `["a", "b"]`
+    List(Vec<LitStr>),
+}
+
+impl OptionValue {
+    //a literal, a list of strings or a name, like the `dfa` of `engine: dfa`
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let function_name = input.parse()?;
+        if input.peek(Bracket) {
+            let list;
+            bracketed!(list in input);
+            Ok(OptionValue::List(
+                list.parse_terminated(|input| input.parse::<LitStr>(), Token![,])?
+                    .into_iter()
+                    .collect(),
+            ))
+        } else if input.peek(Lit) {
+            Ok(OptionValue::Lit(input.parse()?))
+        } else {
+            Ok(OptionValue::Ident(input.parse()?))
+        }
+    }
+
+    //the value of `max_states` or `max_repetition`
+    fn limit(self, name: &Ident) -> syn::Result<u64> {
+        match self {
+            OptionValue::Lit(Lit::Int(limit)) => limit.base10_parse::<u64>(),
+            value => Err(syn::Error::new(
+                value.span(name),
+                format!("Matcher option \"{name}\" expects an integer"),
+            )),
+        }
+    }
+
+    //where an error about the value points
+    fn span(&self, name: &Ident) -> Span {
+        match self {
+            OptionValue::Flag => name.span(),
+            OptionValue::Lit(lit) => lit.span(),
+            OptionValue::Ident(ident) => ident.span(),
+            OptionValue::List(_) => name.span(),
+        }
+    }
+}
+
+impl MatcherDeclaration {
+    fn apply_option(&mut self, option: MatcherOption) -> syn::Result<()> {
+        let MatcherOption { name, value } = option;
+
+        if [
+            "nfc",
+            "case_insensitive",
+            "branch",
+            "glushkov",
+            "search",
+            "anchored",
+            "bytes",
+            "struct_type",
+        ]
+            .iter()
+            .any(|flag| name == flag)
+        {
+            let enabled = match value {
+                OptionValue::Flag => true,
+                OptionValue::Lit(Lit::Bool(enabled)) => enabled.value,
+                value => {
+                    return Err(syn::Error::new(
+                        value.span(&name),
+                        format!("Matcher option \"{name}\" expects true or false"),
+                    ))
+                }
+            };
+
+            if name == "nfc" {
+                self.normalize_nfc = enabled;
+            } else if name == "case_insensitive" {
+                self.case_insensitive = enabled;
+            } else if name == "branch" {
+                self.report_branch = enabled;
+            } else if name == "glushkov" {
+                self.glushkov = enabled;
+            } else if name == "search" {
+                self.search = enabled;
+            } else if name == "anchored" {
+                self.search = !enabled;
+            } else if name == "struct_type" {
+                self.struct_type = enabled;
+            } else {
+                self.bytes = enabled;
+            }
+        } else if name == "engine" {
+            self.glushkov = match value {
+                OptionValue::Ident(engine) if engine == "dfa" => false,
+                OptionValue::Ident(engine) if engine == "glushkov" => true,
+                value => {
+                    return Err(syn::Error::new(
+                        value.span(&name),
+                        "Matcher option \"engine\" expects dfa or glushkov",
+                    ))
+                }
+            };
+        } else if name == "case_folding" {
+            self.case_folding = match value {
+                OptionValue::Ident(folding) if folding == "simple" => CaseFolding::Simple,
+                OptionValue::Ident(folding) if folding == "full" => CaseFolding::Full,
+                value => {
+                    return Err(syn::Error::new(
+                        value.span(&name),
+                        "Matcher option \"case_folding\" expects simple or full",
+                    ))
+                }
+            };
+        } else if name == "dialect" {
+            let PatternSyntax::Regex { dialect, .. } = &mut self.syntax else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Matcher option \"dialect\" only applies to regexes",
+                ));
+            };
 
-        input.parse::<Token![,]>()?;
+            *dialect = match value {
+                OptionValue::Lit(Lit::Str(name)) => name
+                    .value()
+                    .parse()
+                    .map_err(|e: String| syn::Error::new(name.span(), e))?,
+                value => {
+                    return Err(syn::Error::new(
+                        value.span(&name),
+                        "Matcher option \"dialect\" expects a string",
+                    ))
+                }
+            };
+        } else if name == "line_terminator" {
+            let PatternSyntax::Regex {
+                line_terminator, ..
+            } = &mut self.syntax
+            else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Matcher option \"line_terminator\" only applies to regexes",
+                ));
+            };
+
+            *line_terminator = match value {
+                OptionValue::Lit(Lit::Str(name)) => name
+                    .value()
+                    .parse()
+                    .map_err(|e: String| syn::Error::new(name.span(), e))?,
+                value => {
+                    return Err(syn::Error::new(
+                        value.span(&name),
+                        "Matcher option \"line_terminator\" expects a string",
+                    ))
+                }
+            };
+        } else if name == "unicode" {
+            let PatternSyntax::Regex { unicode, .. } = &mut self.syntax else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Matcher option \"unicode\" only applies to regexes",
+                ));
+            };
 
-        let (regex, regex_span) = {
-            let regex = input.parse::<Lit>()?;
+            *unicode = match value {
+                OptionValue::Flag => true,
+                OptionValue::Lit(Lit::Bool(enabled)) => enabled.value,
+                value => {
+                    return Err(syn::Error::new(
+                        value.span(&name),
+                        "Matcher option \"unicode\" expects true or false",
+                    ))
+                }
+            };
+        } else if name == "escape" {
+            let PatternSyntax::Like { escape, .. } = &mut self.syntax else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Matcher option \"escape\" only applies to LIKE patterns",
+                ));
+            };
 
-            let (regex, regex_span) = match regex {
-                Lit::Str(lit_str) => (lit_str.value(), lit_str.span()),
-                error => return Err(syn::Error::new(error.span(), "Expected string literal")),
+            *escape = match value {
+                OptionValue::Lit(Lit::Char(escape)) => Some(escape.value()),
+                value => {
+                    return Err(syn::Error::new(
+                        value.span(&name),
+                        "Matcher option \"escape\" expects a character",
+                    ))
+                }
+            };
+        } else if name == "max_states" || name == "max_repetition" {
+            let limit = value.limit(&name)?;
+            if name == "max_states" {
+                self.max_states = limit;
+            } else {
+                self.max_repetition = limit;
+            }
+        } else if name == "matches" || name == "rejects" {
+            let examples = match value {
+                OptionValue::List(examples) => examples,
+                value => {
+                    return Err(syn::Error::new(
+                        value.span(&name),
+                        format!("Matcher option \"{name}\" expects a list of strings"),
+                    ))
+                }
             };
 
-            (regex, regex_span)
+            if name == "matches" {
+                self.must_match = examples;
+            } else {
+                self.must_reject = examples;
+            }
+        } else {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("Unknown matcher option \"{name}\", expected {OPTIONS}"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl MatcherOption {
+    //a trailing flag, either bare (`nfc`) or with a value (`max_states = 4096`)
+    fn parse_flag(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let value = if input.parse::<Option<Token![=]>>()?.is_some() {
+            OptionValue::parse(input)?
+        } else {
+            OptionValue::Flag
+        };
+
+        Ok(Self { name, value })
+    }
+
+    //a brace-delimited block of fields (`{ nfc: true, max_states: 4096 }`)
+    fn parse_block(input: ParseStream) -> syn::Result<Vec<Self>> {
+        let options;
+        braced!(options in input);
+        Ok(options
+            .parse_terminated(MatcherOption::parse_field, Token![,])?
+            .into_iter()
+            .collect())
+    }
+
+    //an entry of an options block (`max_states: 4096`)
+    fn parse_field(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let value = OptionValue::parse(input)?;
+
+        Ok(Self { name, value })
+    }
+}
+
+impl MatcherDeclaration {
+    /// Parses a declaration whose pattern is written in `syntax`, for use with
+    /// [`syn::parse::Parser`].
+    pub fn parse_with_syntax(
+        syntax: PatternSyntax,
+    ) -> impl FnOnce(ParseStream) -> syn::Result<Self> {
+        move |input| {
+            let attributes = input.call(Attribute::parse_outer)?;
+            let visibility = input.parse()?;
+            let is_const = input.parse::<Option<Token![const]>>()?.is_some();
+            let function_name = input.parse()?;
+
+            input.parse::<Token![,]>()?;
+
+            let mut declaration = Self::parse_name_and_regex(function_name, syntax, input)?;
+            declaration.attributes = attributes;
+            declaration.visibility = visibility;
+            declaration.is_const = is_const;
+            declaration.parse_trailing_options(input)?;
+
+            Ok(declaration)
+        }
+    }
+
+    //a declaration with every option at its default
+    fn parse_name_and_regex(
+        function_name: Ident,
+        syntax: PatternSyntax,
+        input: ParseStream,
+    ) -> syn::Result<Self> {
+        let literal = match input.parse::<Lit>()? {
+            Lit::Str(lit_str) => lit_str,
+            error => return Err(syn::Error::new(error.span(), "Expected string literal")),
         };
+        //the fragments the regex uses are filled in once it is parsed (see `UsesFragments`)
+        let regex = literal.value();
+        let regex_source = PatternSource::new(literal, Vec::new());
 
         Ok(Self {
+            attributes: Vec::new(),
+            visibility: Visibility::Inherited,
+            is_const: false,
             function_name,
             regex,
-            regex_span,
+            regex_source,
+            syntax,
+            normalize_nfc: false,
+            case_insensitive: false,
+            case_folding: CaseFolding::Simple,
+            report_branch: false,
+            glushkov: false,
+            search: false,
+            bytes: false,
+            struct_type: false,
+            max_states: DEFAULT_MAX_STATES,
+            max_repetition: DEFAULT_MAX_REPETITION,
+            must_match: Vec::new(),
+            must_reject: Vec::new(),
         })
     }
+
+    //the comma-separated flags and options blocks after the pattern
+    fn parse_trailing_options(&mut self, input: ParseStream) -> syn::Result<()> {
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            if input.peek(Brace) {
+                for option in MatcherOption::parse_block(input)? {
+                    self.apply_option(option)?;
+                }
+            } else {
+                self.apply_option(MatcherOption::parse_flag(input)?)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl UsesFragments for MatcherDeclaration {
+    //only regexes are built from fragments; braces mean something else in the other syntaxes
+    fn written_patterns(&self) -> Vec<&LitStr> {
+        match self.syntax {
+            PatternSyntax::Regex { .. } => vec![self.regex_source.literal()],
+            _ => Vec::new(),
+        }
+    }
+
+    fn fill_fragments(&mut self, fragments: &Fragments) -> syn::Result<()> {
+        if let PatternSyntax::Regex { .. } = self.syntax {
+            let literal = self.regex_source.literal().clone();
+            let (regex, filled) = fragments.interpolate(&literal)?;
+            self.regex = regex;
+            self.regex_source = PatternSource::new(literal, filled);
+        }
+        Ok(())
+    }
+}
+
+impl Parse for MatcherDeclaration {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Self::parse_with_syntax(PatternSyntax::Regex {
+            dialect: Dialect::Fastregex,
+            unicode: true,
+            line_terminator: LineTerminator::Newline,
+        })(input)
+    }
+}
+
+impl Parse for AssertValidDeclaration {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let function_name = Ident::new("regex_assert_valid", Span::call_site());
+        let mut declaration = MatcherDeclaration::parse_name_and_regex(
+            function_name,
+            PatternSyntax::Regex {
+                dialect: Dialect::Fastregex,
+                unicode: true,
+                line_terminator: LineTerminator::Newline,
+            },
+            input,
+        )?;
+        declaration.parse_trailing_options(input)?;
+
+        Ok(Self(declaration))
+    }
+}
+
+impl UsesFragments for AssertValidDeclaration {
+    fn written_patterns(&self) -> Vec<&LitStr> {
+        self.0.written_patterns()
+    }
+
+    fn fill_fragments(&mut self, fragments: &Fragments) -> syn::Result<()> {
+        self.0.fill_fragments(fragments)
+    }
+}
+
+impl UsesFragments for MatchersDeclaration {
+    fn written_patterns(&self) -> Vec<&LitStr> {
+        self.matchers
+            .iter()
+            .flat_map(MatcherDeclaration::written_patterns)
+            .collect()
+    }
+
+    fn fill_fragments(&mut self, fragments: &Fragments) -> syn::Result<()> {
+        for matcher in &mut self.matchers {
+            matcher.fill_fragments(fragments)?;
+        }
+        Ok(())
+    }
+}
+
+impl Parse for MatchersDeclaration {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let shared_options = if input.peek(Brace) {
+            MatcherOption::parse_block(input)?
+        } else {
+            Vec::new()
+        };
+
+        let mut matchers = Vec::new();
+        while !input.is_empty() {
+            let attributes = input.call(Attribute::parse_outer)?;
+            let visibility = input.parse()?;
+            let is_const = input.parse::<Option<Token![const]>>()?.is_some();
+            let function_name = input.parse()?;
+            input.parse::<Token![=>]>()?;
+            let mut declaration = MatcherDeclaration::parse_name_and_regex(
+                function_name,
+                PatternSyntax::Regex {
+                    dialect: Dialect::Fastregex,
+                    unicode: true,
+                    line_terminator: LineTerminator::Newline,
+                },
+                input,
+            )?;
+            declaration.attributes = attributes;
+            declaration.visibility = visibility;
+            declaration.is_const = is_const;
+
+            //the matcher's own options come last, so they override the shared ones
+            let own_options = if input.peek(Brace) {
+                MatcherOption::parse_block(input)?
+            } else {
+                Vec::new()
+            };
+            for option in shared_options.iter().cloned().chain(own_options) {
+                declaration.apply_option(option)?;
+            }
+            matchers.push(declaration);
+
+            if input.parse::<Option<Token![,]>>()?.is_none() {
+                break;
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("Expected `,` between matchers"));
+        }
+
+        Ok(Self { matchers })
+    }
+}
+
+/// The patterns given to `matcher_set!`, each with its fragments filled in (once
+/// [`fill_fragments`](UsesFragments::fill_fragments) has been called) and where it was written.
+pub struct MatcherSetDeclaration {
+    /// Written before the set's name, and given to its function and module like a matcher's.
+    pub visibility: Visibility,
+    pub function_name: Ident,
+    pub patterns: Vec<(String, PatternSource)>,
+    /// Set by the `max_states` option, defaulting to [`DEFAULT_MAX_STATES`]. The patterns share
+    /// one automaton, so the limit is on the states of all of them together.
+    pub max_states: u64,
+    /// Set by the `max_repetition` option, defaulting to [`DEFAULT_MAX_REPETITION`].
+    pub max_repetition: u64,
+}
+
+impl Parse for MatcherSetDeclaration {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let visibility = input.parse()?;
+        let function_name: Ident = input.parse()?;
+        let mut declaration = Self {
+            visibility,
+            function_name,
+            patterns: Vec::new(),
+            max_states: DEFAULT_MAX_STATES,
+            max_repetition: DEFAULT_MAX_REPETITION,
+        };
+
+        //the patterns, then the same trailing flags and options blocks a matcher takes
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            if input.peek(LitStr) {
+                let pattern = input.parse::<LitStr>()?;
+                declaration
+                    .patterns
+                    .push((pattern.value(), PatternSource::new(pattern, Vec::new())));
+            } else if input.peek(Brace) {
+                for option in MatcherOption::parse_block(input)? {
+                    declaration.apply_option(option)?;
+                }
+            } else {
+                declaration.apply_option(MatcherOption::parse_flag(input)?)?;
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("Expected `,` between patterns"));
+        }
+        if declaration.patterns.is_empty() {
+            return Err(syn::Error::new(
+                declaration.function_name.span(),
+                "A matcher set needs at least one pattern",
+            ));
+        }
+
+        Ok(declaration)
+    }
+}
+
+impl MatcherSetDeclaration {
+    fn apply_option(&mut self, option: MatcherOption) -> syn::Result<()> {
+        let MatcherOption { name, value } = option;
+
+        if name == "max_states" {
+            self.max_states = value.limit(&name)?;
+        } else if name == "max_repetition" {
+            self.max_repetition = value.limit(&name)?;
+        } else {
+            return Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "Unknown matcher set option \"{name}\", expected \"max_states\" or \"max_repetition\""
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl UsesFragments for MatcherSetDeclaration {
+    fn written_patterns(&self) -> Vec<&LitStr> {
+        self.patterns
+            .iter()
+            .map(|(_, source)| source.literal())
+            .collect()
+    }
+
+    fn fill_fragments(&mut self, fragments: &Fragments) -> syn::Result<()> {
+        for (regex, source) in &mut self.patterns {
+            let literal = source.literal().clone();
+            let (filled_regex, filled) = fragments.interpolate(&literal)?;
+            *regex = filled_regex;
+            *source = PatternSource::new(literal, filled);
+        }
+        Ok(())
+    }
 }
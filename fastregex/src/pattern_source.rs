@@ -0,0 +1,167 @@
+use std::ops::Range;
+
+use proc_macro2::Span;
+use syn::LitStr;
+
+use crate::fragment::FilledFragment;
+
+/// The string literal a pattern was written as, to point diagnostics at the part of it they are
+/// about.
+pub struct PatternSource {
+    literal: LitStr,
+    fragments: Vec<FilledFragment>,
+}
+
+impl PatternSource {
+    /// The source of a pattern that is `literal`'s value with `fragments` filled in.
+    pub fn new(literal: LitStr, fragments: Vec<FilledFragment>) -> Self {
+        Self { literal, fragments }
+    }
+
+    /// The literal as written, with any `{NAME}`s of fragments not filled in.
+    pub fn literal(&self) -> &LitStr {
+        &self.literal
+    }
+
+    /// The span of the whole literal.
+    pub fn literal_span(&self) -> Span {
+        self.literal.span()
+    }
+
+    /// The span of the part of the literal that `range` of the pattern was written as, or the
+    /// span of the whole literal if the compiler can not make spans inside of it, which it can
+    /// not on stable Rust. A range reaching into a fragment covers the whole `{NAME}` it was
+    /// filled in for, and an empty range covers the character after it (the closing quote at the
+    /// end of the pattern).
+    pub fn span(&self, range: Range<usize>) -> Span {
+        let token = self.literal.token();
+        let written = self.written_range(range);
+        let source = match value_offsets(&token.to_string(), self.literal.value().len()) {
+            Some(offsets) => offsets[written.start]..offsets[written.end],
+            None => return self.literal_span(),
+        };
+
+        let source = if source.is_empty() {
+            let next = token.to_string()[source.start..]
+                .chars()
+                .next()
+                .map_or(0, char::len_utf8);
+            source.start..source.start + next
+        } else {
+            source
+        };
+        token.subspan(source).unwrap_or_else(|| self.literal_span())
+    }
+
+    //the range of the literal's value that `range` of the interpolated pattern came from
+    fn written_range(&self, range: Range<usize>) -> Range<usize> {
+        self.written_offset(range.start, false)..self.written_offset(range.end, true)
+    }
+
+    fn written_offset(&self, offset: usize, is_end: bool) -> usize {
+        let (mut filled_len, mut written_len) = (0, 0);
+        for fragment in &self.fragments {
+            let inside = if is_end {
+                fragment.filled.start < offset && offset <= fragment.filled.end
+            } else {
+                fragment.filled.contains(&offset)
+            };
+            if inside {
+                return if is_end {
+                    fragment.written.end
+                } else {
+                    fragment.written.start
+                };
+            }
+            if fragment.filled.end > offset {
+                break;
+            }
+            filled_len += fragment.filled.len();
+            written_len += fragment.written.len();
+        }
+
+        offset - filled_len + written_len
+    }
+}
+
+//for each byte offset into the value of the string literal `token` (including its end, so
+//`value_len + 1` of them), the offset in `token` of the character it was written as; escapes like
+//`\n` or `\u{263A}` are several characters of the token for one of the value
+fn value_offsets(token: &str, value_len: usize) -> Option<Vec<usize>> {
+    let mut offsets = Vec::with_capacity(value_len + 1);
+
+    if let Some(raw) = token.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let start = 1 + hashes + 1;
+        offsets.extend(start..=start + value_len);
+        return Some(offsets);
+    }
+
+    let mut position = token.strip_prefix('"').map(|_| 1)?;
+    loop {
+        let rest = &token[position..];
+        let mut chars = rest.chars();
+        let (written_len, value_char_len) = match (chars.next()?, chars.next()) {
+            ('"', _) => break,
+            //a `\` at the end of a line skips the line break and the whitespace after it
+            ('\\', Some('\n')) => {
+                let skipped = rest[2..].len() - rest[2..].trim_start().len();
+                (2 + skipped, 0)
+            }
+            ('\\', Some('x')) => (4, 1),
+            ('\\', Some('u')) => {
+                let end = rest.find('}')?;
+                let value = u32::from_str_radix(&rest[3..end], 16).ok()?;
+                (end + 1, char::from_u32(value)?.len_utf8())
+            }
+            ('\\', Some(_)) => (2, 1),
+            (char, _) => (char.len_utf8(), char.len_utf8()),
+        };
+
+        //every byte of an escaped character comes from the whole escape
+        let escaped = written_len != value_char_len;
+        offsets.extend((0..value_char_len).map(|byte| position + if escaped { 0 } else { byte }));
+        position += written_len;
+    }
+    offsets.push(position);
+
+    (offsets.len() == value_len + 1).then_some(offsets)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{value_offsets, PatternSource};
+    use crate::fragment::FilledFragment;
+    use proc_macro2::Span;
+    use syn::LitStr;
+
+    #[test]
+    fn test_value_offsets() {
+        assert_eq!(value_offsets(r#""a+""#, 2), Some(vec![1, 2, 3]));
+        assert_eq!(value_offsets(r#""\\d""#, 2), Some(vec![1, 3, 4]));
+        assert_eq!(
+            value_offsets(r#""\u{263A}\x41ä""#, 6),
+            Some(vec![1, 1, 1, 9, 13, 14, 15])
+        );
+        assert_eq!(value_offsets("\"a\\\n    b\"", 2), Some(vec![1, 8, 9]));
+        assert_eq!(value_offsets(r###"r#"\d"#"###, 2), Some(vec![3, 4, 5]));
+        assert_eq!(value_offsets(r#"b"a""#, 1), None);
+    }
+
+    #[test]
+    fn test_written_range() {
+        //`[a-z]{NAME}x`, whose `{NAME}` was filled in as `(?:\d+)`
+        let source = PatternSource::new(
+            LitStr::new("", Span::call_site()),
+            vec![FilledFragment {
+                filled: 5..12,
+                written: 5..11,
+            }],
+        );
+
+        assert_eq!(source.written_range(1..4), 1..4);
+        assert_eq!(source.written_range(8..10), 5..11);
+        assert_eq!(source.written_range(12..13), 11..12);
+        assert_eq!(source.written_range(5..5), 5..5);
+    }
+}
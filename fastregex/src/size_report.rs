@@ -0,0 +1,64 @@
+use proc_macro2::{TokenStream, TokenTree};
+use regexlib::automata::Automaton;
+
+/// Set to any value while building to have every `matcher!` print the size of what it generates,
+/// for budgeting code size when many matchers are embedded in one program. The size of the
+/// compiled function can then be compared with a tool like `cargo bloat` or `nm --size-sort`.
+/// Cargo does not know the macro reads this variable, so crates that are already built must be
+/// rebuilt (e.g., after `cargo clean -p <crate>`) for their matchers to be reported.
+pub const REPORT_SIZE_VARIABLE: &str = "FASTREGEX_REPORT_SIZE";
+
+pub struct SizeReport {
+    states: usize,
+    transitions: usize,
+}
+
+impl SizeReport {
+    pub fn new(automaton: &Automaton) -> Self {
+        Self {
+            states: automaton.state_count(),
+            transitions: automaton
+                .states()
+                .iter()
+                .map(|state| state.transitions.len())
+                .sum(),
+        }
+    }
+
+    pub fn report_if_requested(&self, function_name: &str, generated: &TokenStream) {
+        if std::env::var_os(REPORT_SIZE_VARIABLE).is_none() {
+            return;
+        }
+
+        eprintln!(
+            "fastregex: matcher `{}`: {} states, {} transitions, {} tokens generated",
+            function_name,
+            self.states,
+            self.transitions,
+            count_tokens(generated.clone())
+        );
+    }
+}
+
+fn count_tokens(tokens: TokenStream) -> usize {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            //the delimiters count as two tokens
+            TokenTree::Group(group) => 2 + count_tokens(group.stream()),
+            _ => 1,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+
+    use super::count_tokens;
+
+    #[test]
+    fn test_count_tokens() {
+        assert_eq!(count_tokens(quote!(fn f(a: u8) {})), 9);
+    }
+}
@@ -0,0 +1,97 @@
+use std::time::{Duration, Instant};
+
+use regexlib::automata::Automaton;
+
+/// Set to any value while building to have every `matcher!` print how long each step of turning
+/// its pattern into code took, for finding the patterns that slow a build down. Like
+/// [`REPORT_SIZE_VARIABLE`](crate::size_report::REPORT_SIZE_VARIABLE), matchers in crates that are
+/// already built are only reported once those crates are rebuilt.
+pub const REPORT_TIMING_VARIABLE: &str = "FASTREGEX_REPORT_TIMING";
+
+pub struct TimingReport {
+    last_step_end: Instant,
+    steps: Vec<(&'static str, Duration)>,
+    constructed_states: usize,
+    simplified_states: usize,
+}
+
+impl TimingReport {
+    pub fn start() -> Self {
+        Self {
+            last_step_end: Instant::now(),
+            steps: Vec::new(),
+            constructed_states: 0,
+            simplified_states: 0,
+        }
+    }
+
+    /// Records the time since the previous step (or the start) as spent on `step`.
+    pub fn step_done(&mut self, step: &'static str) {
+        let now = Instant::now();
+        self.steps.push((step, now - self.last_step_end));
+        self.last_step_end = now;
+    }
+
+    pub fn constructed(&mut self, automaton: &Automaton) {
+        self.step_done("constructing");
+        self.constructed_states = automaton.state_count();
+    }
+
+    pub fn simplified(&mut self, automaton: &Automaton) {
+        self.step_done("simplifying");
+        self.simplified_states = automaton.state_count();
+    }
+
+    pub fn report_if_requested(&self, function_name: &str) {
+        if std::env::var_os(REPORT_TIMING_VARIABLE).is_none() {
+            return;
+        }
+
+        eprintln!("fastregex: matcher `{}`: {}", function_name, self.summary());
+    }
+
+    fn summary(&self) -> String {
+        let steps = self
+            .steps
+            .iter()
+            .map(|(step, duration)| format!("{} {:.3?}", step, duration))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "{} (total {:.3?}); {} states constructed, {} after simplification",
+            steps,
+            self.steps.iter().map(|(_, duration)| *duration).sum::<Duration>(),
+            self.constructed_states,
+            self.simplified_states
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use regexlib::automata::Automaton;
+    use regexlib::parser::RegexEntry;
+
+    use super::TimingReport;
+
+    #[test]
+    fn test_summary() {
+        let mut report = TimingReport::start();
+        let regex = RegexEntry::parse("ab*").unwrap();
+        report.step_done("parsing");
+        let mut automaton = Automaton::from_regex(regex);
+        report.constructed(&automaton);
+        automaton.simplify();
+        report.simplified(&automaton);
+
+        let summary = report.summary();
+        assert!(summary.starts_with("parsing "));
+        assert!(summary.contains(", constructing "));
+        assert!(summary.contains(", simplifying "));
+        assert!(summary.ends_with(&format!(
+            "states constructed, {} after simplification",
+            automaton.state_count()
+        )));
+    }
+}
@@ -0,0 +1,18 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+
+/// Emits `message` as a compiler warning pointing at `span`. Procedural macros can not emit
+/// warnings of their own on stable Rust, but using a deprecated item does, with the item's note as
+/// the warning, so the warning is a use of a deprecated constant spanned to `span`.
+pub fn warning(message: &str, span: Span) -> TokenStream {
+    let usage = quote_spanned!(span=> fastregex_warning);
+
+    quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const fastregex_warning: () = ();
+            #usage
+        };
+    }
+}
@@ -0,0 +1,120 @@
+use fastregex::{matcher, matchers};
+
+matcher!(word, "^[a-z]+$");
+matcher!(inner_start, "(a^)|(b)c");
+matcher!(inner_end, "a(?:(b$)|(c)d?)");
+matcher!(foo_or_bar, "^foo|bar$");
+matcher!(number_prefix, "^[0-9]+");
+matcher!(lines_of_words, "(?m)(^[a-z]+$\n)*");
+matcher!(single_line, "(?-s)a.*b");
+matcher!(whole_word, r".*\b{start}cat\b{end}.*");
+matcher!(word_end_captures, r"([a-z]+)\b{end}(.*)");
+matchers! {
+    { search: true }
+    starts_with_number => "^[0-9]+",
+    ends_with_number => "[0-9]+$",
+    ends_with_a_or_has_b => "(a$)|(b)",
+    starts_with_foo_or_ends_with_bar => "^foo|bar$",
+    last_assignment => "([a-z]+)=([0-9]+)$",
+    has_error_line => "(?m)^error: [^\n]*$",
+    error_code => "(?m)^error: ([0-9]+)$",
+    has_word_start => r"\b{start}[0-9]",
+}
+
+#[test]
+fn test_anchors_at_the_ends() {
+    //a whole match already starts and ends at the ends of the input
+    assert!(word("abc"));
+    assert!(!word("abc1"));
+    assert_eq!(number_prefix_longest_prefix("123abc"), Some(3));
+    assert_eq!(number_prefix_shortest_match("123abc"), Some(1));
+}
+
+#[test]
+fn test_anchors_inside_the_pattern() {
+    assert!(inner_start("bc"));
+    assert!(!inner_start("ac"));
+
+    assert!(inner_end("ab"));
+    assert!(inner_end("acd"));
+    assert!(!inner_end("abd"));
+}
+
+#[test]
+fn test_anchors_in_alternation_branches() {
+    //`|` binds loosest, so each anchor belongs to the whole branch it is written in
+    assert!(foo_or_bar("foo"));
+    assert!(foo_or_bar("bar"));
+    assert!(!foo_or_bar("fooar"));
+    assert!(!foo_or_bar("fobar"));
+
+    assert!(starts_with_foo_or_ends_with_bar("foo!"));
+    assert!(starts_with_foo_or_ends_with_bar("a bar"));
+    assert!(!starts_with_foo_or_ends_with_bar("a foo"));
+    assert!(!starts_with_foo_or_ends_with_bar("bar!"));
+}
+
+#[test]
+fn test_search_anchors() {
+    assert!(starts_with_number("12 apples"));
+    assert!(!starts_with_number("apples: 12"));
+
+    assert!(ends_with_number("apples: 12"));
+    assert!(!ends_with_number("12 apples"));
+
+    assert!(ends_with_a_or_has_b("xa"));
+    assert!(ends_with_a_or_has_b("xbx"));
+    assert!(!ends_with_a_or_has_b("ax"));
+}
+
+#[test]
+fn test_multi_line_anchors() {
+    assert!(lines_of_words("ab\ncd\n"));
+    assert!(lines_of_words(""));
+    assert!(!lines_of_words("ab\nc1\n"));
+
+    assert!(has_error_line("error: disk full"));
+    assert!(has_error_line("ok\nerror: disk full\nok"));
+    assert!(!has_error_line("ok\nfatal error: disk full"));
+}
+
+#[test]
+fn test_dot_without_newlines() {
+    assert!(single_line("a b"));
+    assert!(!single_line("a\nb"));
+}
+
+#[test]
+fn test_word_boundaries() {
+    assert!(whole_word("a cat!"));
+    assert!(whole_word("cat"));
+    assert!(!whole_word("concat"));
+    assert!(!whole_word("cats"));
+
+    assert!(has_word_start("take 5"));
+    assert!(!has_word_start("a5"));
+
+    let captures = word_end_captures_captures("abc-d").unwrap();
+    assert_eq!(captures.get(1), Some("abc"));
+    assert_eq!(captures.get(2), Some("-d"));
+    assert!(word_end_captures_captures("abc1").is_none());
+}
+
+#[test]
+fn test_search_captures_anchors() {
+    let captures = last_assignment_captures("a=1 b=22").unwrap();
+    assert_eq!(captures.get(0), Some("b=22"));
+    assert_eq!(captures.get(1), Some("b"));
+    assert_eq!(captures.get(2), Some("22"));
+
+    assert!(last_assignment_captures("a=1 b").is_none());
+}
+
+#[test]
+fn test_search_captures_multi_line_anchors() {
+    let captures = error_code_captures("ok\nerror: 404\nerror: x").unwrap();
+    assert_eq!(captures.get(0), Some("error: 404"));
+    assert_eq!(captures.get(1), Some("404"));
+
+    assert!(error_code_captures("error: 404 x").is_none());
+}
@@ -0,0 +1,16 @@
+use fastregex::regex_assert_valid;
+use regexlib::runtime::Regex;
+
+//the runtime engine can not fail on a pattern that is checked at compile time
+const HOST_PATTERN: &str = "[a-z]+(-[a-z]+)*";
+
+regex_assert_valid!("[a-z]+(-[a-z]+)*");
+regex_assert_valid!("[a-z]{1500}", max_states = 2000, max_repetition = 1500);
+
+#[test]
+fn test_assert_valid_in_function() {
+    //expands to nothing, so it can stand anywhere an item or statement can
+    regex_assert_valid!("(GET)|(POST)", { branch: true });
+
+    assert!(Regex::new(HOST_PATTERN).unwrap().is_match("well-known"));
+}
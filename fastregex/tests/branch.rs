@@ -0,0 +1,17 @@
+use fastregex::matcher;
+
+matcher!(method_matcher, "(GET)|(POST)|([A-Z]+)", branch);
+matcher!(single_branch_matcher, "[a-z]+", branch);
+
+#[test]
+fn test_branch() {
+    assert_eq!(method_matcher_branch("GET"), Some(0));
+    assert_eq!(method_matcher_branch("POST"), Some(1));
+    assert_eq!(method_matcher_branch("PATCH"), Some(2));
+    assert_eq!(method_matcher_branch("get"), None);
+    assert!(method_matcher("PUT"));
+    assert!(!method_matcher(""));
+
+    assert_eq!(single_branch_matcher_branch("abc"), Some(0));
+    assert!(!single_branch_matcher("123"));
+}
@@ -0,0 +1,72 @@
+use fastregex::{matcher, matchers};
+
+matcher!(is_header, "Content-Length: [0-9]+", bytes);
+matchers! {
+    { bytes: true }
+    any_three => "...",
+    any_three_bytes => "(?-u)...",
+    replacement => "a\u{FFFD}b",
+    high_bytes => r"[a-z]+(?-u:[\x80-\xFF])+",
+    frame => r"(?-u)\x00\xFF(.{2})\xFE" { search: true },
+    word_then_invalid => r"\w+(?-u:\xFF)",
+    numbered_greeting => "[0-9]héllo",
+    is_word => "\\pL+",
+    is_tag => "(GET)|(POST)" { branch: true },
+    has_marker => "END" { search: true },
+}
+
+#[test]
+fn test_bytes() {
+    assert!(is_header(b"Content-Length: 42"));
+    assert!(!is_header(b"Content-Length: x"));
+    assert!(!is_header(b"Content-Type: 42"));
+    assert!(is_header("Content-Length: 7"));
+
+    assert!(is_word("héllo".as_bytes()));
+    assert!(!is_word(b"h\xFFllo"));
+    assert_eq!(is_tag_branch(b"POST"), Some(1));
+    assert!(has_marker(b"\x00\x01END\xFF"));
+
+    let mut matcher = is_header::Matcher::new();
+    assert!(matcher.is_match(b"Content-Length: 1"));
+}
+
+#[test]
+fn test_invalid_utf8() {
+    //`.` matches a whole character, which a byte that is not valid UTF-8 is not part of
+    assert!(!any_three(b"a\xFFb"));
+    assert!(!any_three(b"\xE2\x82\xFF"));
+    assert!(any_three("€ab".as_bytes()));
+    assert!(!any_three(b"\xE2\x82"));
+
+    //with the `u` flag cleared, it matches any single byte
+    assert!(any_three_bytes(b"a\xFFb"));
+    assert!(any_three_bytes(b"\xE2\x82\xFF"));
+    assert!(!any_three_bytes("€ab".as_bytes()));
+
+    //an invalid byte is not mistaken for the replacement character
+    assert!(replacement("a\u{FFFD}b".as_bytes()));
+    assert!(!replacement(b"a\xFFb"));
+}
+
+#[test]
+fn test_byte_classes() {
+    assert!(high_bytes(b"abc\xFF\x80"));
+    assert!(high_bytes("abcé".as_bytes()));
+    assert!(!high_bytes(b"abc"));
+
+    assert!(frame(b"\x01\x00\xFF\xC3\x28\xFE\x00"));
+    assert!(!frame(b"\x00\xFF\xC3\xFE"));
+
+    //`\u{FF}` is a character, encoded as two bytes, rather than the byte 0xFF
+    assert!(!word_then_invalid("héllo\u{FF}".as_bytes()));
+    assert!(word_then_invalid(b"h\xC3\xA9llo\xFF"));
+    assert!(!word_then_invalid(b"h\xE9llo\xFF"));
+}
+
+#[test]
+fn test_non_ascii_literals() {
+    assert!(numbered_greeting("1héllo".as_bytes()));
+    assert!(!numbered_greeting(b"1h\xE9llo"));
+    assert!(!numbered_greeting("1hello".as_bytes()));
+}
@@ -0,0 +1,102 @@
+use fastregex::{matcher, matchers};
+
+matcher!(date, "(\\d\\d\\d\\d)-(\\d\\d)-(\\d\\d)");
+matchers! {
+    key_value => "([a-z]+)=(?:([a-z]*)|(\\d+))",
+    repeated => "((ab)|(c))*d",
+    any => "(.+)[.](.+)",
+    find_version => "v(\\d+)[.](\\d+)" { search: true },
+    any_lazy => "(.+?)[.](.+)",
+    find_tag => "<(.+?)>" { search: true },
+}
+
+#[test]
+fn test_captures() {
+    let captures = date_captures("2023-04-01").unwrap();
+    assert_eq!(captures.get(0), Some("2023-04-01"));
+    assert_eq!(captures.get(1), Some("2023"));
+    assert_eq!(captures.get(2), Some("04"));
+    assert_eq!(captures.get(3), Some("01"));
+    assert_eq!(captures.range(2), Some(5..7));
+    assert_eq!(captures.len(), 1 + date::CAPTURE_COUNT);
+    assert!(date_captures("2023-04-1").is_none());
+}
+
+#[test]
+fn test_captures_that_do_not_participate() {
+    let captures = key_value_captures("name=").unwrap();
+    assert_eq!(captures.get(1), Some("name"));
+    assert_eq!(captures.get(2), Some(""));
+    assert_eq!(captures.get(3), None);
+
+    let captures = key_value_captures("name=42").unwrap();
+    assert_eq!(captures.get(1), Some("name"));
+    assert_eq!(captures.get(3), Some("42"));
+}
+
+#[test]
+fn test_captures_in_repetitions() {
+    //a group in a repetition holds what it matched in the last repetition
+    let captures = repeated_captures("abcabd").unwrap();
+    assert_eq!(captures.get(1), Some("ab"));
+    assert_eq!(captures.get(2), Some("ab"));
+    assert_eq!(captures.get(3), Some("c"));
+
+    let captures = repeated_captures("d").unwrap();
+    assert_eq!(captures.get(1), None);
+}
+
+#[test]
+fn test_captures_are_greedy() {
+    let captures = any_captures("archive.tar.gz").unwrap();
+    assert_eq!(captures.get(1), Some("archive.tar"));
+    assert_eq!(captures.get(2), Some("gz"));
+}
+
+#[test]
+fn test_lazy_captures() {
+    let captures = any_lazy_captures("archive.tar.gz").unwrap();
+    assert_eq!(captures.get(1), Some("archive"));
+    assert_eq!(captures.get(2), Some("tar.gz"));
+
+    //a search stops extending its match as soon as the lazy repetition can
+    let captures = find_tag_captures("<a><b>").unwrap();
+    assert_eq!(captures.get(0), Some("<a>"));
+    assert_eq!(captures.get(1), Some("a"));
+}
+
+#[test]
+fn test_search_captures() {
+    let captures = find_version_captures("released v1.22, then v2.0").unwrap();
+    assert_eq!(captures.get(0), Some("v1.22"));
+    assert_eq!(captures.range(0), Some(9..14));
+    assert_eq!(captures.get(2), Some("22"));
+    assert!(find_version_captures("no version").is_none());
+}
+
+matchers! {
+    named_date => "(?P<year>\\d\\d\\d\\d)-(?<month>\\d\\d)-(\\d\\d)",
+    reserved_names => "(?P<type>[a-z]+):(?P<len>\\d+)",
+}
+
+#[test]
+fn test_named_captures() {
+    let captures = named_date_captures("2023-04-01").unwrap();
+    assert_eq!(captures.year(), Some("2023"));
+    assert_eq!(captures.month(), Some("04"));
+    assert_eq!(captures.name("year"), Some("2023"));
+    assert_eq!(captures.name("day"), None);
+    assert_eq!(captures.get(3), Some("01"));
+    assert_eq!(
+        named_date::CAPTURE_NAMES,
+        [None, Some("year"), Some("month"), None]
+    );
+}
+
+#[test]
+fn test_names_without_accessors() {
+    let captures = reserved_names_captures("count:12").unwrap();
+    assert_eq!(captures.name("type"), Some("count"));
+    assert_eq!(captures.name("len"), Some("12"));
+    assert_eq!(captures.len(), 3);
+}
@@ -0,0 +1,92 @@
+use fastregex::matcher;
+
+matcher!(method, "(get)|(post)", case_insensitive);
+matcher!(inline_flag, "(?i)[a-c]+x");
+matcher!(kelvin, "(?i)k");
+matcher!(flag_cleared, "a(?i)b(?-i)c");
+matcher!(negated_class, "[^k]", case_insensitive);
+
+#[test]
+fn test_case_insensitive_option() {
+    assert!(method("GET"));
+    assert!(method("Post"));
+    assert!(!method("PUT"));
+}
+
+#[test]
+fn test_inline_flag() {
+    assert!(inline_flag("aBcX"));
+    assert!(!inline_flag("aBdX"));
+
+    //simple case folding also relates characters beyond upper and lower case
+    assert!(kelvin("K"));
+    assert!(kelvin("\u{212A}"));
+
+    assert!(flag_cleared("aBc"));
+    assert!(!flag_cleared("ABc"));
+    assert!(!flag_cleared("aBC"));
+
+    assert!(!negated_class("K"));
+    assert!(negated_class("x"));
+}
+
+matcher!(scoped_flag, "(?i:get)_(post)");
+matcher!(non_capturing, "(?:ab)+(c)");
+
+#[test]
+fn test_scoped_flag() {
+    assert!(scoped_flag("GeT_post"));
+    assert!(!scoped_flag("get_POST"));
+
+    assert!(non_capturing("ababc"));
+    assert_eq!(non_capturing_captures("abc").unwrap().get(1), Some("c"));
+}
+
+matcher!(versioned, "(?i)v[0-9]+");
+matcher!(find_keyword, "(?i)select", search);
+
+#[test]
+fn test_case_insensitive_prefix() {
+    //these start with a class of cases rather than a literal prefix, which is checked before running the automaton
+    assert!(versioned("V12"));
+    assert!(versioned("v3"));
+    assert!(!versioned("x3"));
+    assert!(!versioned(""));
+    assert_eq!(versioned_longest_prefix("v1x"), Some(2));
+    assert_eq!(versioned_longest_prefix("w1"), None);
+    assert_eq!(
+        versioned_find_iter("v1 and V22").collect::<Vec<_>>(),
+        vec![(0, 2), (7, 10)]
+    );
+
+    assert!(find_keyword("xx SeLeCt yy"));
+    assert!(!find_keyword("selec"));
+}
+
+matcher!(sharp_s, "(?i)ß");
+matcher!(street, "(?i)stra(?:ß|ss)e");
+
+#[test]
+fn test_simple_folding_only() {
+    //full folding would relate `ß` to `ss`, which has to be spelled out instead
+    assert!(sharp_s("\u{1E9E}"));
+    assert!(!sharp_s("ss"));
+    assert!(street("Straße"));
+    assert!(street("STRASSE"));
+}
+
+matcher!(full_street, "(?i)straße", { case_folding: full });
+matcher!(full_option, "office", { case_insensitive: true, case_folding: full });
+
+#[test]
+fn test_full_folding() {
+    assert!(full_street("Straße"));
+    assert!(full_street("STRASSE"));
+    assert!(full_street("strasse"));
+    assert!(!full_street("strase"));
+
+    //the `ﬃ` ligature folds to `ffi`
+    assert!(full_option("OFFICE"));
+    assert!(full_option("o\u{FB03}ce"));
+    assert!(!full_option("o\u{FB03}"));
+}
@@ -0,0 +1,49 @@
+use fastregex::matcher;
+use nom::bytes::complete::tag;
+use nom::sequence::separated_pair;
+use nom::Parser;
+use winnow::error::ContextError;
+
+matcher!(key, "[a-z]+(_[a-z]+)*");
+matcher!(value, "[0-9]+");
+
+#[test]
+fn test_longest_prefix() {
+    assert_eq!(key_longest_prefix("snake_case=1"), Some(10));
+    assert_eq!(key_longest_prefix("snake_=1"), Some(5));
+    assert_eq!(key_longest_prefix("=1"), None);
+    assert_eq!(
+        value::longest_prefix("12", &mut value::Scratch::new()),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_nom_parser() {
+    let mut assignment = separated_pair(
+        fastregex_rt::nom_parser::<nom::error::Error<&str>>(key_longest_prefix),
+        tag("="),
+        fastregex_rt::nom_parser(value_longest_prefix),
+    );
+
+    assert_eq!(
+        assignment.parse("max_depth=250;"),
+        Ok((";", ("max_depth", "250")))
+    );
+    assert!(assignment.parse("max_depth=;").is_err());
+}
+
+#[test]
+fn test_winnow_parser() {
+    use winnow::Parser;
+
+    let mut assignment = winnow::combinator::separated_pair(
+        fastregex_rt::winnow_parser::<ContextError>(key_longest_prefix),
+        "=",
+        fastregex_rt::winnow_parser(value_longest_prefix),
+    );
+
+    let mut input = "max_depth=250;";
+    assert_eq!(assignment.parse_next(&mut input), Ok(("max_depth", "250")));
+    assert_eq!(input, ";");
+}
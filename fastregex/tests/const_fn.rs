@@ -0,0 +1,67 @@
+use fastregex::{matcher, matchers};
+
+matcher!(const is_identifier, "[a-z_][a-z0-9_]*");
+matcher!(const is_version, "v[0-9]+[.][0-9]+");
+matcher!(pub const is_capitalized, r"\p{Lu}\p{Ll}*");
+matchers! {
+    const contains_digit => "[0-9]" { search: true },
+    const method => "(GET)|(POST)" { branch: true },
+    const is_tag => "<[a-z]+>" { bytes: true },
+    const IsWord => "[a-z]+" { struct_type: true },
+}
+
+//checked when the crate is compiled
+const _: () = assert!(is_identifier("snake_case"));
+const _: () = assert!(!is_identifier("9lives"));
+const _: () = assert!(is_version("v1.22"));
+const _: () = assert!(!is_version("1.22"));
+const _: () = assert!(is_capitalized("\u{C9}cole"));
+const _: () = assert!(contains_digit("abc4def"));
+const _: () = assert!(matches!(method_branch("POST"), Some(1)));
+const _: () = assert!(is_tag(b"<a>"));
+const _: () = assert!(IsWord.is_match("word"));
+
+#[test]
+fn test_const_matches_agrees_with_matches() {
+    for input in [
+        "",
+        "a",
+        "snake_case",
+        "9lives",
+        "v1.2",
+        "v1.",
+        "v",
+        "\u{C9}cole",
+        "\u{C9}COLE",
+        "abc4",
+        "GET",
+        "POST",
+        "PUT",
+        "w\u{f6}rd",
+    ] {
+        let mut scratch = is_identifier::Scratch::new();
+        assert_eq!(
+            is_identifier::const_matches(input),
+            is_identifier::matches(input, &mut scratch)
+        );
+        assert_eq!(
+            is_version::const_matches(input),
+            is_version::matches(input, &mut is_version::Scratch::new())
+        );
+        assert_eq!(
+            is_capitalized::const_matches(input),
+            is_capitalized::matches(input, &mut is_capitalized::Scratch::new())
+        );
+        assert_eq!(
+            contains_digit::const_matches(input),
+            contains_digit::matches(input, &mut contains_digit::Scratch::new())
+        );
+        assert_eq!(
+            method::const_matches(input),
+            method::matches(input, &mut method::Scratch::new())
+        );
+    }
+
+    assert!(!is_tag(b"<\xFF>"));
+    assert!(method("GET"));
+}
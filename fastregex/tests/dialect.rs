@@ -0,0 +1,27 @@
+use fastregex::matcher;
+
+matcher!(is_go_file, r"[\w-]+\.go", dialect = "re2");
+matcher!(
+    is_version,
+    r"v[[:digit:]]+(\.[[:digit:]]+)*",
+    dialect = "posix-ere"
+);
+matcher!(is_braced, r"\{a{2}\}", { dialect: "re2" });
+
+#[test]
+fn test_re2_dialect() {
+    assert!(is_go_file("main_test.go"));
+    assert!(is_go_file("-x.go"));
+    assert!(!is_go_file("main_testxgo"));
+    assert!(!is_go_file("main test.go"));
+
+    assert!(is_braced("{aa}"));
+    assert!(!is_braced("{a}"));
+}
+
+#[test]
+fn test_posix_ere_dialect() {
+    assert!(is_version("v1.2.10"));
+    assert!(!is_version("v1x2"));
+    assert!(!is_version("v1."));
+}
@@ -0,0 +1,21 @@
+use fastregex::{matcher, matchers, regex_assert_valid};
+
+matcher!(
+    is_ipv4,
+    r"\d{1,3}([.]\d{1,3}){3}",
+    matches = ["1.2.3.4", "255.255.255.255"],
+    rejects = ["1.2.3", "1.2.3.4.5", ""],
+);
+matcher!(is_cafe, "café", nfc, { matches: ["cafe\u{301}", "café"], rejects: ["cafe"] });
+matchers! {
+    { rejects: ["POST"] }
+    is_get => "GET" { matches: ["GET"] },
+}
+regex_assert_valid!("(ab)*", matches = ["", "abab"], rejects = ["aba"]);
+
+#[test]
+fn test_examples() {
+    assert!(is_ipv4("10.0.0.1"));
+    assert!(is_cafe("cafe\u{301}"));
+    assert!(is_get("GET"));
+}
@@ -0,0 +1,113 @@
+use fastregex::{matcher, matchers};
+
+matcher!(number, "[0-9]+");
+matchers! {
+    error_code => "error [0-9]+",
+    maybe_a => "a*",
+    word => "\\pL+",
+    cat_or_category => "cat(egory)?",
+}
+
+#[test]
+fn test_find_iter() {
+    let matches = number_find_iter("a1b22c333").collect::<Vec<_>>();
+    assert_eq!(matches, vec![(1, 2), (3, 5), (6, 9)]);
+    assert_eq!(number_find_iter("none").next(), None);
+    assert_eq!(
+        number::find_at("12 34", 1, &mut number::FindScratch::new()),
+        Some((1, 2))
+    );
+}
+
+#[test]
+fn test_find_iter_with_prefix() {
+    let log = "ok\nerror 404\nerror x\nerror 500";
+    let matches = error_code_find_iter(log)
+        .map(|(start, end)| &log[start..end])
+        .collect::<Vec<_>>();
+    assert_eq!(matches, vec!["error 404", "error 500"]);
+}
+
+#[test]
+fn test_find_iter_empty_matches() {
+    let matches = maybe_a_find_iter("aabé").collect::<Vec<_>>();
+    assert_eq!(matches, vec![(0, 2), (3, 3), (5, 5)]);
+}
+
+#[test]
+fn test_find_iter_longest() {
+    let text = "the cat's category: ünïcode";
+    let matches = cat_or_category_find_iter(text)
+        .map(|(start, end)| &text[start..end])
+        .collect::<Vec<_>>();
+    assert_eq!(matches, vec!["cat", "category"]);
+
+    let words = word_find_iter(text)
+        .map(|(start, end)| &text[start..end])
+        .collect::<Vec<_>>();
+    assert_eq!(words, vec!["the", "cat", "s", "category", "ünïcode"]);
+}
+
+//declares each matcher alongside its pattern, so that the matches it finds can be checked against the ones the runtime finds
+macro_rules! runtime_matchers {
+    ($($name:ident => $pattern:literal,)*) => {
+        matchers! {
+            $($name => $pattern,)*
+        }
+
+        const FIND_ITERS: &[(&str, fn(&str) -> Vec<(usize, usize)>)] =
+            &[$(($pattern, |input| $name::find_iter(input).collect()),)*];
+    };
+}
+
+runtime_matchers! {
+    lazy_plus => "a+?",
+    lazy_star => "a*?b",
+    lazy_optional => "<.??>",
+    earlier_branch => "a|ab",
+    later_branch => "ab|a",
+    optional_groups => "(ab)?(abcd)?",
+    greedy_then_lazy => "[a-z]+?[0-9]*",
+    nested_branches => "x(?:y|yz)*",
+}
+
+#[test]
+fn test_find_iter_matches_runtime() {
+    let inputs = [
+        "",
+        "a",
+        "aaab",
+        "ab abcd",
+        "abcdab",
+        "<>, <a>",
+        "ab12 c3",
+        "xyyzyz xyz",
+    ];
+
+    for (pattern, find_iter) in FIND_ITERS {
+        let regex = regexlib::runtime::Regex::new(pattern).unwrap();
+        for input in inputs {
+            let expected = regex
+                .find_iter(input)
+                .map(|found| (found.start(), found.end()))
+                .collect::<Vec<_>>();
+            assert_eq!(find_iter(input), expected, "{pattern:?} on {input:?}");
+        }
+    }
+}
+
+#[test]
+fn test_find_iter_leftmost_first() {
+    assert_eq!(
+        lazy_plus_find_iter("aaa").collect::<Vec<_>>(),
+        vec![(0, 1), (1, 2), (2, 3)]
+    );
+    assert_eq!(
+        earlier_branch_find_iter("ab").collect::<Vec<_>>(),
+        vec![(0, 1)]
+    );
+    assert_eq!(
+        later_branch_find_iter("ab").collect::<Vec<_>>(),
+        vec![(0, 2)]
+    );
+}
@@ -0,0 +1,74 @@
+use fastregex::{fragment, matcher, matchers, regex_assert_valid};
+
+fragment!(OCTET, r"(25[0-5])|(2[0-4]\d)|(1?\d?\d)");
+fragment!(IPV4, r"{OCTET}([.]{OCTET}){3}");
+
+matcher!(is_ipv4, r"{IPV4}");
+matchers! {
+    is_socket_address => r"{IPV4}:\d+",
+}
+
+fragment!(YEAR, r"\d{4}");
+matcher!(dated_note, r"{YEAR}-(\d\d): ([a-z]+)");
+//braces in a class are members rather than a fragment, so this uses none
+matcher!(braced_letters, r"[{a}]+");
+
+#[test]
+fn test_fragments() {
+    assert!(is_ipv4("192.168.0.1"));
+    assert!(is_ipv4("255.255.255.255"));
+    assert!(!is_ipv4("256.1.1.1"));
+    assert!(!is_ipv4("1.1.1"));
+
+    assert!(is_socket_address("10.0.0.1:8080"));
+    assert!(!is_socket_address("10.0.0.1"));
+
+    assert_eq!(OCTET, r"(25[0-5])|(2[0-4]\d)|(1?\d?\d)");
+    assert_eq!(is_ipv4::PATTERN, format!("(?:{IPV4})"));
+}
+
+#[macro_use]
+mod shared {
+    use fastregex::fragment;
+
+    fragment!(HEX_BYTE, "[0-9a-f]{2}");
+}
+
+mod uses_shared {
+    use fastregex::matcher;
+
+    matcher!(pub is_mac_address, "{HEX_BYTE}(:{HEX_BYTE}){5}");
+}
+
+//like a `macro_rules!` macro, a fragment is in scope in the modules declared after it
+mod child {
+    use fastregex::matcher;
+
+    matcher!(pub is_octet, "{OCTET}");
+}
+
+regex_assert_valid!("{OCTET}{OCTET}");
+
+#[test]
+fn test_fragment_scopes() {
+    assert!(uses_shared::is_mac_address("00:1a:2b:3c:4d:5e"));
+    assert!(!uses_shared::is_mac_address("00:1a:2b"));
+
+    assert!(child::is_octet("255"));
+    assert!(!child::is_octet("256"));
+}
+
+#[test]
+fn test_fragments_add_no_groups() {
+    assert_eq!(dated_note::CAPTURE_COUNT, 2);
+    let captures = dated_note_captures("2024-05: release").unwrap();
+    assert_eq!(captures.get(1), Some("05"));
+    assert_eq!(captures.get(2), Some("release"));
+}
+
+#[test]
+fn test_braces_in_classes() {
+    assert!(braced_letters("{a}"));
+    assert!(braced_letters("a}{"));
+    assert!(!braced_letters("b"));
+}
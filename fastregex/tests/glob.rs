@@ -0,0 +1,16 @@
+use fastregex::glob_matcher;
+
+glob_matcher!(is_archive, "*.{tar.gz,zip}");
+glob_matcher!(is_source_file, "src/**/*.rs", branch);
+
+#[test]
+fn test_glob_matcher() {
+    assert!(is_archive("backup.tar.gz"));
+    assert!(is_archive("backup.zip"));
+    assert!(!is_archive("backup.tarxgz"));
+    assert!(!is_archive("old/backup.zip"));
+
+    assert_eq!(is_source_file_branch("src/parser/mod.rs"), Some(0));
+    assert!(!is_source_file("tests/glob.rs"));
+    assert_eq!(is_archive::PATTERN, "*.{tar.gz,zip}");
+}
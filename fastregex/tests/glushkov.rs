@@ -0,0 +1,24 @@
+use fastregex::{matcher, matchers};
+
+matchers! {
+    { glushkov: true }
+    is_identifier => "[a-z_][a-z0-9_]*",
+    is_list => "(\\d+(,\\d+)*)?",
+    is_word => "(cat)|(dog)|(ca)" { branch: true },
+}
+matcher!(is_word_thompson, "(cat)|(dog)|(ca)", branch);
+
+#[test]
+fn test_glushkov_matchers() {
+    assert!(is_identifier("snake_case_2"));
+    assert!(!is_identifier("2fast"));
+
+    assert!(is_list(""));
+    assert!(is_list("1,22,333"));
+    assert!(!is_list("1,,2"));
+
+    for word in ["cat", "dog", "ca", "cow"] {
+        assert_eq!(is_word_branch(word), is_word_thompson_branch(word));
+    }
+    assert_eq!(is_word_branch("dog"), Some(1));
+}
@@ -0,0 +1,18 @@
+use fastregex::{ilike_matcher, like_matcher};
+
+like_matcher!(is_log_file, "%.log");
+like_matcher!(is_percentage, "%\\%", escape = '\\');
+ilike_matcher!(is_error_line, "error:%");
+
+#[test]
+fn test_like_matcher() {
+    assert!(is_log_file("app.log"));
+    assert!(!is_log_file("app.logs"));
+
+    assert!(is_percentage("100%"));
+    assert!(!is_percentage("100"));
+
+    assert!(is_error_line("ERROR: disk full"));
+    assert!(is_error_line("Error:"));
+    assert!(!is_error_line("warning: disk full"));
+}
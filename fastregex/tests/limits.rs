@@ -0,0 +1,17 @@
+use fastregex::matcher;
+
+//both limits are above their defaults here
+matcher!(
+    large_matcher,
+    "[a-z]{1500}",
+    max_states = 2000,
+    max_repetition = 1500
+);
+matcher!(limited_matcher, "(ab){3}", branch, max_repetition = 3);
+
+#[test]
+fn test_raised_limits() {
+    assert!(large_matcher("x".repeat(1500)));
+    assert!(!large_matcher("x".repeat(1499)));
+    assert_eq!(limited_matcher_branch("ababab"), Some(0));
+}
@@ -0,0 +1,42 @@
+use fastregex::{matcher, matchers};
+
+matcher!(newline_lines, r"(?m)(^[a-z]*$\n)*");
+matcher!(crlf_lines, r"(?m)(^[a-z]*$\R)*", line_terminator = "crlf");
+matcher!(unicode_line, r"(?-s).*\R", { line_terminator: "unicode" });
+matchers! {
+    { search: true, line_terminator: "crlf" }
+    error_code => r"(?m)^error: ([0-9]+)$",
+    any_but_line_end => r"(?-s)a.b",
+}
+
+#[test]
+fn test_newline_terminator() {
+    assert!(newline_lines("ab\ncd\n"));
+    //the `\r` is part of the line, so `[a-z]*` can not reach the `$`
+    assert!(!newline_lines("ab\r\ncd\n"));
+}
+
+#[test]
+fn test_crlf_terminator() {
+    assert!(crlf_lines("ab\r\ncd\n"));
+    assert!(crlf_lines("ab\rcd\r\n"));
+    assert!(!crlf_lines("ab\r\ncd\u{2028}"));
+
+    let captures = error_code_captures("ok\r\nerror: 404\r\nerror: x").unwrap();
+    assert_eq!(captures.get(0), Some("error: 404"));
+    assert_eq!(captures.get(1), Some("404"));
+    assert!(error_code("warn\rerror: 500\r"));
+    assert!(!error_code("error: 500 x\r\n"));
+
+    assert!(any_but_line_end("axb"));
+    assert!(!any_but_line_end("a\rb"));
+    assert!(!any_but_line_end("a\nb"));
+}
+
+#[test]
+fn test_unicode_terminator() {
+    assert!(unicode_line("abc\u{2028}"));
+    assert!(unicode_line("abc\u{85}"));
+    assert!(unicode_line("abc\r\n"));
+    assert!(!unicode_line("a\u{2029}c\n"));
+}
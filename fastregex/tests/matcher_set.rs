@@ -0,0 +1,76 @@
+use fastregex::{fragment, matcher_set};
+
+matcher_set!(classify, "[0-9]+", "[a-z]+", "[0-9a-z]+");
+matcher_set!(keyword, "if", "in", "int", "i[a-z]*");
+matcher_set!(prefixed, "key_[a-z]+", "key_[0-9]+",);
+
+fragment!(SET_DIGITS, "[0-9]+");
+matcher_set!(versions, "{SET_DIGITS}", "{SET_DIGITS}[.]{SET_DIGITS}");
+
+mod declared {
+    use fastregex::matcher_set;
+
+    //the repetition limit is above its default here, and the state limit is just above what the two patterns need together
+    matcher_set!(
+        pub(crate) long_words,
+        "[a-z]{1500}",
+        "[a-z]{1500}x",
+        max_repetition = 1500,
+        { max_states: 3010 }
+    );
+}
+
+#[test]
+fn test_matcher_set() {
+    assert_eq!(classify("123"), [true, false, true]);
+    assert_eq!(classify("abc"), [false, true, true]);
+    assert_eq!(classify("a1"), [false, false, true]);
+    assert_eq!(classify("A"), [false, false, false]);
+    assert_eq!(classify(""), [false, false, false]);
+}
+
+#[test]
+fn test_overlapping_patterns() {
+    assert_eq!(keyword("if"), [true, false, false, true]);
+    assert_eq!(keyword("int"), [false, false, true, true]);
+    assert_eq!(keyword("i"), [false, false, false, true]);
+    assert_eq!(keyword("x"), [false, false, false, false]);
+}
+
+#[test]
+fn test_shared_prefix() {
+    assert_eq!(prefixed("key_abc"), [true, false]);
+    assert_eq!(prefixed("key_42"), [false, true]);
+    assert_eq!(prefixed("key_"), [false, false]);
+    assert_eq!(prefixed("ke"), [false, false]);
+}
+
+#[test]
+fn test_fragments() {
+    assert_eq!(versions("12"), [true, false]);
+    assert_eq!(versions("1.2"), [false, true]);
+    assert_eq!(
+        versions::PATTERNS,
+        ["(?:[0-9]+)", "(?:[0-9]+)[.](?:[0-9]+)"]
+    );
+}
+
+#[test]
+fn test_scratch() {
+    let mut scratch = classify::Scratch::new();
+    assert_eq!(classify::matches("abc", &mut scratch), [false, true, true]);
+    assert_eq!(classify::matches("12", &mut scratch), [true, false, true]);
+}
+
+#[test]
+fn test_visibility_and_limits() {
+    assert_eq!(declared::long_words("a".repeat(1500)), [true, false]);
+    assert_eq!(declared::long_words("a".repeat(1499)), [false, false]);
+    assert_eq!(
+        declared::long_words::matches(
+            &format!("{}x", "a".repeat(1500)),
+            &mut declared::long_words::Scratch::new()
+        ),
+        [false, true]
+    );
+}
@@ -0,0 +1,27 @@
+use fastregex::matchers;
+
+matchers! {
+    { max_repetition: 300 }
+    is_get => "GET",
+    is_method => "(GET)|(POST)" { branch: true },
+    is_long => "a{300}",
+}
+
+matchers! {
+    is_digit => "[0-9]"
+}
+
+#[test]
+fn test_matchers() {
+    assert!(is_get("GET"));
+    assert!(!is_get("POST"));
+
+    assert_eq!(is_method_branch("POST"), Some(1));
+    assert!(is_method("GET"));
+
+    assert!(is_long("a".repeat(300)));
+    assert!(!is_long("a".repeat(299)));
+
+    assert!(is_digit("7"));
+    assert!(!is_digit("x"));
+}
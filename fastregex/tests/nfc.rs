@@ -0,0 +1,17 @@
+use fastregex::matcher;
+
+matcher!(decomposed_matcher, "cafe\u{301}s?", nfc);
+matcher!(composed_matcher, "caf\u{e9}s?", nfc);
+matcher!(plain_matcher, "cafe\u{301}s?");
+
+#[test]
+fn test_nfc_matcher() {
+    for matcher in [decomposed_matcher, composed_matcher] {
+        assert!(matcher("caf\u{e9}"));
+        assert!(matcher("cafe\u{301}s"));
+        assert!(!matcher("cafe"));
+    }
+
+    assert!(plain_matcher("cafe\u{301}"));
+    assert!(!plain_matcher("caf\u{e9}"));
+}
@@ -0,0 +1,44 @@
+use fastregex::matcher;
+
+matcher!(options_matcher, "(GET)|(POST)", { branch: true, max_states: 64 });
+matcher!(disabled_options_matcher, "cafe\u{301}", { nfc: false, branch: false, });
+matcher!(mixed_options_matcher, "(a{300})|(b)", branch, { max_repetition: 300 });
+
+#[test]
+fn test_options_block() {
+    assert_eq!(options_matcher_branch("POST"), Some(1));
+    assert!(!options_matcher("PUT"));
+
+    //without nfc, the composed form of the pattern does not match
+    assert!(disabled_options_matcher("cafe\u{301}"));
+    assert!(!disabled_options_matcher("caf\u{e9}"));
+
+    assert_eq!(mixed_options_matcher_branch("a".repeat(300)), Some(0));
+}
+
+matcher!(ascii_token_matcher, r"\w+(\s\d+)?", unicode = false);
+matcher!(unicode_token_matcher, r"\w+(\s\d+)?", { unicode: true });
+
+#[test]
+fn test_unicode_option() {
+    assert!(ascii_token_matcher("port 8080"));
+    assert!(!ascii_token_matcher("caf\u{e9}"));
+    assert!(!ascii_token_matcher("port\u{a0}8080"));
+    assert!(!ascii_token_matcher("port \u{663}"));
+
+    assert!(unicode_token_matcher("caf\u{e9}"));
+    assert!(unicode_token_matcher("port\u{a0}\u{663}"));
+}
+
+matcher!(unanchored_matcher, "ab+c", { anchored: false, case_insensitive: true, engine: dfa });
+matcher!(anchored_matcher, "ab+c", anchored, engine = glushkov);
+
+#[test]
+fn test_anchored_and_engine_options() {
+    //`anchored: false` finds the pattern anywhere, as `search` does
+    assert!(unanchored_matcher("xxABbCxx"));
+    assert!(!unanchored_matcher("xxacxx"));
+
+    assert!(anchored_matcher("abbc"));
+    assert!(!anchored_matcher("xabbc"));
+}
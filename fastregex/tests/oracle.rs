@@ -0,0 +1,54 @@
+use fastregex::matcher;
+use regexlib::automata::Automaton;
+use regexlib::parser::RegexEntry;
+
+//declares each matcher alongside its pattern, so that the generated code can be checked against the automaton simulated at runtime
+macro_rules! oracle_matchers {
+    ($($name:ident => $pattern:literal,)*) => {
+        $(matcher!($name, $pattern);)*
+
+        const MATCHERS: &[(&str, fn(&str) -> bool)] = &[$(($pattern, |input| $name(input)),)*];
+    };
+}
+
+oracle_matchers! {
+    literal => "abc",
+    alternation => "(GET)|(POST)|(PUT)",
+    repetition => "a*b+c?",
+    counted => "x{2,4}y{3}",
+    classes => "[a-z0-9_]+[^.]",
+    unicode => r"\p{Lu}\PL*",
+    any => "a.{2,3}",
+    nested => "((ab)|(a[0-9]*))*z",
+    one_pass => "a*b[0-9]+(x|y)?",
+    one_pass_alternation => "(ab)|(cd*)",
+    lazy => "a*?b+?c??x{2,4}?",
+    anchored => "^a(b$)|(c)d*$",
+    inner_anchors => "(a^)|(b)c",
+    empty_anchors => "$^",
+}
+
+const INPUTS: &[&str] = &[
+    "", "a", "abc", "abcd", "GET", "POST", "PUT", "PO", "b", "ab", "abbc", "aabbbc", "xxyyy",
+    "xxxxyyy", "xxxxxyyy", "xyyy", "a_1!", "abc.", "Ü", "Üab", "Üä", "a12", "a123", "a1", "abz",
+    "a12abz", "aaz", "z", "éz", "b1", "aab12x", "b1y", "b1xy", "bx", "cddd", "abd",
+    "aabbcxx", "bxxxx", "bcx", "acddd", "abd", "bc", "ac",
+];
+
+#[test]
+fn test_matchers_agree_with_runtime_automaton() {
+    for (pattern, matcher) in MATCHERS {
+        //the automaton as constructed, before any of the simplification the generated code is built from
+        let automaton = Automaton::from_regex(RegexEntry::parse(pattern).unwrap());
+
+        for input in INPUTS {
+            assert_eq!(
+                matcher(input),
+                automaton.is_match(input),
+                "{:?} disagrees with its automaton on {:?}",
+                pattern,
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::borrow::Cow;
+
+use fastregex::{matcher, matchers};
+
+matcher!(number, "[0-9]+");
+matchers! {
+    password => "password=[^ ]*",
+    spaces => " *",
+}
+
+#[test]
+fn test_replace() {
+    assert_eq!(number_replace("a1b22c333", "#"), "a#b22c333");
+    assert_eq!(number_replace_all("a1b22c333", "#"), "a#b#c#");
+    assert!(matches!(number_replace_all("none", "#"), Cow::Borrowed("none")));
+}
+
+#[test]
+fn test_replace_with_prefix() {
+    assert_eq!(
+        password_replace_all("user=me password=hunter2 password= ok", "password=***"),
+        "user=me password=*** password=*** ok"
+    );
+}
+
+#[test]
+fn test_replace_empty_matches() {
+    assert_eq!(spaces_replace_all("a  b", "_"), "_a_b_");
+    assert_eq!(spaces_replace("ab", "_"), "_ab");
+}
@@ -0,0 +1,44 @@
+use fastregex::matcher;
+
+matcher!(scratch_matcher, "[a-z]+[0-9]*");
+matcher!(scratch_branch_matcher, "(GET)|(POST)", branch);
+
+#[test]
+fn test_caller_provided_scratch() {
+    let mut scratch = scratch_matcher::Scratch::new();
+
+    //each call starts over, whatever the previous one left behind
+    assert!(scratch_matcher::matches("abc123", &mut scratch));
+    assert!(!scratch_matcher::matches("abc12x", &mut scratch));
+    assert!(scratch_matcher::matches("x", &mut scratch));
+    assert!(!scratch_matcher::matches("1", &mut scratch));
+
+    let mut scratch = scratch_branch_matcher::Scratch::default();
+    assert_eq!(scratch_branch_matcher::matches("POST", &mut scratch), Some(1));
+    assert_eq!(scratch_branch_matcher::matches("GET", &mut scratch), Some(0));
+}
+
+#[test]
+fn test_reused_matcher() {
+    let mut matcher = scratch_matcher::Matcher::new();
+    for _ in 0..3 {
+        assert!(matcher.is_match("abc123"));
+        assert!(!matcher.is_match("123"));
+        assert!(matcher.is_match(String::from("z")));
+    }
+
+    let mut matcher = scratch_branch_matcher::Matcher::default();
+    assert_eq!(matcher.matching_branch("POST"), Some(1));
+    assert!(matcher.is_match("GET"));
+    assert!(!matcher.is_match("PUT"));
+}
+
+#[test]
+fn test_stack_bytes() {
+    assert_eq!(
+        scratch_matcher::STACK_BYTES,
+        core::mem::size_of::<scratch_matcher::Scratch>()
+    );
+    //two buffers of states and one of marks, each a byte per state with this few states
+    const { assert!(scratch_matcher::STACK_BYTES >= 3 * scratch_matcher::STATE_COUNT) };
+}
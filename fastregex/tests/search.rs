@@ -0,0 +1,37 @@
+use fastregex::{matcher, matchers};
+
+matcher!(contains_error, "error[0-9]+", search);
+matchers! {
+    { search: true }
+    contains_greeting => "(hello)|(goodbye)" { branch: true },
+    contains_date => "\\d\\d\\d\\d-\\d\\d-\\d\\d",
+}
+matcher!(is_error, "error[0-9]+");
+
+#[test]
+fn test_search() {
+    assert!(contains_error("error42"));
+    assert!(contains_error("fatal: error42 in main"));
+    assert!(contains_error("errorerror7"));
+    assert!(!contains_error("error in main"));
+    assert!(!contains_error(""));
+
+    assert!(!is_error("fatal: error42 in main"));
+}
+
+#[test]
+fn test_search_branch() {
+    assert_eq!(contains_greeting_branch("well, hello there"), Some(0));
+    assert_eq!(contains_greeting_branch("goodbye!"), Some(1));
+    assert_eq!(contains_greeting_branch("good day"), None);
+    assert!(contains_greeting("oh hello"));
+}
+
+#[test]
+fn test_search_match_ends() {
+    let input = "released 2023-04-01, patched 2023-05-12";
+    assert!(contains_date(input));
+    assert_eq!(contains_date_shortest_match(input), Some(19));
+    assert_eq!(contains_date_longest_prefix(input), Some(input.len()));
+    assert_eq!(contains_date_shortest_match("no dates"), None);
+}
@@ -1,3 +1,6 @@
+//the original test compares with true and false, which clippy would have written as assert!
+#![allow(clippy::bool_assert_comparison)]
+
 use fastregex::matcher;
 
 matcher!(
@@ -7,7 +10,9 @@ matcher!(
 
 #[test]
 fn test_matcher() {
-    assert_eq!(https_matcher("http://test"), true);
+    //| binds loosest, so the second branch is a bare host name without the scheme
+    assert_eq!(https_matcher("http://test"), false);
+    assert_eq!(https_matcher("example.com"), true);
     assert_eq!(https_matcher("http:/"), false);
     assert_eq!(https_matcher("http://"), false);
     assert_eq!(
@@ -20,3 +25,230 @@ fn test_matcher() {
         false
     );
 }
+
+matcher!(single_class_matcher, "[a][b]");
+
+#[test]
+fn test_matcher_checks_final_character() {
+    assert!(single_class_matcher("ab"));
+    assert!(!single_class_matcher("a"));
+    assert!(!single_class_matcher("abb"));
+}
+
+matcher!(multi_byte_matcher, "[à-ÿ]€.[𝄞]");
+
+#[test]
+fn test_matcher_multi_byte_chars() {
+    assert!(multi_byte_matcher("é€ß𝄞"));
+    assert!(multi_byte_matcher("ÿ€a𝄞"));
+    assert!(!multi_byte_matcher("e€ß𝄞"));
+    assert!(!multi_byte_matcher("é€ß"));
+}
+
+matcher!(literal_chain_matcher, "[a-z]+(?:(foo)|(foobar))[0-9]");
+
+#[test]
+fn test_matcher_literal_chains() {
+    assert!(literal_chain_matcher("xfoo1"));
+    assert!(literal_chain_matcher("xfoobar1"));
+    assert!(!literal_chain_matcher("xfoo"));
+    assert!(!literal_chain_matcher("xfoob1"));
+    assert!(!literal_chain_matcher("xfo1"));
+    assert!(!literal_chain_matcher("xfoobar"));
+}
+
+matcher!(range_dispatch_matcher, "[x]((a)|([a-c]y)|([^b]z))");
+
+#[test]
+fn test_matcher_range_dispatch() {
+    assert!(range_dispatch_matcher("xa"));
+    assert!(range_dispatch_matcher("xay"));
+    assert!(range_dispatch_matcher("xaz"));
+    assert!(range_dispatch_matcher("xby"));
+    assert!(range_dispatch_matcher("x\u{10FFFF}z"));
+    assert!(!range_dispatch_matcher("xbz"));
+    assert!(!range_dispatch_matcher("xdy"));
+    assert!(!range_dispatch_matcher("xb"));
+}
+
+matcher!(generation_wrap_matcher, "[a-z]*((ab)|(b))*x");
+
+#[test]
+fn test_matcher_long_input() {
+    //longer than the generation counter of a small automaton can count
+    let long_input = "ab".repeat(1000) + "x";
+    assert!(generation_wrap_matcher(&long_input));
+    assert!(!generation_wrap_matcher(&long_input[..long_input.len() - 1]));
+}
+
+matcher!(ascii_table_matcher, "[A-Za-z.]+[^0-9]");
+
+#[test]
+fn test_matcher_ascii_tables() {
+    assert!(ascii_table_matcher("ab.C!"));
+    assert!(ascii_table_matcher("a\u{e9}"));
+    assert!(!ascii_table_matcher("a\u{e9}b"));
+    assert!(!ascii_table_matcher("ab1"));
+    assert!(!ascii_table_matcher("a_b"));
+}
+
+#[test]
+fn test_matcher_match_str() {
+    let input = String::from("xfoo1");
+
+    let matched = literal_chain_matcher_match_str(&input).unwrap();
+    assert_eq!(matched, "xfoo1");
+    assert_eq!(matched.as_ptr(), input.as_ptr());
+    assert_eq!(literal_chain_matcher_match_str("xfoo"), None);
+}
+
+matcher!(pattern_matcher, "a{0,}(b|c){1,}");
+
+#[test]
+fn test_pattern() {
+    assert_eq!(pattern_matcher::PATTERN, "a{0,}(b|c){1,}");
+    assert_eq!(pattern_matcher::NORMALIZED_PATTERN, "a*(b|c)+");
+    assert_eq!(
+        pattern_matcher::Matcher::new().pattern(),
+        pattern_matcher::PATTERN
+    );
+}
+
+matcher!(delimiter_matcher, "[a-z]*;+");
+matcher!(prefixed_delimiter_matcher, "end[0-9]?");
+
+#[test]
+fn test_shortest_match() {
+    assert_eq!(delimiter_matcher_shortest_match("abc;;;rest"), Some(4));
+    assert_eq!(delimiter_matcher_shortest_match(";"), Some(1));
+    assert_eq!(delimiter_matcher_shortest_match("abc"), None);
+    assert_eq!(delimiter_matcher_shortest_match("ab,;"), None);
+    assert_eq!(delimiter_matcher_shortest_match("é;"), None);
+
+    assert_eq!(prefixed_delimiter_matcher_shortest_match("end7"), Some(3));
+    assert_eq!(prefixed_delimiter_matcher_shortest_match("en"), None);
+}
+
+matcher!(
+    escaped_matcher,
+    r"([a-z]+\.rs)|(\(\*\+\?\))|(\{\[\\\]\})|(a\|b)"
+);
+
+#[test]
+fn test_matcher_escaped_metacharacters() {
+    assert!(escaped_matcher("main.rs"));
+    assert!(!escaped_matcher("mainxrs"));
+    assert!(escaped_matcher("(*+?)"));
+    assert!(escaped_matcher("{[\\]}"));
+    assert!(escaped_matcher("a|b"));
+    assert!(!escaped_matcher("a"));
+}
+
+matcher!(tsv_line_matcher, r"([^\t\n]*\t)*[^\t\n]*\r?\n");
+
+#[test]
+fn test_matcher_control_escapes() {
+    assert!(tsv_line_matcher("name\tage\n"));
+    assert!(tsv_line_matcher("a\tb\tc\r\n"));
+    assert!(!tsv_line_matcher("a\tb"));
+    assert!(!tsv_line_matcher("a\nb\n"));
+}
+
+matcher!(ansi_color_matcher, r"\033\[[0-9;]*m[^\c[]*\c[\[0m");
+
+#[test]
+fn test_matcher_octal_and_control_char_escapes() {
+    assert!(ansi_color_matcher("\x1b[1;31merror\x1b[0m"));
+    assert!(!ansi_color_matcher("\x1b[31merror"));
+    assert!(!ansi_color_matcher("[31merror[0m"));
+}
+
+matcher!(version_matcher, "(|v)[0-9]+(|[a-z])");
+
+#[test]
+fn test_matcher_empty_alternation_branches() {
+    assert!(version_matcher("2"));
+    assert!(version_matcher("v12b"));
+    assert!(!version_matcher("vv2"));
+    assert!(!version_matcher("v2bc"));
+}
+
+matcher!(
+    log_lines_matcher,
+    r"(\h*[A-Z]+:\h*\V*\R)+",
+    line_terminator = "unicode"
+);
+
+#[test]
+fn test_matcher_line_whitespace_and_breaks() {
+    assert!(log_lines_matcher("INFO: started\r\nWARN:\tslow\n"));
+    assert!(log_lines_matcher("  ERROR:\u{a0}disk full\u{2028}"));
+    assert!(!log_lines_matcher("INFO: started\r\n\nWARN: slow\n"));
+    assert!(!log_lines_matcher("INFO: started"));
+}
+
+matcher!(
+    verbose_matcher,
+    r"(?x)
+    (?P<user> [a-z0-9._]+ )    # before the @
+    @
+    (?P<domain> [a-z0-9-]+ (\. [a-z0-9-]+)+ )
+    (\ \# [0-9]+)?    # an optional ticket number
+    "
+);
+
+#[test]
+fn test_matcher_verbose_mode() {
+    assert!(verbose_matcher("jane.doe@example.com"));
+    assert!(verbose_matcher("jane@mail.example.org #12"));
+    assert!(!verbose_matcher("jane @example.com"));
+    assert!(!verbose_matcher("jane@example"));
+}
+
+matcher!(quoted_matcher, r"\Q$1.00 (USD)\E[0-9]*");
+
+#[test]
+fn test_matcher_quoted() {
+    assert!(quoted_matcher("$1.00 (USD)"));
+    assert!(quoted_matcher("$1.00 (USD)25"));
+    assert!(!quoted_matcher("$1x00 (USD)"));
+    assert!(!quoted_matcher("1.00 USD"));
+}
+
+matcher!(posix_identifier_matcher, "[[:alpha:]_][[:alnum:]_]*");
+
+#[test]
+fn test_matcher_posix_classes() {
+    assert!(posix_identifier_matcher("_private2"));
+    assert!(posix_identifier_matcher("x"));
+    assert!(!posix_identifier_matcher("2x"));
+    assert!(!posix_identifier_matcher("naïve"));
+}
+
+matcher!(consonant_word_matcher, "[a-z&&[^aeiou]]+");
+matcher!(identifier_tail_matcher, "[[:word:]--[0-9]][[:word:]]*");
+
+#[test]
+fn test_matcher_class_set_operations() {
+    assert!(consonant_word_matcher("rhythm"));
+    assert!(!consonant_word_matcher("word"));
+    assert!(!consonant_word_matcher("RHYTHM"));
+    assert!(identifier_tail_matcher("_x9"));
+    assert!(!identifier_tail_matcher("9x"));
+}
+
+matcher!(nested_class_matcher, "[[a-z][0-9]_]+");
+
+#[test]
+fn test_matcher_nested_classes() {
+    assert!(nested_class_matcher("snake_case_2"));
+    assert!(!nested_class_matcher("camelCase"));
+}
+
+matcher!(bracket_matcher, r"[\[\]\-\\\^]+");
+
+#[test]
+fn test_matcher_class_escapes() {
+    assert!(bracket_matcher(r"[-]\^"));
+    assert!(!bracket_matcher("[a]"));
+}
@@ -0,0 +1,31 @@
+use fastregex::{matcher, matchers};
+
+matcher!(separator, "[,;] *");
+matchers! {
+    arrow => "->",
+    maybe_space => " *",
+}
+
+#[test]
+fn test_split() {
+    let pieces = separator_split("a, b;c,  d").collect::<Vec<_>>();
+    assert_eq!(pieces, vec!["a", "b", "c", "d"]);
+
+    let pieces = separator_split(",a,").collect::<Vec<_>>();
+    assert_eq!(pieces, vec!["", "a", ""]);
+
+    assert_eq!(separator_split("").collect::<Vec<_>>(), vec![""]);
+}
+
+#[test]
+fn test_split_with_prefix() {
+    let pieces = arrow_split("a->b->c").collect::<Vec<_>>();
+    assert_eq!(pieces, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_split_empty_matches() {
+    //like regexlib's split, empty matches separate every character
+    let pieces = maybe_space_split("ab c").collect::<Vec<_>>();
+    assert_eq!(pieces, vec!["", "a", "b", "c", ""]);
+}
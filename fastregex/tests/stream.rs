@@ -0,0 +1,58 @@
+use fastregex::{matcher, matchers};
+
+matcher!(number, "[0-9]+");
+matchers! {
+    greeting => "hello, [a-z]+!",
+    command => "(GET)|(POST)" { branch: true },
+    has_end => "END" { search: true },
+}
+
+fn fed<Stream>(mut stream: Stream, chunks: &[&str], feed: impl Fn(&mut Stream, &str)) -> Stream {
+    for chunk in chunks {
+        feed(&mut stream, chunk);
+    }
+    stream
+}
+
+#[test]
+fn test_stream() {
+    let mut stream = number::Stream::new();
+    assert!(!stream.is_match());
+    stream.feed("12");
+    assert!(stream.is_match());
+    stream.feed("3");
+    assert!(stream.is_match());
+    stream.feed("x");
+    assert!(!stream.is_match());
+    stream.feed("4");
+    assert!(!stream.is_match());
+
+    stream.reset();
+    stream.feed("4");
+    assert!(stream.is_match());
+}
+
+#[test]
+fn test_stream_prefix_across_chunks() {
+    let feed = |stream: &mut greeting::Stream, chunk: &str| stream.feed(chunk);
+    assert!(fed(greeting::Stream::new(), &["hel", "lo, ", "world!"], feed).is_match());
+    assert!(fed(greeting::Stream::new(), &["h", "", "ello, wo", "rld!"], feed).is_match());
+    assert!(!fed(greeting::Stream::new(), &["hel"], feed).is_match());
+    assert!(!fed(greeting::Stream::new(), &["help", "o, world!"], feed).is_match());
+}
+
+#[test]
+fn test_stream_branch() {
+    let mut stream = command::Stream::new();
+    stream.feed("PO");
+    assert_eq!(stream.matching_branch(), None);
+    stream.feed("ST");
+    assert_eq!(stream.matching_branch(), Some(1));
+}
+
+#[test]
+fn test_stream_search() {
+    let feed = |stream: &mut has_end::Stream, chunk: &str| stream.feed(chunk);
+    assert!(fed(has_end::Stream::new(), &["data E", "ND more data"], feed).is_match());
+    assert!(!fed(has_end::Stream::new(), &["data E", "N D"], feed).is_match());
+}
@@ -0,0 +1,68 @@
+use fastregex::{matcher, matchers};
+use fastregex_rt::MatcherType;
+
+matcher!(
+    /// Lowercase words.
+    pub IsWord,
+    "[a-z]+",
+    struct_type
+);
+matcher!(HTTPMethod, "(GET)|(POST)", struct_type, branch);
+matchers! {
+    { struct_type: true }
+    IsTag => "<[a-z]+>" { bytes: true },
+    Greeting => "(?<name>[a-z]+)!",
+}
+
+//a type that is generic over the pattern it matches
+struct Filter<M: MatcherType<Input = str>> {
+    matcher: M,
+}
+
+impl<M: MatcherType<Input = str>> Filter<M> {
+    fn keep<'a>(&self, inputs: &[&'a str]) -> Vec<&'a str> {
+        inputs
+            .iter()
+            .copied()
+            .filter(|input| self.matcher.is_match(input))
+            .collect()
+    }
+}
+
+#[test]
+fn test_struct_type() {
+    assert!(IsWord.is_match("abc"));
+    assert!(!IsWord.is_match("ABC"));
+    assert_eq!(IsWord::pattern(), "[a-z]+");
+    assert_eq!(<IsWord as MatcherType>::PATTERN, "[a-z]+");
+    assert_eq!(IsWord.match_str("word"), Some("word"));
+    assert_eq!(IsWord.shortest_match("ab1"), Some(1));
+    assert_eq!(IsWord.longest_prefix("ab1"), Some(2));
+    assert_eq!(IsWord.replace_all("ab 12 cd", "x"), "x 12 x");
+    assert_eq!(IsWord.split("ab1cd2").collect::<Vec<_>>(), ["", "1", "2"]);
+
+    let mut matcher = IsWord.matcher();
+    assert!(matcher.is_match("word"));
+}
+
+#[test]
+fn test_struct_type_options() {
+    assert_eq!(HTTPMethod.matching_branch("POST"), Some(1));
+    assert!(!HTTPMethod.is_match("PUT"));
+    assert!(IsTag.is_match(b"<a>"));
+    assert_eq!(Greeting.captures("hi!").unwrap().name("name"), Some("hi"));
+}
+
+#[test]
+fn test_module_named_in_snake_case() {
+    assert_eq!(is_word::PATTERN, "[a-z]+");
+    assert_eq!(http_method::PATTERN, "(GET)|(POST)");
+    assert!(is_tag::matches(b"<b>", &mut is_tag::Scratch::new()));
+}
+
+#[test]
+fn test_generic_over_matcher() {
+    let filter = Filter { matcher: IsWord };
+    assert_eq!(filter.keep(&["ab", "1", "cd"]), ["ab", "cd"]);
+    assert_eq!(Filter { matcher: Greeting }.keep(&["hi!", "hi"]), ["hi!"]);
+}
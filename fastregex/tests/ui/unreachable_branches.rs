@@ -0,0 +1,8 @@
+#![deny(deprecated)]
+
+use fastregex::matcher;
+
+matcher!(repeated_branch_matcher, "(GET)|(POST)|(GET)");
+matcher!(covered_branch_matcher, "([a-z]+)|(abc)");
+
+fn main() {}
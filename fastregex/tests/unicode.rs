@@ -0,0 +1,76 @@
+use fastregex::matcher;
+
+matcher!(unicode_class_matcher, r"\p{Lu}\pL*\d+\PL");
+matcher!(ascii_prefix_matcher, "abc[0-9]*");
+matcher!(shorthand_class_matcher, r"\w+\s+\W\S");
+matcher!(bracketed_shorthand_matcher, r"[\w.-]+@[\w--\d]+");
+matcher!(greek_word_matcher, r"\p{Greek}+\P{Greek}");
+matcher!(bracketed_property_matcher, r"[\p{Greek}\d]+[^\pL]");
+matcher!(short_display_name, r"\X{1,3}");
+
+#[test]
+fn test_unicode_classes() {
+    assert!(unicode_class_matcher("\u{3a3}abc12!"));
+    assert!(unicode_class_matcher("A\u{e9}\u{661} "));
+    assert!(!unicode_class_matcher("abc12!"));
+    assert!(!unicode_class_matcher("Aabc12x"));
+    assert!(!unicode_class_matcher("Aabc!"));
+}
+
+#[test]
+fn test_scripts() {
+    assert!(greek_word_matcher("\u{3b1}\u{3b2}\u{3b3}!"));
+    //letters from the Greek Extended block, with and without diacritics, are in the script too
+    assert!(greek_word_matcher("\u{1f00}\u{3b1} "));
+    assert!(!greek_word_matcher("abc!"));
+    assert!(!greek_word_matcher("\u{3b1}\u{3b2}"));
+    //the Cyrillic а looks like the Greek α, but belongs to a different script
+    assert!(greek_word_matcher("\u{3b1}\u{430}"));
+}
+
+#[test]
+fn test_grapheme_clusters() {
+    assert!(short_display_name("abc"));
+    assert!(!short_display_name("abcd"));
+    //each of these is a single cluster of several characters
+    assert!(short_display_name("e\u{301}e\u{301}e\u{301}"));
+    assert!(short_display_name(
+        "\u{1f1fa}\u{1f1f8}\u{1f468}\u{200d}\u{1f469}\r\n"
+    ));
+    assert!(short_display_name("\u{1100}\u{1161}\u{11a8}"));
+    assert!(!short_display_name(""));
+}
+
+#[test]
+fn test_shorthand_classes() {
+    assert!(shorthand_class_matcher("snake_case \t-x"));
+    //word characters and whitespace outside of ASCII: a combining accent, a Roman numeral and an ideographic space
+    assert!(shorthand_class_matcher("e\u{301}\u{216b}\u{3000}!\u{e9}"));
+    assert!(!shorthand_class_matcher("a- -x"));
+    assert!(!shorthand_class_matcher("a a x"));
+    assert!(!shorthand_class_matcher("a -\u{85}"));
+}
+
+#[test]
+fn test_bracketed_shorthand_classes() {
+    assert!(bracketed_shorthand_matcher("j\u{f6}rg.m-1@example"));
+    assert!(bracketed_shorthand_matcher("\u{216b}@\u{3b1}\u{3b2}"));
+    assert!(!bracketed_shorthand_matcher("a b@example"));
+    assert!(!bracketed_shorthand_matcher("a@example1"));
+}
+
+#[test]
+fn test_bracketed_properties() {
+    assert!(bracketed_property_matcher("\u{3b1}2\u{3b2}!"));
+    assert!(bracketed_property_matcher("7 "));
+    assert!(!bracketed_property_matcher("a1!"));
+    assert!(!bracketed_property_matcher("\u{3b1}b"));
+}
+
+#[test]
+fn test_prefix_against_multi_byte_input() {
+    //the prefix's length ends inside the 'ä'
+    assert!(!ascii_prefix_matcher("\u{dc}\u{e4}"));
+    assert!(!ascii_prefix_matcher("ab\u{e4}"));
+    assert!(ascii_prefix_matcher("abc12"));
+}
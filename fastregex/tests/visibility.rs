@@ -0,0 +1,40 @@
+mod declared {
+    use fastregex::{matcher, matchers};
+
+    matcher!(
+        /// Whether the input is a lowercase word.
+        #[inline]
+        pub(crate) is_word,
+        "[a-z]+"
+    );
+    matcher!(pub is_number, "[0-9]+", branch);
+    matchers! {
+        #[inline]
+        pub(crate) is_get => "GET",
+        pub(super) is_method => "(GET)|(POST)" { branch: true },
+    }
+}
+
+use declared::{is_get, is_method, is_method_branch, is_number, is_word};
+
+#[test]
+fn test_visible_outside_module() {
+    assert!(is_word("abc"));
+    assert!(!is_word("ABC"));
+    assert_eq!(declared::is_number_branch("42"), Some(0));
+    assert!(is_number("7"));
+    assert!(is_get("GET"));
+    assert!(is_method("POST"));
+    assert_eq!(is_method_branch("GET"), Some(0));
+}
+
+#[test]
+fn test_module_shares_visibility() {
+    let mut matcher = declared::is_word::Matcher::new();
+    assert!(matcher.is_match("word"));
+    assert_eq!(declared::is_word_shortest_match("ab"), Some(1));
+    assert_eq!(
+        declared::is_number::matches("12", &mut declared::is_number::Scratch::new()),
+        Some(0)
+    );
+}
@@ -0,0 +1,20 @@
+//unreachable branches are reported as deprecation warnings, which would fail the build here
+#![allow(deprecated)]
+
+use fastregex::matcher;
+
+matcher!(repeated_branch_matcher, "(GET)|(POST)|(GET)", branch);
+matcher!(covered_branch_matcher, "([a-z]+)|(abc)");
+
+#[test]
+fn test_unreachable_branches_still_compile() {
+    assert_eq!(repeated_branch_matcher_branch("GET"), Some(0));
+    assert_eq!(repeated_branch_matcher_branch("POST"), Some(1));
+    assert!(covered_branch_matcher("abc"));
+}
+
+#[test]
+fn test_unreachable_branches_are_reported() {
+    //denying the warnings makes them errors, whose text trybuild compares with the .stderr files
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
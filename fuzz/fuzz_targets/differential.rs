@@ -0,0 +1,158 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use unic_ucd_category::GeneralCategory;
+
+use regexlib::case_folding::expand_folded_literal;
+use regexlib::general_category;
+use regexlib::parser::character_class::CharacterClass;
+use regexlib::parser::RegexEntry;
+use regexlib::runtime::{Limits, Regex};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    pattern: String,
+    haystacks: Vec<String>,
+}
+
+fuzz_target!(|input: Input| {
+    let parsed = match RegexEntry::parse(&input.pattern) {
+        Ok(parsed) => parsed,
+        Err(_) => return,
+    };
+    //the regex crate has no grapheme clusters to compare `\X` against
+    if parsed.has_grapheme_clusters() {
+        return;
+    }
+
+    //counted repetitions can make the automaton enormous, which is not what is being tested here
+    let limits = Limits {
+        max_states: Some(10_000),
+        max_steps: Some(1_000_000),
+        ..Limits::default()
+    };
+    let regex = match Regex::with_limits(&input.pattern, limits) {
+        Ok(regex) => regex,
+        Err(_) => return,
+    };
+
+    //the regex crate checks the same AST rather than the same pattern, since the two syntaxes differ in places (e.g., only ours has `\h` and only theirs has `\b`)
+    let reference = match regex::Regex::new(&format!("^(?:{})$", to_regex_syntax(&parsed))) {
+        Ok(reference) => reference,
+        Err(_) => return,
+    };
+
+    for haystack in &input.haystacks {
+        //the regex crate's Unicode tables need not be the version of regexlib's, so only characters assigned in ours are compared
+        if haystack
+            .chars()
+            .any(|char| general_category::of(char) == GeneralCategory::Unassigned)
+        {
+            continue;
+        }
+
+        if let Ok(is_match) = regex.try_is_match(haystack) {
+            assert_eq!(
+                is_match,
+                reference.is_match(haystack),
+                "pattern {:?} ({}) disagrees on {:?}",
+                input.pattern,
+                reference.as_str(),
+                haystack
+            );
+        }
+    }
+});
+
+fn to_regex_syntax(entry: &RegexEntry) -> String {
+    match entry {
+        RegexEntry::AnyCharacter => "(?s:.)".into(),
+        RegexEntry::Literal(char) => escape(*char),
+        RegexEntry::FoldedLiteral(literal) => to_regex_syntax(&expand_folded_literal(literal)),
+        RegexEntry::UnicodeCharacterClass(categories) => {
+            format!("[{}]", categories_to_regex_syntax(categories))
+        }
+        RegexEntry::NegatedUnicodeCharacterClass(categories) => {
+            format!("[^{}]", categories_to_regex_syntax(categories))
+        }
+        RegexEntry::NonUnicodeCharacterClass(class) => class_to_regex_syntax(class),
+        RegexEntry::UnicodeScript(script) => format!(r"\p{{{}}}", script.name()),
+        RegexEntry::NegatedUnicodeScript(script) => format!(r"\P{{{}}}", script.name()),
+        RegexEntry::GraphemeCluster => unreachable!("patterns with grapheme clusters are skipped"),
+        RegexEntry::StartOfInput => r"\A".into(),
+        RegexEntry::EndOfInput => r"\z".into(),
+        RegexEntry::StartOfLine(_) => "(?m:^)".into(),
+        RegexEntry::EndOfLine(_) => "(?m:$)".into(),
+        RegexEntry::WordStart => r"\b{start}".into(),
+        RegexEntry::WordEnd => r"\b{end}".into(),
+        RegexEntry::Concatenation(entries) => format!(
+            "(?:{})",
+            entries.iter().map(to_regex_syntax).collect::<String>()
+        ),
+        RegexEntry::Alternation(entries) => format!(
+            "(?:{})",
+            entries
+                .iter()
+                .map(to_regex_syntax)
+                .collect::<Vec<_>>()
+                .join("|")
+        ),
+        RegexEntry::Repetition {
+            base,
+            min,
+            max,
+            lazy,
+        } => {
+            let repetition = match max {
+                Some(max) => format!("(?:{}){{{},{}}}", to_regex_syntax(base), min, max),
+                None => format!("(?:{}){{{},}}", to_regex_syntax(base), min),
+            };
+            if *lazy {
+                repetition + "?"
+            } else {
+                repetition
+            }
+        }
+        RegexEntry::Capture { base, .. } => format!("({})", to_regex_syntax(base)),
+        RegexEntry::Byte(_) => unreachable!("patterns are not parsed as bytes"),
+    }
+}
+
+fn categories_to_regex_syntax(categories: &[GeneralCategory]) -> String {
+    use unic_char_property::EnumeratedCharProperty;
+
+    categories
+        .iter()
+        .map(|category| format!(r"\p{{{}}}", category.abbr_name()))
+        .collect()
+}
+
+//every class is written as its own bracketed class, which the regex crate allows to be nested as a union
+fn class_to_regex_syntax(class: &CharacterClass) -> String {
+    match class {
+        CharacterClass::Char(char) => format!("[{}]", escape(*char)),
+        CharacterClass::Range { start, end } => format!("[{}-{}]", escape(*start), escape(*end)),
+        //an empty class matches nothing
+        CharacterClass::Disjunction(classes) if classes.is_empty() => r"[^\x00-\x{10FFFF}]".into(),
+        CharacterClass::Disjunction(classes) => format!(
+            "[{}]",
+            classes.iter().map(class_to_regex_syntax).collect::<String>()
+        ),
+        CharacterClass::Negated(class) => format!("[^{}]", class_to_regex_syntax(class)),
+        CharacterClass::Intersection(left, right) => format!(
+            "[{}&&{}]",
+            class_to_regex_syntax(left),
+            class_to_regex_syntax(right)
+        ),
+        CharacterClass::Difference(left, right) => format!(
+            "[{}--{}]",
+            class_to_regex_syntax(left),
+            class_to_regex_syntax(right)
+        ),
+    }
+}
+
+fn escape(char: char) -> String {
+    format!(r"\x{{{:X}}}", char as u32)
+}
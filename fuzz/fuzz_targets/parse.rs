@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use regexlib::parser::RegexEntry;
+
+//parsing may reject a pattern, but it must never panic on one
+fuzz_target!(|pattern: &str| {
+    let _ = RegexEntry::parse(pattern);
+});
@@ -1,18 +1,16 @@
 use std::io::Write;
 use std::process::{Command, Stdio};
 
-use itertools::Itertools;
-
-use regexlib::automata::{Automaton, TransitionCondition};
-use regexlib::parser::character_class::CharacterClass;
+use regexlib::automata::Automaton;
+use regexlib::diagram::to_graphviz;
 use regexlib::parser::RegexEntry;
 
 fn main() {
     let args = std::env::args().collect::<Vec<_>>();
 
-    if args.len() != 4 {
+    if args.len() != 4 && args.len() != 5 {
         eprintln!(
-            "Usage: {} <regex> <simple svg output> <svg output>",
+            "Usage: {} <regex> <simple svg output> <svg output> [pass svg output prefix]",
             args[0]
         );
         std::process::exit(-1);
@@ -23,23 +21,36 @@ fn main() {
     let regex = args_iter.next().unwrap();
     let simple_svg_output_path = args_iter.next().unwrap();
     let svg_output_path = args_iter.next().unwrap();
+    let pass_svg_output_prefix = args_iter.next();
 
-    let parsed = match RegexEntry::parse(&regex) {
+    let (parsed, spans) = match RegexEntry::parse_with_spans(&regex) {
         Ok(parsed) => parsed,
-        Err(msg) => {
-            eprintln!("Failed to parse regex: {msg}");
+        Err(error) => {
+            //underline the part of the pattern the error is about
+            let indent = regex[..error.span.start].chars().count();
+            let width = regex[error.span.clone()].chars().count().max(1);
+            eprintln!("Failed to parse regex: {}", error.message);
+            eprintln!("  {regex}");
+            eprintln!("  {}{}", " ".repeat(indent), "^".repeat(width));
             std::process::exit(-1)
         }
     };
 
-    let mut automata = Automaton::from_regex(parsed);
-    output_automata(&automata, &svg_output_path);
-    automata.simplify();
-    output_automata(&automata, &simple_svg_output_path);
+    let mut automata = Automaton::from_regex_with_spans(parsed, &spans);
+    output_automata(&automata, &regex, &svg_output_path);
+    let snapshots = automata.simplify_with_snapshots();
+    output_automata(&automata, &regex, &simple_svg_output_path);
+
+    //one diagram per simplification pass, numbered in the order the passes ran
+    if let Some(prefix) = pass_svg_output_prefix {
+        for (index, (pass, snapshot)) in snapshots.iter().enumerate() {
+            output_automata(snapshot, &regex, &format!("{prefix}{}-{}.svg", index + 1, pass.name()));
+        }
+    }
 }
 
-fn output_automata(automata: &Automaton, file: &str) {
-    let graphviz = automata_to_graphviz(&automata);
+fn output_automata(automata: &Automaton, regex: &str, file: &str) {
+    let graphviz = to_graphviz(automata, regex);
 
     let command = Command::new("dot")
         .args(["-Tsvg"])
@@ -57,82 +68,3 @@ fn output_automata(automata: &Automaton, file: &str) {
 
     std::fs::write(file, output.stdout).unwrap();
 }
-
-fn automata_to_graphviz(automata: &Automaton) -> String {
-    let mut graphviz = String::new();
-
-    graphviz.push_str("digraph NFA{\n");
-    //graphviz.push_str("\trankdir=LR;\n");
-    for state_id in 0..automata.state_count() {
-        emit_state(automata, state_id, &mut graphviz);
-    }
-
-    graphviz.push_str("\tstart [shape=plaintext];\n");
-    for start_state in automata.start_states() {
-        graphviz.push_str(&format!("\tstart->state{};\n", *start_state));
-    }
-
-    graphviz.push_str("}");
-
-    graphviz
-}
-
-fn emit_state(automata: &Automaton, state_id: usize, graphviz: &mut String) {
-    let state = automata.get_state(state_id);
-
-    //debug name
-    graphviz.push_str(&format!(
-        "\tstate{} [label=\"{}\",shape={}];\n",
-        state_id,
-        state_id,
-        if automata.is_terminal_state(state_id) {
-            "doublecircle"
-        } else {
-            "oval"
-        }
-    ));
-
-    //transitions
-    for transition in &state.transitions {
-        graphviz.push_str(&format!(
-            "\tstate{} -> state{} [label=\"{}\"];\n",
-            state_id,
-            transition.next_state_id,
-            transition_to_string(&transition.condition)
-        ));
-    }
-}
-
-fn transition_to_string(transition_condition: &TransitionCondition) -> String {
-    match transition_condition {
-        TransitionCondition::Epsilon => "ε".into(),
-        TransitionCondition::CharacterClass(class) => {
-            format!("[{}]", character_class_to_string(class))
-        }
-        TransitionCondition::Literal(ch) => format!("'{}'", ch),
-        TransitionCondition::AnyCharacter => "*".into(),
-        TransitionCondition::UnicodeCharacterClass(categories) => categories
-            .iter()
-            .map(|category| format!("{:?}", category))
-            .join(", "),
-        TransitionCondition::NegatedUnicodeClass(categories) => format!(
-            "!{}",
-            categories
-                .iter()
-                .map(|category| format!("{:?}", category))
-                .join(", ")
-        ),
-    }
-}
-
-fn character_class_to_string(character_class: &CharacterClass) -> String {
-    match character_class {
-        CharacterClass::Negated(class) => format!("^{}", character_class_to_string(class.as_ref())),
-        CharacterClass::Char(ch) => format!("{}", *ch),
-        CharacterClass::Range { start, end } => format!("{}-{}", *start, *end),
-        CharacterClass::Disjunction(classes) => classes
-            .iter()
-            .map(|class| character_class_to_string(class))
-            .collect(),
-    }
-}
@@ -0,0 +1,313 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use crate::automata::{Automaton, SimplificationPass, TransitionCondition};
+use crate::general_category;
+use crate::parser::character_class::CharacterClass;
+use crate::parser::spans::EntrySpans;
+use crate::parser::RegexEntry;
+
+/// The most condition checks deciding whether one branch is reachable may take. Branches that are
+/// not decided within it are assumed to be reachable.
+pub const BRANCH_ANALYSIS_BUDGET: u64 = 1_000_000;
+
+/// A branch of an alternation that can never be the one that matches: every input it matches is
+/// also matched by an earlier branch of the same alternation, which is preferred.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnreachableBranch {
+    /// The branch's position among the branches of its alternation, from 0.
+    pub index: usize,
+    /// The byte range of the pattern the branch was parsed from.
+    pub span: Range<usize>,
+    /// The earlier branch that is written the same way, if there is one.
+    pub duplicate_of: Option<usize>,
+}
+
+/// Finds the unreachable branches of every alternation in the entry, in pattern order. `spans`
+/// are the entry's spans, as returned by [`RegexEntry::parse_with_spans`].
+pub fn unreachable_branches(entry: &RegexEntry, spans: &EntrySpans) -> Vec<UnreachableBranch> {
+    let mut unreachable = Vec::new();
+    find_unreachable_branches(entry, spans, &mut unreachable);
+    unreachable
+}
+
+fn find_unreachable_branches(
+    entry: &RegexEntry,
+    spans: &EntrySpans,
+    unreachable: &mut Vec<UnreachableBranch>,
+) {
+    match entry {
+        RegexEntry::Alternation(branches) => {
+            for (index, (branch, branch_spans)) in branches.iter().zip(&spans.children).enumerate()
+            {
+                if index == 0 {
+                    continue;
+                }
+
+                //capture groups are numbered, so equal branches are compared by how they are written
+                let written = branch.to_string();
+                let duplicate_of = branches[..index]
+                    .iter()
+                    .position(|earlier| earlier.to_string() == written);
+
+                if duplicate_of.is_some() || is_covered(branch, &branches[..index]) {
+                    unreachable.push(UnreachableBranch {
+                        index,
+                        span: branch_spans.span.clone(),
+                        duplicate_of,
+                    });
+                }
+            }
+
+            for (branch, branch_spans) in branches.iter().zip(&spans.children) {
+                find_unreachable_branches(branch, branch_spans, unreachable);
+            }
+        }
+        RegexEntry::Concatenation(entries) => {
+            for (entry, entry_spans) in entries.iter().zip(&spans.children) {
+                find_unreachable_branches(entry, entry_spans, unreachable);
+            }
+        }
+        RegexEntry::Repetition { base, .. } | RegexEntry::Capture { base, .. } => {
+            if let Some(base_spans) = spans.children.first() {
+                find_unreachable_branches(base, base_spans, unreachable);
+            }
+        }
+        _ => {}
+    }
+}
+
+//whether every input `branch` matches is matched by one of `earlier`, found by searching the product of both automata for an input only `branch` matches
+fn is_covered(branch: &RegexEntry, earlier: &[RegexEntry]) -> bool {
+    //whether `^` and `$` hold depends on what surrounds the alternation, which the automata built here do not know
+    if branch.has_anchors() || earlier.iter().any(RegexEntry::has_anchors) {
+        return false;
+    }
+
+    let branch = analysis_automaton(branch.clone());
+    let earlier = analysis_automaton(RegexEntry::Alternation(earlier.to_vec()));
+    let representatives = representative_chars(&[&branch, &earlier]);
+    let mut budget = BRANCH_ANALYSIS_BUDGET;
+
+    let start = (
+        state_set(branch.start_states()),
+        state_set(earlier.start_states()),
+    );
+    let mut seen = BTreeSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some((branch_states, earlier_states)) = queue.pop_front() {
+        let branch_accepts = branch_states
+            .iter()
+            .any(|state| branch.is_terminal_state(*state));
+        let earlier_accepts = earlier_states
+            .iter()
+            .any(|state| earlier.is_terminal_state(*state));
+        if branch_accepts && !earlier_accepts {
+            return false;
+        }
+
+        for &char in &representatives {
+            let next_branch_states = match step(&branch, &branch_states, char, &mut budget) {
+                Some(states) => states,
+                None => return false,
+            };
+            //once the branch can not match anymore, nothing after this input tells the two apart
+            if next_branch_states.is_empty() {
+                continue;
+            }
+            let next_earlier_states = match step(&earlier, &earlier_states, char, &mut budget) {
+                Some(states) => states,
+                None => return false,
+            };
+
+            let next = (next_branch_states, next_earlier_states);
+            if seen.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    true
+}
+
+//the automaton without its prefix stripped, since the prefixes of the two automata compared would differ
+fn analysis_automaton(entry: RegexEntry) -> Automaton {
+    let mut automaton = Automaton::from_regex(entry);
+    for pass in SimplificationPass::ALL {
+        if pass != SimplificationPass::StripPrefix {
+            automaton.run_pass(pass);
+        }
+    }
+    automaton
+}
+
+fn state_set(states: &[usize]) -> BTreeSet<usize> {
+    states.iter().copied().collect()
+}
+
+//the states reached from `states` on `char`, or None once the budget runs out
+fn step(
+    automaton: &Automaton,
+    states: &BTreeSet<usize>,
+    char: char,
+    budget: &mut u64,
+) -> Option<BTreeSet<usize>> {
+    let transitions = states
+        .iter()
+        .flat_map(|state| &automaton.get_state(*state).transitions);
+
+    let mut next = BTreeSet::new();
+    for transition in transitions {
+        *budget = budget.checked_sub(1)?;
+        if transition.condition.matches(char) {
+            next.insert(transition.next_state_id);
+        }
+    }
+
+    Some(next)
+}
+
+//one character from each run of code points that every transition condition treats the same way
+fn representative_chars(automata: &[&Automaton]) -> Vec<char> {
+    let mut boundaries = BTreeSet::from([0u32]);
+    let mut needs_categories = false;
+
+    let conditions = automata
+        .iter()
+        .flat_map(|automaton| automaton.states())
+        .flat_map(|state| &state.transitions)
+        .map(|transition| &transition.condition);
+    for condition in conditions {
+        match condition {
+            TransitionCondition::Literal(literal) => {
+                boundaries.extend([*literal as u32, *literal as u32 + 1])
+            }
+            TransitionCondition::CharacterClass(class) => class_boundaries(class, &mut boundaries),
+            TransitionCondition::UnicodeCharacterClass(_)
+            | TransitionCondition::NegatedUnicodeClass(_) => needs_categories = true,
+            TransitionCondition::UnicodeScript(script)
+            | TransitionCondition::NegatedUnicodeScript(script) => {
+                for (start, end) in script.ranges() {
+                    boundaries.extend([*start as u32, *end as u32 + 1]);
+                }
+            }
+            TransitionCondition::GraphemeBreak(values)
+            | TransitionCondition::NegatedGraphemeBreak(values) => {
+                for (start, end) in values.iter().flat_map(|value| value.ranges()) {
+                    boundaries.extend([*start as u32, *end as u32 + 1]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if needs_categories {
+        boundaries.extend(category_boundaries());
+    }
+
+    //a run starting in the surrogates, which are not characters, continues after them
+    boundaries
+        .into_iter()
+        .filter_map(|boundary| {
+            char::from_u32(boundary)
+                .or_else(|| (0xD800..0xE000).contains(&boundary).then_some('\u{E000}'))
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+//the code points where the general category changes, which take a scan of every character to find, so they are found once
+fn category_boundaries() -> &'static [u32] {
+    static BOUNDARIES: OnceLock<Vec<u32>> = OnceLock::new();
+
+    BOUNDARIES.get_or_init(|| {
+        let mut boundaries = Vec::new();
+        let mut previous = None;
+        for char in (0..=char::MAX as u32).filter_map(char::from_u32) {
+            let category = general_category::of(char);
+            if previous != Some(category) {
+                boundaries.push(char as u32);
+                previous = Some(category);
+            }
+        }
+        boundaries
+    })
+}
+
+fn class_boundaries(class: &CharacterClass, boundaries: &mut BTreeSet<u32>) {
+    match class {
+        CharacterClass::Char(char) => boundaries.extend([*char as u32, *char as u32 + 1]),
+        CharacterClass::Range { start, end } => boundaries.extend([*start as u32, *end as u32 + 1]),
+        CharacterClass::Disjunction(classes) => {
+            for class in classes {
+                class_boundaries(class, boundaries);
+            }
+        }
+        CharacterClass::Negated(class) => class_boundaries(class, boundaries),
+        CharacterClass::Intersection(left, right) | CharacterClass::Difference(left, right) => {
+            class_boundaries(left, boundaries);
+            class_boundaries(right, boundaries);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{unreachable_branches, UnreachableBranch};
+    use crate::parser::RegexEntry;
+
+    fn unreachable(pattern: &str) -> Vec<UnreachableBranch> {
+        let (entry, spans) = RegexEntry::parse_with_spans(pattern).unwrap();
+        unreachable_branches(&entry, &spans)
+    }
+
+    #[test]
+    fn test_duplicate_branch() {
+        assert_eq!(
+            unreachable("(GET)|(POST)|(GET)"),
+            vec![UnreachableBranch {
+                index: 2,
+                span: 13..18,
+                duplicate_of: Some(0),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_covered_branch() {
+        let found = unreachable("([a-z]+)|(abc)|([0-9])");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].index, 1);
+        assert_eq!(found[0].duplicate_of, None);
+
+        //covered by the earlier branches together, but by neither alone
+        assert_eq!(unreachable("(a*)|(b*)|(a)|(b)").len(), 2);
+        assert_eq!(unreachable("([ab])|([a-c])").len(), 0);
+        assert_eq!(unreachable("(\\pL)|(\\p{Lu})|(\\d)").len(), 1);
+    }
+
+    #[test]
+    fn test_reachable_branches() {
+        assert!(unreachable("(a)|(ab)").is_empty());
+        assert!(unreachable("(a+)|(a*)").is_empty());
+        assert!(unreachable("([^a])|(a)").is_empty());
+        assert!(unreachable("ab|cd").is_empty());
+    }
+
+    #[test]
+    fn test_anchored_branches() {
+        //`^a` only matches where `a` does, but `a` also matches where `^a` does not
+        assert!(unreachable("x(^a)|(a)").is_empty());
+        assert!(unreachable("(a$)|(a)b").is_empty());
+    }
+
+    #[test]
+    fn test_nested_alternation() {
+        let found = unreachable("x((yz)|(yz))");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].span, 7..11);
+    }
+}
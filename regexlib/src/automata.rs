@@ -1,10 +1,28 @@
 use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
 use std::process::id;
 
 use unic_ucd_category::GeneralCategory;
 
+pub(crate) use self::anchors::is_word_character;
+use self::anchors::{BreakKind, Position};
+use self::grapheme::GRAPHEME_CLUSTER_STATES;
+use crate::case_folding::fold_steps;
+use crate::general_category;
+use crate::grapheme::GraphemeBreak;
+use crate::line_terminator::LineTerminator;
 use crate::parser::character_class::CharacterClass;
+use crate::parser::spans::EntrySpans;
 use crate::parser::RegexEntry;
+use crate::script::Script;
+
+mod anchors;
+mod glushkov;
+mod grapheme;
+mod minimize;
+
+//the most characters a class may have to be one of the prefix classes, beyond which checking it is no cheaper than running the automaton
+const MAX_PREFIX_CLASS_SIZE: usize = 4;
 
 #[derive(Clone, Debug)]
 pub struct Automaton {
@@ -12,6 +30,46 @@ pub struct Automaton {
     terminal_states: Vec<usize>,
     start_states: Vec<usize>,
     prefix: String,
+    prefix_classes: Vec<Vec<char>>,
+}
+
+/// How far a match through an [`Automaton`] has come, as [`Automaton::start`] begins it and
+/// [`Automaton::advance`] moves it along: the set of states the automaton may be in after the
+/// input read so far. It belongs to the automaton that created it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchState {
+    //the bytes of the automaton's literal prefix that are yet to be read, before any of its states are entered
+    prefix_remaining: usize,
+    states: BTreeSet<usize>,
+    //whether no input has been read yet, so `^` can still be passed
+    at_start: bool,
+    //what kind of line break the last character read was, which decides whether a multi-line `^` can be passed
+    after: BreakKind,
+    //whether the last character read was a word character, which decides the word boundaries that can be passed
+    after_word: bool,
+}
+
+impl MatchState {
+    /// The ids of the states the automaton may be in. This is empty while the automaton's
+    /// [`prefix`](Automaton::prefix) is being read, since the prefix has no states.
+    pub fn active_states(&self) -> impl Iterator<Item = usize> + '_ {
+        self.states.iter().copied()
+    }
+
+    /// Whether no continuation of the input read so far can match anymore.
+    pub fn is_failed(&self) -> bool {
+        self.prefix_remaining == 0 && self.states.is_empty()
+    }
+
+    //where in the input the states are, before anything is known about the rest of it
+    fn position(&self) -> Position {
+        Position {
+            at_start: self.at_start,
+            after: self.after,
+            after_word: self.after_word,
+            ..Position::default()
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -19,6 +77,10 @@ pub struct State {
     pub debug_name: String,
     pub id: usize,
     pub transitions: Vec<Transition>,
+    /// The byte range of the pattern whose part of the regex created this state, if the automaton
+    /// was built with spans (see [`Automaton::from_regex_with_spans`]). The state's transitions
+    /// come from the same part of the pattern.
+    pub span: Option<Range<usize>>,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -34,7 +96,82 @@ pub enum TransitionCondition {
     CharacterClass(CharacterClass),
     UnicodeCharacterClass(Vec<GeneralCategory>),
     NegatedUnicodeClass(Vec<GeneralCategory>),
+    UnicodeScript(Script),
+    NegatedUnicodeScript(Script),
+    /// Taken on a character with one of the values, as part of a `\X`.
+    GraphemeBreak(Vec<GraphemeBreak>),
+    /// Taken on a character with none of the values (which includes those whose value is
+    /// `Other`), as part of a `\X`.
+    NegatedGraphemeBreak(Vec<GraphemeBreak>),
     Epsilon,
+    /// An epsilon transition that records the position where capture group `n` starts.
+    CaptureStart(usize),
+    /// An epsilon transition that records the position where capture group `n` ends.
+    CaptureEnd(usize),
+    /// An epsilon transition that may only be taken before any of the input has been read, made
+    /// for a `^`.
+    StartOfInput,
+    /// An epsilon transition that may only be taken once all of the input has been read, made for
+    /// a `$`.
+    EndOfInput,
+    /// An epsilon transition that may only be taken at the start of the input or right after one
+    /// of the line terminators, made for a multi-line `^`.
+    StartOfLine(LineTerminator),
+    /// An epsilon transition that may only be taken at the end of the input or right before one
+    /// of the line terminators, made for a multi-line `$`.
+    EndOfLine(LineTerminator),
+    /// An epsilon transition that may only be taken between a character that is not a word
+    /// character (or the start of the input) and one that is, made for a `\b{start}`.
+    WordStart,
+    /// An epsilon transition that may only be taken between a word character and one that is not
+    /// (or the end of the input), made for a `\b{end}`.
+    WordEnd,
+}
+
+/// One of the passes that [`Automaton::simplify`] is made of.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum SimplificationPass {
+    /// Replaces the transitions for `^` and `$` with epsilon transitions in copies of the states
+    /// that can be reached at the start and at the end of the input (see
+    /// [`has_anchors`](Automaton::has_anchors)). Any unanchoring (see
+    /// [`make_unanchored`](Automaton::make_unanchored)) must be done before this.
+    ResolveAnchors,
+    /// Turns the chain of literals every match must start with into the automaton's
+    /// [`prefix`](Automaton::prefix), merging it into a single start state, and finds the
+    /// [`prefix_classes`](Automaton::prefix_classes) after it.
+    StripPrefix,
+    RemoveDuplicateTransitions,
+    /// Replaces every epsilon transition (including capture boundaries) with direct transitions
+    /// to the states it reaches.
+    EliminateEpsilons,
+    /// Removes the states that are unreachable or cannot lead to a terminal state.
+    RemoveDeadStates,
+    /// Merges the states that match the same continuations of the input, which are what is left
+    /// of patterns that repeat themselves (e.g., the two `b`s of `(ab)|(cb)`).
+    MergeEquivalentStates,
+}
+
+impl SimplificationPass {
+    /// Every pass, in the order [`Automaton::simplify`] runs them.
+    pub const ALL: [SimplificationPass; 6] = [
+        SimplificationPass::ResolveAnchors,
+        SimplificationPass::StripPrefix,
+        SimplificationPass::RemoveDuplicateTransitions,
+        SimplificationPass::EliminateEpsilons,
+        SimplificationPass::RemoveDeadStates,
+        SimplificationPass::MergeEquivalentStates,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SimplificationPass::ResolveAnchors => "resolve-anchors",
+            SimplificationPass::StripPrefix => "strip-prefix",
+            SimplificationPass::RemoveDuplicateTransitions => "remove-duplicate-transitions",
+            SimplificationPass::EliminateEpsilons => "eliminate-epsilons",
+            SimplificationPass::RemoveDeadStates => "remove-dead-states",
+            SimplificationPass::MergeEquivalentStates => "merge-equivalent-states",
+        }
+    }
 }
 
 impl Automaton {
@@ -44,14 +181,39 @@ impl Automaton {
             terminal_states: Vec::new(),
             start_states: Vec::new(),
             prefix: String::new(),
+            prefix_classes: Vec::new(),
         }
     }
 
     pub fn simplify(&mut self) {
-        self.simplify_prefix();
-        self.remove_duplicate_transitions();
-        self.simplify_states();
-        self.remove_dead_states();
+        for pass in SimplificationPass::ALL {
+            self.run_pass(pass);
+        }
+    }
+
+    /// Runs a single simplification pass. [`simplify`](Self::simplify) runs every pass in the
+    /// order of [`SimplificationPass::ALL`]; later passes may rely on earlier ones having run.
+    pub fn run_pass(&mut self, pass: SimplificationPass) {
+        match pass {
+            SimplificationPass::ResolveAnchors => self.resolve_anchors(),
+            SimplificationPass::StripPrefix => self.simplify_prefix(),
+            SimplificationPass::RemoveDuplicateTransitions => self.remove_duplicate_transitions(),
+            SimplificationPass::EliminateEpsilons => self.simplify_states(),
+            SimplificationPass::RemoveDeadStates => self.remove_dead_states(),
+            SimplificationPass::MergeEquivalentStates => self.merge_equivalent_states(),
+        }
+    }
+
+    /// Like [`simplify`](Self::simplify), but also returns a copy of the automaton as it was after
+    /// each pass, in the order the passes ran.
+    pub fn simplify_with_snapshots(&mut self) -> Vec<(SimplificationPass, Automaton)> {
+        SimplificationPass::ALL
+            .into_iter()
+            .map(|pass| {
+                self.run_pass(pass);
+                (pass, self.clone())
+            })
+            .collect()
     }
 
     fn simplify_prefix(&mut self){
@@ -70,14 +232,40 @@ impl Automaton {
                     current_state = &self.states[transition.next_state_id];
                     continue;
                 }
+                //a lone epsilon (i.e., the boundary of a capture group) does not change what the state matches, so the prefix may continue through it
+                if transition.condition.is_epsilon(){
+                    current_state = &self.states[transition.next_state_id];
+                    continue;
+                }
             }
             break;
         }
 
-        if prefix.len() != 0{
+        if !prefix.is_empty(){
             self.prefix = prefix;
             self.start_states = vec![current_state.id];
         }
+
+        //the characters after the prefix are kept as states, but a few of them from a small class (e.g., a literal made case-insensitive) are still cheap to check before running the automaton
+        let mut prefix_classes = Vec::new();
+        loop{
+            if current_state.transitions.len() != 1 || self.is_terminal_state(current_state.id){
+                break;
+            }
+            let transition = &current_state.transitions[0];
+            let class = match &transition.condition{
+                condition if condition.is_epsilon() => None,
+                TransitionCondition::Literal(ch) => Some(vec![*ch]),
+                TransitionCondition::CharacterClass(class) => match class.members(MAX_PREFIX_CLASS_SIZE){
+                    Some(members) => Some(members),
+                    None => break,
+                },
+                _ => break,
+            };
+            prefix_classes.extend(class);
+            current_state = &self.states[transition.next_state_id];
+        }
+        self.prefix_classes = prefix_classes;
     }
 
     fn remove_dead_states(&mut self) {
@@ -86,8 +274,12 @@ impl Automaton {
         let mut new_states = self
             .states
             .iter()
-            .filter(|state| reachable_states.contains(&state.id) && !self.is_state_dead(*state))
-            .map(|state| state.clone())
+            //terminal states are always kept so that they stay in correspondence with the regexes of a union
+            .filter(|state| {
+                self.terminal_states.contains(&state.id)
+                    || (reachable_states.contains(&state.id) && !self.is_state_dead(state))
+            })
+            .cloned()
             .collect::<Vec<_>>();
 
         let id_map = new_states
@@ -114,14 +306,14 @@ impl Automaton {
             .start_states
             .iter()
             .filter_map(|state_id| id_map.get(state_id))
-            .map(|u| *u)
+            .copied()
             .collect();
 
         self.terminal_states = self
             .terminal_states
             .iter()
             .filter_map(|state_id| id_map.get(state_id))
-            .map(|u| *u)
+            .copied()
             .collect();
 
         self.states = new_states;
@@ -162,10 +354,10 @@ impl Automaton {
         }
 
         for transition in &state.transitions {
-            if !checked.contains(&transition.next_state_id) {
-                if !self.is_state_dead_checked(checked, &self.states[transition.next_state_id]) {
-                    return false;
-                }
+            if !checked.contains(&transition.next_state_id)
+                && !self.is_state_dead_checked(checked, &self.states[transition.next_state_id])
+            {
+                return false;
             }
         }
 
@@ -193,6 +385,7 @@ impl Automaton {
             id: old.id,
             debug_name: old.debug_name.clone(),
             transitions: Vec::new(),
+            span: old.span.clone(),
         };
 
         for old_transition in &old.transitions {
@@ -215,12 +408,16 @@ impl Automaton {
         state
     }
 
+    //iterative, since large repetitions produce chains of epsilon transitions far longer than the stack is deep
     fn calculate_epsilon_reach(&self, set: &mut BTreeSet<usize>, state_id: usize) {
-        if !set.contains(&state_id) {
-            set.insert(state_id);
-            for transition in &self.states[state_id].transitions {
-                if let TransitionCondition::Epsilon = transition.condition {
-                    self.calculate_epsilon_reach(set, transition.next_state_id);
+        let mut to_visit = vec![state_id];
+
+        while let Some(state_id) = to_visit.pop() {
+            if set.insert(state_id) {
+                for transition in &self.states[state_id].transitions {
+                    if transition.condition.is_epsilon() {
+                        to_visit.push(transition.next_state_id);
+                    }
                 }
             }
         }
@@ -241,21 +438,291 @@ impl Automaton {
     pub fn from_regex(regex: RegexEntry) -> Self {
         let mut automata = Self::new_empty();
 
+        Self::from_regex_and_spans(&regex, None)
+    }
+
+    /// Like [`from_regex`](Self::from_regex), but records in each state the part of the pattern
+    /// that produced it. `spans` must be the spans parsed along with `regex` by
+    /// [`RegexEntry::parse_with_spans`].
+    pub fn from_regex_with_spans(regex: RegexEntry, spans: &EntrySpans) -> Self {
+        Self::from_regex_and_spans(&regex, Some(spans))
+    }
+
+    /// Like [`from_regex`](Self::from_regex), but fails instead of constructing more than
+    /// `max_states` states, as [`check_constructed_state_count`](Self::check_constructed_state_count)
+    /// checks before anything is constructed.
+    pub fn from_regex_with_max_states(regex: RegexEntry, max_states: u64) -> Result<Self, String> {
+        Self::check_constructed_state_count(&regex, max_states)?;
+        Ok(Self::from_regex(regex))
+    }
+
+    fn from_regex_and_spans(regex: &RegexEntry, spans: Option<&EntrySpans>) -> Self {
+        let mut automata = Self::new_empty();
+
         let terminal_state_id = automata.add_state(State {
             debug_name: "terminal".into(),
             id: 0,
             transitions: vec![],
+            span: None,
         });
 
         automata.terminal_states.push(terminal_state_id);
 
-        let start_state = automata.add_regex_entry(&regex, terminal_state_id);
+        let start_state = automata.add_regex_entry(regex, spans, terminal_state_id);
         automata.start_states = vec![start_state];
 
         automata
     }
 
-    fn add_regex_entry(&mut self, entry: &RegexEntry, target: usize) -> usize {
+    /// Builds the union of several regexes as a single automaton, so that all of them can be run
+    /// over an input in one pass. Each regex gets its own terminal state: the `n`th entry of
+    /// `terminal_state_ids` is the one for the `n`th regex, both before and after simplification.
+    pub fn from_regexes(regexes: impl IntoIterator<Item = RegexEntry>) -> Self {
+        let mut automata = Self::new_empty();
+
+        let start_states = regexes
+            .into_iter()
+            .map(|regex| {
+                let terminal_state_id = automata.construct_state("terminal", []);
+                automata.terminal_states.push(terminal_state_id);
+                automata.add_regex_entry(&regex, None, terminal_state_id)
+            })
+            .collect::<Vec<_>>();
+
+        let start_state = automata.construct_state(
+            "Union Epsilon Trampoline State",
+            start_states
+                .into_iter()
+                .map(|target| Transition::new(target, TransitionCondition::Epsilon)),
+        );
+        automata.start_states = vec![start_state];
+
+        automata
+    }
+
+    /// Like [`from_regexes`](Self::from_regexes), but builds the union out of already constructed
+    /// (and possibly simplified) automata, so that a union can be rebuilt without constructing
+    /// the automata of its members again. Each member's terminal states are kept in order, so the
+    /// union's `terminal_state_ids` lists the first member's terminal states, then the second's,
+    /// and so on.
+    ///
+    /// The members must not have had their prefix stripped (see [`SimplificationPass::StripPrefix`]),
+    /// since the prefix is not represented by any state.
+    pub fn union<'a>(automata: impl IntoIterator<Item = &'a Automaton>) -> Self {
+        let mut union = Self::new_empty();
+        let mut start_states = Vec::new();
+
+        for automaton in automata {
+            if !automaton.prefix.is_empty() {
+                panic!(
+                    "Attempted to build a union from an automaton whose prefix has been stripped."
+                );
+            }
+
+            let offset = union.states.len();
+            union.states.extend(automaton.states.iter().map(|state| State {
+                id: state.id + offset,
+                debug_name: state.debug_name.clone(),
+                transitions: state
+                    .transitions
+                    .iter()
+                    .map(|transition| {
+                        Transition::new(
+                            transition.next_state_id + offset,
+                            transition.condition.clone(),
+                        )
+                    })
+                    .collect(),
+                span: state.span.clone(),
+            }));
+            union
+                .terminal_states
+                .extend(automaton.terminal_states.iter().map(|id| id + offset));
+            start_states.extend(automaton.start_states.iter().map(|id| id + offset));
+        }
+
+        let start_state = union.construct_state(
+            "Union Epsilon Trampoline State",
+            start_states
+                .into_iter()
+                .map(|target| Transition::new(target, TransitionCondition::Epsilon)),
+        );
+        union.start_states = vec![start_state];
+
+        union
+    }
+
+    /// Lets a match start anywhere in the input rather than only at its start, by adding a start
+    /// state that skips any character and may begin the pattern at any point. Once this is done,
+    /// the automaton matches an input that ends with a match of the pattern, so a search for the
+    /// pattern stops at the first input position where the automaton is in a terminal state.
+    ///
+    /// A `$` can only be passed at the end of the input, though, after any match that ends earlier
+    /// has been read past. So for a pattern with a `$`, the automaton also skips any characters
+    /// after a match, and matches an input that contains a match of the pattern.
+    ///
+    /// This must be done before the prefix is stripped (see [`SimplificationPass::StripPrefix`]),
+    /// since the prefix is not represented by any state, and before the anchors are resolved (see
+    /// [`SimplificationPass::ResolveAnchors`]), since which states `^` can be passed in depends on
+    /// where the match starts.
+    pub fn make_unanchored(&mut self) {
+        if !self.prefix.is_empty() {
+            panic!("Attempted to unanchor an automaton whose prefix has been stripped.");
+        }
+
+        let skip_state = self.next_state_id();
+        let start_states = std::mem::take(&mut self.start_states);
+        self.construct_state(
+            "Unanchored Start State",
+            [Transition::new(skip_state, TransitionCondition::AnyCharacter)]
+                .into_iter()
+                .chain(
+                    start_states
+                        .into_iter()
+                        .map(|target| Transition::new(target, TransitionCondition::Epsilon)),
+                ),
+        );
+        self.start_states = vec![skip_state];
+
+        if self.has_end_anchors() {
+            //each terminal state gets its own replacement, so that they stay in correspondence with the regexes of a union
+            for index in 0..self.terminal_states.len() {
+                let terminal_state = self.terminal_states[index];
+                let end_state = self.construct_state("Unanchored End State", []);
+                let skip_state = self.next_state_id();
+                self.construct_state(
+                    "Unanchored End Skip State",
+                    [
+                        Transition::new(skip_state, TransitionCondition::AnyCharacter),
+                        Transition::new(end_state, TransitionCondition::Epsilon),
+                    ],
+                );
+                self.states[terminal_state]
+                    .transitions
+                    .push(Transition::new(skip_state, TransitionCondition::Epsilon));
+                self.terminal_states[index] = end_state;
+            }
+        }
+    }
+
+    /// The number of states `from_regex` will create for `regex` before simplification, computed
+    /// without constructing anything. Counted repetitions duplicate their base, so this can be
+    /// checked against a limit before committing to a potentially enormous construction.
+    pub fn constructed_state_count(regex: &RegexEntry) -> u64 {
+        //the terminal state
+        Self::constructed_state_count_for_entry(regex).saturating_add(1)
+    }
+
+    /// Fails if `from_regex` would create more than `max_states` states for `regex`. Counted
+    /// repetitions are the only parts of a pattern whose states grow faster than the pattern
+    /// does, so the error names the one that needs the most states (e.g., `(a|b){1000,2000}`).
+    pub fn check_constructed_state_count(
+        regex: &RegexEntry,
+        max_states: u64,
+    ) -> Result<(), String> {
+        let states = Self::constructed_state_count(regex);
+        if states <= max_states {
+            return Ok(());
+        }
+
+        let mut message =
+            format!("Pattern needs {states} states, more than the limit of {max_states}");
+        if let Some((repetition, repetition_states)) = Self::largest_counted_repetition(regex) {
+            message += &format!(", {repetition_states} of them for the repetition `{repetition}`");
+        }
+        Err(message)
+    }
+
+    //of the repetitions that copy their base more than once, the one needing the most states; an enclosing repetition needs more than those inside it, so it is the one named
+    fn largest_counted_repetition(entry: &RegexEntry) -> Option<(&RegexEntry, u64)> {
+        let children = match entry {
+            RegexEntry::Concatenation(entries) | RegexEntry::Alternation(entries) => {
+                entries.iter().collect()
+            }
+            RegexEntry::Repetition { min, max, .. } if max.unwrap_or(*min) > 1 => {
+                return Some((entry, Self::constructed_state_count_for_entry(entry)));
+            }
+            RegexEntry::Repetition { base, .. } | RegexEntry::Capture { base, .. } => vec![&**base],
+            _ => Vec::new(),
+        };
+        children
+            .into_iter()
+            .filter_map(Self::largest_counted_repetition)
+            .fold(None, |largest, candidate| match largest {
+                Some((_, states)) if states >= candidate.1 => largest,
+                _ => Some(candidate),
+            })
+    }
+
+    fn constructed_state_count_for_entry(entry: &RegexEntry) -> u64 {
+        match entry {
+            RegexEntry::AnyCharacter
+            | RegexEntry::UnicodeCharacterClass(_)
+            | RegexEntry::NegatedUnicodeCharacterClass(_)
+            | RegexEntry::NonUnicodeCharacterClass(_)
+            | RegexEntry::Byte(_)
+            | RegexEntry::UnicodeScript(_)
+            | RegexEntry::NegatedUnicodeScript(_)
+            | RegexEntry::Literal(_)
+            | RegexEntry::StartOfInput
+            | RegexEntry::EndOfInput
+            | RegexEntry::StartOfLine(_)
+            | RegexEntry::EndOfLine(_)
+            | RegexEntry::WordStart
+            | RegexEntry::WordEnd => 1,
+            RegexEntry::GraphemeCluster => GRAPHEME_CLUSTER_STATES,
+            RegexEntry::FoldedLiteral(literal) => fold_steps(literal).0 as u64,
+            RegexEntry::Concatenation(entries) => entries
+                .iter()
+                .map(Self::constructed_state_count_for_entry)
+                .fold(0, u64::saturating_add),
+            RegexEntry::Alternation(entries) => entries
+                .iter()
+                .map(Self::constructed_state_count_for_entry)
+                .fold(1, u64::saturating_add),
+            RegexEntry::Repetition { base, min, max, .. } => {
+                let base = Self::constructed_state_count_for_entry(base);
+                let required = base.saturating_mul(*min);
+                match max {
+                    Some(max) => (base.saturating_add(1))
+                        .saturating_mul(max.saturating_sub(*min))
+                        .saturating_add(required),
+                    None => base.saturating_add(1).saturating_add(required),
+                }
+            }
+            RegexEntry::Capture { base, .. } => {
+                Self::constructed_state_count_for_entry(base).saturating_add(2)
+            }
+        }
+    }
+
+    fn add_regex_entry(
+        &mut self,
+        entry: &RegexEntry,
+        spans: Option<&EntrySpans>,
+        target: usize,
+    ) -> usize {
+        let first_new_state = self.states.len();
+        let start_state = self.construct_regex_entry(entry, spans, target);
+
+        //states made for the entry's children already have their own, narrower, spans
+        if let Some(spans) = spans {
+            for state in &mut self.states[first_new_state..] {
+                if state.span.is_none() {
+                    state.span = Some(spans.span.clone());
+                }
+            }
+        }
+
+        start_state
+    }
+
+    fn construct_regex_entry(
+        &mut self,
+        entry: &RegexEntry,
+        spans: Option<&EntrySpans>,
+        target: usize,
+    ) -> usize {
         match entry {
             RegexEntry::AnyCharacter => self.construct_state(
                 "AnyCharacter",
@@ -282,43 +749,100 @@ impl Automaton {
                     TransitionCondition::CharacterClass(class.clone()),
                 )],
             ),
+            //a byte is read as the character of the same value
+            RegexEntry::Byte(class) => self.construct_state(
+                "Byte",
+                [Transition::new(
+                    target,
+                    TransitionCondition::CharacterClass(class.clone()),
+                )],
+            ),
+            RegexEntry::UnicodeScript(script) => self.construct_state(
+                "UnicodeScript",
+                [Transition::new(
+                    target,
+                    TransitionCondition::UnicodeScript(*script),
+                )],
+            ),
+            RegexEntry::NegatedUnicodeScript(script) => self.construct_state(
+                "NegatedUnicodeScript",
+                [Transition::new(
+                    target,
+                    TransitionCondition::NegatedUnicodeScript(*script),
+                )],
+            ),
+            RegexEntry::GraphemeCluster => self.construct_grapheme_cluster(target),
             RegexEntry::Literal(char) => self.construct_state(
                 "Literal",
                 [Transition::new(target, TransitionCondition::Literal(*char))],
             ),
+            RegexEntry::FoldedLiteral(literal) => self.construct_folded_literal(literal, target),
+            RegexEntry::StartOfInput => self.construct_state(
+                "StartOfInput",
+                [Transition::new(target, TransitionCondition::StartOfInput)],
+            ),
+            RegexEntry::EndOfInput => self.construct_state(
+                "EndOfInput",
+                [Transition::new(target, TransitionCondition::EndOfInput)],
+            ),
+            RegexEntry::StartOfLine(terminator) => self.construct_state(
+                "StartOfLine",
+                [Transition::new(
+                    target,
+                    TransitionCondition::StartOfLine(*terminator),
+                )],
+            ),
+            RegexEntry::EndOfLine(terminator) => self.construct_state(
+                "EndOfLine",
+                [Transition::new(
+                    target,
+                    TransitionCondition::EndOfLine(*terminator),
+                )],
+            ),
+            RegexEntry::WordStart => self.construct_state(
+                "WordStart",
+                [Transition::new(target, TransitionCondition::WordStart)],
+            ),
+            RegexEntry::WordEnd => self.construct_state(
+                "WordEnd",
+                [Transition::new(target, TransitionCondition::WordEnd)],
+            ),
             RegexEntry::Concatenation(entries) => {
                 let mut last_target = target;
-                for child_entry in entries.iter().rev() {
-                    last_target = self.add_regex_entry(child_entry, last_target);
+                for (index, child_entry) in entries.iter().enumerate().rev() {
+                    last_target =
+                        self.add_regex_entry(child_entry, child_spans(spans, index), last_target);
                 }
                 last_target
             }
             RegexEntry::Alternation(entries) => {
                 let mut start_states = entries
                     .iter()
-                    .map(|child_entry| self.add_regex_entry(child_entry, target))
+                    .enumerate()
+                    .map(|(index, child_entry)| {
+                        self.add_regex_entry(child_entry, child_spans(spans, index), target)
+                    })
                     .collect::<Vec<_>>();
 
-                let mut start_state = self.construct_state(
+                self.construct_state(
                     "Alternation Epsilon Trampoline State",
                     start_states
                         .into_iter()
                         .map(|target| Transition::new(target, TransitionCondition::Epsilon)),
-                );
-
-                start_state
+                )
             }
             RegexEntry::Repetition {
                 base,
                 min,
                 max: Some(max),
+                lazy,
             } => {
                 //create accept states (accept within the repetition)
                 let mut new_target =
-                    self.construct_maximum_repetition_count(target, base, *max - *min);
+                    self.construct_maximum_repetition_count(target, base, child_spans(spans, 0), *max - *min, *lazy);
 
                 //create non-accept states (accept within the repetition)
-                new_target = self.construct_exact_repetition_count(new_target, base, *min);
+                new_target = self.construct_exact_repetition_count(new_target, base, child_spans(spans, 0), *min);
 
                 new_target
             }
@@ -326,6 +850,7 @@ impl Automaton {
                 base,
                 min,
                 max: None,
+                lazy,
             } => {
                 //looping repetition trampoline
                 let epsilon_trampoline = self.construct_state(
@@ -334,18 +859,30 @@ impl Automaton {
                 );
 
                 //looping repetition implementation
-                let loop_start = self.add_regex_entry(base, epsilon_trampoline);
+                let loop_start = self.add_regex_entry(base, child_spans(spans, 0), epsilon_trampoline);
 
-                //wire back repetition trampoline in a loop
+                //wire back repetition trampoline in a loop, ahead of the exit so that repetitions are greedy when transition order is used as priority (i.e., for captures), or behind it if the repetition is lazy
+                let loop_index = if *lazy { 1 } else { 0 };
                 self.states[epsilon_trampoline]
                     .transitions
-                    .push(Transition::new(loop_start, TransitionCondition::Epsilon));
+                    .insert(loop_index, Transition::new(loop_start, TransitionCondition::Epsilon));
 
                 //non-accept states
-                let non_accept_start =
-                    self.construct_exact_repetition_count(epsilon_trampoline, base, *min);
-
-                non_accept_start
+                self.construct_exact_repetition_count(epsilon_trampoline, base, child_spans(spans, 0), *min)
+            }
+            RegexEntry::Capture { index, base, .. } => {
+                let end = self.construct_state(
+                    "Capture End",
+                    [Transition::new(target, TransitionCondition::CaptureEnd(*index))],
+                );
+                let base_start = self.add_regex_entry(base, child_spans(spans, 0), end);
+                self.construct_state(
+                    "Capture Start",
+                    [Transition::new(
+                        base_start,
+                        TransitionCondition::CaptureStart(*index),
+                    )],
+                )
             }
         }
     }
@@ -354,11 +891,12 @@ impl Automaton {
         &mut self,
         target: usize,
         base: &RegexEntry,
+        base_spans: Option<&EntrySpans>,
         count: u64,
     ) -> usize {
         let mut new_target = target;
         for _ in 0..count {
-            new_target = self.add_regex_entry(base, new_target);
+            new_target = self.add_regex_entry(base, base_spans, new_target);
         }
         new_target
     }
@@ -367,22 +905,50 @@ impl Automaton {
         &mut self,
         target: usize,
         base: &RegexEntry,
+        base_spans: Option<&EntrySpans>,
         max: u64,
+        lazy: bool,
     ) -> usize {
         let mut last_target = target;
         for _ in 0..max {
-            let this_iteration_start = self.add_regex_entry(base, last_target);
-            last_target = self.construct_state(
-                "Maximum Repetition Count Epsilon Trampoline",
-                [
-                    Transition::new(this_iteration_start, TransitionCondition::Epsilon),
-                    Transition::new(target, TransitionCondition::Epsilon),
-                ],
-            )
+            let this_iteration_start = self.add_regex_entry(base, base_spans, last_target);
+            let mut transitions = [
+                Transition::new(this_iteration_start, TransitionCondition::Epsilon),
+                Transition::new(target, TransitionCondition::Epsilon),
+            ];
+            //a lazy repetition prefers to stop over doing another iteration
+            if lazy {
+                transitions.reverse();
+            }
+            last_target =
+                self.construct_state("Maximum Repetition Count Epsilon Trampoline", transitions)
         }
         last_target
     }
 
+    //one state per character of the literal's full folding, each reading on to the states after the characters it can read
+    fn construct_folded_literal(&mut self, literal: &str, target: usize) -> usize {
+        let (length, steps) = fold_steps(literal);
+        let mut states = vec![target; length + 1];
+        for position in (0..length).rev() {
+            let transitions = steps
+                .iter()
+                .filter(|step| step.start == position)
+                .map(|step| {
+                    let condition = match step.chars[..] {
+                        [char] => TransitionCondition::Literal(char),
+                        _ => TransitionCondition::CharacterClass(CharacterClass::Disjunction(
+                            step.chars.iter().copied().map(CharacterClass::Char).collect(),
+                        )),
+                    };
+                    Transition::new(states[step.end], condition)
+                })
+                .collect::<Vec<_>>();
+            states[position] = self.construct_state("FoldedLiteral", transitions);
+        }
+        states[0]
+    }
+
     fn construct_state(
         &mut self,
         name: impl Into<String>,
@@ -392,6 +958,7 @@ impl Automaton {
             id: 0,
             debug_name: name.into(),
             transitions: transitions.into_iter().collect(),
+            span: None,
         })
     }
 
@@ -433,6 +1000,133 @@ impl Automaton {
     pub fn prefix(&self) -> &str{
         &self.prefix
     }
+
+    /// The characters each of the characters after the [`prefix`](Self::prefix) may be, in order,
+    /// as far as every match has to go through one of a few of them. Unlike the prefix, the
+    /// automaton still has states for these, so checking them first only rejects inputs early.
+    /// This keeps the cheap rejection of a literal prefix for case-insensitive patterns, whose
+    /// literals become classes of their cases: `(?i)get` has no prefix, but its prefix classes are
+    /// `[g, G]`, `[e, E]` and `[t, T]`.
+    pub fn prefix_classes(&self) -> &[Vec<char>]{
+        &self.prefix_classes
+    }
+
+    /// Where a match begins, before any of the input has been read.
+    pub fn start(&self) -> MatchState {
+        let mut state = MatchState {
+            prefix_remaining: self.prefix.len(),
+            states: BTreeSet::new(),
+            at_start: self.prefix.is_empty(),
+            after: BreakKind::Plain,
+            after_word: false,
+        };
+        if self.prefix.is_empty() {
+            state.states =
+                self.epsilon_closure(self.start_states.iter().copied(), state.position());
+        }
+        state
+    }
+
+    /// Moves `state` past the next character of the input. This is the same simulation the
+    /// matchers generated by `fastregex` perform, one set of states at a time, so it lets
+    /// matching be driven from outside (e.g., by an editor highlighting a pattern's progress as it
+    /// is typed). Once [`is_failed`](MatchState::is_failed), the state stays failed.
+    pub fn advance(&self, state: &mut MatchState, ch: char) {
+        //the literal prefix has no states of its own, so it is read first
+        if state.prefix_remaining > 0 {
+            let remaining = &self.prefix[self.prefix.len() - state.prefix_remaining..];
+            if remaining.starts_with(ch) {
+                state.prefix_remaining -= ch.len_utf8();
+                if state.prefix_remaining == 0 {
+                    state.after = BreakKind::of(ch);
+                    state.after_word = is_word_character(ch);
+                    state.states =
+                        self.epsilon_closure(self.start_states.iter().copied(), state.position());
+                }
+            } else {
+                state.prefix_remaining = 0;
+            }
+            return;
+        }
+
+        //a multi-line `$` can only be passed once the next character is known to be a line terminator, and a word boundary once it is known whether it is a word character
+        let before_next = Position {
+            before: BreakKind::of(ch).bit(),
+            before_word: Some(is_word_character(ch)),
+            ..state.position()
+        };
+        let current = self.epsilon_closure(state.states.iter().copied(), before_next);
+
+        let next = current
+            .iter()
+            .flat_map(|state| &self.states[*state].transitions)
+            .filter(|transition| transition.condition.matches(ch))
+            .map(|transition| transition.next_state_id);
+        state.at_start = false;
+        state.after = BreakKind::of(ch);
+        state.after_word = is_word_character(ch);
+        state.states = self.epsilon_closure(next, state.position());
+    }
+
+    /// Whether the input read into `state` so far matches as a whole.
+    pub fn is_accepting(&self, state: &MatchState) -> bool {
+        //`$` can only be passed once the input is known to end
+        let at_end = Position {
+            at_end: true,
+            before: 0,
+            before_word: Some(false),
+            ..state.position()
+        };
+        self.epsilon_closure(state.states.iter().copied(), at_end)
+            .iter()
+            .any(|state| self.is_terminal_state(*state))
+    }
+
+    /// Whether the automaton matches the whole input, found by [`advance`](Self::advance)ing
+    /// through it. This is far slower than a generated matcher, and meant for checks like the
+    /// examples a matcher is declared with, or for testing generated matchers against the
+    /// automaton they were built from. Patterns only known at runtime are better served by
+    /// [`crate::runtime::Regex`], which caches the states it has seen.
+    pub fn is_match(&self, input: &str) -> bool {
+        let mut state = self.start();
+        for ch in input.chars() {
+            self.advance(&mut state, ch);
+            if state.is_failed() {
+                return false;
+            }
+        }
+
+        self.is_accepting(&state)
+    }
+
+    //the states reachable without reading, passing only the anchors that hold at `position`
+    fn epsilon_closure(
+        &self,
+        states: impl IntoIterator<Item = usize>,
+        position: Position,
+    ) -> BTreeSet<usize> {
+        let mut closure = BTreeSet::new();
+        let mut pending = states.into_iter().collect::<Vec<_>>();
+        while let Some(state) = pending.pop() {
+            if closure.insert(state) {
+                pending.extend(
+                    self.states[state]
+                        .transitions
+                        .iter()
+                        .filter(|transition| {
+                            transition.condition.is_epsilon()
+                                || position.passes(&transition.condition)
+                        })
+                        .map(|transition| transition.next_state_id),
+                );
+            }
+        }
+        closure
+    }
+}
+
+fn child_spans(spans: Option<&EntrySpans>, index: usize) -> Option<&EntrySpans> {
+    spans.and_then(|spans| spans.children.get(index))
 }
 
 impl Transition {
@@ -445,10 +1139,228 @@ impl Transition {
 }
 
 impl TransitionCondition {
-    fn is_epsilon(&self) -> bool {
+    pub fn is_epsilon(&self) -> bool {
+        matches!(
+            self,
+            TransitionCondition::Epsilon
+                | TransitionCondition::CaptureStart(_)
+                | TransitionCondition::CaptureEnd(_)
+        )
+    }
+
+    /// Whether this is the transition for a `^`, `$` or word boundary, which is an epsilon
+    /// transition that may only be taken at the start or end of the input (or of a line, if it is
+    /// multi-line), or of a word.
+    pub fn is_anchor(&self) -> bool {
+        matches!(
+            self,
+            TransitionCondition::StartOfInput
+                | TransitionCondition::EndOfInput
+                | TransitionCondition::StartOfLine(_)
+                | TransitionCondition::EndOfLine(_)
+                | TransitionCondition::WordStart
+                | TransitionCondition::WordEnd
+        )
+    }
+
+    /// Whether this transition may be taken on `ch`. Epsilon transitions never consume a
+    /// character, so they never match one.
+    pub fn matches(&self, ch: char) -> bool {
         match self {
-            TransitionCondition::Epsilon => true,
-            _ => false,
+            TransitionCondition::AnyCharacter => true,
+            TransitionCondition::Literal(literal) => *literal == ch,
+            TransitionCondition::CharacterClass(class) => class.matches(ch),
+            TransitionCondition::UnicodeCharacterClass(categories) => {
+                categories.contains(&general_category::of(ch))
+            }
+            TransitionCondition::NegatedUnicodeClass(categories) => {
+                !categories.contains(&general_category::of(ch))
+            }
+            TransitionCondition::UnicodeScript(script) => script.contains(ch),
+            TransitionCondition::NegatedUnicodeScript(script) => !script.contains(ch),
+            TransitionCondition::GraphemeBreak(values) => {
+                values.iter().any(|value| value.contains(ch))
+            }
+            TransitionCondition::NegatedGraphemeBreak(values) => {
+                !values.iter().any(|value| value.contains(ch))
+            }
+            TransitionCondition::Epsilon
+            | TransitionCondition::CaptureStart(_)
+            | TransitionCondition::CaptureEnd(_)
+            | TransitionCondition::StartOfInput
+            | TransitionCondition::EndOfInput
+            | TransitionCondition::StartOfLine(_)
+            | TransitionCondition::EndOfLine(_)
+            | TransitionCondition::WordStart
+            | TransitionCondition::WordEnd => false,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Automaton, SimplificationPass};
+    use crate::parser::RegexEntry;
+
+    #[test]
+    fn test_is_match() {
+        let constructed = Automaton::from_regex(RegexEntry::parse("ab(c|d)*e?").unwrap());
+        let mut simplified = constructed.clone();
+        simplified.simplify();
+
+        for automaton in [&constructed, &simplified] {
+            assert!(automaton.is_match("ab"));
+            assert!(automaton.is_match("abcdce"));
+            assert!(!automaton.is_match("a"));
+            assert!(!automaton.is_match("abx"));
+            assert!(!automaton.is_match("abee"));
+        }
+    }
+
+    #[test]
+    fn test_make_unanchored() {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse("ab+").unwrap());
+        automaton.make_unanchored();
+        automaton.simplify();
+
+        assert_eq!(automaton.prefix(), "");
+        assert!(automaton.is_match("xxabbb"));
+        assert!(automaton.is_match("ab"));
+        assert!(!automaton.is_match("xxa"));
+        assert!(!automaton.is_match("abx"));
+    }
+
+    #[test]
+    fn test_prefix_classes() {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse("(?i)get[0-9]").unwrap());
+        automaton.simplify();
+        assert_eq!(automaton.prefix(), "");
+        assert_eq!(
+            automaton.prefix_classes(),
+            [vec!['G', 'g'], vec!['E', 'e'], vec!['T', 't']]
+        );
+        assert!(automaton.is_match("GeT5"));
+
+        //the classes continue after the literal prefix, and stop where a match can go more than one way or at a class too big to be worth checking
+        let mut automaton = Automaton::from_regex(RegexEntry::parse("ab(?i:c)[xy]?d").unwrap());
+        automaton.simplify();
+        assert_eq!(automaton.prefix(), "ab");
+        assert_eq!(automaton.prefix_classes(), [vec!['C', 'c']]);
+
+        let mut automaton = Automaton::from_regex(RegexEntry::parse("(?i)a[a-z]").unwrap());
+        automaton.simplify();
+        assert_eq!(automaton.prefix_classes(), [vec!['A', 'a']]);
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse("ab[0-9]+").unwrap());
+        automaton.simplify();
+        assert_eq!(automaton.prefix(), "ab");
+
+        let mut state = automaton.start();
+        automaton.advance(&mut state, 'a');
+        assert!(!state.is_failed());
+        assert_eq!(state.active_states().count(), 0);
+
+        automaton.advance(&mut state, 'b');
+        assert!(state.active_states().count() > 0);
+        assert!(!automaton.is_accepting(&state));
+
+        automaton.advance(&mut state, '7');
+        assert!(automaton.is_accepting(&state));
+
+        automaton.advance(&mut state, 'x');
+        assert!(state.is_failed());
+        automaton.advance(&mut state, '7');
+        assert!(state.is_failed());
+
+        let mut state = automaton.start();
+        automaton.advance(&mut state, 'b');
+        assert!(state.is_failed());
+    }
+
+    #[test]
+    fn test_snapshots_match_simplify() {
+        let regex = RegexEntry::parse("ab(c|d)*").unwrap();
+
+        let mut simplified = Automaton::from_regex(RegexEntry::parse("ab(c|d)*").unwrap());
+        simplified.simplify();
+
+        let mut automaton = Automaton::from_regex(regex);
+        let snapshots = automaton.simplify_with_snapshots();
+
+        assert_eq!(
+            snapshots.iter().map(|(pass, _)| *pass).collect::<Vec<_>>(),
+            SimplificationPass::ALL
+        );
+        assert_eq!(snapshots[1].1.prefix(), "ab");
+        assert!(snapshots[3]
+            .1
+            .states()
+            .iter()
+            .flat_map(|state| &state.transitions)
+            .all(|transition| !transition.condition.is_epsilon()));
+
+        let last = &snapshots.last().unwrap().1;
+        assert_eq!(last.state_count(), simplified.state_count());
+        assert_eq!(last.start_states(), simplified.start_states());
+        assert_eq!(automaton.state_count(), simplified.state_count());
+    }
+
+    #[test]
+    fn test_long_epsilon_chain() {
+        //each optional repetition adds an epsilon transition skipping it, chained one after another, which is deeper than this small stack when followed recursively
+        std::thread::Builder::new()
+            .stack_size(16 * 1024)
+            .spawn(|| {
+                let mut automaton =
+                    Automaton::from_regex(RegexEntry::parse("(a?){500}b").unwrap());
+                automaton.simplify();
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_state_limit() {
+        let parse = |pattern| RegexEntry::parse(pattern).unwrap();
+
+        let error = Automaton::from_regex_with_max_states(parse("x(a|b){1000,2000}y(cd){3}"), 4096)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            "Pattern needs 11015 states, more than the limit of 4096, 11000 of them for the repetition `(a|b){1000,2000}`"
+        );
+
+        let error = Automaton::check_constructed_state_count(&parse("abcdef"), 4).unwrap_err();
+        assert_eq!(error, "Pattern needs 7 states, more than the limit of 4");
+
+        assert!(Automaton::from_regex_with_max_states(parse("(a|b){10}"), 100).is_ok());
+    }
+
+    #[test]
+    fn test_state_spans() {
+        let pattern = "a(b|c)*";
+        let (regex, spans) = RegexEntry::parse_with_spans(pattern).unwrap();
+        let automaton = Automaton::from_regex_with_spans(regex, &spans);
+
+        let fragments = |name: &str| {
+            automaton
+                .states()
+                .iter()
+                .filter(|state| state.debug_name == name)
+                .map(|state| &pattern[state.span.clone().unwrap()])
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(fragments("Literal"), vec!["b", "c", "a"]);
+        assert_eq!(fragments("Capture Start"), vec!["(b|c)"]);
+        assert_eq!(
+            fragments("Repetition No-Maximum Epsilon Trampoline"),
+            vec!["(b|c)*"]
+        );
+        assert_eq!(automaton.get_state(0).span, None);
+    }
+}
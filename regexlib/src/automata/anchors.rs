@@ -0,0 +1,746 @@
+//! Turning the `^` and `$` transitions into ordinary ones. Whether an anchor can be passed depends
+//! on where in the input the automaton is, which an automaton only knows at its start (before
+//! reading anything) and at its end (when it may not read anything more). So the states that can
+//! be reached at the start and at the end without reading are copied, and in those copies the
+//! anchor that holds there becomes an epsilon transition. In the original states, which stand for
+//! the middle of the input, `^` can never be passed and `$` leads into the copies for the end of
+//! the input, which can not read anything more. Transitions keep their order, so the priorities
+//! captures rely on are unchanged.
+//!
+//! Multi-line anchors work the same way, with the start of a line being wherever a line
+//! terminator has just been read, and the end of a line being a position where the next character
+//! read must be a line terminator, if the input does not end there. Where a `\r\n` is one line
+//! terminator, whether a `\r` was just read matters as well: a `^` right after one can only be
+//! passed if the next character is not a `\n`, and a `$` not before one.
+//!
+//! Word boundaries depend on the characters on both sides of them. When there are any, every
+//! transition that reads a character is split into the part that reads word characters, which
+//! leads to copies for being after one, and the part that reads the others. Passing a boundary
+//! leads to copies in which the next character read must (or must not) be a word character.
+
+use std::collections::BTreeMap;
+
+use crate::automata::{Automaton, State, Transition, TransitionCondition};
+use crate::general_category;
+use crate::line_terminator::LineTerminator;
+use crate::parser::character_class::CharacterClass;
+use crate::parser::RegexToken;
+
+/// The kinds of character that line terminators tell apart.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub(super) enum BreakKind {
+    /// A character that never ends a line.
+    Plain,
+    Newline,
+    Return,
+    /// One of the line breaks other than `\n` and `\r` that [`LineTerminator::Unicode`] has.
+    Other,
+}
+
+//sets of break kinds, as bits of `BreakKind::bit`
+const PLAIN: u8 = 0b0001;
+const NEWLINE: u8 = 0b0010;
+const RETURN: u8 = 0b0100;
+const OTHER_BREAK: u8 = 0b1000;
+const ANY_KIND: u8 = 0b1111;
+
+impl BreakKind {
+    const ALL: [BreakKind; 4] = [
+        BreakKind::Plain,
+        BreakKind::Newline,
+        BreakKind::Return,
+        BreakKind::Other,
+    ];
+
+    pub(super) fn of(ch: char) -> BreakKind {
+        match ch {
+            '\n' => BreakKind::Newline,
+            '\r' => BreakKind::Return,
+            ch if LineTerminator::Unicode.ends_line(ch) => BreakKind::Other,
+            _ => BreakKind::Plain,
+        }
+    }
+
+    pub(super) fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    fn class(self) -> CharacterClass {
+        match self {
+            BreakKind::Plain => {
+                CharacterClass::Negated(Box::new(CharacterClass::vertical_whitespace()))
+            }
+            BreakKind::Newline => CharacterClass::Char('\n'),
+            BreakKind::Return => CharacterClass::Char('\r'),
+            BreakKind::Other => CharacterClass::Disjunction(vec![
+                CharacterClass::Range {
+                    start: '\x0B',
+                    end: '\x0C',
+                },
+                CharacterClass::Char('\u{85}'),
+                CharacterClass::Range {
+                    start: '\u{2028}',
+                    end: '\u{2029}',
+                },
+            ]),
+        }
+    }
+}
+
+//the kinds of character that end a line with `terminator`
+fn ending_lines(terminator: LineTerminator) -> u8 {
+    match terminator {
+        LineTerminator::Newline => NEWLINE,
+        LineTerminator::Crlf => NEWLINE | RETURN,
+        LineTerminator::Unicode => NEWLINE | RETURN | OTHER_BREAK,
+    }
+}
+
+/// Where in the input a copy of a state stands for; the original states are in the middle of a
+/// line, after a character that is not a word character or line terminator. The start of the
+/// input is also the start of a line, and its end the end of a line.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub(super) struct Position {
+    pub(super) at_start: bool,
+    pub(super) at_end: bool,
+    /// The kind of the last character read, if a line anchor tells it apart.
+    pub(super) after: BreakKind,
+    /// The kinds (as bits of [`BreakKind::bit`]) the next character read may be, which a `$`
+    /// narrows down to the line terminators.
+    pub(super) before: u8,
+    pub(super) after_word: bool,
+    /// Whether the next character read must be a word character, or must not be one, if that is
+    /// known.
+    pub(super) before_word: Option<bool>,
+}
+
+const MIDDLE: Position = Position {
+    at_start: false,
+    at_end: false,
+    after: BreakKind::Plain,
+    before: ANY_KIND,
+    after_word: false,
+    before_word: None,
+};
+
+impl Default for Position {
+    fn default() -> Self {
+        MIDDLE
+    }
+}
+
+/// Whether `\w` matches `ch`, which is what word boundaries tell apart.
+pub(crate) fn is_word_character(ch: char) -> bool {
+    RegexToken::WORD_CATEGORIES.contains(&general_category::of(ch))
+}
+
+impl Position {
+    /// Whether the anchor `condition` can be passed here, whatever the next character read is.
+    pub(super) fn passes(&self, condition: &TransitionCondition) -> bool {
+        match condition {
+            TransitionCondition::StartOfInput => self.at_start,
+            TransitionCondition::EndOfInput => self.at_end,
+            TransitionCondition::StartOfLine(terminator) => {
+                self.at_start
+                    || (self.ends_line(*terminator) && self.line_start_allows(*terminator) == self.before)
+            }
+            TransitionCondition::EndOfLine(terminator) => {
+                self.at_end
+                    || (self.before != 0 && self.before & !self.line_end_allows(*terminator) == 0)
+            }
+            TransitionCondition::WordStart => !self.after_word && self.before_word == Some(true),
+            TransitionCondition::WordEnd => self.after_word && self.before_word == Some(false),
+            _ => false,
+        }
+    }
+
+    //whether the last character read ends a line with `terminator`
+    fn ends_line(&self, terminator: LineTerminator) -> bool {
+        self.after.bit() & ending_lines(terminator) != 0
+    }
+
+    //the kinds the next character may be for a `^` after a line terminator to be passed, which can not be the `\n` of a `\r\n`
+    fn line_start_allows(&self, terminator: LineTerminator) -> u8 {
+        if self.after == BreakKind::Return && terminator.joins_crlf() {
+            self.before & !NEWLINE
+        } else {
+            self.before
+        }
+    }
+
+    //the kinds the next character may be for a `$` to be passed, which are the line terminators other than the `\n` of a `\r\n`
+    fn line_end_allows(&self, terminator: LineTerminator) -> u8 {
+        let allowed = ending_lines(terminator);
+        if self.after == BreakKind::Return && terminator.joins_crlf() {
+            allowed & !NEWLINE
+        } else {
+            allowed
+        }
+    }
+
+    fn description(&self) -> String {
+        let mut parts = Vec::new();
+        if self.at_start {
+            parts.push("at the start of the input");
+        } else {
+            match self.after {
+                BreakKind::Plain => {}
+                BreakKind::Newline => parts.push("after a \\n"),
+                BreakKind::Return => parts.push("after a \\r"),
+                BreakKind::Other => parts.push("after a line break"),
+            }
+        }
+        if self.at_end {
+            parts.push("at the end of the input");
+        } else if self.before & PLAIN == 0 {
+            parts.push("at the end of a line");
+        } else if self.before != ANY_KIND {
+            parts.push("not before a \\n");
+        }
+        if self.after_word {
+            parts.push("after a word character");
+        }
+        match self.before_word {
+            Some(true) => parts.push("before a word character"),
+            Some(false) => parts.push("not before a word character"),
+            None => {}
+        }
+        parts.join(" and ")
+    }
+}
+
+struct Copies {
+    original_transitions: Vec<Vec<Transition>>,
+    ids: BTreeMap<(Position, usize), usize>,
+    pending: Vec<(Position, usize, usize)>,
+    //the kinds of character whose reading leads to copies of their own, which only the line anchors that tell them apart need
+    tracked: u8,
+    //whether reading a character has to tell word characters apart, which only word boundaries need
+    has_word_boundaries: bool,
+}
+
+impl Automaton {
+    pub(super) fn resolve_anchors(&mut self) {
+        if !self.has_anchors() {
+            return;
+        }
+
+        //a `^` needs to know whether a line terminator was just read, and a `$` whether the `\r` of a `\r\n` was
+        let tracked = self
+            .states
+            .iter()
+            .flat_map(|state| &state.transitions)
+            .map(|transition| match transition.condition {
+                TransitionCondition::StartOfLine(terminator) => ending_lines(terminator),
+                TransitionCondition::EndOfLine(terminator) if terminator.joins_crlf() => RETURN,
+                _ => 0,
+            })
+            .fold(0, |tracked, kinds| tracked | kinds);
+        let has_word_boundaries =
+            self.states
+                .iter()
+                .flat_map(|state| &state.transitions)
+                .any(|transition| {
+                    matches!(
+                        transition.condition,
+                        TransitionCondition::WordStart | TransitionCondition::WordEnd
+                    )
+                });
+        let mut copies = Copies {
+            original_transitions: self
+                .states
+                .iter()
+                .map(|state| state.transitions.clone())
+                .collect(),
+            ids: BTreeMap::new(),
+            pending: Vec::new(),
+            tracked,
+            has_word_boundaries,
+        };
+
+        for state_id in 0..copies.original_transitions.len() {
+            self.states[state_id].transitions =
+                self.resolve_transitions(&mut copies, MIDDLE, state_id);
+        }
+
+        let at_start = Position {
+            at_start: true,
+            ..MIDDLE
+        };
+        self.start_states = self
+            .start_states
+            .clone()
+            .into_iter()
+            .map(|state_id| self.copy_at(&mut copies, at_start, state_id))
+            .collect();
+
+        while let Some((position, state_id, copy_id)) = copies.pending.pop() {
+            self.states[copy_id].transitions =
+                self.resolve_transitions(&mut copies, position, state_id);
+        }
+    }
+
+    /// Whether the automaton has transitions for `^` or `$` that have not been resolved yet (see
+    /// [`SimplificationPass::ResolveAnchors`](super::SimplificationPass::ResolveAnchors)).
+    pub fn has_anchors(&self) -> bool {
+        self.states
+            .iter()
+            .flat_map(|state| &state.transitions)
+            .any(|transition| transition.condition.is_anchor())
+    }
+
+    /// Whether the automaton has transitions for `$` that have not been resolved yet, multi-line
+    /// or not, or for word boundaries, which depend on what follows them as well.
+    pub fn has_end_anchors(&self) -> bool {
+        self.states
+            .iter()
+            .flat_map(|state| &state.transitions)
+            .any(|transition| {
+                matches!(
+                    transition.condition,
+                    TransitionCondition::EndOfInput
+                        | TransitionCondition::EndOfLine(_)
+                        | TransitionCondition::WordStart
+                        | TransitionCondition::WordEnd
+                )
+            })
+    }
+
+    //the transitions of the state's copy at `position`, in the order of the original's
+    fn resolve_transitions(
+        &mut self,
+        copies: &mut Copies,
+        position: Position,
+        state_id: usize,
+    ) -> Vec<Transition> {
+        let mut transitions = Vec::new();
+        for transition in copies.original_transitions[state_id].clone() {
+            let target = transition.next_state_id;
+            let condition = &transition.condition;
+            //the characters the transition reads, each with where reading them leads
+            let reads = match condition {
+                TransitionCondition::StartOfInput if position.at_start => {
+                    transitions.push(Transition::new(
+                        self.copy_at(copies, position, target),
+                        TransitionCondition::Epsilon,
+                    ));
+                    continue;
+                }
+                //past a `^` right after a `\r` that is part of a `\r\n`, the `\n` can not follow
+                TransitionCondition::StartOfLine(terminator)
+                    if position.at_start || position.ends_line(*terminator) =>
+                {
+                    let at_line_start = match position.at_start {
+                        true => position,
+                        false => Position {
+                            before: position.line_start_allows(*terminator),
+                            ..position
+                        },
+                    };
+                    transitions.push(Transition::new(
+                        self.copy_at(copies, at_line_start, target),
+                        TransitionCondition::Epsilon,
+                    ));
+                    continue;
+                }
+                TransitionCondition::StartOfInput | TransitionCondition::StartOfLine(_) => continue,
+                //neither the end of the input nor a line terminator is a word character
+                TransitionCondition::EndOfInput | TransitionCondition::EndOfLine(_)
+                    if position.before_word == Some(true) =>
+                {
+                    continue
+                }
+                TransitionCondition::EndOfInput => {
+                    let at_end = Position {
+                        at_end: true,
+                        before: 0,
+                        ..position
+                    };
+                    transitions.push(Transition::new(
+                        self.copy_at(copies, at_end, target),
+                        TransitionCondition::Epsilon,
+                    ));
+                    continue;
+                }
+                TransitionCondition::EndOfLine(terminator) => {
+                    let at_line_end = Position {
+                        before: position.before & position.line_end_allows(*terminator),
+                        ..position
+                    };
+                    transitions.push(Transition::new(
+                        self.copy_at(copies, at_line_end, target),
+                        TransitionCondition::Epsilon,
+                    ));
+                    continue;
+                }
+                TransitionCondition::WordStart | TransitionCondition::WordEnd => {
+                    let before_word = *condition == TransitionCondition::WordStart;
+                    let before_non_word =
+                        position.before & PLAIN == 0 || position.before_word == Some(false);
+                    let passes = if before_word {
+                        !position.after_word && !before_non_word
+                    } else {
+                        position.after_word && position.before_word != Some(true)
+                    };
+                    if !passes {
+                        continue;
+                    }
+                    let at_boundary = Position {
+                        before_word: Some(before_word),
+                        ..position
+                    };
+                    transitions.push(Transition::new(
+                        self.copy_at(copies, at_boundary, target),
+                        TransitionCondition::Epsilon,
+                    ));
+                    continue;
+                }
+                condition if condition.is_epsilon() => {
+                    transitions.push(Transition::new(
+                        self.copy_at(copies, position, target),
+                        condition.clone(),
+                    ));
+                    continue;
+                }
+                //reading a character leaves the start behind, and the end can not be followed by one
+                _ if position.at_end => continue,
+                _ => line_break_reads(condition, position.before, copies.tracked),
+            };
+
+            for (condition, after) in reads {
+                if !copies.has_word_boundaries {
+                    transitions.push(Transition::new(
+                        self.copy_at(copies, after, target),
+                        condition,
+                    ));
+                    continue;
+                }
+                //whether a word character was read decides the boundaries that can be passed next
+                for word in [false, true] {
+                    if position.before_word == Some(!word) {
+                        continue;
+                    }
+                    let Some(condition) = restricted_to_words(&condition, word) else {
+                        continue;
+                    };
+                    let after = Position {
+                        after_word: word,
+                        ..after
+                    };
+                    transitions.push(Transition::new(
+                        self.copy_at(copies, after, target),
+                        condition,
+                    ));
+                }
+            }
+        }
+        transitions
+    }
+
+    //a state without transitions is the same wherever in the input it is, so it is not copied, unless a word character has to follow, which a terminal state can not match with
+    fn copy_at(&mut self, copies: &mut Copies, position: Position, state_id: usize) -> usize {
+        let is_dead_end = copies.original_transitions[state_id].is_empty();
+        if position == MIDDLE || (is_dead_end && position.before_word != Some(true)) {
+            return state_id;
+        }
+        if let Some(copy_id) = copies.ids.get(&(position, state_id)) {
+            return *copy_id;
+        }
+
+        let original = &self.states[state_id];
+        let copy = State {
+            id: 0,
+            debug_name: format!("{} ({})", original.debug_name, position.description()),
+            transitions: Vec::new(),
+            span: original.span.clone(),
+        };
+        let copy_id = self.add_state(copy);
+        //the input can not end where a word character has to come next
+        if self.terminal_states.contains(&state_id) && position.before_word != Some(true) {
+            self.terminal_states.push(copy_id);
+        }
+
+        copies.ids.insert((position, state_id), copy_id);
+        copies.pending.push((position, state_id, copy_id));
+        copy_id
+    }
+}
+
+//the parts of `condition` that read the kinds of character the next one may be (`before`), each with where reading it leads: the tracked kinds lead to copies for being after them, and the others are read together, by the condition itself where it can be. A `\r` is never read together with the others once it is tracked, since in the middle of a line a `$` could then be passed before the `\n` of a `\r\n`; the other kinds only make more `^`s passable, so reading them in the middle as well only keeps states that can not match alive.
+fn line_break_reads(
+    condition: &TransitionCondition,
+    before: u8,
+    tracked: u8,
+) -> Vec<(TransitionCondition, Position)> {
+    let mut together = before & !(tracked & RETURN);
+    //a literal is read one way only
+    if let TransitionCondition::Literal(literal) = condition {
+        if tracked & BreakKind::of(*literal).bit() != 0 {
+            together = 0;
+        }
+    }
+
+    let mut reads = Vec::new();
+    if together == ANY_KIND {
+        reads.push((condition.clone(), MIDDLE));
+    } else if let Some(read) = restricted_to_kinds(condition, together) {
+        reads.push((read, MIDDLE));
+    }
+    for kind in BreakKind::ALL {
+        if before & tracked & kind.bit() == 0 {
+            continue;
+        }
+        if let Some(read) = restricted_to_kinds(condition, kind.bit()) {
+            let after = Position {
+                after: kind,
+                ..MIDDLE
+            };
+            reads.push((read, after));
+        }
+    }
+    reads
+}
+
+//the part of `condition` that reads characters of the kinds in `kinds`, if it can read any
+fn restricted_to_kinds(condition: &TransitionCondition, kinds: u8) -> Option<TransitionCondition> {
+    let included = BreakKind::ALL
+        .into_iter()
+        .filter(|kind| kinds & kind.bit() != 0)
+        .collect::<Vec<_>>();
+    match (condition, &included[..]) {
+        (_, []) => None,
+        (TransitionCondition::Literal(literal), _) => {
+            (kinds & BreakKind::of(*literal).bit() != 0).then(|| condition.clone())
+        }
+        (_, [BreakKind::Newline]) => condition
+            .matches('\n')
+            .then_some(TransitionCondition::Literal('\n')),
+        (_, [BreakKind::Return]) => condition
+            .matches('\r')
+            .then_some(TransitionCondition::Literal('\r')),
+        (_, included) => {
+            let class = CharacterClass::Disjunction(
+                included.iter().map(|kind| kind.class()).collect(),
+            );
+            Some(TransitionCondition::CharacterClass(match condition {
+                TransitionCondition::AnyCharacter => class,
+                condition => {
+                    CharacterClass::Intersection(Box::new(condition_class(condition)), Box::new(class))
+                }
+            }))
+        }
+    }
+}
+
+//the part of `condition` that reads word characters, or the part that reads the others if `word` is false
+fn restricted_to_words(condition: &TransitionCondition, word: bool) -> Option<TransitionCondition> {
+    let words = CharacterClass::with_categories(&RegexToken::WORD_CATEGORIES);
+    let class = match condition {
+        TransitionCondition::Literal(literal) => {
+            return (is_word_character(*literal) == word).then(|| condition.clone())
+        }
+        TransitionCondition::AnyCharacter if word => {
+            return Some(TransitionCondition::CharacterClass(words))
+        }
+        TransitionCondition::AnyCharacter => {
+            return Some(TransitionCondition::CharacterClass(
+                CharacterClass::Negated(Box::new(words)),
+            ))
+        }
+        condition => condition_class(condition),
+    };
+
+    Some(TransitionCondition::CharacterClass(if word {
+        CharacterClass::Intersection(Box::new(class), Box::new(words))
+    } else {
+        CharacterClass::Difference(Box::new(class), Box::new(words))
+    }))
+}
+
+//the class of the characters a transition other than a literal or `.` reads
+fn condition_class(condition: &TransitionCondition) -> CharacterClass {
+    match condition {
+        TransitionCondition::CharacterClass(class) => class.clone(),
+        TransitionCondition::UnicodeCharacterClass(categories) => {
+            CharacterClass::with_categories(categories)
+        }
+        TransitionCondition::NegatedUnicodeClass(categories) => {
+            CharacterClass::Negated(Box::new(CharacterClass::with_categories(categories)))
+        }
+        TransitionCondition::UnicodeScript(script) => {
+            CharacterClass::from_ranges(script.ranges().to_vec())
+        }
+        TransitionCondition::NegatedUnicodeScript(script) => CharacterClass::Negated(Box::new(
+            CharacterClass::from_ranges(script.ranges().to_vec()),
+        )),
+        TransitionCondition::GraphemeBreak(values) => CharacterClass::from_ranges(
+            values
+                .iter()
+                .flat_map(|value| value.ranges())
+                .copied()
+                .collect(),
+        ),
+        TransitionCondition::NegatedGraphemeBreak(values) => {
+            CharacterClass::Negated(Box::new(CharacterClass::from_ranges(
+                values
+                    .iter()
+                    .flat_map(|value| value.ranges())
+                    .copied()
+                    .collect(),
+            )))
+        }
+        _ => unreachable!("only transitions that read a character are split"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::automata::{Automaton, SimplificationPass};
+    use crate::line_terminator::LineTerminator;
+    use crate::parser::{ParseOptions, RegexEntry};
+    use crate::runtime::{Limits, Regex};
+
+    fn resolved(pattern: &str) -> Automaton {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse(pattern).unwrap());
+        automaton.run_pass(SimplificationPass::ResolveAnchors);
+        assert!(!automaton.has_anchors());
+        automaton
+    }
+
+    #[test]
+    fn test_anchors_at_the_ends_change_nothing() {
+        let automaton = resolved("^ab*$");
+        assert!(automaton.is_match("abbb"));
+        assert!(automaton.is_match("a"));
+        assert!(!automaton.is_match("ba"));
+    }
+
+    #[test]
+    fn test_anchors_inside_the_pattern() {
+        //a `^` after a character or a `$` before one can never be passed
+        let automaton = resolved("(a^)|(b)c");
+        assert!(automaton.is_match("bc"));
+        assert!(!automaton.is_match("ac"));
+
+        let automaton = resolved("a(?:(b$)|(c)d?)");
+        assert!(automaton.is_match("ab"));
+        assert!(automaton.is_match("acd"));
+        assert!(!automaton.is_match("abd"));
+
+        //repeating an anchor only repeats it at the same position
+        let automaton = resolved("(^a)*");
+        assert!(automaton.is_match(""));
+        assert!(automaton.is_match("a"));
+        assert!(!automaton.is_match("aa"));
+    }
+
+    #[test]
+    fn test_multi_line_anchors() {
+        let automaton = resolved("(?m)a$\n^b");
+        assert!(automaton.is_match("a\nb"));
+
+        let automaton = resolved("(?m)(a$)?.^b");
+        assert!(automaton.is_match("a\nb"));
+        assert!(!automaton.is_match("axb"));
+        assert!(!automaton.is_match("\nxb"));
+
+        //the start and end of the input are the start and end of a line too
+        let automaton = resolved("(?m)^a$");
+        assert!(automaton.is_match("a"));
+        assert!(!automaton.is_match("a\n"));
+    }
+
+    #[test]
+    fn test_line_terminators() {
+        let patterns = [
+            "(?m)^a$",
+            "(?ms).*^a.*",
+            "(?ms).*a$.*",
+            "(?ms).*\r^.*",
+            "(?ms).*$\n.*",
+            "(?ms)(?:^.|$.)*",
+            "(?m-s)(?:^.*$.?)*",
+        ];
+        let mut haystacks = vec![String::new()];
+        for _ in 0..4 {
+            haystacks = haystacks
+                .iter()
+                .flat_map(|haystack| {
+                    ['a', '\r', '\n', '\u{2028}'].map(|ch| format!("{haystack}{ch}"))
+                })
+                .chain(haystacks.clone())
+                .collect();
+        }
+
+        //the resolved automaton, the simulation of the anchors and the capture engine's checks of them agree
+        for line_terminator in LineTerminator::ALL {
+            let options = ParseOptions {
+                line_terminator,
+                ..ParseOptions::default()
+            };
+            for pattern in patterns {
+                let regex = RegexEntry::parse_with_options(pattern, options).unwrap();
+                let automaton = Automaton::from_regex(regex.clone());
+                let mut resolved = automaton.clone();
+                resolved.run_pass(SimplificationPass::ResolveAnchors);
+                let runtime = Regex::with_options(pattern, options, Limits::default()).unwrap();
+                for haystack in &haystacks {
+                    let expected = runtime.captures(haystack).is_some();
+                    assert_eq!(
+                        automaton.is_match(haystack),
+                        expected,
+                        "{pattern} on {haystack:?} with {line_terminator:?}"
+                    );
+                    assert_eq!(
+                        resolved.is_match(haystack),
+                        expected,
+                        "{pattern} on {haystack:?} with {line_terminator:?}, resolved"
+                    );
+                }
+            }
+        }
+
+        let options = ParseOptions {
+            line_terminator: LineTerminator::Crlf,
+            ..ParseOptions::default()
+        };
+        let regex = RegexEntry::parse_with_options("(?m)a$\r\n^b", options).unwrap();
+        let mut automaton = Automaton::from_regex(regex);
+        automaton.run_pass(SimplificationPass::ResolveAnchors);
+        assert!(automaton.is_match("a\r\nb"));
+        let regex = RegexEntry::parse_with_options("(?m)a\r$\n", options).unwrap();
+        let mut automaton = Automaton::from_regex(regex);
+        automaton.run_pass(SimplificationPass::ResolveAnchors);
+        assert!(!automaton.is_match("a\r\n"));
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        let automaton = resolved("x?\\b{start}a");
+        assert!(automaton.is_match("a"));
+        assert!(!automaton.is_match("xa"));
+
+        let automaton = resolved("a\\b{end}.?");
+        assert!(automaton.is_match("a"));
+        assert!(automaton.is_match("a-"));
+        assert!(!automaton.is_match("ab"));
+
+        //a boundary needs a word character on one side of it
+        assert!(!resolved("\\b{start}").is_match(""));
+        assert!(!resolved("\\b{end}").is_match(""));
+        assert!(!resolved("-\\b{end}").is_match("-"));
+
+        let automaton = resolved(".*\\b{start}cat\\b{end}.*");
+        assert!(automaton.is_match("a cat!"));
+        assert!(automaton.is_match("cat"));
+        assert!(!automaton.is_match("concat"));
+        assert!(!automaton.is_match("cats"));
+    }
+
+    #[test]
+    fn test_start_and_end_of_empty_input() {
+        let automaton = resolved("$^");
+        assert!(automaton.is_match(""));
+        assert!(!automaton.is_match("a"));
+    }
+}
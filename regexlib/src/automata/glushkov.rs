@@ -0,0 +1,280 @@
+//! The Glushkov (or position) construction: one state per occurrence of a character-matching
+//! entry in the pattern (a position), plus a start state, with no epsilon transitions. A
+//! transition into a position is taken on the character that position matches.
+
+use std::collections::BTreeSet;
+
+use crate::automata::{Automaton, State, Transition, TransitionCondition};
+use crate::case_folding::expand_folded_literal;
+use crate::parser::RegexEntry;
+
+//what one entry contributes: whether it matches the empty string, and the positions a match of it can start and end at
+struct Fragment {
+    nullable: bool,
+    first: BTreeSet<usize>,
+    last: BTreeSet<usize>,
+}
+
+impl Fragment {
+    fn empty(nullable: bool) -> Self {
+        Self {
+            nullable,
+            first: BTreeSet::new(),
+            last: BTreeSet::new(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Positions {
+    conditions: Vec<TransitionCondition>,
+    //the positions that may come right after each position
+    follow: Vec<BTreeSet<usize>>,
+}
+
+impl Positions {
+    fn add(&mut self, condition: TransitionCondition) -> Fragment {
+        let position = self.conditions.len();
+        self.conditions.push(condition);
+        self.follow.push(BTreeSet::new());
+
+        Fragment {
+            nullable: false,
+            first: BTreeSet::from([position]),
+            last: BTreeSet::from([position]),
+        }
+    }
+
+    fn fragment(&mut self, entry: &RegexEntry) -> Fragment {
+        match entry {
+            RegexEntry::AnyCharacter => self.add(TransitionCondition::AnyCharacter),
+            RegexEntry::UnicodeCharacterClass(classes) => {
+                self.add(TransitionCondition::UnicodeCharacterClass(classes.clone()))
+            }
+            RegexEntry::NegatedUnicodeCharacterClass(classes) => {
+                self.add(TransitionCondition::NegatedUnicodeClass(classes.clone()))
+            }
+            //a byte is read as the character of the same value
+            RegexEntry::NonUnicodeCharacterClass(class) | RegexEntry::Byte(class) => {
+                self.add(TransitionCondition::CharacterClass(class.clone()))
+            }
+            RegexEntry::UnicodeScript(script) => {
+                self.add(TransitionCondition::UnicodeScript(*script))
+            }
+            RegexEntry::NegatedUnicodeScript(script) => {
+                self.add(TransitionCondition::NegatedUnicodeScript(*script))
+            }
+            RegexEntry::Literal(char) => self.add(TransitionCondition::Literal(*char)),
+            //a position reads one character, so each way of reading the literal is spelled out
+            RegexEntry::FoldedLiteral(literal) => self.fragment(&expand_folded_literal(literal)),
+            RegexEntry::Concatenation(entries) => {
+                entries.iter().fold(Fragment::empty(true), |before, entry| {
+                    let after = self.fragment(entry);
+                    self.concatenate(before, after)
+                })
+            }
+            RegexEntry::Alternation(entries) => {
+                let mut alternation = Fragment::empty(false);
+                for entry in entries {
+                    let branch = self.fragment(entry);
+                    alternation.nullable |= branch.nullable;
+                    alternation.first.extend(branch.first);
+                    alternation.last.extend(branch.last);
+                }
+                alternation
+            }
+            //positions have no boundaries to record, so capture groups only group
+            RegexEntry::Capture { base, .. } => self.fragment(base),
+            RegexEntry::Repetition { base, min, max, .. } => self.repetition(base, *min, *max),
+            RegexEntry::StartOfInput
+            | RegexEntry::EndOfInput
+            | RegexEntry::StartOfLine(_)
+            | RegexEntry::EndOfLine(_)
+            | RegexEntry::WordStart
+            | RegexEntry::WordEnd => {
+                panic!("The Glushkov construction does not support anchors")
+            }
+            //a position matches one character, and a grapheme cluster can be several
+            RegexEntry::GraphemeCluster => {
+                panic!("The Glushkov construction does not support \\X")
+            }
+        }
+    }
+
+    //counted repetitions are spelled out with a copy of the base for each count, so `a{2,3}` is `aaa?`
+    fn repetition(&mut self, base: &RegexEntry, min: u64, max: Option<u64>) -> Fragment {
+        let mut repetition = Fragment::empty(true);
+
+        //an unbounded repetition needs at least the one copy that loops back to itself
+        let required = match max {
+            Some(_) => min,
+            None => min.max(1),
+        };
+        for _ in 0..required {
+            let copy = self.fragment(base);
+            if max.is_none() {
+                for position in &copy.last {
+                    self.follow[*position].extend(copy.first.iter().copied());
+                }
+            }
+            repetition = self.concatenate(repetition, copy);
+        }
+
+        match max {
+            None => {
+                repetition.nullable |= min == 0;
+                repetition
+            }
+            Some(max) => (min..max).fold(repetition, |repetition, _| {
+                let mut copy = self.fragment(base);
+                copy.nullable = true;
+                self.concatenate(repetition, copy)
+            }),
+        }
+    }
+
+    fn concatenate(&mut self, before: Fragment, after: Fragment) -> Fragment {
+        for position in &before.last {
+            self.follow[*position].extend(after.first.iter().copied());
+        }
+
+        let mut first = before.first;
+        if before.nullable {
+            first.extend(after.first.iter().copied());
+        }
+        let mut last = after.last;
+        if after.nullable {
+            last.extend(before.last);
+        }
+
+        Fragment {
+            nullable: before.nullable && after.nullable,
+            first,
+            last,
+        }
+    }
+}
+
+impl Automaton {
+    /// Builds the automaton for `regex` with the Glushkov construction, as an alternative to
+    /// [`from_regex`](Self::from_regex). It has one state per character-matching entry of the
+    /// pattern (with counted repetitions spelled out) plus a start state, and no epsilon
+    /// transitions, so it is often smaller than what `from_regex` builds even after simplifying.
+    ///
+    /// Capture groups are not marked in the result, so it can not be used to find captures.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern has a `^` or `$` (see [`RegexEntry::has_anchors`]), since anchors
+    /// match no character and so have no position, or a `\X` (see
+    /// [`RegexEntry::has_grapheme_clusters`]), which can match several.
+    pub fn from_regex_glushkov(regex: &RegexEntry) -> Self {
+        let mut positions = Positions::default();
+        let pattern = positions.fragment(regex);
+
+        //the start state is state 0, and position `n` is state `n + 1`
+        let transitions_to = |targets: &BTreeSet<usize>| {
+            targets
+                .iter()
+                .map(|position| {
+                    Transition::new(position + 1, positions.conditions[*position].clone())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut automaton = Self::new_empty();
+        automaton.add_state(State {
+            debug_name: "Glushkov Start".into(),
+            id: 0,
+            transitions: transitions_to(&pattern.first),
+            span: None,
+        });
+        for (position, follow) in positions.follow.iter().enumerate() {
+            automaton.add_state(State {
+                debug_name: format!("Position {position}"),
+                id: 0,
+                transitions: transitions_to(follow),
+                span: None,
+            });
+        }
+
+        automaton.start_states = vec![0];
+        if pattern.nullable {
+            automaton.terminal_states.push(0);
+        }
+        automaton
+            .terminal_states
+            .extend(pattern.last.iter().map(|position| position + 1));
+
+        automaton
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::automata::Automaton;
+    use crate::parser::RegexEntry;
+
+    fn both(pattern: &str) -> (Automaton, Automaton) {
+        let regex = RegexEntry::parse(pattern).unwrap();
+        let glushkov = Automaton::from_regex_glushkov(&regex);
+        let mut thompson = Automaton::from_regex(regex);
+        thompson.simplify();
+        (glushkov, thompson)
+    }
+
+    #[test]
+    fn test_same_language() {
+        let inputs = [
+            "", "a", "b", "ab", "aa", "aab", "abab", "abc", "abcabc", "ba", "aaaa", "cab", "x1",
+            "xx1", "123", "é",
+        ];
+        for pattern in [
+            "a*",
+            "a+b",
+            "(ab)*",
+            "a{2,3}b?",
+            "(a|b)*c",
+            "((ab)|(c))+",
+            "a{0,2}",
+            "(a*)*b",
+            "[a-c]{2,}",
+            "x+\\d",
+            "(a?b?){2}",
+            "\\pL",
+            ".",
+        ] {
+            let (glushkov, thompson) = both(pattern);
+            for input in inputs {
+                assert_eq!(
+                    glushkov.is_match(input),
+                    thompson.is_match(input),
+                    "{pattern} on {input:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_state_count() {
+        //one state per position, plus the start state
+        let (glushkov, _) = both("((ab)|(cd))*e");
+        assert_eq!(glushkov.state_count(), 6);
+        assert!(glushkov
+            .states()
+            .iter()
+            .flat_map(|state| &state.transitions)
+            .all(|transition| !transition.condition.is_epsilon()));
+    }
+
+    #[test]
+    fn test_simplifies() {
+        let regex = RegexEntry::parse("abc(d|e)*").unwrap();
+        let mut glushkov = Automaton::from_regex_glushkov(&regex);
+        glushkov.simplify();
+
+        assert_eq!(glushkov.prefix(), "abc");
+        assert!(glushkov.is_match("abcdde"));
+        assert!(!glushkov.is_match("abd"));
+    }
+}
@@ -0,0 +1,151 @@
+//! The automaton `\X` is built as: Unicode's regex for an extended grapheme cluster (UAX #29,
+//! table 1b), spelled out as states, each of whose transitions is taken on a character with some
+//! [`GraphemeBreak`] value. Without the conjunct rule (GB9c), it is
+//!
+//! ```text
+//! \r\n | Control | Prepend* core [Extend ZWJ SpacingMark]*
+//! core = hangul-syllable | RI RI | xpicto-sequence | [^Control CR LF]
+//! hangul-syllable = L* (V+ | LV V* | LVT) T* | L+ | T+
+//! xpicto-sequence = ExtPict (Extend* ZWJ ExtPict)*
+//! ```
+
+use crate::automata::{Automaton, Transition, TransitionCondition};
+use crate::grapheme::GraphemeBreak;
+
+/// The number of states [`Automaton::construct_grapheme_cluster`] creates.
+pub(super) const GRAPHEME_CLUSTER_STATES: u64 = 12;
+
+fn on(values: &[GraphemeBreak], target: usize) -> Transition {
+    Transition::new(target, TransitionCondition::GraphemeBreak(values.to_vec()))
+}
+
+impl Automaton {
+    /// Adds the states matching one grapheme cluster and then continuing at `target`, returning
+    /// the first of them.
+    pub(super) fn construct_grapheme_cluster(&mut self, target: usize) -> usize {
+        use GraphemeBreak::*;
+
+        //states that loop are made first and wired up once their targets exist; every loop comes ahead of the way out, so that \X is greedy when transition order is used as priority
+        let after_core = self.construct_state("Grapheme Cluster Extensions", []);
+        let trailing = self.construct_state("Grapheme Cluster Hangul T", []);
+        let vowels = self.construct_state("Grapheme Cluster Hangul V", []);
+        let leading = self.construct_state("Grapheme Cluster Hangul L", []);
+        let pictograph = self.construct_state("Grapheme Cluster Pictograph", []);
+        let extensions = self.construct_state("Grapheme Cluster Pictograph Extensions", []);
+        let prepended = self.construct_state("Grapheme Cluster Prepend", []);
+
+        //whatever extends the cluster once its core has been read
+        self.states[after_core].transitions = vec![
+            on(&[Extend, Zwj, SpacingMark], after_core),
+            Transition::new(target, TransitionCondition::Epsilon),
+        ];
+
+        //a Hangul syllable, as jamo or precomposed syllables, in its three parts
+        self.states[trailing].transitions = vec![
+            on(&[T], trailing),
+            Transition::new(after_core, TransitionCondition::Epsilon),
+        ];
+        self.states[vowels].transitions = vec![
+            on(&[V], vowels),
+            on(&[T], trailing),
+            Transition::new(after_core, TransitionCondition::Epsilon),
+        ];
+        self.states[leading].transitions = vec![
+            on(&[L], leading),
+            on(&[V, Lv], vowels),
+            on(&[Lvt], trailing),
+            Transition::new(after_core, TransitionCondition::Epsilon),
+        ];
+
+        //a flag, which is a pair of regional indicators
+        let second_indicator = self.construct_state(
+            "Grapheme Cluster Regional Indicator",
+            [on(&[RegionalIndicator], after_core)],
+        );
+
+        //pictographs joined into one emoji by zero-width joiners
+        let joiner = self.construct_state(
+            "Grapheme Cluster Joiner",
+            [on(&[ExtendedPictographic], pictograph)],
+        );
+        self.states[extensions].transitions = vec![on(&[Extend], extensions), on(&[Zwj], joiner)];
+        self.states[pictograph].transitions = vec![
+            on(&[Extend], extensions),
+            on(&[Zwj], joiner),
+            Transition::new(after_core, TransitionCondition::Epsilon),
+        ];
+
+        let core = self.construct_state(
+            "Grapheme Cluster Core",
+            [
+                on(&[L], leading),
+                on(&[V, Lv], vowels),
+                on(&[Lvt, T], trailing),
+                on(&[RegionalIndicator], second_indicator),
+                on(&[ExtendedPictographic], pictograph),
+                Transition::new(
+                    after_core,
+                    TransitionCondition::NegatedGraphemeBreak(vec![Control, Cr, Lf]),
+                ),
+            ],
+        );
+        self.states[prepended].transitions = vec![
+            on(&[Prepend], prepended),
+            Transition::new(core, TransitionCondition::Epsilon),
+        ];
+
+        //controls are clusters of their own, except that \r\n is one
+        let carriage_return = self.construct_state(
+            "Grapheme Cluster CR",
+            [
+                on(&[Lf], target),
+                Transition::new(target, TransitionCondition::Epsilon),
+            ],
+        );
+        self.construct_state(
+            "Grapheme Cluster",
+            [
+                on(&[Cr], carriage_return),
+                on(&[Lf, Control], target),
+                Transition::new(prepended, TransitionCondition::Epsilon),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::automata::Automaton;
+    use crate::parser::RegexEntry;
+
+    #[test]
+    fn test_grapheme_clusters() {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse(r"\X").unwrap());
+        automaton.simplify();
+
+        for cluster in [
+            "a",
+            "e\u{301}",
+            "\r\n",
+            "\n",
+            "\u{1100}\u{1161}\u{11A8}",
+            "\u{AC00}\u{11A8}",
+            "\u{1F1FA}\u{1F1F8}",
+            "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}",
+            "\u{1F44D}\u{1F3FD}",
+            "\u{600}\u{661}",
+        ] {
+            assert!(automaton.is_match(cluster), "{cluster:?}");
+        }
+        for not_cluster in [
+            "",
+            "ab",
+            "\n\r",
+            "\r\n\u{301}",
+            "\u{1F1FA}\u{1F1F8}\u{1F1EC}",
+            "\u{AC01}\u{1161}",
+        ] {
+            assert!(!automaton.is_match(not_cluster), "{not_cluster:?}");
+        }
+    }
+}
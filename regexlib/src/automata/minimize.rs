@@ -0,0 +1,352 @@
+//! Merging the states that no input can tell apart, by partition refinement. The automaton is not
+//! determinized first, so two states are merged when they are equally terminal and each transition
+//! of one is matched by a transition of the other on the same condition into the same group of
+//! states (i.e., they are bisimilar). On a deterministic automaton this is the minimal automaton
+//! that Moore's and Hopcroft's algorithms find; like Hopcroft's, it only revisits the states that
+//! lead into a group that split, rather than every state each round.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::automata::{Automaton, State, Transition, TransitionCondition};
+
+//the pairs of a transition's condition (see `condition_ids`) and the group of states it leads into
+type Signature = Vec<(usize, usize)>;
+
+//the groups the states are divided into while they are being told apart
+struct Partition {
+    groups: Vec<usize>,
+    //the states of each group, along with states that have since left it
+    members: Vec<Vec<usize>>,
+    sizes: Vec<usize>,
+    //the transitions every state of each group has, once it is known
+    signatures: Vec<Option<Signature>>,
+}
+
+impl Partition {
+    //each terminal state starts out in a group of its own, so that the terminal states of a union stay in correspondence with its regexes
+    fn new(state_count: usize, terminal_states: &[usize]) -> Self {
+        let mut groups = vec![0; state_count];
+        for (index, terminal_state) in terminal_states.iter().enumerate() {
+            groups[*terminal_state] = index + 1;
+        }
+
+        let group_count = terminal_states.len() + 1;
+        let mut members = vec![Vec::new(); group_count];
+        for (state_id, group) in groups.iter().enumerate() {
+            members[*group].push(state_id);
+        }
+
+        Self {
+            sizes: members.iter().map(Vec::len).collect(),
+            groups,
+            members,
+            signatures: vec![None; group_count],
+        }
+    }
+
+    //moves the states into a new group
+    fn split_off(&mut self, group: usize, states: Vec<usize>, signature: Option<Signature>) {
+        let new_group = self.sizes.len();
+        self.sizes[group] -= states.len();
+        self.sizes.push(states.len());
+        self.signatures.push(signature);
+        for state_id in &states {
+            self.groups[*state_id] = new_group;
+        }
+        self.members.push(states);
+
+        //the states that left are dropped once they are most of the list
+        if self.members[group].len() > 2 * self.sizes[group] {
+            let groups = &self.groups;
+            self.members[group].retain(|state_id| groups[*state_id] == group);
+        }
+    }
+}
+
+impl Automaton {
+    pub(super) fn merge_equivalent_states(&mut self) {
+        let conditions = self.condition_ids();
+        let predecessors = self.predecessors();
+        let mut partition = Partition::new(self.states.len(), &self.terminal_states);
+        let mut is_pending = vec![false; self.states.len()];
+
+        //only a state leading into a state that changed groups can have to leave its own group, so each round looks at just those; the largest part of a group that splits keeps its id, so that as few states as possible change groups
+        let mut pending = (0..self.states.len()).collect::<Vec<_>>();
+        while !pending.is_empty() {
+            let mut pending_by_group = BTreeMap::<usize, Vec<usize>>::new();
+            for state_id in &pending {
+                is_pending[*state_id] = true;
+                pending_by_group
+                    .entry(partition.groups[*state_id])
+                    .or_default()
+                    .push(*state_id);
+            }
+
+            let mut changed = Vec::new();
+            for (group, state_ids) in pending_by_group {
+                let mut parts: Vec<(Signature, Vec<usize>)> = Vec::new();
+                let mut part_indices = HashMap::new();
+                for state_id in &state_ids {
+                    let signature =
+                        self.signature(*state_id, &conditions[*state_id], &partition.groups);
+                    let index = *part_indices.entry(signature.clone()).or_insert_with(|| {
+                        parts.push((signature, Vec::new()));
+                        parts.len() - 1
+                    });
+                    parts[index].1.push(*state_id);
+                }
+
+                //the states that were not looked at still have the group's signature, so they go together with the part that has it
+                let unchanged = partition.sizes[group] - state_ids.len();
+                let unchanged_part = parts.iter().position(|(signature, _)| {
+                    unchanged > 0 && Some(signature) == partition.signatures[group].as_ref()
+                });
+                let unchanged_size =
+                    unchanged + unchanged_part.map_or(0, |index| parts[index].1.len());
+                let largest = (0..parts.len())
+                    .rev()
+                    .filter(|index| Some(*index) != unchanged_part)
+                    .max_by_key(|index| parts[*index].1.len());
+
+                let stays = match largest {
+                    Some(largest) if unchanged == 0 => {
+                        partition.signatures[group] = Some(parts[largest].0.clone());
+                        Some(largest)
+                    }
+                    Some(largest) if parts[largest].1.len() > unchanged_size => {
+                        let mut moved = partition.members[group]
+                            .iter()
+                            .copied()
+                            .filter(|state_id| {
+                                partition.groups[*state_id] == group && !is_pending[*state_id]
+                            })
+                            .collect::<Vec<_>>();
+                        if let Some(unchanged_part) = unchanged_part {
+                            moved.append(&mut parts[unchanged_part].1);
+                        }
+                        changed.extend_from_slice(&moved);
+                        let signature = partition.signatures[group].take();
+                        partition.split_off(group, moved, signature);
+
+                        partition.signatures[group] = Some(parts[largest].0.clone());
+                        partition.members[group] = parts[largest].1.clone();
+                        Some(largest)
+                    }
+                    _ => unchanged_part,
+                };
+
+                for (index, (signature, part)) in parts.into_iter().enumerate() {
+                    if Some(index) == stays || part.is_empty() {
+                        continue;
+                    }
+
+                    changed.extend_from_slice(&part);
+                    partition.split_off(group, part, Some(signature));
+                }
+            }
+
+            for state_id in pending {
+                is_pending[state_id] = false;
+            }
+            pending = changed
+                .iter()
+                .flat_map(|state_id| &predecessors[*state_id])
+                .copied()
+                .collect();
+            pending.sort_unstable();
+            pending.dedup();
+        }
+
+        if partition.sizes.iter().filter(|size| **size > 0).count() == self.states.len() {
+            return;
+        }
+
+        self.merge_groups(&partition.groups);
+    }
+
+    //the conditions of the state's transitions paired with the groups they lead into, in a canonical order
+    fn signature(&self, state_id: usize, conditions: &[usize], groups: &[usize]) -> Signature {
+        let mut signature = self.states[state_id]
+            .transitions
+            .iter()
+            .zip(conditions)
+            .map(|(transition, condition)| (*condition, groups[transition.next_state_id]))
+            .collect::<Vec<_>>();
+        signature.sort_unstable();
+        signature.dedup();
+        signature
+    }
+
+    fn predecessors(&self) -> Vec<Vec<usize>> {
+        let mut predecessors = vec![Vec::new(); self.states.len()];
+        for state in &self.states {
+            for transition in &state.transitions {
+                predecessors[transition.next_state_id].push(state.id);
+            }
+        }
+        for state_predecessors in &mut predecessors {
+            state_predecessors.sort_unstable();
+            state_predecessors.dedup();
+        }
+        predecessors
+    }
+
+    //the index of each transition's condition among the distinct conditions, since conditions can be compared but not ordered
+    fn condition_ids(&self) -> Vec<Vec<usize>> {
+        let mut distinct: Vec<&TransitionCondition> = Vec::new();
+
+        self.states
+            .iter()
+            .map(|state| {
+                state
+                    .transitions
+                    .iter()
+                    .map(|transition| {
+                        match distinct
+                            .iter()
+                            .position(|condition| **condition == transition.condition)
+                        {
+                            Some(id) => id,
+                            None => {
+                                distinct.push(&transition.condition);
+                                distinct.len() - 1
+                            }
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    //replaces the states of each group with the first of them, numbering the groups in the order of their first states
+    fn merge_groups(&mut self, groups: &[usize]) {
+        let mut new_ids = BTreeMap::new();
+        let mut new_states = Vec::new();
+        for state in &self.states {
+            new_ids.entry(groups[state.id]).or_insert_with(|| {
+                new_states.push(State {
+                    id: new_states.len(),
+                    ..state.clone()
+                });
+                new_states.len() - 1
+            });
+        }
+
+        let new_id = |state_id: usize| new_ids[&groups[state_id]];
+        for state in &mut new_states {
+            let mut transitions = Vec::new();
+            for transition in &state.transitions {
+                let transition = Transition::new(
+                    new_id(transition.next_state_id),
+                    transition.condition.clone(),
+                );
+                if !transitions.contains(&transition) {
+                    transitions.push(transition);
+                }
+            }
+            state.transitions = transitions;
+        }
+
+        let mut start_states = Vec::new();
+        for start_state in &self.start_states {
+            if !start_states.contains(&new_id(*start_state)) {
+                start_states.push(new_id(*start_state));
+            }
+        }
+
+        self.terminal_states = self
+            .terminal_states
+            .iter()
+            .map(|state| new_id(*state))
+            .collect();
+        self.start_states = start_states;
+        self.states = new_states;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::automata::{Automaton, SimplificationPass};
+    use crate::parser::RegexEntry;
+
+    fn simplified_without_merging(pattern: &str) -> Automaton {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse(pattern).unwrap());
+        for pass in SimplificationPass::ALL {
+            if pass != SimplificationPass::MergeEquivalentStates {
+                automaton.run_pass(pass);
+            }
+        }
+        automaton
+    }
+
+    #[test]
+    fn test_merges_equivalent_branches() {
+        //both branches end in the same way, so their states after the first character are interchangeable
+        let mut automaton = simplified_without_merging("x((ab)|(cb))");
+        let before = automaton.state_count();
+        automaton.run_pass(SimplificationPass::MergeEquivalentStates);
+
+        assert!(automaton.state_count() < before);
+        assert!(automaton.is_match("xab"));
+        assert!(automaton.is_match("xcb"));
+        assert!(!automaton.is_match("xac"));
+        assert!(!automaton.is_match("xb"));
+    }
+
+    #[test]
+    fn test_preserves_language() {
+        for (pattern, inputs) in [
+            ("a*b+c?", &["", "b", "abbc", "aac", "bcc", "aabb"][..]),
+            ("(x|y)*(xy)+", &["xy", "yxxy", "xyxy", "x", "yx", ""]),
+            ("[a-c]{2,3}d*", &["ab", "abc", "abcd", "a", "abca", "cddd"]),
+        ] {
+            let unmerged = simplified_without_merging(pattern);
+            let mut merged = unmerged.clone();
+            merged.run_pass(SimplificationPass::MergeEquivalentStates);
+
+            assert!(merged.state_count() <= unmerged.state_count());
+            for input in inputs {
+                assert_eq!(
+                    merged.is_match(input),
+                    unmerged.is_match(input),
+                    "{:?} on {:?}",
+                    pattern,
+                    input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimal_state_count() {
+        let simplified = |pattern: &str| {
+            let mut automaton = Automaton::from_regex(RegexEntry::parse(pattern).unwrap());
+            automaton.simplify();
+            automaton.state_count()
+        };
+
+        //the branches share their ends, as if the pattern were written with them factored out
+        assert_eq!(simplified("x((ab)|(cb))"), simplified("x((a)|(c))b"));
+        assert_eq!(simplified("((ab)|(cb))*"), simplified("(((a)|(c))b)*"));
+        assert_eq!(
+            simplified("(a[0-9]{10})|(b[0-9]{10})"),
+            simplified("((a)|(b))[0-9]{10}")
+        );
+        //long runs of states that differ only in how far they are from the end, which are told apart one state per round
+        assert_eq!(simplified("[a-z]{10}[0-9]{10}[a-f]{10}"), 31);
+    }
+
+    #[test]
+    fn test_keeps_terminal_states_of_union() {
+        //the two regexes are the same, but which of them matched must still be told apart
+        let mut automaton = Automaton::from_regexes([
+            RegexEntry::parse("a[0-9]").unwrap(),
+            RegexEntry::parse("a[0-9]").unwrap(),
+        ]);
+        automaton.simplify();
+
+        let terminal_states = automaton.terminal_state_ids();
+        assert_eq!(terminal_states.len(), 2);
+        assert_ne!(terminal_states[0], terminal_states[1]);
+    }
+}
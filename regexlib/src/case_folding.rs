@@ -0,0 +1,438 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use crate::parser::character_class::CharacterClass;
+use crate::parser::RegexEntry;
+
+mod tables;
+
+//the characters that are the same letter in different cases, grouped by what they fold to
+struct CaseGroups {
+    groups: Vec<Vec<char>>,
+    group_of: BTreeMap<char, usize>,
+}
+
+fn case_groups() -> &'static CaseGroups {
+    static CASE_GROUPS: OnceLock<CaseGroups> = OnceLock::new();
+    CASE_GROUPS.get_or_init(|| {
+        let groups = tables::CASE_GROUPS
+            .iter()
+            .map(|group| group.to_vec())
+            .collect::<Vec<_>>();
+
+        let mut group_of = BTreeMap::new();
+        for (index, group) in groups.iter().enumerate() {
+            for char in group {
+                group_of.insert(*char, index);
+            }
+        }
+        CaseGroups { groups, group_of }
+    })
+}
+
+/// The characters that match `char` when case is ignored, including `char` itself, in codepoint
+/// order. These are the characters with the same simple case folding, so `k` matches `K` and the
+/// Kelvin sign `K`, but `ß` does not match `SS`, which folding to more than one character would
+/// need.
+pub fn case_variants(char: char) -> Vec<char> {
+    let groups = case_groups();
+    match groups.group_of.get(&char) {
+        Some(index) => groups.groups[*index].clone(),
+        None => vec![char],
+    }
+}
+
+/// The class of a literal `char` when case is ignored, or `None` if it has no other case.
+pub fn case_insensitive_literal(char: char) -> Option<CharacterClass> {
+    let variants = case_variants(char);
+    if variants.len() == 1 {
+        return None;
+    }
+
+    //the literal itself comes first, so that the class reads as the literal and its other cases
+    let others = variants.into_iter().filter(|variant| *variant != char);
+    Some(CharacterClass::Disjunction(
+        std::iter::once(char)
+            .chain(others)
+            .map(CharacterClass::Char)
+            .collect(),
+    ))
+}
+
+/// Which characters are taken to be the same letter in a different case when case is ignored, as
+/// by the `i` flag or [`RegexEntry::make_case_insensitive_with`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum CaseFolding {
+    /// Unicode's simple case folding, which only relates single characters (see
+    /// [`case_variants`]): `ß` matches `ẞ`, but not `ss`.
+    #[default]
+    Simple,
+    /// Unicode's full case folding, under which some characters fold to several (e.g., `ß` to
+    /// `ss`), so that a run of literals matches any text with the same folding: `straße` matches
+    /// `STRASSE` and `strasse` matches `straße`. Runs of literals are read as
+    /// [`RegexEntry::FoldedLiteral`]s. A bracketed class still matches one character at a time,
+    /// so it is folded the simple way.
+    Full,
+}
+
+//the simple folding of `char`, as the first character of its case group, which is all that comparing foldings needs
+fn folding_key(char: char) -> char {
+    let groups = case_groups();
+    match groups.group_of.get(&char) {
+        Some(index) => groups.groups[*index][0],
+        None => char,
+    }
+}
+
+//the full folding of `char`, as the keys of the characters it folds to
+fn full_folding_keys(char: char) -> Vec<char> {
+    match tables::FULL_FOLDINGS.binary_search_by_key(&char, |(folded, _)| *folded) {
+        Ok(index) => tables::FULL_FOLDINGS[index]
+            .1
+            .chars()
+            .map(folding_key)
+            .collect(),
+        Err(_) => vec![folding_key(char)],
+    }
+}
+
+//one way of reading part of a folded literal: any of `chars` matches the characters of its full folding from `start` up to `end`, which are several for a character that folds to several
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct FoldStep {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) chars: Vec<char>,
+}
+
+//the length of the full folding of `literal`, and the steps that read it, ordered by where they start; text matches the literal when steps read it from the start of the folding to its end, each starting where the one before ended
+pub(crate) fn fold_steps(literal: &str) -> (usize, Vec<FoldStep>) {
+    let folding = literal
+        .chars()
+        .flat_map(full_folding_keys)
+        .collect::<Vec<_>>();
+
+    let mut steps = Vec::new();
+    for start in 0..folding.len() {
+        //those that fold to one character are its case group, less the members that fold to more
+        let chars = case_variants(folding[start])
+            .into_iter()
+            .filter(|char| full_folding_keys(*char).len() == 1)
+            .collect::<Vec<_>>();
+        if !chars.is_empty() {
+            steps.push(FoldStep {
+                start,
+                end: start + 1,
+                chars,
+            });
+        }
+
+        for (char, _) in tables::FULL_FOLDINGS {
+            let keys = full_folding_keys(*char);
+            if !folding[start..].starts_with(&keys) {
+                continue;
+            }
+            let end = start + keys.len();
+            match steps
+                .iter_mut()
+                .find(|step| step.start == start && step.end == end)
+            {
+                Some(step) => step.chars.push(*char),
+                None => steps.push(FoldStep {
+                    start,
+                    end,
+                    chars: vec![*char],
+                }),
+            }
+        }
+    }
+
+    (folding.len(), steps)
+}
+
+/// An entry made of single characters and classes that matches what
+/// [`RegexEntry::FoldedLiteral`]`(literal)` does: each stretch of the folding that a character
+/// folding to several can be read across is an alternation of the ways to read it, like
+/// `(?:[Ssſ][Ssſ]|[ßẞ])` for `ß`. Stretches that can be read in many overlapping ways, like a
+/// long run of `s`, have exponentially many.
+pub fn expand_folded_literal(literal: &str) -> RegexEntry {
+    let (length, steps) = fold_steps(literal);
+
+    //no step spans the boundaries between stretches, which can so be read one after the other
+    let mut stretches = Vec::new();
+    let mut stretch_start = 0;
+    for boundary in 1..=length {
+        if steps
+            .iter()
+            .all(|step| step.end <= boundary || step.start >= boundary)
+        {
+            stretches.push(expand_stretch(&steps, stretch_start, boundary));
+            stretch_start = boundary;
+        }
+    }
+
+    match stretches.len() {
+        1 => stretches.pop().unwrap(),
+        _ => RegexEntry::Concatenation(stretches),
+    }
+}
+
+//every way of reading the folding from `start` up to `end`
+fn expand_stretch(steps: &[FoldStep], start: usize, end: usize) -> RegexEntry {
+    let mut branches = steps
+        .iter()
+        .filter(|step| step.start == start && step.end <= end)
+        .map(|step| {
+            let read = match step.chars[..] {
+                [char] => RegexEntry::Literal(char),
+                _ => RegexEntry::NonUnicodeCharacterClass(CharacterClass::Disjunction(
+                    step.chars
+                        .iter()
+                        .copied()
+                        .map(CharacterClass::Char)
+                        .collect(),
+                )),
+            };
+            if step.end == end {
+                return read;
+            }
+            match expand_stretch(steps, step.end, end) {
+                RegexEntry::Concatenation(mut rest) => {
+                    rest.insert(0, read);
+                    RegexEntry::Concatenation(rest)
+                }
+                rest => RegexEntry::Concatenation(vec![read, rest]),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    match branches.len() {
+        1 => branches.pop().unwrap(),
+        _ => RegexEntry::Alternation(branches),
+    }
+}
+
+//a run of literals read with full case folding, which is left as a literal or class per character, as simple folding would make it, if no step reads more than one character of it
+pub(crate) fn fold_literal_run(literal: String) -> Vec<RegexEntry> {
+    let (_, steps) = fold_steps(&literal);
+    if steps.iter().any(|step| step.end != step.start + 1) {
+        return vec![RegexEntry::FoldedLiteral(literal)];
+    }
+
+    //each character then has one step, which is its case group less any members that fold to more
+    steps
+        .into_iter()
+        .zip(literal.chars())
+        .map(|(step, char)| match step.chars[..] {
+            [_] => RegexEntry::Literal(char),
+            _ => RegexEntry::NonUnicodeCharacterClass(CharacterClass::Disjunction(
+                std::iter::once(char)
+                    .chain(step.chars.into_iter().filter(|variant| *variant != char))
+                    .map(CharacterClass::Char)
+                    .collect(),
+            )),
+        })
+        .collect()
+}
+
+impl CharacterClass {
+    /// The class matching every character that matches this one when case is ignored. A negated
+    /// class is folded before it is negated, so `[^k]` matches neither `k` nor `K`, and the sides
+    /// of an intersection or difference are folded before they are combined.
+    pub fn case_insensitive(&self) -> CharacterClass {
+        match self {
+            CharacterClass::Negated(class) => {
+                CharacterClass::Negated(Box::new(class.case_insensitive()))
+            }
+            CharacterClass::Intersection(left, right) => CharacterClass::Intersection(
+                Box::new(left.case_insensitive()),
+                Box::new(right.case_insensitive()),
+            ),
+            CharacterClass::Difference(left, right) => CharacterClass::Difference(
+                Box::new(left.case_insensitive()),
+                Box::new(right.case_insensitive()),
+            ),
+            class => class.with_case_variants(),
+        }
+    }
+
+    fn with_case_variants(&self) -> CharacterClass {
+        let mut members = match self {
+            CharacterClass::Disjunction(members) => members.clone(),
+            class => vec![class.clone()],
+        };
+        for group in &case_groups().groups {
+            if group.iter().any(|char| self.matches(*char)) {
+                members.extend(
+                    group
+                        .iter()
+                        .filter(|char| !self.matches(**char))
+                        .map(|char| CharacterClass::Char(*char)),
+                );
+            }
+        }
+
+        match members.len() {
+            1 => members.pop().unwrap(),
+            _ => CharacterClass::Disjunction(members),
+        }
+    }
+}
+
+impl RegexEntry {
+    /// Makes the literals and bracketed classes of the regex match regardless of case, as if it
+    /// were written with the `(?i)` flag. Unicode classes like `\p{Lu}` name a property of the
+    /// character rather than a set of letters, so they are left as they are.
+    ///
+    /// This is simple case folding, so `ß` does not match `ss`; see
+    /// [`make_case_insensitive_with`](Self::make_case_insensitive_with) for full folding.
+    pub fn make_case_insensitive(&mut self) {
+        self.make_case_insensitive_with(CaseFolding::Simple)
+    }
+
+    /// Like [`make_case_insensitive`](Self::make_case_insensitive), but with the given kind of
+    /// case folding. With [`CaseFolding::Full`], each run of literals becomes a
+    /// [`FoldedLiteral`](RegexEntry::FoldedLiteral), so `(?:ab)+ß` matches `ABabSS`.
+    pub fn make_case_insensitive_with(&mut self, folding: CaseFolding) {
+        match self {
+            RegexEntry::Literal(literal) => match folding {
+                CaseFolding::Simple => {
+                    if let Some(class) = case_insensitive_literal(*literal) {
+                        *self = RegexEntry::NonUnicodeCharacterClass(class);
+                    }
+                }
+                //a single character is folded into a single entry
+                CaseFolding::Full => *self = fold_literal_run(literal.to_string()).remove(0),
+            },
+            RegexEntry::NonUnicodeCharacterClass(class) | RegexEntry::Byte(class) => {
+                *class = class.case_insensitive()
+            }
+            RegexEntry::Concatenation(entries) if folding == CaseFolding::Full => {
+                let mut folded = Vec::new();
+                let mut literal_run = String::new();
+                for mut entry in entries.drain(..) {
+                    match &entry {
+                        RegexEntry::Literal(literal) => {
+                            literal_run.push(*literal);
+                            continue;
+                        }
+                        RegexEntry::FoldedLiteral(literal) => {
+                            literal_run.push_str(literal);
+                            continue;
+                        }
+                        _ => {}
+                    }
+
+                    if !literal_run.is_empty() {
+                        folded.append(&mut fold_literal_run(std::mem::take(&mut literal_run)));
+                    }
+                    entry.make_case_insensitive_with(folding);
+                    folded.push(entry);
+                }
+                if !literal_run.is_empty() {
+                    folded.append(&mut fold_literal_run(literal_run));
+                }
+
+                *entries = folded;
+            }
+            RegexEntry::Concatenation(entries) | RegexEntry::Alternation(entries) => {
+                for entry in entries {
+                    entry.make_case_insensitive_with(folding);
+                }
+            }
+            RegexEntry::Repetition { base, .. } | RegexEntry::Capture { base, .. } => {
+                base.make_case_insensitive_with(folding)
+            }
+            RegexEntry::AnyCharacter
+            | RegexEntry::UnicodeCharacterClass(_)
+            | RegexEntry::NegatedUnicodeCharacterClass(_)
+            | RegexEntry::UnicodeScript(_)
+            | RegexEntry::NegatedUnicodeScript(_)
+            | RegexEntry::GraphemeCluster
+            | RegexEntry::FoldedLiteral(_)
+            | RegexEntry::StartOfInput
+            | RegexEntry::EndOfInput
+            | RegexEntry::StartOfLine(_)
+            | RegexEntry::EndOfLine(_)
+            | RegexEntry::WordStart
+            | RegexEntry::WordEnd => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::automata::Automaton;
+    use crate::case_folding::{case_variants, expand_folded_literal, CaseFolding};
+    use crate::parser::character_class::CharacterClass;
+    use crate::parser::RegexEntry;
+
+    #[test]
+    fn test_case_variants() {
+        assert_eq!(case_variants('a'), vec!['A', 'a']);
+        assert_eq!(case_variants('k'), vec!['K', 'k', '\u{212A}']);
+        assert_eq!(
+            case_variants('\u{3C3}'),
+            vec!['\u{3A3}', '\u{3C2}', '\u{3C3}']
+        );
+        assert_eq!(case_variants('1'), vec!['1']);
+        assert_eq!(case_variants('\u{DF}'), vec!['\u{DF}', '\u{1E9E}']);
+    }
+
+    #[test]
+    fn test_case_insensitive_class() {
+        let class = CharacterClass::Range {
+            start: 'a',
+            end: 'c',
+        }
+        .case_insensitive();
+        for char in ['a', 'B', 'c'] {
+            assert!(class.matches(char));
+        }
+        assert!(!class.matches('d'));
+
+        let negated = CharacterClass::Negated(Box::new(CharacterClass::Char('k')));
+        let negated = negated.case_insensitive();
+        assert!(!negated.matches('K'));
+        assert!(!negated.matches('\u{212A}'));
+        assert!(negated.matches('x'));
+
+        let without_k = CharacterClass::Difference(
+            Box::new(CharacterClass::Range {
+                start: 'a',
+                end: 'z',
+            }),
+            Box::new(CharacterClass::Char('k')),
+        );
+        let without_k = without_k.case_insensitive();
+        assert!(without_k.matches('B'));
+        assert!(!without_k.matches('K'));
+        assert!(!without_k.matches('\u{212A}'));
+    }
+
+    #[test]
+    fn test_make_case_insensitive() {
+        let mut regex = RegexEntry::parse("(ab)+[x-z]1").unwrap();
+        regex.make_case_insensitive();
+        assert_eq!(regex.to_string(), "([aA][bB])+[x-zXYZ]1");
+    }
+
+    #[test]
+    fn test_full_folding() {
+        assert_eq!(
+            expand_folded_literal("\u{DF}").to_string(),
+            "[Ss\u{17F}][Ss\u{17F}]|[\u{DF}\u{1E9E}]"
+        );
+
+        let mut regex = RegexEntry::parse("stra\u{DF}e|x").unwrap();
+        regex.make_case_insensitive_with(CaseFolding::Full);
+        for automaton in [
+            Automaton::from_regex_glushkov(&regex),
+            Automaton::from_regex(regex),
+        ] {
+            for input in ["Stra\u{DF}e", "STRASSE", "strasse", "STRA\u{1E9E}E", "X"] {
+                assert!(automaton.is_match(input), "{input}");
+            }
+            assert!(!automaton.is_match("strase"));
+        }
+    }
+}
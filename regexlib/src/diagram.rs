@@ -0,0 +1,152 @@
+use crate::automata::{Automaton, TransitionCondition};
+use crate::parser::character_class::CharacterClass;
+
+/// Renders `automaton` in graphviz's dot language. States built with spans (see
+/// [`Automaton::from_regex_with_spans`]) are labeled with the part of `pattern` they came from.
+pub fn to_graphviz(automaton: &Automaton, pattern: &str) -> String {
+    let mut graphviz = String::new();
+
+    graphviz.push_str("digraph NFA{\n");
+    //graphviz.push_str("\trankdir=LR;\n");
+    for state_id in 0..automaton.state_count() {
+        emit_state(automaton, pattern, state_id, &mut graphviz);
+    }
+
+    graphviz.push_str("\tstart [shape=plaintext];\n");
+    for start_state in automaton.start_states() {
+        graphviz.push_str(&format!("\tstart->state{};\n", *start_state));
+    }
+
+    graphviz.push('}');
+
+    graphviz
+}
+
+fn emit_state(automaton: &Automaton, pattern: &str, state_id: usize, graphviz: &mut String) {
+    let state = automaton.get_state(state_id);
+
+    //label with the part of the pattern the state came from, where known
+    let label = match state.span.as_ref().and_then(|span| pattern.get(span.clone())) {
+        Some(fragment) => format!("{}: {}", state_id, escape_label(fragment)),
+        None => state_id.to_string(),
+    };
+
+    //debug name
+    graphviz.push_str(&format!(
+        "\tstate{} [label=\"{}\",shape={}];\n",
+        state_id,
+        label,
+        if automaton.is_terminal_state(state_id) {
+            "doublecircle"
+        } else {
+            "oval"
+        }
+    ));
+
+    //transitions
+    for transition in &state.transitions {
+        graphviz.push_str(&format!(
+            "\tstate{} -> state{} [label=\"{}\"];\n",
+            state_id,
+            transition.next_state_id,
+            escape_label(&transition_to_string(&transition.condition))
+        ));
+    }
+}
+
+fn transition_to_string(transition_condition: &TransitionCondition) -> String {
+    match transition_condition {
+        TransitionCondition::Epsilon => "ε".into(),
+        TransitionCondition::CaptureStart(index) => format!("ε ({index}"),
+        TransitionCondition::CaptureEnd(index) => format!("ε {index})"),
+        TransitionCondition::StartOfInput => "ε ^".into(),
+        TransitionCondition::EndOfInput => "ε $".into(),
+        TransitionCondition::StartOfLine(_) => "ε (?m:^)".into(),
+        TransitionCondition::EndOfLine(_) => "ε (?m:$)".into(),
+        TransitionCondition::WordStart => "ε \\b{start}".into(),
+        TransitionCondition::WordEnd => "ε \\b{end}".into(),
+        TransitionCondition::CharacterClass(class) => {
+            format!("[{}]", character_class_to_string(class))
+        }
+        TransitionCondition::Literal(ch) => format!("'{}'", ch),
+        TransitionCondition::AnyCharacter => "*".into(),
+        TransitionCondition::UnicodeCharacterClass(categories) => categories
+            .iter()
+            .map(|category| format!("{:?}", category))
+            .collect::<Vec<_>>()
+            .join(", "),
+        TransitionCondition::NegatedUnicodeClass(categories) => format!(
+            "!{}",
+            categories
+                .iter()
+                .map(|category| format!("{:?}", category))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        TransitionCondition::UnicodeScript(script) => script.name().into(),
+        TransitionCondition::NegatedUnicodeScript(script) => format!("!{}", script.name()),
+        TransitionCondition::GraphemeBreak(values) => values
+            .iter()
+            .map(|value| value.name())
+            .collect::<Vec<_>>()
+            .join(", "),
+        TransitionCondition::NegatedGraphemeBreak(values) => format!(
+            "!{}",
+            values
+                .iter()
+                .map(|value| value.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn character_class_to_string(character_class: &CharacterClass) -> String {
+    match character_class {
+        CharacterClass::Negated(class) => format!("^{}", character_class_to_string(class.as_ref())),
+        CharacterClass::Char(ch) => format!("{}", *ch),
+        CharacterClass::Range { start, end } => format!("{}-{}", *start, *end),
+        CharacterClass::Disjunction(classes) => classes
+            .iter()
+            .map(character_class_to_string)
+            .collect(),
+        CharacterClass::Intersection(left, right) => format!(
+            "{}&&[{}]",
+            character_class_to_string(left),
+            character_class_to_string(right)
+        ),
+        CharacterClass::Difference(left, right) => format!(
+            "{}--[{}]",
+            character_class_to_string(left),
+            character_class_to_string(right)
+        ),
+    }
+}
+
+fn escape_label(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::automata::Automaton;
+    use crate::parser::RegexEntry;
+
+    use super::to_graphviz;
+
+    #[test]
+    fn test_to_graphviz() {
+        let (regex, spans) = RegexEntry::parse_with_spans("a[\"b]").unwrap();
+        let automaton = Automaton::from_regex_with_spans(regex, &spans);
+        let graphviz = to_graphviz(&automaton, "a[\"b]");
+
+        assert!(graphviz.starts_with("digraph NFA{\n"));
+        assert!(graphviz.ends_with('}'));
+        assert!(graphviz.contains("[label=\"'a'\"]"));
+        assert!(graphviz.contains("[label=\"[\\\"b]\"]"));
+        assert!(graphviz.contains("shape=doublecircle"));
+        for start_state in automaton.start_states() {
+            assert!(graphviz.contains(&format!("\tstart->state{};\n", start_state)));
+        }
+    }
+}
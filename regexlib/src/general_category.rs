@@ -0,0 +1,50 @@
+use unic_ucd_category::GeneralCategory;
+
+mod tables;
+
+/// The general category of `char` (e.g., `UppercaseLetter` for `A`), which is what `\pL`, `\d`
+/// and `\w` match against, from the tables of [`UNICODE_VERSION`](crate::UNICODE_VERSION).
+/// `GeneralCategory::of` answers from the older tables of the crate that defines the enum, so
+/// use this instead wherever the answer has to agree with the rest of regexlib.
+pub fn of(char: char) -> GeneralCategory {
+    let found = tables::CATEGORIES.binary_search_by(|(start, end, _)| {
+        if *end < char {
+            std::cmp::Ordering::Less
+        } else if *start > char {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    });
+    match found {
+        Ok(index) => tables::CATEGORIES[index].2,
+        Err(_) => GeneralCategory::Unassigned,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use unic_ucd_category::GeneralCategory;
+
+    use crate::general_category;
+
+    #[test]
+    fn test_of() {
+        assert_eq!(general_category::of('A'), GeneralCategory::UppercaseLetter);
+        assert_eq!(general_category::of('1'), GeneralCategory::DecimalNumber);
+        assert_eq!(general_category::of('\u{378}'), GeneralCategory::Unassigned);
+        assert_eq!(
+            general_category::of('\u{E000}'),
+            GeneralCategory::PrivateUse
+        );
+        //added after Unicode 10.0: a Kawi letter (15.0) and a Garay digit (16.0)
+        assert_eq!(
+            general_category::of('\u{11F04}'),
+            GeneralCategory::OtherLetter
+        );
+        assert_eq!(
+            general_category::of('\u{10D40}'),
+            GeneralCategory::DecimalNumber
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::parser::character_class::CharacterClass;
+use crate::parser::RegexEntry;
+
+/// Converts a shell glob into the regex matching the same paths, as a whole:
+///
+/// - `*` matches any run of characters other than `/`, and `**` any run of characters at all
+/// - `?` matches any one character other than `/`
+/// - `[abc]`, `[a-z]` and their negations `[!abc]` (or `[^abc]`) match one character, as in a
+///   regex, except that a `]` right after the opening bracket is a member of the class
+/// - `{tar,zip}` matches any one of its comma-separated alternatives, which are globs themselves
+/// - `\` makes the character after it literal
+///
+/// Every other character matches itself, including `,` and `}` outside of braces.
+pub fn from_glob(glob: &str) -> Result<RegexEntry, String> {
+    parse_sequence(&mut glob.chars().peekable(), false)
+}
+
+//parses up to the end of the glob, or (inside braces) up to the next unescaped `,` or `}`, which is left unread
+fn parse_sequence(chars: &mut Peekable<Chars>, in_braces: bool) -> Result<RegexEntry, String> {
+    let mut entries = Vec::new();
+
+    while let Some(&char) = chars.peek() {
+        if in_braces && (char == ',' || char == '}') {
+            break;
+        }
+        chars.next();
+
+        let entry = match char {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                RegexEntry::Repetition {
+                    base: Box::new(RegexEntry::AnyCharacter),
+                    min: 0,
+                    max: None,
+                    lazy: false,
+                }
+            }
+            '*' => RegexEntry::Repetition {
+                base: Box::new(not_separator()),
+                min: 0,
+                max: None,
+                lazy: false,
+            },
+            '?' => not_separator(),
+            '[' => RegexEntry::NonUnicodeCharacterClass(parse_class(chars)?),
+            '{' => parse_alternatives(chars)?,
+            '\\' => match chars.next() {
+                Some(escaped) => RegexEntry::Literal(escaped),
+                None => return Err("Glob ends with an unfinished \\ escape".into()),
+            },
+            literal => RegexEntry::Literal(literal),
+        };
+        entries.push(entry);
+    }
+
+    Ok(match entries.len() {
+        1 => entries.pop().unwrap(),
+        _ => RegexEntry::Concatenation(entries),
+    })
+}
+
+fn not_separator() -> RegexEntry {
+    RegexEntry::NonUnicodeCharacterClass(CharacterClass::Negated(Box::new(CharacterClass::Char(
+        '/',
+    ))))
+}
+
+//the opening `[` has been read
+fn parse_class(chars: &mut Peekable<Chars>) -> Result<CharacterClass, String> {
+    let negated = chars.next_if(|char| *char == '!' || *char == '^').is_some();
+    let mut members = Vec::new();
+
+    loop {
+        let start = match chars.next() {
+            Some(']') if !members.is_empty() => break,
+            Some('\\') => chars.next(),
+            start => start,
+        }
+        .ok_or("Glob ends inside of a character class")?;
+
+        //a `-` right before the closing bracket is a member, not a range
+        let mut lookahead = chars.clone();
+        let member = match (lookahead.next(), lookahead.next()) {
+            (Some('-'), Some(end)) if end != ']' => {
+                chars.next();
+                chars.next();
+                if start > end {
+                    return Err(format!(
+                        "Character class range {start}-{end} in glob is inverted, so it can never match; did you mean {end}-{start}?"
+                    ));
+                }
+                CharacterClass::Range { start, end }
+            }
+            _ => CharacterClass::Char(start),
+        };
+        members.push(member);
+    }
+
+    let class = match members.len() {
+        1 => members.pop().unwrap(),
+        _ => CharacterClass::Disjunction(members),
+    };
+
+    Ok(if negated {
+        CharacterClass::Negated(Box::new(class))
+    } else {
+        class
+    })
+}
+
+//the opening `{` has been read
+fn parse_alternatives(chars: &mut Peekable<Chars>) -> Result<RegexEntry, String> {
+    let mut alternatives = Vec::new();
+
+    loop {
+        alternatives.push(parse_sequence(chars, true)?);
+
+        match chars.next() {
+            Some(',') => {}
+            Some('}') => break,
+            _ => return Err("Glob ends inside of braces".into()),
+        }
+    }
+
+    Ok(RegexEntry::Alternation(alternatives))
+}
+
+#[cfg(test)]
+mod test {
+    use super::from_glob;
+    use crate::runtime::Regex;
+
+    fn glob_matches(glob: &str, path: &str) -> bool {
+        Regex::from_glob(glob).unwrap().is_match(path)
+    }
+
+    #[test]
+    fn test_wildcards() {
+        assert!(glob_matches("*.tar.gz", "backup.tar.gz"));
+        assert!(!glob_matches("*.tar.gz", "backup.tarxgz"));
+        assert!(!glob_matches("*.tar.gz", "backup.tar.gz.part"));
+        assert!(!glob_matches("*.tar.gz", "old/backup.tar.gz"));
+        assert!(glob_matches("**.tar.gz", "old/backup.tar.gz"));
+        assert!(glob_matches("src/**/*.rs", "src/parser/mod.rs"));
+        assert!(glob_matches("file?.txt", "file1.txt"));
+        assert!(!glob_matches("file?.txt", "file/.txt"));
+    }
+
+    #[test]
+    fn test_classes() {
+        assert!(glob_matches("log[0-9].txt", "log3.txt"));
+        assert!(!glob_matches("log[!0-9].txt", "log3.txt"));
+        assert!(glob_matches("log[!0-9].txt", "logx.txt"));
+        assert!(glob_matches("[]a]", "]"));
+        assert!(glob_matches("[a-]", "-"));
+        assert!(from_glob("[z-a]").is_err());
+        assert!(from_glob("[ab").is_err());
+    }
+
+    #[test]
+    fn test_alternatives() {
+        assert!(glob_matches("*.{tar.gz,zip}", "backup.tar.gz"));
+        assert!(glob_matches("*.{tar.gz,zip}", "backup.zip"));
+        assert!(!glob_matches("*.{tar.gz,zip}", "backup.tar"));
+        assert!(glob_matches("{a,{b,c}d}", "cd"));
+        assert!(from_glob("{a,b").is_err());
+        assert!(glob_matches("{a,}b}", "b}"));
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_eq!(
+            from_glob("\\*").unwrap(),
+            crate::parser::RegexEntry::Literal('*')
+        );
+        assert!(from_glob("a\\").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+mod tables;
+
+/// A character's part in deciding where an extended grapheme cluster (what a reader sees as one
+/// character, like an `e` followed by a combining accent) begins and ends, as `\X` matches them.
+/// These are the values of Unicode's Grapheme_Cluster_Break property, except that characters
+/// whose value is `Other` have none, and pictographs like emoji (the Extended_Pictographic
+/// property, whose characters are all `Other`) are told apart as
+/// [`ExtendedPictographic`](GraphemeBreak::ExtendedPictographic).
+///
+/// The tables are compiled in from the Unicode Character Database of
+/// [`UNICODE_VERSION`](crate::UNICODE_VERSION).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum GraphemeBreak {
+    Cr,
+    Lf,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    /// A leading Hangul jamo (a consonant).
+    L,
+    /// A vowel Hangul jamo.
+    V,
+    /// A trailing Hangul jamo (a consonant).
+    T,
+    /// A Hangul syllable of a leading and a vowel jamo, which a trailing jamo may follow.
+    Lv,
+    /// A Hangul syllable of a leading, a vowel and a trailing jamo.
+    Lvt,
+    ExtendedPictographic,
+}
+
+impl GraphemeBreak {
+    pub const ALL: [GraphemeBreak; 14] = [
+        GraphemeBreak::Cr,
+        GraphemeBreak::Lf,
+        GraphemeBreak::Control,
+        GraphemeBreak::Extend,
+        GraphemeBreak::Zwj,
+        GraphemeBreak::RegionalIndicator,
+        GraphemeBreak::Prepend,
+        GraphemeBreak::SpacingMark,
+        GraphemeBreak::L,
+        GraphemeBreak::V,
+        GraphemeBreak::T,
+        GraphemeBreak::Lv,
+        GraphemeBreak::Lvt,
+        GraphemeBreak::ExtendedPictographic,
+    ];
+
+    /// The name Unicode gives the value, like `Extend` or `Regional_Indicator`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GraphemeBreak::Cr => "CR",
+            GraphemeBreak::Lf => "LF",
+            GraphemeBreak::Control => "Control",
+            GraphemeBreak::Extend => "Extend",
+            GraphemeBreak::Zwj => "ZWJ",
+            GraphemeBreak::RegionalIndicator => "Regional_Indicator",
+            GraphemeBreak::Prepend => "Prepend",
+            GraphemeBreak::SpacingMark => "SpacingMark",
+            GraphemeBreak::L => "L",
+            GraphemeBreak::V => "V",
+            GraphemeBreak::T => "T",
+            GraphemeBreak::Lv => "LV",
+            GraphemeBreak::Lvt => "LVT",
+            GraphemeBreak::ExtendedPictographic => "Extended_Pictographic",
+        }
+    }
+
+    /// The sorted, non-overlapping inclusive codepoint ranges of the characters with this value.
+    pub fn ranges(&self) -> &'static [(char, char)] {
+        match self {
+            GraphemeBreak::Cr => tables::CR,
+            GraphemeBreak::Lf => tables::LF,
+            GraphemeBreak::Control => tables::CONTROL,
+            GraphemeBreak::Extend => tables::EXTEND,
+            GraphemeBreak::Zwj => tables::ZWJ,
+            GraphemeBreak::RegionalIndicator => tables::REGIONAL_INDICATOR,
+            GraphemeBreak::Prepend => tables::PREPEND,
+            GraphemeBreak::SpacingMark => tables::SPACINGMARK,
+            GraphemeBreak::L => tables::L,
+            GraphemeBreak::V => tables::V,
+            GraphemeBreak::T => tables::T,
+            GraphemeBreak::Lv => tables::LV,
+            GraphemeBreak::Lvt => tables::LVT,
+            GraphemeBreak::ExtendedPictographic => tables::EXTENDED_PICTOGRAPHIC,
+        }
+    }
+
+    pub fn contains(&self, char: char) -> bool {
+        self.ranges()
+            .binary_search_by(|(start, end)| {
+                if *end < char {
+                    std::cmp::Ordering::Less
+                } else if *start > char {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    /// The value of `char`, or `None` if it is `Other`.
+    pub fn of(char: char) -> Option<GraphemeBreak> {
+        GraphemeBreak::ALL
+            .into_iter()
+            .find(|value| value.contains(char))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grapheme::GraphemeBreak;
+
+    #[test]
+    fn test_of() {
+        assert_eq!(GraphemeBreak::of('\r'), Some(GraphemeBreak::Cr));
+        assert_eq!(GraphemeBreak::of('\u{301}'), Some(GraphemeBreak::Extend));
+        assert_eq!(GraphemeBreak::of('\u{AC00}'), Some(GraphemeBreak::Lv));
+        assert_eq!(GraphemeBreak::of('\u{AC01}'), Some(GraphemeBreak::Lvt));
+        assert_eq!(
+            GraphemeBreak::of('\u{1F600}'),
+            Some(GraphemeBreak::ExtendedPictographic)
+        );
+        assert_eq!(GraphemeBreak::of('a'), None);
+    }
+}
@@ -0,0 +1,1484 @@
+//Generated from the GraphemeBreakProperty.txt and emoji-data.txt of the Unicode Character Database,
+//version 16.0.0, which are covered by the Unicode license (see LICENSE-UNICODE). Do not edit by hand.
+
+pub(super) static CR: &[(char, char)] = &[('\u{D}', '\u{D}')];
+
+pub(super) static LF: &[(char, char)] = &[('\u{A}', '\u{A}')];
+
+pub(super) static CONTROL: &[(char, char)] = &[
+    ('\u{0}', '\u{9}'),
+    ('\u{B}', '\u{C}'),
+    ('\u{E}', '\u{1F}'),
+    ('\u{7F}', '\u{9F}'),
+    ('\u{AD}', '\u{AD}'),
+    ('\u{61C}', '\u{61C}'),
+    ('\u{180E}', '\u{180E}'),
+    ('\u{200B}', '\u{200B}'),
+    ('\u{200E}', '\u{200F}'),
+    ('\u{2028}', '\u{202E}'),
+    ('\u{2060}', '\u{206F}'),
+    ('\u{FEFF}', '\u{FEFF}'),
+    ('\u{FFF0}', '\u{FFFB}'),
+    ('\u{13430}', '\u{1343F}'),
+    ('\u{1BCA0}', '\u{1BCA3}'),
+    ('\u{1D173}', '\u{1D17A}'),
+    ('\u{E0000}', '\u{E001F}'),
+    ('\u{E0080}', '\u{E00FF}'),
+    ('\u{E01F0}', '\u{E0FFF}'),
+];
+
+pub(super) static EXTEND: &[(char, char)] = &[
+    ('\u{300}', '\u{36F}'),
+    ('\u{483}', '\u{489}'),
+    ('\u{591}', '\u{5BD}'),
+    ('\u{5BF}', '\u{5BF}'),
+    ('\u{5C1}', '\u{5C2}'),
+    ('\u{5C4}', '\u{5C5}'),
+    ('\u{5C7}', '\u{5C7}'),
+    ('\u{610}', '\u{61A}'),
+    ('\u{64B}', '\u{65F}'),
+    ('\u{670}', '\u{670}'),
+    ('\u{6D6}', '\u{6DC}'),
+    ('\u{6DF}', '\u{6E4}'),
+    ('\u{6E7}', '\u{6E8}'),
+    ('\u{6EA}', '\u{6ED}'),
+    ('\u{711}', '\u{711}'),
+    ('\u{730}', '\u{74A}'),
+    ('\u{7A6}', '\u{7B0}'),
+    ('\u{7EB}', '\u{7F3}'),
+    ('\u{7FD}', '\u{7FD}'),
+    ('\u{816}', '\u{819}'),
+    ('\u{81B}', '\u{823}'),
+    ('\u{825}', '\u{827}'),
+    ('\u{829}', '\u{82D}'),
+    ('\u{859}', '\u{85B}'),
+    ('\u{897}', '\u{89F}'),
+    ('\u{8CA}', '\u{8E1}'),
+    ('\u{8E3}', '\u{902}'),
+    ('\u{93A}', '\u{93A}'),
+    ('\u{93C}', '\u{93C}'),
+    ('\u{941}', '\u{948}'),
+    ('\u{94D}', '\u{94D}'),
+    ('\u{951}', '\u{957}'),
+    ('\u{962}', '\u{963}'),
+    ('\u{981}', '\u{981}'),
+    ('\u{9BC}', '\u{9BC}'),
+    ('\u{9BE}', '\u{9BE}'),
+    ('\u{9C1}', '\u{9C4}'),
+    ('\u{9CD}', '\u{9CD}'),
+    ('\u{9D7}', '\u{9D7}'),
+    ('\u{9E2}', '\u{9E3}'),
+    ('\u{9FE}', '\u{9FE}'),
+    ('\u{A01}', '\u{A02}'),
+    ('\u{A3C}', '\u{A3C}'),
+    ('\u{A41}', '\u{A42}'),
+    ('\u{A47}', '\u{A48}'),
+    ('\u{A4B}', '\u{A4D}'),
+    ('\u{A51}', '\u{A51}'),
+    ('\u{A70}', '\u{A71}'),
+    ('\u{A75}', '\u{A75}'),
+    ('\u{A81}', '\u{A82}'),
+    ('\u{ABC}', '\u{ABC}'),
+    ('\u{AC1}', '\u{AC5}'),
+    ('\u{AC7}', '\u{AC8}'),
+    ('\u{ACD}', '\u{ACD}'),
+    ('\u{AE2}', '\u{AE3}'),
+    ('\u{AFA}', '\u{AFF}'),
+    ('\u{B01}', '\u{B01}'),
+    ('\u{B3C}', '\u{B3C}'),
+    ('\u{B3E}', '\u{B3F}'),
+    ('\u{B41}', '\u{B44}'),
+    ('\u{B4D}', '\u{B4D}'),
+    ('\u{B55}', '\u{B57}'),
+    ('\u{B62}', '\u{B63}'),
+    ('\u{B82}', '\u{B82}'),
+    ('\u{BBE}', '\u{BBE}'),
+    ('\u{BC0}', '\u{BC0}'),
+    ('\u{BCD}', '\u{BCD}'),
+    ('\u{BD7}', '\u{BD7}'),
+    ('\u{C00}', '\u{C00}'),
+    ('\u{C04}', '\u{C04}'),
+    ('\u{C3C}', '\u{C3C}'),
+    ('\u{C3E}', '\u{C40}'),
+    ('\u{C46}', '\u{C48}'),
+    ('\u{C4A}', '\u{C4D}'),
+    ('\u{C55}', '\u{C56}'),
+    ('\u{C62}', '\u{C63}'),
+    ('\u{C81}', '\u{C81}'),
+    ('\u{CBC}', '\u{CBC}'),
+    ('\u{CBF}', '\u{CC0}'),
+    ('\u{CC2}', '\u{CC2}'),
+    ('\u{CC6}', '\u{CC8}'),
+    ('\u{CCA}', '\u{CCD}'),
+    ('\u{CD5}', '\u{CD6}'),
+    ('\u{CE2}', '\u{CE3}'),
+    ('\u{D00}', '\u{D01}'),
+    ('\u{D3B}', '\u{D3C}'),
+    ('\u{D3E}', '\u{D3E}'),
+    ('\u{D41}', '\u{D44}'),
+    ('\u{D4D}', '\u{D4D}'),
+    ('\u{D57}', '\u{D57}'),
+    ('\u{D62}', '\u{D63}'),
+    ('\u{D81}', '\u{D81}'),
+    ('\u{DCA}', '\u{DCA}'),
+    ('\u{DCF}', '\u{DCF}'),
+    ('\u{DD2}', '\u{DD4}'),
+    ('\u{DD6}', '\u{DD6}'),
+    ('\u{DDF}', '\u{DDF}'),
+    ('\u{E31}', '\u{E31}'),
+    ('\u{E34}', '\u{E3A}'),
+    ('\u{E47}', '\u{E4E}'),
+    ('\u{EB1}', '\u{EB1}'),
+    ('\u{EB4}', '\u{EBC}'),
+    ('\u{EC8}', '\u{ECE}'),
+    ('\u{F18}', '\u{F19}'),
+    ('\u{F35}', '\u{F35}'),
+    ('\u{F37}', '\u{F37}'),
+    ('\u{F39}', '\u{F39}'),
+    ('\u{F71}', '\u{F7E}'),
+    ('\u{F80}', '\u{F84}'),
+    ('\u{F86}', '\u{F87}'),
+    ('\u{F8D}', '\u{F97}'),
+    ('\u{F99}', '\u{FBC}'),
+    ('\u{FC6}', '\u{FC6}'),
+    ('\u{102D}', '\u{1030}'),
+    ('\u{1032}', '\u{1037}'),
+    ('\u{1039}', '\u{103A}'),
+    ('\u{103D}', '\u{103E}'),
+    ('\u{1058}', '\u{1059}'),
+    ('\u{105E}', '\u{1060}'),
+    ('\u{1071}', '\u{1074}'),
+    ('\u{1082}', '\u{1082}'),
+    ('\u{1085}', '\u{1086}'),
+    ('\u{108D}', '\u{108D}'),
+    ('\u{109D}', '\u{109D}'),
+    ('\u{135D}', '\u{135F}'),
+    ('\u{1712}', '\u{1715}'),
+    ('\u{1732}', '\u{1734}'),
+    ('\u{1752}', '\u{1753}'),
+    ('\u{1772}', '\u{1773}'),
+    ('\u{17B4}', '\u{17B5}'),
+    ('\u{17B7}', '\u{17BD}'),
+    ('\u{17C6}', '\u{17C6}'),
+    ('\u{17C9}', '\u{17D3}'),
+    ('\u{17DD}', '\u{17DD}'),
+    ('\u{180B}', '\u{180D}'),
+    ('\u{180F}', '\u{180F}'),
+    ('\u{1885}', '\u{1886}'),
+    ('\u{18A9}', '\u{18A9}'),
+    ('\u{1920}', '\u{1922}'),
+    ('\u{1927}', '\u{1928}'),
+    ('\u{1932}', '\u{1932}'),
+    ('\u{1939}', '\u{193B}'),
+    ('\u{1A17}', '\u{1A18}'),
+    ('\u{1A1B}', '\u{1A1B}'),
+    ('\u{1A56}', '\u{1A56}'),
+    ('\u{1A58}', '\u{1A5E}'),
+    ('\u{1A60}', '\u{1A60}'),
+    ('\u{1A62}', '\u{1A62}'),
+    ('\u{1A65}', '\u{1A6C}'),
+    ('\u{1A73}', '\u{1A7C}'),
+    ('\u{1A7F}', '\u{1A7F}'),
+    ('\u{1AB0}', '\u{1ACE}'),
+    ('\u{1B00}', '\u{1B03}'),
+    ('\u{1B34}', '\u{1B3D}'),
+    ('\u{1B42}', '\u{1B44}'),
+    ('\u{1B6B}', '\u{1B73}'),
+    ('\u{1B80}', '\u{1B81}'),
+    ('\u{1BA2}', '\u{1BA5}'),
+    ('\u{1BA8}', '\u{1BAD}'),
+    ('\u{1BE6}', '\u{1BE6}'),
+    ('\u{1BE8}', '\u{1BE9}'),
+    ('\u{1BED}', '\u{1BED}'),
+    ('\u{1BEF}', '\u{1BF3}'),
+    ('\u{1C2C}', '\u{1C33}'),
+    ('\u{1C36}', '\u{1C37}'),
+    ('\u{1CD0}', '\u{1CD2}'),
+    ('\u{1CD4}', '\u{1CE0}'),
+    ('\u{1CE2}', '\u{1CE8}'),
+    ('\u{1CED}', '\u{1CED}'),
+    ('\u{1CF4}', '\u{1CF4}'),
+    ('\u{1CF8}', '\u{1CF9}'),
+    ('\u{1DC0}', '\u{1DFF}'),
+    ('\u{200C}', '\u{200C}'),
+    ('\u{20D0}', '\u{20F0}'),
+    ('\u{2CEF}', '\u{2CF1}'),
+    ('\u{2D7F}', '\u{2D7F}'),
+    ('\u{2DE0}', '\u{2DFF}'),
+    ('\u{302A}', '\u{302F}'),
+    ('\u{3099}', '\u{309A}'),
+    ('\u{A66F}', '\u{A672}'),
+    ('\u{A674}', '\u{A67D}'),
+    ('\u{A69E}', '\u{A69F}'),
+    ('\u{A6F0}', '\u{A6F1}'),
+    ('\u{A802}', '\u{A802}'),
+    ('\u{A806}', '\u{A806}'),
+    ('\u{A80B}', '\u{A80B}'),
+    ('\u{A825}', '\u{A826}'),
+    ('\u{A82C}', '\u{A82C}'),
+    ('\u{A8C4}', '\u{A8C5}'),
+    ('\u{A8E0}', '\u{A8F1}'),
+    ('\u{A8FF}', '\u{A8FF}'),
+    ('\u{A926}', '\u{A92D}'),
+    ('\u{A947}', '\u{A951}'),
+    ('\u{A953}', '\u{A953}'),
+    ('\u{A980}', '\u{A982}'),
+    ('\u{A9B3}', '\u{A9B3}'),
+    ('\u{A9B6}', '\u{A9B9}'),
+    ('\u{A9BC}', '\u{A9BD}'),
+    ('\u{A9C0}', '\u{A9C0}'),
+    ('\u{A9E5}', '\u{A9E5}'),
+    ('\u{AA29}', '\u{AA2E}'),
+    ('\u{AA31}', '\u{AA32}'),
+    ('\u{AA35}', '\u{AA36}'),
+    ('\u{AA43}', '\u{AA43}'),
+    ('\u{AA4C}', '\u{AA4C}'),
+    ('\u{AA7C}', '\u{AA7C}'),
+    ('\u{AAB0}', '\u{AAB0}'),
+    ('\u{AAB2}', '\u{AAB4}'),
+    ('\u{AAB7}', '\u{AAB8}'),
+    ('\u{AABE}', '\u{AABF}'),
+    ('\u{AAC1}', '\u{AAC1}'),
+    ('\u{AAEC}', '\u{AAED}'),
+    ('\u{AAF6}', '\u{AAF6}'),
+    ('\u{ABE5}', '\u{ABE5}'),
+    ('\u{ABE8}', '\u{ABE8}'),
+    ('\u{ABED}', '\u{ABED}'),
+    ('\u{FB1E}', '\u{FB1E}'),
+    ('\u{FE00}', '\u{FE0F}'),
+    ('\u{FE20}', '\u{FE2F}'),
+    ('\u{FF9E}', '\u{FF9F}'),
+    ('\u{101FD}', '\u{101FD}'),
+    ('\u{102E0}', '\u{102E0}'),
+    ('\u{10376}', '\u{1037A}'),
+    ('\u{10A01}', '\u{10A03}'),
+    ('\u{10A05}', '\u{10A06}'),
+    ('\u{10A0C}', '\u{10A0F}'),
+    ('\u{10A38}', '\u{10A3A}'),
+    ('\u{10A3F}', '\u{10A3F}'),
+    ('\u{10AE5}', '\u{10AE6}'),
+    ('\u{10D24}', '\u{10D27}'),
+    ('\u{10D69}', '\u{10D6D}'),
+    ('\u{10EAB}', '\u{10EAC}'),
+    ('\u{10EFC}', '\u{10EFF}'),
+    ('\u{10F46}', '\u{10F50}'),
+    ('\u{10F82}', '\u{10F85}'),
+    ('\u{11001}', '\u{11001}'),
+    ('\u{11038}', '\u{11046}'),
+    ('\u{11070}', '\u{11070}'),
+    ('\u{11073}', '\u{11074}'),
+    ('\u{1107F}', '\u{11081}'),
+    ('\u{110B3}', '\u{110B6}'),
+    ('\u{110B9}', '\u{110BA}'),
+    ('\u{110C2}', '\u{110C2}'),
+    ('\u{11100}', '\u{11102}'),
+    ('\u{11127}', '\u{1112B}'),
+    ('\u{1112D}', '\u{11134}'),
+    ('\u{11173}', '\u{11173}'),
+    ('\u{11180}', '\u{11181}'),
+    ('\u{111B6}', '\u{111BE}'),
+    ('\u{111C0}', '\u{111C0}'),
+    ('\u{111C9}', '\u{111CC}'),
+    ('\u{111CF}', '\u{111CF}'),
+    ('\u{1122F}', '\u{11231}'),
+    ('\u{11234}', '\u{11237}'),
+    ('\u{1123E}', '\u{1123E}'),
+    ('\u{11241}', '\u{11241}'),
+    ('\u{112DF}', '\u{112DF}'),
+    ('\u{112E3}', '\u{112EA}'),
+    ('\u{11300}', '\u{11301}'),
+    ('\u{1133B}', '\u{1133C}'),
+    ('\u{1133E}', '\u{1133E}'),
+    ('\u{11340}', '\u{11340}'),
+    ('\u{1134D}', '\u{1134D}'),
+    ('\u{11357}', '\u{11357}'),
+    ('\u{11366}', '\u{1136C}'),
+    ('\u{11370}', '\u{11374}'),
+    ('\u{113B8}', '\u{113B8}'),
+    ('\u{113BB}', '\u{113C0}'),
+    ('\u{113C2}', '\u{113C2}'),
+    ('\u{113C5}', '\u{113C5}'),
+    ('\u{113C7}', '\u{113C9}'),
+    ('\u{113CE}', '\u{113D0}'),
+    ('\u{113D2}', '\u{113D2}'),
+    ('\u{113E1}', '\u{113E2}'),
+    ('\u{11438}', '\u{1143F}'),
+    ('\u{11442}', '\u{11444}'),
+    ('\u{11446}', '\u{11446}'),
+    ('\u{1145E}', '\u{1145E}'),
+    ('\u{114B0}', '\u{114B0}'),
+    ('\u{114B3}', '\u{114B8}'),
+    ('\u{114BA}', '\u{114BA}'),
+    ('\u{114BD}', '\u{114BD}'),
+    ('\u{114BF}', '\u{114C0}'),
+    ('\u{114C2}', '\u{114C3}'),
+    ('\u{115AF}', '\u{115AF}'),
+    ('\u{115B2}', '\u{115B5}'),
+    ('\u{115BC}', '\u{115BD}'),
+    ('\u{115BF}', '\u{115C0}'),
+    ('\u{115DC}', '\u{115DD}'),
+    ('\u{11633}', '\u{1163A}'),
+    ('\u{1163D}', '\u{1163D}'),
+    ('\u{1163F}', '\u{11640}'),
+    ('\u{116AB}', '\u{116AB}'),
+    ('\u{116AD}', '\u{116AD}'),
+    ('\u{116B0}', '\u{116B7}'),
+    ('\u{1171D}', '\u{1171D}'),
+    ('\u{1171F}', '\u{1171F}'),
+    ('\u{11722}', '\u{11725}'),
+    ('\u{11727}', '\u{1172B}'),
+    ('\u{1182F}', '\u{11837}'),
+    ('\u{11839}', '\u{1183A}'),
+    ('\u{11930}', '\u{11930}'),
+    ('\u{1193B}', '\u{1193E}'),
+    ('\u{11943}', '\u{11943}'),
+    ('\u{119D4}', '\u{119D7}'),
+    ('\u{119DA}', '\u{119DB}'),
+    ('\u{119E0}', '\u{119E0}'),
+    ('\u{11A01}', '\u{11A0A}'),
+    ('\u{11A33}', '\u{11A38}'),
+    ('\u{11A3B}', '\u{11A3E}'),
+    ('\u{11A47}', '\u{11A47}'),
+    ('\u{11A51}', '\u{11A56}'),
+    ('\u{11A59}', '\u{11A5B}'),
+    ('\u{11A8A}', '\u{11A96}'),
+    ('\u{11A98}', '\u{11A99}'),
+    ('\u{11C30}', '\u{11C36}'),
+    ('\u{11C38}', '\u{11C3D}'),
+    ('\u{11C3F}', '\u{11C3F}'),
+    ('\u{11C92}', '\u{11CA7}'),
+    ('\u{11CAA}', '\u{11CB0}'),
+    ('\u{11CB2}', '\u{11CB3}'),
+    ('\u{11CB5}', '\u{11CB6}'),
+    ('\u{11D31}', '\u{11D36}'),
+    ('\u{11D3A}', '\u{11D3A}'),
+    ('\u{11D3C}', '\u{11D3D}'),
+    ('\u{11D3F}', '\u{11D45}'),
+    ('\u{11D47}', '\u{11D47}'),
+    ('\u{11D90}', '\u{11D91}'),
+    ('\u{11D95}', '\u{11D95}'),
+    ('\u{11D97}', '\u{11D97}'),
+    ('\u{11EF3}', '\u{11EF4}'),
+    ('\u{11F00}', '\u{11F01}'),
+    ('\u{11F36}', '\u{11F3A}'),
+    ('\u{11F40}', '\u{11F42}'),
+    ('\u{11F5A}', '\u{11F5A}'),
+    ('\u{13440}', '\u{13440}'),
+    ('\u{13447}', '\u{13455}'),
+    ('\u{1611E}', '\u{16129}'),
+    ('\u{1612D}', '\u{1612F}'),
+    ('\u{16AF0}', '\u{16AF4}'),
+    ('\u{16B30}', '\u{16B36}'),
+    ('\u{16F4F}', '\u{16F4F}'),
+    ('\u{16F8F}', '\u{16F92}'),
+    ('\u{16FE4}', '\u{16FE4}'),
+    ('\u{16FF0}', '\u{16FF1}'),
+    ('\u{1BC9D}', '\u{1BC9E}'),
+    ('\u{1CF00}', '\u{1CF2D}'),
+    ('\u{1CF30}', '\u{1CF46}'),
+    ('\u{1D165}', '\u{1D169}'),
+    ('\u{1D16D}', '\u{1D172}'),
+    ('\u{1D17B}', '\u{1D182}'),
+    ('\u{1D185}', '\u{1D18B}'),
+    ('\u{1D1AA}', '\u{1D1AD}'),
+    ('\u{1D242}', '\u{1D244}'),
+    ('\u{1DA00}', '\u{1DA36}'),
+    ('\u{1DA3B}', '\u{1DA6C}'),
+    ('\u{1DA75}', '\u{1DA75}'),
+    ('\u{1DA84}', '\u{1DA84}'),
+    ('\u{1DA9B}', '\u{1DA9F}'),
+    ('\u{1DAA1}', '\u{1DAAF}'),
+    ('\u{1E000}', '\u{1E006}'),
+    ('\u{1E008}', '\u{1E018}'),
+    ('\u{1E01B}', '\u{1E021}'),
+    ('\u{1E023}', '\u{1E024}'),
+    ('\u{1E026}', '\u{1E02A}'),
+    ('\u{1E08F}', '\u{1E08F}'),
+    ('\u{1E130}', '\u{1E136}'),
+    ('\u{1E2AE}', '\u{1E2AE}'),
+    ('\u{1E2EC}', '\u{1E2EF}'),
+    ('\u{1E4EC}', '\u{1E4EF}'),
+    ('\u{1E5EE}', '\u{1E5EF}'),
+    ('\u{1E8D0}', '\u{1E8D6}'),
+    ('\u{1E944}', '\u{1E94A}'),
+    ('\u{1F3FB}', '\u{1F3FF}'),
+    ('\u{E0020}', '\u{E007F}'),
+    ('\u{E0100}', '\u{E01EF}'),
+];
+
+pub(super) static ZWJ: &[(char, char)] = &[('\u{200D}', '\u{200D}')];
+
+pub(super) static REGIONAL_INDICATOR: &[(char, char)] = &[('\u{1F1E6}', '\u{1F1FF}')];
+
+pub(super) static PREPEND: &[(char, char)] = &[
+    ('\u{600}', '\u{605}'),
+    ('\u{6DD}', '\u{6DD}'),
+    ('\u{70F}', '\u{70F}'),
+    ('\u{890}', '\u{891}'),
+    ('\u{8E2}', '\u{8E2}'),
+    ('\u{D4E}', '\u{D4E}'),
+    ('\u{110BD}', '\u{110BD}'),
+    ('\u{110CD}', '\u{110CD}'),
+    ('\u{111C2}', '\u{111C3}'),
+    ('\u{113D1}', '\u{113D1}'),
+    ('\u{1193F}', '\u{1193F}'),
+    ('\u{11941}', '\u{11941}'),
+    ('\u{11A3A}', '\u{11A3A}'),
+    ('\u{11A84}', '\u{11A89}'),
+    ('\u{11D46}', '\u{11D46}'),
+    ('\u{11F02}', '\u{11F02}'),
+];
+
+pub(super) static SPACINGMARK: &[(char, char)] = &[
+    ('\u{903}', '\u{903}'),
+    ('\u{93B}', '\u{93B}'),
+    ('\u{93E}', '\u{940}'),
+    ('\u{949}', '\u{94C}'),
+    ('\u{94E}', '\u{94F}'),
+    ('\u{982}', '\u{983}'),
+    ('\u{9BF}', '\u{9C0}'),
+    ('\u{9C7}', '\u{9C8}'),
+    ('\u{9CB}', '\u{9CC}'),
+    ('\u{A03}', '\u{A03}'),
+    ('\u{A3E}', '\u{A40}'),
+    ('\u{A83}', '\u{A83}'),
+    ('\u{ABE}', '\u{AC0}'),
+    ('\u{AC9}', '\u{AC9}'),
+    ('\u{ACB}', '\u{ACC}'),
+    ('\u{B02}', '\u{B03}'),
+    ('\u{B40}', '\u{B40}'),
+    ('\u{B47}', '\u{B48}'),
+    ('\u{B4B}', '\u{B4C}'),
+    ('\u{BBF}', '\u{BBF}'),
+    ('\u{BC1}', '\u{BC2}'),
+    ('\u{BC6}', '\u{BC8}'),
+    ('\u{BCA}', '\u{BCC}'),
+    ('\u{C01}', '\u{C03}'),
+    ('\u{C41}', '\u{C44}'),
+    ('\u{C82}', '\u{C83}'),
+    ('\u{CBE}', '\u{CBE}'),
+    ('\u{CC1}', '\u{CC1}'),
+    ('\u{CC3}', '\u{CC4}'),
+    ('\u{CF3}', '\u{CF3}'),
+    ('\u{D02}', '\u{D03}'),
+    ('\u{D3F}', '\u{D40}'),
+    ('\u{D46}', '\u{D48}'),
+    ('\u{D4A}', '\u{D4C}'),
+    ('\u{D82}', '\u{D83}'),
+    ('\u{DD0}', '\u{DD1}'),
+    ('\u{DD8}', '\u{DDE}'),
+    ('\u{DF2}', '\u{DF3}'),
+    ('\u{E33}', '\u{E33}'),
+    ('\u{EB3}', '\u{EB3}'),
+    ('\u{F3E}', '\u{F3F}'),
+    ('\u{F7F}', '\u{F7F}'),
+    ('\u{1031}', '\u{1031}'),
+    ('\u{103B}', '\u{103C}'),
+    ('\u{1056}', '\u{1057}'),
+    ('\u{1084}', '\u{1084}'),
+    ('\u{17B6}', '\u{17B6}'),
+    ('\u{17BE}', '\u{17C5}'),
+    ('\u{17C7}', '\u{17C8}'),
+    ('\u{1923}', '\u{1926}'),
+    ('\u{1929}', '\u{192B}'),
+    ('\u{1930}', '\u{1931}'),
+    ('\u{1933}', '\u{1938}'),
+    ('\u{1A19}', '\u{1A1A}'),
+    ('\u{1A55}', '\u{1A55}'),
+    ('\u{1A57}', '\u{1A57}'),
+    ('\u{1A6D}', '\u{1A72}'),
+    ('\u{1B04}', '\u{1B04}'),
+    ('\u{1B3E}', '\u{1B41}'),
+    ('\u{1B82}', '\u{1B82}'),
+    ('\u{1BA1}', '\u{1BA1}'),
+    ('\u{1BA6}', '\u{1BA7}'),
+    ('\u{1BE7}', '\u{1BE7}'),
+    ('\u{1BEA}', '\u{1BEC}'),
+    ('\u{1BEE}', '\u{1BEE}'),
+    ('\u{1C24}', '\u{1C2B}'),
+    ('\u{1C34}', '\u{1C35}'),
+    ('\u{1CE1}', '\u{1CE1}'),
+    ('\u{1CF7}', '\u{1CF7}'),
+    ('\u{A823}', '\u{A824}'),
+    ('\u{A827}', '\u{A827}'),
+    ('\u{A880}', '\u{A881}'),
+    ('\u{A8B4}', '\u{A8C3}'),
+    ('\u{A952}', '\u{A952}'),
+    ('\u{A983}', '\u{A983}'),
+    ('\u{A9B4}', '\u{A9B5}'),
+    ('\u{A9BA}', '\u{A9BB}'),
+    ('\u{A9BE}', '\u{A9BF}'),
+    ('\u{AA2F}', '\u{AA30}'),
+    ('\u{AA33}', '\u{AA34}'),
+    ('\u{AA4D}', '\u{AA4D}'),
+    ('\u{AAEB}', '\u{AAEB}'),
+    ('\u{AAEE}', '\u{AAEF}'),
+    ('\u{AAF5}', '\u{AAF5}'),
+    ('\u{ABE3}', '\u{ABE4}'),
+    ('\u{ABE6}', '\u{ABE7}'),
+    ('\u{ABE9}', '\u{ABEA}'),
+    ('\u{ABEC}', '\u{ABEC}'),
+    ('\u{11000}', '\u{11000}'),
+    ('\u{11002}', '\u{11002}'),
+    ('\u{11082}', '\u{11082}'),
+    ('\u{110B0}', '\u{110B2}'),
+    ('\u{110B7}', '\u{110B8}'),
+    ('\u{1112C}', '\u{1112C}'),
+    ('\u{11145}', '\u{11146}'),
+    ('\u{11182}', '\u{11182}'),
+    ('\u{111B3}', '\u{111B5}'),
+    ('\u{111BF}', '\u{111BF}'),
+    ('\u{111CE}', '\u{111CE}'),
+    ('\u{1122C}', '\u{1122E}'),
+    ('\u{11232}', '\u{11233}'),
+    ('\u{112E0}', '\u{112E2}'),
+    ('\u{11302}', '\u{11303}'),
+    ('\u{1133F}', '\u{1133F}'),
+    ('\u{11341}', '\u{11344}'),
+    ('\u{11347}', '\u{11348}'),
+    ('\u{1134B}', '\u{1134C}'),
+    ('\u{11362}', '\u{11363}'),
+    ('\u{113B9}', '\u{113BA}'),
+    ('\u{113CA}', '\u{113CA}'),
+    ('\u{113CC}', '\u{113CD}'),
+    ('\u{11435}', '\u{11437}'),
+    ('\u{11440}', '\u{11441}'),
+    ('\u{11445}', '\u{11445}'),
+    ('\u{114B1}', '\u{114B2}'),
+    ('\u{114B9}', '\u{114B9}'),
+    ('\u{114BB}', '\u{114BC}'),
+    ('\u{114BE}', '\u{114BE}'),
+    ('\u{114C1}', '\u{114C1}'),
+    ('\u{115B0}', '\u{115B1}'),
+    ('\u{115B8}', '\u{115BB}'),
+    ('\u{115BE}', '\u{115BE}'),
+    ('\u{11630}', '\u{11632}'),
+    ('\u{1163B}', '\u{1163C}'),
+    ('\u{1163E}', '\u{1163E}'),
+    ('\u{116AC}', '\u{116AC}'),
+    ('\u{116AE}', '\u{116AF}'),
+    ('\u{1171E}', '\u{1171E}'),
+    ('\u{11726}', '\u{11726}'),
+    ('\u{1182C}', '\u{1182E}'),
+    ('\u{11838}', '\u{11838}'),
+    ('\u{11931}', '\u{11935}'),
+    ('\u{11937}', '\u{11938}'),
+    ('\u{11940}', '\u{11940}'),
+    ('\u{11942}', '\u{11942}'),
+    ('\u{119D1}', '\u{119D3}'),
+    ('\u{119DC}', '\u{119DF}'),
+    ('\u{119E4}', '\u{119E4}'),
+    ('\u{11A39}', '\u{11A39}'),
+    ('\u{11A57}', '\u{11A58}'),
+    ('\u{11A97}', '\u{11A97}'),
+    ('\u{11C2F}', '\u{11C2F}'),
+    ('\u{11C3E}', '\u{11C3E}'),
+    ('\u{11CA9}', '\u{11CA9}'),
+    ('\u{11CB1}', '\u{11CB1}'),
+    ('\u{11CB4}', '\u{11CB4}'),
+    ('\u{11D8A}', '\u{11D8E}'),
+    ('\u{11D93}', '\u{11D94}'),
+    ('\u{11D96}', '\u{11D96}'),
+    ('\u{11EF5}', '\u{11EF6}'),
+    ('\u{11F03}', '\u{11F03}'),
+    ('\u{11F34}', '\u{11F35}'),
+    ('\u{11F3E}', '\u{11F3F}'),
+    ('\u{1612A}', '\u{1612C}'),
+    ('\u{16F51}', '\u{16F87}'),
+];
+
+pub(super) static L: &[(char, char)] = &[('\u{1100}', '\u{115F}'), ('\u{A960}', '\u{A97C}')];
+
+pub(super) static V: &[(char, char)] = &[
+    ('\u{1160}', '\u{11A7}'),
+    ('\u{D7B0}', '\u{D7C6}'),
+    ('\u{16D63}', '\u{16D63}'),
+    ('\u{16D67}', '\u{16D6A}'),
+];
+
+pub(super) static T: &[(char, char)] = &[('\u{11A8}', '\u{11FF}'), ('\u{D7CB}', '\u{D7FB}')];
+
+pub(super) static LV: &[(char, char)] = &[
+    ('\u{AC00}', '\u{AC00}'),
+    ('\u{AC1C}', '\u{AC1C}'),
+    ('\u{AC38}', '\u{AC38}'),
+    ('\u{AC54}', '\u{AC54}'),
+    ('\u{AC70}', '\u{AC70}'),
+    ('\u{AC8C}', '\u{AC8C}'),
+    ('\u{ACA8}', '\u{ACA8}'),
+    ('\u{ACC4}', '\u{ACC4}'),
+    ('\u{ACE0}', '\u{ACE0}'),
+    ('\u{ACFC}', '\u{ACFC}'),
+    ('\u{AD18}', '\u{AD18}'),
+    ('\u{AD34}', '\u{AD34}'),
+    ('\u{AD50}', '\u{AD50}'),
+    ('\u{AD6C}', '\u{AD6C}'),
+    ('\u{AD88}', '\u{AD88}'),
+    ('\u{ADA4}', '\u{ADA4}'),
+    ('\u{ADC0}', '\u{ADC0}'),
+    ('\u{ADDC}', '\u{ADDC}'),
+    ('\u{ADF8}', '\u{ADF8}'),
+    ('\u{AE14}', '\u{AE14}'),
+    ('\u{AE30}', '\u{AE30}'),
+    ('\u{AE4C}', '\u{AE4C}'),
+    ('\u{AE68}', '\u{AE68}'),
+    ('\u{AE84}', '\u{AE84}'),
+    ('\u{AEA0}', '\u{AEA0}'),
+    ('\u{AEBC}', '\u{AEBC}'),
+    ('\u{AED8}', '\u{AED8}'),
+    ('\u{AEF4}', '\u{AEF4}'),
+    ('\u{AF10}', '\u{AF10}'),
+    ('\u{AF2C}', '\u{AF2C}'),
+    ('\u{AF48}', '\u{AF48}'),
+    ('\u{AF64}', '\u{AF64}'),
+    ('\u{AF80}', '\u{AF80}'),
+    ('\u{AF9C}', '\u{AF9C}'),
+    ('\u{AFB8}', '\u{AFB8}'),
+    ('\u{AFD4}', '\u{AFD4}'),
+    ('\u{AFF0}', '\u{AFF0}'),
+    ('\u{B00C}', '\u{B00C}'),
+    ('\u{B028}', '\u{B028}'),
+    ('\u{B044}', '\u{B044}'),
+    ('\u{B060}', '\u{B060}'),
+    ('\u{B07C}', '\u{B07C}'),
+    ('\u{B098}', '\u{B098}'),
+    ('\u{B0B4}', '\u{B0B4}'),
+    ('\u{B0D0}', '\u{B0D0}'),
+    ('\u{B0EC}', '\u{B0EC}'),
+    ('\u{B108}', '\u{B108}'),
+    ('\u{B124}', '\u{B124}'),
+    ('\u{B140}', '\u{B140}'),
+    ('\u{B15C}', '\u{B15C}'),
+    ('\u{B178}', '\u{B178}'),
+    ('\u{B194}', '\u{B194}'),
+    ('\u{B1B0}', '\u{B1B0}'),
+    ('\u{B1CC}', '\u{B1CC}'),
+    ('\u{B1E8}', '\u{B1E8}'),
+    ('\u{B204}', '\u{B204}'),
+    ('\u{B220}', '\u{B220}'),
+    ('\u{B23C}', '\u{B23C}'),
+    ('\u{B258}', '\u{B258}'),
+    ('\u{B274}', '\u{B274}'),
+    ('\u{B290}', '\u{B290}'),
+    ('\u{B2AC}', '\u{B2AC}'),
+    ('\u{B2C8}', '\u{B2C8}'),
+    ('\u{B2E4}', '\u{B2E4}'),
+    ('\u{B300}', '\u{B300}'),
+    ('\u{B31C}', '\u{B31C}'),
+    ('\u{B338}', '\u{B338}'),
+    ('\u{B354}', '\u{B354}'),
+    ('\u{B370}', '\u{B370}'),
+    ('\u{B38C}', '\u{B38C}'),
+    ('\u{B3A8}', '\u{B3A8}'),
+    ('\u{B3C4}', '\u{B3C4}'),
+    ('\u{B3E0}', '\u{B3E0}'),
+    ('\u{B3FC}', '\u{B3FC}'),
+    ('\u{B418}', '\u{B418}'),
+    ('\u{B434}', '\u{B434}'),
+    ('\u{B450}', '\u{B450}'),
+    ('\u{B46C}', '\u{B46C}'),
+    ('\u{B488}', '\u{B488}'),
+    ('\u{B4A4}', '\u{B4A4}'),
+    ('\u{B4C0}', '\u{B4C0}'),
+    ('\u{B4DC}', '\u{B4DC}'),
+    ('\u{B4F8}', '\u{B4F8}'),
+    ('\u{B514}', '\u{B514}'),
+    ('\u{B530}', '\u{B530}'),
+    ('\u{B54C}', '\u{B54C}'),
+    ('\u{B568}', '\u{B568}'),
+    ('\u{B584}', '\u{B584}'),
+    ('\u{B5A0}', '\u{B5A0}'),
+    ('\u{B5BC}', '\u{B5BC}'),
+    ('\u{B5D8}', '\u{B5D8}'),
+    ('\u{B5F4}', '\u{B5F4}'),
+    ('\u{B610}', '\u{B610}'),
+    ('\u{B62C}', '\u{B62C}'),
+    ('\u{B648}', '\u{B648}'),
+    ('\u{B664}', '\u{B664}'),
+    ('\u{B680}', '\u{B680}'),
+    ('\u{B69C}', '\u{B69C}'),
+    ('\u{B6B8}', '\u{B6B8}'),
+    ('\u{B6D4}', '\u{B6D4}'),
+    ('\u{B6F0}', '\u{B6F0}'),
+    ('\u{B70C}', '\u{B70C}'),
+    ('\u{B728}', '\u{B728}'),
+    ('\u{B744}', '\u{B744}'),
+    ('\u{B760}', '\u{B760}'),
+    ('\u{B77C}', '\u{B77C}'),
+    ('\u{B798}', '\u{B798}'),
+    ('\u{B7B4}', '\u{B7B4}'),
+    ('\u{B7D0}', '\u{B7D0}'),
+    ('\u{B7EC}', '\u{B7EC}'),
+    ('\u{B808}', '\u{B808}'),
+    ('\u{B824}', '\u{B824}'),
+    ('\u{B840}', '\u{B840}'),
+    ('\u{B85C}', '\u{B85C}'),
+    ('\u{B878}', '\u{B878}'),
+    ('\u{B894}', '\u{B894}'),
+    ('\u{B8B0}', '\u{B8B0}'),
+    ('\u{B8CC}', '\u{B8CC}'),
+    ('\u{B8E8}', '\u{B8E8}'),
+    ('\u{B904}', '\u{B904}'),
+    ('\u{B920}', '\u{B920}'),
+    ('\u{B93C}', '\u{B93C}'),
+    ('\u{B958}', '\u{B958}'),
+    ('\u{B974}', '\u{B974}'),
+    ('\u{B990}', '\u{B990}'),
+    ('\u{B9AC}', '\u{B9AC}'),
+    ('\u{B9C8}', '\u{B9C8}'),
+    ('\u{B9E4}', '\u{B9E4}'),
+    ('\u{BA00}', '\u{BA00}'),
+    ('\u{BA1C}', '\u{BA1C}'),
+    ('\u{BA38}', '\u{BA38}'),
+    ('\u{BA54}', '\u{BA54}'),
+    ('\u{BA70}', '\u{BA70}'),
+    ('\u{BA8C}', '\u{BA8C}'),
+    ('\u{BAA8}', '\u{BAA8}'),
+    ('\u{BAC4}', '\u{BAC4}'),
+    ('\u{BAE0}', '\u{BAE0}'),
+    ('\u{BAFC}', '\u{BAFC}'),
+    ('\u{BB18}', '\u{BB18}'),
+    ('\u{BB34}', '\u{BB34}'),
+    ('\u{BB50}', '\u{BB50}'),
+    ('\u{BB6C}', '\u{BB6C}'),
+    ('\u{BB88}', '\u{BB88}'),
+    ('\u{BBA4}', '\u{BBA4}'),
+    ('\u{BBC0}', '\u{BBC0}'),
+    ('\u{BBDC}', '\u{BBDC}'),
+    ('\u{BBF8}', '\u{BBF8}'),
+    ('\u{BC14}', '\u{BC14}'),
+    ('\u{BC30}', '\u{BC30}'),
+    ('\u{BC4C}', '\u{BC4C}'),
+    ('\u{BC68}', '\u{BC68}'),
+    ('\u{BC84}', '\u{BC84}'),
+    ('\u{BCA0}', '\u{BCA0}'),
+    ('\u{BCBC}', '\u{BCBC}'),
+    ('\u{BCD8}', '\u{BCD8}'),
+    ('\u{BCF4}', '\u{BCF4}'),
+    ('\u{BD10}', '\u{BD10}'),
+    ('\u{BD2C}', '\u{BD2C}'),
+    ('\u{BD48}', '\u{BD48}'),
+    ('\u{BD64}', '\u{BD64}'),
+    ('\u{BD80}', '\u{BD80}'),
+    ('\u{BD9C}', '\u{BD9C}'),
+    ('\u{BDB8}', '\u{BDB8}'),
+    ('\u{BDD4}', '\u{BDD4}'),
+    ('\u{BDF0}', '\u{BDF0}'),
+    ('\u{BE0C}', '\u{BE0C}'),
+    ('\u{BE28}', '\u{BE28}'),
+    ('\u{BE44}', '\u{BE44}'),
+    ('\u{BE60}', '\u{BE60}'),
+    ('\u{BE7C}', '\u{BE7C}'),
+    ('\u{BE98}', '\u{BE98}'),
+    ('\u{BEB4}', '\u{BEB4}'),
+    ('\u{BED0}', '\u{BED0}'),
+    ('\u{BEEC}', '\u{BEEC}'),
+    ('\u{BF08}', '\u{BF08}'),
+    ('\u{BF24}', '\u{BF24}'),
+    ('\u{BF40}', '\u{BF40}'),
+    ('\u{BF5C}', '\u{BF5C}'),
+    ('\u{BF78}', '\u{BF78}'),
+    ('\u{BF94}', '\u{BF94}'),
+    ('\u{BFB0}', '\u{BFB0}'),
+    ('\u{BFCC}', '\u{BFCC}'),
+    ('\u{BFE8}', '\u{BFE8}'),
+    ('\u{C004}', '\u{C004}'),
+    ('\u{C020}', '\u{C020}'),
+    ('\u{C03C}', '\u{C03C}'),
+    ('\u{C058}', '\u{C058}'),
+    ('\u{C074}', '\u{C074}'),
+    ('\u{C090}', '\u{C090}'),
+    ('\u{C0AC}', '\u{C0AC}'),
+    ('\u{C0C8}', '\u{C0C8}'),
+    ('\u{C0E4}', '\u{C0E4}'),
+    ('\u{C100}', '\u{C100}'),
+    ('\u{C11C}', '\u{C11C}'),
+    ('\u{C138}', '\u{C138}'),
+    ('\u{C154}', '\u{C154}'),
+    ('\u{C170}', '\u{C170}'),
+    ('\u{C18C}', '\u{C18C}'),
+    ('\u{C1A8}', '\u{C1A8}'),
+    ('\u{C1C4}', '\u{C1C4}'),
+    ('\u{C1E0}', '\u{C1E0}'),
+    ('\u{C1FC}', '\u{C1FC}'),
+    ('\u{C218}', '\u{C218}'),
+    ('\u{C234}', '\u{C234}'),
+    ('\u{C250}', '\u{C250}'),
+    ('\u{C26C}', '\u{C26C}'),
+    ('\u{C288}', '\u{C288}'),
+    ('\u{C2A4}', '\u{C2A4}'),
+    ('\u{C2C0}', '\u{C2C0}'),
+    ('\u{C2DC}', '\u{C2DC}'),
+    ('\u{C2F8}', '\u{C2F8}'),
+    ('\u{C314}', '\u{C314}'),
+    ('\u{C330}', '\u{C330}'),
+    ('\u{C34C}', '\u{C34C}'),
+    ('\u{C368}', '\u{C368}'),
+    ('\u{C384}', '\u{C384}'),
+    ('\u{C3A0}', '\u{C3A0}'),
+    ('\u{C3BC}', '\u{C3BC}'),
+    ('\u{C3D8}', '\u{C3D8}'),
+    ('\u{C3F4}', '\u{C3F4}'),
+    ('\u{C410}', '\u{C410}'),
+    ('\u{C42C}', '\u{C42C}'),
+    ('\u{C448}', '\u{C448}'),
+    ('\u{C464}', '\u{C464}'),
+    ('\u{C480}', '\u{C480}'),
+    ('\u{C49C}', '\u{C49C}'),
+    ('\u{C4B8}', '\u{C4B8}'),
+    ('\u{C4D4}', '\u{C4D4}'),
+    ('\u{C4F0}', '\u{C4F0}'),
+    ('\u{C50C}', '\u{C50C}'),
+    ('\u{C528}', '\u{C528}'),
+    ('\u{C544}', '\u{C544}'),
+    ('\u{C560}', '\u{C560}'),
+    ('\u{C57C}', '\u{C57C}'),
+    ('\u{C598}', '\u{C598}'),
+    ('\u{C5B4}', '\u{C5B4}'),
+    ('\u{C5D0}', '\u{C5D0}'),
+    ('\u{C5EC}', '\u{C5EC}'),
+    ('\u{C608}', '\u{C608}'),
+    ('\u{C624}', '\u{C624}'),
+    ('\u{C640}', '\u{C640}'),
+    ('\u{C65C}', '\u{C65C}'),
+    ('\u{C678}', '\u{C678}'),
+    ('\u{C694}', '\u{C694}'),
+    ('\u{C6B0}', '\u{C6B0}'),
+    ('\u{C6CC}', '\u{C6CC}'),
+    ('\u{C6E8}', '\u{C6E8}'),
+    ('\u{C704}', '\u{C704}'),
+    ('\u{C720}', '\u{C720}'),
+    ('\u{C73C}', '\u{C73C}'),
+    ('\u{C758}', '\u{C758}'),
+    ('\u{C774}', '\u{C774}'),
+    ('\u{C790}', '\u{C790}'),
+    ('\u{C7AC}', '\u{C7AC}'),
+    ('\u{C7C8}', '\u{C7C8}'),
+    ('\u{C7E4}', '\u{C7E4}'),
+    ('\u{C800}', '\u{C800}'),
+    ('\u{C81C}', '\u{C81C}'),
+    ('\u{C838}', '\u{C838}'),
+    ('\u{C854}', '\u{C854}'),
+    ('\u{C870}', '\u{C870}'),
+    ('\u{C88C}', '\u{C88C}'),
+    ('\u{C8A8}', '\u{C8A8}'),
+    ('\u{C8C4}', '\u{C8C4}'),
+    ('\u{C8E0}', '\u{C8E0}'),
+    ('\u{C8FC}', '\u{C8FC}'),
+    ('\u{C918}', '\u{C918}'),
+    ('\u{C934}', '\u{C934}'),
+    ('\u{C950}', '\u{C950}'),
+    ('\u{C96C}', '\u{C96C}'),
+    ('\u{C988}', '\u{C988}'),
+    ('\u{C9A4}', '\u{C9A4}'),
+    ('\u{C9C0}', '\u{C9C0}'),
+    ('\u{C9DC}', '\u{C9DC}'),
+    ('\u{C9F8}', '\u{C9F8}'),
+    ('\u{CA14}', '\u{CA14}'),
+    ('\u{CA30}', '\u{CA30}'),
+    ('\u{CA4C}', '\u{CA4C}'),
+    ('\u{CA68}', '\u{CA68}'),
+    ('\u{CA84}', '\u{CA84}'),
+    ('\u{CAA0}', '\u{CAA0}'),
+    ('\u{CABC}', '\u{CABC}'),
+    ('\u{CAD8}', '\u{CAD8}'),
+    ('\u{CAF4}', '\u{CAF4}'),
+    ('\u{CB10}', '\u{CB10}'),
+    ('\u{CB2C}', '\u{CB2C}'),
+    ('\u{CB48}', '\u{CB48}'),
+    ('\u{CB64}', '\u{CB64}'),
+    ('\u{CB80}', '\u{CB80}'),
+    ('\u{CB9C}', '\u{CB9C}'),
+    ('\u{CBB8}', '\u{CBB8}'),
+    ('\u{CBD4}', '\u{CBD4}'),
+    ('\u{CBF0}', '\u{CBF0}'),
+    ('\u{CC0C}', '\u{CC0C}'),
+    ('\u{CC28}', '\u{CC28}'),
+    ('\u{CC44}', '\u{CC44}'),
+    ('\u{CC60}', '\u{CC60}'),
+    ('\u{CC7C}', '\u{CC7C}'),
+    ('\u{CC98}', '\u{CC98}'),
+    ('\u{CCB4}', '\u{CCB4}'),
+    ('\u{CCD0}', '\u{CCD0}'),
+    ('\u{CCEC}', '\u{CCEC}'),
+    ('\u{CD08}', '\u{CD08}'),
+    ('\u{CD24}', '\u{CD24}'),
+    ('\u{CD40}', '\u{CD40}'),
+    ('\u{CD5C}', '\u{CD5C}'),
+    ('\u{CD78}', '\u{CD78}'),
+    ('\u{CD94}', '\u{CD94}'),
+    ('\u{CDB0}', '\u{CDB0}'),
+    ('\u{CDCC}', '\u{CDCC}'),
+    ('\u{CDE8}', '\u{CDE8}'),
+    ('\u{CE04}', '\u{CE04}'),
+    ('\u{CE20}', '\u{CE20}'),
+    ('\u{CE3C}', '\u{CE3C}'),
+    ('\u{CE58}', '\u{CE58}'),
+    ('\u{CE74}', '\u{CE74}'),
+    ('\u{CE90}', '\u{CE90}'),
+    ('\u{CEAC}', '\u{CEAC}'),
+    ('\u{CEC8}', '\u{CEC8}'),
+    ('\u{CEE4}', '\u{CEE4}'),
+    ('\u{CF00}', '\u{CF00}'),
+    ('\u{CF1C}', '\u{CF1C}'),
+    ('\u{CF38}', '\u{CF38}'),
+    ('\u{CF54}', '\u{CF54}'),
+    ('\u{CF70}', '\u{CF70}'),
+    ('\u{CF8C}', '\u{CF8C}'),
+    ('\u{CFA8}', '\u{CFA8}'),
+    ('\u{CFC4}', '\u{CFC4}'),
+    ('\u{CFE0}', '\u{CFE0}'),
+    ('\u{CFFC}', '\u{CFFC}'),
+    ('\u{D018}', '\u{D018}'),
+    ('\u{D034}', '\u{D034}'),
+    ('\u{D050}', '\u{D050}'),
+    ('\u{D06C}', '\u{D06C}'),
+    ('\u{D088}', '\u{D088}'),
+    ('\u{D0A4}', '\u{D0A4}'),
+    ('\u{D0C0}', '\u{D0C0}'),
+    ('\u{D0DC}', '\u{D0DC}'),
+    ('\u{D0F8}', '\u{D0F8}'),
+    ('\u{D114}', '\u{D114}'),
+    ('\u{D130}', '\u{D130}'),
+    ('\u{D14C}', '\u{D14C}'),
+    ('\u{D168}', '\u{D168}'),
+    ('\u{D184}', '\u{D184}'),
+    ('\u{D1A0}', '\u{D1A0}'),
+    ('\u{D1BC}', '\u{D1BC}'),
+    ('\u{D1D8}', '\u{D1D8}'),
+    ('\u{D1F4}', '\u{D1F4}'),
+    ('\u{D210}', '\u{D210}'),
+    ('\u{D22C}', '\u{D22C}'),
+    ('\u{D248}', '\u{D248}'),
+    ('\u{D264}', '\u{D264}'),
+    ('\u{D280}', '\u{D280}'),
+    ('\u{D29C}', '\u{D29C}'),
+    ('\u{D2B8}', '\u{D2B8}'),
+    ('\u{D2D4}', '\u{D2D4}'),
+    ('\u{D2F0}', '\u{D2F0}'),
+    ('\u{D30C}', '\u{D30C}'),
+    ('\u{D328}', '\u{D328}'),
+    ('\u{D344}', '\u{D344}'),
+    ('\u{D360}', '\u{D360}'),
+    ('\u{D37C}', '\u{D37C}'),
+    ('\u{D398}', '\u{D398}'),
+    ('\u{D3B4}', '\u{D3B4}'),
+    ('\u{D3D0}', '\u{D3D0}'),
+    ('\u{D3EC}', '\u{D3EC}'),
+    ('\u{D408}', '\u{D408}'),
+    ('\u{D424}', '\u{D424}'),
+    ('\u{D440}', '\u{D440}'),
+    ('\u{D45C}', '\u{D45C}'),
+    ('\u{D478}', '\u{D478}'),
+    ('\u{D494}', '\u{D494}'),
+    ('\u{D4B0}', '\u{D4B0}'),
+    ('\u{D4CC}', '\u{D4CC}'),
+    ('\u{D4E8}', '\u{D4E8}'),
+    ('\u{D504}', '\u{D504}'),
+    ('\u{D520}', '\u{D520}'),
+    ('\u{D53C}', '\u{D53C}'),
+    ('\u{D558}', '\u{D558}'),
+    ('\u{D574}', '\u{D574}'),
+    ('\u{D590}', '\u{D590}'),
+    ('\u{D5AC}', '\u{D5AC}'),
+    ('\u{D5C8}', '\u{D5C8}'),
+    ('\u{D5E4}', '\u{D5E4}'),
+    ('\u{D600}', '\u{D600}'),
+    ('\u{D61C}', '\u{D61C}'),
+    ('\u{D638}', '\u{D638}'),
+    ('\u{D654}', '\u{D654}'),
+    ('\u{D670}', '\u{D670}'),
+    ('\u{D68C}', '\u{D68C}'),
+    ('\u{D6A8}', '\u{D6A8}'),
+    ('\u{D6C4}', '\u{D6C4}'),
+    ('\u{D6E0}', '\u{D6E0}'),
+    ('\u{D6FC}', '\u{D6FC}'),
+    ('\u{D718}', '\u{D718}'),
+    ('\u{D734}', '\u{D734}'),
+    ('\u{D750}', '\u{D750}'),
+    ('\u{D76C}', '\u{D76C}'),
+    ('\u{D788}', '\u{D788}'),
+];
+
+pub(super) static LVT: &[(char, char)] = &[
+    ('\u{AC01}', '\u{AC1B}'),
+    ('\u{AC1D}', '\u{AC37}'),
+    ('\u{AC39}', '\u{AC53}'),
+    ('\u{AC55}', '\u{AC6F}'),
+    ('\u{AC71}', '\u{AC8B}'),
+    ('\u{AC8D}', '\u{ACA7}'),
+    ('\u{ACA9}', '\u{ACC3}'),
+    ('\u{ACC5}', '\u{ACDF}'),
+    ('\u{ACE1}', '\u{ACFB}'),
+    ('\u{ACFD}', '\u{AD17}'),
+    ('\u{AD19}', '\u{AD33}'),
+    ('\u{AD35}', '\u{AD4F}'),
+    ('\u{AD51}', '\u{AD6B}'),
+    ('\u{AD6D}', '\u{AD87}'),
+    ('\u{AD89}', '\u{ADA3}'),
+    ('\u{ADA5}', '\u{ADBF}'),
+    ('\u{ADC1}', '\u{ADDB}'),
+    ('\u{ADDD}', '\u{ADF7}'),
+    ('\u{ADF9}', '\u{AE13}'),
+    ('\u{AE15}', '\u{AE2F}'),
+    ('\u{AE31}', '\u{AE4B}'),
+    ('\u{AE4D}', '\u{AE67}'),
+    ('\u{AE69}', '\u{AE83}'),
+    ('\u{AE85}', '\u{AE9F}'),
+    ('\u{AEA1}', '\u{AEBB}'),
+    ('\u{AEBD}', '\u{AED7}'),
+    ('\u{AED9}', '\u{AEF3}'),
+    ('\u{AEF5}', '\u{AF0F}'),
+    ('\u{AF11}', '\u{AF2B}'),
+    ('\u{AF2D}', '\u{AF47}'),
+    ('\u{AF49}', '\u{AF63}'),
+    ('\u{AF65}', '\u{AF7F}'),
+    ('\u{AF81}', '\u{AF9B}'),
+    ('\u{AF9D}', '\u{AFB7}'),
+    ('\u{AFB9}', '\u{AFD3}'),
+    ('\u{AFD5}', '\u{AFEF}'),
+    ('\u{AFF1}', '\u{B00B}'),
+    ('\u{B00D}', '\u{B027}'),
+    ('\u{B029}', '\u{B043}'),
+    ('\u{B045}', '\u{B05F}'),
+    ('\u{B061}', '\u{B07B}'),
+    ('\u{B07D}', '\u{B097}'),
+    ('\u{B099}', '\u{B0B3}'),
+    ('\u{B0B5}', '\u{B0CF}'),
+    ('\u{B0D1}', '\u{B0EB}'),
+    ('\u{B0ED}', '\u{B107}'),
+    ('\u{B109}', '\u{B123}'),
+    ('\u{B125}', '\u{B13F}'),
+    ('\u{B141}', '\u{B15B}'),
+    ('\u{B15D}', '\u{B177}'),
+    ('\u{B179}', '\u{B193}'),
+    ('\u{B195}', '\u{B1AF}'),
+    ('\u{B1B1}', '\u{B1CB}'),
+    ('\u{B1CD}', '\u{B1E7}'),
+    ('\u{B1E9}', '\u{B203}'),
+    ('\u{B205}', '\u{B21F}'),
+    ('\u{B221}', '\u{B23B}'),
+    ('\u{B23D}', '\u{B257}'),
+    ('\u{B259}', '\u{B273}'),
+    ('\u{B275}', '\u{B28F}'),
+    ('\u{B291}', '\u{B2AB}'),
+    ('\u{B2AD}', '\u{B2C7}'),
+    ('\u{B2C9}', '\u{B2E3}'),
+    ('\u{B2E5}', '\u{B2FF}'),
+    ('\u{B301}', '\u{B31B}'),
+    ('\u{B31D}', '\u{B337}'),
+    ('\u{B339}', '\u{B353}'),
+    ('\u{B355}', '\u{B36F}'),
+    ('\u{B371}', '\u{B38B}'),
+    ('\u{B38D}', '\u{B3A7}'),
+    ('\u{B3A9}', '\u{B3C3}'),
+    ('\u{B3C5}', '\u{B3DF}'),
+    ('\u{B3E1}', '\u{B3FB}'),
+    ('\u{B3FD}', '\u{B417}'),
+    ('\u{B419}', '\u{B433}'),
+    ('\u{B435}', '\u{B44F}'),
+    ('\u{B451}', '\u{B46B}'),
+    ('\u{B46D}', '\u{B487}'),
+    ('\u{B489}', '\u{B4A3}'),
+    ('\u{B4A5}', '\u{B4BF}'),
+    ('\u{B4C1}', '\u{B4DB}'),
+    ('\u{B4DD}', '\u{B4F7}'),
+    ('\u{B4F9}', '\u{B513}'),
+    ('\u{B515}', '\u{B52F}'),
+    ('\u{B531}', '\u{B54B}'),
+    ('\u{B54D}', '\u{B567}'),
+    ('\u{B569}', '\u{B583}'),
+    ('\u{B585}', '\u{B59F}'),
+    ('\u{B5A1}', '\u{B5BB}'),
+    ('\u{B5BD}', '\u{B5D7}'),
+    ('\u{B5D9}', '\u{B5F3}'),
+    ('\u{B5F5}', '\u{B60F}'),
+    ('\u{B611}', '\u{B62B}'),
+    ('\u{B62D}', '\u{B647}'),
+    ('\u{B649}', '\u{B663}'),
+    ('\u{B665}', '\u{B67F}'),
+    ('\u{B681}', '\u{B69B}'),
+    ('\u{B69D}', '\u{B6B7}'),
+    ('\u{B6B9}', '\u{B6D3}'),
+    ('\u{B6D5}', '\u{B6EF}'),
+    ('\u{B6F1}', '\u{B70B}'),
+    ('\u{B70D}', '\u{B727}'),
+    ('\u{B729}', '\u{B743}'),
+    ('\u{B745}', '\u{B75F}'),
+    ('\u{B761}', '\u{B77B}'),
+    ('\u{B77D}', '\u{B797}'),
+    ('\u{B799}', '\u{B7B3}'),
+    ('\u{B7B5}', '\u{B7CF}'),
+    ('\u{B7D1}', '\u{B7EB}'),
+    ('\u{B7ED}', '\u{B807}'),
+    ('\u{B809}', '\u{B823}'),
+    ('\u{B825}', '\u{B83F}'),
+    ('\u{B841}', '\u{B85B}'),
+    ('\u{B85D}', '\u{B877}'),
+    ('\u{B879}', '\u{B893}'),
+    ('\u{B895}', '\u{B8AF}'),
+    ('\u{B8B1}', '\u{B8CB}'),
+    ('\u{B8CD}', '\u{B8E7}'),
+    ('\u{B8E9}', '\u{B903}'),
+    ('\u{B905}', '\u{B91F}'),
+    ('\u{B921}', '\u{B93B}'),
+    ('\u{B93D}', '\u{B957}'),
+    ('\u{B959}', '\u{B973}'),
+    ('\u{B975}', '\u{B98F}'),
+    ('\u{B991}', '\u{B9AB}'),
+    ('\u{B9AD}', '\u{B9C7}'),
+    ('\u{B9C9}', '\u{B9E3}'),
+    ('\u{B9E5}', '\u{B9FF}'),
+    ('\u{BA01}', '\u{BA1B}'),
+    ('\u{BA1D}', '\u{BA37}'),
+    ('\u{BA39}', '\u{BA53}'),
+    ('\u{BA55}', '\u{BA6F}'),
+    ('\u{BA71}', '\u{BA8B}'),
+    ('\u{BA8D}', '\u{BAA7}'),
+    ('\u{BAA9}', '\u{BAC3}'),
+    ('\u{BAC5}', '\u{BADF}'),
+    ('\u{BAE1}', '\u{BAFB}'),
+    ('\u{BAFD}', '\u{BB17}'),
+    ('\u{BB19}', '\u{BB33}'),
+    ('\u{BB35}', '\u{BB4F}'),
+    ('\u{BB51}', '\u{BB6B}'),
+    ('\u{BB6D}', '\u{BB87}'),
+    ('\u{BB89}', '\u{BBA3}'),
+    ('\u{BBA5}', '\u{BBBF}'),
+    ('\u{BBC1}', '\u{BBDB}'),
+    ('\u{BBDD}', '\u{BBF7}'),
+    ('\u{BBF9}', '\u{BC13}'),
+    ('\u{BC15}', '\u{BC2F}'),
+    ('\u{BC31}', '\u{BC4B}'),
+    ('\u{BC4D}', '\u{BC67}'),
+    ('\u{BC69}', '\u{BC83}'),
+    ('\u{BC85}', '\u{BC9F}'),
+    ('\u{BCA1}', '\u{BCBB}'),
+    ('\u{BCBD}', '\u{BCD7}'),
+    ('\u{BCD9}', '\u{BCF3}'),
+    ('\u{BCF5}', '\u{BD0F}'),
+    ('\u{BD11}', '\u{BD2B}'),
+    ('\u{BD2D}', '\u{BD47}'),
+    ('\u{BD49}', '\u{BD63}'),
+    ('\u{BD65}', '\u{BD7F}'),
+    ('\u{BD81}', '\u{BD9B}'),
+    ('\u{BD9D}', '\u{BDB7}'),
+    ('\u{BDB9}', '\u{BDD3}'),
+    ('\u{BDD5}', '\u{BDEF}'),
+    ('\u{BDF1}', '\u{BE0B}'),
+    ('\u{BE0D}', '\u{BE27}'),
+    ('\u{BE29}', '\u{BE43}'),
+    ('\u{BE45}', '\u{BE5F}'),
+    ('\u{BE61}', '\u{BE7B}'),
+    ('\u{BE7D}', '\u{BE97}'),
+    ('\u{BE99}', '\u{BEB3}'),
+    ('\u{BEB5}', '\u{BECF}'),
+    ('\u{BED1}', '\u{BEEB}'),
+    ('\u{BEED}', '\u{BF07}'),
+    ('\u{BF09}', '\u{BF23}'),
+    ('\u{BF25}', '\u{BF3F}'),
+    ('\u{BF41}', '\u{BF5B}'),
+    ('\u{BF5D}', '\u{BF77}'),
+    ('\u{BF79}', '\u{BF93}'),
+    ('\u{BF95}', '\u{BFAF}'),
+    ('\u{BFB1}', '\u{BFCB}'),
+    ('\u{BFCD}', '\u{BFE7}'),
+    ('\u{BFE9}', '\u{C003}'),
+    ('\u{C005}', '\u{C01F}'),
+    ('\u{C021}', '\u{C03B}'),
+    ('\u{C03D}', '\u{C057}'),
+    ('\u{C059}', '\u{C073}'),
+    ('\u{C075}', '\u{C08F}'),
+    ('\u{C091}', '\u{C0AB}'),
+    ('\u{C0AD}', '\u{C0C7}'),
+    ('\u{C0C9}', '\u{C0E3}'),
+    ('\u{C0E5}', '\u{C0FF}'),
+    ('\u{C101}', '\u{C11B}'),
+    ('\u{C11D}', '\u{C137}'),
+    ('\u{C139}', '\u{C153}'),
+    ('\u{C155}', '\u{C16F}'),
+    ('\u{C171}', '\u{C18B}'),
+    ('\u{C18D}', '\u{C1A7}'),
+    ('\u{C1A9}', '\u{C1C3}'),
+    ('\u{C1C5}', '\u{C1DF}'),
+    ('\u{C1E1}', '\u{C1FB}'),
+    ('\u{C1FD}', '\u{C217}'),
+    ('\u{C219}', '\u{C233}'),
+    ('\u{C235}', '\u{C24F}'),
+    ('\u{C251}', '\u{C26B}'),
+    ('\u{C26D}', '\u{C287}'),
+    ('\u{C289}', '\u{C2A3}'),
+    ('\u{C2A5}', '\u{C2BF}'),
+    ('\u{C2C1}', '\u{C2DB}'),
+    ('\u{C2DD}', '\u{C2F7}'),
+    ('\u{C2F9}', '\u{C313}'),
+    ('\u{C315}', '\u{C32F}'),
+    ('\u{C331}', '\u{C34B}'),
+    ('\u{C34D}', '\u{C367}'),
+    ('\u{C369}', '\u{C383}'),
+    ('\u{C385}', '\u{C39F}'),
+    ('\u{C3A1}', '\u{C3BB}'),
+    ('\u{C3BD}', '\u{C3D7}'),
+    ('\u{C3D9}', '\u{C3F3}'),
+    ('\u{C3F5}', '\u{C40F}'),
+    ('\u{C411}', '\u{C42B}'),
+    ('\u{C42D}', '\u{C447}'),
+    ('\u{C449}', '\u{C463}'),
+    ('\u{C465}', '\u{C47F}'),
+    ('\u{C481}', '\u{C49B}'),
+    ('\u{C49D}', '\u{C4B7}'),
+    ('\u{C4B9}', '\u{C4D3}'),
+    ('\u{C4D5}', '\u{C4EF}'),
+    ('\u{C4F1}', '\u{C50B}'),
+    ('\u{C50D}', '\u{C527}'),
+    ('\u{C529}', '\u{C543}'),
+    ('\u{C545}', '\u{C55F}'),
+    ('\u{C561}', '\u{C57B}'),
+    ('\u{C57D}', '\u{C597}'),
+    ('\u{C599}', '\u{C5B3}'),
+    ('\u{C5B5}', '\u{C5CF}'),
+    ('\u{C5D1}', '\u{C5EB}'),
+    ('\u{C5ED}', '\u{C607}'),
+    ('\u{C609}', '\u{C623}'),
+    ('\u{C625}', '\u{C63F}'),
+    ('\u{C641}', '\u{C65B}'),
+    ('\u{C65D}', '\u{C677}'),
+    ('\u{C679}', '\u{C693}'),
+    ('\u{C695}', '\u{C6AF}'),
+    ('\u{C6B1}', '\u{C6CB}'),
+    ('\u{C6CD}', '\u{C6E7}'),
+    ('\u{C6E9}', '\u{C703}'),
+    ('\u{C705}', '\u{C71F}'),
+    ('\u{C721}', '\u{C73B}'),
+    ('\u{C73D}', '\u{C757}'),
+    ('\u{C759}', '\u{C773}'),
+    ('\u{C775}', '\u{C78F}'),
+    ('\u{C791}', '\u{C7AB}'),
+    ('\u{C7AD}', '\u{C7C7}'),
+    ('\u{C7C9}', '\u{C7E3}'),
+    ('\u{C7E5}', '\u{C7FF}'),
+    ('\u{C801}', '\u{C81B}'),
+    ('\u{C81D}', '\u{C837}'),
+    ('\u{C839}', '\u{C853}'),
+    ('\u{C855}', '\u{C86F}'),
+    ('\u{C871}', '\u{C88B}'),
+    ('\u{C88D}', '\u{C8A7}'),
+    ('\u{C8A9}', '\u{C8C3}'),
+    ('\u{C8C5}', '\u{C8DF}'),
+    ('\u{C8E1}', '\u{C8FB}'),
+    ('\u{C8FD}', '\u{C917}'),
+    ('\u{C919}', '\u{C933}'),
+    ('\u{C935}', '\u{C94F}'),
+    ('\u{C951}', '\u{C96B}'),
+    ('\u{C96D}', '\u{C987}'),
+    ('\u{C989}', '\u{C9A3}'),
+    ('\u{C9A5}', '\u{C9BF}'),
+    ('\u{C9C1}', '\u{C9DB}'),
+    ('\u{C9DD}', '\u{C9F7}'),
+    ('\u{C9F9}', '\u{CA13}'),
+    ('\u{CA15}', '\u{CA2F}'),
+    ('\u{CA31}', '\u{CA4B}'),
+    ('\u{CA4D}', '\u{CA67}'),
+    ('\u{CA69}', '\u{CA83}'),
+    ('\u{CA85}', '\u{CA9F}'),
+    ('\u{CAA1}', '\u{CABB}'),
+    ('\u{CABD}', '\u{CAD7}'),
+    ('\u{CAD9}', '\u{CAF3}'),
+    ('\u{CAF5}', '\u{CB0F}'),
+    ('\u{CB11}', '\u{CB2B}'),
+    ('\u{CB2D}', '\u{CB47}'),
+    ('\u{CB49}', '\u{CB63}'),
+    ('\u{CB65}', '\u{CB7F}'),
+    ('\u{CB81}', '\u{CB9B}'),
+    ('\u{CB9D}', '\u{CBB7}'),
+    ('\u{CBB9}', '\u{CBD3}'),
+    ('\u{CBD5}', '\u{CBEF}'),
+    ('\u{CBF1}', '\u{CC0B}'),
+    ('\u{CC0D}', '\u{CC27}'),
+    ('\u{CC29}', '\u{CC43}'),
+    ('\u{CC45}', '\u{CC5F}'),
+    ('\u{CC61}', '\u{CC7B}'),
+    ('\u{CC7D}', '\u{CC97}'),
+    ('\u{CC99}', '\u{CCB3}'),
+    ('\u{CCB5}', '\u{CCCF}'),
+    ('\u{CCD1}', '\u{CCEB}'),
+    ('\u{CCED}', '\u{CD07}'),
+    ('\u{CD09}', '\u{CD23}'),
+    ('\u{CD25}', '\u{CD3F}'),
+    ('\u{CD41}', '\u{CD5B}'),
+    ('\u{CD5D}', '\u{CD77}'),
+    ('\u{CD79}', '\u{CD93}'),
+    ('\u{CD95}', '\u{CDAF}'),
+    ('\u{CDB1}', '\u{CDCB}'),
+    ('\u{CDCD}', '\u{CDE7}'),
+    ('\u{CDE9}', '\u{CE03}'),
+    ('\u{CE05}', '\u{CE1F}'),
+    ('\u{CE21}', '\u{CE3B}'),
+    ('\u{CE3D}', '\u{CE57}'),
+    ('\u{CE59}', '\u{CE73}'),
+    ('\u{CE75}', '\u{CE8F}'),
+    ('\u{CE91}', '\u{CEAB}'),
+    ('\u{CEAD}', '\u{CEC7}'),
+    ('\u{CEC9}', '\u{CEE3}'),
+    ('\u{CEE5}', '\u{CEFF}'),
+    ('\u{CF01}', '\u{CF1B}'),
+    ('\u{CF1D}', '\u{CF37}'),
+    ('\u{CF39}', '\u{CF53}'),
+    ('\u{CF55}', '\u{CF6F}'),
+    ('\u{CF71}', '\u{CF8B}'),
+    ('\u{CF8D}', '\u{CFA7}'),
+    ('\u{CFA9}', '\u{CFC3}'),
+    ('\u{CFC5}', '\u{CFDF}'),
+    ('\u{CFE1}', '\u{CFFB}'),
+    ('\u{CFFD}', '\u{D017}'),
+    ('\u{D019}', '\u{D033}'),
+    ('\u{D035}', '\u{D04F}'),
+    ('\u{D051}', '\u{D06B}'),
+    ('\u{D06D}', '\u{D087}'),
+    ('\u{D089}', '\u{D0A3}'),
+    ('\u{D0A5}', '\u{D0BF}'),
+    ('\u{D0C1}', '\u{D0DB}'),
+    ('\u{D0DD}', '\u{D0F7}'),
+    ('\u{D0F9}', '\u{D113}'),
+    ('\u{D115}', '\u{D12F}'),
+    ('\u{D131}', '\u{D14B}'),
+    ('\u{D14D}', '\u{D167}'),
+    ('\u{D169}', '\u{D183}'),
+    ('\u{D185}', '\u{D19F}'),
+    ('\u{D1A1}', '\u{D1BB}'),
+    ('\u{D1BD}', '\u{D1D7}'),
+    ('\u{D1D9}', '\u{D1F3}'),
+    ('\u{D1F5}', '\u{D20F}'),
+    ('\u{D211}', '\u{D22B}'),
+    ('\u{D22D}', '\u{D247}'),
+    ('\u{D249}', '\u{D263}'),
+    ('\u{D265}', '\u{D27F}'),
+    ('\u{D281}', '\u{D29B}'),
+    ('\u{D29D}', '\u{D2B7}'),
+    ('\u{D2B9}', '\u{D2D3}'),
+    ('\u{D2D5}', '\u{D2EF}'),
+    ('\u{D2F1}', '\u{D30B}'),
+    ('\u{D30D}', '\u{D327}'),
+    ('\u{D329}', '\u{D343}'),
+    ('\u{D345}', '\u{D35F}'),
+    ('\u{D361}', '\u{D37B}'),
+    ('\u{D37D}', '\u{D397}'),
+    ('\u{D399}', '\u{D3B3}'),
+    ('\u{D3B5}', '\u{D3CF}'),
+    ('\u{D3D1}', '\u{D3EB}'),
+    ('\u{D3ED}', '\u{D407}'),
+    ('\u{D409}', '\u{D423}'),
+    ('\u{D425}', '\u{D43F}'),
+    ('\u{D441}', '\u{D45B}'),
+    ('\u{D45D}', '\u{D477}'),
+    ('\u{D479}', '\u{D493}'),
+    ('\u{D495}', '\u{D4AF}'),
+    ('\u{D4B1}', '\u{D4CB}'),
+    ('\u{D4CD}', '\u{D4E7}'),
+    ('\u{D4E9}', '\u{D503}'),
+    ('\u{D505}', '\u{D51F}'),
+    ('\u{D521}', '\u{D53B}'),
+    ('\u{D53D}', '\u{D557}'),
+    ('\u{D559}', '\u{D573}'),
+    ('\u{D575}', '\u{D58F}'),
+    ('\u{D591}', '\u{D5AB}'),
+    ('\u{D5AD}', '\u{D5C7}'),
+    ('\u{D5C9}', '\u{D5E3}'),
+    ('\u{D5E5}', '\u{D5FF}'),
+    ('\u{D601}', '\u{D61B}'),
+    ('\u{D61D}', '\u{D637}'),
+    ('\u{D639}', '\u{D653}'),
+    ('\u{D655}', '\u{D66F}'),
+    ('\u{D671}', '\u{D68B}'),
+    ('\u{D68D}', '\u{D6A7}'),
+    ('\u{D6A9}', '\u{D6C3}'),
+    ('\u{D6C5}', '\u{D6DF}'),
+    ('\u{D6E1}', '\u{D6FB}'),
+    ('\u{D6FD}', '\u{D717}'),
+    ('\u{D719}', '\u{D733}'),
+    ('\u{D735}', '\u{D74F}'),
+    ('\u{D751}', '\u{D76B}'),
+    ('\u{D76D}', '\u{D787}'),
+    ('\u{D789}', '\u{D7A3}'),
+];
+
+pub(super) static EXTENDED_PICTOGRAPHIC: &[(char, char)] = &[
+    ('\u{A9}', '\u{A9}'),
+    ('\u{AE}', '\u{AE}'),
+    ('\u{203C}', '\u{203C}'),
+    ('\u{2049}', '\u{2049}'),
+    ('\u{2122}', '\u{2122}'),
+    ('\u{2139}', '\u{2139}'),
+    ('\u{2194}', '\u{2199}'),
+    ('\u{21A9}', '\u{21AA}'),
+    ('\u{231A}', '\u{231B}'),
+    ('\u{2328}', '\u{2328}'),
+    ('\u{2388}', '\u{2388}'),
+    ('\u{23CF}', '\u{23CF}'),
+    ('\u{23E9}', '\u{23F3}'),
+    ('\u{23F8}', '\u{23FA}'),
+    ('\u{24C2}', '\u{24C2}'),
+    ('\u{25AA}', '\u{25AB}'),
+    ('\u{25B6}', '\u{25B6}'),
+    ('\u{25C0}', '\u{25C0}'),
+    ('\u{25FB}', '\u{25FE}'),
+    ('\u{2600}', '\u{2605}'),
+    ('\u{2607}', '\u{2612}'),
+    ('\u{2614}', '\u{2685}'),
+    ('\u{2690}', '\u{2705}'),
+    ('\u{2708}', '\u{2712}'),
+    ('\u{2714}', '\u{2714}'),
+    ('\u{2716}', '\u{2716}'),
+    ('\u{271D}', '\u{271D}'),
+    ('\u{2721}', '\u{2721}'),
+    ('\u{2728}', '\u{2728}'),
+    ('\u{2733}', '\u{2734}'),
+    ('\u{2744}', '\u{2744}'),
+    ('\u{2747}', '\u{2747}'),
+    ('\u{274C}', '\u{274C}'),
+    ('\u{274E}', '\u{274E}'),
+    ('\u{2753}', '\u{2755}'),
+    ('\u{2757}', '\u{2757}'),
+    ('\u{2763}', '\u{2767}'),
+    ('\u{2795}', '\u{2797}'),
+    ('\u{27A1}', '\u{27A1}'),
+    ('\u{27B0}', '\u{27B0}'),
+    ('\u{27BF}', '\u{27BF}'),
+    ('\u{2934}', '\u{2935}'),
+    ('\u{2B05}', '\u{2B07}'),
+    ('\u{2B1B}', '\u{2B1C}'),
+    ('\u{2B50}', '\u{2B50}'),
+    ('\u{2B55}', '\u{2B55}'),
+    ('\u{3030}', '\u{3030}'),
+    ('\u{303D}', '\u{303D}'),
+    ('\u{3297}', '\u{3297}'),
+    ('\u{3299}', '\u{3299}'),
+    ('\u{1F000}', '\u{1F0FF}'),
+    ('\u{1F10D}', '\u{1F10F}'),
+    ('\u{1F12F}', '\u{1F12F}'),
+    ('\u{1F16C}', '\u{1F171}'),
+    ('\u{1F17E}', '\u{1F17F}'),
+    ('\u{1F18E}', '\u{1F18E}'),
+    ('\u{1F191}', '\u{1F19A}'),
+    ('\u{1F1AD}', '\u{1F1E5}'),
+    ('\u{1F201}', '\u{1F20F}'),
+    ('\u{1F21A}', '\u{1F21A}'),
+    ('\u{1F22F}', '\u{1F22F}'),
+    ('\u{1F232}', '\u{1F23A}'),
+    ('\u{1F23C}', '\u{1F23F}'),
+    ('\u{1F249}', '\u{1F3FA}'),
+    ('\u{1F400}', '\u{1F53D}'),
+    ('\u{1F546}', '\u{1F64F}'),
+    ('\u{1F680}', '\u{1F6FF}'),
+    ('\u{1F774}', '\u{1F77F}'),
+    ('\u{1F7D5}', '\u{1F7FF}'),
+    ('\u{1F80C}', '\u{1F80F}'),
+    ('\u{1F848}', '\u{1F84F}'),
+    ('\u{1F85A}', '\u{1F85F}'),
+    ('\u{1F888}', '\u{1F88F}'),
+    ('\u{1F8AE}', '\u{1F8FF}'),
+    ('\u{1F90C}', '\u{1F93A}'),
+    ('\u{1F93C}', '\u{1F945}'),
+    ('\u{1F947}', '\u{1FAFF}'),
+    ('\u{1FC00}', '\u{1FFFD}'),
+];
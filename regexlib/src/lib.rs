@@ -1,4 +1,44 @@
 #![allow(unused)]
 
+pub mod analysis;
 pub mod automata;
+pub mod case_folding;
+pub mod diagram;
+pub mod general_category;
+mod glob;
+pub mod grapheme;
+mod like;
+pub mod line_terminator;
+pub mod normalization;
 pub mod parser;
+pub mod runtime;
+pub mod script;
+mod utf8;
+
+pub use glob::from_glob;
+pub use like::{from_ilike, from_like};
+pub use unic_ucd_version::UnicodeVersion;
+
+/// The version of the Unicode Character Database that regexlib's Unicode tables are generated
+/// from: the general categories of `\pL` and `\w`, scripts, grapheme clusters, case folding and
+/// normalization all come from the same version, so a character is classified the same way
+/// whichever property a pattern uses. The tables are compiled in, so this only changes when they
+/// are deliberately regenerated.
+pub const UNICODE_VERSION: UnicodeVersion = UnicodeVersion {
+    major: 16,
+    minor: 0,
+    micro: 0,
+};
+
+#[test]
+fn test_unicode_versions_agree() {
+    let (major, minor, micro) = unicode_normalization::UNICODE_VERSION;
+    assert_eq!(
+        UnicodeVersion {
+            major: major.into(),
+            minor: minor.into(),
+            micro: micro.into(),
+        },
+        UNICODE_VERSION
+    );
+}
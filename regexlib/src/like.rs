@@ -0,0 +1,134 @@
+use crate::parser::character_class::CharacterClass;
+use crate::parser::RegexEntry;
+
+/// Converts an SQL `LIKE` pattern into the regex matching the same strings, as a whole: `%`
+/// matches any run of characters, `_` matches any one character, and every other character
+/// matches itself. If `escape` is given (as by `LIKE ... ESCAPE '\'`), the character after it is
+/// matched literally, so that `\%` matches a `%`.
+pub fn from_like(pattern: &str, escape: Option<char>) -> Result<RegexEntry, String> {
+    convert(pattern, escape, false)
+}
+
+/// Like [`from_like`], but for SQL's case-insensitive `ILIKE`: letters also match their other
+/// case, if it is a single character (so `ß` does not match `SS`).
+pub fn from_ilike(pattern: &str, escape: Option<char>) -> Result<RegexEntry, String> {
+    convert(pattern, escape, true)
+}
+
+fn convert(
+    pattern: &str,
+    escape: Option<char>,
+    case_insensitive: bool,
+) -> Result<RegexEntry, String> {
+    let mut chars = pattern.chars();
+    let mut entries = Vec::new();
+
+    while let Some(char) = chars.next() {
+        let entry = match char {
+            escape_char if Some(escape_char) == escape => match chars.next() {
+                Some(escaped) => literal(escaped, case_insensitive),
+                None => {
+                    return Err(format!(
+                        "LIKE pattern ends with its escape character {escape_char}"
+                    ))
+                }
+            },
+            '%' => RegexEntry::Repetition {
+                base: Box::new(RegexEntry::AnyCharacter),
+                min: 0,
+                max: None,
+                lazy: false,
+            },
+            '_' => RegexEntry::AnyCharacter,
+            char => literal(char, case_insensitive),
+        };
+        entries.push(entry);
+    }
+
+    Ok(match entries.len() {
+        1 => entries.pop().unwrap(),
+        _ => RegexEntry::Concatenation(entries),
+    })
+}
+
+fn literal(char: char, case_insensitive: bool) -> RegexEntry {
+    if !case_insensitive {
+        return RegexEntry::Literal(char);
+    }
+
+    let mut cases = vec![char];
+    for other_case in [
+        single_char(char.to_lowercase()),
+        single_char(char.to_uppercase()),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if !cases.contains(&other_case) {
+            cases.push(other_case);
+        }
+    }
+
+    match cases.as_slice() {
+        [char] => RegexEntry::Literal(*char),
+        _ => RegexEntry::NonUnicodeCharacterClass(CharacterClass::Disjunction(
+            cases.into_iter().map(CharacterClass::Char).collect(),
+        )),
+    }
+}
+
+fn single_char(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    match (chars.next(), chars.next()) {
+        (Some(char), None) => Some(char),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{from_ilike, from_like};
+    use crate::runtime::{Limits, Regex};
+
+    fn like_matches(pattern: &str, escape: Option<char>, haystack: &str) -> bool {
+        let entry = from_like(pattern, escape).unwrap();
+        Regex::from_entry(pattern, entry, Limits::default())
+            .unwrap()
+            .is_match(haystack)
+    }
+
+    fn ilike_matches(pattern: &str, haystack: &str) -> bool {
+        let entry = from_ilike(pattern, None).unwrap();
+        Regex::from_entry(pattern, entry, Limits::default())
+            .unwrap()
+            .is_match(haystack)
+    }
+
+    #[test]
+    fn test_wildcards() {
+        assert!(like_matches("abc%", None, "abcdef"));
+        assert!(like_matches("%.log", None, "app.log"));
+        assert!(!like_matches("%.log", None, "app.logs"));
+        assert!(!like_matches("%.log", None, "appxlog"));
+        assert!(like_matches("a_c", None, "abc"));
+        assert!(!like_matches("a_c", None, "ac"));
+        assert!(like_matches("(%)", None, "(x)"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert!(like_matches("100\\%", Some('\\'), "100%"));
+        assert!(!like_matches("100\\%", Some('\\'), "1000"));
+        assert!(like_matches("a!_b", Some('!'), "a_b"));
+        assert!(!like_matches("a!_b", Some('!'), "axb"));
+        assert!(like_matches("a\\%", None, "a\\bc"));
+        assert!(from_like("abc\\", Some('\\')).is_err());
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert!(ilike_matches("abc%", "ABCdef"));
+        assert!(ilike_matches("Straße", "STRAßE"));
+        assert!(ilike_matches("_1", "x1"));
+        assert!(!like_matches("abc%", None, "ABCdef"));
+    }
+}
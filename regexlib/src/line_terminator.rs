@@ -0,0 +1,139 @@
+use std::str::FromStr;
+
+use crate::parser::character_class::CharacterClass;
+
+/// Which characters end a line: the ones `.` does not match without the `s` flag, the ones `\R`
+/// matches, and the ones the multi-line `^` and `$` match next to (see
+/// [`ParseOptions::line_terminator`](crate::parser::ParseOptions::line_terminator)).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineTerminator {
+    /// Only `\n`, as in text with Unix line endings. The `\r` of a `\r\n` is part of the line
+    /// before it, so `(?m-s)^.*$` matches it.
+    #[default]
+    Newline,
+    /// `\n` and `\r`, as in text with Windows line endings. A `\r\n` is one line terminator, so
+    /// neither `^` nor `$` matches between its two characters.
+    Crlf,
+    /// Every line break Unicode names: `\n`, `\v`, `\f`, `\r`, the next line control `\x85`, and
+    /// the line and paragraph separators `\u{2028}` and `\u{2029}` (i.e., what `\v` matches). A
+    /// `\r\n` is again one line terminator.
+    Unicode,
+}
+
+impl LineTerminator {
+    pub const ALL: [LineTerminator; 3] = [
+        LineTerminator::Newline,
+        LineTerminator::Crlf,
+        LineTerminator::Unicode,
+    ];
+
+    /// The name the terminator is selected by, e.g., `crlf`.
+    pub fn name(self) -> &'static str {
+        match self {
+            LineTerminator::Newline => "newline",
+            LineTerminator::Crlf => "crlf",
+            LineTerminator::Unicode => "unicode",
+        }
+    }
+
+    /// Whether `char` ends a line.
+    pub fn ends_line(self, char: char) -> bool {
+        match self {
+            LineTerminator::Newline => char == '\n',
+            LineTerminator::Crlf => matches!(char, '\n' | '\r'),
+            LineTerminator::Unicode => {
+                matches!(char, '\n'..='\r' | '\u{85}' | '\u{2028}' | '\u{2029}')
+            }
+        }
+    }
+
+    /// The class of the characters that end a line.
+    pub fn class(self) -> CharacterClass {
+        match self {
+            LineTerminator::Newline => CharacterClass::Char('\n'),
+            LineTerminator::Crlf => CharacterClass::Disjunction(vec![
+                CharacterClass::Char('\n'),
+                CharacterClass::Char('\r'),
+            ]),
+            LineTerminator::Unicode => CharacterClass::vertical_whitespace(),
+        }
+    }
+
+    /// Whether a `\r\n` is one line terminator, so that no line starts or ends between its two
+    /// characters.
+    pub fn joins_crlf(self) -> bool {
+        self != LineTerminator::Newline
+    }
+
+    /// Whether a multi-line `^` matches at byte `position` of `haystack`: at its start, or right
+    /// after a line terminator.
+    pub fn is_line_start(self, haystack: &str, position: usize) -> bool {
+        match haystack[..position].chars().next_back() {
+            None => true,
+            Some('\r') if self.joins_crlf() => !haystack[position..].starts_with('\n'),
+            Some(char) => self.ends_line(char),
+        }
+    }
+
+    /// Whether a multi-line `$` matches at byte `position` of `haystack`: at its end, or right
+    /// before a line terminator.
+    pub fn is_line_end(self, haystack: &str, position: usize) -> bool {
+        match haystack[position..].chars().next() {
+            None => true,
+            Some('\n') if self.joins_crlf() => !haystack[..position].ends_with('\r'),
+            Some(char) => self.ends_line(char),
+        }
+    }
+}
+
+impl FromStr for LineTerminator {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        LineTerminator::ALL
+            .into_iter()
+            .find(|terminator| terminator.name() == name)
+            .ok_or_else(|| {
+                format!("Unknown line terminator \"{name}\", expected \"newline\", \"crlf\" or \"unicode\"")
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LineTerminator;
+
+    #[test]
+    fn test_names() {
+        for terminator in LineTerminator::ALL {
+            assert_eq!(terminator.name().parse(), Ok(terminator));
+        }
+        assert!("lf".parse::<LineTerminator>().is_err());
+    }
+
+    #[test]
+    fn test_line_starts_and_ends() {
+        let haystack = "a\r\nb\rc\u{2028}d";
+        let starts = |terminator: LineTerminator| {
+            (0..=haystack.len())
+                .filter(|position| haystack.is_char_boundary(*position))
+                .filter(|position| terminator.is_line_start(haystack, *position))
+                .collect::<Vec<_>>()
+        };
+        let ends = |terminator: LineTerminator| {
+            (0..=haystack.len())
+                .filter(|position| haystack.is_char_boundary(*position))
+                .filter(|position| terminator.is_line_end(haystack, *position))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(starts(LineTerminator::Newline), vec![0, 3]);
+        assert_eq!(ends(LineTerminator::Newline), vec![2, 10]);
+        //nothing starts or ends between the `\r` and `\n` of a `\r\n`
+        assert_eq!(starts(LineTerminator::Crlf), vec![0, 3, 5]);
+        assert_eq!(ends(LineTerminator::Crlf), vec![1, 4, 10]);
+        assert_eq!(starts(LineTerminator::Unicode), vec![0, 3, 5, 9]);
+        assert_eq!(ends(LineTerminator::Unicode), vec![1, 4, 6, 10]);
+    }
+}
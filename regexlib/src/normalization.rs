@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::parser::RegexEntry;
+
+/// Converts `string` to Unicode Normalization Form C, borrowing it if it is already normalized.
+pub fn to_nfc(string: &str) -> Cow<'_, str> {
+    let normalized = string.nfc().collect::<String>();
+    if normalized == string {
+        Cow::Borrowed(string)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+/// Whether `string` is already in Unicode Normalization Form C.
+pub fn is_nfc(string: &str) -> bool {
+    string.nfc().eq(string.chars())
+}
+
+impl RegexEntry {
+    /// Converts the literals of the regex to Unicode Normalization Form C, so that the regex
+    /// matches NFC-normalized input when written with decomposed characters (or vice versa).
+    ///
+    /// Each run of consecutive literals is normalized as a whole, so a base character followed by
+    /// a literal combining mark is composed. Characters that are not plain literals (i.e., those
+    /// in character classes, or a combining mark under a repetition) are left as written.
+    pub fn normalize_literals_nfc(&mut self) {
+        match self {
+            RegexEntry::Literal(literal) => {
+                let mut normalized = normalize_literal_run(&[*literal]);
+                *self = if normalized.len() == 1 {
+                    normalized.remove(0)
+                } else {
+                    RegexEntry::Concatenation(normalized)
+                };
+            }
+            RegexEntry::Concatenation(entries) => {
+                let mut normalized = Vec::new();
+                let mut literal_run = Vec::new();
+
+                for mut entry in entries.drain(..) {
+                    if let RegexEntry::Literal(literal) = entry {
+                        literal_run.push(literal);
+                        continue;
+                    }
+
+                    normalized.append(&mut normalize_literal_run(&literal_run));
+                    literal_run.clear();
+
+                    entry.normalize_literals_nfc();
+                    normalized.push(entry);
+                }
+                normalized.append(&mut normalize_literal_run(&literal_run));
+
+                *entries = normalized;
+            }
+            //a folded literal is a run of literals already
+            RegexEntry::FoldedLiteral(literal) => *literal = to_nfc(literal).into_owned(),
+            RegexEntry::Alternation(entries) => {
+                for entry in entries {
+                    entry.normalize_literals_nfc();
+                }
+            }
+            RegexEntry::Repetition { base, .. } | RegexEntry::Capture { base, .. } => {
+                base.normalize_literals_nfc()
+            }
+            RegexEntry::AnyCharacter
+            | RegexEntry::UnicodeCharacterClass(_)
+            | RegexEntry::NegatedUnicodeCharacterClass(_)
+            | RegexEntry::UnicodeScript(_)
+            | RegexEntry::NegatedUnicodeScript(_)
+            | RegexEntry::GraphemeCluster
+            | RegexEntry::NonUnicodeCharacterClass(_)
+            | RegexEntry::Byte(_)
+            | RegexEntry::StartOfInput
+            | RegexEntry::EndOfInput
+            | RegexEntry::StartOfLine(_)
+            | RegexEntry::EndOfLine(_)
+            | RegexEntry::WordStart
+            | RegexEntry::WordEnd => {}
+        }
+    }
+}
+
+fn normalize_literal_run(literals: &[char]) -> Vec<RegexEntry> {
+    literals
+        .iter()
+        .copied()
+        .nfc()
+        .map(RegexEntry::Literal)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::normalization::{is_nfc, to_nfc};
+    use crate::parser::RegexEntry;
+
+    #[test]
+    fn test_to_nfc() {
+        assert_eq!(to_nfc("cafe\u{301}"), "caf\u{e9}");
+        assert!(is_nfc("caf\u{e9}"));
+        assert!(!is_nfc("cafe\u{301}"));
+    }
+
+    #[test]
+    fn test_normalize_literals() {
+        let mut decomposed = RegexEntry::parse("(cafe\u{301})+|x").unwrap();
+        decomposed.normalize_literals_nfc();
+
+        assert_eq!(decomposed, RegexEntry::parse("(caf\u{e9})+|x").unwrap());
+    }
+}
@@ -1,21 +1,203 @@
+use std::sync::OnceLock;
+
+use unic_ucd_category::GeneralCategory;
+
+use crate::general_category;
+use crate::parser::error::{LexError, ParseError, ParseErrorKind};
+use crate::parser::tokenizer::RegexToken;
+
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CharacterClass {
     Char(char),
-    Range { start: char, end: char },
+    Range {
+        start: char,
+        end: char,
+    },
     Disjunction(Vec<CharacterClass>),
     Negated(Box<CharacterClass>),
+    /// `[a-z&&[^aeiou]]`: the characters in both classes.
+    Intersection(Box<CharacterClass>, Box<CharacterClass>),
+    /// `[a-z--[aeiou]]`: the characters in the first class but not in the second.
+    Difference(Box<CharacterClass>, Box<CharacterClass>),
 }
 
 impl CharacterClass {
-    pub fn try_parse(mut remaining: &str) -> Result<Option<(CharacterClass, &str)>, String> {
+    /// The characters with Unicode's White_Space property, which `\s` matches.
+    pub fn whitespace() -> CharacterClass {
+        CharacterClass::Disjunction(vec![
+            CharacterClass::Range {
+                start: '\t',
+                end: '\r',
+            },
+            CharacterClass::Char(' '),
+            CharacterClass::Char('\u{85}'),
+            CharacterClass::Char('\u{A0}'),
+            CharacterClass::Char('\u{1680}'),
+            CharacterClass::Range {
+                start: '\u{2000}',
+                end: '\u{200A}',
+            },
+            CharacterClass::Char('\u{2028}'),
+            CharacterClass::Char('\u{2029}'),
+            CharacterClass::Char('\u{202F}'),
+            CharacterClass::Char('\u{205F}'),
+            CharacterClass::Char('\u{3000}'),
+        ])
+    }
+
+    /// The horizontal whitespace characters, which `\h` matches: the tab and the characters in the
+    /// Space_Separator general category (Unicode's Blank property).
+    pub fn horizontal_whitespace() -> CharacterClass {
+        CharacterClass::Disjunction(vec![
+            CharacterClass::Char('\t'),
+            CharacterClass::Char(' '),
+            CharacterClass::Char('\u{A0}'),
+            CharacterClass::Char('\u{1680}'),
+            CharacterClass::Range {
+                start: '\u{2000}',
+                end: '\u{200A}',
+            },
+            CharacterClass::Char('\u{202F}'),
+            CharacterClass::Char('\u{205F}'),
+            CharacterClass::Char('\u{3000}'),
+        ])
+    }
+
+    /// The vertical whitespace characters, which `\v` matches: the characters that end a line,
+    /// from `\n` to `\r`, the next line control `\x85`, and the line and paragraph separators.
+    pub fn vertical_whitespace() -> CharacterClass {
+        CharacterClass::Disjunction(vec![
+            CharacterClass::Range {
+                start: '\n',
+                end: '\r',
+            },
+            CharacterClass::Char('\u{85}'),
+            CharacterClass::Range {
+                start: '\u{2028}',
+                end: '\u{2029}',
+            },
+        ])
+    }
+
+    /// The POSIX class `[:name:]` (or its negation, `[:^name:]`, when `name` starts with `^`), like
+    /// `alpha` or `digit`. POSIX defines the classes for the C locale, and RE2 defines them the
+    /// same way, so they only contain ASCII characters. An unknown name is an
+    /// [`InvalidClass`](ParseErrorKind::InvalidClass) error spanning all of `name`.
+    pub fn posix_named(name: &str) -> Result<CharacterClass, ParseError> {
+        let whole_name = name;
+        let (negated, name) = match name.strip_prefix('^') {
+            Some(name) => (true, name),
+            None => (false, name),
+        };
+
+        let ranges: &[(char, char)] = match name {
+            "alnum" => &[('0', '9'), ('A', 'Z'), ('a', 'z')],
+            "alpha" => &[('A', 'Z'), ('a', 'z')],
+            "ascii" => &[('\0', '\x7F')],
+            "blank" => &[('\t', '\t'), (' ', ' ')],
+            "cntrl" => &[('\0', '\x1F'), ('\x7F', '\x7F')],
+            "digit" => &[('0', '9')],
+            "graph" => &[('!', '~')],
+            "lower" => &[('a', 'z')],
+            "print" => &[(' ', '~')],
+            "punct" => &[('!', '/'), (':', '@'), ('[', '`'), ('{', '~')],
+            "space" => &[('\t', '\r'), (' ', ' ')],
+            "upper" => &[('A', 'Z')],
+            "word" => &[('0', '9'), ('A', 'Z'), ('a', 'z'), ('_', '_')],
+            "xdigit" => &[('0', '9'), ('A', 'F'), ('a', 'f')],
+            name => {
+                return Err(ParseError::new(
+                    ParseErrorKind::InvalidClass,
+                    0..whole_name.len(),
+                    format!("[:{name}:] is not a known character class"),
+                ))
+            }
+        };
+
+        let class = CharacterClass::Disjunction(
+            ranges
+                .iter()
+                .map(|&(start, end)| {
+                    if start == end {
+                        CharacterClass::Char(start)
+                    } else {
+                        CharacterClass::Range { start, end }
+                    }
+                })
+                .collect(),
+        );
+        Ok(if negated {
+            CharacterClass::Negated(Box::new(class))
+        } else {
+            class
+        })
+    }
+
+    pub fn matches(&self, ch: char) -> bool {
+        match self {
+            CharacterClass::Char(class_char) => *class_char == ch,
+            CharacterClass::Range { start, end } => (*start..=*end).contains(&ch),
+            CharacterClass::Disjunction(classes) => classes.iter().any(|class| class.matches(ch)),
+            CharacterClass::Negated(class) => !class.matches(ch),
+            CharacterClass::Intersection(left, right) => left.matches(ch) && right.matches(ch),
+            CharacterClass::Difference(left, right) => left.matches(ch) && !right.matches(ch),
+        }
+    }
+
+    //the characters the class matches, in codepoint order, or `None` if there are more than `limit` of them (or they are not simple to tell, as for a negated class)
+    pub(crate) fn members(&self, limit: usize) -> Option<Vec<char>> {
+        let mut members = match self {
+            CharacterClass::Char(ch) => vec![*ch],
+            CharacterClass::Range { start, end } => {
+                if (*end as u32).saturating_sub(*start as u32) >= limit as u32 {
+                    return None;
+                }
+                (*start..=*end).collect()
+            }
+            CharacterClass::Disjunction(classes) => {
+                let mut members = Vec::new();
+                for class in classes {
+                    members.extend(class.members(limit)?);
+                }
+                members
+            }
+            CharacterClass::Negated(_)
+            | CharacterClass::Intersection(_, _)
+            | CharacterClass::Difference(_, _) => return None,
+        };
+        members.sort();
+        members.dedup();
+        (members.len() <= limit).then_some(members)
+    }
+
+    /// Parses a bracketed class like `[a-z_]` from the start of `remaining`. Its members may be
+    /// bracketed classes of their own, so `[[a-z][0-9]_]` is the union of the three, or shorthands
+    /// like `\d` and properties like `\p{Greek}`, which match the Unicode characters they match
+    /// outside of brackets. The span of an error is a byte range of `remaining`.
+    pub fn try_parse(remaining: &str) -> Result<Option<(CharacterClass, &str)>, ParseError> {
+        Self::try_parse_bracketed(remaining, true).map_err(|error| error.in_pattern(remaining))
+    }
+
+    //`unicode` is the option of the same name, which decides what the shorthands in the class match
+    pub(super) fn try_parse_bracketed(
+        mut remaining: &str,
+        unicode: bool,
+    ) -> Result<Option<(CharacterClass, &str)>, LexError> {
         if remaining.chars().nth(0) == Some('[') {
             remaining = &remaining[1..];
-            match Self::try_parse_no_prefix(remaining)? {
+            //only a `^` right after the opening bracket negates the class, and anywhere else it is a member
+            let parsed = match Self::try_parse_negated(remaining, unicode)? {
+                Some(negated) => Some(negated),
+                None => Self::try_parse_no_prefix(remaining, unicode)?,
+            };
+            match parsed {
                 Some((parsed, remaining)) => {
                     if !remaining.starts_with(']') {
-                        return Err(format!(
-                            "Expected ] after character class, found {}",
-                            remaining
+                        return Err(LexError::new(
+                            ParseErrorKind::InvalidClass,
+                            format!("Expected ] after character class, found {}", remaining),
+                            remaining,
                         ));
                     }
                     Ok(Some((parsed, &remaining[1..])))
@@ -27,32 +209,96 @@ impl CharacterClass {
         }
     }
 
-    fn try_parse_no_prefix(mut remaining: &str) -> Result<Option<(CharacterClass, &str)>, String> {
+    //set operations are left-associative and all bind the same, so `[a-z--[aeiou]&&[a-m]]` is the consonants up to m
+    fn try_parse_no_prefix(
+        remaining: &str,
+        unicode: bool,
+    ) -> Result<Option<(CharacterClass, &str)>, LexError> {
+        let (mut class, mut remaining) = Self::parse_union(remaining, unicode)?;
+        while let Some((operator, after_operator)) = Self::try_parse_set_operator(remaining) {
+            if class == CharacterClass::Disjunction(Vec::new()) {
+                return Err(LexError::between(
+                    ParseErrorKind::InvalidClass,
+                    format!("Expected a class before {operator} in character class"),
+                    remaining,
+                    after_operator,
+                ));
+            }
+
+            let (operand, after_operand) = Self::parse_union(after_operator, unicode)?;
+            if operand == CharacterClass::Disjunction(Vec::new()) {
+                return Err(LexError::between(
+                    ParseErrorKind::InvalidClass,
+                    format!("Expected a class after {operator} in character class"),
+                    remaining,
+                    after_operator,
+                ));
+            }
+
+            class = match operator {
+                "&&" => CharacterClass::Intersection(Box::new(class), Box::new(operand)),
+                _ => CharacterClass::Difference(Box::new(class), Box::new(operand)),
+            };
+            remaining = after_operand;
+        }
+
+        Ok(Some((class, remaining)))
+    }
+
+    fn try_parse_set_operator(remaining: &str) -> Option<(&'static str, &str)> {
+        ["&&", "--"].into_iter().find_map(|operator| {
+            remaining
+                .strip_prefix(operator)
+                .map(|remaining| (operator, remaining))
+        })
+    }
+
+    //the members of a class up to its closing bracket or a set operation
+    fn parse_union(mut remaining: &str, unicode: bool) -> Result<(CharacterClass, &str), LexError> {
         let mut disjuncton = Vec::new();
 
         loop {
-            if remaining.starts_with(']') {
+            if remaining.starts_with(']') || Self::try_parse_set_operator(remaining).is_some() {
                 break;
-            } else if let Some((class, new_remaining)) = Self::try_parse_single_class(remaining)? {
+            } else if let Some((class, new_remaining)) =
+                Self::try_parse_single_class(remaining, unicode)?
+            {
                 disjuncton.push(class);
                 remaining = new_remaining;
             } else {
-                return Err(format!(
-                    "Failed to parse remaining character class pattern from start of string: {}",
-                    remaining
+                return Err(LexError::new(
+                    ParseErrorKind::InvalidClass,
+                    "Pattern ends inside of a character class, which must be closed with ]",
+                    remaining,
                 ));
             }
         }
 
         if disjuncton.len() == 1 {
-            Ok(Some((disjuncton.into_iter().nth(0).unwrap(), remaining)))
+            Ok((disjuncton.into_iter().nth(0).unwrap(), remaining))
         } else {
-            Ok(Some((CharacterClass::Disjunction(disjuncton), remaining)))
+            Ok((CharacterClass::Disjunction(disjuncton), remaining))
         }
     }
 
-    fn try_parse_single_class(remaining: &str) -> Result<Option<(CharacterClass, &str)>, String> {
-        if let Some(tuple) = Self::try_parse_negated(remaining)? {
+    fn try_parse_single_class(
+        remaining: &str,
+        unicode: bool,
+    ) -> Result<Option<(CharacterClass, &str)>, LexError> {
+        if let Some(tuple) = Self::try_parse_posix_named(remaining)? {
+            return Ok(Some(tuple));
+        }
+
+        //a class nested in brackets of its own, like `[a-z]` in `[[a-z][0-9]_]`
+        if let Some(tuple) = Self::try_parse_bracketed(remaining, unicode)? {
+            return Ok(Some(tuple));
+        }
+
+        if let Some(tuple) = Self::try_parse_shorthand(remaining, unicode) {
+            return Ok(Some(tuple));
+        }
+
+        if let Some(tuple) = Self::try_parse_property(remaining)? {
             return Ok(Some(tuple));
         }
 
@@ -67,9 +313,121 @@ impl CharacterClass {
         Ok(None)
     }
 
-    fn try_parse_negated(remaining: &str) -> Result<Option<(CharacterClass, &str)>, String> {
+    //`[:alpha:]` and the like; anything else starting with `[:` is left to be read as characters, as it always was
+    fn try_parse_posix_named(remaining: &str) -> Result<Option<(CharacterClass, &str)>, LexError> {
+        let Some(named) = remaining.strip_prefix("[:") else {
+            return Ok(None);
+        };
+        let Some(end) = named.find(":]") else {
+            return Ok(None);
+        };
+        let name = &named[..end];
+        if !name
+            .trim_start_matches('^')
+            .chars()
+            .all(|char| char.is_ascii_alphabetic())
+        {
+            return Ok(None);
+        }
+
+        let after = &named[end + 2..];
+        let class = Self::posix_named(name)
+            .map_err(|error| LexError::between(error.kind, error.message, remaining, after))?;
+        Ok(Some((class, after)))
+    }
+
+    //`\d`, `\w`, `\s` and their negations, which match the same characters as they do outside of brackets
+    fn try_parse_shorthand(remaining: &str, unicode: bool) -> Option<(CharacterClass, &str)> {
+        let mut chars = remaining.strip_prefix('\\')?.chars();
+        let escaped = chars.next()?;
+        let class = match (escaped.to_ascii_lowercase(), unicode) {
+            ('d', true) => Self::with_categories(&RegexToken::DIGIT_CATEGORIES),
+            ('w', true) => Self::with_categories(&RegexToken::WORD_CATEGORIES),
+            ('s', true) => CharacterClass::whitespace(),
+            ('d', false) => Self::posix_named("digit").unwrap(),
+            ('w', false) => Self::posix_named("word").unwrap(),
+            ('s', false) => Self::posix_named("space").unwrap(),
+            _ => return None,
+        };
+
+        Some((
+            if escaped.is_uppercase() {
+                CharacterClass::Negated(Box::new(class))
+            } else {
+                class
+            },
+            chars.as_str(),
+        ))
+    }
+
+    //`\p{Greek}`, `\pL` and their negations, which match the same characters as they do outside of brackets
+    fn try_parse_property(remaining: &str) -> Result<Option<(CharacterClass, &str)>, LexError> {
+        let Some((token, remaining)) = RegexToken::try_parse_unicode_property(remaining)? else {
+            return Ok(None);
+        };
+
+        let class = match token {
+            RegexToken::UnicodeCharacterClass(categories) => Self::with_categories(&categories),
+            RegexToken::NegatedUnicodeCharacterClass(categories) => {
+                CharacterClass::Negated(Box::new(Self::with_categories(&categories)))
+            }
+            RegexToken::UnicodeScript(script) => Self::from_ranges(script.ranges().to_vec()),
+            RegexToken::NegatedUnicodeScript(script) => {
+                CharacterClass::Negated(Box::new(Self::from_ranges(script.ranges().to_vec())))
+            }
+            _ => unreachable!("only Unicode properties are parsed here"),
+        };
+        Ok(Some((class, remaining)))
+    }
+
+    //the characters in general categories, as ranges; finding them takes a pass over every character, so it is only done once for the categories of `\d` and `\w`
+    pub(crate) fn with_categories(categories: &[GeneralCategory]) -> CharacterClass {
+        static DIGITS: OnceLock<CharacterClass> = OnceLock::new();
+        static WORD: OnceLock<CharacterClass> = OnceLock::new();
+        let find = || {
+            let mut ranges = Vec::<(char, char)>::new();
+            for char in (0..=char::MAX as u32).filter_map(char::from_u32) {
+                if !categories.contains(&general_category::of(char)) {
+                    continue;
+                }
+                match ranges.last_mut() {
+                    Some((_, end)) if *end as u32 + 1 == char as u32 => *end = char,
+                    _ => ranges.push((char, char)),
+                }
+            }
+            Self::from_ranges(ranges)
+        };
+
+        if categories == RegexToken::DIGIT_CATEGORIES {
+            DIGITS.get_or_init(find).clone()
+        } else if categories == RegexToken::WORD_CATEGORIES {
+            WORD.get_or_init(find).clone()
+        } else {
+            find()
+        }
+    }
+
+    pub(crate) fn from_ranges(ranges: Vec<(char, char)>) -> CharacterClass {
+        CharacterClass::Disjunction(
+            ranges
+                .into_iter()
+                .map(|(start, end)| {
+                    if start == end {
+                        CharacterClass::Char(start)
+                    } else {
+                        CharacterClass::Range { start, end }
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    fn try_parse_negated(
+        remaining: &str,
+        unicode: bool,
+    ) -> Result<Option<(CharacterClass, &str)>, LexError> {
         if remaining.chars().nth(0) == Some('^') {
-            match Self::try_parse_no_prefix(&remaining[1..])? {
+            match Self::try_parse_no_prefix(&remaining[1..], unicode)? {
                 Some((to_negate, remaining)) => Ok(Some((
                     CharacterClass::Negated(Box::new(to_negate)),
                     remaining,
@@ -81,34 +439,72 @@ impl CharacterClass {
         }
     }
 
-    fn try_parse_range(remaining: &str) -> Result<Option<(CharacterClass, &str)>, String> {
-        if remaining.len() < 3 {
+    fn try_parse_range(remaining: &str) -> Result<Option<(CharacterClass, &str)>, LexError> {
+        let Some((start, after_start)) = Self::try_parse_member_char(remaining)? else {
+            return Ok(None);
+        };
+        //`--` is a difference rather than a range ending in a `-`
+        if after_start.starts_with("--") {
             return Ok(None);
         }
-
-        if remaining.chars().nth(1) != Some('-') {
+        let Some(after_dash) = after_start.strip_prefix('-') else {
+            return Ok(None);
+        };
+        //a `-` right before the closing bracket is a member, as in `[+-]`
+        if after_dash.starts_with(']') {
+            return Ok(None);
+        }
+        let Some((end, after_end)) = Self::try_parse_member_char(after_dash)? else {
             return Ok(None);
+        };
+
+        //an inverted range contains no characters, which is almost always a typo for the swapped range
+        if start > end {
+            return Err(LexError::between(
+                ParseErrorKind::InvalidClass,
+                format!("Character class range {start}-{end} is inverted, so it can never match; did you mean {end}-{start}?"),
+                remaining,
+                after_end,
+            ));
         }
 
-        //won't panic since we checked if the length is < 3
-        let start = remaining.chars().nth(0).unwrap();
-        let end = remaining.chars().nth(2).unwrap();
+        Ok(Some((CharacterClass::Range { start, end }, after_end)))
+    }
 
-        Ok(Some((
-            CharacterClass::Range { start, end },
-            &remaining[3..],
-        )))
+    fn try_parse_simple_char(remaining: &str) -> Result<Option<(CharacterClass, &str)>, LexError> {
+        Ok(Self::try_parse_member_char(remaining)?
+            .map(|(char, remaining)| (CharacterClass::Char(char), remaining)))
     }
 
-    fn try_parse_simple_char(remaining: &str) -> Result<Option<(CharacterClass, &str)>, String> {
-        if remaining.len() < 1 {
-            return Ok(None);
+    //a single character, either as it is or as an escape like `\t`, `\x1F` or `\]`
+    fn try_parse_member_char(remaining: &str) -> Result<Option<(char, &str)>, LexError> {
+        if let Some(tuple) = RegexToken::try_parse_char_escape(remaining)? {
+            return Ok(Some(tuple));
+        }
+
+        //anything but a letter or digit can be escaped, so that `]`, `-`, `^`, `\` and the like are members rather than syntax; as outside of brackets, an unknown escape of a letter is rejected
+        if let Some(escaped) = remaining.strip_prefix('\\') {
+            let mut chars = escaped.chars();
+            return match chars.next() {
+                Some(char) if !char.is_alphanumeric() => Ok(Some((char, chars.as_str()))),
+                Some(char) => Err(LexError::between(
+                    ParseErrorKind::Unsupported,
+                    format!("\\{char} is not a known escape in a character class"),
+                    remaining,
+                    chars.as_str(),
+                )),
+                None => Err(LexError::new(
+                    ParseErrorKind::InvalidEscape,
+                    "Unfinished escape in character class",
+                    remaining,
+                )),
+            };
         }
 
-        Ok(Some((
-            CharacterClass::Char(remaining.chars().nth(0).unwrap()),
-            &remaining[1..],
-        )))
+        Ok(remaining
+            .chars()
+            .next()
+            .map(|char| (char, &remaining[char.len_utf8()..])))
     }
 }
 
@@ -175,6 +571,25 @@ fn test_range() {
     );
 }
 
+#[test]
+fn test_inverted_range() {
+    let error = CharacterClass::try_parse("[a-z9-0]").unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::InvalidClass);
+    assert_eq!(error.span, 4..7);
+    assert_eq!(
+        error.message,
+        "Character class range 9-0 is inverted, so it can never match; did you mean 0-9?"
+    );
+
+    assert_eq!(
+        test_parse("[a-a]").unwrap(),
+        CharacterClass::Range {
+            start: 'a',
+            end: 'a',
+        }
+    );
+}
+
 #[test]
 fn test_double_range() {
     assert_eq!(
@@ -202,3 +617,289 @@ fn test_negated_range() {
         }))
     );
 }
+
+#[test]
+fn test_codepoint_escapes() {
+    assert_eq!(
+        test_parse("[\\x00-\\x1F\\u{7F}]").unwrap(),
+        CharacterClass::Disjunction(vec![
+            CharacterClass::Range {
+                start: '\0',
+                end: '\x1F',
+            },
+            CharacterClass::Char('\x7F'),
+        ])
+    );
+    assert!(CharacterClass::try_parse("[\\u{110000}]").is_err());
+}
+
+#[test]
+fn test_control_escapes() {
+    assert_eq!(
+        test_parse("[^\\t\\r\\n]").unwrap(),
+        CharacterClass::Negated(Box::new(CharacterClass::Disjunction(vec![
+            CharacterClass::Char('\t'),
+            CharacterClass::Char('\r'),
+            CharacterClass::Char('\n'),
+        ])))
+    );
+    assert_eq!(
+        test_parse("[\\0-\\f]").unwrap(),
+        CharacterClass::Range {
+            start: '\0',
+            end: '\x0C',
+        }
+    );
+}
+
+#[test]
+fn test_non_ascii() {
+    assert_eq!(
+        test_parse("[é-ü€]").unwrap(),
+        CharacterClass::Disjunction(vec![
+            CharacterClass::Range {
+                start: 'é',
+                end: 'ü',
+            },
+            CharacterClass::Char('€'),
+        ])
+    );
+}
+
+#[test]
+fn test_posix_named() {
+    let alpha_or_underscore = test_parse("[[:alpha:]_]").unwrap();
+    for char in ['a', 'Z', '_'] {
+        assert!(alpha_or_underscore.matches(char));
+    }
+    for char in ['0', 'é', ':'] {
+        assert!(!alpha_or_underscore.matches(char));
+    }
+
+    let not_digit = test_parse("[^[:digit:]]").unwrap();
+    assert!(!not_digit.matches('5'));
+    assert!(not_digit.matches('x'));
+    assert_eq!(test_parse("[[:^digit:]]").unwrap(), not_digit);
+
+    let space = test_parse("[[:space:]]").unwrap();
+    assert!(space.matches('\t'));
+    assert!(!space.matches('\u{A0}'));
+
+    //what does not look like a named class is a nested class of characters
+    assert_eq!(
+        test_parse("[[:x]]").unwrap(),
+        CharacterClass::Disjunction(vec![CharacterClass::Char(':'), CharacterClass::Char('x')])
+    );
+    assert!(CharacterClass::try_parse("[[:alfa:]]").is_err());
+
+    let error = CharacterClass::posix_named("^alfa").unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::InvalidClass);
+    assert_eq!(error.span, 0..5);
+}
+
+#[test]
+fn test_intersection() {
+    let consonants = test_parse("[a-z&&[^aeiou]]").unwrap();
+    assert_eq!(
+        consonants,
+        CharacterClass::Intersection(
+            Box::new(CharacterClass::Range {
+                start: 'a',
+                end: 'z',
+            }),
+            Box::new(CharacterClass::Negated(Box::new(
+                CharacterClass::Disjunction(vec![
+                    CharacterClass::Char('a'),
+                    CharacterClass::Char('e'),
+                    CharacterClass::Char('i'),
+                    CharacterClass::Char('o'),
+                    CharacterClass::Char('u'),
+                ])
+            )))
+        )
+    );
+    assert!(consonants.matches('b'));
+    assert!(!consonants.matches('e'));
+    assert!(!consonants.matches('B'));
+
+    //the operand after && does not need brackets of its own
+    assert!(test_parse("[a-z&&b-y]").unwrap().matches('m'));
+    assert!(!test_parse("[a-z&&b-y]").unwrap().matches('z'));
+}
+
+#[test]
+fn test_difference() {
+    let not_digits = test_parse("[[:word:]--[0-9]]").unwrap();
+    assert!(not_digits.matches('_'));
+    assert!(not_digits.matches('x'));
+    assert!(!not_digits.matches('5'));
+
+    //operations apply from left to right
+    let consonants_to_m = test_parse("[a-z--[aeiou]&&[a-m]]").unwrap();
+    assert!(consonants_to_m.matches('b'));
+    assert!(!consonants_to_m.matches('a'));
+    assert!(!consonants_to_m.matches('n'));
+
+    //a negation covers the whole class
+    let negated = test_parse("[^a-z--x]").unwrap();
+    assert!(negated.matches('x'));
+    assert!(!negated.matches('y'));
+
+    assert!(CharacterClass::try_parse("[a-z--]").is_err());
+    assert!(CharacterClass::try_parse("[&&a]").is_err());
+}
+
+#[test]
+fn test_nested() {
+    assert_eq!(
+        test_parse("[[a-z][0-9]_]").unwrap(),
+        CharacterClass::Disjunction(vec![
+            CharacterClass::Range {
+                start: 'a',
+                end: 'z',
+            },
+            CharacterClass::Range {
+                start: '0',
+                end: '9',
+            },
+            CharacterClass::Char('_'),
+        ])
+    );
+
+    let not_vowel_or_digit = test_parse("[^[aeiou][[:digit:]]]").unwrap();
+    assert!(not_vowel_or_digit.matches('b'));
+    assert!(!not_vowel_or_digit.matches('e'));
+    assert!(!not_vowel_or_digit.matches('7'));
+
+    let consonant_or_digit = test_parse("[[a-z&&[^aeiou]]0-9]").unwrap();
+    assert!(consonant_or_digit.matches('b'));
+    assert!(consonant_or_digit.matches('7'));
+    assert!(!consonant_or_digit.matches('e'));
+
+    assert!(CharacterClass::try_parse("[[a-z]").is_err());
+}
+
+#[test]
+fn test_escapes() {
+    assert_eq!(
+        test_parse(r"[\]\-\\\n\^]").unwrap(),
+        CharacterClass::Disjunction(vec![
+            CharacterClass::Char(']'),
+            CharacterClass::Char('-'),
+            CharacterClass::Char('\\'),
+            CharacterClass::Char('\n'),
+            CharacterClass::Char('^'),
+        ])
+    );
+    assert_eq!(
+        test_parse(r"[\[-\]]").unwrap(),
+        CharacterClass::Range {
+            start: '[',
+            end: ']',
+        }
+    );
+    assert_eq!(
+        test_parse(r"[a\&&b]").unwrap(),
+        CharacterClass::Disjunction(vec![
+            CharacterClass::Char('a'),
+            CharacterClass::Char('&'),
+            CharacterClass::Char('&'),
+            CharacterClass::Char('b'),
+        ])
+    );
+
+    assert_eq!(test_parse(r"[\ ]").unwrap(), CharacterClass::Char(' '));
+
+    let error = CharacterClass::try_parse(r"[\q]").unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::Unsupported);
+    assert!(CharacterClass::try_parse(r"[a\").is_err());
+}
+
+#[test]
+fn test_caret_member() {
+    assert_eq!(
+        test_parse("[a^b]").unwrap(),
+        CharacterClass::Disjunction(vec![
+            CharacterClass::Char('a'),
+            CharacterClass::Char('^'),
+            CharacterClass::Char('b'),
+        ])
+    );
+}
+
+#[test]
+fn test_trailing_dash() {
+    assert_eq!(
+        test_parse("[a-]").unwrap(),
+        CharacterClass::Disjunction(vec![CharacterClass::Char('a'), CharacterClass::Char('-')])
+    );
+    assert_eq!(
+        test_parse("[+-]").unwrap(),
+        CharacterClass::Disjunction(vec![CharacterClass::Char('+'), CharacterClass::Char('-')])
+    );
+    assert_eq!(
+        test_parse("[a-z-]").unwrap(),
+        CharacterClass::Disjunction(vec![
+            CharacterClass::Range {
+                start: 'a',
+                end: 'z',
+            },
+            CharacterClass::Char('-'),
+        ])
+    );
+}
+
+#[test]
+fn test_shorthands() {
+    let digits = test_parse(r"[\d]").unwrap();
+    assert!(digits.matches('7'));
+    assert!(digits.matches('٣'));
+    assert!(!digits.matches('x'));
+
+    let word = test_parse(r"[\w.-]").unwrap();
+    for char in ['a', 'é', '_', '9', '.', '-'] {
+        assert!(word.matches(char), "{char}");
+    }
+    assert!(!word.matches(' '));
+
+    let letters = test_parse(r"[\w--\d]").unwrap();
+    assert!(letters.matches('a'));
+    assert!(!letters.matches('1'));
+
+    let not_space = test_parse(r"[\S]").unwrap();
+    assert!(not_space.matches('a'));
+    assert!(!not_space.matches('\u{A0}'));
+
+    let (ascii, _) = CharacterClass::try_parse_bracketed(r"[\w]", false)
+        .unwrap()
+        .unwrap();
+    assert!(ascii.matches('a'));
+    assert!(!ascii.matches('é'));
+}
+
+#[test]
+fn test_properties() {
+    let greek = test_parse(r"[\p{Greek}a]").unwrap();
+    for char in ['α', 'Ω', 'a'] {
+        assert!(greek.matches(char), "{char}");
+    }
+    assert!(!greek.matches('b'));
+
+    let letters = test_parse(r"[\pL\d]").unwrap();
+    assert!(letters.matches('é'));
+    assert!(letters.matches('5'));
+    assert!(!letters.matches('-'));
+
+    let not_letters = test_parse(r"[\P{L}x]").unwrap();
+    assert!(not_letters.matches('-'));
+    assert!(not_letters.matches('x'));
+    assert!(!not_letters.matches('y'));
+
+    let not_greek = test_parse(r"[\P{sc=Greek}]").unwrap();
+    assert!(not_greek.matches('a'));
+    assert!(!not_greek.matches('α'));
+
+    let error = RegexToken::parse(r"[\p{Klingon}]").unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::UnknownProperty);
+    assert_eq!(error.span, 1..12);
+}
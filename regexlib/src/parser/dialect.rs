@@ -0,0 +1,486 @@
+use std::str::FromStr;
+
+use crate::parser::character_class::CharacterClass;
+use crate::parser::error::{LexError, ParseErrorKind};
+use crate::parser::tokenizer::RegexToken;
+
+/// The syntax a pattern is written in. Patterns copied from another ecosystem keep their meaning
+/// when parsed in its dialect: each dialect's escapes, bracket expressions and special
+/// characters are translated to fastregex's, and what fastregex can not express (like word
+/// boundaries) is an error instead of being read as something else.
+///
+/// Matching is unaffected by the dialect: a pattern always has to match the whole input.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Dialect {
+    /// fastregex's own syntax, in which `\` only starts the `\d`, `\D`, `\p` and `\P` classes.
+    #[default]
+    Fastregex,
+    /// The syntax of RE2 and Go's `regexp` package. `\d`, `\w` and `\s` are ASCII-only, `.` does
+    /// not match `\n`, and a `{` that does not start a repetition is a literal.
+    Re2,
+    /// POSIX extended regular expressions, as used by `grep -E` and `regcomp` with `REG_EXTENDED`.
+    /// `\` only makes a special character literal, and is itself a literal inside brackets.
+    PosixEre,
+}
+
+impl Dialect {
+    pub const ALL: [Dialect; 3] = [Dialect::Fastregex, Dialect::Re2, Dialect::PosixEre];
+
+    /// The name the dialect is selected by, e.g., `posix-ere`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dialect::Fastregex => "fastregex",
+            Dialect::Re2 => "re2",
+            Dialect::PosixEre => "posix-ere",
+        }
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Dialect::ALL
+            .into_iter()
+            .find(|dialect| dialect.name() == name)
+            .ok_or_else(|| {
+                format!("Unknown regex dialect \"{name}\", expected \"fastregex\", \"re2\" or \"posix-ere\"")
+            })
+    }
+}
+
+/// Tokenizes what `dialect` reads differently from fastregex at the start of `remaining`, leaving
+/// everything else (like groups and quantifiers) to the common tokenizer.
+pub(super) fn try_parse_dialect_token(
+    remaining: &str,
+    dialect: Dialect,
+) -> Result<Option<(RegexToken, &str)>, LexError> {
+    if dialect == Dialect::Fastregex {
+        return Ok(None);
+    }
+
+    let mut chars = remaining.chars();
+    match (chars.next(), chars.next()) {
+        //RE2 has lazy quantifiers like fastregex, while POSIX leaves a quantifier after a quantifier undefined
+        (Some('*' | '+' | '?'), Some('?')) if dialect == Dialect::PosixEre => {
+            Err(LexError::between(
+                ParseErrorKind::Unsupported,
+                "Lazy quantifiers are not supported in the posix-ere dialect",
+                remaining,
+                chars.as_str(),
+            ))
+        }
+        //RE2 names groups, sets flags and writes groups that do not capture the same ways fastregex does, so those are left to the common tokenizer
+        (Some('('), Some('?')) if dialect == Dialect::Re2 => Ok(None),
+        (Some('('), Some('?')) => {
+            let message =
+                "Group flags and non-capturing groups are not supported in the posix-ere dialect";
+            Err(LexError::between(
+                ParseErrorKind::Unsupported,
+                message,
+                remaining,
+                chars.as_str(),
+            ))
+        }
+        (Some('{'), _) => parse_brace(remaining, dialect),
+        (Some('['), _) => {
+            let (class, remaining) = parse_bracket(remaining, dialect)?;
+            Ok(Some((
+                RegexToken::NonUnicodeCharacterClass(class),
+                remaining,
+            )))
+        }
+        (Some('\\'), Some('p' | 'P')) if dialect == Dialect::Re2 => Ok(None),
+        (Some('\\'), _) => {
+            let (class, remaining) = parse_escape(remaining, dialect)?;
+            let token = match class {
+                CharacterClass::Char(literal) => RegexToken::Literal(literal),
+                class => RegexToken::NonUnicodeCharacterClass(class),
+            };
+            Ok(Some((token, remaining)))
+        }
+        _ => Ok(None),
+    }
+}
+
+//a repetition like `{2,5}` is left to the common tokenizer; RE2 reads anything else as a literal `{`
+fn parse_brace(remaining: &str, dialect: Dialect) -> Result<Option<(RegexToken, &str)>, LexError> {
+    let end = remaining.find('}');
+    let is_repetition = end.is_some_and(|end| {
+        let inner = &remaining[1..end];
+        let (min, max) = inner.split_once(',').unwrap_or((inner, "0"));
+        is_number(min) && (max.is_empty() || is_number(max))
+    });
+
+    if is_repetition {
+        let after = &remaining[end.unwrap() + 1..];
+        if dialect == Dialect::PosixEre && after.starts_with('?') {
+            return Err(LexError::between(
+                ParseErrorKind::Unsupported,
+                "Lazy quantifiers are not supported in the posix-ere dialect",
+                remaining,
+                &after[1..],
+            ));
+        }
+        Ok(None)
+    } else if dialect == Dialect::Re2 {
+        Ok(Some((RegexToken::Literal('{'), &remaining[1..])))
+    } else {
+        Err(LexError::new(
+            ParseErrorKind::InvalidRepetition,
+            "A { must start a repetition like {2}, {2,} or {2,5}",
+            remaining,
+        ))
+    }
+}
+
+fn is_number(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|char| char.is_ascii_digit())
+}
+
+//an escape starting with its `\`; escapes of single characters are returned as a `Char`
+fn parse_escape(escape: &str, dialect: Dialect) -> Result<(CharacterClass, &str), LexError> {
+    let mut chars = escape[1..].chars();
+    let Some(escaped) = chars.next() else {
+        return Err(LexError::new(
+            ParseErrorKind::InvalidEscape,
+            "Pattern ends with an unfinished \\ escape",
+            escape,
+        ));
+    };
+    let rest = chars.as_str();
+
+    if escaped.is_ascii_punctuation() {
+        return Ok((CharacterClass::Char(escaped), rest));
+    }
+
+    if dialect == Dialect::PosixEre {
+        return Err(LexError::between(
+            ParseErrorKind::Unsupported,
+            format!("\\{escaped} is not defined in POSIX extended regular expressions; only special characters can be escaped"),
+            escape,
+            rest,
+        ));
+    }
+
+    let class = match escaped {
+        'd' | 'D' => perl_class(&[('0', '9')], escaped.is_uppercase()),
+        'w' | 'W' => perl_class(
+            &[('0', '9'), ('A', 'Z'), ('a', 'z'), ('_', '_')],
+            escaped.is_uppercase(),
+        ),
+        's' | 'S' => perl_class(
+            &[('\t', '\n'), ('\x0C', '\r'), (' ', ' ')],
+            escaped.is_uppercase(),
+        ),
+        'a' => CharacterClass::Char('\x07'),
+        'f' => CharacterClass::Char('\x0C'),
+        't' => CharacterClass::Char('\t'),
+        'n' => CharacterClass::Char('\n'),
+        'r' => CharacterClass::Char('\r'),
+        'v' => CharacterClass::Char('\x0B'),
+        'x' => return parse_hex_escape(escape, rest),
+        'b' | 'B' | 'A' | 'z' => {
+            return Err(LexError::between(
+                ParseErrorKind::Unsupported,
+                format!("The assertion \\{escaped} is not supported, since patterns always match the whole input"),
+                escape,
+                rest,
+            ))
+        }
+        escaped => {
+            let kind = if escaped.is_alphanumeric() {
+                ParseErrorKind::Unsupported
+            } else {
+                ParseErrorKind::InvalidEscape
+            };
+            return Err(LexError::between(
+                kind,
+                format!("\\{escaped} is not a supported escape"),
+                escape,
+                rest,
+            ))
+        }
+    };
+
+    Ok((class, rest))
+}
+
+fn perl_class(ranges: &[(char, char)], negated: bool) -> CharacterClass {
+    let class = CharacterClass::Disjunction(
+        ranges
+            .iter()
+            .map(|&(start, end)| CharacterClass::Range { start, end })
+            .collect(),
+    );
+
+    if negated {
+        CharacterClass::Negated(Box::new(class))
+    } else {
+        class
+    }
+}
+
+//`\x7F` or `\x{10FFFF}`, which `remaining` is the rest of after the `\x`
+fn parse_hex_escape<'a>(
+    escape: &str,
+    remaining: &'a str,
+) -> Result<(CharacterClass, &'a str), LexError> {
+    let invalid = |message: String, after: &str| {
+        LexError::between(ParseErrorKind::InvalidEscape, message, escape, after)
+    };
+
+    let (digits, rest) = match remaining.strip_prefix('{') {
+        Some(braced) => match braced.find('}') {
+            Some(end) => (&braced[..end], &braced[end + 1..]),
+            None => return Err(invalid("Unfinished \\x{...} escape".into(), "")),
+        },
+        None if remaining.len() >= 2 && remaining.is_char_boundary(2) => remaining.split_at(2),
+        None => {
+            return Err(invalid(
+                "A \\x escape needs two hexadecimal digits".into(),
+                "",
+            ))
+        }
+    };
+
+    u32::from_str_radix(digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .map(|char| (CharacterClass::Char(char), rest))
+        .ok_or_else(|| invalid(format!("\\x{{{digits}}} is not a character"), rest))
+}
+
+//a bracket expression, starting with its `[`
+fn parse_bracket(remaining: &str, dialect: Dialect) -> Result<(CharacterClass, &str), LexError> {
+    let remaining = &remaining[1..];
+    let (negated, mut remaining) = match remaining.strip_prefix('^') {
+        Some(remaining) => (true, remaining),
+        None => (false, remaining),
+    };
+    let mut members = Vec::new();
+
+    loop {
+        //a `]` right after the opening bracket is a member
+        if remaining.starts_with(']') && !members.is_empty() {
+            remaining = &remaining[1..];
+            break;
+        }
+
+        let member_start = remaining;
+        let (member, rest) = parse_bracket_member(remaining, dialect)?;
+        remaining = rest;
+
+        //a `-` before the closing bracket is a member, not a range
+        let member = match (member, remaining.strip_prefix('-')) {
+            (CharacterClass::Char(start), Some(after_dash)) if !after_dash.starts_with(']') => {
+                let (end, rest) = parse_bracket_member(after_dash, dialect)?;
+                let invalid = |message: String| {
+                    LexError::between(ParseErrorKind::InvalidClass, message, member_start, rest)
+                };
+                let CharacterClass::Char(end) = end else {
+                    return Err(invalid(
+                        "A range in brackets must end in a single character".into(),
+                    ));
+                };
+                if start > end {
+                    return Err(invalid(format!(
+                        "Character class range {start}-{end} is inverted, so it can never match; did you mean {end}-{start}?"
+                    )));
+                }
+                remaining = rest;
+                CharacterClass::Range { start, end }
+            }
+            (member, _) => member,
+        };
+        members.push(member);
+    }
+
+    let class = match members.len() {
+        1 => members.pop().unwrap(),
+        _ => CharacterClass::Disjunction(members),
+    };
+
+    Ok((
+        if negated {
+            CharacterClass::Negated(Box::new(class))
+        } else {
+            class
+        },
+        remaining,
+    ))
+}
+
+fn parse_bracket_member(
+    remaining: &str,
+    dialect: Dialect,
+) -> Result<(CharacterClass, &str), LexError> {
+    if let Some(named) = remaining.strip_prefix("[:") {
+        let Some(end) = named.find(":]") else {
+            return Err(LexError::between(
+                ParseErrorKind::InvalidClass,
+                "Unfinished [:name:] class",
+                remaining,
+                named,
+            ));
+        };
+        let after = &named[end + 2..];
+        let class = CharacterClass::posix_named(&named[..end])
+            .map_err(|error| LexError::between(error.kind, error.message, remaining, after))?;
+        return Ok((class, after));
+    }
+
+    if remaining.starts_with("[.") || remaining.starts_with("[=") {
+        return Err(LexError::between(
+            ParseErrorKind::Unsupported,
+            "Collating elements and equivalence classes are not supported",
+            remaining,
+            &remaining[2..],
+        ));
+    }
+
+    let mut chars = remaining.chars();
+    match chars.next() {
+        None => Err(LexError::new(
+            ParseErrorKind::InvalidClass,
+            "Pattern ends inside of brackets",
+            remaining,
+        )),
+        Some('\\') if dialect == Dialect::Re2 => {
+            if remaining[1..].starts_with(['p', 'P']) {
+                return Err(LexError::between(
+                    ParseErrorKind::Unsupported,
+                    "Unicode classes inside of brackets are not supported",
+                    remaining,
+                    &remaining[2..],
+                ));
+            }
+            parse_escape(remaining, dialect)
+        }
+        Some(char) => Ok((CharacterClass::Char(char), chars.as_str())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Dialect;
+    use crate::parser::character_class::CharacterClass;
+    use crate::parser::{ParseError, RegexEntry};
+
+    fn parse(pattern: &str, dialect: Dialect) -> Result<RegexEntry, ParseError> {
+        RegexEntry::parse_in_dialect(pattern, dialect)
+    }
+
+    fn matches(pattern: &str, dialect: Dialect, haystack: &str) -> bool {
+        let class = match parse(pattern, dialect).unwrap() {
+            RegexEntry::NonUnicodeCharacterClass(class) => class,
+            RegexEntry::Literal(literal) => CharacterClass::Char(literal),
+            entry => panic!("{pattern} did not parse to one class: {entry:?}"),
+        };
+        haystack.chars().all(|char| class.matches(char))
+    }
+
+    #[test]
+    fn test_names() {
+        for dialect in Dialect::ALL {
+            assert_eq!(dialect.name().parse(), Ok(dialect));
+        }
+        assert!("pcre".parse::<Dialect>().is_err());
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_eq!(
+            parse("a\\.b", Dialect::Re2),
+            Ok(RegexEntry::Concatenation(vec![
+                RegexEntry::Literal('a'),
+                RegexEntry::Literal('.'),
+                RegexEntry::Literal('b'),
+            ]))
+        );
+        assert_eq!(
+            parse("\\.", Dialect::PosixEre),
+            Ok(RegexEntry::Literal('.'))
+        );
+        assert_eq!(
+            parse("\\x41\\x{263A}\\t", Dialect::Re2),
+            parse("A☺\t", Dialect::Fastregex)
+        );
+        assert_eq!(
+            parse("\\Qa+\\E", Dialect::Re2),
+            parse("a\\+", Dialect::Fastregex)
+        );
+
+        assert!(matches("\\d", Dialect::Re2, "0123456789"));
+        assert!(!matches("\\d", Dialect::Re2, "٣"));
+        assert!(matches("\\W", Dialect::Re2, "-. "));
+        assert!(matches("\\s", Dialect::Re2, " \t\n\r\x0C"));
+        assert!(parse("\\pL", Dialect::Re2).is_ok());
+
+        assert!(parse("\\d", Dialect::PosixEre).is_err());
+        assert!(parse("\\Qa\\E", Dialect::PosixEre).is_err());
+        assert!(parse("\\b", Dialect::Re2).is_err());
+        assert!(parse("\\q", Dialect::Re2).is_err());
+        assert!(parse("a\\", Dialect::Re2).is_err());
+    }
+
+    #[test]
+    fn test_brackets() {
+        assert!(matches("[\\]\\d]", Dialect::Re2, "]5"));
+        assert!(matches("[]a-c]", Dialect::PosixEre, "]b"));
+        assert!(matches("[\\n]", Dialect::PosixEre, "\\n"));
+        assert!(matches("[[:alpha:]_-]", Dialect::PosixEre, "aZ_-"));
+        assert!(!matches("[[:alpha:]_-]", Dialect::PosixEre, "1"));
+        assert!(matches("[^[:digit:]]", Dialect::Re2, "x"));
+        assert!(!matches("[^[:digit:]]", Dialect::Re2, "1"));
+        assert!(matches("[[:^space:]]", Dialect::Re2, "x"));
+
+        assert!(parse("[z-a]", Dialect::Re2).is_err());
+        assert!(parse("[[:letter:]]", Dialect::PosixEre).is_err());
+        assert!(parse("[\\pL]", Dialect::Re2).is_err());
+        assert!(parse("[[.a.]]", Dialect::PosixEre).is_err());
+        assert!(parse("[ab", Dialect::Re2).is_err());
+    }
+
+    #[test]
+    fn test_special_characters() {
+        assert!(matches(".", Dialect::Re2, "a"));
+        assert!(!matches(".", Dialect::Re2, "\n"));
+        assert_eq!(parse("(?s).", Dialect::Re2), Ok(RegexEntry::AnyCharacter));
+        assert_eq!(
+            parse("(?-s).", Dialect::Fastregex),
+            parse(".", Dialect::Re2)
+        );
+        assert_eq!(parse(".", Dialect::PosixEre), Ok(RegexEntry::AnyCharacter));
+
+        assert_eq!(
+            parse("a{", Dialect::Re2),
+            parse("a", Dialect::Fastregex)
+                .map(|a| RegexEntry::Concatenation(vec![a, RegexEntry::Literal('{')]))
+        );
+        assert!(parse("a{,2}", Dialect::PosixEre).is_err());
+        assert!(parse("a{2,3}", Dialect::PosixEre).is_ok());
+
+        for flags in ["(?i)a", "(?:a)b", "(?i:a)b"] {
+            assert_eq!(parse(flags, Dialect::Re2), parse(flags, Dialect::Fastregex));
+        }
+        for dialect in [Dialect::Re2, Dialect::PosixEre] {
+            assert_eq!(parse("^a$", dialect), parse("^a$", Dialect::Fastregex));
+            assert_eq!(
+                parse("ab|cd", dialect),
+                Ok(RegexEntry::Alternation(vec![
+                    parse("ab", dialect).unwrap(),
+                    parse("cd", dialect).unwrap(),
+                ]))
+            );
+        }
+        for lazy in ["a*?", "a{2}?"] {
+            assert_eq!(parse(lazy, Dialect::Re2), parse(lazy, Dialect::Fastregex));
+            assert!(parse(lazy, Dialect::PosixEre).is_err(), "{lazy}");
+        }
+
+        assert!(parse("(?P<name>a)", Dialect::Re2).is_ok());
+        assert!(parse("(?<name>a)", Dialect::Re2).is_ok());
+        assert!(parse("(?P<name>a)", Dialect::PosixEre).is_err());
+    }
+}
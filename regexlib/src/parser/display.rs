@@ -0,0 +1,328 @@
+use std::fmt::{Display, Formatter, Write};
+
+use unic_char_property::EnumeratedCharProperty;
+use unic_ucd_category::GeneralCategory;
+
+use crate::case_folding::expand_folded_literal;
+use crate::parser::character_class::CharacterClass;
+use crate::parser::tokenizer::RegexToken;
+use crate::parser::RegexEntry;
+
+/// Writes the entry back out as a pattern. A parsed entry is written in a normalized form that
+/// parses back to the same entry (e.g., `a{0,}` is written as `a*`), with literal
+/// metacharacters escaped, inside classes as well as outside of them.
+///
+/// What a pattern can only nest inside a group, like an alternation inside a concatenation, is
+/// written inside a group that does not capture (e.g., `a(?:b|c)`). The entries that do not
+/// parse back the same are a [`FoldedLiteral`](RegexEntry::FoldedLiteral), which is written as
+/// the alternatives it matches (see [`expand_folded_literal`]), and the line anchors, which are
+/// written as `(?m:^)` and `(?m:$)` whatever their
+/// [`LineTerminator`](crate::line_terminator::LineTerminator), since the pattern alone does not
+/// say which case folding or line terminator to read it with. A [`Byte`](RegexEntry::Byte) is
+/// written in a `(?-u:...)` group, which only reads back as one in a pattern matched against bytes.
+impl Display for RegexEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegexEntry::AnyCharacter => f.write_char('.'),
+            RegexEntry::UnicodeCharacterClass(categories) => {
+                write_unicode_class(f, 'p', categories)
+            }
+            RegexEntry::NegatedUnicodeCharacterClass(categories) => {
+                write_unicode_class(f, 'P', categories)
+            }
+            RegexEntry::NonUnicodeCharacterClass(class) => write_class(f, class),
+            RegexEntry::Byte(class) => {
+                f.write_str("(?-u:")?;
+                write_class(f, class)?;
+                f.write_char(')')
+            }
+            RegexEntry::UnicodeScript(script) => write!(f, "\\p{{{}}}", script.name()),
+            RegexEntry::NegatedUnicodeScript(script) => write!(f, "\\P{{{}}}", script.name()),
+            RegexEntry::GraphemeCluster => f.write_str("\\X"),
+            RegexEntry::Literal(literal) if RegexToken::METACHARACTERS.contains(*literal) => {
+                write!(f, "\\{}", literal)
+            }
+            RegexEntry::Literal(literal) => write_char(f, *literal),
+            RegexEntry::FoldedLiteral(literal) => match expand_folded_literal(literal) {
+                expanded @ (RegexEntry::Concatenation(_) | RegexEntry::Alternation(_)) => {
+                    write!(f, "(?:{})", expanded)
+                }
+                expanded => write!(f, "{}", expanded),
+            },
+            RegexEntry::StartOfInput => f.write_char('^'),
+            RegexEntry::EndOfInput => f.write_char('$'),
+            RegexEntry::StartOfLine(_) => f.write_str("(?m:^)"),
+            RegexEntry::EndOfLine(_) => f.write_str("(?m:$)"),
+            RegexEntry::WordStart => f.write_str("\\b{start}"),
+            RegexEntry::WordEnd => f.write_str("\\b{end}"),
+            RegexEntry::Concatenation(entries) => entries
+                .iter()
+                .try_for_each(|entry| write_concatenation_member(f, entry)),
+            RegexEntry::Alternation(entries) => {
+                for (index, entry) in entries.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char('|')?;
+                    }
+                    write_alternation_member(f, entry)?;
+                }
+                Ok(())
+            }
+            RegexEntry::Repetition {
+                base,
+                min,
+                max,
+                lazy,
+            } => {
+                write_repetition_base(f, base)?;
+                match (min, max) {
+                    (0, None) => f.write_char('*')?,
+                    (1, None) => f.write_char('+')?,
+                    (0, Some(1)) => f.write_char('?')?,
+                    (min, None) => write!(f, "{{{},}}", min)?,
+                    (min, Some(max)) if min == max => write!(f, "{{{}}}", min)?,
+                    (min, Some(max)) => write!(f, "{{{},{}}}", min, max)?,
+                }
+                if *lazy {
+                    f.write_char('?')?;
+                }
+                Ok(())
+            }
+            RegexEntry::Capture {
+                name: Some(name),
+                base,
+                ..
+            } => write!(f, "(?P<{}>{})", name, base),
+            RegexEntry::Capture { base, .. } => write!(f, "({})", base),
+        }
+    }
+}
+
+//`|` binds loosest, so an alternation inside a concatenation needs a group of its own
+fn write_concatenation_member(f: &mut Formatter<'_>, entry: &RegexEntry) -> std::fmt::Result {
+    match entry {
+        RegexEntry::Alternation(_) => write!(f, "(?:{})", entry),
+        _ => write!(f, "{}", entry),
+    }
+}
+
+//a member of an alternation is everything up to the next `|`, so only an alternation nested in it needs a group to stay nested
+fn write_alternation_member(f: &mut Formatter<'_>, entry: &RegexEntry) -> std::fmt::Result {
+    match entry {
+        RegexEntry::Alternation(_) => write!(f, "(?:{})", entry),
+        _ => write!(f, "{}", entry),
+    }
+}
+
+//quantifiers bind tightest and can not be stacked, so what is repeated can only be a single item or a group
+fn write_repetition_base(f: &mut Formatter<'_>, entry: &RegexEntry) -> std::fmt::Result {
+    match entry {
+        RegexEntry::Concatenation(_)
+        | RegexEntry::Alternation(_)
+        | RegexEntry::Repetition { .. } => {
+            write!(f, "(?:{})", entry)
+        }
+        _ => write!(f, "{}", entry),
+    }
+}
+
+//`\s`, `\h` and `\v` are the only classes the tokenizer produces that brackets could not spell without their control characters
+fn write_class(f: &mut Formatter<'_>, class: &CharacterClass) -> std::fmt::Result {
+    let shorthands = [
+        ('s', CharacterClass::whitespace()),
+        ('h', CharacterClass::horizontal_whitespace()),
+        ('v', CharacterClass::vertical_whitespace()),
+    ];
+    for (letter, shorthand) in shorthands {
+        if *class == shorthand {
+            return write!(f, "\\{}", letter);
+        }
+        if *class == CharacterClass::Negated(Box::new(shorthand)) {
+            return write!(f, "\\{}", letter.to_ascii_uppercase());
+        }
+    }
+    write!(f, "[{}]", class)
+}
+
+//operations apply from left to right, so only a negation has to be kept from covering the operation it is the left side of
+fn write_left_operand(f: &mut Formatter<'_>, class: &CharacterClass) -> std::fmt::Result {
+    match class {
+        CharacterClass::Negated(_) => write!(f, "[{}]", class),
+        class => write!(f, "{}", class),
+    }
+}
+
+//the tokenizer produces one category (`\p{Lu}`), a one-letter group of them (`\pL`), the digits (`\d`) or the word characters (`\w`)
+fn write_unicode_class(
+    f: &mut Formatter<'_>,
+    escape: char,
+    categories: &[GeneralCategory],
+) -> std::fmt::Result {
+    use GeneralCategory::*;
+
+    if let [category] = categories {
+        return write!(f, "\\{}{{{}}}", escape, category.abbr_name());
+    }
+
+    if categories == [DecimalNumber, OtherNumber, LetterNumber] {
+        return write!(f, "\\{}", if escape == 'p' { 'd' } else { 'D' });
+    }
+
+    if categories == RegexToken::WORD_CATEGORIES {
+        return write!(f, "\\{}", if escape == 'p' { 'w' } else { 'W' });
+    }
+
+    let group = "LMNPSZC".chars().find(|&group| {
+        RegexToken::get_unicode_classes_single_letter(group).as_deref() == Some(categories)
+    });
+    match group {
+        Some(group) => write!(f, "\\{}{}", escape, group),
+        //no syntax spells any other set, so it is written in a form that reads clearly but does not parse
+        None => {
+            let names = categories
+                .iter()
+                .map(|category| category.abbr_name())
+                .collect::<Vec<_>>();
+            write!(f, "\\{}{{{}}}", escape, names.join("|"))
+        }
+    }
+}
+
+//control characters (which are all below 0x100) are escaped, since they would be invisible or break the pattern's line
+fn write_char(f: &mut Formatter<'_>, char: char) -> std::fmt::Result {
+    match char {
+        '\n' => f.write_str("\\n"),
+        '\t' => f.write_str("\\t"),
+        '\r' => f.write_str("\\r"),
+        '\0' => f.write_str("\\0"),
+        '\x0C' => f.write_str("\\f"),
+        char if char.is_control() => write!(f, "\\x{:02X}", char as u32),
+        char => f.write_char(char),
+    }
+}
+
+//the characters that are syntax inside of a class; `&` is only syntax as part of `&&`, but escaping every one is simpler
+fn write_class_char(f: &mut Formatter<'_>, char: char) -> std::fmt::Result {
+    match char {
+        '\\' | ']' | '[' | '^' | '-' | '&' => write!(f, "\\{}", char),
+        char => write_char(f, char),
+    }
+}
+
+/// Writes the class without its surrounding brackets, e.g., `^a-z0`.
+impl Display for CharacterClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CharacterClass::Char(char) => write_class_char(f, *char),
+            CharacterClass::Range { start, end } => {
+                write_class_char(f, *start)?;
+                f.write_char('-')?;
+                write_class_char(f, *end)
+            }
+            CharacterClass::Disjunction(classes) => {
+                classes.iter().try_for_each(|class| write_member(f, class))
+            }
+            CharacterClass::Negated(class) => write!(f, "^{}", class),
+            //the right side is bracketed, so that it ends where the next operation starts
+            CharacterClass::Intersection(left, right) => {
+                write_left_operand(f, left)?;
+                write!(f, "&&[{}]", right)
+            }
+            CharacterClass::Difference(left, right) => {
+                write_left_operand(f, left)?;
+                write!(f, "--[{}]", right)
+            }
+        }
+    }
+}
+
+//a member that is not a single character or range is nested in brackets, so that it does not take the members after it into a negation or set operation
+fn write_member(f: &mut Formatter<'_>, class: &CharacterClass) -> std::fmt::Result {
+    match class {
+        CharacterClass::Char(_) | CharacterClass::Range { .. } => write!(f, "{}", class),
+        class => write!(f, "[{}]", class),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::RegexEntry;
+
+    fn assert_normalized(pattern: &str, expected: &str) {
+        let parsed = RegexEntry::parse(pattern).unwrap();
+        assert_eq!(parsed.to_string(), expected);
+        assert_eq!(RegexEntry::parse(expected).unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for pattern in [
+            "abc",
+            "a.c",
+            "(GET)|(POST)",
+            "ab|cd",
+            "a*|b+|c",
+            "(a|)|b",
+            "[a-z0-9_]+",
+            "[^abc]*",
+            "x?(yz){3}",
+            "a{2,}b{2,5}",
+            "a*?b+?c??d{2,}?e{2,5}?",
+            "\\p{Lu}\\P{Nd}\\pL\\PZ\\d\\D",
+            "\\p{Greek}\\P{Old_Italic}",
+            "\\X{1,3}",
+            "[a-z&&[^aeiou]][^0-9--[5]&&[1-8]]",
+            "[[^a-z]&&[^0-9]][[^a]b[c-e&&[d]]]",
+            "[\\]\\-\\\\\\^\\[\\&a]",
+            "\\w+\\s*\\W\\S",
+            "\\h+\\H\\v\\V",
+            "((a)(b(c)))",
+            "(?P<year>\\d{4})-(?P<month>\\d{2})",
+            "\\b{start}\\w+\\b{end}",
+        ] {
+            assert_normalized(pattern, pattern);
+        }
+    }
+
+    #[test]
+    fn test_normalization() {
+        assert_normalized("a{0,}b{1,}c{0,1}d{4,4}", "a*b+c?d{4}");
+        assert_normalized("(?<name>a)", "(?P<name>a)");
+        assert_normalized("\\p{Uppercase_Letter}", "\\p{Lu}");
+        assert_normalized("\\p{old italic}\\P{grek}", "\\p{Old_Italic}\\P{Greek}");
+        assert_normalized("\\p{gc=Lu}\\P{sc=Grek}", "\\p{Lu}\\P{Greek}");
+        assert_normalized("[a-z&&aeiou]", "[a-z&&[aeiou]]");
+        assert_normalized("e{,3}", "e{0,3}");
+        assert_normalized("f{0,1}?", "f??");
+        assert_normalized("\\.\\]\\\\", "\\.\\]\\\\");
+        assert_normalized("\\u{41}\\u{0}[\\u{9}-\\x0d]", "A\\0[\\t-\\r]");
+        assert_normalized("\\x1B[^\\n]", "\\x1B[^\\n]");
+        assert_normalized("x(?i:ab)*(?:c)", "x(?:[aA][bB])*c");
+        assert_normalized("(?m)^a$(?-m)$(?-s).", "(?m:^)a(?m:$)$[^\\n]");
+    }
+
+    #[test]
+    fn test_built_entries() {
+        let entry = RegexEntry::Alternation(vec![
+            RegexEntry::Concatenation(vec![RegexEntry::Literal('a'), RegexEntry::Literal('b')]),
+            RegexEntry::Repetition {
+                base: Box::new(RegexEntry::Repetition {
+                    base: Box::new(RegexEntry::Literal('c')),
+                    min: 1,
+                    max: None,
+                    lazy: false,
+                }),
+                min: 0,
+                max: Some(1),
+                lazy: false,
+            },
+        ]);
+
+        assert_eq!(entry.to_string(), "ab|(?:c+)?");
+
+        let entry = RegexEntry::Concatenation(vec![
+            RegexEntry::Literal('a'),
+            RegexEntry::Alternation(vec![RegexEntry::Literal('b'), RegexEntry::Literal('c')]),
+        ]);
+        assert_eq!(entry.to_string(), "a(?:b|c)");
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// Why a pattern could not be parsed, and where in it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// The bytes of the pattern that are wrong. Its start is the byte offset at which the problem
+    /// was found, and it is empty if the problem is that the pattern ends too early.
+    pub span: Range<usize>,
+    /// What is wrong, for a person to read.
+    pub message: String,
+}
+
+/// What sort of mistake a [`ParseError`] is about.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ParseErrorKind {
+    /// A construct that can not be compiled to a finite automaton, like a backreference or a
+    /// lookahead, one the dialect the pattern is read in does not have, or an escape of a letter
+    /// that fastregex does not know, like the word boundary `\b`.
+    Unsupported,
+    /// A `\` escape that is malformed, like `\x4`, or unfinished.
+    InvalidEscape,
+    /// A bracketed class that is malformed, like `[z-a]` or an unclosed `[a`.
+    InvalidClass,
+    /// A `\p{...}` or `\P{...}` naming no Unicode property or value that is known.
+    UnknownProperty,
+    /// A quantifier that is malformed, like `{3,1}`, or that has nothing to repeat, like the `*`
+    /// in `(*a)`.
+    InvalidRepetition,
+    /// A group or set of flags that is malformed, like `(?P<1>a)`, or a group name that is used
+    /// more than once.
+    InvalidGroup,
+    /// Groups nested more deeply than [`ParseOptions::max_depth`](super::ParseOptions::max_depth)
+    /// allows.
+    NestingTooDeep,
+}
+
+impl ParseError {
+    pub(super) fn new(
+        kind: ParseErrorKind,
+        span: Range<usize>,
+        message: impl Into<String>,
+    ) -> Self {
+        ParseError {
+            kind,
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Error occurred at byte {}: {}",
+            self.span.start, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+//An error found by the functions that read a pattern a piece at a time. Each of them only sees
+//the rest of the pattern from where it starts reading, so where the error is is kept as how much
+//of the pattern is left from there, which places it once the whole pattern is known.
+#[derive(Debug)]
+pub(super) struct LexError {
+    kind: ParseErrorKind,
+    message: String,
+    remaining: usize,
+    len: usize,
+}
+
+impl LexError {
+    //an error about the character at the start of `at`, a part of the pattern that runs to its end
+    pub(super) fn new(kind: ParseErrorKind, message: impl Into<String>, at: &str) -> Self {
+        LexError {
+            kind,
+            message: message.into(),
+            remaining: at.len(),
+            len: at.chars().next().map_or(0, char::len_utf8),
+        }
+    }
+
+    //an error about the text from the start of `at` up to where `after` starts
+    pub(super) fn between(
+        kind: ParseErrorKind,
+        message: impl Into<String>,
+        at: &str,
+        after: &str,
+    ) -> Self {
+        LexError {
+            len: at.len() - after.len(),
+            ..LexError::new(kind, message, at)
+        }
+    }
+
+    //places the error in `pattern`, which the text it was found in is the end of
+    pub(super) fn in_pattern(self, pattern: &str) -> ParseError {
+        let start = pattern.len() - self.remaining;
+        ParseError::new(self.kind, start..start + self.len, self.message)
+    }
+}
@@ -0,0 +1,300 @@
+use crate::case_folding::{case_insensitive_literal, CaseFolding};
+use crate::line_terminator::LineTerminator;
+use crate::parser::character_class::CharacterClass;
+use crate::parser::dialect::Dialect;
+use crate::parser::error::{LexError, ParseErrorKind};
+use crate::parser::tokenizer::RegexToken;
+use crate::parser::ParseOptions;
+
+/// One of the flags a pattern can set with a `(?...)` group, which changes how the rest of the
+/// group it is written in is read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Flag {
+    /// `i`: literals and bracketed classes match regardless of case (see
+    /// [`RegexEntry::make_case_insensitive`](crate::parser::RegexEntry::make_case_insensitive)),
+    /// with the case folding of [`ParseOptions::case_folding`].
+    CaseInsensitive,
+    /// `s`: `.` matches the line terminators (see [`ParseOptions::line_terminator`]) as well as
+    /// every other character. The flag starts out set, except in the re2 dialect, so it is mostly
+    /// cleared (e.g., `(?-s)`) to keep `.` within a line. Cleared, `.` still matches a `\r`
+    /// unless it is one of the line terminators.
+    DotMatchesNewline,
+    /// `m`: `^` and `$` match at the start and end of every line (right after and right before a
+    /// line terminator) as well as at the start and end of the input.
+    ///
+    /// Only `\n` ends a line by default, so in text with `\r\n` line endings, `$` matches right
+    /// before the `\n` and a line's `\r` is part of what `(?m-s)^.*$` matches. With
+    /// [`LineTerminator::Crlf`] or [`LineTerminator::Unicode`], a `\r\n` is one line terminator,
+    /// and neither anchor matches between its two characters.
+    MultiLine,
+    /// `x`: whitespace and comments from a `#` to the end of the line are ignored, outside of
+    /// brackets, so that a long pattern can be spread over lines. A space or `#` to be matched is
+    /// escaped with a `\`.
+    Verbose,
+    /// `u`: `\d`, `\w` and `\s` (and their negations, inside brackets as well) match Unicode
+    /// digits, word characters and whitespace. The flag starts out set unless
+    /// [`ParseOptions::unicode`] is false, and cleared (e.g., `(?-u)`), they only match ASCII.
+    ///
+    /// In a pattern matched against bytes (see [`ParseOptions::bytes`]), clearing it also makes
+    /// literals, bracketed classes and `.` match single bytes rather than the UTF-8 encoding of a
+    /// character (see [`RegexEntry::Byte`](crate::parser::RegexEntry::Byte)), so that
+    /// `(?-u:\xFF)` matches the byte 0xFF, which is not valid UTF-8.
+    Unicode,
+}
+
+impl Flag {
+    fn from_letter(letter: char) -> Option<Self> {
+        match letter {
+            'i' => Some(Flag::CaseInsensitive),
+            's' => Some(Flag::DotMatchesNewline),
+            'm' => Some(Flag::MultiLine),
+            'x' => Some(Flag::Verbose),
+            'u' => Some(Flag::Unicode),
+            _ => None,
+        }
+    }
+}
+
+/// The flags in effect at some point of a pattern.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub(super) struct Flags {
+    case_insensitive: bool,
+    dot_matches_newline: bool,
+    multi_line: bool,
+    unicode: bool,
+    //whether the pattern is matched against bytes, in which the `u` flag decides whether a character is a byte or a UTF-8 sequence
+    bytes: bool,
+    //not flags of their own, but what the `i` flag means and what the `s` and `m` flags take to end a line
+    case_folding: CaseFolding,
+    line_terminator: LineTerminator,
+}
+
+impl Flags {
+    /// The flags a pattern read with `options` starts out with.
+    pub(super) fn for_options(options: ParseOptions) -> Self {
+        Flags {
+            case_insensitive: false,
+            //RE2's `.` does not match `\n`, while fastregex has always matched a whole input that may span lines
+            dot_matches_newline: options.dialect != Dialect::Re2,
+            multi_line: false,
+            unicode: options.unicode,
+            bytes: options.bytes,
+            case_folding: options.case_folding,
+            line_terminator: options.line_terminator,
+        }
+    }
+
+    /// Whether literals are read with full case folding, which [`apply`](Self::apply) leaves to
+    /// the parser, since it reads runs of them together.
+    pub(super) fn folds_fully(&self) -> bool {
+        self.case_insensitive && self.case_folding == CaseFolding::Full && !self.reads_bytes()
+    }
+
+    //whether literals, classes and `.` stand for single bytes, whose case folding is simple
+    fn reads_bytes(&self) -> bool {
+        self.bytes && !self.unicode
+    }
+
+    pub(super) fn set(&mut self, flag: Flag, enabled: bool) {
+        match flag {
+            Flag::CaseInsensitive => self.case_insensitive = enabled,
+            Flag::DotMatchesNewline => self.dot_matches_newline = enabled,
+            Flag::MultiLine => self.multi_line = enabled,
+            Flag::Unicode => self.unicode = enabled,
+            //verbose mode changes what the tokens are rather than what they mean, so the tokenizer keeps track of it (see VerboseMode)
+            Flag::Verbose => {}
+        }
+    }
+
+    /// The token as it is read with these flags in effect.
+    pub(super) fn apply(&self, token: RegexToken) -> RegexToken {
+        let token = self.apply_to_characters(token);
+        if !self.reads_bytes() {
+            return token;
+        }
+
+        //a literal above `\xFF` stands for no single byte, so it is still matched as UTF-8
+        match token {
+            RegexToken::Literal(literal) if literal <= '\u{FF}' => {
+                RegexToken::Byte(CharacterClass::Char(literal))
+            }
+            RegexToken::NonUnicodeCharacterClass(class) => RegexToken::Byte(class),
+            RegexToken::AnyCharacter => RegexToken::Byte(CharacterClass::Range {
+                start: '\0',
+                end: '\u{FF}',
+            }),
+            token => token,
+        }
+    }
+
+    fn apply_to_characters(&self, token: RegexToken) -> RegexToken {
+        match token {
+            RegexToken::Literal(literal) if self.case_insensitive => {
+                match case_insensitive_literal(literal) {
+                    Some(class) => RegexToken::NonUnicodeCharacterClass(class),
+                    None => token,
+                }
+            }
+            RegexToken::NonUnicodeCharacterClass(class) if self.case_insensitive => {
+                RegexToken::NonUnicodeCharacterClass(class.case_insensitive())
+            }
+            RegexToken::AnyCharacter if !self.dot_matches_newline => {
+                RegexToken::NonUnicodeCharacterClass(CharacterClass::Negated(Box::new(
+                    self.line_terminator.class(),
+                )))
+            }
+            RegexToken::StartOfInput if self.multi_line => {
+                RegexToken::StartOfLine(self.line_terminator)
+            }
+            RegexToken::EndOfInput if self.multi_line => {
+                RegexToken::EndOfLine(self.line_terminator)
+            }
+            token => token,
+        }
+    }
+}
+
+/// Tokenizes a `(?flags)` group, which sets flags for the rest of the enclosing group, or the
+/// opening of a `(?flags:...)` group, which sets them for its contents only and does not capture.
+/// Each listed flag is set, or cleared if it comes after a `-` (e.g., `(?i)` or `(?-i:...)`), and
+/// `(?:...)` is a group that only groups.
+pub(super) fn try_parse_set_flags(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+    let start = remaining;
+    let Some(remaining) = remaining.strip_prefix("(?") else {
+        return Ok(None);
+    };
+    let Some(end) = remaining.find([')', ':']) else {
+        return Ok(None);
+    };
+    let letters = &remaining[..end];
+    let is_group = remaining[end..].starts_with(':');
+    if (letters.is_empty() && !is_group)
+        || !letters
+            .chars()
+            .all(|letter| letter.is_ascii_alphabetic() || letter == '-')
+    {
+        return Ok(None);
+    }
+
+    let mut changes = Vec::new();
+    let mut enabled = true;
+    for (index, letter) in letters.char_indices() {
+        if letter == '-' {
+            if !enabled {
+                return Err(LexError::between(
+                    ParseErrorKind::InvalidGroup,
+                    format!("Flags (?{letters}) have more than one -"),
+                    start,
+                    &remaining[end + 1..],
+                ));
+            }
+            enabled = false;
+            continue;
+        }
+
+        let flag = Flag::from_letter(letter).ok_or_else(|| {
+            LexError::new(
+                ParseErrorKind::InvalidGroup,
+                format!("{letter} is not a known flag. Expected one of i, m, s, u or x"),
+                &remaining[index..],
+            )
+        })?;
+        changes.push((flag, enabled));
+    }
+
+    let token = if is_group {
+        RegexToken::OpenFlagGroup(changes)
+    } else {
+        RegexToken::SetFlags(changes)
+    };
+    Ok(Some((token, &remaining[end + 1..])))
+}
+
+/// The flags the tokenizer has to follow, at its position, through the groups opened and closed
+/// so far: `x`, to skip whitespace and comments, and `u`, to read `\d`, `\w` and `\s` as ASCII
+/// where it is cleared.
+pub(super) struct TokenizerFlags {
+    verbose: bool,
+    unicode: bool,
+    //the flags outside each open group, to be restored when it closes
+    enclosing: Vec<(bool, bool)>,
+}
+
+impl TokenizerFlags {
+    pub(super) fn for_options(options: ParseOptions) -> Self {
+        TokenizerFlags {
+            verbose: false,
+            unicode: options.unicode,
+            enclosing: Vec::new(),
+        }
+    }
+
+    /// The options to read the next token with, which follow the `u` flag.
+    pub(super) fn options(&self, options: ParseOptions) -> ParseOptions {
+        ParseOptions {
+            unicode: self.unicode,
+            ..options
+        }
+    }
+
+    /// Updates the flags for the token that was just read.
+    pub(super) fn read(&mut self, token: &RegexToken) {
+        match token {
+            RegexToken::OpenGroup | RegexToken::OpenNamedGroup(_) => {
+                self.enclosing.push((self.verbose, self.unicode))
+            }
+            RegexToken::OpenFlagGroup(changes) => {
+                self.enclosing.push((self.verbose, self.unicode));
+                self.set(changes);
+            }
+            RegexToken::SetFlags(changes) => self.set(changes),
+            RegexToken::CloseGroup => {
+                if let Some((verbose, unicode)) = self.enclosing.pop() {
+                    self.verbose = verbose;
+                    self.unicode = unicode;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn set(&mut self, changes: &[(Flag, bool)]) {
+        for (flag, enabled) in changes {
+            match flag {
+                Flag::Verbose => self.verbose = *enabled,
+                Flag::Unicode => self.unicode = *enabled,
+                _ => {}
+            }
+        }
+    }
+
+    /// What is left of `remaining` after the whitespace and comments that are ignored in verbose
+    /// mode, or all of it outside of verbose mode.
+    pub(super) fn skip_ignored<'a>(&self, mut remaining: &'a str) -> &'a str {
+        if !self.verbose {
+            return remaining;
+        }
+
+        loop {
+            remaining = remaining.trim_start();
+            match remaining.strip_prefix('#') {
+                Some(comment) => remaining = comment.find('\n').map_or("", |end| &comment[end..]),
+                None => return remaining,
+            }
+        }
+    }
+
+    /// Tokenizes an escaped space or `#`, which verbose mode would otherwise ignore.
+    pub(super) fn try_parse_escaped_ignored<'a>(
+        &self,
+        remaining: &'a str,
+    ) -> Option<(RegexToken, &'a str)> {
+        let mut chars = remaining.strip_prefix('\\')?.chars();
+        match chars.next() {
+            Some(escaped) if self.verbose && (escaped.is_whitespace() || escaped == '#') => {
+                Some((RegexToken::Literal(escaped), chars.as_str()))
+            }
+            _ => None,
+        }
+    }
+}
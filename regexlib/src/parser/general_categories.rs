@@ -0,0 +1,30 @@
+//GeneralCategory has no serde support of its own, so categories are stored by their abbreviated
+//names (e.g., "Lu"), which is also how patterns refer to them
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use unic_char_property::EnumeratedCharProperty;
+use unic_ucd_category::GeneralCategory;
+
+pub fn serialize<S: Serializer>(
+    categories: &[GeneralCategory],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    categories
+        .iter()
+        .map(|category| category.abbr_name())
+        .collect::<Vec<_>>()
+        .serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<GeneralCategory>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|name| {
+            name.parse::<GeneralCategory>()
+                .map_err(|_| D::Error::custom(format!("unknown general category \"{name}\"")))
+        })
+        .collect()
+}
@@ -1,25 +1,104 @@
+use std::ops::Range;
+
 use unic_ucd_category::GeneralCategory;
 
 use character_class::CharacterClass;
 
-use self::tokenizer::RegexToken;
+use crate::case_folding::{fold_literal_run, CaseFolding};
+use crate::line_terminator::LineTerminator;
+use crate::script::Script;
+
+pub use self::dialect::Dialect;
+pub use self::error::{ParseError, ParseErrorKind};
+pub use self::flags::Flag;
+use self::flags::Flags;
+pub use self::tokenizer::RegexToken;
+pub use self::visit::{RegexVisitor, RegexVisitorMut};
 
 pub mod character_class;
+mod dialect;
+mod display;
+mod error;
+mod flags;
+#[cfg(feature = "serde")]
+mod general_categories;
+pub mod spans;
 mod tokenizer;
+mod visit;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RegexEntry {
     AnyCharacter,
-    UnicodeCharacterClass(Vec<GeneralCategory>),
-    NegatedUnicodeCharacterClass(Vec<GeneralCategory>),
+    UnicodeCharacterClass(
+        #[cfg_attr(feature = "serde", serde(with = "general_categories"))] Vec<GeneralCategory>,
+    ),
+    NegatedUnicodeCharacterClass(
+        #[cfg_attr(feature = "serde", serde(with = "general_categories"))] Vec<GeneralCategory>,
+    ),
     NonUnicodeCharacterClass(CharacterClass),
+    /// `\p{Greek}` and the like, which match the characters of a Unicode script.
+    UnicodeScript(Script),
+    /// `\P{Greek}` and the like, which match every character outside of a Unicode script.
+    NegatedUnicodeScript(Script),
+    /// `\X`, which matches one extended grapheme cluster: what a reader sees as one character,
+    /// like an `e` followed by a combining accent, a `\r\n`, or an emoji made of several joined
+    /// ones. Clusters follow Unicode's default rules (UAX #29), except for the one that keeps
+    /// Indic conjuncts together, which needs a property the tables do not have.
+    ///
+    /// It matches a sequence of characters whose length depends on the characters, so it is built
+    /// as a small automaton of its own (see [`GraphemeBreak`](crate::grapheme::GraphemeBreak)). Like any
+    /// other entry, it matches the same strings wherever it is used, so a repeated `\X` can also
+    /// split a cluster (e.g., `\X{2}` matches an `e` followed by a combining accent); it is best
+    /// used to bound how many clusters an input has, as in `\X{1,20}`.
+    GraphemeCluster,
     Literal(char),
+    /// A run of literals read with the `i` flag and full case folding (see
+    /// [`CaseFolding::Full`](crate::case_folding::CaseFolding::Full)), which matches any text whose
+    /// full folding is the run's: `ß` matches `SS`, and `ss` matches `ß`. The automaton reads it
+    /// along its folding, reading a character that folds to several across all of them at once.
+    FoldedLiteral(String),
+    /// `^`, which matches the empty string, but only at the start of the input.
+    StartOfInput,
+    /// `$`, which matches the empty string, but only at the end of the input.
+    EndOfInput,
+    /// `^` with the `m` flag set, which matches the empty string at the start of the input and
+    /// right after every line terminator (only `\n`, unless another
+    /// [`LineTerminator`](crate::line_terminator::LineTerminator) was chosen).
+    StartOfLine(LineTerminator),
+    /// `$` with the `m` flag set, which matches the empty string at the end of the input and right
+    /// before every line terminator.
+    EndOfLine(LineTerminator),
+    /// `\b{start}`, which matches the empty string where a word starts: before a word character
+    /// (one that `\w` matches with the `u` flag set) that is not preceded by another.
+    WordStart,
+    /// `\b{end}`, which matches the empty string where a word ends: after a word character that
+    /// is not followed by another.
+    WordEnd,
+    /// A literal, bracketed class or `.` read with the `u` flag cleared in a pattern matched
+    /// against bytes (see [`ParseOptions::bytes`]), which matches a single byte: one whose value is
+    /// a character of the class (e.g., `(?-u:\xFF)` matches the byte 0xFF, and `(?-u:.)` any byte
+    /// but `\n`). The characters above `\xFF` stand for no byte, so they are never matched.
+    Byte(CharacterClass),
     Concatenation(Vec<RegexEntry>),
     Alternation(Vec<RegexEntry>),
     Repetition {
         base: Box<RegexEntry>,
         min: u64,
         max: Option<u64>,
+        /// Whether the repetition prefers fewer iterations of its base, as written with a `?`
+        /// after the quantifier (e.g., `*?`). The same inputs match either way, so this only
+        /// changes which groups and extent captures and leftmost-first searches report.
+        #[cfg_attr(feature = "serde", serde(default))]
+        lazy: bool,
+    },
+    /// A parenthesized group, numbered from 1 in the order of its opening parenthesis. Group 0 is
+    /// implicitly the whole match. A group written as `(?P<name>...)` or `(?<name>...)` is named
+    /// as well.
+    Capture {
+        index: usize,
+        name: Option<String>,
+        base: Box<RegexEntry>,
     },
 }
 
@@ -28,33 +107,339 @@ pub enum RegexEntry {
 enum PartiallyParsed {
     Lexed(RegexToken),
     Group(Vec<PartiallyParsed>),
+    NamedGroup(String, Vec<PartiallyParsed>),
+    //a `(?flags:...)` group, whose flags are applied to its tokens before the later stages, which treat it as a group that does not capture
+    FlagGroup(Vec<(Flag, bool)>, Vec<PartiallyParsed>),
+    //a literal read with the `i` flag and full case folding, which is merged with the ones next to it once the groups are lowered
+    FoldedLiteral(char),
     Repetition {
         base: Box<PartiallyParsed>,
         min: u64,
         max: Option<u64>,
+        lazy: bool,
     },
     Alternation(Vec<PartiallyParsed>),
 }
 
+/// The deepest nesting of groups [`RegexEntry::parse`] accepts. Parsing, and
+/// building an automaton from the result, recurse once per level, so this bounds their stack use.
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 250;
+
+/// The largest count a repetition like `a{2,5}` may give. A repetition is built as a copy of its
+/// base per count, so one any larger could never practically be built.
+pub const MAX_REPETITION_BOUND: u64 = 100_000;
+
+/// How [`RegexEntry::parse_with_options`] reads a pattern. The default reads fastregex's own
+/// syntax, like [`RegexEntry::parse`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ParseOptions {
+    pub dialect: Dialect,
+    /// The deepest nesting of groups (e.g., `((a))`) that is accepted.
+    pub max_depth: usize,
+    /// Whether `\d`, `\w` and `\s` (and their negations) match Unicode digits, word characters
+    /// and whitespace. Without it they only match ASCII, like `[0-9]`, `[0-9A-Za-z_]` and
+    /// `[\t-\r ]`, which take far simpler checks to match than the Unicode classes. Classes
+    /// written as `\p{...}` are Unicode either way. This is where the `u` flag starts out (see
+    /// [`Flag::Unicode`]).
+    pub unicode: bool,
+    /// How the `i` flag relates characters of different case.
+    pub case_folding: CaseFolding,
+    /// Which characters end a line, for `.` without the `s` flag, `\R`, and `^` and `$` with the
+    /// `m` flag.
+    pub line_terminator: LineTerminator,
+    /// Whether the pattern is matched against bytes, which may not be valid UTF-8, rather than a
+    /// str. Where the `u` flag is cleared, literals, bracketed classes and `.` are then read as
+    /// [`Byte`](RegexEntry::Byte)s. Everything else still matches characters, until the entry is
+    /// turned into bytes with [`to_utf8_bytes`](RegexEntry::to_utf8_bytes).
+    pub bytes: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            dialect: Dialect::Fastregex,
+            max_depth: DEFAULT_MAX_NESTING_DEPTH,
+            unicode: true,
+            case_folding: CaseFolding::Simple,
+            line_terminator: LineTerminator::Newline,
+            bytes: false,
+        }
+    }
+}
+
 impl RegexEntry {
-    pub fn parse(regex: &str) -> Result<Self, String> {
-        let lexed = Self::lex(regex)?;
-        let grouped = Self::group(lexed);
-        let repetitions =
-            Self::parse_for_all_groups_recursively(grouped, &Self::parse_repetitions)?;
+    pub fn parse(regex: &str) -> Result<Self, ParseError> {
+        Self::parse_with_options(regex, ParseOptions::default())
+    }
+
+    /// Like [`parse`](Self::parse), but with a different limit on how deeply groups (e.g.,
+    /// `((a))`) may nest.
+    pub fn parse_with_max_depth(regex: &str, max_depth: usize) -> Result<Self, ParseError> {
+        Self::parse_with_options(
+            regex,
+            ParseOptions {
+                max_depth,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Like [`parse`](Self::parse), but reads the regex in `dialect` instead of fastregex's own
+    /// syntax.
+    pub fn parse_in_dialect(regex: &str, dialect: Dialect) -> Result<Self, ParseError> {
+        Self::parse_with_options(
+            regex,
+            ParseOptions {
+                dialect,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Like [`parse`](Self::parse), but reads the regex as `options` say.
+    pub fn parse_with_options(regex: &str, options: ParseOptions) -> Result<Self, ParseError> {
+        let tokens = RegexToken::parse_with_spans_and_options(regex, options)?;
+        //everything that can be wrong past tokenizing is checked on the tokens, which know where they came from
+        Self::check_groups_closed(&tokens, regex.len())?;
+        Self::check_nesting_depth(&tokens, options.max_depth)?;
+        Self::check_repetitions(&tokens)?;
+        Self::check_capture_names(&tokens)?;
+
+        let grouped = Self::apply_flags(
+            Self::group(Self::lex(tokens)),
+            Flags::for_options(options),
+        );
+        let repetitions = Self::parse_for_all_groups_recursively(grouped, &Self::parse_repetitions);
         let alternations =
-            Self::parse_for_all_groups_recursively(repetitions, &Self::parse_alternation)?;
+            Self::parse_for_all_groups_recursively(repetitions, &Self::parse_alternation);
         let mut parsed = Self::finish_parsing(alternations);
         Self::simplify_ast(&mut parsed);
 
         Ok(parsed)
     }
 
-    fn lex(regex: &str) -> Result<Vec<PartiallyParsed>, String> {
-        Ok(RegexToken::parse(regex)?
+    /// The number of capture groups in the pattern, not counting the implicit group 0.
+    pub fn capture_count(&self) -> usize {
+        match self {
+            RegexEntry::Capture { index, base, .. } => (*index).max(base.capture_count()),
+            RegexEntry::Concatenation(entries) | RegexEntry::Alternation(entries) => entries
+                .iter()
+                .map(RegexEntry::capture_count)
+                .max()
+                .unwrap_or(0),
+            RegexEntry::Repetition { base, .. } => base.capture_count(),
+            _ => 0,
+        }
+    }
+
+    /// The name of each capture group, indexed like the groups, or `None` for groups without one
+    /// (which always includes the implicit group 0).
+    pub fn capture_names(&self) -> Vec<Option<&str>> {
+        fn collect<'entry>(entry: &'entry RegexEntry, names: &mut Vec<Option<&'entry str>>) {
+            match entry {
+                RegexEntry::Capture { index, name, base } => {
+                    names[*index] = name.as_deref();
+                    collect(base, names);
+                }
+                RegexEntry::Concatenation(entries) | RegexEntry::Alternation(entries) => {
+                    for entry in entries {
+                        collect(entry, names);
+                    }
+                }
+                RegexEntry::Repetition { base, .. } => collect(base, names),
+                _ => {}
+            }
+        }
+
+        let mut names = vec![None; self.capture_count() + 1];
+        collect(self, &mut names);
+        names
+    }
+
+    /// The largest count any repetition in the pattern spells out (its maximum, or its minimum if
+    /// it is unbounded), or 0 if there are no repetitions. Each counted repetition is expanded
+    /// into that many copies of its base.
+    pub fn largest_repetition_count(&self) -> u64 {
+        match self {
+            RegexEntry::Repetition { base, min, max, .. } => max
+                .unwrap_or(*min)
+                .max(base.largest_repetition_count()),
+            RegexEntry::Capture { base, .. } => base.largest_repetition_count(),
+            RegexEntry::Concatenation(entries) | RegexEntry::Alternation(entries) => entries
+                .iter()
+                .map(RegexEntry::largest_repetition_count)
+                .max()
+                .unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// Whether the pattern contains a `^`, `$` or word boundary anywhere. Such a pattern is only
+    /// known to match once it is known where the input starts and ends, so matching it in part of
+    /// a larger input (e.g., finding it anywhere in a string) has to take them into account.
+    pub fn has_anchors(&self) -> bool {
+        self.has_start_anchor() || self.has_end_anchor()
+    }
+
+    /// Whether the pattern contains a `^` anywhere, whether or not it is multi-line, or a word
+    /// boundary, which depends on the character before it as well.
+    pub fn has_start_anchor(&self) -> bool {
+        self.contains(&|entry| matches!(entry, RegexEntry::StartOfInput | RegexEntry::StartOfLine(_)))
+            || self.has_word_boundaries()
+    }
+
+    /// Whether the pattern contains a `$` anywhere, whether or not it is multi-line, or a word
+    /// boundary, which depends on the character after it as well.
+    pub fn has_end_anchor(&self) -> bool {
+        self.contains(&|entry| matches!(entry, RegexEntry::EndOfInput | RegexEntry::EndOfLine(_)))
+            || self.has_word_boundaries()
+    }
+
+    /// Whether the pattern contains a `\b{start}` or `\b{end}` anywhere.
+    pub fn has_word_boundaries(&self) -> bool {
+        self.contains(&|entry| matches!(entry, RegexEntry::WordStart | RegexEntry::WordEnd))
+    }
+
+    /// Whether the pattern contains a `\X` anywhere.
+    pub fn has_grapheme_clusters(&self) -> bool {
+        self.contains(&|entry| matches!(entry, RegexEntry::GraphemeCluster))
+    }
+
+    fn contains(&self, predicate: &impl Fn(&RegexEntry) -> bool) -> bool {
+        predicate(self)
+            || match self {
+                RegexEntry::Concatenation(entries) | RegexEntry::Alternation(entries) => {
+                    entries.iter().any(|entry| entry.contains(predicate))
+                }
+                RegexEntry::Repetition { base, .. } | RegexEntry::Capture { base, .. } => {
+                    base.contains(predicate)
+                }
+                _ => false,
+            }
+    }
+
+    fn lex(tokens: Vec<(RegexToken, Range<usize>)>) -> Vec<PartiallyParsed> {
+        tokens
             .into_iter()
-            .map(|token| PartiallyParsed::Lexed(token))
-            .collect())
+            .map(|(token, _)| PartiallyParsed::Lexed(token))
+            .collect()
+    }
+
+    //a name has to identify a single group for lookups by name to make sense
+    fn check_capture_names(tokens: &[(RegexToken, Range<usize>)]) -> Result<(), ParseError> {
+        let mut names = Vec::new();
+        for (token, span) in tokens {
+            if let RegexToken::OpenNamedGroup(name) = token {
+                if names.contains(&name) {
+                    return Err(ParseError::new(
+                        ParseErrorKind::InvalidGroup,
+                        span.clone(),
+                        format!("Group name \"{}\" is used more than once", name),
+                    ));
+                }
+                names.push(name);
+            }
+        }
+
+        Ok(())
+    }
+
+    //every group has to be closed, and every `)` has to close one, or the pattern would be read as something other than it says
+    fn check_groups_closed(
+        tokens: &[(RegexToken, Range<usize>)],
+        pattern_len: usize,
+    ) -> Result<(), ParseError> {
+        let mut open = Vec::new();
+
+        for (token, span) in tokens {
+            match token {
+                RegexToken::OpenGroup
+                | RegexToken::OpenNamedGroup(_)
+                | RegexToken::OpenFlagGroup(_) => open.push(span),
+                RegexToken::CloseGroup if open.pop().is_none() => {
+                    return Err(ParseError::new(
+                        ParseErrorKind::InvalidGroup,
+                        span.clone(),
+                        "Encountered ) without a group to close",
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        match open.pop() {
+            Some(unclosed) => Err(ParseError::new(
+                ParseErrorKind::InvalidGroup,
+                unclosed.start..pattern_len,
+                "Group is never closed, since the pattern ends before its )",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    //checked on the tokens, before any of the recursive parsing steps run
+    fn check_nesting_depth(
+        tokens: &[(RegexToken, Range<usize>)],
+        max_depth: usize,
+    ) -> Result<(), ParseError> {
+        let mut depth = 0usize;
+
+        for (token, span) in tokens {
+            match token {
+                RegexToken::OpenGroup
+                | RegexToken::OpenNamedGroup(_)
+                | RegexToken::OpenFlagGroup(_) => depth += 1,
+                RegexToken::CloseGroup => depth -= 1,
+                _ => {}
+            }
+
+            if depth > max_depth {
+                return Err(ParseError::new(
+                    ParseErrorKind::NestingTooDeep,
+                    span.clone(),
+                    format!("Groups are nested more than {} deep", max_depth),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    //a repetition needs an item right before it in its group, which rules out the start of a group or branch, another repetition and flags
+    fn check_repetitions(tokens: &[(RegexToken, Range<usize>)]) -> Result<(), ParseError> {
+        let mut previous: Option<&RegexToken> = None;
+
+        for (token, span) in tokens {
+            if let RegexToken::Repetition { min, max, .. } = token {
+                let message = match previous {
+                    Some(RegexToken::SetFlags(_)) => {
+                        "Encountered repetition of flags, which are not repeatable.".to_string()
+                    }
+                    None
+                    | Some(
+                        RegexToken::OpenGroup
+                        | RegexToken::OpenNamedGroup(_)
+                        | RegexToken::OpenFlagGroup(_)
+                        | RegexToken::Alternation
+                        | RegexToken::Repetition { .. },
+                    ) => format!(
+                        "Encountered repetition from {:?} to {:?} not succeeding repeatable token or group.", min, max
+                    ),
+                    Some(_) => {
+                        previous = Some(token);
+                        continue;
+                    }
+                };
+                return Err(ParseError::new(
+                    ParseErrorKind::InvalidRepetition,
+                    span.clone(),
+                    message,
+                ));
+            }
+            previous = Some(token);
+        }
+
+        Ok(())
     }
 
     fn group(mut input: Vec<PartiallyParsed>) -> Vec<PartiallyParsed> {
@@ -66,6 +451,12 @@ impl RegexEntry {
                     PartiallyParsed::Lexed(RegexToken::OpenGroup) => {
                         PartiallyParsed::Group(parse_group(input))
                     }
+                    PartiallyParsed::Lexed(RegexToken::OpenNamedGroup(name)) => {
+                        PartiallyParsed::NamedGroup(name, parse_group(input))
+                    }
+                    PartiallyParsed::Lexed(RegexToken::OpenFlagGroup(changes)) => {
+                        PartiallyParsed::FlagGroup(changes, parse_group(input))
+                    }
                     PartiallyParsed::Lexed(RegexToken::CloseGroup) => break,
                     partial => partial,
                 };
@@ -78,65 +469,111 @@ impl RegexEntry {
         parse_group(&mut input.into_iter())
     }
 
+    //flags only change how tokens are read, so they are applied to the tokens after them (in their group and the groups nested in it) and then dropped
+    fn apply_flags(input: Vec<PartiallyParsed>, mut flags: Flags) -> Vec<PartiallyParsed> {
+        let mut output = Vec::new();
+
+        for part in input {
+            let part = match part {
+                PartiallyParsed::Lexed(RegexToken::SetFlags(changes)) => {
+                    for (flag, enabled) in changes {
+                        flags.set(flag, enabled);
+                    }
+                    continue;
+                }
+                PartiallyParsed::Lexed(RegexToken::Literal(literal)) if flags.folds_fully() => {
+                    PartiallyParsed::FoldedLiteral(literal)
+                }
+                PartiallyParsed::Lexed(token) => PartiallyParsed::Lexed(flags.apply(token)),
+                PartiallyParsed::Group(children) => {
+                    PartiallyParsed::Group(Self::apply_flags(children, flags))
+                }
+                PartiallyParsed::NamedGroup(name, children) => {
+                    PartiallyParsed::NamedGroup(name, Self::apply_flags(children, flags))
+                }
+                PartiallyParsed::FlagGroup(changes, children) => {
+                    let mut group_flags = flags;
+                    for (flag, enabled) in &changes {
+                        group_flags.set(*flag, *enabled);
+                    }
+                    PartiallyParsed::FlagGroup(changes, Self::apply_flags(children, group_flags))
+                }
+                part => part,
+            };
+            output.push(part);
+        }
+
+        output
+    }
+
     fn parse_for_all_groups_recursively(
         input: Vec<PartiallyParsed>,
-        parser: &impl Fn(Vec<PartiallyParsed>) -> Result<Vec<PartiallyParsed>, String>,
-    ) -> Result<Vec<PartiallyParsed>, String> {
-        let mut input = (*parser)(input)?;
+        parser: &impl Fn(Vec<PartiallyParsed>) -> Vec<PartiallyParsed>,
+    ) -> Vec<PartiallyParsed> {
+        let mut input = (*parser)(input);
 
         for child in &mut input {
-            Self::parse_child_recursively(child, parser)?;
+            Self::parse_child_recursively(child, parser);
         }
 
-        Ok(input)
+        input
     }
 
     fn parse_child_recursively(
         child: &mut PartiallyParsed,
-        parser: &impl Fn(Vec<PartiallyParsed>) -> Result<Vec<PartiallyParsed>, String>,
-    ) -> Result<(), String> {
+        parser: &impl Fn(Vec<PartiallyParsed>) -> Vec<PartiallyParsed>,
+    ) {
         match child {
-            PartiallyParsed::Group(child) | PartiallyParsed::Alternation(child) => {
+            PartiallyParsed::Group(child)
+            | PartiallyParsed::NamedGroup(_, child)
+            | PartiallyParsed::FlagGroup(_, child)
+            | PartiallyParsed::Alternation(child) => {
                 let mut child_stack = Vec::new();
                 std::mem::swap(&mut child_stack, child);
-                child_stack = Self::parse_for_all_groups_recursively(child_stack, parser)?;
+                child_stack = Self::parse_for_all_groups_recursively(child_stack, parser);
                 std::mem::swap(&mut child_stack, child);
             }
             PartiallyParsed::Repetition { base, .. } => {
-                Self::parse_child_recursively(base.as_mut(), parser)?;
+                Self::parse_child_recursively(base.as_mut(), parser);
             }
-            PartiallyParsed::Lexed(_) => {}
+            PartiallyParsed::Lexed(_) | PartiallyParsed::FoldedLiteral(_) => {}
         }
-        Ok(())
     }
 
-    fn parse_repetitions(mut input: Vec<PartiallyParsed>) -> Result<Vec<PartiallyParsed>, String> {
+    fn parse_repetitions(mut input: Vec<PartiallyParsed>) -> Vec<PartiallyParsed> {
         let mut iterator = input.into_iter().peekable();
 
         let mut output = Vec::new();
 
         loop {
             match iterator.next() {
-                Some(PartiallyParsed::Lexed(RegexToken::Repetition { min, max })) => {
-                    return Err(format!(
-                        "Encountered repetition from {:?} to {:?} not succeeding repeatable token or group.", min, max
-                    ));
+                Some(PartiallyParsed::Lexed(RegexToken::Repetition { .. })) => {
+                    unreachable!("check_repetitions rejects repetitions with nothing to repeat")
                 }
                 Some(part) => {
                     if let Some(PartiallyParsed::Lexed(RegexToken::Repetition { .. })) =
                         iterator.peek()
                     {
-                        let (min, max) = match iterator.next() {
-                            Some(PartiallyParsed::Lexed(RegexToken::Repetition { min, max })) => {
-                                (min, max)
-                            }
+                        let (min, max, lazy) = match iterator.next() {
+                            Some(PartiallyParsed::Lexed(RegexToken::Repetition {
+                                min,
+                                max,
+                                lazy,
+                            })) => (min, max, lazy),
                             _ => panic!(),
                         };
 
+                        //an alternation token is an operator between items rather than an item itself, so it can not be repeated
+                        assert!(
+                            !matches!(part, PartiallyParsed::Lexed(RegexToken::Alternation)),
+                            "check_repetitions rejects repetitions of alternations"
+                        );
+
                         output.push(PartiallyParsed::Repetition {
                             base: Box::new(part),
                             min,
                             max,
+                            lazy,
                         });
                     } else {
                         output.push(part);
@@ -146,75 +583,147 @@ impl RegexEntry {
             }
         }
 
-        Ok(output)
+        output
     }
 
-    //TODO: make this be O(n)
-    fn parse_alternation(mut input: Vec<PartiallyParsed>) -> Result<Vec<PartiallyParsed>, String> {
-        loop {
-            let mut did_make_change = false;
-
-            for index in 0..input.len() {
-                if let PartiallyParsed::Lexed(RegexToken::Alternation) = input[index] {
-                    if index == 0 {
-                        return Err("Found alternation token without preceding item.".into());
-                    }
-
-                    if index == input.len() - 1 {
-                        return Err("Found alternation token without succeeding item.".into());
-                    }
-
-                    let preceeding = input[index - 1].clone();
-                    let succeeding = input[index + 1].clone();
-
-                    if let PartiallyParsed::Alternation(..) = preceeding {
-                        if let PartiallyParsed::Alternation(inner) = &mut input[index - 1] {
-                            inner.push(succeeding);
-                        }
-                        input.remove(index + 1);
-                        input.remove(index);
-                    } else {
-                        input[index] = PartiallyParsed::Alternation(vec![preceeding, succeeding]);
-                        input.remove(index + 1);
-                        input.remove(index - 1);
-                    }
-
-                    did_make_change = true;
-                    break;
-                }
-            }
+    //`|` binds loosest, so once a group has one, each branch is all of the items between the group's `|`s; a branch left empty, like the second one of `(a|)`, matches the empty string, so it is read as an empty group that does not capture
+    fn parse_alternation(input: Vec<PartiallyParsed>) -> Vec<PartiallyParsed> {
+        if !input
+            .iter()
+            .any(|part| matches!(part, PartiallyParsed::Lexed(RegexToken::Alternation)))
+        {
+            return input;
+        }
 
-            if !did_make_change {
-                break;
+        let mut branches = vec![Vec::new()];
+        for part in input {
+            match part {
+                PartiallyParsed::Lexed(RegexToken::Alternation) => branches.push(Vec::new()),
+                part => branches.last_mut().unwrap().push(part),
             }
         }
 
-        Ok(input)
+        let branches = branches
+            .into_iter()
+            .map(|mut branch| {
+                if branch.len() == 1 {
+                    branch.pop().unwrap()
+                } else {
+                    PartiallyParsed::FlagGroup(Vec::new(), branch)
+                }
+            })
+            .collect();
+        vec![PartiallyParsed::Alternation(branches)]
     }
 
     fn finish_parsing(input: Vec<PartiallyParsed>) -> RegexEntry {
-        let concatenation = input
-            .into_iter()
-            .map(|partially_parsed| Self::lower_single_partially_parsed(partially_parsed))
-            .collect::<Vec<_>>();
-        RegexEntry::Concatenation(concatenation)
+        Self::lower_all_partially_parsed(input, &mut 0, RegexEntry::Concatenation)
     }
 
-    fn lower_single_partially_parsed(partially_parsed: PartiallyParsed) -> RegexEntry {
+    //lowering visits groups in the order of their opening parentheses, so counting them as they are lowered numbers them the way users expect
+    fn lower_single_partially_parsed(
+        partially_parsed: PartiallyParsed,
+        capture_count: &mut usize,
+    ) -> RegexEntry {
         match partially_parsed {
             PartiallyParsed::Lexed(RegexToken::AnyCharacter) => RegexEntry::AnyCharacter,
             PartiallyParsed::Lexed(RegexToken::NonUnicodeCharacterClass(class)) => RegexEntry::NonUnicodeCharacterClass(class),
             PartiallyParsed::Lexed(RegexToken::NegatedUnicodeCharacterClass(categories)) => RegexEntry::NegatedUnicodeCharacterClass(categories),
             PartiallyParsed::Lexed(RegexToken::UnicodeCharacterClass(categories)) => RegexEntry::UnicodeCharacterClass(categories),
+            PartiallyParsed::Lexed(RegexToken::UnicodeScript(script)) => RegexEntry::UnicodeScript(script),
+            PartiallyParsed::Lexed(RegexToken::NegatedUnicodeScript(script)) => RegexEntry::NegatedUnicodeScript(script),
+            PartiallyParsed::Lexed(RegexToken::GraphemeCluster) => RegexEntry::GraphemeCluster,
             PartiallyParsed::Lexed(RegexToken::Literal(literal)) => RegexEntry::Literal(literal),
+            PartiallyParsed::Lexed(RegexToken::StartOfInput) => RegexEntry::StartOfInput,
+            PartiallyParsed::Lexed(RegexToken::EndOfInput) => RegexEntry::EndOfInput,
+            PartiallyParsed::Lexed(RegexToken::StartOfLine(terminator)) => {
+                RegexEntry::StartOfLine(terminator)
+            }
+            PartiallyParsed::Lexed(RegexToken::EndOfLine(terminator)) => {
+                RegexEntry::EndOfLine(terminator)
+            }
+            PartiallyParsed::Lexed(RegexToken::WordStart) => RegexEntry::WordStart,
+            PartiallyParsed::Lexed(RegexToken::WordEnd) => RegexEntry::WordEnd,
+            PartiallyParsed::Lexed(RegexToken::Byte(class)) => RegexEntry::Byte(class),
+            PartiallyParsed::FoldedLiteral(literal) => RegexEntry::FoldedLiteral(literal.to_string()),
             PartiallyParsed::Lexed(token) => panic!("Encountered unexpected lexed but not parsed token when lowering intermediate parsing representation. This is an internal error in the parsed. {:#?}", token),
-            PartiallyParsed::Group(concatenation) => RegexEntry::Concatenation(concatenation.into_iter().map(|entry| Self::lower_single_partially_parsed(entry)).collect()),
-            PartiallyParsed::Repetition { base, min, max } => RegexEntry::Repetition { base: Box::new(Self::lower_single_partially_parsed(*base)), min, max },
-            PartiallyParsed::Alternation(entries) => RegexEntry::Alternation(entries.into_iter().map(|entry| Self::lower_single_partially_parsed(entry)).collect()),
+            PartiallyParsed::Group(concatenation) => Self::lower_group(None, concatenation, capture_count),
+            PartiallyParsed::NamedGroup(name, concatenation) => Self::lower_group(Some(name), concatenation, capture_count),
+            PartiallyParsed::FlagGroup(_, concatenation) => Self::lower_all_partially_parsed(concatenation, capture_count, RegexEntry::Concatenation),
+            PartiallyParsed::Repetition { base, min, max, lazy } => RegexEntry::Repetition { base: Box::new(Self::lower_single_partially_parsed(*base, capture_count)), min, max, lazy },
+            PartiallyParsed::Alternation(entries) => Self::lower_all_partially_parsed(entries, capture_count, RegexEntry::Alternation),
+        }
+    }
+
+    fn lower_group(
+        name: Option<String>,
+        concatenation: Vec<PartiallyParsed>,
+        capture_count: &mut usize,
+    ) -> RegexEntry {
+        *capture_count += 1;
+        let index = *capture_count;
+        RegexEntry::Capture {
+            index,
+            name,
+            base: Box::new(Self::lower_all_partially_parsed(
+                concatenation,
+                capture_count,
+                RegexEntry::Concatenation,
+            )),
         }
     }
 
+    fn lower_all_partially_parsed(
+        partially_parsed: Vec<PartiallyParsed>,
+        capture_count: &mut usize,
+        combine: impl FnOnce(Vec<RegexEntry>) -> RegexEntry,
+    ) -> RegexEntry {
+        combine(
+            partially_parsed
+                .into_iter()
+                .map(|partially_parsed| Self::lower_single_partially_parsed(partially_parsed, capture_count))
+                .collect(),
+        )
+    }
+
     fn simplify_ast(input: &mut RegexEntry) {
+        //a group that does not capture leaves a concatenation inside another, which is written and matched the same as the two merged
+        if let RegexEntry::Concatenation(members) = input {
+            while members
+                .iter()
+                .any(|member| matches!(member, RegexEntry::Concatenation(_)))
+            {
+                *members = std::mem::take(members)
+                    .into_iter()
+                    .flat_map(|member| match member {
+                        RegexEntry::Concatenation(inner) => inner,
+                        member => vec![member],
+                    })
+                    .collect();
+            }
+
+            //full folding reads a run of literals as a whole, since `ss` matches `ß`
+            let mut merged = Vec::new();
+            let mut literal_run = String::new();
+            for member in std::mem::take(members) {
+                if let RegexEntry::FoldedLiteral(literal) = &member {
+                    literal_run.push_str(literal);
+                    continue;
+                }
+                if !literal_run.is_empty() {
+                    merged.append(&mut fold_literal_run(std::mem::take(&mut literal_run)));
+                }
+                merged.push(member);
+            }
+            if !literal_run.is_empty() {
+                merged.append(&mut fold_literal_run(literal_run));
+            }
+            *members = merged;
+        }
+        if let RegexEntry::FoldedLiteral(literal) = input {
+            *input = fold_literal_run(std::mem::take(literal)).remove(0);
+        }
+
         match input {
             RegexEntry::Alternation(members_ref) | RegexEntry::Concatenation(members_ref) => {
                 if members_ref.len() == 1 {
@@ -228,7 +737,9 @@ impl RegexEntry {
                     }
                 }
             }
-            RegexEntry::Repetition { base, .. } => Self::simplify_ast(base),
+            RegexEntry::Repetition { base, .. } | RegexEntry::Capture { base, .. } => {
+                Self::simplify_ast(base)
+            }
             _ => {}
         }
     }
@@ -236,22 +747,33 @@ impl RegexEntry {
 
 #[test]
 fn test_grouping() {
-    let grouped = RegexEntry::group(RegexEntry::lex("(.+(+.)){5}").unwrap());
+    let grouped = RegexEntry::group(RegexEntry::lex(
+        RegexToken::parse_with_spans("(.+(+.)){5}").unwrap(),
+    ));
 
     assert_eq!(
         grouped,
         vec![
             PartiallyParsed::Group(vec![
                 PartiallyParsed::Lexed(RegexToken::AnyCharacter),
-                PartiallyParsed::Lexed(RegexToken::Repetition { min: 1, max: None }),
+                PartiallyParsed::Lexed(RegexToken::Repetition {
+                    min: 1,
+                    max: None,
+                    lazy: false,
+                }),
                 PartiallyParsed::Group(vec![
-                    PartiallyParsed::Lexed(RegexToken::Repetition { min: 1, max: None }),
+                    PartiallyParsed::Lexed(RegexToken::Repetition {
+                        min: 1,
+                        max: None,
+                        lazy: false,
+                    }),
                     PartiallyParsed::Lexed(RegexToken::AnyCharacter),
                 ]),
             ]),
             PartiallyParsed::Lexed(RegexToken::Repetition {
                 min: 5,
                 max: Some(5),
+                lazy: false,
             }),
         ]
     )
@@ -260,13 +782,12 @@ fn test_grouping() {
 #[cfg(test)]
 fn test_util_test_incremental_parser(
     to_lex: &str,
-    incremental_parser: impl Fn(Vec<PartiallyParsed>) -> Result<Vec<PartiallyParsed>, String>,
+    incremental_parser: impl Fn(Vec<PartiallyParsed>) -> Vec<PartiallyParsed>,
     expected: &Vec<PartiallyParsed>,
 ) {
-    let lexed = RegexEntry::lex(to_lex).expect("Lexing failed");
+    let lexed = RegexEntry::lex(RegexToken::parse_with_spans(to_lex).expect("Lexing failed"));
     let grouped = RegexEntry::group(lexed);
-    let parsed = RegexEntry::parse_for_all_groups_recursively(grouped, &incremental_parser)
-        .expect("Recursive parsing failed");
+    let parsed = RegexEntry::parse_for_all_groups_recursively(grouped, &incremental_parser);
     assert_eq!(&parsed, expected);
 }
 
@@ -282,6 +803,7 @@ fn test_repetitions() {
             ])),
             min: 5,
             max: Some(6),
+            lazy: false,
         }],
     );
 }
@@ -300,6 +822,7 @@ fn test_nested_repetitions() {
                     )])),
                     min: 7,
                     max: Some(8),
+                    lazy: false,
                 },
                 PartiallyParsed::Repetition {
                     base: Box::new(PartiallyParsed::Lexed(
@@ -307,10 +830,12 @@ fn test_nested_repetitions() {
                     )),
                     min: 4,
                     max: None,
+                    lazy: false,
                 },
             ])),
             min: 5,
             max: Some(6),
+            lazy: false,
         }],
     );
 }
@@ -322,7 +847,11 @@ fn test_alternations_basic() {
         RegexEntry::parse_alternation,
         &vec![PartiallyParsed::Alternation(vec![
             PartiallyParsed::Lexed(RegexToken::AnyCharacter),
-            PartiallyParsed::Lexed(RegexToken::Repetition { min: 1, max: None }),
+            PartiallyParsed::Lexed(RegexToken::Repetition {
+                min: 1,
+                max: None,
+                lazy: false,
+            }),
         ])],
     );
 }
@@ -334,8 +863,16 @@ fn test_alternations_nested() {
         RegexEntry::parse_alternation,
         &vec![PartiallyParsed::Alternation(vec![
             PartiallyParsed::Lexed(RegexToken::AnyCharacter),
-            PartiallyParsed::Lexed(RegexToken::Repetition { min: 1, max: None }),
-            PartiallyParsed::Lexed(RegexToken::Repetition { min: 0, max: None }),
+            PartiallyParsed::Lexed(RegexToken::Repetition {
+                min: 1,
+                max: None,
+                lazy: false,
+            }),
+            PartiallyParsed::Lexed(RegexToken::Repetition {
+                min: 0,
+                max: None,
+                lazy: false,
+            }),
         ])],
     );
 }
@@ -348,8 +885,16 @@ fn test_alternations_nested_recursive() {
         &vec![PartiallyParsed::Alternation(vec![
             PartiallyParsed::Group(vec![PartiallyParsed::Alternation(vec![
                 PartiallyParsed::Lexed(RegexToken::AnyCharacter),
-                PartiallyParsed::Lexed(RegexToken::Repetition { min: 1, max: None }),
-                PartiallyParsed::Lexed(RegexToken::Repetition { min: 0, max: None }),
+                PartiallyParsed::Lexed(RegexToken::Repetition {
+                    min: 1,
+                    max: None,
+                    lazy: false,
+                }),
+                PartiallyParsed::Lexed(RegexToken::Repetition {
+                    min: 0,
+                    max: None,
+                    lazy: false,
+                }),
             ])]),
             PartiallyParsed::Group(vec![PartiallyParsed::Lexed(RegexToken::AnyCharacter)]),
         ])],
@@ -371,10 +916,30 @@ fn test_simple_parse() {
             min: 1,
             max: None,
             base: Box::new(RegexEntry::AnyCharacter),
+            lazy: false,
         },
     );
 }
 
+#[test]
+fn test_lazy_parse() {
+    test_full_parse(
+        "a+?b",
+        RegexEntry::Concatenation(vec![
+            RegexEntry::Repetition {
+                base: Box::new(RegexEntry::Literal('a')),
+                min: 1,
+                max: None,
+                lazy: true,
+            },
+            RegexEntry::Literal('b'),
+        ]),
+    );
+
+    //only one `?` is taken as the laziness of a quantifier, and quantifiers can not be stacked
+    assert!(RegexEntry::parse("a*??").is_err());
+}
+
 #[test]
 fn test_complex_parse_1() {
     use unic_ucd_category::GeneralCategory::*;
@@ -382,22 +947,32 @@ fn test_complex_parse_1() {
     test_full_parse(
         r#"((\d\PL)*){1,3}"#,
         Repetition {
-            base: Box::new(Repetition {
-                base: Box::new(Concatenation(vec![
-                    UnicodeCharacterClass(vec![DecimalNumber, OtherNumber, LetterNumber]),
-                    NegatedUnicodeCharacterClass(vec![
-                        UppercaseLetter,
-                        LowercaseLetter,
-                        TitlecaseLetter,
-                        ModifierLetter,
-                        OtherLetter,
-                    ]),
-                ])),
-                min: 0,
-                max: None,
+            base: Box::new(Capture {
+                index: 1,
+                name: None,
+                base: Box::new(Repetition {
+                    base: Box::new(Capture {
+                        index: 2,
+                        name: None,
+                        base: Box::new(Concatenation(vec![
+                            UnicodeCharacterClass(vec![DecimalNumber, OtherNumber, LetterNumber]),
+                            NegatedUnicodeCharacterClass(vec![
+                                UppercaseLetter,
+                                LowercaseLetter,
+                                TitlecaseLetter,
+                                ModifierLetter,
+                                OtherLetter,
+                            ]),
+                        ])),
+                    }),
+                    min: 0,
+                    max: None,
+                    lazy: false,
+                }),
             }),
             min: 1,
             max: Some(3),
+            lazy: false,
         },
     );
 }
@@ -410,32 +985,43 @@ fn test_complex_parse_2() {
     test_full_parse(
         r#"([A-Z]+[0-9]*)|(\d+)"#,
         Alternation(vec![
-            Concatenation(vec![
-                Repetition {
-                    base: Box::new(NonUnicodeCharacterClass(Range {
-                        start: 'A',
-                        end: 'Z',
-                    })),
+            Capture {
+                index: 1,
+                name: None,
+                base: Box::new(Concatenation(vec![
+                    Repetition {
+                        base: Box::new(NonUnicodeCharacterClass(Range {
+                            start: 'A',
+                            end: 'Z',
+                        })),
+                        min: 1,
+                        max: None,
+                        lazy: false,
+                    },
+                    Repetition {
+                        base: Box::new(NonUnicodeCharacterClass(Range {
+                            start: '0',
+                            end: '9',
+                        })),
+                        min: 0,
+                        max: None,
+                        lazy: false,
+                    },
+                ])),
+            },
+            Capture {
+                index: 2,
+                name: None,
+                base: Box::new(Repetition {
+                    base: Box::new(UnicodeCharacterClass(vec![
+                        DecimalNumber,
+                        OtherNumber,
+                        LetterNumber,
+                    ])),
                     min: 1,
                     max: None,
-                },
-                Repetition {
-                    base: Box::new(NonUnicodeCharacterClass(Range {
-                        start: '0',
-                        end: '9',
-                    })),
-                    min: 0,
-                    max: None,
-                },
-            ]),
-            Repetition {
-                base: Box::new(UnicodeCharacterClass(vec![
-                    DecimalNumber,
-                    OtherNumber,
-                    LetterNumber,
-                ])),
-                min: 1,
-                max: None,
+                    lazy: false,
+                }),
             },
         ]),
     );
@@ -457,13 +1043,44 @@ fn test_url() {
                 base: Box::new(Literal('s')),
                 min: 0,
                 max: Some(1),
+                lazy: false,
             },
             Literal(':'),
             Literal('/'),
             Literal('/'),
             Repetition {
-                base: Box::new(Concatenation(vec![
-                    Repetition {
+                base: Box::new(Capture {
+                    index: 1,
+                    name: None,
+                    base: Box::new(Concatenation(vec![
+                        Repetition {
+                            base: Box::new(NonUnicodeCharacterClass(Disjunction(vec![
+                                Range {
+                                    start: 'A',
+                                    end: 'Z',
+                                },
+                                Range {
+                                    start: 'a',
+                                    end: 'z',
+                                },
+                                Char('.'),
+                            ]))),
+                            min: 1,
+                            max: None,
+                            lazy: false,
+                        },
+                        Literal('/'),
+                    ])),
+                }),
+                min: 0,
+                max: None,
+                lazy: false,
+            },
+            Repetition {
+                base: Box::new(Capture {
+                    index: 2,
+                    name: None,
+                    base: Box::new(Repetition {
                         base: Box::new(NonUnicodeCharacterClass(Disjunction(vec![
                             Range {
                                 start: 'A',
@@ -477,31 +1094,244 @@ fn test_url() {
                         ]))),
                         min: 1,
                         max: None,
-                    },
-                    Literal('/'),
-                ])),
-                min: 0,
-                max: None,
-            },
-            Repetition {
-                base: Box::new(Repetition {
-                    base: Box::new(NonUnicodeCharacterClass(Disjunction(vec![
-                        Range {
-                            start: 'A',
-                            end: 'Z',
-                        },
-                        Range {
-                            start: 'a',
-                            end: 'z',
-                        },
-                        Char('.'),
-                    ]))),
-                    min: 1,
-                    max: None,
+                        lazy: false,
+                    }),
                 }),
                 min: 0,
                 max: Some(1),
+                lazy: false,
             },
         ]),
     );
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_round_trip() {
+    let parsed = RegexEntry::parse(r#"([A-Z]+[^0-9]*)|(\d+\PL?\p{Greek})"#).unwrap();
+
+    let json = serde_json::to_string(&parsed).unwrap();
+    assert!(json.contains(r#""Nd""#));
+    assert!(json.contains(r#""Greek""#));
+
+    let deserialized: RegexEntry = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, parsed);
+}
+
+#[test]
+fn test_alternation_precedence() {
+    use RegexEntry::*;
+
+    let literals = |text: &str| Concatenation(text.chars().map(Literal).collect());
+    test_full_parse(
+        "^foo|bar$",
+        Alternation(vec![
+            Concatenation(vec![StartOfInput, Literal('f'), Literal('o'), Literal('o')]),
+            Concatenation(vec![Literal('b'), Literal('a'), Literal('r'), EndOfInput]),
+        ]),
+    );
+    test_full_parse(
+        "ab|c(d|ef)",
+        Alternation(vec![
+            literals("ab"),
+            Concatenation(vec![
+                Literal('c'),
+                Capture {
+                    index: 1,
+                    name: None,
+                    base: Box::new(Alternation(vec![Literal('d'), literals("ef")])),
+                },
+            ]),
+        ]),
+    );
+}
+
+#[test]
+fn test_repeated_alternation() {
+    assert!(RegexEntry::parse("a|?b").is_err());
+}
+
+#[test]
+fn test_empty_alternation_branches() {
+    use crate::automata::Automaton;
+
+    let empty = || RegexEntry::Concatenation(Vec::new());
+    test_full_parse(
+        "(a|)",
+        RegexEntry::Capture {
+            index: 1,
+            name: None,
+            base: Box::new(RegexEntry::Alternation(vec![
+                RegexEntry::Literal('a'),
+                empty(),
+            ])),
+        },
+    );
+    test_full_parse(
+        "|a||b",
+        RegexEntry::Alternation(vec![
+            empty(),
+            RegexEntry::Literal('a'),
+            empty(),
+            RegexEntry::Literal('b'),
+        ]),
+    );
+
+    let mut automaton = Automaton::from_regex(RegexEntry::parse("x(|a)y").unwrap());
+    automaton.simplify();
+    assert!(automaton.is_match("xy"));
+    assert!(automaton.is_match("xay"));
+    assert!(!automaton.is_match("xaay"));
+}
+
+#[test]
+fn test_nesting_depth_limit() {
+    use crate::automata::Automaton;
+
+    let nested = |depth: usize| format!("{}a{}", "(".repeat(depth), ")".repeat(depth));
+
+    //the deepest accepted nesting also fits on a test thread's stack once turned into an automaton
+    let mut automaton =
+        Automaton::from_regex(RegexEntry::parse(&nested(DEFAULT_MAX_NESTING_DEPTH)).unwrap());
+    automaton.simplify();
+
+    assert!(RegexEntry::parse(&nested(DEFAULT_MAX_NESTING_DEPTH + 1)).is_err());
+    assert!(RegexEntry::parse(&nested(100_000)).is_err());
+
+    assert!(RegexEntry::parse_with_max_depth(&nested(3), 3).is_ok());
+    assert!(RegexEntry::parse_with_max_depth(&nested(4), 3).is_err());
+    assert!(RegexEntry::parse_with_max_depth("((a)?)*(b)", 2).is_ok());
+}
+
+#[test]
+fn test_unbalanced_groups() {
+    for (pattern, span) in [
+        ("(", 0..1),
+        (")", 0..1),
+        ("a)", 1..2),
+        ("(a)b)", 4..5),
+        ("x(?:a(b)", 1..8),
+    ] {
+        let error = RegexEntry::parse(pattern).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::InvalidGroup, "{pattern}");
+        assert_eq!(error.span, span, "{pattern}");
+    }
+
+    assert!(RegexEntry::parse(r"\(a\)").is_ok());
+    assert!(RegexEntry::parse("[(]a[)]").is_ok());
+}
+
+#[test]
+fn test_largest_repetition_count() {
+    assert_eq!(RegexEntry::parse("abc").unwrap().largest_repetition_count(), 0);
+    assert_eq!(RegexEntry::parse("a*b+").unwrap().largest_repetition_count(), 1);
+    assert_eq!(
+        RegexEntry::parse("(a{2,300}|(b{5,}))c{4}")
+            .unwrap()
+            .largest_repetition_count(),
+        300
+    );
+}
+
+#[test]
+fn test_named_captures() {
+    let parsed = RegexEntry::parse("(?P<key>[a-z]+)=((?<value>[a-z]*)|(\\d+))").unwrap();
+    assert_eq!(parsed.capture_count(), 4);
+    assert_eq!(
+        parsed.capture_names(),
+        vec![None, Some("key"), None, Some("value"), None]
+    );
+
+    assert!(RegexEntry::parse("(?P<a>x)|(?<a>y)").is_err());
+    assert!(RegexEntry::parse("(?P<a>x)(?P<b>y)").is_ok());
+}
+
+#[test]
+fn test_ascii_shorthand_classes() {
+    let options = ParseOptions {
+        unicode: false,
+        ..ParseOptions::default()
+    };
+    let parsed = RegexEntry::parse_with_options(r"\d\D\w\W\s\S\p{Nd}", options).unwrap();
+    assert_eq!(
+        parsed.to_string(),
+        r"[0-9][^0-9][0-9A-Za-z_][^0-9A-Za-z_][\t-\r ][^\t-\r ]\p{Nd}"
+    );
+
+    let RegexEntry::NonUnicodeCharacterClass(digit) =
+        RegexEntry::parse_with_options(r"\d", options).unwrap()
+    else {
+        panic!("\\d did not parse to a class");
+    };
+    assert!(digit.matches('7'));
+    assert!(!digit.matches('\u{663}'));
+}
+
+#[test]
+fn test_unicode_flag() {
+    //cleared, the flag makes the shorthands ASCII, up to the end of its group
+    let parsed = RegexEntry::parse(r"(?-u:\d[\w])\d").unwrap();
+    assert_eq!(parsed.to_string(), r"[0-9][0-9A-Za-z_]\d");
+
+    //in a pattern matched against bytes, it also makes literals, classes and `.` single bytes
+    let options = ParseOptions {
+        bytes: true,
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        RegexEntry::parse_with_options(r"(?-u)\xFF[a-c].é☃", options).unwrap(),
+        RegexEntry::Concatenation(vec![
+            RegexEntry::Byte(CharacterClass::Char('\u{FF}')),
+            RegexEntry::Byte(CharacterClass::Range {
+                start: 'a',
+                end: 'c'
+            }),
+            RegexEntry::Byte(CharacterClass::Range {
+                start: '\0',
+                end: '\u{FF}'
+            }),
+            RegexEntry::Byte(CharacterClass::Char('é')),
+            RegexEntry::Literal('☃'),
+        ])
+    );
+    assert_eq!(
+        RegexEntry::parse_with_options(r"(?-u)\xFF", ParseOptions::default()).unwrap(),
+        RegexEntry::Literal('\u{FF}')
+    );
+    let parsed = RegexEntry::parse_with_options(r"(?-u:[\x80-\xFF])", options).unwrap();
+    assert_eq!(
+        RegexEntry::parse_with_options(&parsed.to_string(), options).unwrap(),
+        parsed
+    );
+}
+
+#[test]
+fn test_full_case_folding() {
+    use crate::automata::Automaton;
+    use crate::case_folding::CaseFolding;
+
+    let options = ParseOptions {
+        case_folding: CaseFolding::Full,
+        ..ParseOptions::default()
+    };
+    let matches = |pattern: &str, input: &str| {
+        Automaton::from_regex(RegexEntry::parse_with_options(pattern, options).unwrap())
+            .is_match(input)
+    };
+
+    //a run of literals is folded as a whole, so `ss` matches `ß` even across a scoped flag
+    assert!(matches("(?i)stra\u{DF}e", "STRASSE"));
+    assert!(matches("(?i)ss", "\u{DF}"));
+    assert!(matches("(?i)s(?i:s)", "\u{1E9E}"));
+    assert!(matches("(?i)\u{FB03}", "FFI"));
+    assert!(!matches("(?i)ss", "s"));
+    //without the flag, literals are only themselves
+    assert!(!matches("\u{DF}", "ss"));
+    assert!(!matches("s(?i)s", "\u{DF}"));
+
+    //literals that no other characters fold together with are folded as simple folding would
+    assert_eq!(
+        RegexEntry::parse_with_options("(?i)ab", options).unwrap(),
+        RegexEntry::parse("(?i)ab").unwrap()
+    );
+}
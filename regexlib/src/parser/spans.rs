@@ -0,0 +1,279 @@
+use std::ops::Range;
+
+use crate::parser::dialect::Dialect;
+use crate::parser::tokenizer::RegexToken;
+use crate::parser::{ParseError, ParseOptions, RegexEntry};
+
+/// The byte range of the pattern that each node of a [`RegexEntry`] was parsed from. The tree has
+/// the same shape as the entry it describes: `children` holds the spans of the members of a
+/// concatenation or alternation, or the single base of a repetition or capture group.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntrySpans {
+    pub span: Range<usize>,
+    pub children: Vec<EntrySpans>,
+}
+
+impl RegexEntry {
+    /// Like [`parse`](Self::parse), but also returns where in `regex` each part of the parsed
+    /// entry came from.
+    pub fn parse_with_spans(regex: &str) -> Result<(Self, EntrySpans), ParseError> {
+        Self::parse_with_spans_in_dialect(regex, Dialect::Fastregex)
+    }
+
+    /// Like [`parse_with_spans`](Self::parse_with_spans), but reads the regex in `dialect`.
+    pub fn parse_with_spans_in_dialect(
+        regex: &str,
+        dialect: Dialect,
+    ) -> Result<(Self, EntrySpans), ParseError> {
+        Self::parse_with_spans_and_options(
+            regex,
+            ParseOptions {
+                dialect,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Like [`parse_with_spans`](Self::parse_with_spans), but reads the regex as `options` say.
+    pub fn parse_with_spans_and_options(
+        regex: &str,
+        options: ParseOptions,
+    ) -> Result<(Self, EntrySpans), ParseError> {
+        let parsed = Self::parse_with_options(regex, options)?;
+
+        let mut assigner = SpanAssigner {
+            tokens: without_flag_tokens(RegexToken::parse_with_spans_and_options(regex, options)?),
+            next_token: 0,
+            pattern_len: regex.len(),
+        };
+        let spans = assigner.spans_of(&parsed);
+
+        Ok((parsed, spans))
+    }
+
+    /// The innermost part of the entry whose span contains the byte at `offset` of the pattern,
+    /// along with its spans, or `None` if `offset` is outside of the pattern. `spans` are the
+    /// entry's spans, as returned by [`parse_with_spans`](Self::parse_with_spans). A byte that
+    /// belongs to no part of its own, like the `|` of an alternation, gives the part around it.
+    pub fn entry_at<'entry>(
+        &'entry self,
+        spans: &'entry EntrySpans,
+        offset: usize,
+    ) -> Option<(&'entry RegexEntry, &'entry EntrySpans)> {
+        if !spans.span.contains(&offset) {
+            return None;
+        }
+
+        let children = match self {
+            RegexEntry::Concatenation(entries) | RegexEntry::Alternation(entries) => {
+                entries.iter().collect()
+            }
+            RegexEntry::Repetition { base, .. } | RegexEntry::Capture { base, .. } => {
+                vec![base.as_ref()]
+            }
+            _ => Vec::new(),
+        };
+        children
+            .into_iter()
+            .zip(&spans.children)
+            .find_map(|(child, child_spans)| child.entry_at(child_spans, offset))
+            .or(Some((self, spans)))
+    }
+}
+
+//flags leave nothing in the parsed entry, and neither do the parentheses of a `(?flags:...)` group, whose contents are merged into the enclosing concatenation
+fn without_flag_tokens(tokens: Vec<(RegexToken, Range<usize>)>) -> Vec<(RegexToken, Range<usize>)> {
+    //whether each open group is a flag group
+    let mut open_groups = Vec::new();
+    tokens
+        .into_iter()
+        .filter(|(token, _)| match token {
+            RegexToken::SetFlags(_) => false,
+            RegexToken::OpenFlagGroup(_) => {
+                open_groups.push(true);
+                false
+            }
+            RegexToken::OpenGroup | RegexToken::OpenNamedGroup(_) => {
+                open_groups.push(false);
+                true
+            }
+            RegexToken::CloseGroup => !open_groups.pop().unwrap_or(false),
+            _ => true,
+        })
+        .collect()
+}
+
+//Every node of the parsed entry consumes its tokens in pattern order (a repetition's base comes
+//before its quantifier, alternation members are separated by `|` and so on), so the spans can be
+//recovered by walking the entry while stepping through the tokens.
+struct SpanAssigner {
+    tokens: Vec<(RegexToken, Range<usize>)>,
+    next_token: usize,
+    pattern_len: usize,
+}
+
+impl SpanAssigner {
+    fn spans_of(&mut self, entry: &RegexEntry) -> EntrySpans {
+        match entry {
+            RegexEntry::AnyCharacter
+            | RegexEntry::UnicodeCharacterClass(_)
+            | RegexEntry::NegatedUnicodeCharacterClass(_)
+            | RegexEntry::UnicodeScript(_)
+            | RegexEntry::NegatedUnicodeScript(_)
+            | RegexEntry::GraphemeCluster
+            | RegexEntry::NonUnicodeCharacterClass(_)
+            | RegexEntry::Byte(_)
+            | RegexEntry::Literal(_)
+            | RegexEntry::StartOfInput
+            | RegexEntry::EndOfInput
+            | RegexEntry::StartOfLine(_)
+            | RegexEntry::EndOfLine(_)
+            | RegexEntry::WordStart
+            | RegexEntry::WordEnd => EntrySpans {
+                span: self.take_token(),
+                children: Vec::new(),
+            },
+            //every character of the run was a literal token of its own
+            RegexEntry::FoldedLiteral(literal) => {
+                let spans = literal
+                    .chars()
+                    .map(|_| self.take_token())
+                    .collect::<Vec<_>>();
+                EntrySpans {
+                    span: spans[0].start..spans[spans.len() - 1].end,
+                    children: Vec::new(),
+                }
+            }
+            RegexEntry::Concatenation(entries) => {
+                let children = entries
+                    .iter()
+                    .map(|entry| self.spans_of(entry))
+                    .collect();
+                self.enclosing(children)
+            }
+            RegexEntry::Alternation(entries) => {
+                let mut children = Vec::new();
+                for (index, entry) in entries.iter().enumerate() {
+                    if index > 0 {
+                        self.take_token();
+                    }
+                    children.push(self.spans_of(entry));
+                }
+                self.enclosing(children)
+            }
+            RegexEntry::Repetition { base, .. } => {
+                let base = self.spans_of(base);
+                let quantifier = self.take_token();
+                EntrySpans {
+                    span: base.span.start..quantifier.end,
+                    children: vec![base],
+                }
+            }
+            RegexEntry::Capture { base, .. } => {
+                let open = self.take_token();
+                let base = self.spans_of(base);
+                let end = self.take_token().end;
+                EntrySpans {
+                    span: open.start..end,
+                    children: vec![base],
+                }
+            }
+        }
+    }
+
+    fn enclosing(&self, children: Vec<EntrySpans>) -> EntrySpans {
+        let span = match (children.first(), children.last()) {
+            (Some(first), Some(last)) => first.span.start..last.span.end,
+            _ => {
+                let position = self.position();
+                position..position
+            }
+        };
+        EntrySpans { span, children }
+    }
+
+    fn take_token(&mut self) -> Range<usize> {
+        let span = match self.tokens.get(self.next_token) {
+            Some((_, span)) => span.clone(),
+            None => self.pattern_len..self.pattern_len,
+        };
+        self.next_token += 1;
+        span
+    }
+
+    fn position(&self) -> usize {
+        match self.tokens.get(self.next_token) {
+            Some((_, span)) => span.start,
+            None => self.pattern_len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::RegexEntry;
+
+    #[test]
+    fn test_spans() {
+        let pattern = r"ab(c|\d)*";
+        let (_, spans) = RegexEntry::parse_with_spans(pattern).unwrap();
+
+        assert_eq!(spans.span, 0..pattern.len());
+        assert_eq!(
+            spans
+                .children
+                .iter()
+                .map(|child| &pattern[child.span.clone()])
+                .collect::<Vec<_>>(),
+            vec!["a", "b", r"(c|\d)*"]
+        );
+
+        let capture = &spans.children[2].children[0];
+        assert_eq!(&pattern[capture.span.clone()], r"(c|\d)");
+        let alternation = &capture.children[0];
+        assert_eq!(
+            alternation
+                .children
+                .iter()
+                .map(|child| &pattern[child.span.clone()])
+                .collect::<Vec<_>>(),
+            vec!["c", r"\d"]
+        );
+    }
+
+    #[test]
+    fn test_entry_at() {
+        let pattern = r"ab(c|\d)*";
+        let (entry, spans) = RegexEntry::parse_with_spans(pattern).unwrap();
+        let text_at = |offset| {
+            entry
+                .entry_at(&spans, offset)
+                .map(|(_, spans)| &pattern[spans.span.clone()])
+        };
+
+        assert_eq!(text_at(1), Some("b"));
+        assert_eq!(text_at(2), Some(r"(c|\d)"));
+        assert_eq!(text_at(4), Some(r"c|\d"));
+        assert_eq!(text_at(6), Some(r"\d"));
+        assert_eq!(text_at(8), Some(r"(c|\d)*"));
+        assert_eq!(text_at(9), None);
+
+        let (part, _) = entry.entry_at(&spans, 5).unwrap();
+        assert!(matches!(part, RegexEntry::UnicodeCharacterClass(_)));
+    }
+
+    #[test]
+    fn test_flag_spans() {
+        let pattern = "(?i)a(?-i:b(c))*";
+        let (_, spans) = RegexEntry::parse_with_spans(pattern).unwrap();
+
+        assert_eq!(
+            spans
+                .children
+                .iter()
+                .map(|child| &pattern[child.span.clone()])
+                .collect::<Vec<_>>(),
+            vec!["a", "b(c))*"]
+        );
+    }
+}
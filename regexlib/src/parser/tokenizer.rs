@@ -1,41 +1,262 @@
+use std::ops::Range;
+
 use unic_ucd_category::GeneralCategory;
 
+use crate::line_terminator::LineTerminator;
 use crate::parser::character_class::CharacterClass;
+use crate::parser::dialect::{try_parse_dialect_token, Dialect};
+use crate::parser::error::{LexError, ParseError, ParseErrorKind};
+use crate::parser::flags::{try_parse_set_flags, Flag, TokenizerFlags};
+use crate::parser::{ParseOptions, MAX_REPETITION_BOUND};
+use crate::script::Script;
 
+/// One piece of a pattern as it is written, like a literal, a class or a parenthesis.
+/// [`parse_with_spans`](Self::parse_with_spans) gives each token with the bytes of the pattern it
+/// was read from, for tools that describe a pattern piece by piece; parsing into a
+/// [`RegexEntry`](super::RegexEntry) keeps those spans in its
+/// [`EntrySpans`](super::spans::EntrySpans).
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum RegexToken {
     AnyCharacter,
     UnicodeCharacterClass(Vec<GeneralCategory>),
     NegatedUnicodeCharacterClass(Vec<GeneralCategory>),
     NonUnicodeCharacterClass(CharacterClass),
+    UnicodeScript(Script),
+    NegatedUnicodeScript(Script),
+    /// `\X`, which matches one extended grapheme cluster.
+    GraphemeCluster,
     Alternation,
     OpenGroup,
+    OpenNamedGroup(String),
     CloseGroup,
-    Repetition { min: u64, max: Option<u64> },
+    /// `(?i)` and the like, which set or clear flags for the rest of the enclosing group.
+    SetFlags(Vec<(Flag, bool)>),
+    /// `(?i:` and the like, which open a non-capturing group with flags set or cleared for its
+    /// contents.
+    OpenFlagGroup(Vec<(Flag, bool)>),
+    StartOfInput,
+    EndOfInput,
+    /// `^` with the `m` flag set.
+    StartOfLine(LineTerminator),
+    /// `$` with the `m` flag set.
+    EndOfLine(LineTerminator),
+    /// `\b{start}`.
+    WordStart,
+    /// `\b{end}`.
+    WordEnd,
+    /// A literal, bracketed class or `.` read as a single byte, which only the flags make (see
+    /// [`Flag::Unicode`]).
+    Byte(CharacterClass),
+    Repetition {
+        min: u64,
+        max: Option<u64>,
+        lazy: bool,
+    },
     Literal(char),
 }
 
 impl RegexToken {
-    pub fn parse(regex: impl AsRef<str>) -> Result<Vec<RegexToken>, String> {
+    /// The categories `\w` matches: letters, marks, decimal digits, letter numbers (like `Ⅻ`) and
+    /// connector punctuation (like `_`), which is what Unicode's definition of a word character
+    /// comes to in general categories.
+    pub const WORD_CATEGORIES: [GeneralCategory; 11] = [
+        GeneralCategory::UppercaseLetter,
+        GeneralCategory::LowercaseLetter,
+        GeneralCategory::TitlecaseLetter,
+        GeneralCategory::ModifierLetter,
+        GeneralCategory::OtherLetter,
+        GeneralCategory::NonspacingMark,
+        GeneralCategory::SpacingMark,
+        GeneralCategory::EnclosingMark,
+        GeneralCategory::DecimalNumber,
+        GeneralCategory::LetterNumber,
+        GeneralCategory::ConnectorPunctuation,
+    ];
+
+    /// The categories `\d` matches: decimal digits, letter numbers and other numbers (like `²`).
+    pub const DIGIT_CATEGORIES: [GeneralCategory; 3] = [
+        GeneralCategory::DecimalNumber,
+        GeneralCategory::OtherNumber,
+        GeneralCategory::LetterNumber,
+    ];
+
+    /// The characters that have a meaning of their own in a pattern, which are matched literally
+    /// when escaped with a `\`.
+    pub const METACHARACTERS: &'static str = ".*+?()[]{}|\\^$";
+
+    pub fn parse(regex: impl AsRef<str>) -> Result<Vec<RegexToken>, ParseError> {
+        Ok(Self::parse_with_spans(regex)?
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect())
+    }
+
+    /// Like [`parse`](Self::parse), but also returns the byte range of the regex each token was
+    /// parsed from.
+    pub fn parse_with_spans(
+        regex: impl AsRef<str>,
+    ) -> Result<Vec<(RegexToken, Range<usize>)>, ParseError> {
+        Self::parse_with_spans_and_options(regex, ParseOptions::default())
+    }
+
+    /// Like [`parse_with_spans`](Self::parse_with_spans), but reads the regex as `options` say.
+    pub fn parse_with_spans_and_options(
+        regex: impl AsRef<str>,
+        options: ParseOptions,
+    ) -> Result<Vec<(RegexToken, Range<usize>)>, ParseError> {
         //this function is somewhat a parser combinator: https://en.wikipedia.org/wiki/Parser_combinator
-        let mut remaining_regex = regex.as_ref();
+        let regex = regex.as_ref();
+        let mut remaining_regex = regex;
 
         let mut entries = Vec::new();
-        while !remaining_regex.is_empty() {
-            match Self::try_parse_one_entry(remaining_regex) {
+        let mut flags = TokenizerFlags::for_options(options);
+        loop {
+            remaining_regex = flags.skip_ignored(remaining_regex);
+            if remaining_regex.is_empty() {
+                break;
+            }
+
+            //a quoted run and `\R` are the places a single step makes more than one token
+            if let Some(new_remaining_regex) = Self::try_parse_line_break(remaining_regex, options)
+            {
+                let start = regex.len() - remaining_regex.len();
+                let end = regex.len() - new_remaining_regex.len();
+                entries.extend(
+                    Self::line_break_tokens(options.line_terminator)
+                        .map(|token| (token, start..end)),
+                );
+                remaining_regex = new_remaining_regex;
+                continue;
+            }
+            if let Some((quoted, new_remaining_regex)) =
+                Self::try_parse_quoted(remaining_regex, options.dialect)
+            {
+                let quoted_start = regex.len() - remaining_regex.len() + "\\Q".len();
+                entries.extend(quoted.char_indices().map(|(index, char)| {
+                    let start = quoted_start + index;
+                    (RegexToken::Literal(char), start..start + char.len_utf8())
+                }));
+                remaining_regex = new_remaining_regex;
+                continue;
+            }
+
+            if let Some((construct, length)) = Self::find_unsupported(remaining_regex) {
+                let start = regex.len() - remaining_regex.len();
+                return Err(ParseError::new(
+                    ParseErrorKind::Unsupported,
+                    start..start + length,
+                    format!(
+                        "{construct}, like \"{}\", are not supported by fastregex because it compiles to a finite automaton",
+                        &remaining_regex[..length]
+                    ),
+                ));
+            }
+
+            let entry = match flags.try_parse_escaped_ignored(remaining_regex) {
+                Some(tuple) => Ok(Some(tuple)),
+                None => Self::try_parse_one_entry(remaining_regex, flags.options(options)),
+            };
+            match entry {
                 Ok(Some((entry, new_remaining_regex))) => {
-                    entries.push(entry);
+                    let start = regex.len() - remaining_regex.len();
+                    let end = regex.len() - new_remaining_regex.len();
+                    flags.read(&entry);
+                    entries.push((entry, start..end));
                     remaining_regex = new_remaining_regex;
                 }
-                Ok(None) => return Err(format!("Failed to parse regex remaining at because no tokens matched: {remaining_regex}")),
-                Err(msg) => return Err(format!("Error occurred with remaining regex \"{remaining_regex}\": {msg}")),
+                Ok(None) => unreachable!("any character can be read as a literal"),
+                Err(error) => return Err(error.in_pattern(regex)),
             }
         }
 
         Ok(entries)
     }
 
-    fn try_parse_one_entry(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
+    //`\Q...\E`, which matches the text between them literally, metacharacters and all; without a `\E`, the rest of the pattern is quoted
+    fn try_parse_quoted(remaining: &str, dialect: Dialect) -> Option<(&str, &str)> {
+        if dialect == Dialect::PosixEre {
+            return None;
+        }
+        let quoted = remaining.strip_prefix("\\Q")?;
+        Some(match quoted.find("\\E") {
+            Some(end) => (&quoted[..end], &quoted[end + "\\E".len()..]),
+            None => (quoted, ""),
+        })
+    }
+
+    //constructs that need to remember or look around the input, which no finite automaton can; naming them explains more than the error they would otherwise run into
+    fn find_unsupported(remaining: &str) -> Option<(&'static str, usize)> {
+        const GROUPS: [(&str, &str); 5] = [
+            ("(?=", "Lookaheads"),
+            ("(?!", "Lookaheads"),
+            ("(?<=", "Lookbehinds"),
+            ("(?<!", "Lookbehinds"),
+            ("(?>", "Atomic groups"),
+        ];
+        if let Some((opening, construct)) = GROUPS
+            .into_iter()
+            .find(|(opening, _)| remaining.starts_with(opening))
+        {
+            return Some((construct, opening.len()));
+        }
+
+        let escaped = remaining.strip_prefix('\\')?;
+        let number_len = |text: &str| {
+            text.find(|char: char| !char.is_ascii_digit())
+                .unwrap_or(text.len())
+        };
+        //`\1`, `\g1`, `\g-1`, and names or numbers in brackets like `\k<name>` and `\g{1}`
+        let reference_len = match escaped.chars().next()? {
+            '1'..='9' => number_len(escaped),
+            'g' if escaped[1..].starts_with(|char: char| char.is_ascii_digit()) => {
+                1 + number_len(&escaped[1..])
+            }
+            'g' if escaped[1..].starts_with('-') => 2 + number_len(&escaped[2..]),
+            'k' | 'g' => {
+                let close = match escaped[1..].chars().next()? {
+                    '<' => '>',
+                    '{' => '}',
+                    '\'' => '\'',
+                    _ => return None,
+                };
+                match escaped[2..].find(close) {
+                    Some(end) => 2 + end + 1,
+                    None => escaped.len(),
+                }
+            }
+            _ => return None,
+        };
+        Some(("Backreferences", "\\".len() + reference_len))
+    }
+
+    //`\R`, which matches any one line terminator, counting `\r\n` as one where `\r` is one
+    fn try_parse_line_break(remaining: &str, options: ParseOptions) -> Option<&str> {
+        if options.dialect != Dialect::Fastregex {
+            return None;
+        }
+        remaining.strip_prefix("\\R")
+    }
+
+    //the tokens of what `\R` stands for: `(?:\n)` if only `\n` ends a line, or the likes of `(?:\r\n|\v)` otherwise
+    fn line_break_tokens(terminator: LineTerminator) -> impl Iterator<Item = RegexToken> {
+        let crlf = [
+            RegexToken::Literal('\r'),
+            RegexToken::Literal('\n'),
+            RegexToken::Alternation,
+        ];
+        let one = match terminator.class() {
+            CharacterClass::Char(char) => RegexToken::Literal(char),
+            class => RegexToken::NonUnicodeCharacterClass(class),
+        };
+        std::iter::once(RegexToken::OpenFlagGroup(Vec::new()))
+            .chain(crlf.into_iter().filter(move |_| terminator.joins_crlf()))
+            .chain([one, RegexToken::CloseGroup])
+    }
+
+    fn try_parse_one_entry(
+        remaining: &str,
+        options: ParseOptions,
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
         macro_rules! try_entry {
             ($fn_path: path) => {
                 if let Some(tuple) = $fn_path(remaining)? {
@@ -44,119 +265,454 @@ impl RegexToken {
             };
         }
 
+        //whatever the dialect reads differently takes precedence over fastregex's own syntax
+        if let Some(tuple) = try_parse_dialect_token(remaining, options.dialect)? {
+            return Ok(Some(tuple));
+        }
+        if !options.unicode {
+            if let Some(tuple) = Self::try_parse_ascii_shorthand(remaining)? {
+                return Ok(Some(tuple));
+            }
+        }
+
+        try_entry!(Self::try_parse_escaped_metacharacter);
+        try_entry!(Self::try_parse_escaped_literal);
+        try_entry!(Self::try_parse_open_named_group);
+        try_entry!(try_parse_set_flags);
         try_entry!(Self::try_parse_open_group);
         try_entry!(Self::try_parse_close_group);
         try_entry!(Self::try_parse_dot);
+        try_entry!(Self::try_parse_start_of_input);
+        try_entry!(Self::try_parse_end_of_input);
+        try_entry!(Self::try_parse_word_start);
+        try_entry!(Self::try_parse_word_end);
         try_entry!(Self::try_parse_zero_or_more);
         try_entry!(Self::try_parse_one_or_more);
         try_entry!(Self::try_parse_optional);
         try_entry!(Self::try_parse_alternation);
         try_entry!(Self::try_parse_digit);
         try_entry!(Self::try_parse_not_digit);
-        //we must parse the multi letter case here first so that \p{ is not seen as a single-unicode class name with the invalid identifier '{'. We could simply move on on such failures, but it is more user-friendly to return a useful error in the case of unknown class names
-        try_entry!(Self::try_parse_multi_letter_unicode_class_name);
-        try_entry!(Self::try_parse_one_letter_unicode_class_name);
-        //we must parse the multi letter case here first so that \p{ is not seen as a single-unicode class name with the invalid identifier '{'. We could simply move on on such failures, but it is more user-friendly to return a useful error in the case of unknown class names
-        try_entry!(Self::try_parse_negated_multi_letter_unicode_class_name);
-        try_entry!(Self::try_parse_negated_one_letter_unicode_class_name);
-        try_entry!(Self::try_parse_character_class);
+        try_entry!(Self::try_parse_word);
+        try_entry!(Self::try_parse_not_word);
+        try_entry!(Self::try_parse_whitespace);
+        try_entry!(Self::try_parse_not_whitespace);
+        try_entry!(Self::try_parse_horizontal_whitespace);
+        try_entry!(Self::try_parse_not_horizontal_whitespace);
+        try_entry!(Self::try_parse_vertical_whitespace);
+        try_entry!(Self::try_parse_not_vertical_whitespace);
+        try_entry!(Self::try_parse_grapheme_cluster);
+        try_entry!(Self::try_parse_unicode_property);
+        if let Some(tuple) = Self::try_parse_character_class(remaining, options.unicode)? {
+            return Ok(Some(tuple));
+        }
         try_entry!(Self::try_parse_repetition);
+        try_entry!(Self::try_parse_other_escape);
         try_entry!(Self::try_parse_single_char);
 
         Ok(None)
     }
 
-    fn try_parse_open_group(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
+    fn try_parse_escaped_metacharacter(
+        remaining: &str,
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
+        let Some(remaining) = remaining.strip_prefix('\\') else {
+            return Ok(None);
+        };
+
+        let mut chars = remaining.chars();
+        match chars.next() {
+            Some(escaped) if Self::METACHARACTERS.contains(escaped) => {
+                Ok(Some((RegexToken::Literal(escaped), chars.as_str())))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn try_parse_escaped_literal(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Ok(Self::try_parse_char_escape(remaining)?
+            .map(|(char, remaining)| (RegexToken::Literal(char), remaining)))
+    }
+
+    /// Parses an escape that stands for a single character which is hard to type or see in a
+    /// pattern: one of the control escapes `\n`, `\t`, `\r` and `\f`, an octal escape like `\012`,
+    /// a control-character escape like `\cJ`, or a codepoint escape.
+    pub(super) fn try_parse_char_escape(remaining: &str) -> Result<Option<(char, &str)>, LexError> {
+        if let Some(tuple) = Self::try_parse_control_escape(remaining) {
+            return Ok(Some(tuple));
+        }
+        if let Some(tuple) = Self::try_parse_octal_escape(remaining) {
+            return Ok(Some(tuple));
+        }
+        if let Some(tuple) = Self::try_parse_control_char_escape(remaining)? {
+            return Ok(Some(tuple));
+        }
+
+        Self::try_parse_codepoint_escape(remaining)
+    }
+
+    fn try_parse_control_escape(remaining: &str) -> Option<(char, &str)> {
+        let mut chars = remaining.strip_prefix('\\')?.chars();
+        let char = match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            'f' => '\x0C',
+            _ => return None,
+        };
+        Some((char, chars.as_str()))
+    }
+
+    //`\0` followed by up to two more octal digits, as in Perl and PCRE; without the leading 0, `\12` would be a backreference there
+    fn try_parse_octal_escape(remaining: &str) -> Option<(char, &str)> {
+        let octal = remaining.strip_prefix("\\0")?;
+        let digits = octal
+            .bytes()
+            .take(2)
+            .take_while(|digit| (b'0'..=b'7').contains(digit))
+            .count();
+        let value = u8::from_str_radix(&octal[..digits], 8).unwrap_or(0);
+        Some((char::from(value), &octal[digits..]))
+    }
+
+    //`\cX` is the control character typed as Ctrl+X: the letter (or one of `@[\]^_`) with bit 6 flipped, so `\cJ` is `\n`, and `\c?` is DEL
+    fn try_parse_control_char_escape(remaining: &str) -> Result<Option<(char, &str)>, LexError> {
+        let Some(control) = remaining.strip_prefix("\\c") else {
+            return Ok(None);
+        };
+
+        let mut chars = control.chars();
+        match chars.next() {
+            Some(letter @ ('@'..='_' | 'a'..='z' | '?')) => {
+                let value = letter.to_ascii_uppercase() as u8 ^ 0x40;
+                Ok(Some((char::from(value), chars.as_str())))
+            }
+            _ => Err(LexError::between(
+                ParseErrorKind::InvalidEscape,
+                "\\c must be followed by a letter or one of @[\\]^_?, like \\cJ",
+                remaining,
+                chars.as_str(),
+            )),
+        }
+    }
+
+    //`\xHH` (exactly two hexadecimal digits) or `\u{XXXX}` (one to six of them)
+    fn try_parse_codepoint_escape(remaining: &str) -> Result<Option<(char, &str)>, LexError> {
+        let invalid = |message: String, after: &str| {
+            LexError::between(ParseErrorKind::InvalidEscape, message, remaining, after)
+        };
+
+        let (digits, rest) = if let Some(hex) = remaining.strip_prefix("\\x") {
+            match hex.get(..2) {
+                Some(digits) => (digits, &hex[2..]),
+                None => {
+                    return Err(invalid(
+                        "A \\x escape needs two hexadecimal digits".into(),
+                        "",
+                    ))
+                }
+            }
+        } else if let Some(unicode) = remaining.strip_prefix("\\u") {
+            let Some(braced) = unicode.strip_prefix('{') else {
+                return Err(invalid(
+                    "A \\u escape needs its hexadecimal digits in braces, like \\u{263A}".into(),
+                    unicode,
+                ));
+            };
+            let Some(end) = braced.find('}') else {
+                return Err(invalid("Unfinished \\u{...} escape".into(), ""));
+            };
+            if end > 6 {
+                return Err(invalid(
+                    format!(
+                        "\\u{{{}}} has more digits than any codepoint",
+                        &braced[..end]
+                    ),
+                    &braced[end + 1..],
+                ));
+            }
+            (&braced[..end], &braced[end + 1..])
+        } else {
+            return Ok(None);
+        };
+
+        if digits.is_empty() || !digits.chars().all(|digit| digit.is_ascii_hexdigit()) {
+            return Err(invalid(
+                format!("{digits} is not a hexadecimal codepoint"),
+                rest,
+            ));
+        }
+        match char::from_u32(u32::from_str_radix(digits, 16).unwrap()) {
+            Some(char) => Ok(Some((char, rest))),
+            None => Err(invalid(
+                format!("{digits} is not the codepoint of a character"),
+                rest,
+            )),
+        }
+    }
+
+    fn try_parse_open_group(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
         Self::try_parse_static_prefix_character(remaining, "(", RegexToken::OpenGroup)
     }
 
-    fn try_parse_close_group(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
+    //`(?P<name>` is how Python and RE2 spell a named group, and `(?<name>` how Perl and .NET do
+    fn try_parse_open_named_group(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        let Some(after_prefix) = remaining
+            .strip_prefix("(?P<")
+            .or_else(|| remaining.strip_prefix("(?<"))
+        else {
+            return Ok(None);
+        };
+
+        let Some((name, after_name)) = after_prefix.split_once('>') else {
+            return Err(LexError::between(
+                ParseErrorKind::InvalidGroup,
+                "Group name is not closed with >",
+                remaining,
+                after_prefix,
+            ));
+        };
+
+        let mut chars = name.chars();
+        let starts_validly = chars
+            .next()
+            .is_some_and(|first| first.is_ascii_alphabetic() || first == '_');
+        if !starts_validly || !chars.all(|char| char.is_ascii_alphanumeric() || char == '_') {
+            return Err(LexError::between(
+                ParseErrorKind::InvalidGroup,
+                format!("Group name \"{name}\" must be ASCII letters, digits and underscores, and not start with a digit"),
+                remaining,
+                after_name,
+            ));
+        }
+
+        Ok(Some((RegexToken::OpenNamedGroup(name.into()), after_name)))
+    }
+
+    fn try_parse_close_group(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
         Self::try_parse_static_prefix_character(remaining, ")", RegexToken::CloseGroup)
     }
 
-    fn try_parse_dot(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
+    fn try_parse_dot(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
         Self::try_parse_static_prefix_character(remaining, ".", RegexToken::AnyCharacter)
     }
 
-    fn try_parse_zero_or_more(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
-        Self::try_parse_static_prefix_character(
-            remaining,
-            "*",
-            RegexToken::Repetition { min: 0, max: None },
-        )
+    fn try_parse_start_of_input(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(remaining, "^", RegexToken::StartOfInput)
     }
 
-    fn try_parse_one_or_more(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
-        Self::try_parse_static_prefix_character(
-            remaining,
-            "+",
-            RegexToken::Repetition { min: 1, max: None },
-        )
+    fn try_parse_end_of_input(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(remaining, "$", RegexToken::EndOfInput)
     }
 
-    fn try_parse_optional(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
-        Self::try_parse_static_prefix_character(
-            remaining,
-            "?",
-            RegexToken::Repetition {
-                min: 0,
-                max: Some(1),
-            },
-        )
+    fn try_parse_word_start(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(remaining, "\\b{start}", RegexToken::WordStart)
+    }
+
+    fn try_parse_word_end(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(remaining, "\\b{end}", RegexToken::WordEnd)
+    }
+
+    fn try_parse_zero_or_more(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_quantifier(remaining, "*", 0, None)
+    }
+
+    fn try_parse_one_or_more(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_quantifier(remaining, "+", 1, None)
     }
 
-    fn try_parse_alternation(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
+    fn try_parse_optional(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_quantifier(remaining, "?", 0, Some(1))
+    }
+
+    fn try_parse_quantifier<'remaining>(
+        remaining: &'remaining str,
+        prefix: &str,
+        min: u64,
+        max: Option<u64>,
+    ) -> Result<Option<(RegexToken, &'remaining str)>, LexError> {
+        Ok(remaining
+            .strip_prefix(prefix)
+            .map(|remaining| Self::repetition_with_laziness(min, max, remaining)))
+    }
+
+    //a `?` right after a quantifier makes it lazy rather than making the repetition optional
+    fn repetition_with_laziness(min: u64, max: Option<u64>, remaining: &str) -> (RegexToken, &str) {
+        let (lazy, remaining) = match remaining.strip_prefix('?') {
+            Some(remaining) => (true, remaining),
+            None => (false, remaining),
+        };
+        (RegexToken::Repetition { min, max, lazy }, remaining)
+    }
+
+    fn try_parse_alternation(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
         Self::try_parse_static_prefix_character(remaining, "|", RegexToken::Alternation)
     }
 
-    fn try_parse_static_prefix_character<'remaining, 'prefix>(
+    fn try_parse_static_prefix_character<'remaining>(
         remaining: &'remaining str,
-        prefix: &'prefix str,
+        prefix: &str,
         to_return: RegexToken,
-    ) -> Result<Option<(RegexToken, &'remaining str)>, String> {
-        if remaining.starts_with(prefix) {
-            Ok(Some((to_return, &remaining[1..])))
-        } else {
-            Ok(None)
-        }
+    ) -> Result<Option<(RegexToken, &'remaining str)>, LexError> {
+        Ok(remaining
+            .strip_prefix(prefix)
+            .map(|remaining| (to_return, remaining)))
     }
 
-    fn try_parse_digit(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
-        if remaining.starts_with("\\d") {
+    fn try_parse_digit(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        if let Some(remaining) = remaining.strip_prefix("\\d") {
             Ok(Some((
-                RegexToken::UnicodeCharacterClass(vec![
-                    GeneralCategory::DecimalNumber,
-                    GeneralCategory::OtherNumber,
-                    GeneralCategory::LetterNumber,
-                ]),
-                &remaining[2..],
+                RegexToken::UnicodeCharacterClass(Self::DIGIT_CATEGORIES.to_vec()),
+                remaining,
             )))
         } else {
             Ok(None)
         }
     }
 
-    fn try_parse_not_digit(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
-        if remaining.starts_with("\\D") {
+    fn try_parse_not_digit(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        if let Some(remaining) = remaining.strip_prefix("\\D") {
             Ok(Some((
-                RegexToken::NegatedUnicodeCharacterClass(vec![
-                    GeneralCategory::DecimalNumber,
-                    GeneralCategory::OtherNumber,
-                    GeneralCategory::LetterNumber,
-                ]),
-                &remaining[2..],
+                RegexToken::NegatedUnicodeCharacterClass(Self::DIGIT_CATEGORIES.to_vec()),
+                remaining,
             )))
         } else {
             Ok(None)
         }
     }
 
+    fn try_parse_word(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(
+            remaining,
+            "\\w",
+            RegexToken::UnicodeCharacterClass(Self::WORD_CATEGORIES.to_vec()),
+        )
+    }
+
+    fn try_parse_not_word(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(
+            remaining,
+            "\\W",
+            RegexToken::NegatedUnicodeCharacterClass(Self::WORD_CATEGORIES.to_vec()),
+        )
+    }
+
+    //White_Space is a property of its own rather than a set of general categories (tabs and newlines are controls, like every other Cc), so it is spelled out
+    fn try_parse_whitespace(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(
+            remaining,
+            "\\s",
+            RegexToken::NonUnicodeCharacterClass(CharacterClass::whitespace()),
+        )
+    }
+
+    fn try_parse_not_whitespace(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(
+            remaining,
+            "\\S",
+            RegexToken::NonUnicodeCharacterClass(CharacterClass::Negated(Box::new(
+                CharacterClass::whitespace(),
+            ))),
+        )
+    }
+
+    fn try_parse_horizontal_whitespace(
+        remaining: &str,
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(
+            remaining,
+            "\\h",
+            RegexToken::NonUnicodeCharacterClass(CharacterClass::horizontal_whitespace()),
+        )
+    }
+
+    fn try_parse_not_horizontal_whitespace(
+        remaining: &str,
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(
+            remaining,
+            "\\H",
+            RegexToken::NonUnicodeCharacterClass(CharacterClass::Negated(Box::new(
+                CharacterClass::horizontal_whitespace(),
+            ))),
+        )
+    }
+
+    fn try_parse_vertical_whitespace(
+        remaining: &str,
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(
+            remaining,
+            "\\v",
+            RegexToken::NonUnicodeCharacterClass(CharacterClass::vertical_whitespace()),
+        )
+    }
+
+    fn try_parse_not_vertical_whitespace(
+        remaining: &str,
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(
+            remaining,
+            "\\V",
+            RegexToken::NonUnicodeCharacterClass(CharacterClass::Negated(Box::new(
+                CharacterClass::vertical_whitespace(),
+            ))),
+        )
+    }
+
+    fn try_parse_grapheme_cluster(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        Self::try_parse_static_prefix_character(remaining, "\\X", RegexToken::GraphemeCluster)
+    }
+
+    //without Unicode, the shorthand classes are the POSIX classes of the same characters in ASCII
+    fn try_parse_ascii_shorthand(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        let Some(escaped) = remaining.strip_prefix('\\') else {
+            return Ok(None);
+        };
+        let mut chars = escaped.chars();
+        let name = match chars.next() {
+            Some('d') => "digit",
+            Some('D') => "^digit",
+            Some('w') => "word",
+            Some('W') => "^word",
+            Some('s') => "space",
+            Some('S') => "^space",
+            _ => return Ok(None),
+        };
+
+        Ok(Some((
+            RegexToken::NonUnicodeCharacterClass(
+                CharacterClass::posix_named(name).expect("the shorthands are known POSIX classes"),
+            ),
+            chars.as_str(),
+        )))
+    }
+
+    /// `\p{Greek}`, `\pL` or one of their negations, which bracketed classes also accept as members.
+    pub(super) fn try_parse_unicode_property(
+        remaining: &str,
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
+        //we must parse the multi letter cases first so that \p{ is not seen as a single-unicode class name with the invalid identifier '{'. We could simply move on on such failures, but it is more user-friendly to return a useful error in the case of unknown class names
+        for try_parse in [
+            Self::try_parse_multi_letter_unicode_class_name,
+            Self::try_parse_one_letter_unicode_class_name,
+            Self::try_parse_negated_multi_letter_unicode_class_name,
+            Self::try_parse_negated_one_letter_unicode_class_name,
+        ] {
+            if let Some(tuple) = try_parse(remaining)? {
+                return Ok(Some(tuple));
+            }
+        }
+
+        Ok(None)
+    }
+
     fn try_parse_one_letter_unicode_class_name(
         remaining: &str,
-    ) -> Result<Option<(RegexToken, &str)>, String> {
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
         if remaining.starts_with("\\p") && remaining.len() >= 3 {
             let class_name_identifier = remaining.chars().nth(2).unwrap(); //unwrap will not panic since we checked length
-            let classes = Self::get_unicode_classes_single_letter(class_name_identifier)?;
+            let classes = Self::get_unicode_classes_single_letter(class_name_identifier)
+                .ok_or_else(|| Self::unknown_single_letter(class_name_identifier, remaining))?;
             Ok(Some((
                 RegexToken::UnicodeCharacterClass(classes),
                 &remaining[3..],
@@ -168,12 +724,12 @@ impl RegexToken {
 
     fn try_parse_multi_letter_unicode_class_name(
         remaining: &str,
-    ) -> Result<Option<(RegexToken, &str)>, String> {
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
         if remaining.starts_with("\\p{") && remaining.len() >= 3 {
-            let class_name_identifier = Self::parse_string_until_bracket(&remaining[3..]);
-            let classes = Self::get_unicode_classes_multi_or_single_letter(&class_name_identifier)?;
+            let class_name_identifier = Self::parse_string_until_bracket(remaining)?;
+            let property = Self::get_unicode_property(&class_name_identifier, remaining)?;
             Ok(Some((
-                RegexToken::UnicodeCharacterClass(classes),
+                property.token(false),
                 &remaining[(4 + class_name_identifier.len())..],
             )))
         } else {
@@ -183,10 +739,11 @@ impl RegexToken {
 
     fn try_parse_negated_one_letter_unicode_class_name(
         remaining: &str,
-    ) -> Result<Option<(RegexToken, &str)>, String> {
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
         if remaining.starts_with("\\P") && remaining.len() >= 3 {
             let class_name_identifier = remaining.chars().nth(2).unwrap(); //unwrap will not panic since we checked length
-            let classes = Self::get_unicode_classes_single_letter(class_name_identifier)?;
+            let classes = Self::get_unicode_classes_single_letter(class_name_identifier)
+                .ok_or_else(|| Self::unknown_single_letter(class_name_identifier, remaining))?;
             Ok(Some((
                 RegexToken::NegatedUnicodeCharacterClass(classes),
                 &remaining[3..],
@@ -198,12 +755,12 @@ impl RegexToken {
 
     fn try_parse_negated_multi_letter_unicode_class_name(
         remaining: &str,
-    ) -> Result<Option<(RegexToken, &str)>, String> {
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
         if remaining.starts_with("\\P{") && remaining.len() >= 3 {
-            let class_name_identifier = Self::parse_string_until_bracket(&remaining[3..]);
-            let classes = Self::get_unicode_classes_multi_or_single_letter(&class_name_identifier)?;
+            let class_name_identifier = Self::parse_string_until_bracket(remaining)?;
+            let property = Self::get_unicode_property(&class_name_identifier, remaining)?;
             Ok(Some((
-                RegexToken::NegatedUnicodeCharacterClass(classes),
+                property.token(true),
                 &remaining[(4 + class_name_identifier.len())..],
             )))
         } else {
@@ -211,8 +768,11 @@ impl RegexToken {
         }
     }
 
-    fn try_parse_character_class(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
-        match CharacterClass::try_parse(remaining)? {
+    fn try_parse_character_class(
+        remaining: &str,
+        unicode: bool,
+    ) -> Result<Option<(RegexToken, &str)>, LexError> {
+        match CharacterClass::try_parse_bracketed(remaining, unicode)? {
             Some((class, remaining)) => Ok(Some((
                 RegexToken::NonUnicodeCharacterClass(class),
                 remaining,
@@ -221,90 +781,198 @@ impl RegexToken {
         }
     }
 
-    fn try_parse_repetition(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
+    fn try_parse_repetition(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
         if !remaining.starts_with('{') {
             return Ok(None);
         }
 
-        let mut inner_string = String::new();
-
-        let mut chars = remaining.chars();
-        chars.next();
-        loop {
-            match chars.next() {
-                Some('}') => {
-                    break;
-                }
-                Some(ch) => inner_string.push(ch),
-                None => return Err("Started repetition token but did not finish".into()),
+        let end = match remaining.find('}') {
+            Some(end) => end,
+            None => {
+                return Err(LexError::new(
+                    ParseErrorKind::InvalidRepetition,
+                    "Started repetition token but did not finish",
+                    remaining,
+                ))
             }
-        }
+        };
+        //every message names the whole brace expression, since the error itself only says where it starts
+        let brace = &remaining[..=end];
+        let inner = &remaining[1..end];
+        let invalid = |message: String| {
+            LexError::between(
+                ParseErrorKind::InvalidRepetition,
+                message,
+                remaining,
+                &remaining[brace.len()..],
+            )
+        };
 
-        let split = inner_string.split(",").collect::<Vec<_>>();
-        let (min, max) = match split.len() {
-            1 => {
-                let number = split[0].parse().map_err(|err| {
-                    format!(
-                        "Failed to parse a number inside a repetition token: {} {:?}",
-                        split[0], err
-                    )
-                })?;
-                (Some(number), Some(number))
+        let parse_bound = |bound: &str| -> Result<Option<u64>, LexError> {
+            if bound.is_empty() {
+                return Ok(None);
             }
-            2 => {
-                fn parse_number(number: &str) -> Result<Option<u64>, String> {
-                    if number.is_empty() {
-                        Ok(None)
-                    } else {
-                        let number = number.parse().map_err(|err| {
-                            format!(
-                                "Failed to parse a number inside a repetition token: {} {:?}",
-                                number, err
-                            )
-                        })?;
-                        Ok(Some(number))
-                    }
-                }
-
-                let min = parse_number(split[0])?;
-                let max = parse_number(split[1])?;
+            if !bound.chars().all(|char| char.is_ascii_digit()) {
+                return Err(invalid(format!(
+                    "The repetition {brace} must count with decimal digits, like {{2,5}}, but has {bound:?}"
+                )));
+            }
+            match bound.parse() {
+                Ok(number) if number <= MAX_REPETITION_BOUND => Ok(Some(number)),
+                _ => Err(invalid(format!(
+                    "The repetition {brace} counts past {MAX_REPETITION_BOUND}, the most a repetition may"
+                ))),
+            }
+        };
 
-                (min, max)
+        let (min, max) = match inner.split_once(',') {
+            None => {
+                let count = parse_bound(inner)?;
+                (count, count)
+            }
+            Some((_, max)) if max.contains(',') => {
+                return Err(invalid(format!(
+                    "The repetition {brace} must have exactly zero or one commas"
+                )))
             }
-            _ => return Err("A repetition token must have exactly zero or one commas.".into()),
+            Some((min, max)) => (parse_bound(min)?, parse_bound(max)?),
         };
 
-        Ok(Some((
-            RegexToken::Repetition {
-                min: if let Some(min) = min { min } else { 0 },
-                max,
-            },
-            &remaining[(2 + inner_string.len())..],
+        match (min, max) {
+            (None, None) => {
+                return Err(invalid(format!(
+                    "The repetition {brace} is empty; give a count like {{2}}, a range like {{2,5}} or a minimum like {{2,}}"
+                )))
+            }
+            (Some(min), Some(max)) if min > max => {
+                return Err(invalid(format!(
+                    "The repetition {brace} has a minimum ({min}) greater than its maximum ({max})"
+                )))
+            }
+            _ => {}
+        }
+
+        Ok(Some(Self::repetition_with_laziness(
+            min.unwrap_or(0),
+            max,
+            &remaining[brace.len()..],
         )))
     }
 
-    fn try_parse_single_char(remaining: &str) -> Result<Option<(RegexToken, &str)>, String> {
-        if remaining.len() == 0 {
+    //an escape none of the others are: one of a character that is neither a letter nor a digit is that character, like `\-`, while one of a letter is rejected, since it is most likely an escape of another engine, like the word boundary `\b`, and would silently match something else
+    fn try_parse_other_escape(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        let Some(escaped) = remaining.strip_prefix('\\') else {
+            return Ok(None);
+        };
+
+        let mut chars = escaped.chars();
+        match chars.next() {
+            Some(char) if char.is_alphanumeric() => Err(LexError::between(
+                ParseErrorKind::Unsupported,
+                format!("\\{char} is not an escape fastregex supports"),
+                remaining,
+                chars.as_str(),
+            )),
+            Some(char) => Ok(Some((RegexToken::Literal(char), chars.as_str()))),
+            None => Err(LexError::new(
+                ParseErrorKind::InvalidEscape,
+                "Pattern ends with an unfinished \\ escape",
+                remaining,
+            )),
+        }
+    }
+
+    fn try_parse_single_char(remaining: &str) -> Result<Option<(RegexToken, &str)>, LexError> {
+        if remaining.is_empty() {
             return Ok(None);
         }
 
+        let literal = remaining.chars().nth(0).unwrap();
         Ok(Some((
-            RegexToken::Literal(remaining.chars().nth(0).unwrap()),
-            &remaining[1..],
+            RegexToken::Literal(literal),
+            &remaining[literal.len_utf8()..],
         )))
     }
 
-    fn parse_string_until_bracket(remaining: &str) -> String {
+    //the name in a `\p{...}` or `\P{...}` at the start of `remaining`
+    fn parse_string_until_bracket(remaining: &str) -> Result<String, LexError> {
         let mut class_name = String::new();
 
-        for char in remaining.chars() {
+        for char in remaining[3..].chars() {
             match char {
-                '}' => break,
+                '}' => return Ok(class_name),
                 char => class_name.push(char),
             }
         }
 
-        class_name
+        Err(LexError::between(
+            ParseErrorKind::InvalidEscape,
+            "Started unicode class name but did not finish",
+            remaining,
+            &remaining[3..],
+        ))
+    }
+
+    //places a message about the class named at the start of `remaining` on the whole escape naming it
+    fn unknown_property(message: String, remaining: &str) -> LexError {
+        let mut name = remaining[2..].chars();
+        let after = match name.next() {
+            Some('{') => name.as_str().split_once('}').map_or("", |(_, after)| after),
+            _ => name.as_str(),
+        };
+        LexError::between(ParseErrorKind::UnknownProperty, message, remaining, after)
+    }
+
+    fn unknown_single_letter(identifier: char, remaining: &str) -> LexError {
+        Self::unknown_property(
+            format!("{identifier} is not a known single-character Unicode class name identifier. Expected one of L, M, N, P, S, Z, or C."),
+            remaining,
+        )
+    }
+
+    //every name of a general category, for error messages
+    const GENERAL_CATEGORY_NAMES: &'static str = r#""Lu", "Uppercase_Letter", "Ll", "Lowercase_Letter", "Lt", "Titlecase_Letter", "Lm", "Modifier_Letter", "Lo", "Other_Letter", "Mn", "Nonspacing_Mark", "Mc", "Spacing_Mark", "Me", "Enclosing_Mark", "Nd", "Decimal_Number", "Nl", "Letter_Number", "No", "Other_Number", "Pc", "Connector_Punctuation", "Pd", "Dash_Punctuation", "Ps", "Open_Punctuation", "Pe", "Close_Punctuation", "Pi", "Initial_Punctuation", "Pf", "Final_Punctuation", "Po", "Other_Punctuation", "Sm", "Math_Symbol", "Sc", "Currency_Symbol", "Sk", "Modifier_Symbol", "So", "Other_Symbol", "Zs", "Space_Separator", "Zl", "Line_Separator", "Zp", "Paragraph_Separator", "Cc", "Control", "Cf", "Format", "Cs", "Surrogate", "Co", "Private_Use", "Cn", "Unassigned", "L", "M", "N", "P", "S", "Z", "C""#;
+
+    /// The general categories or script that the name in a `\p{...}` refers to, either by itself
+    /// (e.g., `Lu` or `Greek`) or as the value of a named property (e.g., `gc=Lu` or `sc=Greek`).
+    /// `remaining` starts with the escape naming it, which an error is placed on.
+    fn get_unicode_property(
+        identifier: &str,
+        remaining: &str,
+    ) -> Result<UnicodeProperty, LexError> {
+        let Some((property, value)) = identifier.split_once('=') else {
+            if let Ok(categories) =
+                Self::get_unicode_classes_multi_or_single_letter(identifier, remaining)
+            {
+                return Ok(UnicodeProperty::GeneralCategories(categories));
+            }
+            return match Script::from_name(identifier) {
+                Some(script) => Ok(UnicodeProperty::Script(script)),
+                None => Err(Self::unknown_property(
+                    format!(
+                        r#"{identifier} is not a known Unicode class name identifier. Expected one of {}, or the name of a script, like "Greek"."#,
+                        Self::GENERAL_CATEGORY_NAMES
+                    ),
+                    remaining,
+                )),
+            };
+        };
+
+        match property {
+            "gc" | "General_Category" => Ok(UnicodeProperty::GeneralCategories(
+                Self::get_unicode_classes_multi_or_single_letter(value, remaining)?,
+            )),
+            "sc" | "Script" => Script::from_name(value)
+                .map(UnicodeProperty::Script)
+                .ok_or_else(|| {
+                    Self::unknown_property(format!(
+                        r#"{value} is not a known script. Expected the name of a script, like "Greek", or its four-letter code, like "Grek"."#
+                    ), remaining)
+                }),
+            unknown_property => Err(Self::unknown_property(format!(
+                r#"{unknown_property} is not a known Unicode property. Expected one of "gc", "General_Category", "sc" or "Script"."#
+            ), remaining)),
+        }
     }
 
     /// This function gets the set of unicode classes that refer to a named set of
@@ -313,11 +981,12 @@ impl RegexToken {
     /// https://unicode.org/reports/tr44/#General_Category_Values
     fn get_unicode_classes_multi_or_single_letter(
         class_identifier: &str,
-    ) -> Result<Vec<GeneralCategory>, String> {
+        remaining: &str,
+    ) -> Result<Vec<GeneralCategory>, LexError> {
         use GeneralCategory::*;
 
         if class_identifier.len() == 1 {
-            if let Ok(category) =
+            if let Some(category) =
                 Self::get_unicode_classes_single_letter(class_identifier.chars().nth(0).unwrap())
             {
                 //unwrap will not panic since we checked length
@@ -357,9 +1026,12 @@ impl RegexToken {
             "Co" | "Private_Use" => PrivateUse,
             "Cn" | "Unassigned" => Unassigned,
             unknown_class_identifier => {
-                return Err(format!(
-                    r#"{} is not a known single-character Unicode class name identifier. Expected one of "Lu", "Uppercase_Letter", "Ll", "Lowercase_Letter", "Lt", "Titlecase_Letter", "Lm", "Modifier_Letter", "Lo", "Other_Letter", "Mn", "Nonspacing_Mark", "Mc", "Spacing_Mark", "Me", "Enclosing_Mark", "Nd", "Decimal_Number", "Nl", "Letter_Number", "No", "Other_Number", "Pc", "Connector_Punctuation", "Pd", "Dash_Punctuation", "Ps", "Open_Punctuation", "Pe", "Close_Punctuation", "Pi", "Initial_Punctuation", "Pf", "Final_Punctuation", "Po", "Other_Punctuation", "Sm", "Math_Symbol", "Sc", "Currency_Symbol", "Sk", "Modifier_Symbol", "So", "Other_Symbol", "Zs", "Space_Separator", "Zl", "Line_Separator", "Zp", "Paragraph_Separator", "Cc", "Control", "Cf", "Format", "Cs", "Surrogate", "Co", "Private_Use", "Cn", "Unassigned", "L", "M", "N", "P", "S", "Z", "C"."#,
-                    unknown_class_identifier
+                return Err(Self::unknown_property(
+                    format!(
+                        "{unknown_class_identifier} is not a known general category. Expected one of {}.",
+                        Self::GENERAL_CATEGORY_NAMES
+                    ),
+                    remaining,
                 ));
             }
         };
@@ -368,30 +1040,76 @@ impl RegexToken {
     }
 
     /// This function gets the set of unicode classes that refer to a single-letter-named set of
-    /// unicode classes as per the unicode standard.
+    /// unicode classes as per the unicode standard, or `None` if there is no such set.
     ///
     /// https://unicode.org/reports/tr44/#General_Category_Values
-    fn get_unicode_classes_single_letter(
+    pub(super) fn get_unicode_classes_single_letter(
         class_identifier: char,
-    ) -> Result<Vec<GeneralCategory>, String> {
+    ) -> Option<Vec<GeneralCategory>> {
         use GeneralCategory::*;
         match class_identifier {
-            'L' => Ok(vec![UppercaseLetter, LowercaseLetter, TitlecaseLetter, ModifierLetter, OtherLetter]),
-            'M' => Ok(vec![NonspacingMark, SpacingMark, EnclosingMark]),
-            'N' => Ok(vec![DecimalNumber, LetterNumber, OtherNumber]),
-            'P' => Ok(vec![ConnectorPunctuation, DashPunctuation, OpenPunctuation, ClosePunctuation, InitialPunctuation, FinalPunctuation, OpenPunctuation]),
-            'S' => Ok(vec![MathSymbol, CurrencySymbol, ModifierSymbol, OtherSymbol]),
-            'Z' => Ok(vec![SpaceSeparator, LineSeparator, ParagraphSeparator]),
-            'C' => Ok(vec![Control, Format, Surrogate, PrivateUse, Unassigned]),
-            //we try to parse multi-letter names first so that \p{ is not seen as a single-unicode class name with the invalid identifier '{'. We could simply move on on such failures, but it is more user-friendly to return a useful error in the case of unknown class names
-            unknown_identifier => Err(format!("{unknown_identifier} is not a known single-character Unicode class name identifier. Expected one of L, M, N, P, S, Z, or C."))
+            'L' => Some(vec![
+                UppercaseLetter,
+                LowercaseLetter,
+                TitlecaseLetter,
+                ModifierLetter,
+                OtherLetter,
+            ]),
+            'M' => Some(vec![NonspacingMark, SpacingMark, EnclosingMark]),
+            'N' => Some(vec![DecimalNumber, LetterNumber, OtherNumber]),
+            'P' => Some(vec![
+                ConnectorPunctuation,
+                DashPunctuation,
+                OpenPunctuation,
+                ClosePunctuation,
+                InitialPunctuation,
+                FinalPunctuation,
+                OpenPunctuation,
+            ]),
+            'S' => Some(vec![
+                MathSymbol,
+                CurrencySymbol,
+                ModifierSymbol,
+                OtherSymbol,
+            ]),
+            'Z' => Some(vec![SpaceSeparator, LineSeparator, ParagraphSeparator]),
+            'C' => Some(vec![Control, Format, Surrogate, PrivateUse, Unassigned]),
+            //we try to parse multi-letter names first so that \\p{ is not seen as a single-unicode class name with the invalid identifier '{'. We could simply move on on such failures, but it is more user-friendly to return a useful error in the case of unknown class names
+            _ => None,
+        }
+    }
+}
+
+//what a `\p{...}` can name
+enum UnicodeProperty {
+    GeneralCategories(Vec<GeneralCategory>),
+    Script(Script),
+}
+
+impl UnicodeProperty {
+    fn token(self, negated: bool) -> RegexToken {
+        match (self, negated) {
+            (UnicodeProperty::GeneralCategories(categories), false) => {
+                RegexToken::UnicodeCharacterClass(categories)
+            }
+            (UnicodeProperty::GeneralCategories(categories), true) => {
+                RegexToken::NegatedUnicodeCharacterClass(categories)
+            }
+            (UnicodeProperty::Script(script), false) => RegexToken::UnicodeScript(script),
+            (UnicodeProperty::Script(script), true) => RegexToken::NegatedUnicodeScript(script),
         }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use unic_ucd_category::GeneralCategory;
+
+    use crate::line_terminator::LineTerminator;
     use crate::parser::character_class::CharacterClass;
+    use crate::parser::flags::Flag;
+    use crate::parser::{ParseErrorKind, ParseOptions};
+    use crate::script::Script;
 
     use super::RegexToken;
 
@@ -419,11 +1137,15 @@ mod test {
             vec![
                 RegexToken::OpenGroup,
                 RegexToken::AnyCharacter,
-                RegexToken::Repetition { min: 1, max: None },
-                RegexToken::Repetition { min: 0, max: None },
+                RegexToken::Repetition {
+                    min: 1,
+                    max: None,
+                    lazy: false,
+                },
                 RegexToken::Repetition {
                     min: 0,
-                    max: Some(1),
+                    max: None,
+                    lazy: true,
                 },
                 RegexToken::Alternation,
                 RegexToken::CloseGroup,
@@ -492,17 +1214,506 @@ mod test {
                 RegexToken::Repetition {
                     min: 55,
                     max: Some(55),
+                    lazy: false,
+                },
+                RegexToken::Repetition {
+                    min: 50,
+                    max: None,
+                    lazy: false,
                 },
-                RegexToken::Repetition { min: 50, max: None },
                 RegexToken::Repetition {
                     min: 0,
                     max: Some(51),
+                    lazy: false,
                 },
                 RegexToken::Repetition {
                     min: 52,
                     max: Some(53),
+                    lazy: false,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_shorthand_classes() {
+        let word = RegexToken::WORD_CATEGORIES.to_vec();
+        assert_equal(
+            r"\w\W\s\S",
+            vec![
+                RegexToken::UnicodeCharacterClass(word.clone()),
+                RegexToken::NegatedUnicodeCharacterClass(word),
+                RegexToken::NonUnicodeCharacterClass(CharacterClass::whitespace()),
+                RegexToken::NonUnicodeCharacterClass(CharacterClass::Negated(Box::new(
+                    CharacterClass::whitespace(),
+                ))),
+            ],
+        );
+
+        for whitespace in [
+            '\t', '\n', '\x0B', '\r', ' ', '\u{85}', '\u{A0}', '\u{2009}', '\u{3000}',
+        ] {
+            assert!(
+                CharacterClass::whitespace().matches(whitespace),
+                "{whitespace:?}"
+            );
+        }
+        for not_whitespace in ['a', '\0', '\u{200B}', '\u{FEFF}'] {
+            assert!(
+                !CharacterClass::whitespace().matches(not_whitespace),
+                "{not_whitespace:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_escaped_metacharacters() {
+        assert_equal(
+            r"\.\*\+\?\(\)\[\]\{\}\|\\\^\$",
+            RegexToken::METACHARACTERS
+                .chars()
+                .map(RegexToken::Literal)
+                .collect(),
+        );
+        assert_equal(
+            r"a\.+\\d",
+            vec![
+                RegexToken::Literal('a'),
+                RegexToken::Literal('.'),
+                RegexToken::Repetition {
+                    min: 1,
+                    max: None,
+                    lazy: false,
                 },
+                RegexToken::Literal('\\'),
+                RegexToken::Literal('d'),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_unknown_escapes() {
+        assert_equal(r"\-\/\#", "-/#".chars().map(RegexToken::Literal).collect());
+
+        for (pattern, escape) in [(r"\bword\b", r"\b"), (r"a\q", r"\q"), (r"\A\z", r"\A")] {
+            let error = RegexToken::parse(pattern).unwrap_err();
+            assert_eq!(error.kind, ParseErrorKind::Unsupported);
+            assert_eq!(&pattern[error.span], escape);
+        }
+    }
+
+    #[test]
+    fn test_codepoint_escapes() {
+        assert_equal(
+            r"\x41\x7f\u{1F600}\u{9}",
+            vec![
+                RegexToken::Literal('A'),
+                RegexToken::Literal('\x7F'),
+                RegexToken::Literal('😀'),
+                RegexToken::Literal('\t'),
+            ],
+        );
+
+        for malformed in [
+            r"\x4",
+            r"\xg0",
+            r"\u263A",
+            r"\u{}",
+            r"\u{263A",
+            r"\u{1000000}",
+            r"\u{D800}",
+        ] {
+            assert!(RegexToken::parse(malformed).is_err(), "{malformed}");
+        }
+    }
+
+    #[test]
+    fn test_control_escapes() {
+        assert_equal(
+            r"\n\t\r\0\f",
+            "\n\t\r\0\x0C".chars().map(RegexToken::Literal).collect(),
+        );
+    }
+
+    #[test]
+    fn test_octal_escapes() {
+        assert_equal(
+            r"\012\07\0\0778",
+            "\n\x07\0\x3F8".chars().map(RegexToken::Literal).collect(),
+        );
+    }
+
+    #[test]
+    fn test_control_char_escapes() {
+        assert_equal(
+            r"\cJ\cj\c@\c[\c?",
+            "\n\n\0\x1B\x7F".chars().map(RegexToken::Literal).collect(),
+        );
+
+        for malformed in [r"\c", r"\c1", "\\c\u{e9}"] {
+            assert!(RegexToken::parse(malformed).is_err(), "{malformed}");
+        }
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        assert_equal(
+            "ä€",
+            vec![RegexToken::Literal('ä'), RegexToken::Literal('€')],
+        );
+    }
+
+    #[test]
+    fn test_lazy_quantifiers() {
+        assert_equal(
+            "a*?b+?c??d{2,5}?e{3}?",
+            vec![
+                RegexToken::Literal('a'),
+                RegexToken::Repetition {
+                    min: 0,
+                    max: None,
+                    lazy: true,
+                },
+                RegexToken::Literal('b'),
+                RegexToken::Repetition {
+                    min: 1,
+                    max: None,
+                    lazy: true,
+                },
+                RegexToken::Literal('c'),
+                RegexToken::Repetition {
+                    min: 0,
+                    max: Some(1),
+                    lazy: true,
+                },
+                RegexToken::Literal('d'),
+                RegexToken::Repetition {
+                    min: 2,
+                    max: Some(5),
+                    lazy: true,
+                },
+                RegexToken::Literal('e'),
+                RegexToken::Repetition {
+                    min: 3,
+                    max: Some(3),
+                    lazy: true,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_named_groups() {
+        assert_equal(
+            "(?P<year>a)(?<_2>)",
+            vec![
+                RegexToken::OpenNamedGroup("year".into()),
+                RegexToken::Literal('a'),
+                RegexToken::CloseGroup,
+                RegexToken::OpenNamedGroup("_2".into()),
+                RegexToken::CloseGroup,
             ],
         );
+        assert!(RegexToken::parse("(?P<year").is_err());
+        assert!(RegexToken::parse("(?P<2nd>a)").is_err());
+        assert!(RegexToken::parse("(?<>a)").is_err());
+        assert!(RegexToken::parse("(?<a-b>a)").is_err());
+    }
+
+    #[test]
+    fn test_flags() {
+        assert_equal(
+            "(?i)a(?-i)",
+            vec![
+                RegexToken::SetFlags(vec![(Flag::CaseInsensitive, true)]),
+                RegexToken::Literal('a'),
+                RegexToken::SetFlags(vec![(Flag::CaseInsensitive, false)]),
+            ],
+        );
+        assert_equal(
+            "(?-i:)(?:",
+            vec![
+                RegexToken::OpenFlagGroup(vec![(Flag::CaseInsensitive, false)]),
+                RegexToken::CloseGroup,
+                RegexToken::OpenFlagGroup(Vec::new()),
+            ],
+        );
+        assert!(RegexToken::parse("(?q)").is_err());
+        assert!(RegexToken::parse("(?i-i-i)").is_err());
+    }
+
+    #[test]
+    fn test_verbose_mode() {
+        assert_equal(
+            "(?x) a b # comment\n  \\  [ ] ( c \\# )(?-x: d)",
+            vec![
+                RegexToken::SetFlags(vec![(Flag::Verbose, true)]),
+                RegexToken::Literal('a'),
+                RegexToken::Literal('b'),
+                RegexToken::Literal(' '),
+                RegexToken::NonUnicodeCharacterClass(CharacterClass::Char(' ')),
+                RegexToken::OpenGroup,
+                RegexToken::Literal('c'),
+                RegexToken::Literal('#'),
+                RegexToken::CloseGroup,
+                RegexToken::OpenFlagGroup(vec![(Flag::Verbose, false)]),
+                RegexToken::Literal(' '),
+                RegexToken::Literal('d'),
+                RegexToken::CloseGroup,
+            ],
+        );
+        //the flag ends with the group it is set in
+        assert_equal(
+            "((?x) a) b",
+            vec![
+                RegexToken::OpenGroup,
+                RegexToken::SetFlags(vec![(Flag::Verbose, true)]),
+                RegexToken::Literal('a'),
+                RegexToken::CloseGroup,
+                RegexToken::Literal(' '),
+                RegexToken::Literal('b'),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_quoted() {
+        assert_equal(
+            r"a\Q.*(\E+\Q\E\Qb",
+            vec![
+                RegexToken::Literal('a'),
+                RegexToken::Literal('.'),
+                RegexToken::Literal('*'),
+                RegexToken::Literal('('),
+                RegexToken::Repetition {
+                    min: 1,
+                    max: None,
+                    lazy: false,
+                },
+                RegexToken::Literal('b'),
+            ],
+        );
+
+        let spans = RegexToken::parse_with_spans(r"\Qé.\E")
+            .unwrap()
+            .into_iter()
+            .map(|(_, span)| span)
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![2..4, 4..5]);
+    }
+
+    #[test]
+    fn test_scripts() {
+        let greek = Script::from_name("Greek").unwrap();
+        assert_equal(
+            r"\p{Greek}\P{Grek}\p{L}",
+            vec![
+                RegexToken::UnicodeScript(greek),
+                RegexToken::NegatedUnicodeScript(greek),
+                RegexToken::UnicodeCharacterClass(vec![
+                    GeneralCategory::UppercaseLetter,
+                    GeneralCategory::LowercaseLetter,
+                    GeneralCategory::TitlecaseLetter,
+                    GeneralCategory::ModifierLetter,
+                    GeneralCategory::OtherLetter,
+                ]),
+            ],
+        );
+
+        let error = RegexToken::parse(r"\p{Klingon}").unwrap_err();
+        assert!(error.message.contains("the name of a script"), "{error}");
+    }
+
+    #[test]
+    fn test_grapheme_cluster() {
+        assert_equal(
+            r"\X+\\X",
+            vec![
+                RegexToken::GraphemeCluster,
+                RegexToken::Repetition {
+                    min: 1,
+                    max: None,
+                    lazy: false,
+                },
+                RegexToken::Literal('\\'),
+                RegexToken::Literal('X'),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        assert_equal(
+            r"\b{start}a\b{end}",
+            vec![
+                RegexToken::WordStart,
+                RegexToken::Literal('a'),
+                RegexToken::WordEnd,
+            ],
+        );
+        //only the one-sided boundaries are supported
+        for pattern in [r"\b", r"\b{start-half}"] {
+            let error = RegexToken::parse(pattern).unwrap_err();
+            assert_eq!(error.kind, ParseErrorKind::Unsupported);
+        }
+    }
+
+    #[test]
+    fn test_line_whitespace() {
+        assert_equal(
+            r"\h\H\v\V",
+            vec![
+                RegexToken::NonUnicodeCharacterClass(CharacterClass::horizontal_whitespace()),
+                RegexToken::NonUnicodeCharacterClass(CharacterClass::Negated(Box::new(
+                    CharacterClass::horizontal_whitespace(),
+                ))),
+                RegexToken::NonUnicodeCharacterClass(CharacterClass::vertical_whitespace()),
+                RegexToken::NonUnicodeCharacterClass(CharacterClass::Negated(Box::new(
+                    CharacterClass::vertical_whitespace(),
+                ))),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_line_break() {
+        assert_equal(
+            r"a\R",
+            vec![
+                RegexToken::Literal('a'),
+                RegexToken::OpenFlagGroup(Vec::new()),
+                RegexToken::Literal('\n'),
+                RegexToken::CloseGroup,
+            ],
+        );
+
+        //a `\r\n` is one line break wherever a `\r` is one
+        let options = ParseOptions {
+            line_terminator: LineTerminator::Unicode,
+            ..ParseOptions::default()
+        };
+        let tokens = RegexToken::parse_with_spans_and_options(r"a\R", options)
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            vec![
+                RegexToken::Literal('a'),
+                RegexToken::OpenFlagGroup(Vec::new()),
+                RegexToken::Literal('\r'),
+                RegexToken::Literal('\n'),
+                RegexToken::Alternation,
+                RegexToken::NonUnicodeCharacterClass(CharacterClass::vertical_whitespace()),
+                RegexToken::CloseGroup,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_constructs() {
+        for (pattern, construct, span) in [
+            (r"(a)\1", "Backreferences", 3..5),
+            (r"(a)\12b", "Backreferences", 3..6),
+            (r"(?P<x>a)\k<x>", "Backreferences", 8..13),
+            (r"(a)\g{-1}", "Backreferences", 3..9),
+            (r"(a)\g-1", "Backreferences", 3..7),
+            ("x(?=a)", "Lookaheads", 1..4),
+            ("x(?!a)", "Lookaheads", 1..4),
+            ("(?<=a)x", "Lookbehinds", 0..4),
+            ("(?<!a)x", "Lookbehinds", 0..4),
+            ("(?>a+)b", "Atomic groups", 0..3),
+        ] {
+            let error = RegexToken::parse(pattern).unwrap_err();
+            let expected = format!(
+                "{construct}, like \"{}\", are not supported by fastregex because it compiles to a finite automaton",
+                &pattern[span.clone()]
+            );
+            assert_eq!(error.kind, ParseErrorKind::Unsupported);
+            assert_eq!(error.span, span);
+            assert_eq!(error.message, expected);
+        }
+
+        assert!(RegexToken::parse(r"\Q\1(?=\k\E\0").is_ok());
+    }
+
+    #[test]
+    fn test_property_values() {
+        let latin = Script::from_name("Latin").unwrap();
+        assert_equal(
+            r"\p{Script=Latin}\p{sc=Latn}\P{sc=Latin}\p{gc=Lu}\P{General_Category=Math_Symbol}",
+            vec![
+                RegexToken::UnicodeScript(latin),
+                RegexToken::UnicodeScript(latin),
+                RegexToken::NegatedUnicodeScript(latin),
+                RegexToken::UnicodeCharacterClass(vec![GeneralCategory::UppercaseLetter]),
+                RegexToken::NegatedUnicodeCharacterClass(vec![GeneralCategory::MathSymbol]),
+            ],
+        );
+
+        let error = RegexToken::parse(r"\p{Block=Greek}").unwrap_err();
+        assert!(
+            error
+                .message
+                .contains(r#"Expected one of "gc", "General_Category", "sc" or "Script""#),
+            "{error}"
+        );
+        //the value must be of the property it is given for
+        assert!(RegexToken::parse(r"\p{gc=Greek}").is_err());
+        assert!(RegexToken::parse(r"\p{sc=Lu}").is_err());
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert!(RegexToken::parse(r"\p{Lu").is_err());
+        assert!(RegexToken::parse("a{3,1}").is_err());
+    }
+
+    #[test]
+    fn test_repetition_bounds() {
+        for (pattern, message) in [
+            ("a{3,1}b", "{3,1} has a minimum (3) greater than"),
+            ("a{}", "{} is empty"),
+            ("a{,}", "{,} is empty"),
+            ("a{100001}", "{100001} counts past 100000"),
+            ("a{2,99999999999999999999}", "counts past 100000"),
+            ("a{+2}", "{+2} must count with decimal digits"),
+            ("a{1,2,3}", "{1,2,3} must have exactly zero or one commas"),
+        ] {
+            let error = RegexToken::parse(pattern).unwrap_err();
+            assert_eq!(error.kind, ParseErrorKind::InvalidRepetition);
+            //the span is the whole brace expression
+            assert_eq!(
+                &pattern[error.span.clone()],
+                pattern[1..].split('b').next().unwrap()
+            );
+            assert!(error.message.contains(message), "{pattern}: {error}");
+        }
+
+        assert!(RegexToken::parse("a{,3}b{100000}c{0}").is_ok());
+    }
+
+    #[test]
+    fn test_error_position() {
+        for (pattern, kind, span) in [
+            ("ab[z-a]", ParseErrorKind::InvalidClass, 3..6),
+            (r"a\p{Klingon}b", ParseErrorKind::UnknownProperty, 1..12),
+            (r"x\cé", ParseErrorKind::InvalidEscape, 1..5),
+            (r"a\bc", ParseErrorKind::Unsupported, 1..3),
+            ("a\\", ParseErrorKind::InvalidEscape, 1..2),
+            ("(?P<1>a)", ParseErrorKind::InvalidGroup, 0..6),
+            ("a(?ix-q)", ParseErrorKind::InvalidGroup, 6..7),
+            ("a{2", ParseErrorKind::InvalidRepetition, 1..2),
+        ] {
+            let error = RegexToken::parse(pattern).unwrap_err();
+            assert_eq!((error.kind, error.span), (kind, span), "{pattern}");
+        }
+
+        let error = RegexToken::parse("ab[z-a]").unwrap_err();
+        assert!(
+            error.to_string().starts_with("Error occurred at byte 3: "),
+            "{error}"
+        );
     }
 }
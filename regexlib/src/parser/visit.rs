@@ -0,0 +1,140 @@
+use crate::parser::RegexEntry;
+
+/// Something that looks at every part of a [`RegexEntry`], given to [`RegexEntry::visit`]. Both
+/// methods do nothing by default, so a visitor only implements the ones it needs.
+pub trait RegexVisitor {
+    /// Called on each entry before the entries it is made of (the members of a concatenation or
+    /// alternation, or the base of a repetition or capture group). Returning `false` skips them.
+    fn enter(&mut self, entry: &RegexEntry) -> bool {
+        let _ = entry;
+        true
+    }
+
+    /// Called on each entry after the entries it is made of.
+    fn leave(&mut self, entry: &RegexEntry) {
+        let _ = entry;
+    }
+}
+
+/// Like [`RegexVisitor`], but may change the entries it visits, given to
+/// [`RegexEntry::visit_mut`]. An entry replaced in [`enter`](Self::enter) has the entries of its
+/// replacement visited, so rewriting top-down happens in `enter` and bottom-up in
+/// [`leave`](Self::leave).
+pub trait RegexVisitorMut {
+    /// Called on each entry before the entries it is made of. Returning `false` skips them.
+    fn enter(&mut self, entry: &mut RegexEntry) -> bool {
+        let _ = entry;
+        true
+    }
+
+    /// Called on each entry after the entries it is made of.
+    fn leave(&mut self, entry: &mut RegexEntry) {
+        let _ = entry;
+    }
+}
+
+impl RegexEntry {
+    /// Walks the entry depth-first, in the order its parts are written, calling `visitor` on each
+    /// of them including the entry itself.
+    pub fn visit(&self, visitor: &mut impl RegexVisitor) {
+        if visitor.enter(self) {
+            match self {
+                RegexEntry::Concatenation(entries) | RegexEntry::Alternation(entries) => {
+                    for entry in entries {
+                        entry.visit(visitor);
+                    }
+                }
+                RegexEntry::Repetition { base, .. } | RegexEntry::Capture { base, .. } => {
+                    base.visit(visitor)
+                }
+                _ => {}
+            }
+        }
+        visitor.leave(self);
+    }
+
+    /// Like [`visit`](Self::visit), but lets `visitor` change the entries it visits.
+    pub fn visit_mut(&mut self, visitor: &mut impl RegexVisitorMut) {
+        if visitor.enter(self) {
+            match self {
+                RegexEntry::Concatenation(entries) | RegexEntry::Alternation(entries) => {
+                    for entry in entries {
+                        entry.visit_mut(visitor);
+                    }
+                }
+                RegexEntry::Repetition { base, .. } | RegexEntry::Capture { base, .. } => {
+                    base.visit_mut(visitor)
+                }
+                _ => {}
+            }
+        }
+        visitor.leave(self);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{RegexVisitor, RegexVisitorMut};
+    use crate::parser::RegexEntry;
+
+    //the entries entered, written out, with a `/` for each one left
+    #[derive(Default)]
+    struct Trace {
+        events: Vec<String>,
+        skip_captures: bool,
+    }
+
+    impl RegexVisitor for Trace {
+        fn enter(&mut self, entry: &RegexEntry) -> bool {
+            self.events.push(entry.to_string());
+            !(self.skip_captures && matches!(entry, RegexEntry::Capture { .. }))
+        }
+
+        fn leave(&mut self, _: &RegexEntry) {
+            self.events.push("/".into());
+        }
+    }
+
+    #[test]
+    fn test_visit() {
+        let entry = RegexEntry::parse("a(b|c)*").unwrap();
+
+        let mut trace = Trace::default();
+        entry.visit(&mut trace);
+        assert_eq!(
+            trace.events,
+            vec![
+                "a(b|c)*", "a", "/", "(b|c)*", "(b|c)", "b|c", "b", "/", "c", "/", "/", "/", "/",
+                "/"
+            ]
+        );
+
+        let mut trace = Trace {
+            skip_captures: true,
+            ..Trace::default()
+        };
+        entry.visit(&mut trace);
+        assert_eq!(
+            trace.events,
+            vec!["a(b|c)*", "a", "/", "(b|c)*", "(b|c)", "/", "/", "/"]
+        );
+    }
+
+    //replaces each repetition by its base, from the innermost out
+    struct Unrepeat;
+
+    impl RegexVisitorMut for Unrepeat {
+        fn leave(&mut self, entry: &mut RegexEntry) {
+            if let RegexEntry::Repetition { base, .. } = entry {
+                *entry = std::mem::replace(base.as_mut(), RegexEntry::Concatenation(Vec::new()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_visit_mut() {
+        let mut entry = RegexEntry::parse("a(b+|c)*d?").unwrap();
+        entry.visit_mut(&mut Unrepeat);
+        assert_eq!(entry.to_string(), "a(b|c)d");
+    }
+}
@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+use crate::runtime::error::MatchError;
+use crate::runtime::Limits;
+
+/// Tracks the steps and time a single search has used against its [`Limits`].
+pub struct SearchBudget {
+    steps: u64,
+    step_limit: Option<u64>,
+    deadline: Option<(Instant, Duration)>,
+}
+
+impl SearchBudget {
+    /// Reading the clock on every character would dominate the search, so the deadline is only
+    /// checked once per this many steps (and whenever an engine calls `check_deadline` itself).
+    const DEADLINE_CHECK_INTERVAL: u64 = 1024;
+
+    /// Creates a budget for a single search, which starts counting time immediately.
+    pub fn new(limits: &Limits) -> Self {
+        Self {
+            steps: 0,
+            step_limit: limits.max_steps,
+            deadline: limits
+                .max_duration
+                .map(|duration| (Instant::now() + duration, duration)),
+        }
+    }
+
+    pub fn step(&mut self, steps: u64) -> Result<(), MatchError> {
+        let previous_steps = self.steps;
+        self.steps = self.steps.saturating_add(steps);
+
+        if let Some(limit) = self.step_limit {
+            if self.steps > limit {
+                return Err(MatchError::StepLimitExceeded { limit });
+            }
+        }
+
+        if previous_steps / Self::DEADLINE_CHECK_INTERVAL
+            != self.steps / Self::DEADLINE_CHECK_INTERVAL
+        {
+            self.check_deadline()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn check_deadline(&self) -> Result<(), MatchError> {
+        match self.deadline {
+            Some((deadline, limit)) if Instant::now() > deadline => {
+                Err(MatchError::TimedOut { limit })
+            }
+            _ => Ok(()),
+        }
+    }
+}
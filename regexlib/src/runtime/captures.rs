@@ -0,0 +1,109 @@
+use std::ops::{Index, Range};
+use std::sync::Arc;
+
+use crate::runtime::pike_vm::Slots;
+
+/// A span of the haystack that matched a pattern or one of its groups.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Match<'haystack> {
+    haystack: &'haystack str,
+    start: usize,
+    end: usize,
+}
+
+/// The spans matched by each capture group of a pattern. Group 0 is the whole match, and the
+/// remaining groups are numbered by the position of their opening parenthesis. Groups written as
+/// `(?P<name>...)` can also be looked up by name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Captures<'haystack> {
+    haystack: &'haystack str,
+    slots: Slots,
+    names: Arc<[Option<String>]>,
+}
+
+impl<'haystack> Match<'haystack> {
+    pub(crate) fn new(haystack: &'haystack str, start: usize, end: usize) -> Self {
+        Self {
+            haystack,
+            start,
+            end,
+        }
+    }
+
+    /// The byte offset where the match starts.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The byte offset just past the end of the match.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    pub fn as_str(&self) -> &'haystack str {
+        &self.haystack[self.range()]
+    }
+}
+
+impl<'haystack> Captures<'haystack> {
+    pub(crate) fn new(
+        haystack: &'haystack str,
+        slots: Slots,
+        names: Arc<[Option<String>]>,
+    ) -> Self {
+        Self {
+            haystack,
+            slots,
+            names,
+        }
+    }
+
+    /// The span of group `index`, or `None` if that group did not participate in the match (or
+    /// does not exist).
+    pub fn get(&self, index: usize) -> Option<Match<'haystack>> {
+        let start = (*self.slots.get(2 * index)?)?;
+        let end = (*self.slots.get(2 * index + 1)?)?;
+        Some(Match::new(self.haystack, start, end))
+    }
+
+    /// The span of the group named `name`, or `None` if that group did not participate in the
+    /// match (or no group has that name).
+    pub fn name(&self, name: &str) -> Option<Match<'haystack>> {
+        let index = self
+            .names
+            .iter()
+            .position(|group_name| group_name.as_deref() == Some(name))?;
+        self.get(index)
+    }
+
+    /// The number of groups, including group 0.
+    pub fn len(&self) -> usize {
+        self.slots.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<Match<'haystack>>> + '_ {
+        (0..self.len()).map(|index| self.get(index))
+    }
+}
+
+impl<'haystack> Index<usize> for Captures<'haystack> {
+    type Output = str;
+
+    /// # Panics
+    ///
+    /// Panics if group `index` did not participate in the match.
+    fn index(&self, index: usize) -> &str {
+        match self.get(index) {
+            Some(group) => group.as_str(),
+            None => panic!("Capture group {index} did not participate in the match."),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Adapters for using a [`Regex`] as a parser in the nom and winnow parser-combinator libraries,
+//! each behind the feature of the same name.
+
+use super::Regex;
+
+#[cfg(feature = "nom")]
+impl Regex {
+    /// A nom parser consuming the longest start of its input that matches (see
+    /// [`longest_prefix`](Self::longest_prefix)) and returning it. If no start of the input
+    /// matches, it fails with [`ErrorKind::RegexpMatch`](nom::error::ErrorKind::RegexpMatch). A
+    /// search that exceeds the regex's [`Limits`](super::Limits) fails the whole parse, with
+    /// [`ErrorKind::TooLarge`](nom::error::ErrorKind::TooLarge), rather than letting another
+    /// branch be tried.
+    pub fn nom_parser<'input, E: nom::error::ParseError<&'input str>>(
+        &self,
+    ) -> impl FnMut(&'input str) -> nom::IResult<&'input str, &'input str, E> + '_ {
+        use nom::error::ErrorKind;
+
+        move |input| match self.try_longest_prefix(input) {
+            Ok(Some(length)) => Ok((&input[length..], &input[..length])),
+            Ok(None) => Err(nom::Err::Error(E::from_error_kind(
+                input,
+                ErrorKind::RegexpMatch,
+            ))),
+            Err(_) => Err(nom::Err::Failure(E::from_error_kind(
+                input,
+                ErrorKind::TooLarge,
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "winnow")]
+impl Regex {
+    /// A winnow parser consuming the longest start of its input that matches (see
+    /// [`longest_prefix`](Self::longest_prefix)) and returning it. It fails without consuming
+    /// anything if no start of the input matches, or if the search exceeds the regex's
+    /// [`Limits`](super::Limits).
+    pub fn winnow_parser<'input, E: winnow::error::ParserError<&'input str>>(
+        &self,
+    ) -> impl FnMut(&mut &'input str) -> Result<&'input str, E> + '_ {
+        move |input| match self.try_longest_prefix(input) {
+            Ok(Some(length)) => {
+                let (matched, rest) = input.split_at(length);
+                *input = rest;
+                Ok(matched)
+            }
+            Ok(None) | Err(_) => Err(E::from_input(input)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::Regex;
+
+    #[cfg(feature = "nom")]
+    #[test]
+    fn test_nom_parser() {
+        use nom::bytes::complete::tag;
+        use nom::sequence::separated_pair;
+        use nom::Parser;
+
+        let number = Regex::new("[0-9]+").unwrap();
+        let word = Regex::new("[a-z]+").unwrap();
+        let mut pair = separated_pair(
+            number.nom_parser::<nom::error::Error<&str>>(),
+            tag("="),
+            word.nom_parser(),
+        );
+
+        assert_eq!(pair.parse("12=ab;"), Ok((";", ("12", "ab"))));
+        assert!(pair.parse("x=ab").is_err());
+    }
+
+    #[cfg(feature = "winnow")]
+    #[test]
+    fn test_winnow_parser() {
+        use winnow::combinator::separated_pair;
+        use winnow::error::ContextError;
+        use winnow::Parser;
+
+        let number = Regex::new("[0-9]+").unwrap();
+        let word = Regex::new("[a-z]+").unwrap();
+        let mut pair = separated_pair(
+            number.winnow_parser::<ContextError>(),
+            "=",
+            word.winnow_parser(),
+        );
+
+        let mut input = "12=ab;";
+        assert_eq!(pair.parse_next(&mut input), Ok(("12", "ab")));
+        assert_eq!(input, ";");
+        assert!(pair.parse_next(&mut "x=ab").is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use crate::parser::ParseError;
+
+/// An error produced while turning a pattern into a runtime [`Regex`](super::Regex).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CompileError {
+    /// The pattern is not a valid regex; the error says where in it and why.
+    Parse(ParseError),
+    /// Pattern `index` of a [`RegexSet`](super::RegexSet) is not a valid regex.
+    SetPatternParse {
+        index: usize,
+        error: ParseError,
+    },
+    /// The glob given to [`Regex::from_glob`](super::Regex::from_glob) can not be converted to a
+    /// regex, for the reason given.
+    Glob(String),
+    TooManyStates {
+        states: u64,
+        limit: usize,
+    },
+}
+
+/// An error produced while searching with a runtime [`Regex`](super::Regex).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MatchError {
+    CacheExhausted { limit: usize },
+    StepLimitExceeded { limit: u64 },
+    TimedOut { limit: Duration },
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompileError::Parse(error) => write!(f, "Failed to parse regex: {error}"),
+            CompileError::SetPatternParse { index, error } => {
+                write!(f, "Failed to parse regex: pattern {index}: {error}")
+            }
+            CompileError::Glob(msg) => write!(f, "Failed to convert glob: {msg}"),
+            CompileError::TooManyStates { states, limit } => write!(
+                f,
+                "Regex would construct an automaton with {states} states, which exceeds the limit of {limit}."
+            ),
+        }
+    }
+}
+
+impl Display for MatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchError::CacheExhausted { limit } => write!(
+                f,
+                "Lazy DFA cache exceeded its limit of {limit} bytes while searching."
+            ),
+            MatchError::StepLimitExceeded { limit } => {
+                write!(f, "Search exceeded its limit of {limit} steps.")
+            }
+            MatchError::TimedOut { limit } => {
+                write!(f, "Search did not finish within its limit of {limit:?}.")
+            }
+        }
+    }
+}
+
+impl Error for CompileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CompileError::Parse(error) | CompileError::SetPatternParse { error, .. } => Some(error),
+            CompileError::Glob(_) | CompileError::TooManyStates { .. } => None,
+        }
+    }
+}
+
+impl Error for MatchError {}
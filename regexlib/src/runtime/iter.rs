@@ -0,0 +1,184 @@
+use crate::runtime::budget::SearchBudget;
+use crate::runtime::captures::{Captures, Match};
+use crate::runtime::error::MatchError;
+use crate::runtime::{pike_vm, Regex};
+
+/// Finds successive non-overlapping matches in a haystack, sharing one [`SearchBudget`] between
+/// all of them so that the configured limits bound the iteration as a whole.
+pub(crate) struct Searcher<'regex, 'haystack> {
+    regex: &'regex Regex,
+    haystack: &'haystack str,
+    position: Option<usize>,
+    last_match_end: Option<usize>,
+    budget: SearchBudget,
+}
+
+impl<'regex, 'haystack> Searcher<'regex, 'haystack> {
+    pub fn new(regex: &'regex Regex, haystack: &'haystack str) -> Self {
+        Self {
+            regex,
+            haystack,
+            position: Some(0),
+            last_match_end: None,
+            budget: SearchBudget::new(&regex.limits),
+        }
+    }
+
+    pub fn next_captures(&mut self) -> Result<Option<Captures<'haystack>>, MatchError> {
+        while let Some(position) = self.position {
+            let slots = match pike_vm::search(
+                &self.regex.capture_automaton,
+                self.haystack,
+                position,
+                self.regex.capture_count,
+                &mut self.budget,
+            )? {
+                Some(slots) => slots,
+                None => {
+                    self.position = None;
+                    return Ok(None);
+                }
+            };
+
+            let captures = Captures::new(self.haystack, slots, self.regex.capture_names.clone());
+            //group 0 always participates in a match
+            let found = captures.get(0).unwrap();
+
+            //an empty match right where the previous match ended would just repeat that match's end, so search again one character later
+            if found.range().is_empty() && Some(found.end()) == self.last_match_end {
+                self.position = self.next_char_boundary(found.end());
+                continue;
+            }
+
+            self.position = if found.range().is_empty() {
+                self.next_char_boundary(found.end())
+            } else {
+                Some(found.end())
+            };
+            self.last_match_end = Some(found.end());
+
+            return Ok(Some(captures));
+        }
+
+        Ok(None)
+    }
+
+    fn next_char_boundary(&self, position: usize) -> Option<usize> {
+        self.haystack[position..]
+            .chars()
+            .next()
+            .map(|char| position + char.len_utf8())
+    }
+}
+
+/// An iterator over the captures of each successive non-overlapping match in a haystack.
+///
+/// # Panics
+///
+/// Iterating panics if the search exceeds one of the regex's configured
+/// [`Limits`](super::Limits).
+pub struct CapturesIter<'regex, 'haystack> {
+    pub(crate) searcher: Searcher<'regex, 'haystack>,
+}
+
+/// An iterator over each successive non-overlapping match in a haystack.
+///
+/// # Panics
+///
+/// Iterating panics if the search exceeds one of the regex's configured
+/// [`Limits`](super::Limits).
+pub struct FindIter<'regex, 'haystack> {
+    pub(crate) searcher: Searcher<'regex, 'haystack>,
+}
+
+impl<'regex, 'haystack> Iterator for CapturesIter<'regex, 'haystack> {
+    type Item = Captures<'haystack>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.searcher.next_captures() {
+            Ok(captures) => captures,
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
+
+impl<'regex, 'haystack> Iterator for FindIter<'regex, 'haystack> {
+    type Item = Match<'haystack>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.searcher.next_captures() {
+            Ok(captures) => captures.and_then(|captures| captures.get(0)),
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
+
+/// An iterator over the substrings of a haystack between successive non-overlapping matches.
+///
+/// # Panics
+///
+/// Iterating panics if the search exceeds one of the regex's configured
+/// [`Limits`](super::Limits).
+pub struct Split<'regex, 'haystack> {
+    pub(crate) finder: FindIter<'regex, 'haystack>,
+    pub(crate) last: usize,
+    pub(crate) done: bool,
+}
+
+/// An iterator over at most `limit` substrings of a haystack between successive non-overlapping
+/// matches, the last of which is the rest of the haystack.
+///
+/// # Panics
+///
+/// Iterating panics if the search exceeds one of the regex's configured
+/// [`Limits`](super::Limits).
+pub struct SplitN<'regex, 'haystack> {
+    pub(crate) split: Split<'regex, 'haystack>,
+    pub(crate) remaining: usize,
+}
+
+impl<'regex, 'haystack> Iterator for Split<'regex, 'haystack> {
+    type Item = &'haystack str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let haystack = self.finder.searcher.haystack;
+        match self.finder.next() {
+            Some(found) => {
+                let piece = &haystack[self.last..found.start()];
+                self.last = found.end();
+                Some(piece)
+            }
+            None => {
+                self.done = true;
+                Some(&haystack[self.last..])
+            }
+        }
+    }
+}
+
+impl<'regex, 'haystack> Iterator for SplitN<'regex, 'haystack> {
+    type Item = &'haystack str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.remaining {
+            0 => None,
+            1 => {
+                self.remaining = 0;
+                if self.split.done {
+                    None
+                } else {
+                    self.split.done = true;
+                    Some(&self.split.finder.searcher.haystack[self.split.last..])
+                }
+            }
+            _ => {
+                self.remaining -= 1;
+                self.split.next()
+            }
+        }
+    }
+}
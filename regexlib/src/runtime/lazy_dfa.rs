@@ -0,0 +1,378 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Debug, Formatter};
+use std::mem::size_of;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+
+use crate::automata::Automaton;
+use crate::runtime::budget::SearchBudget;
+use crate::runtime::error::MatchError;
+use crate::runtime::Limits;
+
+/// A DFA over `automaton` that is determinized one transition at a time as the input demands it.
+///
+/// Each DFA state is the set of NFA states that are active at once, so a search only ever builds
+/// the part of the (potentially exponential) subset construction that it actually visits. The
+/// states built are kept in a [`DfaCache`], which later searches over the same automaton can
+/// reuse.
+pub struct LazyDfa<'search> {
+    automaton: &'search Automaton,
+    cache: &'search mut DfaCache,
+    memory_limit: Option<usize>,
+    budget: SearchBudget,
+    //how many times this search has cleared the cache
+    cache_clears: usize,
+}
+
+/// The DFA states a [`LazyDfa`] has built so far, and the transitions between them that it has
+/// followed. It belongs to the automaton it was first used with.
+pub struct DfaCache {
+    states: Vec<DfaState>,
+    state_ids: BTreeMap<Vec<usize>, usize>,
+    memory_usage: usize,
+}
+
+struct DfaState {
+    nfa_states: Vec<usize>,
+    is_match: bool,
+    transitions: BTreeMap<char, usize>,
+    //the state with the start states added, once it has been asked for
+    restarted: Option<usize>,
+}
+
+impl DfaCache {
+    pub fn new() -> Self {
+        let mut cache = Self {
+            states: Vec::new(),
+            state_ids: BTreeMap::new(),
+            memory_usage: 0,
+        };
+
+        //the dead state is tiny and always needed, so it is not charged against the limit
+        cache.states.push(DfaState {
+            nfa_states: Vec::new(),
+            is_match: false,
+            transitions: BTreeMap::new(),
+            restarted: None,
+        });
+        cache.state_ids.insert(Vec::new(), LazyDfa::DEAD_STATE);
+
+        cache
+    }
+
+    //forgets every state but the dead one
+    fn clear(&mut self) {
+        self.states.truncate(1);
+        self.states[LazyDfa::DEAD_STATE].transitions.clear();
+        self.states[LazyDfa::DEAD_STATE].restarted = None;
+        self.state_ids.clear();
+        self.state_ids.insert(Vec::new(), LazyDfa::DEAD_STATE);
+        self.memory_usage = 0;
+    }
+}
+
+impl Default for DfaCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`DfaCache`] kept by a compiled regex between its searches. Searches running at the same
+/// time can not share one cache, so a search that finds it in use builds a cache of its own.
+#[derive(Default)]
+pub struct SharedCache(Mutex<DfaCache>);
+
+impl SharedCache {
+    pub fn get(&self) -> CacheGuard<'_> {
+        match self.0.try_lock() {
+            Ok(cache) => CacheGuard::Shared(cache),
+            //a search that panicked may have left the cache half updated
+            Err(TryLockError::Poisoned(poisoned)) => {
+                let mut cache = poisoned.into_inner();
+                cache.clear();
+                //once cleared the cache is sound again, so later searches can keep what they build
+                self.0.clear_poison();
+                CacheGuard::Shared(cache)
+            }
+            Err(TryLockError::WouldBlock) => CacheGuard::Own(DfaCache::new()),
+        }
+    }
+}
+
+/// A clone starts out with an empty cache, as a freshly compiled regex would.
+impl Clone for SharedCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl Debug for SharedCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedCache").finish_non_exhaustive()
+    }
+}
+
+/// The cache a search works with: the shared one, or its own if the shared one was in use.
+pub enum CacheGuard<'cache> {
+    Shared(MutexGuard<'cache, DfaCache>),
+    Own(DfaCache),
+}
+
+impl Deref for CacheGuard<'_> {
+    type Target = DfaCache;
+
+    fn deref(&self) -> &DfaCache {
+        match self {
+            CacheGuard::Shared(cache) => cache,
+            CacheGuard::Own(cache) => cache,
+        }
+    }
+}
+
+impl DerefMut for CacheGuard<'_> {
+    fn deref_mut(&mut self) -> &mut DfaCache {
+        match self {
+            CacheGuard::Shared(cache) => cache,
+            CacheGuard::Own(cache) => cache,
+        }
+    }
+}
+
+impl<'search> LazyDfa<'search> {
+    /// The DFA state for the empty set of NFA states, from which nothing can match.
+    pub const DEAD_STATE: usize = 0;
+
+    /// Creates a DFA for a single search, which starts counting steps and time immediately.
+    /// `cache` must only ever have been used with `automaton`.
+    pub fn new(
+        automaton: &'search Automaton,
+        cache: &'search mut DfaCache,
+        limits: &Limits,
+    ) -> Self {
+        Self {
+            automaton,
+            cache,
+            memory_limit: limits.max_cache_bytes,
+            budget: SearchBudget::new(limits),
+            cache_clears: 0,
+        }
+    }
+
+    pub fn start_state(&mut self) -> Result<usize, MatchError> {
+        let nfa_states = self
+            .automaton
+            .start_states()
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>();
+
+        self.get_or_add_state(nfa_states.into_iter().collect())
+    }
+
+    /// The state reached from `from` on `next`. Building it may clear the cache, after which
+    /// `from` and every other state id but the one returned (and [`DEAD_STATE`](Self::DEAD_STATE))
+    /// are no longer valid.
+    pub fn next_state(&mut self, from: usize, next: char) -> Result<usize, MatchError> {
+        self.budget.step(1)?;
+
+        if let Some(to) = self.cache.states[from].transitions.get(&next) {
+            return Ok(*to);
+        }
+
+        let transitions = self.cache.states[from]
+            .nfa_states
+            .iter()
+            .flat_map(|state_id| &self.automaton.get_state(*state_id).transitions)
+            .collect::<Vec<_>>();
+        let transition_count = transitions.len() as u64;
+
+        let nfa_states = transitions
+            .into_iter()
+            .filter(|transition| transition.condition.matches(next))
+            .map(|transition| transition.next_state_id)
+            .collect::<BTreeSet<_>>();
+
+        //building a state costs one step per NFA transition considered
+        self.budget.step(transition_count)?;
+        self.budget.check_deadline()?;
+
+        let cleared_count = self.cache_clears;
+        let to = self.get_or_add_state(nfa_states.into_iter().collect())?;
+
+        //a cache cleared to make room for `to` no longer has `from` to record the transition in
+        if self.cache_clears != cleared_count {
+            return Ok(to);
+        }
+        if self.charge(size_of::<(char, usize)>()).is_err() {
+            return self.clear_keeping(to);
+        }
+        self.cache.states[from].transitions.insert(next, to);
+
+        Ok(to)
+    }
+
+    /// The state with the automaton's start states added to it, which lets an unanchored search
+    /// start a new match at the current position while continuing the ones already underway.
+    /// Like [`next_state`](Self::next_state), this may clear the cache.
+    pub fn restart_state(&mut self, from: usize) -> Result<usize, MatchError> {
+        if let Some(to) = self.cache.states[from].restarted {
+            return Ok(to);
+        }
+
+        let nfa_states = self.cache.states[from]
+            .nfa_states
+            .iter()
+            .chain(self.automaton.start_states())
+            .copied()
+            .collect::<BTreeSet<_>>();
+
+        let cleared_count = self.cache_clears;
+        let to = self.get_or_add_state(nfa_states.into_iter().collect())?;
+
+        if self.cache_clears != cleared_count {
+            return Ok(to);
+        }
+        if self.charge(size_of::<usize>()).is_err() {
+            return self.clear_keeping(to);
+        }
+        self.cache.states[from].restarted = Some(to);
+
+        Ok(to)
+    }
+
+    pub fn is_match_state(&self, state: usize) -> bool {
+        self.cache.states[state].is_match
+    }
+
+    /// The NFA states that make up `state`, in ascending order.
+    pub fn nfa_states(&self, state: usize) -> &[usize] {
+        &self.cache.states[state].nfa_states
+    }
+
+    //a full cache is cleared to make room, so that the search goes on building the states it needs from there; it only fails if the one state does not fit in an empty cache
+    fn get_or_add_state(&mut self, nfa_states: Vec<usize>) -> Result<usize, MatchError> {
+        if let Some(id) = self.cache.state_ids.get(&nfa_states) {
+            return Ok(*id);
+        }
+
+        //the set of NFA states is stored twice: once in the state and once as the lookup key
+        let bytes = size_of::<DfaState>() + 2 * nfa_states.len() * size_of::<usize>();
+        if self.charge(bytes).is_err() {
+            self.cache.clear();
+            self.cache_clears += 1;
+            self.charge(bytes)?;
+        }
+
+        let id = self.cache.states.len();
+        let is_match = nfa_states
+            .iter()
+            .any(|state_id| self.automaton.is_terminal_state(*state_id));
+
+        self.cache.state_ids.insert(nfa_states.clone(), id);
+        self.cache.states.push(DfaState {
+            nfa_states,
+            is_match,
+            transitions: BTreeMap::new(),
+            restarted: None,
+        });
+
+        Ok(id)
+    }
+
+    //clears the cache for the transitions that did not fit, keeping just the state the search is in, which is returned under its new id
+    fn clear_keeping(&mut self, state: usize) -> Result<usize, MatchError> {
+        let nfa_states = self.cache.states[state].nfa_states.clone();
+        self.cache.clear();
+        self.cache_clears += 1;
+
+        self.get_or_add_state(nfa_states)
+    }
+
+    //counts the bytes against the cache, unless they do not fit in it
+    fn charge(&mut self, bytes: usize) -> Result<(), MatchError> {
+        let memory_usage = self.cache.memory_usage + bytes;
+
+        match self.memory_limit {
+            Some(limit) if memory_usage > limit => Err(MatchError::CacheExhausted { limit }),
+            _ => {
+                self.cache.memory_usage = memory_usage;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use super::{DfaCache, LazyDfa, SharedCache};
+    use crate::automata::Automaton;
+    use crate::parser::RegexEntry;
+    use crate::runtime::Limits;
+
+    fn run(automaton: &Automaton, cache: &mut DfaCache, limits: &Limits, haystack: &str) -> bool {
+        let mut dfa = LazyDfa::new(automaton, cache, limits);
+        let mut state = dfa.start_state().unwrap();
+        for char in haystack.chars() {
+            state = dfa.next_state(state, char).unwrap();
+        }
+        dfa.is_match_state(state)
+    }
+
+    #[test]
+    fn test_cache_is_reused() {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse("[a-c]*d").unwrap());
+        automaton.simplify();
+        let mut cache = DfaCache::new();
+
+        assert!(run(&automaton, &mut cache, &Limits::default(), "abcd"));
+        let built = cache.states.len();
+        let memory_usage = cache.memory_usage;
+
+        //a second search over the same characters only follows transitions it already has
+        assert!(run(&automaton, &mut cache, &Limits::default(), "cbad"));
+        assert_eq!(cache.states.len(), built);
+        assert_eq!(cache.memory_usage, memory_usage);
+    }
+
+    #[test]
+    fn test_full_cache_is_cleared() {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse("[a-z]*x[a-z]{8}").unwrap());
+        automaton.simplify();
+        let limits = Limits {
+            max_cache_bytes: Some(512),
+            ..Limits::default()
+        };
+        let mut cache = DfaCache::new();
+
+        for haystack in ["xabcdefgh", "abxxxxxxxxxxxxxxxxxy", "abcdefgh"] {
+            assert_eq!(
+                run(&automaton, &mut cache, &limits, haystack),
+                automaton.is_match(haystack)
+            );
+            assert!(cache.memory_usage <= 512);
+        }
+    }
+    #[test]
+    fn test_poisoned_cache_is_recovered() {
+        let mut automaton = Automaton::from_regex(RegexEntry::parse("[a-c]*d").unwrap());
+        automaton.simplify();
+        let shared = SharedCache::default();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut cache = shared.get();
+            run(&automaton, &mut cache, &Limits::default(), "abcd");
+            panic!("a search panicking partway");
+        }));
+        assert!(result.is_err());
+        assert!(shared.0.is_poisoned());
+
+        //the search after the panic clears the cache, and the one after that keeps what it built
+        assert!(run(&automaton, &mut shared.get(), &Limits::default(), "abcd"));
+        assert!(!shared.0.is_poisoned());
+        let built = shared.get().states.len();
+        assert!(built > 1);
+        assert_eq!(shared.get().states.len(), built);
+    }
+}
@@ -0,0 +1,74 @@
+use std::io::{self, BufRead};
+
+use crate::runtime::Regex;
+
+impl Regex {
+    /// The lines of `haystack` that contain a match, like `grep`. Lines are split as by
+    /// [`str::lines`], so the returned lines do not include their `\n` or `\r\n`.
+    ///
+    /// Note that, unlike [`is_match`](Self::is_match), a line does not have to match as a whole;
+    /// use `lines().filter(|line| regex.is_match(line))` for that.
+    ///
+    /// # Panics
+    ///
+    /// Iterating panics if searching a line exceeds one of the configured
+    /// [`Limits`](super::Limits).
+    pub fn matching_lines<'regex, 'haystack>(
+        &'regex self,
+        haystack: &'haystack str,
+    ) -> impl Iterator<Item = &'haystack str> + 'regex
+    where
+        'haystack: 'regex,
+    {
+        haystack
+            .lines()
+            .filter(move |line| self.find(line).is_some())
+    }
+
+    /// Like [`matching_lines`](Self::matching_lines), but reads the lines from `reader` as they are
+    /// needed instead of requiring the whole input up front. Errors from the reader (including
+    /// invalid UTF-8) are passed through.
+    ///
+    /// # Panics
+    ///
+    /// Iterating panics if searching a line exceeds one of the configured
+    /// [`Limits`](super::Limits).
+    pub fn matching_lines_from<'regex>(
+        &'regex self,
+        reader: impl BufRead + 'regex,
+    ) -> impl Iterator<Item = io::Result<String>> + 'regex {
+        reader.lines().filter(move |line| match line {
+            Ok(line) => self.find(line).is_some(),
+            Err(_) => true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::runtime::Regex;
+
+    #[test]
+    fn test_matching_lines() {
+        let regex = Regex::new("(ERROR)|(WARN)").unwrap();
+        let log = "INFO started\nERROR failed\r\nINFO retrying\nWARN slow\n";
+
+        assert_eq!(
+            regex.matching_lines(log).collect::<Vec<_>>(),
+            vec!["ERROR failed", "WARN slow"]
+        );
+    }
+
+    #[test]
+    fn test_matching_lines_from() {
+        let regex = Regex::new("[0-9]+").unwrap();
+        let lines = regex
+            .matching_lines_from(Cursor::new("a\nb1\nc\n22"))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(lines, vec!["b1", "22"]);
+    }
+}
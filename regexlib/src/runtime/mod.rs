@@ -0,0 +1,943 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::automata::Automaton;
+use crate::parser::{Dialect, ParseOptions, RegexEntry};
+
+use self::budget::SearchBudget;
+pub use self::captures::{Captures, Match};
+pub use self::error::{CompileError, MatchError};
+use self::iter::Searcher;
+pub use self::iter::{CapturesIter, FindIter, Split, SplitN};
+use self::lazy_dfa::{LazyDfa, SharedCache};
+pub use self::set::{RegexSet, SetMatches};
+
+mod budget;
+mod captures;
+#[cfg(any(feature = "nom", feature = "winnow"))]
+mod combinators;
+mod error;
+mod iter;
+mod lazy_dfa;
+mod lines;
+mod pike_vm;
+mod replace;
+mod set;
+
+/// A regex compiled at runtime, for patterns that are not known until the program runs.
+///
+/// Like the functions generated by `fastregex::matcher!`, [`is_match`](Self::is_match) and
+/// [`captures`](Self::captures) are anchored at both ends: the whole haystack must match the
+/// pattern. The `find` family of methods (and everything built on them, like `replace`) instead
+/// search for matches anywhere in the haystack.
+#[derive(Clone, Debug)]
+pub struct Regex {
+    pattern: String,
+    automaton: Automaton,
+    //captures are found on the automaton before simplification, which still has the transitions marking group boundaries
+    capture_automaton: Automaton,
+    capture_count: usize,
+    //shared with every [`Captures`] found, which look groups up by name in it
+    capture_names: Arc<[Option<String>]>,
+    //for a pattern whose top level is an alternation, the union of its branches, with one terminal state per branch
+    branch_automaton: Option<Automaton>,
+    //the lazy DFA only matches `^` and `$` at the ends of the whole haystack, so searches for matches anywhere else fall back to the capture automaton
+    has_start_anchor: bool,
+    has_end_anchor: bool,
+    limits: Limits,
+    //the lazy DFA states built by earlier searches, over `automaton` and `branch_automaton`
+    cache: SharedCache,
+    branch_cache: SharedCache,
+}
+
+/// Resource bounds for compiling and running a [`Regex`]. `None` means unbounded.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Limits {
+    /// The maximum number of states the automaton may have before simplification.
+    pub max_states: Option<usize>,
+    /// The maximum number of bytes the lazy DFA's cache of states may take. The cache is kept
+    /// between searches and cleared whenever it is full, so a search only fails for want of
+    /// cache if a single state does not fit in it.
+    pub max_cache_bytes: Option<usize>,
+    /// The maximum number of steps one search may take. Each character consumed is a step, and
+    /// the engine's work for a character costs more: building a new DFA state costs one step per
+    /// NFA transition it has to consider, and tracking captures costs one step per NFA state.
+    pub max_steps: Option<u64>,
+    /// The maximum wall-clock time one search may take. The clock is only read periodically, so
+    /// a search may overrun this slightly before it is aborted.
+    pub max_duration: Option<Duration>,
+}
+
+impl Regex {
+    pub fn new(pattern: &str) -> Result<Self, CompileError> {
+        Self::with_limits(pattern, Limits::default())
+    }
+
+    pub fn with_limits(pattern: &str, limits: Limits) -> Result<Self, CompileError> {
+        let regex = RegexEntry::parse(pattern).map_err(CompileError::Parse)?;
+        Self::from_entry(pattern, regex, limits)
+    }
+
+    /// Compiles a pattern written in another regex dialect (e.g., RE2), which keeps the meaning it
+    /// has there or fails to compile.
+    pub fn in_dialect(pattern: &str, dialect: Dialect) -> Result<Self, CompileError> {
+        Self::in_dialect_with_limits(pattern, dialect, Limits::default())
+    }
+
+    pub fn in_dialect_with_limits(
+        pattern: &str,
+        dialect: Dialect,
+        limits: Limits,
+    ) -> Result<Self, CompileError> {
+        let regex = RegexEntry::parse_in_dialect(pattern, dialect).map_err(CompileError::Parse)?;
+        Self::from_entry(pattern, regex, limits)
+    }
+
+    /// Compiles a pattern read as `options` say, e.g., with a
+    /// [`LineTerminator`](crate::line_terminator::LineTerminator) other than `\n`.
+    pub fn with_options(
+        pattern: &str,
+        options: ParseOptions,
+        limits: Limits,
+    ) -> Result<Self, CompileError> {
+        let regex = RegexEntry::parse_with_options(pattern, options).map_err(CompileError::Parse)?;
+        Self::from_entry(pattern, regex, limits)
+    }
+
+    /// Compiles a shell glob (e.g., `*.tar.gz`), as converted by [`crate::from_glob`]. Like a
+    /// regex, the glob has to match the whole haystack for [`is_match`](Self::is_match).
+    pub fn from_glob(glob: &str) -> Result<Self, CompileError> {
+        Self::from_glob_with_limits(glob, Limits::default())
+    }
+
+    pub fn from_glob_with_limits(glob: &str, limits: Limits) -> Result<Self, CompileError> {
+        let regex = crate::from_glob(glob).map_err(CompileError::Glob)?;
+        Self::from_entry(glob, regex, limits)
+    }
+
+    //`pattern` is what the entry was converted from, which is what [`pattern`](Self::pattern) reports
+    pub(crate) fn from_entry(pattern: &str, regex: RegexEntry, limits: Limits) -> Result<Self, CompileError> {
+        if let Some(limit) = limits.max_states {
+            let states = Automaton::constructed_state_count(&regex);
+            if states > limit as u64 {
+                return Err(CompileError::TooManyStates { states, limit });
+            }
+        }
+
+        let capture_count = regex.capture_count();
+        let capture_names = regex
+            .capture_names()
+            .into_iter()
+            .map(|name| name.map(String::from))
+            .collect();
+        let has_start_anchor = regex.has_start_anchor();
+        let has_end_anchor = regex.has_end_anchor();
+        let branch_automaton = match &regex {
+            RegexEntry::Alternation(branches) => {
+                let mut branch_automaton = Automaton::from_regexes(branches.clone());
+                branch_automaton.simplify();
+                Some(branch_automaton)
+            }
+            _ => None,
+        };
+        let capture_automaton = Automaton::from_regex(regex);
+        let mut automaton = capture_automaton.clone();
+        automaton.simplify();
+
+        Ok(Self {
+            pattern: pattern.into(),
+            automaton,
+            capture_automaton,
+            capture_count,
+            capture_names,
+            branch_automaton,
+            has_start_anchor,
+            has_end_anchor,
+            limits,
+            cache: SharedCache::default(),
+            branch_cache: SharedCache::default(),
+        })
+    }
+
+    /// Whether the whole haystack matches.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`]; use
+    /// [`try_is_match`](Self::try_is_match) to handle that case instead.
+    pub fn is_match(&self, haystack: &str) -> bool {
+        match self.try_is_match(haystack) {
+            Ok(is_match) => is_match,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_is_match(&self, haystack: &str) -> Result<bool, MatchError> {
+        let haystack = match haystack.strip_prefix(self.automaton.prefix()) {
+            Some(haystack) => haystack,
+            None => return Ok(false),
+        };
+
+        let mut cache = self.cache.get();
+        let mut dfa = LazyDfa::new(&self.automaton, &mut cache, &self.limits);
+        let mut state = dfa.start_state()?;
+
+        for char in haystack.chars() {
+            state = dfa.next_state(state, char)?;
+
+            if state == LazyDfa::DEAD_STATE {
+                return Ok(false);
+            }
+        }
+
+        Ok(dfa.is_match_state(state))
+    }
+
+    /// For a pattern whose top level is an alternation (e.g., `(GET)|(POST)|(PUT)`), the index of
+    /// the branch that matches the whole haystack, or `None` if none does. If several branches
+    /// match, the first one is reported. A pattern that is not an alternation is treated as a
+    /// single branch, numbered 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`]; use
+    /// [`try_matching_branch`](Self::try_matching_branch) to handle that case instead.
+    pub fn matching_branch(&self, haystack: &str) -> Option<usize> {
+        match self.try_matching_branch(haystack) {
+            Ok(branch) => branch,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_matching_branch(&self, haystack: &str) -> Result<Option<usize>, MatchError> {
+        match &self.branch_automaton {
+            Some(branch_automaton) => {
+                Ok(
+                    set::matching_terminals(
+                        branch_automaton,
+                        &mut self.branch_cache.get(),
+                        &self.limits,
+                        haystack,
+                    )?
+                        .iter()
+                        .position(|matched| *matched),
+                )
+            }
+            None => Ok(self.try_is_match(haystack)?.then_some(0)),
+        }
+    }
+
+    /// The spans of each capture group if the whole haystack matches.
+    ///
+    /// When a group could match several parts of the haystack, earlier alternation branches are
+    /// preferred and repetitions match as much as they can. A group inside a repetition reports
+    /// its last iteration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`]; use
+    /// [`try_captures`](Self::try_captures) to handle that case instead.
+    pub fn captures<'haystack>(&self, haystack: &'haystack str) -> Option<Captures<'haystack>> {
+        match self.try_captures(haystack) {
+            Ok(captures) => captures,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_captures<'haystack>(
+        &self,
+        haystack: &'haystack str,
+    ) -> Result<Option<Captures<'haystack>>, MatchError> {
+        let mut budget = SearchBudget::new(&self.limits);
+        let slots = pike_vm::full_match(
+            &self.capture_automaton,
+            haystack,
+            self.capture_count,
+            &mut budget,
+        )?;
+
+        Ok(slots.map(|slots| Captures::new(haystack, slots, self.capture_names.clone())))
+    }
+
+    /// The leftmost match anywhere in the haystack. Among matches starting at the same position,
+    /// the same preferences as for [`captures`](Self::captures) decide which one is reported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`]; use
+    /// [`try_find`](Self::try_find) to handle that case instead.
+    pub fn find<'haystack>(&self, haystack: &'haystack str) -> Option<Match<'haystack>> {
+        match self.try_find(haystack) {
+            Ok(found) => found,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_find<'haystack>(
+        &self,
+        haystack: &'haystack str,
+    ) -> Result<Option<Match<'haystack>>, MatchError> {
+        Ok(Searcher::new(self, haystack)
+            .next_captures()?
+            .and_then(|captures| captures.get(0)))
+    }
+
+    /// Like [`find`](Self::find), but returns the matched part of the haystack itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`]; use
+    /// [`try_find_str`](Self::try_find_str) to handle that case instead.
+    pub fn find_str<'haystack>(&self, haystack: &'haystack str) -> Option<&'haystack str> {
+        self.find(haystack).map(|found| found.as_str())
+    }
+
+    pub fn try_find_str<'haystack>(
+        &self,
+        haystack: &'haystack str,
+    ) -> Result<Option<&'haystack str>, MatchError> {
+        Ok(self.try_find(haystack)?.map(|found| found.as_str()))
+    }
+
+    /// The end of the match that ends earliest anywhere in the haystack, or `None` if there is no
+    /// match. The search stops as soon as some match is complete, without reading further to see
+    /// where it (or a match starting further left) could end instead, so this is cheaper than
+    /// [`find`](Self::find) when only the presence or position of a match matters, e.g. when
+    /// scanning a stream for a delimiter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`]; use
+    /// [`try_shortest_match`](Self::try_shortest_match) to handle that case instead.
+    pub fn shortest_match(&self, haystack: &str) -> Option<usize> {
+        match self.try_shortest_match(haystack) {
+            Ok(end) => end,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_shortest_match(&self, haystack: &str) -> Result<Option<usize>, MatchError> {
+        if self.has_start_anchor || self.has_end_anchor {
+            let mut budget = SearchBudget::new(&self.limits);
+            return pike_vm::shortest_match(&self.capture_automaton, haystack, &mut budget);
+        }
+
+        //the automaton starts after its literal prefix, so a match can only start where that prefix has just been read
+        let prefix = self.automaton.prefix().as_bytes();
+        let mut cache = self.cache.get();
+        let mut dfa = LazyDfa::new(&self.automaton, &mut cache, &self.limits);
+        let mut state = LazyDfa::DEAD_STATE;
+        let mut chars = haystack.char_indices();
+
+        loop {
+            let position = chars.offset();
+            if haystack.as_bytes()[..position].ends_with(prefix) {
+                state = dfa.restart_state(state)?;
+            }
+
+            if dfa.is_match_state(state) {
+                return Ok(Some(position));
+            }
+
+            match chars.next() {
+                Some((_, char)) => state = dfa.next_state(state, char)?,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// The length of the longest start of the haystack that matches, or `None` if no start does
+    /// (not even the empty one). This is what a parser consuming a token does, and what the
+    /// [`nom_parser`](Self::nom_parser) and [`winnow_parser`](Self::winnow_parser) adapters build
+    /// on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`]; use
+    /// [`try_longest_prefix`](Self::try_longest_prefix) to handle that case instead.
+    pub fn longest_prefix(&self, haystack: &str) -> Option<usize> {
+        match self.try_longest_prefix(haystack) {
+            Ok(end) => end,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_longest_prefix(&self, haystack: &str) -> Result<Option<usize>, MatchError> {
+        //a match found by the lazy DFA starts at the start of the haystack, so only `$` is a problem
+        if self.has_end_anchor {
+            let mut budget = SearchBudget::new(&self.limits);
+            return pike_vm::longest_prefix(&self.capture_automaton, haystack, &mut budget);
+        }
+
+        let prefix = self.automaton.prefix();
+        let Some(rest) = haystack.strip_prefix(prefix) else {
+            return Ok(None);
+        };
+
+        let mut cache = self.cache.get();
+        let mut dfa = LazyDfa::new(&self.automaton, &mut cache, &self.limits);
+        let mut state = dfa.start_state()?;
+        let mut longest = None;
+        let mut chars = rest.char_indices();
+
+        //reading stops once no match can be extended any further
+        while state != LazyDfa::DEAD_STATE {
+            if dfa.is_match_state(state) {
+                longest = Some(prefix.len() + chars.offset());
+            }
+
+            match chars.next() {
+                Some((_, char)) => state = dfa.next_state(state, char)?,
+                None => break,
+            }
+        }
+
+        Ok(longest)
+    }
+
+    /// Every non-overlapping match in the haystack, from left to right.
+    pub fn find_iter<'regex, 'haystack>(
+        &'regex self,
+        haystack: &'haystack str,
+    ) -> FindIter<'regex, 'haystack> {
+        FindIter {
+            searcher: Searcher::new(self, haystack),
+        }
+    }
+
+    /// The captures of every non-overlapping match in the haystack, from left to right.
+    pub fn captures_iter<'regex, 'haystack>(
+        &'regex self,
+        haystack: &'haystack str,
+    ) -> CapturesIter<'regex, 'haystack> {
+        CapturesIter {
+            searcher: Searcher::new(self, haystack),
+        }
+    }
+
+    /// The substrings of the haystack separated by matches, from left to right. Matches at the
+    /// start or end of the haystack produce empty substrings there, as do adjacent matches.
+    pub fn split<'regex, 'haystack>(
+        &'regex self,
+        haystack: &'haystack str,
+    ) -> Split<'regex, 'haystack> {
+        Split {
+            finder: self.find_iter(haystack),
+            last: 0,
+            done: false,
+        }
+    }
+
+    /// Like [`split`](Self::split), but produces at most `limit` substrings, the last of which is
+    /// the unsplit rest of the haystack.
+    pub fn splitn<'regex, 'haystack>(
+        &'regex self,
+        haystack: &'haystack str,
+        limit: usize,
+    ) -> SplitN<'regex, 'haystack> {
+        SplitN {
+            split: self.split(haystack),
+            remaining: limit,
+        }
+    }
+
+    /// The number of capture groups, including the implicit group 0 for the whole match.
+    pub fn captures_len(&self) -> usize {
+        self.capture_count + 1
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn automaton(&self) -> &Automaton {
+        &self.automaton
+    }
+
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+}
+
+impl FromStr for Regex {
+    type Err = CompileError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        Self::new(pattern)
+    }
+}
+
+impl TryFrom<&str> for Regex {
+    type Error = CompileError;
+
+    fn try_from(pattern: &str) -> Result<Self, Self::Error> {
+        Self::new(pattern)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+    use std::time::Duration;
+
+    use super::{CompileError, Limits, Match, MatchError, Regex, RegexSet};
+    use crate::line_terminator::LineTerminator;
+    use crate::parser::{Dialect, ParseErrorKind, ParseOptions};
+
+    #[test]
+    fn test_is_match() {
+        let regex = Regex::new("https?://(?:(([A-Za-z.]+/)+([A-Za-z.]+)?)|([A-Za-z.]+))").unwrap();
+
+        assert!(regex.is_match("http://test"));
+        assert!(regex.is_match("http://example.com/this/is/a/test/page.html"));
+        assert!(regex.is_match("http://example.com"));
+        assert!(!regex.is_match("http:/"));
+        assert!(!regex.is_match("http://"));
+        assert!(!regex.is_match(""));
+        assert!(!regex.is_match("The quick brown fox jumped over the lazy dog."));
+    }
+
+    #[test]
+    fn test_unicode_classes() {
+        let regex = Regex::new(r"\pL+\d\P{Lu}").unwrap();
+
+        assert!(regex.is_match("Größe٣x"));
+        assert!(!regex.is_match("Größe٣X"));
+        assert!(!regex.is_match("3x"));
+    }
+
+    #[test]
+    fn test_shorthands_in_brackets() {
+        let regex = Regex::new(r"[\w.-]+@[+-]?[\d]+").unwrap();
+
+        assert!(regex.is_match("jöhn.doe-1@-42"));
+        assert!(regex.is_match("a@+٣"));
+        assert!(!regex.is_match("a b@1"));
+        assert!(!regex.is_match("a@x"));
+    }
+
+    #[test]
+    fn test_state_limit() {
+        let limits = Limits {
+            max_states: Some(100),
+            ..Limits::default()
+        };
+
+        assert!(Regex::with_limits("(a|b){10}", limits).is_ok());
+        assert!(matches!(
+            Regex::with_limits("(a|b){1000,2000}", limits),
+            Err(CompileError::TooManyStates { limit: 100, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cache_limit() {
+        let limits = Limits {
+            max_cache_bytes: Some(1024),
+            ..Limits::default()
+        };
+        let regex = Regex::with_limits("[a-z]*x[a-z]{8}", limits).unwrap();
+
+        //the cache is cleared whenever it fills up, so the search still finishes
+        assert_eq!(regex.try_is_match("x"), Ok(false));
+        assert_eq!(
+            regex.try_is_match("abcdefghijklmnopqrstuvwxyzxxxxxxxxxxxxxxxxx"),
+            Ok(true)
+        );
+
+        //but not if not even one state fits
+        let limits = Limits {
+            max_cache_bytes: Some(64),
+            ..Limits::default()
+        };
+        let regex = Regex::with_limits("[a-z]*x[a-z]{8}", limits).unwrap();
+        assert_eq!(
+            regex.try_is_match("x"),
+            Err(MatchError::CacheExhausted { limit: 64 })
+        );
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let limits = Limits {
+            max_steps: Some(100),
+            ..Limits::default()
+        };
+        let regex = Regex::with_limits("[ab]*c", limits).unwrap();
+
+        assert_eq!(regex.try_is_match("abababc"), Ok(true));
+        assert_eq!(
+            regex.try_is_match(&"ab".repeat(100)),
+            Err(MatchError::StepLimitExceeded { limit: 100 })
+        );
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let limits = Limits {
+            max_duration: Some(Duration::ZERO),
+            ..Limits::default()
+        };
+        let regex = Regex::with_limits("[ab]*c", limits).unwrap();
+
+        assert_eq!(
+            regex.try_is_match(&"ab".repeat(10_000)),
+            Err(MatchError::TimedOut {
+                limit: Duration::ZERO
+            })
+        );
+    }
+
+    #[test]
+    fn test_captures() {
+        let regex = Regex::new("https?://(([A-Za-z.]+/)+([A-Za-z.]+)?)").unwrap();
+        let haystack = "http://example.com/index.html";
+        let captures = regex.captures(haystack).unwrap();
+
+        assert_eq!(captures.len(), 4);
+        assert_eq!(captures.get(0), Some(Match::new(haystack, 0, 29)));
+        assert_eq!(&captures[1], "example.com/index.html");
+        assert_eq!(captures.get(2).unwrap().range(), 7..19);
+        assert_eq!(&captures[3], "index.html");
+
+        assert!(regex.captures("http://").is_none());
+    }
+
+    #[test]
+    fn test_captures_priority() {
+        let regex = Regex::new("(a*)(a*)(b)?").unwrap();
+        let captures = regex.captures("aaa").unwrap();
+
+        assert_eq!(&captures[1], "aaa");
+        assert_eq!(&captures[2], "");
+        assert_eq!(captures.get(3), None);
+
+        let regex = Regex::new("((a)|(b))+").unwrap();
+        let captures = regex.captures("ab").unwrap();
+
+        assert_eq!(&captures[1], "b");
+        assert_eq!(&captures[2], "a");
+        assert_eq!(&captures[3], "b");
+    }
+
+    #[test]
+    fn test_named_captures() {
+        let regex = Regex::new("(?P<key>[a-z]+)=(?:(?<value>[a-z]+)|([0-9]+))").unwrap();
+        let captures = regex.captures("width=auto").unwrap();
+
+        assert_eq!(captures.name("key").unwrap().range(), 0..5);
+        assert_eq!(captures.name("value").unwrap().as_str(), "auto");
+        assert_eq!(captures.name("missing"), None);
+
+        let captures = regex.captures("width=10").unwrap();
+        assert_eq!(captures.name("value"), None);
+        assert_eq!(&captures[3], "10");
+
+        let found = regex.captures_iter("a=1 b=c").nth(1).unwrap();
+        assert_eq!(found.name("key").unwrap().as_str(), "b");
+    }
+
+    #[test]
+    fn test_find_iter() {
+        let regex = Regex::new("[0-9]+").unwrap();
+        let found = regex
+            .find_iter("a1b22c333")
+            .map(|found| found.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(found, vec!["1", "22", "333"]);
+        assert_eq!(regex.find("abc"), None);
+
+        let regex = Regex::new("a*").unwrap();
+        let found = regex
+            .find_iter("baaa")
+            .map(|found| found.range())
+            .collect::<Vec<_>>();
+
+        assert_eq!(found, vec![0..0, 1..4]);
+    }
+
+    #[test]
+    fn test_find_leftmost_first() {
+        let regex = Regex::new("(ab|a)(bc)?").unwrap();
+
+        assert_eq!(regex.find("xabc").unwrap().as_str(), "ab");
+    }
+
+    #[test]
+    fn test_lazy_quantifiers() {
+        let regex = Regex::new("(a+?)(a*)").unwrap();
+        let captures = regex.captures("aaa").unwrap();
+
+        assert_eq!(&captures[1], "a");
+        assert_eq!(&captures[2], "aa");
+
+        let regex = Regex::new("<(.+?)>").unwrap();
+        assert_eq!(regex.find_str("<a><b>"), Some("<a>"));
+        assert_eq!(&regex.captures("<a><b>").unwrap()[1], "a><b");
+
+        let found = Regex::new("a{2,3}?")
+            .unwrap()
+            .find_iter("aaaaa")
+            .map(|found| found.range())
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec![0..2, 2..4]);
+    }
+
+    #[test]
+    fn test_anchors() {
+        let regex = Regex::new("^[0-9]+").unwrap();
+        assert!(regex.is_match("123"));
+        assert_eq!(regex.find_str("12 34"), Some("12"));
+        assert_eq!(regex.find_str("a12"), None);
+        assert_eq!(regex.shortest_match("12 34"), Some(1));
+        assert_eq!(regex.shortest_match("a12"), None);
+
+        let regex = Regex::new("[0-9]+$").unwrap();
+        assert_eq!(regex.find_str("12 34"), Some("34"));
+        assert_eq!(regex.shortest_match("12 34"), Some(5));
+        assert_eq!(regex.longest_prefix("12 34"), None);
+        assert_eq!(regex.longest_prefix("1234"), Some(4));
+
+        //an anchor inside the pattern only holds at the matching end of the haystack
+        let regex = Regex::new("(a$)|(ab)").unwrap();
+        assert!(regex.is_match("a"));
+        assert!(regex.is_match("ab"));
+        let found = regex
+            .find_iter("ab a")
+            .map(|found| found.range())
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec![0..2, 3..4]);
+
+        let regex = Regex::new("(?m)^[0-9]+$").unwrap();
+        let found = regex
+            .find_iter("12\na3\n45")
+            .map(|found| found.range())
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec![0..2, 6..8]);
+        assert_eq!(regex.longest_prefix("12\nab"), Some(2));
+        assert_eq!(regex.longest_prefix("12ab"), None);
+
+        //only a `\n` ends a line by default, so the `\r` of a `\r\n` is part of the line before it
+        let regex = Regex::new("(?m-s)^.*$").unwrap();
+        let found = regex
+            .find_iter("ab\r\ncd")
+            .map(|found| found.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec!["ab\r", "cd"]);
+    }
+
+    #[test]
+    fn test_line_terminators() {
+        let compile = |pattern: &str, line_terminator: LineTerminator| {
+            let options = ParseOptions {
+                line_terminator,
+                ..ParseOptions::default()
+            };
+            Regex::with_options(pattern, options, Limits::default()).unwrap()
+        };
+        fn lines<'haystack>(regex: &Regex, haystack: &'haystack str) -> Vec<&'haystack str> {
+            regex
+                .find_iter(haystack)
+                .map(|found| found.as_str())
+                .collect()
+        }
+
+        let regex = compile("(?m-s)^.*$", LineTerminator::Crlf);
+        assert_eq!(lines(&regex, "ab\r\ncd\re"), vec!["ab", "cd", "e"]);
+        let regex = compile("(?m-s)^.+$", LineTerminator::Unicode);
+        assert_eq!(
+            lines(&regex, "ab\u{2028}cd\u{85}e\x0Cf"),
+            vec!["ab", "cd", "e", "f"]
+        );
+
+        //neither anchor matches between the `\r` and `\n` of a `\r\n`
+        let regex = compile("(?m)\r$", LineTerminator::Crlf);
+        assert!(regex.find("a\r\n").is_none());
+        assert!(regex.find("a\r\r").is_some());
+        let regex = compile("(?m)^\n", LineTerminator::Crlf);
+        assert!(regex.find("a\r\n").is_none());
+        assert!(regex.find("a\n\n").is_some());
+        assert_eq!(
+            regex.captures("\n").unwrap().get(0).map(|found| found.range()),
+            Some(0..1)
+        );
+
+        let regex = compile(r"a\Rb", LineTerminator::Crlf);
+        assert!(regex.is_match("a\r\nb"));
+        assert!(regex.is_match("a\rb"));
+        assert!(!regex.is_match("a\u{2028}b"));
+        assert!(compile(r"a\Rb", LineTerminator::Unicode).is_match("a\u{2028}b"));
+        assert!(!compile(r"a\Rb", LineTerminator::Newline).is_match("a\r\nb"));
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        let regex = Regex::new("\\b{start}[a-z]+\\b{end}").unwrap();
+        assert!(regex.is_match("cat"));
+        let found = regex
+            .find_iter("a cat, concat2 dog")
+            .map(|found| found.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec!["a", "cat", "dog"]);
+        assert_eq!(regex.shortest_match("cats!"), Some(4));
+        assert_eq!(regex.longest_prefix("ab1"), None);
+
+        //the characters on either side of the haystack's part count, even when they are not matched
+        let regex = Regex::new("\\b{end}-").unwrap();
+        assert_eq!(regex.find_str("--a-").map(str::len), Some(1));
+        assert_eq!(regex.find("--a-").map(|found| found.start()), Some(3));
+    }
+
+    #[test]
+    fn test_find_str() {
+        let regex = Regex::new("[0-9]+").unwrap();
+        let haystack = String::from("abc123def");
+
+        let found = regex.find_str(&haystack).unwrap();
+        assert_eq!(found, "123");
+        assert_eq!(found.as_ptr(), haystack[3..].as_ptr());
+        assert_eq!(regex.find_str("abc"), None);
+    }
+
+    #[test]
+    fn test_in_dialect() {
+        let regex = Regex::in_dialect(r"\w+\.txt", Dialect::Re2).unwrap();
+        assert!(regex.is_match("notes_2.txt"));
+        assert!(!regex.is_match("notes\\txt"));
+        assert_eq!(regex.find_str("see notes.txt"), Some("notes.txt"));
+
+        //`|` splits the whole concatenations on either side, as it does in RE2
+        let regex = Regex::in_dialect("ab|cd", Dialect::Re2).unwrap();
+        assert!(regex.is_match("ab"));
+        assert!(regex.is_match("cd"));
+        assert!(!regex.is_match("abd"));
+
+        assert!(matches!(
+            Regex::in_dialect(r"\w", Dialect::PosixEre),
+            Err(CompileError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_longest_prefix() {
+        let regex = Regex::new("[0-9]+").unwrap();
+        assert_eq!(regex.longest_prefix("123abc"), Some(3));
+        assert_eq!(regex.longest_prefix("abc123"), None);
+
+        assert_eq!(Regex::new("x*").unwrap().longest_prefix("yyy"), Some(0));
+        assert_eq!(Regex::new("ab(cd)*").unwrap().longest_prefix("abcdcdc"), Some(6));
+        assert_eq!(Regex::new("ab(cd)*").unwrap().longest_prefix("a"), None);
+        assert_eq!(Regex::new("é+").unwrap().longest_prefix("éé!"), Some(4));
+    }
+
+    #[test]
+    fn test_shortest_match() {
+        let regex = Regex::new("[0-9]+").unwrap();
+        assert_eq!(regex.shortest_match("abc123def"), Some(4));
+        assert_eq!(regex.shortest_match("abc"), None);
+
+        //the match that ends first wins, even if another one starts further left
+        let regex = Regex::new("(a[0-9]*x)|(bc)").unwrap();
+        assert_eq!(regex.shortest_match("a12bcx"), Some(5));
+
+        //the literal prefix has to be read before the rest of the pattern is
+        let regex = Regex::new("abc+").unwrap();
+        assert_eq!(regex.shortest_match("aabxabcc"), Some(7));
+        assert_eq!(Regex::new("x*").unwrap().shortest_match("yyy"), Some(0));
+        assert_eq!(Regex::new("éé").unwrap().shortest_match("aéaéé"), Some(8));
+    }
+
+    #[test]
+    fn test_replace() {
+        let regex = Regex::new("([a-z]+)=([0-9]+)").unwrap();
+
+        assert_eq!(regex.replace("a=1 b=2", "$2=$1"), "1=a b=2");
+        assert_eq!(regex.replace_all("a=1 b=2", "${2}x=$1"), "1x=a 2x=b");
+        assert_eq!(
+            regex.replace_all("a=1", "$$1 $0 $1a ${name} $"),
+            "$1 a=1   $"
+        );
+        assert!(matches!(
+            regex.replace_all("none", "$0"),
+            Cow::Borrowed("none")
+        ));
+
+        let regex = Regex::new("(?P<k>[a-z]+)=(?<v>[0-9]+)").unwrap();
+        assert_eq!(regex.replace_all("a=1 b=2", "${v}:${k}"), "1:a 2:b");
+        assert_eq!(regex.replace_all("a=1", "$v$k $1 ${x}"), "1a a ");
+    }
+
+    #[test]
+    fn test_split() {
+        let regex = Regex::new("[,;] *").unwrap();
+
+        assert_eq!(
+            regex.split("a, b;c,, d").collect::<Vec<_>>(),
+            vec!["a", "b", "c", "", "d"]
+        );
+        assert_eq!(regex.split(",a,").collect::<Vec<_>>(), vec!["", "a", ""]);
+        assert_eq!(regex.split("").collect::<Vec<_>>(), vec![""]);
+    }
+
+    #[test]
+    fn test_splitn() {
+        let regex = Regex::new(",").unwrap();
+
+        assert_eq!(
+            regex.splitn("a,b,c", 2).collect::<Vec<_>>(),
+            vec!["a", "b,c"]
+        );
+        assert_eq!(
+            regex.splitn("a,b,c", 5).collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(regex.splitn("a,b,c", 1).collect::<Vec<_>>(), vec!["a,b,c"]);
+        assert_eq!(regex.splitn("a,b,c", 0).count(), 0);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let regex: Regex = "[a-z]+".parse().unwrap();
+        assert!(regex.is_match("abc"));
+
+        let regex = Regex::try_from("[0-9]+").unwrap();
+        assert!(regex.is_match("123"));
+
+        //the error says where in the pattern it is, for a caller to report
+        let error = "ab{2,x}".parse::<Regex>().unwrap_err();
+        let CompileError::Parse(parse_error) = &error else {
+            panic!("expected a parse error, got {error:?}");
+        };
+        assert_eq!(parse_error.kind, ParseErrorKind::InvalidRepetition);
+        assert_eq!(parse_error.span.start, 2);
+        assert_eq!(
+            std::error::Error::source(&error).map(|source| source.to_string()),
+            Some(parse_error.to_string())
+        );
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        assert!(matches!(
+            RegexSet::new(["a", "b{2,1}"]),
+            Err(CompileError::SetPatternParse { index: 1, .. })
+        ));
+        assert!(matches!(Regex::from_glob("[a"), Err(CompileError::Glob(_))));
+    }
+
+    #[test]
+    fn test_matching_branch() {
+        let regex = Regex::new("(GET)|(POST)|([A-Z]+)").unwrap();
+
+        assert_eq!(regex.matching_branch("GET"), Some(0));
+        assert_eq!(regex.matching_branch("POST"), Some(1));
+        assert_eq!(regex.matching_branch("PATCH"), Some(2));
+        assert_eq!(regex.matching_branch("get"), None);
+
+        let regex = Regex::new("[a-z]+").unwrap();
+        assert_eq!(regex.matching_branch("abc"), Some(0));
+        assert_eq!(regex.matching_branch("123"), None);
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::BTreeSet;
+
+use crate::automata::{is_word_character, Automaton, TransitionCondition};
+use crate::runtime::budget::SearchBudget;
+use crate::runtime::error::MatchError;
+
+/// The capture positions of one path through the automaton: for group `n`, slot `2n` holds the
+/// start and slot `2n + 1` the end, as byte offsets into the haystack.
+pub type Slots = Vec<Option<usize>>;
+
+//The functions here run the automaton over the haystack keeping one thread per active state in
+//priority order, where priority is the order of transitions out of each state. The automaton
+//construction arranges that order so that alternations prefer earlier branches and repetitions are
+//greedy. The automaton must not have been simplified, since simplification removes the capture
+//transitions.
+
+/// Returns the capture slots of the highest priority path that matches the whole haystack.
+pub fn full_match(
+    automaton: &Automaton,
+    haystack: &str,
+    capture_count: usize,
+    budget: &mut SearchBudget,
+) -> Result<Option<Slots>, MatchError> {
+    let mut threads = Threads::default();
+    threads.add_start(automaton, capture_count, 0, haystack, budget)?;
+
+    for (index, char) in haystack.char_indices() {
+        threads = threads.step(automaton, char, index + char.len_utf8(), haystack, budget)?;
+
+        if threads.list.is_empty() {
+            return Ok(None);
+        }
+    }
+
+    Ok(threads
+        .list
+        .into_iter()
+        .find(|(state_id, _)| automaton.is_terminal_state(*state_id))
+        .map(|(_, mut slots)| {
+            slots[1] = Some(haystack.len());
+            slots
+        }))
+}
+
+/// Returns the capture slots of the leftmost match that starts at or after `start`. Among the
+/// matches starting there, the highest priority one is chosen (i.e., leftmost-first semantics).
+pub fn search(
+    automaton: &Automaton,
+    haystack: &str,
+    start: usize,
+    capture_count: usize,
+    budget: &mut SearchBudget,
+) -> Result<Option<Slots>, MatchError> {
+    let mut threads = Threads::default();
+    let mut found = None;
+    let mut chars = haystack[start..].char_indices();
+    let mut position = start;
+
+    loop {
+        //a match found at an earlier start is always preferred, so stop starting new attempts
+        if found.is_none() {
+            threads.add_start(automaton, capture_count, position, haystack, budget)?;
+        }
+
+        //threads after the first match are lower priority than it, so they can never win
+        if let Some(index) = threads
+            .list
+            .iter()
+            .position(|(state_id, _)| automaton.is_terminal_state(*state_id))
+        {
+            let (_, mut slots) = threads.list.remove(index);
+            slots[1] = Some(position);
+            found = Some(slots);
+            threads.list.truncate(index);
+        }
+
+        if threads.list.is_empty() && found.is_some() {
+            break;
+        }
+
+        match chars.next() {
+            Some((index, char)) => {
+                position = start + index + char.len_utf8();
+                threads = threads.step(automaton, char, position, haystack, budget)?;
+            }
+            None => break,
+        }
+    }
+
+    Ok(found)
+}
+
+/// Returns the end of the match that ends earliest anywhere in the haystack. The lazy DFA is
+/// faster at this, but it starts matching anew at every position, so it can not tell whether `^`
+/// or `$` hold there.
+pub fn shortest_match(
+    automaton: &Automaton,
+    haystack: &str,
+    budget: &mut SearchBudget,
+) -> Result<Option<usize>, MatchError> {
+    let mut threads = Threads::default();
+    let mut chars = haystack.char_indices();
+    let mut position = 0;
+
+    loop {
+        threads.add_start(automaton, 0, position, haystack, budget)?;
+
+        if threads
+            .list
+            .iter()
+            .any(|(state_id, _)| automaton.is_terminal_state(*state_id))
+        {
+            return Ok(Some(position));
+        }
+
+        match chars.next() {
+            Some((index, char)) => {
+                position = index + char.len_utf8();
+                threads = threads.step(automaton, char, position, haystack, budget)?;
+            }
+            None => return Ok(None),
+        }
+    }
+}
+
+/// Returns the length of the longest start of the haystack that matches. Like
+/// [`shortest_match`], this is for patterns the lazy DFA can not run, here because of `$`.
+pub fn longest_prefix(
+    automaton: &Automaton,
+    haystack: &str,
+    budget: &mut SearchBudget,
+) -> Result<Option<usize>, MatchError> {
+    let mut threads = Threads::default();
+    threads.add_start(automaton, 0, 0, haystack, budget)?;
+    let mut longest = None;
+    let mut chars = haystack.char_indices();
+    let mut position = 0;
+
+    while !threads.list.is_empty() {
+        if threads
+            .list
+            .iter()
+            .any(|(state_id, _)| automaton.is_terminal_state(*state_id))
+        {
+            longest = Some(position);
+        }
+
+        match chars.next() {
+            Some((index, char)) => {
+                position = index + char.len_utf8();
+                threads = threads.step(automaton, char, position, haystack, budget)?;
+            }
+            None => break,
+        }
+    }
+
+    Ok(longest)
+}
+
+/// The active threads for one position in the haystack, in priority order.
+#[derive(Default)]
+struct Threads {
+    list: Vec<(usize, Slots)>,
+    states: BTreeSet<usize>,
+}
+
+impl Threads {
+    fn add_start(
+        &mut self,
+        automaton: &Automaton,
+        capture_count: usize,
+        position: usize,
+        haystack: &str,
+        budget: &mut SearchBudget,
+    ) -> Result<(), MatchError> {
+        let mut slots = vec![None; 2 * (capture_count + 1)];
+        slots[0] = Some(position);
+
+        for start_state in automaton.start_states() {
+            self.add(
+                automaton,
+                *start_state,
+                slots.clone(),
+                position,
+                haystack,
+                budget,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Consumes `char` in every thread, producing the threads for the following position.
+    fn step(
+        self,
+        automaton: &Automaton,
+        char: char,
+        next_position: usize,
+        haystack: &str,
+        budget: &mut SearchBudget,
+    ) -> Result<Threads, MatchError> {
+        budget.step(1)?;
+
+        let mut next = Threads::default();
+
+        for (state_id, slots) in self.list {
+            for transition in &automaton.get_state(state_id).transitions {
+                if transition.condition.matches(char) {
+                    next.add(
+                        automaton,
+                        transition.next_state_id,
+                        slots.clone(),
+                        next_position,
+                        haystack,
+                        budget,
+                    )?;
+                }
+            }
+        }
+
+        Ok(next)
+    }
+
+    /// Adds the thread for `state_id` and every state reachable from it through epsilon
+    /// transitions, in priority order. A state that already has a thread keeps it, since that
+    /// thread got there by a higher priority path. `^` and `$` are followed only at the start and
+    /// end of the haystack, or of one of its lines if they are multi-line.
+    fn add(
+        &mut self,
+        automaton: &Automaton,
+        state_id: usize,
+        slots: Slots,
+        position: usize,
+        haystack: &str,
+        budget: &mut SearchBudget,
+    ) -> Result<(), MatchError> {
+        //an explicit stack rather than recursion, since long chains of epsilon transitions are common
+        let mut stack = vec![(state_id, slots)];
+
+        while let Some((state_id, slots)) = stack.pop() {
+            if !self.states.insert(state_id) {
+                continue;
+            }
+
+            budget.step(1)?;
+
+            let transitions = &automaton.get_state(state_id).transitions;
+
+            //pushed in reverse so that the first transition is explored first
+            for transition in transitions.iter().rev() {
+                let mut slots = slots.clone();
+                match transition.condition {
+                    TransitionCondition::CaptureStart(index) => slots[2 * index] = Some(position),
+                    TransitionCondition::CaptureEnd(index) => slots[2 * index + 1] = Some(position),
+                    TransitionCondition::Epsilon => {}
+                    TransitionCondition::StartOfInput if position == 0 => {}
+                    TransitionCondition::EndOfInput if position == haystack.len() => {}
+                    TransitionCondition::StartOfLine(terminator)
+                        if terminator.is_line_start(haystack, position) => {}
+                    TransitionCondition::EndOfLine(terminator)
+                        if terminator.is_line_end(haystack, position) => {}
+                    TransitionCondition::WordStart if is_word_start(haystack, position) => {}
+                    TransitionCondition::WordEnd if is_word_end(haystack, position) => {}
+                    _ => continue,
+                }
+                stack.push((transition.next_state_id, slots));
+            }
+
+            self.list.push((state_id, slots));
+        }
+
+        Ok(())
+    }
+}
+
+fn is_word_start(haystack: &str, position: usize) -> bool {
+    !is_word_before(haystack, position) && is_word_after(haystack, position)
+}
+
+fn is_word_end(haystack: &str, position: usize) -> bool {
+    is_word_before(haystack, position) && !is_word_after(haystack, position)
+}
+
+fn is_word_before(haystack: &str, position: usize) -> bool {
+    haystack[..position]
+        .chars()
+        .next_back()
+        .is_some_and(is_word_character)
+}
+
+fn is_word_after(haystack: &str, position: usize) -> bool {
+    haystack[position..]
+        .chars()
+        .next()
+        .is_some_and(is_word_character)
+}
@@ -0,0 +1,160 @@
+use std::borrow::Cow;
+
+use crate::runtime::captures::Captures;
+use crate::runtime::error::MatchError;
+use crate::runtime::iter::Searcher;
+use crate::runtime::Regex;
+
+impl<'haystack> Captures<'haystack> {
+    /// Appends `template` to `dst`, replacing references to capture groups with the text they
+    /// matched.
+    ///
+    /// `$1` and `${1}` refer to group 1 (and `$0` to the whole match), `$name` and `${name}` to
+    /// the group with that name, and `$$` is a literal `$`. A reference to a group that does not
+    /// exist or did not participate in the match is replaced with nothing. Without braces, the
+    /// reference is the longest run of letters, digits and underscores, so `$1a` refers to a group
+    /// named `1a` rather than to group 1 followed by `a`.
+    pub fn expand(&self, template: &str, dst: &mut String) {
+        let mut remaining = template;
+
+        while let Some(dollar) = remaining.find('$') {
+            dst.push_str(&remaining[..dollar]);
+            remaining = &remaining[dollar..];
+
+            match parse_reference(remaining) {
+                Some((reference, rest)) => {
+                    if let Some(group) = self.get_reference(reference) {
+                        dst.push_str(group);
+                    }
+                    remaining = rest;
+                }
+                None => {
+                    //either an escaped $$ or a lone $ that starts no reference, both of which stand for themselves
+                    dst.push('$');
+                    remaining = remaining.strip_prefix("$$").unwrap_or(&remaining[1..]);
+                }
+            }
+        }
+
+        dst.push_str(remaining);
+    }
+
+    fn get_reference(&self, reference: &str) -> Option<&'haystack str> {
+        let group = match reference.parse::<usize>() {
+            Ok(index) => self.get(index),
+            Err(_) => self.name(reference),
+        };
+        group.map(|group| group.as_str())
+    }
+}
+
+/// Parses a group reference (without the `$`) from the start of `template`, which must start with
+/// `$`, returning it and the template after it.
+fn parse_reference(template: &str) -> Option<(&str, &str)> {
+    let after_dollar = &template[1..];
+
+    if let Some(braced) = after_dollar.strip_prefix('{') {
+        let end = braced.find('}')?;
+        if end == 0 {
+            return None;
+        }
+        return Some((&braced[..end], &braced[end + 1..]));
+    }
+
+    let end = after_dollar
+        .find(|char: char| !(char.is_ascii_alphanumeric() || char == '_'))
+        .unwrap_or(after_dollar.len());
+
+    if end == 0 {
+        None
+    } else {
+        Some((&after_dollar[..end], &after_dollar[end..]))
+    }
+}
+
+impl Regex {
+    /// Replaces the leftmost match in `haystack` with `replacement`, expanded as per
+    /// [`Captures::expand`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`](super::Limits); use
+    /// [`try_replace`](Self::try_replace) to handle that case instead.
+    pub fn replace<'haystack>(
+        &self,
+        haystack: &'haystack str,
+        replacement: &str,
+    ) -> Cow<'haystack, str> {
+        match self.try_replace(haystack, replacement) {
+            Ok(replaced) => replaced,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_replace<'haystack>(
+        &self,
+        haystack: &'haystack str,
+        replacement: &str,
+    ) -> Result<Cow<'haystack, str>, MatchError> {
+        self.replace_at_most(haystack, replacement, Some(1))
+    }
+
+    /// Replaces every non-overlapping match in `haystack` with `replacement`, expanded as per
+    /// [`Captures::expand`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`](super::Limits); use
+    /// [`try_replace_all`](Self::try_replace_all) to handle that case instead.
+    pub fn replace_all<'haystack>(
+        &self,
+        haystack: &'haystack str,
+        replacement: &str,
+    ) -> Cow<'haystack, str> {
+        match self.try_replace_all(haystack, replacement) {
+            Ok(replaced) => replaced,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_replace_all<'haystack>(
+        &self,
+        haystack: &'haystack str,
+        replacement: &str,
+    ) -> Result<Cow<'haystack, str>, MatchError> {
+        self.replace_at_most(haystack, replacement, None)
+    }
+
+    fn replace_at_most<'haystack>(
+        &self,
+        haystack: &'haystack str,
+        replacement: &str,
+        limit: Option<usize>,
+    ) -> Result<Cow<'haystack, str>, MatchError> {
+        let mut searcher = Searcher::new(self, haystack);
+        let mut replaced = String::new();
+        let mut copied_up_to = 0;
+        let mut replacements = 0;
+
+        while limit.is_none_or(|limit| replacements < limit) {
+            let captures = match searcher.next_captures()? {
+                Some(captures) => captures,
+                None => break,
+            };
+            //group 0 always participates in a match
+            let found = captures.get(0).unwrap();
+
+            replaced.push_str(&haystack[copied_up_to..found.start()]);
+            captures.expand(replacement, &mut replaced);
+            copied_up_to = found.end();
+            replacements += 1;
+        }
+
+        if replacements == 0 {
+            return Ok(Cow::Borrowed(haystack));
+        }
+
+        replaced.push_str(&haystack[copied_up_to..]);
+        Ok(Cow::Owned(replaced))
+    }
+}
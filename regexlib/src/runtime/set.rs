@@ -0,0 +1,280 @@
+use crate::automata::{Automaton, SimplificationPass};
+use crate::parser::RegexEntry;
+use crate::runtime::error::{CompileError, MatchError};
+use crate::runtime::lazy_dfa::{DfaCache, LazyDfa, SharedCache};
+use crate::runtime::Limits;
+
+/// Several regexes compiled into one automaton, which reports which of them match an input in a
+/// single pass over it. As with [`Regex::is_match`](super::Regex::is_match), a regex only matches
+/// if it matches the whole input.
+///
+/// Patterns can be added and removed after the set is built. The automaton of each pattern is
+/// kept, so that doing so only rebuilds the union of them rather than every pattern's automaton.
+#[derive(Clone, Debug)]
+pub struct RegexSet {
+    patterns: Vec<String>,
+    //simplified except for prefix stripping, which the union can not be built from
+    members: Vec<Automaton>,
+    automaton: Automaton,
+    limits: Limits,
+    //the lazy DFA states built by earlier searches over `automaton`
+    cache: SharedCache,
+}
+
+/// Which regexes of a [`RegexSet`] matched an input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetMatches {
+    matched: Vec<bool>,
+}
+
+impl RegexSet {
+    pub fn new<Pattern: AsRef<str>>(
+        patterns: impl IntoIterator<Item = Pattern>,
+    ) -> Result<Self, CompileError> {
+        Self::with_limits(patterns, Limits::default())
+    }
+
+    /// Compiles the set, applying `limits.max_states` to the combined automaton for all patterns.
+    pub fn with_limits<Pattern: AsRef<str>>(
+        patterns: impl IntoIterator<Item = Pattern>,
+        limits: Limits,
+    ) -> Result<Self, CompileError> {
+        let mut set = Self {
+            patterns: Vec::new(),
+            members: Vec::new(),
+            automaton: Automaton::union([]),
+            limits,
+            cache: SharedCache::default(),
+        };
+
+        for pattern in patterns {
+            set.add_member(pattern.as_ref())?;
+        }
+        set.rebuild_union();
+
+        Ok(set)
+    }
+
+    /// Adds `pattern` to the end of the set, returning its index. `limits.max_states` applies to
+    /// the set as a whole, including the new pattern.
+    pub fn push(&mut self, pattern: &str) -> Result<usize, CompileError> {
+        self.add_member(pattern)?;
+        self.rebuild_union();
+
+        Ok(self.patterns.len() - 1)
+    }
+
+    /// Removes the pattern at `index` from the set and returns it. The patterns after it move
+    /// down by one index, as with [`Vec::remove`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> String {
+        self.members.remove(index);
+        let pattern = self.patterns.remove(index);
+        self.rebuild_union();
+
+        pattern
+    }
+
+    fn add_member(&mut self, pattern: &str) -> Result<(), CompileError> {
+        let index = self.patterns.len();
+        let regex = RegexEntry::parse(pattern)
+            .map_err(|error| CompileError::SetPatternParse { index, error })?;
+
+        if let Some(limit) = self.limits.max_states {
+            //the union adds a single start state in front of its members
+            let states = self
+                .members
+                .iter()
+                .map(|member| member.state_count() as u64)
+                .fold(1, u64::saturating_add)
+                .saturating_add(Automaton::constructed_state_count(&regex));
+            if states > limit as u64 {
+                return Err(CompileError::TooManyStates { states, limit });
+            }
+        }
+
+        let mut member = Automaton::from_regex(regex);
+        for pass in SimplificationPass::ALL {
+            if pass != SimplificationPass::StripPrefix {
+                member.run_pass(pass);
+            }
+        }
+
+        self.patterns.push(pattern.to_string());
+        self.members.push(member);
+
+        Ok(())
+    }
+
+    fn rebuild_union(&mut self) {
+        self.automaton = Automaton::union(&self.members);
+        self.automaton.simplify();
+        self.cache = SharedCache::default();
+    }
+
+    /// Whether any regex in the set matches the whole haystack.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`]; use
+    /// [`try_is_match`](Self::try_is_match) to handle that case instead.
+    pub fn is_match(&self, haystack: &str) -> bool {
+        self.matches(haystack).matched_any()
+    }
+
+    pub fn try_is_match(&self, haystack: &str) -> Result<bool, MatchError> {
+        Ok(self.try_matches(haystack)?.matched_any())
+    }
+
+    /// Which regexes in the set match the whole haystack.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search exceeds one of the configured [`Limits`]; use
+    /// [`try_matches`](Self::try_matches) to handle that case instead.
+    pub fn matches(&self, haystack: &str) -> SetMatches {
+        match self.try_matches(haystack) {
+            Ok(matches) => matches,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_matches(&self, haystack: &str) -> Result<SetMatches, MatchError> {
+        Ok(SetMatches {
+            matched: matching_terminals(
+                &self.automaton,
+                &mut self.cache.get(),
+                &self.limits,
+                haystack,
+            )?,
+        })
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+}
+
+impl SetMatches {
+    /// Whether the regex at `index` in the set matched.
+    pub fn matched(&self, index: usize) -> bool {
+        self.matched[index]
+    }
+
+    pub fn matched_any(&self) -> bool {
+        self.matched.contains(&true)
+    }
+
+    /// The indices of the regexes that matched, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.matched
+            .iter()
+            .enumerate()
+            .filter(|(_, matched)| **matched)
+            .map(|(index, _)| index)
+    }
+
+    /// The number of regexes in the set, whether they matched or not.
+    pub fn len(&self) -> usize {
+        self.matched.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.matched.is_empty()
+    }
+}
+
+/// For each terminal state of `automaton` (in the order of `terminal_state_ids`), whether the
+/// whole haystack leads to it.
+pub(crate) fn matching_terminals(
+    automaton: &Automaton,
+    cache: &mut DfaCache,
+    limits: &Limits,
+    haystack: &str,
+) -> Result<Vec<bool>, MatchError> {
+    let terminal_state_ids = automaton.terminal_state_ids();
+    let mut matched = vec![false; terminal_state_ids.len()];
+
+    let haystack = match haystack.strip_prefix(automaton.prefix()) {
+        Some(haystack) => haystack,
+        None => return Ok(matched),
+    };
+
+    let mut dfa = LazyDfa::new(automaton, cache, limits);
+    let mut state = dfa.start_state()?;
+
+    for char in haystack.chars() {
+        state = dfa.next_state(state, char)?;
+
+        if state == LazyDfa::DEAD_STATE {
+            return Ok(matched);
+        }
+    }
+
+    for nfa_state in dfa.nfa_states(state) {
+        if let Some(index) = terminal_state_ids.iter().position(|id| id == nfa_state) {
+            matched[index] = true;
+        }
+    }
+
+    Ok(matched)
+}
+
+#[cfg(test)]
+mod test {
+    use super::RegexSet;
+
+    #[test]
+    fn test_matches() {
+        let set = RegexSet::new([r"\d+", "[a-z]+", "[a-z0-9]+", "x"]).unwrap();
+
+        assert_eq!(set.matches("123").iter().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(set.matches("abc").iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(set.matches("x").iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(!set.is_match("ABC"));
+    }
+
+    #[test]
+    fn test_prefix() {
+        let set = RegexSet::new(["abc+"]).unwrap();
+
+        assert!(set.matches("abccc").matched(0));
+        assert!(!set.is_match("xbc"));
+    }
+
+    #[test]
+    fn test_empty() {
+        let set = RegexSet::new(Vec::<&str>::new()).unwrap();
+
+        assert!(!set.is_match(""));
+        assert!(set.matches("abc").is_empty());
+    }
+
+    #[test]
+    fn test_push_and_remove() {
+        let mut set = RegexSet::new([r"\d+", "[a-z]+"]).unwrap();
+
+        assert_eq!(set.push("abc+").unwrap(), 2);
+        assert_eq!(set.matches("abccc").iter().collect::<Vec<_>>(), vec![1, 2]);
+
+        assert_eq!(set.remove(0), r"\d+");
+        assert!(!set.is_match("123"));
+        assert_eq!(set.matches("abccc").iter().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(set.patterns(), ["[a-z]+", "abc+"]);
+
+        assert!(set.push("(").is_err());
+        assert!(set.push("a{2,1}").is_err());
+        assert_eq!(set.len(), 2);
+    }
+}
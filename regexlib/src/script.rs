@@ -0,0 +1,114 @@
+use std::fmt::{Debug, Formatter};
+
+mod tables;
+
+/// A Unicode script (e.g., `Greek` or `Han`): the writing system a character belongs to, as
+/// matched by `\p{Greek}`. Characters shared by several scripts, like digits and punctuation,
+/// are in `Common`, and combining marks that take the script of the character they follow are in
+/// `Inherited`.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Script {
+    //index into tables::SCRIPTS
+    index: usize,
+}
+
+impl Script {
+    /// The script called `name`, by its full name (e.g., `Old_Italic`) or its four-letter code
+    /// (e.g., `Ital`). Names are compared loosely, ignoring case, spaces, underscores and hyphens,
+    /// so `old italic` works as well.
+    pub fn from_name(name: &str) -> Option<Script> {
+        let loose_name = name
+            .chars()
+            .filter(|char| !matches!(char, ' ' | '_' | '-'))
+            .flat_map(char::to_lowercase)
+            .collect::<String>();
+        let (_, full_name) = tables::ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == loose_name)?;
+        let index = tables::SCRIPTS
+            .iter()
+            .position(|(name, _)| name == full_name)?;
+        Some(Script { index })
+    }
+
+    /// Every script, in alphabetical order.
+    pub fn all() -> impl Iterator<Item = Script> {
+        (0..tables::SCRIPTS.len()).map(|index| Script { index })
+    }
+
+    /// The full name of the script, like `Old_Italic`.
+    pub fn name(&self) -> &'static str {
+        tables::SCRIPTS[self.index].0
+    }
+
+    /// The sorted, non-overlapping inclusive codepoint ranges of the script's characters.
+    pub fn ranges(&self) -> &'static [(char, char)] {
+        tables::SCRIPTS[self.index].1
+    }
+
+    pub fn contains(&self, char: char) -> bool {
+        self.ranges()
+            .binary_search_by(|(start, end)| {
+                if *end < char {
+                    std::cmp::Ordering::Less
+                } else if *start > char {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+}
+
+impl Debug for Script {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Script").field(&self.name()).finish()
+    }
+}
+
+//scripts are stored by their full names, which is also how patterns are written back out
+#[cfg(feature = "serde")]
+impl serde::Serialize for Script {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Script {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Script::from_name(&name)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown script \"{name}\"")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::script::Script;
+
+    #[test]
+    fn test_from_name() {
+        let greek = Script::from_name("Greek").unwrap();
+        assert_eq!(greek.name(), "Greek");
+        assert_eq!(Script::from_name("Grek"), Some(greek));
+        assert_eq!(
+            Script::from_name("old italic").map(|script| script.name()),
+            Some("Old_Italic")
+        );
+        assert_eq!(Script::from_name("Klingon"), None);
+    }
+
+    #[test]
+    fn test_contains() {
+        let greek = Script::from_name("Greek").unwrap();
+        assert!(greek.contains('\u{3B1}'));
+        assert!(greek.contains('\u{1F00}'));
+        assert!(!greek.contains('a'));
+
+        let common = Script::from_name("Common").unwrap();
+        assert!(common.contains('1'));
+        assert!(!common.contains('\u{3B1}'));
+    }
+}
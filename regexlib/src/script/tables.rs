@@ -0,0 +1,1799 @@
+//Generated from the Scripts.txt and PropertyValueAliases.txt of the Unicode Character Database,
+//version 16.0.0, which are covered by the Unicode license (see LICENSE-UNICODE). Do not edit by hand.
+
+pub(super) static SCRIPTS: &[(&str, &[(char, char)])] = &[
+    (
+        "Adlam",
+        &[
+            ('\u{1E900}', '\u{1E94B}'),
+            ('\u{1E950}', '\u{1E959}'),
+            ('\u{1E95E}', '\u{1E95F}'),
+        ],
+    ),
+    (
+        "Ahom",
+        &[
+            ('\u{11700}', '\u{1171A}'),
+            ('\u{1171D}', '\u{1172B}'),
+            ('\u{11730}', '\u{11746}'),
+        ],
+    ),
+    ("Anatolian_Hieroglyphs", &[('\u{14400}', '\u{14646}')]),
+    (
+        "Arabic",
+        &[
+            ('\u{600}', '\u{604}'),
+            ('\u{606}', '\u{60B}'),
+            ('\u{60D}', '\u{61A}'),
+            ('\u{61C}', '\u{61E}'),
+            ('\u{620}', '\u{63F}'),
+            ('\u{641}', '\u{64A}'),
+            ('\u{656}', '\u{66F}'),
+            ('\u{671}', '\u{6DC}'),
+            ('\u{6DE}', '\u{6FF}'),
+            ('\u{750}', '\u{77F}'),
+            ('\u{870}', '\u{88E}'),
+            ('\u{890}', '\u{891}'),
+            ('\u{897}', '\u{8E1}'),
+            ('\u{8E3}', '\u{8FF}'),
+            ('\u{FB50}', '\u{FBC2}'),
+            ('\u{FBD3}', '\u{FD3D}'),
+            ('\u{FD40}', '\u{FD8F}'),
+            ('\u{FD92}', '\u{FDC7}'),
+            ('\u{FDCF}', '\u{FDCF}'),
+            ('\u{FDF0}', '\u{FDFF}'),
+            ('\u{FE70}', '\u{FE74}'),
+            ('\u{FE76}', '\u{FEFC}'),
+            ('\u{10E60}', '\u{10E7E}'),
+            ('\u{10EC2}', '\u{10EC4}'),
+            ('\u{10EFC}', '\u{10EFF}'),
+            ('\u{1EE00}', '\u{1EE03}'),
+            ('\u{1EE05}', '\u{1EE1F}'),
+            ('\u{1EE21}', '\u{1EE22}'),
+            ('\u{1EE24}', '\u{1EE24}'),
+            ('\u{1EE27}', '\u{1EE27}'),
+            ('\u{1EE29}', '\u{1EE32}'),
+            ('\u{1EE34}', '\u{1EE37}'),
+            ('\u{1EE39}', '\u{1EE39}'),
+            ('\u{1EE3B}', '\u{1EE3B}'),
+            ('\u{1EE42}', '\u{1EE42}'),
+            ('\u{1EE47}', '\u{1EE47}'),
+            ('\u{1EE49}', '\u{1EE49}'),
+            ('\u{1EE4B}', '\u{1EE4B}'),
+            ('\u{1EE4D}', '\u{1EE4F}'),
+            ('\u{1EE51}', '\u{1EE52}'),
+            ('\u{1EE54}', '\u{1EE54}'),
+            ('\u{1EE57}', '\u{1EE57}'),
+            ('\u{1EE59}', '\u{1EE59}'),
+            ('\u{1EE5B}', '\u{1EE5B}'),
+            ('\u{1EE5D}', '\u{1EE5D}'),
+            ('\u{1EE5F}', '\u{1EE5F}'),
+            ('\u{1EE61}', '\u{1EE62}'),
+            ('\u{1EE64}', '\u{1EE64}'),
+            ('\u{1EE67}', '\u{1EE6A}'),
+            ('\u{1EE6C}', '\u{1EE72}'),
+            ('\u{1EE74}', '\u{1EE77}'),
+            ('\u{1EE79}', '\u{1EE7C}'),
+            ('\u{1EE7E}', '\u{1EE7E}'),
+            ('\u{1EE80}', '\u{1EE89}'),
+            ('\u{1EE8B}', '\u{1EE9B}'),
+            ('\u{1EEA1}', '\u{1EEA3}'),
+            ('\u{1EEA5}', '\u{1EEA9}'),
+            ('\u{1EEAB}', '\u{1EEBB}'),
+            ('\u{1EEF0}', '\u{1EEF1}'),
+        ],
+    ),
+    (
+        "Armenian",
+        &[
+            ('\u{531}', '\u{556}'),
+            ('\u{559}', '\u{58A}'),
+            ('\u{58D}', '\u{58F}'),
+            ('\u{FB13}', '\u{FB17}'),
+        ],
+    ),
+    (
+        "Avestan",
+        &[('\u{10B00}', '\u{10B35}'), ('\u{10B39}', '\u{10B3F}')],
+    ),
+    (
+        "Balinese",
+        &[('\u{1B00}', '\u{1B4C}'), ('\u{1B4E}', '\u{1B7F}')],
+    ),
+    (
+        "Bamum",
+        &[('\u{A6A0}', '\u{A6F7}'), ('\u{16800}', '\u{16A38}')],
+    ),
+    (
+        "Bassa_Vah",
+        &[('\u{16AD0}', '\u{16AED}'), ('\u{16AF0}', '\u{16AF5}')],
+    ),
+    (
+        "Batak",
+        &[('\u{1BC0}', '\u{1BF3}'), ('\u{1BFC}', '\u{1BFF}')],
+    ),
+    (
+        "Bengali",
+        &[
+            ('\u{980}', '\u{983}'),
+            ('\u{985}', '\u{98C}'),
+            ('\u{98F}', '\u{990}'),
+            ('\u{993}', '\u{9A8}'),
+            ('\u{9AA}', '\u{9B0}'),
+            ('\u{9B2}', '\u{9B2}'),
+            ('\u{9B6}', '\u{9B9}'),
+            ('\u{9BC}', '\u{9C4}'),
+            ('\u{9C7}', '\u{9C8}'),
+            ('\u{9CB}', '\u{9CE}'),
+            ('\u{9D7}', '\u{9D7}'),
+            ('\u{9DC}', '\u{9DD}'),
+            ('\u{9DF}', '\u{9E3}'),
+            ('\u{9E6}', '\u{9FE}'),
+        ],
+    ),
+    (
+        "Bhaiksuki",
+        &[
+            ('\u{11C00}', '\u{11C08}'),
+            ('\u{11C0A}', '\u{11C36}'),
+            ('\u{11C38}', '\u{11C45}'),
+            ('\u{11C50}', '\u{11C6C}'),
+        ],
+    ),
+    (
+        "Bopomofo",
+        &[
+            ('\u{2EA}', '\u{2EB}'),
+            ('\u{3105}', '\u{312F}'),
+            ('\u{31A0}', '\u{31BF}'),
+        ],
+    ),
+    (
+        "Brahmi",
+        &[
+            ('\u{11000}', '\u{1104D}'),
+            ('\u{11052}', '\u{11075}'),
+            ('\u{1107F}', '\u{1107F}'),
+        ],
+    ),
+    ("Braille", &[('\u{2800}', '\u{28FF}')]),
+    (
+        "Buginese",
+        &[('\u{1A00}', '\u{1A1B}'), ('\u{1A1E}', '\u{1A1F}')],
+    ),
+    ("Buhid", &[('\u{1740}', '\u{1753}')]),
+    (
+        "Canadian_Aboriginal",
+        &[
+            ('\u{1400}', '\u{167F}'),
+            ('\u{18B0}', '\u{18F5}'),
+            ('\u{11AB0}', '\u{11ABF}'),
+        ],
+    ),
+    ("Carian", &[('\u{102A0}', '\u{102D0}')]),
+    (
+        "Caucasian_Albanian",
+        &[('\u{10530}', '\u{10563}'), ('\u{1056F}', '\u{1056F}')],
+    ),
+    (
+        "Chakma",
+        &[('\u{11100}', '\u{11134}'), ('\u{11136}', '\u{11147}')],
+    ),
+    (
+        "Cham",
+        &[
+            ('\u{AA00}', '\u{AA36}'),
+            ('\u{AA40}', '\u{AA4D}'),
+            ('\u{AA50}', '\u{AA59}'),
+            ('\u{AA5C}', '\u{AA5F}'),
+        ],
+    ),
+    (
+        "Cherokee",
+        &[
+            ('\u{13A0}', '\u{13F5}'),
+            ('\u{13F8}', '\u{13FD}'),
+            ('\u{AB70}', '\u{ABBF}'),
+        ],
+    ),
+    ("Chorasmian", &[('\u{10FB0}', '\u{10FCB}')]),
+    (
+        "Common",
+        &[
+            ('\u{0}', '\u{40}'),
+            ('\u{5B}', '\u{60}'),
+            ('\u{7B}', '\u{A9}'),
+            ('\u{AB}', '\u{B9}'),
+            ('\u{BB}', '\u{BF}'),
+            ('\u{D7}', '\u{D7}'),
+            ('\u{F7}', '\u{F7}'),
+            ('\u{2B9}', '\u{2DF}'),
+            ('\u{2E5}', '\u{2E9}'),
+            ('\u{2EC}', '\u{2FF}'),
+            ('\u{374}', '\u{374}'),
+            ('\u{37E}', '\u{37E}'),
+            ('\u{385}', '\u{385}'),
+            ('\u{387}', '\u{387}'),
+            ('\u{605}', '\u{605}'),
+            ('\u{60C}', '\u{60C}'),
+            ('\u{61B}', '\u{61B}'),
+            ('\u{61F}', '\u{61F}'),
+            ('\u{640}', '\u{640}'),
+            ('\u{6DD}', '\u{6DD}'),
+            ('\u{8E2}', '\u{8E2}'),
+            ('\u{964}', '\u{965}'),
+            ('\u{E3F}', '\u{E3F}'),
+            ('\u{FD5}', '\u{FD8}'),
+            ('\u{10FB}', '\u{10FB}'),
+            ('\u{16EB}', '\u{16ED}'),
+            ('\u{1735}', '\u{1736}'),
+            ('\u{1802}', '\u{1803}'),
+            ('\u{1805}', '\u{1805}'),
+            ('\u{1CD3}', '\u{1CD3}'),
+            ('\u{1CE1}', '\u{1CE1}'),
+            ('\u{1CE9}', '\u{1CEC}'),
+            ('\u{1CEE}', '\u{1CF3}'),
+            ('\u{1CF5}', '\u{1CF7}'),
+            ('\u{1CFA}', '\u{1CFA}'),
+            ('\u{2000}', '\u{200B}'),
+            ('\u{200E}', '\u{2064}'),
+            ('\u{2066}', '\u{2070}'),
+            ('\u{2074}', '\u{207E}'),
+            ('\u{2080}', '\u{208E}'),
+            ('\u{20A0}', '\u{20C0}'),
+            ('\u{2100}', '\u{2125}'),
+            ('\u{2127}', '\u{2129}'),
+            ('\u{212C}', '\u{2131}'),
+            ('\u{2133}', '\u{214D}'),
+            ('\u{214F}', '\u{215F}'),
+            ('\u{2189}', '\u{218B}'),
+            ('\u{2190}', '\u{2429}'),
+            ('\u{2440}', '\u{244A}'),
+            ('\u{2460}', '\u{27FF}'),
+            ('\u{2900}', '\u{2B73}'),
+            ('\u{2B76}', '\u{2B95}'),
+            ('\u{2B97}', '\u{2BFF}'),
+            ('\u{2E00}', '\u{2E5D}'),
+            ('\u{2FF0}', '\u{3004}'),
+            ('\u{3006}', '\u{3006}'),
+            ('\u{3008}', '\u{3020}'),
+            ('\u{3030}', '\u{3037}'),
+            ('\u{303C}', '\u{303F}'),
+            ('\u{309B}', '\u{309C}'),
+            ('\u{30A0}', '\u{30A0}'),
+            ('\u{30FB}', '\u{30FC}'),
+            ('\u{3190}', '\u{319F}'),
+            ('\u{31C0}', '\u{31E5}'),
+            ('\u{31EF}', '\u{31EF}'),
+            ('\u{3220}', '\u{325F}'),
+            ('\u{327F}', '\u{32CF}'),
+            ('\u{32FF}', '\u{32FF}'),
+            ('\u{3358}', '\u{33FF}'),
+            ('\u{4DC0}', '\u{4DFF}'),
+            ('\u{A700}', '\u{A721}'),
+            ('\u{A788}', '\u{A78A}'),
+            ('\u{A830}', '\u{A839}'),
+            ('\u{A92E}', '\u{A92E}'),
+            ('\u{A9CF}', '\u{A9CF}'),
+            ('\u{AB5B}', '\u{AB5B}'),
+            ('\u{AB6A}', '\u{AB6B}'),
+            ('\u{FD3E}', '\u{FD3F}'),
+            ('\u{FE10}', '\u{FE19}'),
+            ('\u{FE30}', '\u{FE52}'),
+            ('\u{FE54}', '\u{FE66}'),
+            ('\u{FE68}', '\u{FE6B}'),
+            ('\u{FEFF}', '\u{FEFF}'),
+            ('\u{FF01}', '\u{FF20}'),
+            ('\u{FF3B}', '\u{FF40}'),
+            ('\u{FF5B}', '\u{FF65}'),
+            ('\u{FF70}', '\u{FF70}'),
+            ('\u{FF9E}', '\u{FF9F}'),
+            ('\u{FFE0}', '\u{FFE6}'),
+            ('\u{FFE8}', '\u{FFEE}'),
+            ('\u{FFF9}', '\u{FFFD}'),
+            ('\u{10100}', '\u{10102}'),
+            ('\u{10107}', '\u{10133}'),
+            ('\u{10137}', '\u{1013F}'),
+            ('\u{10190}', '\u{1019C}'),
+            ('\u{101D0}', '\u{101FC}'),
+            ('\u{102E1}', '\u{102FB}'),
+            ('\u{1BCA0}', '\u{1BCA3}'),
+            ('\u{1CC00}', '\u{1CCF9}'),
+            ('\u{1CD00}', '\u{1CEB3}'),
+            ('\u{1CF50}', '\u{1CFC3}'),
+            ('\u{1D000}', '\u{1D0F5}'),
+            ('\u{1D100}', '\u{1D126}'),
+            ('\u{1D129}', '\u{1D166}'),
+            ('\u{1D16A}', '\u{1D17A}'),
+            ('\u{1D183}', '\u{1D184}'),
+            ('\u{1D18C}', '\u{1D1A9}'),
+            ('\u{1D1AE}', '\u{1D1EA}'),
+            ('\u{1D2C0}', '\u{1D2D3}'),
+            ('\u{1D2E0}', '\u{1D2F3}'),
+            ('\u{1D300}', '\u{1D356}'),
+            ('\u{1D360}', '\u{1D378}'),
+            ('\u{1D400}', '\u{1D454}'),
+            ('\u{1D456}', '\u{1D49C}'),
+            ('\u{1D49E}', '\u{1D49F}'),
+            ('\u{1D4A2}', '\u{1D4A2}'),
+            ('\u{1D4A5}', '\u{1D4A6}'),
+            ('\u{1D4A9}', '\u{1D4AC}'),
+            ('\u{1D4AE}', '\u{1D4B9}'),
+            ('\u{1D4BB}', '\u{1D4BB}'),
+            ('\u{1D4BD}', '\u{1D4C3}'),
+            ('\u{1D4C5}', '\u{1D505}'),
+            ('\u{1D507}', '\u{1D50A}'),
+            ('\u{1D50D}', '\u{1D514}'),
+            ('\u{1D516}', '\u{1D51C}'),
+            ('\u{1D51E}', '\u{1D539}'),
+            ('\u{1D53B}', '\u{1D53E}'),
+            ('\u{1D540}', '\u{1D544}'),
+            ('\u{1D546}', '\u{1D546}'),
+            ('\u{1D54A}', '\u{1D550}'),
+            ('\u{1D552}', '\u{1D6A5}'),
+            ('\u{1D6A8}', '\u{1D7CB}'),
+            ('\u{1D7CE}', '\u{1D7FF}'),
+            ('\u{1EC71}', '\u{1ECB4}'),
+            ('\u{1ED01}', '\u{1ED3D}'),
+            ('\u{1F000}', '\u{1F02B}'),
+            ('\u{1F030}', '\u{1F093}'),
+            ('\u{1F0A0}', '\u{1F0AE}'),
+            ('\u{1F0B1}', '\u{1F0BF}'),
+            ('\u{1F0C1}', '\u{1F0CF}'),
+            ('\u{1F0D1}', '\u{1F0F5}'),
+            ('\u{1F100}', '\u{1F1AD}'),
+            ('\u{1F1E6}', '\u{1F1FF}'),
+            ('\u{1F201}', '\u{1F202}'),
+            ('\u{1F210}', '\u{1F23B}'),
+            ('\u{1F240}', '\u{1F248}'),
+            ('\u{1F250}', '\u{1F251}'),
+            ('\u{1F260}', '\u{1F265}'),
+            ('\u{1F300}', '\u{1F6D7}'),
+            ('\u{1F6DC}', '\u{1F6EC}'),
+            ('\u{1F6F0}', '\u{1F6FC}'),
+            ('\u{1F700}', '\u{1F776}'),
+            ('\u{1F77B}', '\u{1F7D9}'),
+            ('\u{1F7E0}', '\u{1F7EB}'),
+            ('\u{1F7F0}', '\u{1F7F0}'),
+            ('\u{1F800}', '\u{1F80B}'),
+            ('\u{1F810}', '\u{1F847}'),
+            ('\u{1F850}', '\u{1F859}'),
+            ('\u{1F860}', '\u{1F887}'),
+            ('\u{1F890}', '\u{1F8AD}'),
+            ('\u{1F8B0}', '\u{1F8BB}'),
+            ('\u{1F8C0}', '\u{1F8C1}'),
+            ('\u{1F900}', '\u{1FA53}'),
+            ('\u{1FA60}', '\u{1FA6D}'),
+            ('\u{1FA70}', '\u{1FA7C}'),
+            ('\u{1FA80}', '\u{1FA89}'),
+            ('\u{1FA8F}', '\u{1FAC6}'),
+            ('\u{1FACE}', '\u{1FADC}'),
+            ('\u{1FADF}', '\u{1FAE9}'),
+            ('\u{1FAF0}', '\u{1FAF8}'),
+            ('\u{1FB00}', '\u{1FB92}'),
+            ('\u{1FB94}', '\u{1FBF9}'),
+            ('\u{E0001}', '\u{E0001}'),
+            ('\u{E0020}', '\u{E007F}'),
+        ],
+    ),
+    (
+        "Coptic",
+        &[
+            ('\u{3E2}', '\u{3EF}'),
+            ('\u{2C80}', '\u{2CF3}'),
+            ('\u{2CF9}', '\u{2CFF}'),
+        ],
+    ),
+    (
+        "Cuneiform",
+        &[
+            ('\u{12000}', '\u{12399}'),
+            ('\u{12400}', '\u{1246E}'),
+            ('\u{12470}', '\u{12474}'),
+            ('\u{12480}', '\u{12543}'),
+        ],
+    ),
+    (
+        "Cypriot",
+        &[
+            ('\u{10800}', '\u{10805}'),
+            ('\u{10808}', '\u{10808}'),
+            ('\u{1080A}', '\u{10835}'),
+            ('\u{10837}', '\u{10838}'),
+            ('\u{1083C}', '\u{1083C}'),
+            ('\u{1083F}', '\u{1083F}'),
+        ],
+    ),
+    ("Cypro_Minoan", &[('\u{12F90}', '\u{12FF2}')]),
+    (
+        "Cyrillic",
+        &[
+            ('\u{400}', '\u{484}'),
+            ('\u{487}', '\u{52F}'),
+            ('\u{1C80}', '\u{1C8A}'),
+            ('\u{1D2B}', '\u{1D2B}'),
+            ('\u{1D78}', '\u{1D78}'),
+            ('\u{2DE0}', '\u{2DFF}'),
+            ('\u{A640}', '\u{A69F}'),
+            ('\u{FE2E}', '\u{FE2F}'),
+            ('\u{1E030}', '\u{1E06D}'),
+            ('\u{1E08F}', '\u{1E08F}'),
+        ],
+    ),
+    ("Deseret", &[('\u{10400}', '\u{1044F}')]),
+    (
+        "Devanagari",
+        &[
+            ('\u{900}', '\u{950}'),
+            ('\u{955}', '\u{963}'),
+            ('\u{966}', '\u{97F}'),
+            ('\u{A8E0}', '\u{A8FF}'),
+            ('\u{11B00}', '\u{11B09}'),
+        ],
+    ),
+    (
+        "Dives_Akuru",
+        &[
+            ('\u{11900}', '\u{11906}'),
+            ('\u{11909}', '\u{11909}'),
+            ('\u{1190C}', '\u{11913}'),
+            ('\u{11915}', '\u{11916}'),
+            ('\u{11918}', '\u{11935}'),
+            ('\u{11937}', '\u{11938}'),
+            ('\u{1193B}', '\u{11946}'),
+            ('\u{11950}', '\u{11959}'),
+        ],
+    ),
+    ("Dogra", &[('\u{11800}', '\u{1183B}')]),
+    (
+        "Duployan",
+        &[
+            ('\u{1BC00}', '\u{1BC6A}'),
+            ('\u{1BC70}', '\u{1BC7C}'),
+            ('\u{1BC80}', '\u{1BC88}'),
+            ('\u{1BC90}', '\u{1BC99}'),
+            ('\u{1BC9C}', '\u{1BC9F}'),
+        ],
+    ),
+    (
+        "Egyptian_Hieroglyphs",
+        &[('\u{13000}', '\u{13455}'), ('\u{13460}', '\u{143FA}')],
+    ),
+    ("Elbasan", &[('\u{10500}', '\u{10527}')]),
+    ("Elymaic", &[('\u{10FE0}', '\u{10FF6}')]),
+    (
+        "Ethiopic",
+        &[
+            ('\u{1200}', '\u{1248}'),
+            ('\u{124A}', '\u{124D}'),
+            ('\u{1250}', '\u{1256}'),
+            ('\u{1258}', '\u{1258}'),
+            ('\u{125A}', '\u{125D}'),
+            ('\u{1260}', '\u{1288}'),
+            ('\u{128A}', '\u{128D}'),
+            ('\u{1290}', '\u{12B0}'),
+            ('\u{12B2}', '\u{12B5}'),
+            ('\u{12B8}', '\u{12BE}'),
+            ('\u{12C0}', '\u{12C0}'),
+            ('\u{12C2}', '\u{12C5}'),
+            ('\u{12C8}', '\u{12D6}'),
+            ('\u{12D8}', '\u{1310}'),
+            ('\u{1312}', '\u{1315}'),
+            ('\u{1318}', '\u{135A}'),
+            ('\u{135D}', '\u{137C}'),
+            ('\u{1380}', '\u{1399}'),
+            ('\u{2D80}', '\u{2D96}'),
+            ('\u{2DA0}', '\u{2DA6}'),
+            ('\u{2DA8}', '\u{2DAE}'),
+            ('\u{2DB0}', '\u{2DB6}'),
+            ('\u{2DB8}', '\u{2DBE}'),
+            ('\u{2DC0}', '\u{2DC6}'),
+            ('\u{2DC8}', '\u{2DCE}'),
+            ('\u{2DD0}', '\u{2DD6}'),
+            ('\u{2DD8}', '\u{2DDE}'),
+            ('\u{AB01}', '\u{AB06}'),
+            ('\u{AB09}', '\u{AB0E}'),
+            ('\u{AB11}', '\u{AB16}'),
+            ('\u{AB20}', '\u{AB26}'),
+            ('\u{AB28}', '\u{AB2E}'),
+            ('\u{1E7E0}', '\u{1E7E6}'),
+            ('\u{1E7E8}', '\u{1E7EB}'),
+            ('\u{1E7ED}', '\u{1E7EE}'),
+            ('\u{1E7F0}', '\u{1E7FE}'),
+        ],
+    ),
+    (
+        "Garay",
+        &[
+            ('\u{10D40}', '\u{10D65}'),
+            ('\u{10D69}', '\u{10D85}'),
+            ('\u{10D8E}', '\u{10D8F}'),
+        ],
+    ),
+    (
+        "Georgian",
+        &[
+            ('\u{10A0}', '\u{10C5}'),
+            ('\u{10C7}', '\u{10C7}'),
+            ('\u{10CD}', '\u{10CD}'),
+            ('\u{10D0}', '\u{10FA}'),
+            ('\u{10FC}', '\u{10FF}'),
+            ('\u{1C90}', '\u{1CBA}'),
+            ('\u{1CBD}', '\u{1CBF}'),
+            ('\u{2D00}', '\u{2D25}'),
+            ('\u{2D27}', '\u{2D27}'),
+            ('\u{2D2D}', '\u{2D2D}'),
+        ],
+    ),
+    (
+        "Glagolitic",
+        &[
+            ('\u{2C00}', '\u{2C5F}'),
+            ('\u{1E000}', '\u{1E006}'),
+            ('\u{1E008}', '\u{1E018}'),
+            ('\u{1E01B}', '\u{1E021}'),
+            ('\u{1E023}', '\u{1E024}'),
+            ('\u{1E026}', '\u{1E02A}'),
+        ],
+    ),
+    ("Gothic", &[('\u{10330}', '\u{1034A}')]),
+    (
+        "Grantha",
+        &[
+            ('\u{11300}', '\u{11303}'),
+            ('\u{11305}', '\u{1130C}'),
+            ('\u{1130F}', '\u{11310}'),
+            ('\u{11313}', '\u{11328}'),
+            ('\u{1132A}', '\u{11330}'),
+            ('\u{11332}', '\u{11333}'),
+            ('\u{11335}', '\u{11339}'),
+            ('\u{1133C}', '\u{11344}'),
+            ('\u{11347}', '\u{11348}'),
+            ('\u{1134B}', '\u{1134D}'),
+            ('\u{11350}', '\u{11350}'),
+            ('\u{11357}', '\u{11357}'),
+            ('\u{1135D}', '\u{11363}'),
+            ('\u{11366}', '\u{1136C}'),
+            ('\u{11370}', '\u{11374}'),
+        ],
+    ),
+    (
+        "Greek",
+        &[
+            ('\u{370}', '\u{373}'),
+            ('\u{375}', '\u{377}'),
+            ('\u{37A}', '\u{37D}'),
+            ('\u{37F}', '\u{37F}'),
+            ('\u{384}', '\u{384}'),
+            ('\u{386}', '\u{386}'),
+            ('\u{388}', '\u{38A}'),
+            ('\u{38C}', '\u{38C}'),
+            ('\u{38E}', '\u{3A1}'),
+            ('\u{3A3}', '\u{3E1}'),
+            ('\u{3F0}', '\u{3FF}'),
+            ('\u{1D26}', '\u{1D2A}'),
+            ('\u{1D5D}', '\u{1D61}'),
+            ('\u{1D66}', '\u{1D6A}'),
+            ('\u{1DBF}', '\u{1DBF}'),
+            ('\u{1F00}', '\u{1F15}'),
+            ('\u{1F18}', '\u{1F1D}'),
+            ('\u{1F20}', '\u{1F45}'),
+            ('\u{1F48}', '\u{1F4D}'),
+            ('\u{1F50}', '\u{1F57}'),
+            ('\u{1F59}', '\u{1F59}'),
+            ('\u{1F5B}', '\u{1F5B}'),
+            ('\u{1F5D}', '\u{1F5D}'),
+            ('\u{1F5F}', '\u{1F7D}'),
+            ('\u{1F80}', '\u{1FB4}'),
+            ('\u{1FB6}', '\u{1FC4}'),
+            ('\u{1FC6}', '\u{1FD3}'),
+            ('\u{1FD6}', '\u{1FDB}'),
+            ('\u{1FDD}', '\u{1FEF}'),
+            ('\u{1FF2}', '\u{1FF4}'),
+            ('\u{1FF6}', '\u{1FFE}'),
+            ('\u{2126}', '\u{2126}'),
+            ('\u{AB65}', '\u{AB65}'),
+            ('\u{10140}', '\u{1018E}'),
+            ('\u{101A0}', '\u{101A0}'),
+            ('\u{1D200}', '\u{1D245}'),
+        ],
+    ),
+    (
+        "Gujarati",
+        &[
+            ('\u{A81}', '\u{A83}'),
+            ('\u{A85}', '\u{A8D}'),
+            ('\u{A8F}', '\u{A91}'),
+            ('\u{A93}', '\u{AA8}'),
+            ('\u{AAA}', '\u{AB0}'),
+            ('\u{AB2}', '\u{AB3}'),
+            ('\u{AB5}', '\u{AB9}'),
+            ('\u{ABC}', '\u{AC5}'),
+            ('\u{AC7}', '\u{AC9}'),
+            ('\u{ACB}', '\u{ACD}'),
+            ('\u{AD0}', '\u{AD0}'),
+            ('\u{AE0}', '\u{AE3}'),
+            ('\u{AE6}', '\u{AF1}'),
+            ('\u{AF9}', '\u{AFF}'),
+        ],
+    ),
+    (
+        "Gunjala_Gondi",
+        &[
+            ('\u{11D60}', '\u{11D65}'),
+            ('\u{11D67}', '\u{11D68}'),
+            ('\u{11D6A}', '\u{11D8E}'),
+            ('\u{11D90}', '\u{11D91}'),
+            ('\u{11D93}', '\u{11D98}'),
+            ('\u{11DA0}', '\u{11DA9}'),
+        ],
+    ),
+    (
+        "Gurmukhi",
+        &[
+            ('\u{A01}', '\u{A03}'),
+            ('\u{A05}', '\u{A0A}'),
+            ('\u{A0F}', '\u{A10}'),
+            ('\u{A13}', '\u{A28}'),
+            ('\u{A2A}', '\u{A30}'),
+            ('\u{A32}', '\u{A33}'),
+            ('\u{A35}', '\u{A36}'),
+            ('\u{A38}', '\u{A39}'),
+            ('\u{A3C}', '\u{A3C}'),
+            ('\u{A3E}', '\u{A42}'),
+            ('\u{A47}', '\u{A48}'),
+            ('\u{A4B}', '\u{A4D}'),
+            ('\u{A51}', '\u{A51}'),
+            ('\u{A59}', '\u{A5C}'),
+            ('\u{A5E}', '\u{A5E}'),
+            ('\u{A66}', '\u{A76}'),
+        ],
+    ),
+    ("Gurung_Khema", &[('\u{16100}', '\u{16139}')]),
+    (
+        "Han",
+        &[
+            ('\u{2E80}', '\u{2E99}'),
+            ('\u{2E9B}', '\u{2EF3}'),
+            ('\u{2F00}', '\u{2FD5}'),
+            ('\u{3005}', '\u{3005}'),
+            ('\u{3007}', '\u{3007}'),
+            ('\u{3021}', '\u{3029}'),
+            ('\u{3038}', '\u{303B}'),
+            ('\u{3400}', '\u{4DBF}'),
+            ('\u{4E00}', '\u{9FFF}'),
+            ('\u{F900}', '\u{FA6D}'),
+            ('\u{FA70}', '\u{FAD9}'),
+            ('\u{16FE2}', '\u{16FE3}'),
+            ('\u{16FF0}', '\u{16FF1}'),
+            ('\u{20000}', '\u{2A6DF}'),
+            ('\u{2A700}', '\u{2B739}'),
+            ('\u{2B740}', '\u{2B81D}'),
+            ('\u{2B820}', '\u{2CEA1}'),
+            ('\u{2CEB0}', '\u{2EBE0}'),
+            ('\u{2EBF0}', '\u{2EE5D}'),
+            ('\u{2F800}', '\u{2FA1D}'),
+            ('\u{30000}', '\u{3134A}'),
+            ('\u{31350}', '\u{323AF}'),
+        ],
+    ),
+    (
+        "Hangul",
+        &[
+            ('\u{1100}', '\u{11FF}'),
+            ('\u{302E}', '\u{302F}'),
+            ('\u{3131}', '\u{318E}'),
+            ('\u{3200}', '\u{321E}'),
+            ('\u{3260}', '\u{327E}'),
+            ('\u{A960}', '\u{A97C}'),
+            ('\u{AC00}', '\u{D7A3}'),
+            ('\u{D7B0}', '\u{D7C6}'),
+            ('\u{D7CB}', '\u{D7FB}'),
+            ('\u{FFA0}', '\u{FFBE}'),
+            ('\u{FFC2}', '\u{FFC7}'),
+            ('\u{FFCA}', '\u{FFCF}'),
+            ('\u{FFD2}', '\u{FFD7}'),
+            ('\u{FFDA}', '\u{FFDC}'),
+        ],
+    ),
+    (
+        "Hanifi_Rohingya",
+        &[('\u{10D00}', '\u{10D27}'), ('\u{10D30}', '\u{10D39}')],
+    ),
+    ("Hanunoo", &[('\u{1720}', '\u{1734}')]),
+    (
+        "Hatran",
+        &[
+            ('\u{108E0}', '\u{108F2}'),
+            ('\u{108F4}', '\u{108F5}'),
+            ('\u{108FB}', '\u{108FF}'),
+        ],
+    ),
+    (
+        "Hebrew",
+        &[
+            ('\u{591}', '\u{5C7}'),
+            ('\u{5D0}', '\u{5EA}'),
+            ('\u{5EF}', '\u{5F4}'),
+            ('\u{FB1D}', '\u{FB36}'),
+            ('\u{FB38}', '\u{FB3C}'),
+            ('\u{FB3E}', '\u{FB3E}'),
+            ('\u{FB40}', '\u{FB41}'),
+            ('\u{FB43}', '\u{FB44}'),
+            ('\u{FB46}', '\u{FB4F}'),
+        ],
+    ),
+    (
+        "Hiragana",
+        &[
+            ('\u{3041}', '\u{3096}'),
+            ('\u{309D}', '\u{309F}'),
+            ('\u{1B001}', '\u{1B11F}'),
+            ('\u{1B132}', '\u{1B132}'),
+            ('\u{1B150}', '\u{1B152}'),
+            ('\u{1F200}', '\u{1F200}'),
+        ],
+    ),
+    (
+        "Imperial_Aramaic",
+        &[('\u{10840}', '\u{10855}'), ('\u{10857}', '\u{1085F}')],
+    ),
+    (
+        "Inherited",
+        &[
+            ('\u{300}', '\u{36F}'),
+            ('\u{485}', '\u{486}'),
+            ('\u{64B}', '\u{655}'),
+            ('\u{670}', '\u{670}'),
+            ('\u{951}', '\u{954}'),
+            ('\u{1AB0}', '\u{1ACE}'),
+            ('\u{1CD0}', '\u{1CD2}'),
+            ('\u{1CD4}', '\u{1CE0}'),
+            ('\u{1CE2}', '\u{1CE8}'),
+            ('\u{1CED}', '\u{1CED}'),
+            ('\u{1CF4}', '\u{1CF4}'),
+            ('\u{1CF8}', '\u{1CF9}'),
+            ('\u{1DC0}', '\u{1DFF}'),
+            ('\u{200C}', '\u{200D}'),
+            ('\u{20D0}', '\u{20F0}'),
+            ('\u{302A}', '\u{302D}'),
+            ('\u{3099}', '\u{309A}'),
+            ('\u{FE00}', '\u{FE0F}'),
+            ('\u{FE20}', '\u{FE2D}'),
+            ('\u{101FD}', '\u{101FD}'),
+            ('\u{102E0}', '\u{102E0}'),
+            ('\u{1133B}', '\u{1133B}'),
+            ('\u{1CF00}', '\u{1CF2D}'),
+            ('\u{1CF30}', '\u{1CF46}'),
+            ('\u{1D167}', '\u{1D169}'),
+            ('\u{1D17B}', '\u{1D182}'),
+            ('\u{1D185}', '\u{1D18B}'),
+            ('\u{1D1AA}', '\u{1D1AD}'),
+            ('\u{E0100}', '\u{E01EF}'),
+        ],
+    ),
+    (
+        "Inscriptional_Pahlavi",
+        &[('\u{10B60}', '\u{10B72}'), ('\u{10B78}', '\u{10B7F}')],
+    ),
+    (
+        "Inscriptional_Parthian",
+        &[('\u{10B40}', '\u{10B55}'), ('\u{10B58}', '\u{10B5F}')],
+    ),
+    (
+        "Javanese",
+        &[
+            ('\u{A980}', '\u{A9CD}'),
+            ('\u{A9D0}', '\u{A9D9}'),
+            ('\u{A9DE}', '\u{A9DF}'),
+        ],
+    ),
+    (
+        "Kaithi",
+        &[('\u{11080}', '\u{110C2}'), ('\u{110CD}', '\u{110CD}')],
+    ),
+    (
+        "Kannada",
+        &[
+            ('\u{C80}', '\u{C8C}'),
+            ('\u{C8E}', '\u{C90}'),
+            ('\u{C92}', '\u{CA8}'),
+            ('\u{CAA}', '\u{CB3}'),
+            ('\u{CB5}', '\u{CB9}'),
+            ('\u{CBC}', '\u{CC4}'),
+            ('\u{CC6}', '\u{CC8}'),
+            ('\u{CCA}', '\u{CCD}'),
+            ('\u{CD5}', '\u{CD6}'),
+            ('\u{CDD}', '\u{CDE}'),
+            ('\u{CE0}', '\u{CE3}'),
+            ('\u{CE6}', '\u{CEF}'),
+            ('\u{CF1}', '\u{CF3}'),
+        ],
+    ),
+    (
+        "Katakana",
+        &[
+            ('\u{30A1}', '\u{30FA}'),
+            ('\u{30FD}', '\u{30FF}'),
+            ('\u{31F0}', '\u{31FF}'),
+            ('\u{32D0}', '\u{32FE}'),
+            ('\u{3300}', '\u{3357}'),
+            ('\u{FF66}', '\u{FF6F}'),
+            ('\u{FF71}', '\u{FF9D}'),
+            ('\u{1AFF0}', '\u{1AFF3}'),
+            ('\u{1AFF5}', '\u{1AFFB}'),
+            ('\u{1AFFD}', '\u{1AFFE}'),
+            ('\u{1B000}', '\u{1B000}'),
+            ('\u{1B120}', '\u{1B122}'),
+            ('\u{1B155}', '\u{1B155}'),
+            ('\u{1B164}', '\u{1B167}'),
+        ],
+    ),
+    (
+        "Kawi",
+        &[
+            ('\u{11F00}', '\u{11F10}'),
+            ('\u{11F12}', '\u{11F3A}'),
+            ('\u{11F3E}', '\u{11F5A}'),
+        ],
+    ),
+    (
+        "Kayah_Li",
+        &[('\u{A900}', '\u{A92D}'), ('\u{A92F}', '\u{A92F}')],
+    ),
+    (
+        "Kharoshthi",
+        &[
+            ('\u{10A00}', '\u{10A03}'),
+            ('\u{10A05}', '\u{10A06}'),
+            ('\u{10A0C}', '\u{10A13}'),
+            ('\u{10A15}', '\u{10A17}'),
+            ('\u{10A19}', '\u{10A35}'),
+            ('\u{10A38}', '\u{10A3A}'),
+            ('\u{10A3F}', '\u{10A48}'),
+            ('\u{10A50}', '\u{10A58}'),
+        ],
+    ),
+    (
+        "Khitan_Small_Script",
+        &[
+            ('\u{16FE4}', '\u{16FE4}'),
+            ('\u{18B00}', '\u{18CD5}'),
+            ('\u{18CFF}', '\u{18CFF}'),
+        ],
+    ),
+    (
+        "Khmer",
+        &[
+            ('\u{1780}', '\u{17DD}'),
+            ('\u{17E0}', '\u{17E9}'),
+            ('\u{17F0}', '\u{17F9}'),
+            ('\u{19E0}', '\u{19FF}'),
+        ],
+    ),
+    (
+        "Khojki",
+        &[('\u{11200}', '\u{11211}'), ('\u{11213}', '\u{11241}')],
+    ),
+    (
+        "Khudawadi",
+        &[('\u{112B0}', '\u{112EA}'), ('\u{112F0}', '\u{112F9}')],
+    ),
+    ("Kirat_Rai", &[('\u{16D40}', '\u{16D79}')]),
+    (
+        "Lao",
+        &[
+            ('\u{E81}', '\u{E82}'),
+            ('\u{E84}', '\u{E84}'),
+            ('\u{E86}', '\u{E8A}'),
+            ('\u{E8C}', '\u{EA3}'),
+            ('\u{EA5}', '\u{EA5}'),
+            ('\u{EA7}', '\u{EBD}'),
+            ('\u{EC0}', '\u{EC4}'),
+            ('\u{EC6}', '\u{EC6}'),
+            ('\u{EC8}', '\u{ECE}'),
+            ('\u{ED0}', '\u{ED9}'),
+            ('\u{EDC}', '\u{EDF}'),
+        ],
+    ),
+    (
+        "Latin",
+        &[
+            ('\u{41}', '\u{5A}'),
+            ('\u{61}', '\u{7A}'),
+            ('\u{AA}', '\u{AA}'),
+            ('\u{BA}', '\u{BA}'),
+            ('\u{C0}', '\u{D6}'),
+            ('\u{D8}', '\u{F6}'),
+            ('\u{F8}', '\u{2B8}'),
+            ('\u{2E0}', '\u{2E4}'),
+            ('\u{1D00}', '\u{1D25}'),
+            ('\u{1D2C}', '\u{1D5C}'),
+            ('\u{1D62}', '\u{1D65}'),
+            ('\u{1D6B}', '\u{1D77}'),
+            ('\u{1D79}', '\u{1DBE}'),
+            ('\u{1E00}', '\u{1EFF}'),
+            ('\u{2071}', '\u{2071}'),
+            ('\u{207F}', '\u{207F}'),
+            ('\u{2090}', '\u{209C}'),
+            ('\u{212A}', '\u{212B}'),
+            ('\u{2132}', '\u{2132}'),
+            ('\u{214E}', '\u{214E}'),
+            ('\u{2160}', '\u{2188}'),
+            ('\u{2C60}', '\u{2C7F}'),
+            ('\u{A722}', '\u{A787}'),
+            ('\u{A78B}', '\u{A7CD}'),
+            ('\u{A7D0}', '\u{A7D1}'),
+            ('\u{A7D3}', '\u{A7D3}'),
+            ('\u{A7D5}', '\u{A7DC}'),
+            ('\u{A7F2}', '\u{A7FF}'),
+            ('\u{AB30}', '\u{AB5A}'),
+            ('\u{AB5C}', '\u{AB64}'),
+            ('\u{AB66}', '\u{AB69}'),
+            ('\u{FB00}', '\u{FB06}'),
+            ('\u{FF21}', '\u{FF3A}'),
+            ('\u{FF41}', '\u{FF5A}'),
+            ('\u{10780}', '\u{10785}'),
+            ('\u{10787}', '\u{107B0}'),
+            ('\u{107B2}', '\u{107BA}'),
+            ('\u{1DF00}', '\u{1DF1E}'),
+            ('\u{1DF25}', '\u{1DF2A}'),
+        ],
+    ),
+    (
+        "Lepcha",
+        &[
+            ('\u{1C00}', '\u{1C37}'),
+            ('\u{1C3B}', '\u{1C49}'),
+            ('\u{1C4D}', '\u{1C4F}'),
+        ],
+    ),
+    (
+        "Limbu",
+        &[
+            ('\u{1900}', '\u{191E}'),
+            ('\u{1920}', '\u{192B}'),
+            ('\u{1930}', '\u{193B}'),
+            ('\u{1940}', '\u{1940}'),
+            ('\u{1944}', '\u{194F}'),
+        ],
+    ),
+    (
+        "Linear_A",
+        &[
+            ('\u{10600}', '\u{10736}'),
+            ('\u{10740}', '\u{10755}'),
+            ('\u{10760}', '\u{10767}'),
+        ],
+    ),
+    (
+        "Linear_B",
+        &[
+            ('\u{10000}', '\u{1000B}'),
+            ('\u{1000D}', '\u{10026}'),
+            ('\u{10028}', '\u{1003A}'),
+            ('\u{1003C}', '\u{1003D}'),
+            ('\u{1003F}', '\u{1004D}'),
+            ('\u{10050}', '\u{1005D}'),
+            ('\u{10080}', '\u{100FA}'),
+        ],
+    ),
+    (
+        "Lisu",
+        &[('\u{A4D0}', '\u{A4FF}'), ('\u{11FB0}', '\u{11FB0}')],
+    ),
+    ("Lycian", &[('\u{10280}', '\u{1029C}')]),
+    (
+        "Lydian",
+        &[('\u{10920}', '\u{10939}'), ('\u{1093F}', '\u{1093F}')],
+    ),
+    ("Mahajani", &[('\u{11150}', '\u{11176}')]),
+    ("Makasar", &[('\u{11EE0}', '\u{11EF8}')]),
+    (
+        "Malayalam",
+        &[
+            ('\u{D00}', '\u{D0C}'),
+            ('\u{D0E}', '\u{D10}'),
+            ('\u{D12}', '\u{D44}'),
+            ('\u{D46}', '\u{D48}'),
+            ('\u{D4A}', '\u{D4F}'),
+            ('\u{D54}', '\u{D63}'),
+            ('\u{D66}', '\u{D7F}'),
+        ],
+    ),
+    ("Mandaic", &[('\u{840}', '\u{85B}'), ('\u{85E}', '\u{85E}')]),
+    (
+        "Manichaean",
+        &[('\u{10AC0}', '\u{10AE6}'), ('\u{10AEB}', '\u{10AF6}')],
+    ),
+    (
+        "Marchen",
+        &[
+            ('\u{11C70}', '\u{11C8F}'),
+            ('\u{11C92}', '\u{11CA7}'),
+            ('\u{11CA9}', '\u{11CB6}'),
+        ],
+    ),
+    (
+        "Masaram_Gondi",
+        &[
+            ('\u{11D00}', '\u{11D06}'),
+            ('\u{11D08}', '\u{11D09}'),
+            ('\u{11D0B}', '\u{11D36}'),
+            ('\u{11D3A}', '\u{11D3A}'),
+            ('\u{11D3C}', '\u{11D3D}'),
+            ('\u{11D3F}', '\u{11D47}'),
+            ('\u{11D50}', '\u{11D59}'),
+        ],
+    ),
+    ("Medefaidrin", &[('\u{16E40}', '\u{16E9A}')]),
+    (
+        "Meetei_Mayek",
+        &[
+            ('\u{AAE0}', '\u{AAF6}'),
+            ('\u{ABC0}', '\u{ABED}'),
+            ('\u{ABF0}', '\u{ABF9}'),
+        ],
+    ),
+    (
+        "Mende_Kikakui",
+        &[('\u{1E800}', '\u{1E8C4}'), ('\u{1E8C7}', '\u{1E8D6}')],
+    ),
+    (
+        "Meroitic_Cursive",
+        &[
+            ('\u{109A0}', '\u{109B7}'),
+            ('\u{109BC}', '\u{109CF}'),
+            ('\u{109D2}', '\u{109FF}'),
+        ],
+    ),
+    ("Meroitic_Hieroglyphs", &[('\u{10980}', '\u{1099F}')]),
+    (
+        "Miao",
+        &[
+            ('\u{16F00}', '\u{16F4A}'),
+            ('\u{16F4F}', '\u{16F87}'),
+            ('\u{16F8F}', '\u{16F9F}'),
+        ],
+    ),
+    (
+        "Modi",
+        &[('\u{11600}', '\u{11644}'), ('\u{11650}', '\u{11659}')],
+    ),
+    (
+        "Mongolian",
+        &[
+            ('\u{1800}', '\u{1801}'),
+            ('\u{1804}', '\u{1804}'),
+            ('\u{1806}', '\u{1819}'),
+            ('\u{1820}', '\u{1878}'),
+            ('\u{1880}', '\u{18AA}'),
+            ('\u{11660}', '\u{1166C}'),
+        ],
+    ),
+    (
+        "Mro",
+        &[
+            ('\u{16A40}', '\u{16A5E}'),
+            ('\u{16A60}', '\u{16A69}'),
+            ('\u{16A6E}', '\u{16A6F}'),
+        ],
+    ),
+    (
+        "Multani",
+        &[
+            ('\u{11280}', '\u{11286}'),
+            ('\u{11288}', '\u{11288}'),
+            ('\u{1128A}', '\u{1128D}'),
+            ('\u{1128F}', '\u{1129D}'),
+            ('\u{1129F}', '\u{112A9}'),
+        ],
+    ),
+    (
+        "Myanmar",
+        &[
+            ('\u{1000}', '\u{109F}'),
+            ('\u{A9E0}', '\u{A9FE}'),
+            ('\u{AA60}', '\u{AA7F}'),
+            ('\u{116D0}', '\u{116E3}'),
+        ],
+    ),
+    (
+        "Nabataean",
+        &[('\u{10880}', '\u{1089E}'), ('\u{108A7}', '\u{108AF}')],
+    ),
+    ("Nag_Mundari", &[('\u{1E4D0}', '\u{1E4F9}')]),
+    (
+        "Nandinagari",
+        &[
+            ('\u{119A0}', '\u{119A7}'),
+            ('\u{119AA}', '\u{119D7}'),
+            ('\u{119DA}', '\u{119E4}'),
+        ],
+    ),
+    (
+        "New_Tai_Lue",
+        &[
+            ('\u{1980}', '\u{19AB}'),
+            ('\u{19B0}', '\u{19C9}'),
+            ('\u{19D0}', '\u{19DA}'),
+            ('\u{19DE}', '\u{19DF}'),
+        ],
+    ),
+    (
+        "Newa",
+        &[('\u{11400}', '\u{1145B}'), ('\u{1145D}', '\u{11461}')],
+    ),
+    ("Nko", &[('\u{7C0}', '\u{7FA}'), ('\u{7FD}', '\u{7FF}')]),
+    (
+        "Nushu",
+        &[('\u{16FE1}', '\u{16FE1}'), ('\u{1B170}', '\u{1B2FB}')],
+    ),
+    (
+        "Nyiakeng_Puachue_Hmong",
+        &[
+            ('\u{1E100}', '\u{1E12C}'),
+            ('\u{1E130}', '\u{1E13D}'),
+            ('\u{1E140}', '\u{1E149}'),
+            ('\u{1E14E}', '\u{1E14F}'),
+        ],
+    ),
+    ("Ogham", &[('\u{1680}', '\u{169C}')]),
+    ("Ol_Chiki", &[('\u{1C50}', '\u{1C7F}')]),
+    (
+        "Ol_Onal",
+        &[('\u{1E5D0}', '\u{1E5FA}'), ('\u{1E5FF}', '\u{1E5FF}')],
+    ),
+    (
+        "Old_Hungarian",
+        &[
+            ('\u{10C80}', '\u{10CB2}'),
+            ('\u{10CC0}', '\u{10CF2}'),
+            ('\u{10CFA}', '\u{10CFF}'),
+        ],
+    ),
+    (
+        "Old_Italic",
+        &[('\u{10300}', '\u{10323}'), ('\u{1032D}', '\u{1032F}')],
+    ),
+    ("Old_North_Arabian", &[('\u{10A80}', '\u{10A9F}')]),
+    ("Old_Permic", &[('\u{10350}', '\u{1037A}')]),
+    (
+        "Old_Persian",
+        &[('\u{103A0}', '\u{103C3}'), ('\u{103C8}', '\u{103D5}')],
+    ),
+    ("Old_Sogdian", &[('\u{10F00}', '\u{10F27}')]),
+    ("Old_South_Arabian", &[('\u{10A60}', '\u{10A7F}')]),
+    ("Old_Turkic", &[('\u{10C00}', '\u{10C48}')]),
+    ("Old_Uyghur", &[('\u{10F70}', '\u{10F89}')]),
+    (
+        "Oriya",
+        &[
+            ('\u{B01}', '\u{B03}'),
+            ('\u{B05}', '\u{B0C}'),
+            ('\u{B0F}', '\u{B10}'),
+            ('\u{B13}', '\u{B28}'),
+            ('\u{B2A}', '\u{B30}'),
+            ('\u{B32}', '\u{B33}'),
+            ('\u{B35}', '\u{B39}'),
+            ('\u{B3C}', '\u{B44}'),
+            ('\u{B47}', '\u{B48}'),
+            ('\u{B4B}', '\u{B4D}'),
+            ('\u{B55}', '\u{B57}'),
+            ('\u{B5C}', '\u{B5D}'),
+            ('\u{B5F}', '\u{B63}'),
+            ('\u{B66}', '\u{B77}'),
+        ],
+    ),
+    (
+        "Osage",
+        &[('\u{104B0}', '\u{104D3}'), ('\u{104D8}', '\u{104FB}')],
+    ),
+    (
+        "Osmanya",
+        &[('\u{10480}', '\u{1049D}'), ('\u{104A0}', '\u{104A9}')],
+    ),
+    (
+        "Pahawh_Hmong",
+        &[
+            ('\u{16B00}', '\u{16B45}'),
+            ('\u{16B50}', '\u{16B59}'),
+            ('\u{16B5B}', '\u{16B61}'),
+            ('\u{16B63}', '\u{16B77}'),
+            ('\u{16B7D}', '\u{16B8F}'),
+        ],
+    ),
+    ("Palmyrene", &[('\u{10860}', '\u{1087F}')]),
+    ("Pau_Cin_Hau", &[('\u{11AC0}', '\u{11AF8}')]),
+    ("Phags_Pa", &[('\u{A840}', '\u{A877}')]),
+    (
+        "Phoenician",
+        &[('\u{10900}', '\u{1091B}'), ('\u{1091F}', '\u{1091F}')],
+    ),
+    (
+        "Psalter_Pahlavi",
+        &[
+            ('\u{10B80}', '\u{10B91}'),
+            ('\u{10B99}', '\u{10B9C}'),
+            ('\u{10BA9}', '\u{10BAF}'),
+        ],
+    ),
+    (
+        "Rejang",
+        &[('\u{A930}', '\u{A953}'), ('\u{A95F}', '\u{A95F}')],
+    ),
+    (
+        "Runic",
+        &[('\u{16A0}', '\u{16EA}'), ('\u{16EE}', '\u{16F8}')],
+    ),
+    (
+        "Samaritan",
+        &[('\u{800}', '\u{82D}'), ('\u{830}', '\u{83E}')],
+    ),
+    (
+        "Saurashtra",
+        &[('\u{A880}', '\u{A8C5}'), ('\u{A8CE}', '\u{A8D9}')],
+    ),
+    ("Sharada", &[('\u{11180}', '\u{111DF}')]),
+    ("Shavian", &[('\u{10450}', '\u{1047F}')]),
+    (
+        "Siddham",
+        &[('\u{11580}', '\u{115B5}'), ('\u{115B8}', '\u{115DD}')],
+    ),
+    (
+        "SignWriting",
+        &[
+            ('\u{1D800}', '\u{1DA8B}'),
+            ('\u{1DA9B}', '\u{1DA9F}'),
+            ('\u{1DAA1}', '\u{1DAAF}'),
+        ],
+    ),
+    (
+        "Sinhala",
+        &[
+            ('\u{D81}', '\u{D83}'),
+            ('\u{D85}', '\u{D96}'),
+            ('\u{D9A}', '\u{DB1}'),
+            ('\u{DB3}', '\u{DBB}'),
+            ('\u{DBD}', '\u{DBD}'),
+            ('\u{DC0}', '\u{DC6}'),
+            ('\u{DCA}', '\u{DCA}'),
+            ('\u{DCF}', '\u{DD4}'),
+            ('\u{DD6}', '\u{DD6}'),
+            ('\u{DD8}', '\u{DDF}'),
+            ('\u{DE6}', '\u{DEF}'),
+            ('\u{DF2}', '\u{DF4}'),
+            ('\u{111E1}', '\u{111F4}'),
+        ],
+    ),
+    ("Sogdian", &[('\u{10F30}', '\u{10F59}')]),
+    (
+        "Sora_Sompeng",
+        &[('\u{110D0}', '\u{110E8}'), ('\u{110F0}', '\u{110F9}')],
+    ),
+    ("Soyombo", &[('\u{11A50}', '\u{11AA2}')]),
+    (
+        "Sundanese",
+        &[('\u{1B80}', '\u{1BBF}'), ('\u{1CC0}', '\u{1CC7}')],
+    ),
+    (
+        "Sunuwar",
+        &[('\u{11BC0}', '\u{11BE1}'), ('\u{11BF0}', '\u{11BF9}')],
+    ),
+    ("Syloti_Nagri", &[('\u{A800}', '\u{A82C}')]),
+    (
+        "Syriac",
+        &[
+            ('\u{700}', '\u{70D}'),
+            ('\u{70F}', '\u{74A}'),
+            ('\u{74D}', '\u{74F}'),
+            ('\u{860}', '\u{86A}'),
+        ],
+    ),
+    (
+        "Tagalog",
+        &[('\u{1700}', '\u{1715}'), ('\u{171F}', '\u{171F}')],
+    ),
+    (
+        "Tagbanwa",
+        &[
+            ('\u{1760}', '\u{176C}'),
+            ('\u{176E}', '\u{1770}'),
+            ('\u{1772}', '\u{1773}'),
+        ],
+    ),
+    (
+        "Tai_Le",
+        &[('\u{1950}', '\u{196D}'), ('\u{1970}', '\u{1974}')],
+    ),
+    (
+        "Tai_Tham",
+        &[
+            ('\u{1A20}', '\u{1A5E}'),
+            ('\u{1A60}', '\u{1A7C}'),
+            ('\u{1A7F}', '\u{1A89}'),
+            ('\u{1A90}', '\u{1A99}'),
+            ('\u{1AA0}', '\u{1AAD}'),
+        ],
+    ),
+    (
+        "Tai_Viet",
+        &[('\u{AA80}', '\u{AAC2}'), ('\u{AADB}', '\u{AADF}')],
+    ),
+    (
+        "Takri",
+        &[('\u{11680}', '\u{116B9}'), ('\u{116C0}', '\u{116C9}')],
+    ),
+    (
+        "Tamil",
+        &[
+            ('\u{B82}', '\u{B83}'),
+            ('\u{B85}', '\u{B8A}'),
+            ('\u{B8E}', '\u{B90}'),
+            ('\u{B92}', '\u{B95}'),
+            ('\u{B99}', '\u{B9A}'),
+            ('\u{B9C}', '\u{B9C}'),
+            ('\u{B9E}', '\u{B9F}'),
+            ('\u{BA3}', '\u{BA4}'),
+            ('\u{BA8}', '\u{BAA}'),
+            ('\u{BAE}', '\u{BB9}'),
+            ('\u{BBE}', '\u{BC2}'),
+            ('\u{BC6}', '\u{BC8}'),
+            ('\u{BCA}', '\u{BCD}'),
+            ('\u{BD0}', '\u{BD0}'),
+            ('\u{BD7}', '\u{BD7}'),
+            ('\u{BE6}', '\u{BFA}'),
+            ('\u{11FC0}', '\u{11FF1}'),
+            ('\u{11FFF}', '\u{11FFF}'),
+        ],
+    ),
+    (
+        "Tangsa",
+        &[('\u{16A70}', '\u{16ABE}'), ('\u{16AC0}', '\u{16AC9}')],
+    ),
+    (
+        "Tangut",
+        &[
+            ('\u{16FE0}', '\u{16FE0}'),
+            ('\u{17000}', '\u{187F7}'),
+            ('\u{18800}', '\u{18AFF}'),
+            ('\u{18D00}', '\u{18D08}'),
+        ],
+    ),
+    (
+        "Telugu",
+        &[
+            ('\u{C00}', '\u{C0C}'),
+            ('\u{C0E}', '\u{C10}'),
+            ('\u{C12}', '\u{C28}'),
+            ('\u{C2A}', '\u{C39}'),
+            ('\u{C3C}', '\u{C44}'),
+            ('\u{C46}', '\u{C48}'),
+            ('\u{C4A}', '\u{C4D}'),
+            ('\u{C55}', '\u{C56}'),
+            ('\u{C58}', '\u{C5A}'),
+            ('\u{C5D}', '\u{C5D}'),
+            ('\u{C60}', '\u{C63}'),
+            ('\u{C66}', '\u{C6F}'),
+            ('\u{C77}', '\u{C7F}'),
+        ],
+    ),
+    ("Thaana", &[('\u{780}', '\u{7B1}')]),
+    ("Thai", &[('\u{E01}', '\u{E3A}'), ('\u{E40}', '\u{E5B}')]),
+    (
+        "Tibetan",
+        &[
+            ('\u{F00}', '\u{F47}'),
+            ('\u{F49}', '\u{F6C}'),
+            ('\u{F71}', '\u{F97}'),
+            ('\u{F99}', '\u{FBC}'),
+            ('\u{FBE}', '\u{FCC}'),
+            ('\u{FCE}', '\u{FD4}'),
+            ('\u{FD9}', '\u{FDA}'),
+        ],
+    ),
+    (
+        "Tifinagh",
+        &[
+            ('\u{2D30}', '\u{2D67}'),
+            ('\u{2D6F}', '\u{2D70}'),
+            ('\u{2D7F}', '\u{2D7F}'),
+        ],
+    ),
+    (
+        "Tirhuta",
+        &[('\u{11480}', '\u{114C7}'), ('\u{114D0}', '\u{114D9}')],
+    ),
+    ("Todhri", &[('\u{105C0}', '\u{105F3}')]),
+    ("Toto", &[('\u{1E290}', '\u{1E2AE}')]),
+    (
+        "Tulu_Tigalari",
+        &[
+            ('\u{11380}', '\u{11389}'),
+            ('\u{1138B}', '\u{1138B}'),
+            ('\u{1138E}', '\u{1138E}'),
+            ('\u{11390}', '\u{113B5}'),
+            ('\u{113B7}', '\u{113C0}'),
+            ('\u{113C2}', '\u{113C2}'),
+            ('\u{113C5}', '\u{113C5}'),
+            ('\u{113C7}', '\u{113CA}'),
+            ('\u{113CC}', '\u{113D5}'),
+            ('\u{113D7}', '\u{113D8}'),
+            ('\u{113E1}', '\u{113E2}'),
+        ],
+    ),
+    (
+        "Ugaritic",
+        &[('\u{10380}', '\u{1039D}'), ('\u{1039F}', '\u{1039F}')],
+    ),
+    ("Vai", &[('\u{A500}', '\u{A62B}')]),
+    (
+        "Vithkuqi",
+        &[
+            ('\u{10570}', '\u{1057A}'),
+            ('\u{1057C}', '\u{1058A}'),
+            ('\u{1058C}', '\u{10592}'),
+            ('\u{10594}', '\u{10595}'),
+            ('\u{10597}', '\u{105A1}'),
+            ('\u{105A3}', '\u{105B1}'),
+            ('\u{105B3}', '\u{105B9}'),
+            ('\u{105BB}', '\u{105BC}'),
+        ],
+    ),
+    (
+        "Wancho",
+        &[('\u{1E2C0}', '\u{1E2F9}'), ('\u{1E2FF}', '\u{1E2FF}')],
+    ),
+    (
+        "Warang_Citi",
+        &[('\u{118A0}', '\u{118F2}'), ('\u{118FF}', '\u{118FF}')],
+    ),
+    (
+        "Yezidi",
+        &[
+            ('\u{10E80}', '\u{10EA9}'),
+            ('\u{10EAB}', '\u{10EAD}'),
+            ('\u{10EB0}', '\u{10EB1}'),
+        ],
+    ),
+    ("Yi", &[('\u{A000}', '\u{A48C}'), ('\u{A490}', '\u{A4C6}')]),
+    ("Zanabazar_Square", &[('\u{11A00}', '\u{11A47}')]),
+];
+
+//each script's names with case, spaces, underscores and hyphens removed, as loose matching compares them
+pub(super) static ALIASES: &[(&str, &str)] = &[
+    ("adlam", "Adlam"),
+    ("adlm", "Adlam"),
+    ("aghb", "Caucasian_Albanian"),
+    ("ahom", "Ahom"),
+    ("anatolianhieroglyphs", "Anatolian_Hieroglyphs"),
+    ("arab", "Arabic"),
+    ("arabic", "Arabic"),
+    ("armenian", "Armenian"),
+    ("armi", "Imperial_Aramaic"),
+    ("armn", "Armenian"),
+    ("avestan", "Avestan"),
+    ("avst", "Avestan"),
+    ("bali", "Balinese"),
+    ("balinese", "Balinese"),
+    ("bamu", "Bamum"),
+    ("bamum", "Bamum"),
+    ("bass", "Bassa_Vah"),
+    ("bassavah", "Bassa_Vah"),
+    ("batak", "Batak"),
+    ("batk", "Batak"),
+    ("beng", "Bengali"),
+    ("bengali", "Bengali"),
+    ("bhaiksuki", "Bhaiksuki"),
+    ("bhks", "Bhaiksuki"),
+    ("bopo", "Bopomofo"),
+    ("bopomofo", "Bopomofo"),
+    ("brah", "Brahmi"),
+    ("brahmi", "Brahmi"),
+    ("brai", "Braille"),
+    ("braille", "Braille"),
+    ("bugi", "Buginese"),
+    ("buginese", "Buginese"),
+    ("buhd", "Buhid"),
+    ("buhid", "Buhid"),
+    ("cakm", "Chakma"),
+    ("canadianaboriginal", "Canadian_Aboriginal"),
+    ("cans", "Canadian_Aboriginal"),
+    ("cari", "Carian"),
+    ("carian", "Carian"),
+    ("caucasianalbanian", "Caucasian_Albanian"),
+    ("chakma", "Chakma"),
+    ("cham", "Cham"),
+    ("cher", "Cherokee"),
+    ("cherokee", "Cherokee"),
+    ("chorasmian", "Chorasmian"),
+    ("chrs", "Chorasmian"),
+    ("common", "Common"),
+    ("copt", "Coptic"),
+    ("coptic", "Coptic"),
+    ("cpmn", "Cypro_Minoan"),
+    ("cprt", "Cypriot"),
+    ("cuneiform", "Cuneiform"),
+    ("cypriot", "Cypriot"),
+    ("cyprominoan", "Cypro_Minoan"),
+    ("cyrillic", "Cyrillic"),
+    ("cyrl", "Cyrillic"),
+    ("deseret", "Deseret"),
+    ("deva", "Devanagari"),
+    ("devanagari", "Devanagari"),
+    ("diak", "Dives_Akuru"),
+    ("divesakuru", "Dives_Akuru"),
+    ("dogr", "Dogra"),
+    ("dogra", "Dogra"),
+    ("dsrt", "Deseret"),
+    ("dupl", "Duployan"),
+    ("duployan", "Duployan"),
+    ("egyp", "Egyptian_Hieroglyphs"),
+    ("egyptianhieroglyphs", "Egyptian_Hieroglyphs"),
+    ("elba", "Elbasan"),
+    ("elbasan", "Elbasan"),
+    ("elym", "Elymaic"),
+    ("elymaic", "Elymaic"),
+    ("ethi", "Ethiopic"),
+    ("ethiopic", "Ethiopic"),
+    ("gara", "Garay"),
+    ("garay", "Garay"),
+    ("geor", "Georgian"),
+    ("georgian", "Georgian"),
+    ("glag", "Glagolitic"),
+    ("glagolitic", "Glagolitic"),
+    ("gong", "Gunjala_Gondi"),
+    ("gonm", "Masaram_Gondi"),
+    ("goth", "Gothic"),
+    ("gothic", "Gothic"),
+    ("gran", "Grantha"),
+    ("grantha", "Grantha"),
+    ("greek", "Greek"),
+    ("grek", "Greek"),
+    ("gujarati", "Gujarati"),
+    ("gujr", "Gujarati"),
+    ("gukh", "Gurung_Khema"),
+    ("gunjalagondi", "Gunjala_Gondi"),
+    ("gurmukhi", "Gurmukhi"),
+    ("guru", "Gurmukhi"),
+    ("gurungkhema", "Gurung_Khema"),
+    ("han", "Han"),
+    ("hang", "Hangul"),
+    ("hangul", "Hangul"),
+    ("hani", "Han"),
+    ("hanifirohingya", "Hanifi_Rohingya"),
+    ("hano", "Hanunoo"),
+    ("hanunoo", "Hanunoo"),
+    ("hatr", "Hatran"),
+    ("hatran", "Hatran"),
+    ("hebr", "Hebrew"),
+    ("hebrew", "Hebrew"),
+    ("hira", "Hiragana"),
+    ("hiragana", "Hiragana"),
+    ("hluw", "Anatolian_Hieroglyphs"),
+    ("hmng", "Pahawh_Hmong"),
+    ("hmnp", "Nyiakeng_Puachue_Hmong"),
+    ("hung", "Old_Hungarian"),
+    ("imperialaramaic", "Imperial_Aramaic"),
+    ("inherited", "Inherited"),
+    ("inscriptionalpahlavi", "Inscriptional_Pahlavi"),
+    ("inscriptionalparthian", "Inscriptional_Parthian"),
+    ("ital", "Old_Italic"),
+    ("java", "Javanese"),
+    ("javanese", "Javanese"),
+    ("kaithi", "Kaithi"),
+    ("kali", "Kayah_Li"),
+    ("kana", "Katakana"),
+    ("kannada", "Kannada"),
+    ("katakana", "Katakana"),
+    ("kawi", "Kawi"),
+    ("kayahli", "Kayah_Li"),
+    ("khar", "Kharoshthi"),
+    ("kharoshthi", "Kharoshthi"),
+    ("khitansmallscript", "Khitan_Small_Script"),
+    ("khmer", "Khmer"),
+    ("khmr", "Khmer"),
+    ("khoj", "Khojki"),
+    ("khojki", "Khojki"),
+    ("khudawadi", "Khudawadi"),
+    ("kiratrai", "Kirat_Rai"),
+    ("kits", "Khitan_Small_Script"),
+    ("knda", "Kannada"),
+    ("krai", "Kirat_Rai"),
+    ("kthi", "Kaithi"),
+    ("lana", "Tai_Tham"),
+    ("lao", "Lao"),
+    ("laoo", "Lao"),
+    ("latin", "Latin"),
+    ("latn", "Latin"),
+    ("lepc", "Lepcha"),
+    ("lepcha", "Lepcha"),
+    ("limb", "Limbu"),
+    ("limbu", "Limbu"),
+    ("lina", "Linear_A"),
+    ("linb", "Linear_B"),
+    ("lineara", "Linear_A"),
+    ("linearb", "Linear_B"),
+    ("lisu", "Lisu"),
+    ("lyci", "Lycian"),
+    ("lycian", "Lycian"),
+    ("lydi", "Lydian"),
+    ("lydian", "Lydian"),
+    ("mahajani", "Mahajani"),
+    ("mahj", "Mahajani"),
+    ("maka", "Makasar"),
+    ("makasar", "Makasar"),
+    ("malayalam", "Malayalam"),
+    ("mand", "Mandaic"),
+    ("mandaic", "Mandaic"),
+    ("mani", "Manichaean"),
+    ("manichaean", "Manichaean"),
+    ("marc", "Marchen"),
+    ("marchen", "Marchen"),
+    ("masaramgondi", "Masaram_Gondi"),
+    ("medefaidrin", "Medefaidrin"),
+    ("medf", "Medefaidrin"),
+    ("meeteimayek", "Meetei_Mayek"),
+    ("mend", "Mende_Kikakui"),
+    ("mendekikakui", "Mende_Kikakui"),
+    ("merc", "Meroitic_Cursive"),
+    ("mero", "Meroitic_Hieroglyphs"),
+    ("meroiticcursive", "Meroitic_Cursive"),
+    ("meroitichieroglyphs", "Meroitic_Hieroglyphs"),
+    ("miao", "Miao"),
+    ("mlym", "Malayalam"),
+    ("modi", "Modi"),
+    ("mong", "Mongolian"),
+    ("mongolian", "Mongolian"),
+    ("mro", "Mro"),
+    ("mroo", "Mro"),
+    ("mtei", "Meetei_Mayek"),
+    ("mult", "Multani"),
+    ("multani", "Multani"),
+    ("myanmar", "Myanmar"),
+    ("mymr", "Myanmar"),
+    ("nabataean", "Nabataean"),
+    ("nagm", "Nag_Mundari"),
+    ("nagmundari", "Nag_Mundari"),
+    ("nand", "Nandinagari"),
+    ("nandinagari", "Nandinagari"),
+    ("narb", "Old_North_Arabian"),
+    ("nbat", "Nabataean"),
+    ("newa", "Newa"),
+    ("newtailue", "New_Tai_Lue"),
+    ("nko", "Nko"),
+    ("nkoo", "Nko"),
+    ("nshu", "Nushu"),
+    ("nushu", "Nushu"),
+    ("nyiakengpuachuehmong", "Nyiakeng_Puachue_Hmong"),
+    ("ogam", "Ogham"),
+    ("ogham", "Ogham"),
+    ("olchiki", "Ol_Chiki"),
+    ("olck", "Ol_Chiki"),
+    ("oldhungarian", "Old_Hungarian"),
+    ("olditalic", "Old_Italic"),
+    ("oldnortharabian", "Old_North_Arabian"),
+    ("oldpermic", "Old_Permic"),
+    ("oldpersian", "Old_Persian"),
+    ("oldsogdian", "Old_Sogdian"),
+    ("oldsoutharabian", "Old_South_Arabian"),
+    ("oldturkic", "Old_Turkic"),
+    ("olduyghur", "Old_Uyghur"),
+    ("olonal", "Ol_Onal"),
+    ("onao", "Ol_Onal"),
+    ("oriya", "Oriya"),
+    ("orkh", "Old_Turkic"),
+    ("orya", "Oriya"),
+    ("osage", "Osage"),
+    ("osge", "Osage"),
+    ("osma", "Osmanya"),
+    ("osmanya", "Osmanya"),
+    ("ougr", "Old_Uyghur"),
+    ("pahawhhmong", "Pahawh_Hmong"),
+    ("palm", "Palmyrene"),
+    ("palmyrene", "Palmyrene"),
+    ("pauc", "Pau_Cin_Hau"),
+    ("paucinhau", "Pau_Cin_Hau"),
+    ("perm", "Old_Permic"),
+    ("phag", "Phags_Pa"),
+    ("phagspa", "Phags_Pa"),
+    ("phli", "Inscriptional_Pahlavi"),
+    ("phlp", "Psalter_Pahlavi"),
+    ("phnx", "Phoenician"),
+    ("phoenician", "Phoenician"),
+    ("plrd", "Miao"),
+    ("prti", "Inscriptional_Parthian"),
+    ("psalterpahlavi", "Psalter_Pahlavi"),
+    ("qaac", "Coptic"),
+    ("qaai", "Inherited"),
+    ("rejang", "Rejang"),
+    ("rjng", "Rejang"),
+    ("rohg", "Hanifi_Rohingya"),
+    ("runic", "Runic"),
+    ("runr", "Runic"),
+    ("samaritan", "Samaritan"),
+    ("samr", "Samaritan"),
+    ("sarb", "Old_South_Arabian"),
+    ("saur", "Saurashtra"),
+    ("saurashtra", "Saurashtra"),
+    ("sgnw", "SignWriting"),
+    ("sharada", "Sharada"),
+    ("shavian", "Shavian"),
+    ("shaw", "Shavian"),
+    ("shrd", "Sharada"),
+    ("sidd", "Siddham"),
+    ("siddham", "Siddham"),
+    ("signwriting", "SignWriting"),
+    ("sind", "Khudawadi"),
+    ("sinh", "Sinhala"),
+    ("sinhala", "Sinhala"),
+    ("sogd", "Sogdian"),
+    ("sogdian", "Sogdian"),
+    ("sogo", "Old_Sogdian"),
+    ("sora", "Sora_Sompeng"),
+    ("sorasompeng", "Sora_Sompeng"),
+    ("soyo", "Soyombo"),
+    ("soyombo", "Soyombo"),
+    ("sund", "Sundanese"),
+    ("sundanese", "Sundanese"),
+    ("sunu", "Sunuwar"),
+    ("sunuwar", "Sunuwar"),
+    ("sylo", "Syloti_Nagri"),
+    ("sylotinagri", "Syloti_Nagri"),
+    ("syrc", "Syriac"),
+    ("syriac", "Syriac"),
+    ("tagalog", "Tagalog"),
+    ("tagb", "Tagbanwa"),
+    ("tagbanwa", "Tagbanwa"),
+    ("taile", "Tai_Le"),
+    ("taitham", "Tai_Tham"),
+    ("taiviet", "Tai_Viet"),
+    ("takr", "Takri"),
+    ("takri", "Takri"),
+    ("tale", "Tai_Le"),
+    ("talu", "New_Tai_Lue"),
+    ("tamil", "Tamil"),
+    ("taml", "Tamil"),
+    ("tang", "Tangut"),
+    ("tangsa", "Tangsa"),
+    ("tangut", "Tangut"),
+    ("tavt", "Tai_Viet"),
+    ("telu", "Telugu"),
+    ("telugu", "Telugu"),
+    ("tfng", "Tifinagh"),
+    ("tglg", "Tagalog"),
+    ("thaa", "Thaana"),
+    ("thaana", "Thaana"),
+    ("thai", "Thai"),
+    ("tibetan", "Tibetan"),
+    ("tibt", "Tibetan"),
+    ("tifinagh", "Tifinagh"),
+    ("tirh", "Tirhuta"),
+    ("tirhuta", "Tirhuta"),
+    ("tnsa", "Tangsa"),
+    ("todhri", "Todhri"),
+    ("todr", "Todhri"),
+    ("toto", "Toto"),
+    ("tulutigalari", "Tulu_Tigalari"),
+    ("tutg", "Tulu_Tigalari"),
+    ("ugar", "Ugaritic"),
+    ("ugaritic", "Ugaritic"),
+    ("vai", "Vai"),
+    ("vaii", "Vai"),
+    ("vith", "Vithkuqi"),
+    ("vithkuqi", "Vithkuqi"),
+    ("wancho", "Wancho"),
+    ("wara", "Warang_Citi"),
+    ("warangciti", "Warang_Citi"),
+    ("wcho", "Wancho"),
+    ("xpeo", "Old_Persian"),
+    ("xsux", "Cuneiform"),
+    ("yezi", "Yezidi"),
+    ("yezidi", "Yezidi"),
+    ("yi", "Yi"),
+    ("yiii", "Yi"),
+    ("zanabazarsquare", "Zanabazar_Square"),
+    ("zanb", "Zanabazar_Square"),
+    ("zinh", "Inherited"),
+    ("zyyy", "Common"),
+];
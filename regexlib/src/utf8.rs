@@ -0,0 +1,385 @@
+use crate::case_folding::expand_folded_literal;
+use crate::line_terminator::LineTerminator;
+use crate::parser::character_class::CharacterClass;
+use crate::parser::RegexEntry;
+
+const SURROGATES: (u32, u32) = (0xD800, 0xDFFF);
+
+impl RegexEntry {
+    /// The entry as it matches bytes rather than characters, for input that may not be valid
+    /// UTF-8: each character is matched as the bytes of its UTF-8 encoding, so `é` becomes the
+    /// bytes 0xC3 0xA9, and `.` or a class becomes an alternation of the byte sequences of its
+    /// characters. A [`Byte`](RegexEntry::Byte) is kept as the single byte it matches. An
+    /// automaton built from the result reads each byte as the character of the same value (e.g.,
+    /// the byte 0xFF as `\u{FF}`), so a byte that is not part of valid UTF-8 is only matched by a
+    /// `Byte`, never by a character class, and is not mistaken for `\u{FFFD}`.
+    ///
+    /// `\X`, `\b{start}`, `\b{end}`, and `^` and `$` with the Unicode line terminators depend on
+    /// the characters around them, which can not be told from a single byte, so an entry with any
+    /// of them is an error.
+    pub fn to_utf8_bytes(&self) -> Result<RegexEntry, String> {
+        Ok(match self {
+            RegexEntry::AnyCharacter => sequences_entry(&utf8_sequences(&[(0, char::MAX as u32)])),
+            RegexEntry::UnicodeCharacterClass(categories) => {
+                class_entry(&CharacterClass::with_categories(categories))
+            }
+            RegexEntry::NegatedUnicodeCharacterClass(categories) => class_entry(
+                &CharacterClass::Negated(Box::new(CharacterClass::with_categories(categories))),
+            ),
+            RegexEntry::NonUnicodeCharacterClass(class) => class_entry(class),
+            RegexEntry::UnicodeScript(script) => {
+                class_entry(&CharacterClass::from_ranges(script.ranges().to_vec()))
+            }
+            RegexEntry::NegatedUnicodeScript(script) => class_entry(&CharacterClass::Negated(
+                Box::new(CharacterClass::from_ranges(script.ranges().to_vec())),
+            )),
+            RegexEntry::Literal(literal) => literal_entry(*literal),
+            RegexEntry::FoldedLiteral(literal) => expand_folded_literal(literal).to_utf8_bytes()?,
+            //the characters above `\xFF` stand for no byte, so they are left out
+            RegexEntry::Byte(class) => RegexEntry::Byte(CharacterClass::from_ranges(
+                class_ranges(class)
+                    .into_iter()
+                    .take_while(|(start, _)| *start <= 0xFF)
+                    .map(|(start, end)| (byte_char(start as u8), byte_char(end.min(0xFF) as u8)))
+                    .collect(),
+            )),
+            RegexEntry::GraphemeCluster => {
+                return Err("\\X can not be matched against bytes".to_string())
+            }
+            RegexEntry::WordStart | RegexEntry::WordEnd => {
+                return Err("Word boundaries can not be matched against bytes".to_string())
+            }
+            RegexEntry::StartOfLine(LineTerminator::Unicode)
+            | RegexEntry::EndOfLine(LineTerminator::Unicode) => {
+                return Err(
+                    "^ and $ can not be matched against bytes with the Unicode line terminators"
+                        .to_string(),
+                )
+            }
+            RegexEntry::StartOfInput
+            | RegexEntry::EndOfInput
+            | RegexEntry::StartOfLine(_)
+            | RegexEntry::EndOfLine(_) => self.clone(),
+            RegexEntry::Concatenation(entries) => RegexEntry::Concatenation(
+                entries
+                    .iter()
+                    .map(RegexEntry::to_utf8_bytes)
+                    .collect::<Result<_, _>>()?,
+            ),
+            RegexEntry::Alternation(entries) => RegexEntry::Alternation(
+                entries
+                    .iter()
+                    .map(RegexEntry::to_utf8_bytes)
+                    .collect::<Result<_, _>>()?,
+            ),
+            RegexEntry::Repetition {
+                base,
+                min,
+                max,
+                lazy,
+            } => RegexEntry::Repetition {
+                base: Box::new(base.to_utf8_bytes()?),
+                min: *min,
+                max: *max,
+                lazy: *lazy,
+            },
+            RegexEntry::Capture { index, name, base } => RegexEntry::Capture {
+                index: *index,
+                name: name.clone(),
+                base: Box::new(base.to_utf8_bytes()?),
+            },
+        })
+    }
+}
+
+fn byte_char(byte: u8) -> char {
+    byte as char
+}
+
+fn literal_entry(literal: char) -> RegexEntry {
+    if literal.is_ascii() {
+        return RegexEntry::Literal(literal);
+    }
+
+    let mut encoded = [0; 4];
+    RegexEntry::Concatenation(
+        literal
+            .encode_utf8(&mut encoded)
+            .bytes()
+            .map(|byte| RegexEntry::Literal(byte_char(byte)))
+            .collect(),
+    )
+}
+
+fn class_entry(class: &CharacterClass) -> RegexEntry {
+    sequences_entry(&utf8_sequences(&class_ranges(class)))
+}
+
+//the sequences are in the order of their characters, so the ones sharing a first byte range are next to each other, and are read as that range followed by the alternation of their rests
+fn sequences_entry(sequences: &[Vec<(u8, u8)>]) -> RegexEntry {
+    let mut single_bytes = Vec::new();
+    let mut members = Vec::new();
+    let mut index = 0;
+    while index < sequences.len() {
+        let first = sequences[index][0];
+        if sequences[index].len() == 1 {
+            single_bytes.push((byte_char(first.0), byte_char(first.1)));
+            index += 1;
+            continue;
+        }
+
+        let group_end = index
+            + sequences[index..]
+                .iter()
+                .take_while(|sequence| sequence.len() > 1 && sequence[0] == first)
+                .count();
+        let rests = sequences[index..group_end]
+            .iter()
+            .map(|sequence| sequence[1..].to_vec())
+            .collect::<Vec<_>>();
+        members.push(RegexEntry::Concatenation(vec![
+            byte_range_entry(first),
+            sequences_entry(&rests),
+        ]));
+        index = group_end;
+    }
+
+    if !single_bytes.is_empty() || members.is_empty() {
+        members.insert(
+            0,
+            match single_bytes.as_slice() {
+                [(start, end)] => byte_range_entry((*start as u8, *end as u8)),
+                _ => {
+                    RegexEntry::NonUnicodeCharacterClass(CharacterClass::from_ranges(single_bytes))
+                }
+            },
+        );
+    }
+    match members.len() {
+        1 => members.remove(0),
+        _ => RegexEntry::Alternation(members),
+    }
+}
+
+fn byte_range_entry((start, end): (u8, u8)) -> RegexEntry {
+    if start == end {
+        RegexEntry::Literal(byte_char(start))
+    } else {
+        RegexEntry::NonUnicodeCharacterClass(CharacterClass::Range {
+            start: byte_char(start),
+            end: byte_char(end),
+        })
+    }
+}
+
+/// The sequences of byte ranges that the UTF-8 encodings of the characters in `ranges` (sorted
+/// and disjoint) are made of: each sequence matches the encodings of one run of characters, a
+/// byte from each of its ranges in turn.
+pub(crate) fn utf8_sequences(ranges: &[(u32, u32)]) -> Vec<Vec<(u8, u8)>> {
+    let mut sequences = Vec::new();
+    for (start, end) in ranges {
+        push_sequences(*start, *end, &mut sequences);
+    }
+    sequences
+}
+
+//a range is split until each of its parts has encodings of one length, whose bytes after the first vary over their whole span or not at all, so that the part is a product of byte ranges
+fn push_sequences(start: u32, end: u32, sequences: &mut Vec<Vec<(u8, u8)>>) {
+    if start > end {
+        return;
+    }
+    if start <= SURROGATES.1 && end >= SURROGATES.0 {
+        if start < SURROGATES.0 {
+            push_sequences(start, SURROGATES.0 - 1, sequences);
+        }
+        if end > SURROGATES.1 {
+            push_sequences(SURROGATES.1 + 1, end, sequences);
+        }
+        return;
+    }
+    for last_of_length in [0x7F, 0x7FF, 0xFFFF] {
+        if start <= last_of_length && last_of_length < end {
+            push_sequences(start, last_of_length, sequences);
+            push_sequences(last_of_length + 1, end, sequences);
+            return;
+        }
+    }
+    if end <= 0x7F {
+        sequences.push(vec![(start as u8, end as u8)]);
+        return;
+    }
+
+    for continuation_bytes in 1..4 {
+        let mask = (1 << (6 * continuation_bytes)) - 1;
+        if start & !mask == end & !mask {
+            continue;
+        }
+        if start & mask != 0 {
+            push_sequences(start, start | mask, sequences);
+            push_sequences((start | mask) + 1, end, sequences);
+            return;
+        }
+        if end & mask != mask {
+            push_sequences(start, (end & !mask) - 1, sequences);
+            push_sequences(end & !mask, end, sequences);
+            return;
+        }
+    }
+
+    let (mut start_bytes, mut end_bytes) = ([0; 4], [0; 4]);
+    let start_bytes = char::from_u32(start)
+        .expect("surrogates were split off")
+        .encode_utf8(&mut start_bytes)
+        .as_bytes();
+    let end_bytes = char::from_u32(end)
+        .expect("surrogates were split off")
+        .encode_utf8(&mut end_bytes)
+        .as_bytes();
+    sequences.push(
+        start_bytes
+            .iter()
+            .zip(end_bytes)
+            .map(|(start, end)| (*start, *end))
+            .collect(),
+    );
+}
+
+//the code points the class matches, as sorted and disjoint ranges
+fn class_ranges(class: &CharacterClass) -> Vec<(u32, u32)> {
+    let ranges = match class {
+        CharacterClass::Char(char) => vec![(*char as u32, *char as u32)],
+        CharacterClass::Range { start, end } if start <= end => vec![(*start as u32, *end as u32)],
+        CharacterClass::Range { .. } => Vec::new(),
+        CharacterClass::Disjunction(classes) => classes.iter().flat_map(class_ranges).collect(),
+        CharacterClass::Negated(class) => complement(class_ranges(class)),
+        CharacterClass::Intersection(left, right) => {
+            let mut outside = complement(class_ranges(left));
+            outside.extend(complement(class_ranges(right)));
+            complement(outside)
+        }
+        CharacterClass::Difference(left, right) => {
+            let mut outside = complement(class_ranges(left));
+            outside.extend(class_ranges(right));
+            complement(outside)
+        }
+    };
+    //the complement of the complement merges them
+    complement(complement(ranges))
+}
+
+fn complement(mut ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    ranges.sort_unstable();
+
+    let mut complement = Vec::new();
+    let mut next_start = 0;
+    for (start, end) in ranges {
+        if start > next_start {
+            complement.push((next_start, start - 1));
+        }
+        next_start = next_start.max(end + 1);
+    }
+    if next_start <= char::MAX as u32 {
+        complement.push((next_start, char::MAX as u32));
+    }
+    complement
+}
+
+#[cfg(test)]
+mod test {
+    use crate::automata::Automaton;
+    use crate::parser::{ParseOptions, RegexEntry};
+
+    use super::utf8_sequences;
+
+    //the automaton of a byte pattern reads each byte as the character of the same value
+    fn matches_bytes(pattern: &str, haystack: &[u8]) -> bool {
+        let options = ParseOptions {
+            bytes: true,
+            ..ParseOptions::default()
+        };
+        let regex = RegexEntry::parse_with_options(pattern, options)
+            .unwrap()
+            .to_utf8_bytes()
+            .unwrap();
+        let haystack = haystack
+            .iter()
+            .map(|byte| *byte as char)
+            .collect::<String>();
+        Automaton::from_regex(regex).is_match(&haystack)
+    }
+
+    #[test]
+    fn test_utf8_sequences() {
+        assert_eq!(utf8_sequences(&[(0x61, 0x7A)]), vec![vec![(0x61, 0x7A)]]);
+        assert_eq!(
+            utf8_sequences(&[(0x7F, 0x80)]),
+            vec![vec![(0x7F, 0x7F)], vec![(0xC2, 0xC2), (0x80, 0x80)]]
+        );
+        //every character's encoding is in exactly one sequence, and no surrogate's is
+        let sequences = utf8_sequences(&[(0, char::MAX as u32)]);
+        for char in ['\0', 'é', '\u{D7FF}', '\u{E000}', '€', '\u{10FFFF}'] {
+            let mut encoded = [0; 4];
+            let bytes = char.encode_utf8(&mut encoded).as_bytes();
+            let containing = sequences
+                .iter()
+                .filter(|sequence| {
+                    sequence.len() == bytes.len()
+                        && sequence
+                            .iter()
+                            .zip(bytes)
+                            .all(|((start, end), byte)| (start..=end).contains(&byte))
+                })
+                .count();
+            assert_eq!(containing, 1, "{char:?}");
+        }
+        assert!(!sequences
+            .iter()
+            .any(|sequence| sequence[0] == (0xED, 0xED) && sequence[1].1 > 0x9F));
+    }
+
+    #[test]
+    fn test_characters_match_their_encoding() {
+        for (pattern, haystack) in [
+            ("é", "é"),
+            (".", "€"),
+            ("[à-ÿ]+", "éü"),
+            (r"\pL+", "straße"),
+            (r"\p{Greek}", "λ"),
+            (r"\PL", "1"),
+            ("(?i)ß", "ẞ"),
+            ("a.c", "a\u{10FFFF}c"),
+        ] {
+            assert!(matches_bytes(pattern, haystack.as_bytes()), "{pattern}");
+        }
+        assert!(!matches_bytes("[à-ÿ]", "ā".as_bytes()));
+        assert!(!matches_bytes(r"\pL", "1".as_bytes()));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        //only a byte matches what is not valid UTF-8, and the replacement character is its own
+        assert!(!matches_bytes("a.b", b"a\xFFb"));
+        assert!(!matches_bytes("a\u{FFFD}b", b"a\xFFb"));
+        assert!(matches_bytes("a\u{FFFD}b", "a\u{FFFD}b".as_bytes()));
+        assert!(matches_bytes("a(?-u:.)b", b"a\xFFb"));
+        assert!(matches_bytes(r"(?-u)a\xFFb", b"a\xFFb"));
+        assert!(matches_bytes(r"(?-u:[\x80-\xFF])+", b"\xC3\xA9\xFF"));
+        assert!(!matches_bytes(r"(?-u:[\x80-\xFF])+", b"\xC3a"));
+        //a single byte of an encoding is not a character
+        assert!(!matches_bytes(".", b"\xC3"));
+        assert!(matches_bytes("(?-u:..)", "é".as_bytes()));
+    }
+
+    #[test]
+    fn test_unsupported() {
+        for pattern in [r"\X", r"\b{start}a", "(?m)^a"] {
+            let options = ParseOptions {
+                bytes: true,
+                line_terminator: crate::line_terminator::LineTerminator::Unicode,
+                ..ParseOptions::default()
+            };
+            let regex = RegexEntry::parse_with_options(pattern, options).unwrap();
+            assert!(regex.to_utf8_bytes().is_err(), "{pattern}");
+        }
+    }
+}